@@ -0,0 +1,136 @@
+//! Times the queries every page reload runs on a db with 50k txs, once with the prepared
+//! statement cache turned off and once with it on.
+//!
+//! Run with `cargo bench -p rex-core`
+
+use rex_core::db::{create_db, set_db_pragmas};
+use rex_core::utility::{get_all_changes, get_all_txs, get_last_balances};
+use rusqlite::Connection;
+use std::fs;
+use std::time::{Duration, Instant};
+
+const DB_PATH: &str = "bench_db_queries.sqlite";
+const TOTAL_TXS: usize = 50_000;
+/// The txs are spread over the months of these many years starting from 2022
+const TOTAL_YEARS: usize = 4;
+/// How many times every month of the db is loaded
+const ROUNDS: usize = 5;
+
+fn create_bench_db() -> Connection {
+    fs::remove_file(DB_PATH).ok();
+
+    let mut conn = Connection::open(DB_PATH).unwrap();
+    let tx_methods = ["Cash".to_string(), "Bank".to_string(), "Card".to_string()];
+    create_db(&tx_methods, &mut conn).unwrap();
+    set_db_pragmas(&conn).unwrap();
+
+    let sp = conn.savepoint().unwrap();
+    {
+        let mut tx_statement = sp
+            .prepare(
+                "INSERT INTO tx_all (date, details, tx_method, amount, tx_type, tags)
+                VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .unwrap();
+        let mut changes_statement = sp
+            .prepare(
+                r#"INSERT INTO changes_all (id_num, date, "Cash", "Bank", "Card")
+                VALUES (?, ?, ?, ?, ?)"#,
+            )
+            .unwrap();
+
+        for index in 0..TOTAL_TXS {
+            let month = index % (TOTAL_YEARS * 12);
+            let date = format!(
+                "{}-{:02}-{:02}",
+                2022 + month / 12,
+                month % 12 + 1,
+                index % 28 + 1
+            );
+            let method = &tx_methods[index % tx_methods.len()];
+            let amount = format!("{}.00", index % 500 + 1);
+            let (tx_type, change) = if index % 4 == 0 {
+                ("Income", format!("↑{amount}"))
+            } else {
+                ("Expense", format!("↓{amount}"))
+            };
+
+            let id_num = tx_statement
+                .insert((&date, "Bench tx", method, &amount, tx_type, "Bench"))
+                .unwrap();
+
+            let mut changes = vec!["0.00".to_string(); tx_methods.len()];
+            changes[index % tx_methods.len()] = change;
+            changes_statement
+                .execute((id_num, &date, &changes[0], &changes[1], &changes[2]))
+                .unwrap();
+        }
+    }
+    sp.commit().unwrap();
+
+    conn
+}
+
+/// A query that loads the data of a month. Takes the month and the year index
+type MonthQuery = fn(usize, usize, &Connection);
+
+/// Runs the query for every month of the db and returns how long it took
+fn time_every_month(conn: &Connection, query: MonthQuery) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..ROUNDS {
+        for year in 0..TOTAL_YEARS {
+            for month in 0..12 {
+                query(month, year, conn);
+            }
+        }
+    }
+
+    start.elapsed()
+}
+
+/// The queries a page reload runs with the name they are reported with
+fn get_queries() -> [(&'static str, MonthQuery); 3] {
+    [
+        ("get_all_txs", |month, year, conn| {
+            get_all_txs(conn, month, year);
+        }),
+        ("get_all_changes", |month, year, conn| {
+            get_all_changes(month, year, conn);
+        }),
+        ("get_last_balances", |_, _, conn| {
+            get_last_balances(conn);
+        }),
+    ]
+}
+
+fn main() {
+    let conn = create_bench_db();
+    let loads = (ROUNDS * TOTAL_YEARS * 12) as u32;
+
+    println!("{TOTAL_TXS} txs, {loads} calls per query\n");
+
+    for (name, query) in get_queries() {
+        // warm up the page cache of SQLite so both runs read the same way
+        time_every_month(&conn, query);
+
+        conn.set_prepared_statement_cache_capacity(0);
+        let uncached = time_every_month(&conn, query);
+
+        conn.set_prepared_statement_cache_capacity(16);
+        let cached = time_every_month(&conn, query);
+
+        println!("{name}");
+        println!("  without statement cache: {:?} per call", uncached / loads);
+        println!("  with statement cache:    {:?} per call", cached / loads);
+        println!(
+            "  speedup: {:.2}x",
+            uncached.as_secs_f64() / cached.as_secs_f64()
+        );
+    }
+
+    conn.close().unwrap();
+    fs::remove_file(DB_PATH).unwrap();
+    fs::remove_file(format!("{DB_PATH}-wal")).ok();
+    fs::remove_file(format!("{DB_PATH}-shm")).ok();
+}
@@ -1,7 +1,7 @@
 use rusqlite::Connection;
 use std::collections::HashMap;
 
-use crate::page_handler::ActivityType;
+use crate::state::ActivityType;
 use crate::utility::get_all_activities;
 
 pub struct ActivityDetails {
@@ -52,6 +52,7 @@ pub struct ActivityTx {
 }
 
 impl ActivityTx {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         date: String,
         details: String,
@@ -0,0 +1,3 @@
+mod activity_data;
+
+pub use activity_data::{ActivityData, ActivityDetails, ActivityTx};
@@ -0,0 +1,167 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use crate::outputs::ApiError;
+
+/// Requests with a bigger body than this are refused before the body is read
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// A parsed HTTP request sent to the API
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiRequest {
+    pub method: String,
+    /// The path without the query like `/txs`
+    pub path: String,
+    /// The decoded query parameters
+    pub query: HashMap<String, String>,
+    /// The token of the `Authorization: Bearer <token>` header
+    pub token: Option<String>,
+    pub body: String,
+}
+
+impl ApiRequest {
+    /// Reads a single HTTP/1.1 request. Only the request line, the `Authorization` and the
+    /// `Content-Length` headers and the body are kept
+    pub fn read(reader: &mut impl BufRead) -> Result<Self, ApiError> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target), Some(_)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ApiError::InvalidRequest);
+        };
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let mut request = ApiRequest {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            query: parse_query(query),
+            ..ApiRequest::default()
+        };
+
+        let mut content_length = 0;
+
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(ApiError::InvalidRequest);
+            }
+
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+
+            let Some((name, value)) = header.split_once(':') else {
+                return Err(ApiError::InvalidRequest);
+            };
+            let value = value.trim();
+
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().map_err(|_| ApiError::InvalidRequest)?;
+            } else if name.eq_ignore_ascii_case("authorization") {
+                request.token = value
+                    .strip_prefix("Bearer ")
+                    .map(|token| token.trim().to_string());
+            }
+        }
+
+        if content_length > MAX_BODY_SIZE {
+            return Err(ApiError::BodyTooLarge);
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+        request.body = String::from_utf8(body).map_err(|_| ApiError::InvalidRequest)?;
+
+        Ok(request)
+    }
+}
+
+/// The status code and the json body the API answers with
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl ApiResponse {
+    #[must_use]
+    pub fn new(status: u16, body: Value) -> Self {
+        ApiResponse { status, body }
+    }
+
+    /// Answers the error with its status code and `{"error": "<message>"}`
+    #[must_use]
+    pub fn from_error(error: &ApiError) -> Self {
+        ApiResponse::new(error.status(), json!({ "error": error.to_string() }))
+    }
+
+    /// Writes the response as HTTP/1.1. The connection is closed after every response
+    pub fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        let body = self.body.to_string();
+        let reason = match self.status {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        };
+
+        write!(
+            writer,
+            "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.status,
+            body.len()
+        )?;
+        writer.flush()
+    }
+}
+
+/// Splits the query of the request target into its decoded keys and values
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_query_part(key), decode_query_part(value))
+        })
+        .collect()
+}
+
+/// Decodes the `%XX` escapes and the `+` spaces of a query key or value. Invalid escapes are
+/// kept as they are
+fn decode_query_part(part: &str) -> String {
+    let bytes = part.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let escaped = part
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+                if let Some(byte) = escaped {
+                    decoded.push(byte);
+                    index += 2;
+                } else {
+                    decoded.push(b'%');
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
@@ -0,0 +1,7 @@
+mod http;
+mod routes;
+mod server;
+
+pub use http::*;
+pub use routes::*;
+pub use server::*;
@@ -0,0 +1,283 @@
+use chrono::{Datelike, Local};
+use rusqlite::Connection;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+use crate::api::{ApiRequest, ApiResponse};
+use crate::db::YEARS;
+use crate::events::{EventHook, RexEvent};
+use crate::outputs::{ApiError, TxType, VerifyingOutput};
+use crate::state::{DateType, IndexedData, SortingDirection, SortingType};
+use crate::summary::SummaryData;
+use crate::tx_handler::TxData;
+use crate::utility::{get_all_tx_methods, get_all_txs, get_last_balances, Config};
+
+/// The body of `POST /txs`. Only `details`, `tx_method` and `amount` are needed, the date falls
+/// back to today and the tx type to Expense. The amount can be a number or a text like `25 EUR`
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct NewTx {
+    date: String,
+    details: String,
+    tx_method: String,
+    /// The tx method that receives the amount of a transfer
+    to_method: String,
+    amount: Value,
+    tx_type: String,
+    tags: String,
+}
+
+/// Answers a request sent to the API. Every endpoint needs the token:
+///
+/// - `GET /txs?month=5&year=2024` lists the txs of a month. The current month is the default and
+///   only giving the year lists the whole year
+/// - `POST /txs` adds a tx after verifying it the same way the Add Tx page does. The tx is sent
+///   to the event hook
+/// - `GET /balances` lists the current balance of every tx method
+/// - `GET /summary?month=5&year=2024` returns the income and expense of the month and its tags.
+///   Only giving the year returns the whole year
+pub fn handle_api_request(
+    request: &ApiRequest,
+    token: &str,
+    config: &Config,
+    events: &EventHook,
+    conn: &mut Connection,
+) -> ApiResponse {
+    let status = if request
+        .token
+        .as_deref()
+        .is_some_and(|sent| is_same_token(sent, token))
+    {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/txs") => list_txs(request, conn),
+            ("POST", "/txs") => add_api_tx(request, config, events, conn),
+            ("GET", "/balances") => Ok(get_balances(conn)),
+            ("GET", "/summary") => get_summary(request, conn),
+            (method, "/txs" | "/balances" | "/summary") => {
+                Err(ApiError::MethodNotAllowed(method.to_string()))
+            }
+            (_, path) => Err(ApiError::NotFound(path.to_string())),
+        }
+    } else {
+        Err(ApiError::Unauthorized)
+    };
+
+    match status {
+        Ok(response) => response,
+        Err(e) => ApiResponse::from_error(&e),
+    }
+}
+
+/// Compares the tokens without stopping at the first different byte so the time taken does not
+/// tell how much of the token was right
+fn is_same_token(sent: &str, token: &str) -> bool {
+    if sent.len() != token.len() || token.is_empty() {
+        return false;
+    }
+
+    sent.bytes()
+        .zip(token.bytes())
+        .fold(0, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+/// Returns the month and the year index of the period in the query. The month is None if only
+/// the year was given
+fn get_period(request: &ApiRequest) -> Result<(Option<usize>, usize), ApiError> {
+    let today = Local::now().date_naive();
+
+    let month = request.query.get("month");
+    let year = request.query.get("year");
+
+    let year_index = match year {
+        Some(year) => year
+            .parse::<usize>()
+            .ok()
+            .and_then(|year| year.checked_sub(2022))
+            .filter(|index| *index < YEARS.len())
+            .ok_or_else(|| ApiError::InvalidPeriod(year.to_string()))?,
+        None => today.year() as usize - 2022,
+    };
+
+    let month_index = match (month, year) {
+        (Some(month), _) => Some(
+            month
+                .parse::<usize>()
+                .ok()
+                .filter(|month| (1..=12).contains(month))
+                .ok_or_else(|| ApiError::InvalidPeriod(month.to_string()))?
+                - 1,
+        ),
+        (None, Some(_)) => None,
+        (None, None) => Some(today.month0() as usize),
+    };
+
+    Ok((month_index, year_index))
+}
+
+/// `GET /txs`
+fn list_txs(request: &ApiRequest, conn: &Connection) -> Result<ApiResponse, ApiError> {
+    let (month, year) = get_period(request)?;
+
+    let months = match month {
+        Some(month) => month..month + 1,
+        None => 0..12,
+    };
+
+    let txs = months
+        .flat_map(|month| {
+            let (txs, _, id_nums) = get_all_txs(conn, month, year);
+            txs.into_iter().zip(id_nums)
+        })
+        .map(|(tx, id_num)| {
+            json!({
+                "id": id_num.parse::<i32>().unwrap_or_default(),
+                "date": tx[0],
+                "details": tx[1],
+                "tx_method": tx[2],
+                "amount": tx[3].parse::<f64>().unwrap_or_default(),
+                "tx_type": tx[4],
+                "tags": tx[5].split(", ").collect::<Vec<&str>>(),
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    Ok(ApiResponse::new(200, json!({ "txs": txs })))
+}
+
+/// `POST /txs`
+fn add_api_tx(
+    request: &ApiRequest,
+    config: &Config,
+    events: &EventHook,
+    conn: &mut Connection,
+) -> Result<ApiResponse, ApiError> {
+    let new_tx: NewTx =
+        serde_json::from_str(&request.body).map_err(|e| ApiError::InvalidBody(e.to_string()))?;
+
+    let amount = match &new_tx.amount {
+        Value::String(amount) => amount.clone(),
+        Value::Number(amount) => amount.to_string(),
+        Value::Null => String::new(),
+        other => return Err(ApiError::InvalidBody(format!("'{other}' is not an amount"))),
+    };
+
+    let date = if new_tx.date.is_empty() {
+        Local::now().date_naive().to_string()
+    } else {
+        new_tx.date
+    };
+
+    let tx_type = if new_tx.tx_type.is_empty() {
+        String::from("Expense")
+    } else {
+        new_tx.tx_type
+    };
+
+    let mut tx_data = TxData::filled(
+        &date,
+        &new_tx.details,
+        &new_tx.tx_method,
+        &new_tx.to_method,
+        &amount,
+        &tx_type,
+        &new_tx.tags,
+    );
+
+    // an amount like `25 EUR` is converted to the base currency before it is verified
+    tx_data
+        .convert_foreign_amount(config, conn)
+        .map_err(|e| ApiError::NotAccepted(e.to_string()))?;
+
+    let mut statuses = vec![
+        tx_data.check_date(&DateType::Exact),
+        tx_data.check_tx_type(),
+    ];
+    statuses.push(tx_data.check_from_method(conn));
+    if tx_data.get_tx_type() == TxType::Transfer {
+        statuses.push(tx_data.check_to_method(conn));
+    }
+    statuses.push(tx_data.check_amount(false, conn));
+    tx_data.check_tags();
+    statuses.push(tx_data.check_tag_constraints(config));
+
+    for status in statuses {
+        if let VerifyingOutput::NotAccepted(err) = status {
+            return Err(ApiError::NotAccepted(err.to_string()));
+        }
+    }
+
+    let id_nums = tx_data.add_tx(conn).map_err(ApiError::NotAccepted)?;
+    let id_num = id_nums.first().copied().unwrap_or_default();
+
+    // a failed event is logged by the hook and does not fail the request
+    events.emit(&RexEvent::tx_added(id_num, conn)).ok();
+
+    Ok(ApiResponse::new(201, json!({ "id": id_num })))
+}
+
+/// `GET /balances`
+fn get_balances(conn: &Connection) -> ApiResponse {
+    let balances = get_all_tx_methods(conn)
+        .into_iter()
+        .zip(get_last_balances(conn))
+        .map(|(method, balance)| {
+            json!({
+                "tx_method": method,
+                "balance": balance.parse::<f64>().unwrap_or_default(),
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    ApiResponse::new(200, json!({ "balances": balances }))
+}
+
+/// `GET /summary`
+fn get_summary(request: &ApiRequest, conn: &Connection) -> Result<ApiResponse, ApiError> {
+    let (month, year) = get_period(request)?;
+
+    // 0 is the monthly mode and 1 the yearly mode of the Summary page
+    let mut mode = IndexedData::new_modes();
+    mode.index = usize::from(month.is_none());
+    let month = month.unwrap_or_default();
+
+    let summary_data = SummaryData::new(conn);
+    let (totals, ..) = summary_data.get_tx_data(&mode, month, year, conn);
+
+    let tags = summary_data
+        .get_table_data(
+            &mode,
+            month,
+            year,
+            &SortingType::ByTags,
+            &SortingDirection::Ascending,
+            &HashSet::new(),
+        )
+        .iter()
+        .map(|row| {
+            json!({
+                "tag": row[0],
+                "income": row[1].parse::<f64>().unwrap_or_default(),
+                "expense": row[2].parse::<f64>().unwrap_or_default(),
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    let get_total = |index: usize| {
+        totals
+            .get(index)
+            .and_then(|row| row[1].parse::<f64>().ok())
+            .unwrap_or_default()
+    };
+
+    Ok(ApiResponse::new(
+        200,
+        json!({
+            "income": get_total(0),
+            "expense": get_total(1),
+            "net": get_total(2),
+            "tags": tags,
+        }),
+    ))
+}
@@ -0,0 +1,104 @@
+use rusqlite::Connection;
+use std::fs;
+use std::io::{BufReader, Result};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::api::{handle_api_request, ApiRequest, ApiResponse};
+use crate::events::EventHook;
+use crate::utility::{restrict_permissions, write_private_file, Config};
+
+/// How long a client gets to send its request before the connection is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns the location of `api_token.txt` which is kept beside the original db location
+pub fn get_api_token_path(original_db_path: &Path) -> PathBuf {
+    let mut token_path = original_db_path.to_owned();
+    token_path.pop();
+    token_path.push("api_token.txt");
+    token_path
+}
+
+/// Loads the token the API requests must be sent with. A new one is created and saved the first
+/// time. Deleting `api_token.txt` creates a new token on the next start. Only the user can read
+/// the file
+pub fn get_api_token(original_db_path: &Path) -> Result<String> {
+    let token_path = get_api_token_path(original_db_path);
+
+    if let Ok(token) = fs::read_to_string(&token_path) {
+        let token = token.trim();
+        if !token.is_empty() {
+            // files saved before the permissions were set may still be readable by others
+            restrict_permissions(&token_path)?;
+            return Ok(token.to_string());
+        }
+    }
+
+    let token = generate_api_token()?;
+    write_private_file(&token_path, &token)?;
+    Ok(token)
+}
+
+/// Creates a random 32 characters long hex token from the random source of the OS
+fn generate_api_token() -> Result<String> {
+    let mut bytes = [0; 16];
+    getrandom::getrandom(&mut bytes)?;
+
+    Ok(format!("{:032x}", u128::from_be_bytes(bytes)))
+}
+
+/// Answers the API requests sent to the listener one at a time until the listener fails. A
+/// request that could not be read is answered with its error and does not stop the server
+pub fn serve_api(
+    listener: &TcpListener,
+    token: &str,
+    config: &Config,
+    events: &EventHook,
+    conn: &mut Connection,
+) -> Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+
+        if let Err(e) = answer_connection(&stream, token, config, events, conn) {
+            warn!("Failed to answer a request. Error: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Reads the request of the connection and writes the response to it
+fn answer_connection(
+    stream: &TcpStream,
+    token: &str,
+    config: &Config,
+    events: &EventHook,
+    conn: &mut Connection,
+) -> Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream);
+    let start = Instant::now();
+
+    let response = match ApiRequest::read(&mut reader) {
+        Ok(request) => {
+            let response = handle_api_request(&request, token, config, events, conn);
+            info!(
+                method = request.method,
+                path = request.path,
+                status = response.status,
+                elapsed_ms = start.elapsed().as_millis(),
+                "Answered an API request"
+            );
+            response
+        }
+        Err(e) => {
+            warn!("Failed to read an API request. Error: {e}");
+            ApiResponse::from_error(&e)
+        }
+    };
+
+    let mut writer = stream;
+    response.write(&mut writer)
+}
@@ -0,0 +1,557 @@
+use chrono::{naive::NaiveDate, Datelike, Duration};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::db::{MONTHS, YEARS};
+use crate::state::{ChartView, IndexedData};
+use crate::utility::{get_all_tx_methods, get_all_txs};
+
+/// An RGB color given to a chart line or a tx method. The UI turns it into its own color type
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// Colors that are given to the chart lines and the tx methods without a picked color.
+/// The last color gets used first
+pub const LINE_COLORS: [Rgb; 6] = [
+    Rgb(139, 233, 253), // Cyan
+    Rgb(80, 250, 123),  // Green
+    Rgb(255, 184, 108), // Orange
+    Rgb(255, 121, 198), // Pink
+    Rgb(189, 147, 249), // Purple
+    Rgb(255, 85, 85),   // Red
+];
+
+/// Color used once every color of `LINE_COLORS` is taken
+const EXTRA_LINE_COLOR: Rgb = Rgb(241, 250, 140);
+
+/// A single line of the chart containing the y value of every day of the selected period
+pub struct ChartLine {
+    pub name: String,
+    pub color: Rgb,
+    /// Whether the line is currently shown on the chart
+    pub activated: bool,
+    /// `(day number from the first date, value)` for every day of the period
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Every line of the chart within the selected period alongside the date the lines start from
+pub struct ChartLines {
+    pub lines: Vec<ChartLine>,
+    pub start_date: Option<NaiveDate>,
+    /// The x value of the last point of every line
+    pub last_point: usize,
+    /// Days between two points of a line. 1 on daily points, 7 on weekly points
+    pub step_days: i64,
+}
+
+impl ChartLines {
+    /// Merges the daily points into ISO weeks. Each week keeps the value of its last day and
+    /// the lines start from the Monday of the first week
+    pub fn into_weekly(mut self) -> Self {
+        let Some(start_date) = self.start_date else {
+            return self;
+        };
+
+        let days_from_monday = i64::from(start_date.weekday().num_days_from_monday());
+
+        for line in &mut self.lines {
+            let mut weekly_points: Vec<(f64, f64)> = Vec::new();
+
+            for (day, value) in &line.points {
+                let week = ((*day as i64 + days_from_monday) / 7) as f64;
+
+                if let Some(last_point) = weekly_points.last_mut() {
+                    if last_point.0 == week {
+                        last_point.1 = *value;
+                        continue;
+                    }
+                }
+                weekly_points.push((week, *value));
+            }
+            line.points = weekly_points;
+        }
+
+        self.start_date = Some(start_date - Duration::days(days_from_monday));
+        self.last_point = (self.last_point + days_from_monday as usize) / 7;
+        self.step_days = 7;
+        self
+    }
+}
+
+/// Stores every transaction in the database and along with
+/// all balance amount after each transaction was committed
+/// Gets reloaded only after a new tx is added/removed/edited
+pub struct ChartData {
+    all_txs: HashMap<i32, Vec<Vec<String>>>,
+    all_balance: HashMap<i32, Vec<Vec<String>>>,
+    /// The parsed date of every tx in `all_txs`, in the same order
+    all_dates: HashMap<i32, Vec<NaiveDate>>,
+    tx_methods: Vec<String>,
+    /// The last created chart lines with the state they were created for
+    cached_lines: Option<(ChartCacheKey, ChartLines)>,
+}
+
+/// Every chart state the chart lines depend on. The tx method colors are not part of it
+/// as they stay the same while the app is running
+#[derive(PartialEq)]
+struct ChartCacheKey {
+    chart_view: ChartView,
+    mode: usize,
+    month: usize,
+    year: usize,
+    weekly: bool,
+    activated_methods: Vec<(String, bool)>,
+    activated_tags: Vec<(String, bool)>,
+}
+
+/// Turns the activation status into a sorted list so it can be compared
+fn sorted_activation<S: ::std::hash::BuildHasher>(
+    activation: &HashMap<String, bool, S>,
+) -> Vec<(String, bool)> {
+    let mut to_return: Vec<(String, bool)> = activation
+        .iter()
+        .map(|(name, activated)| (name.to_string(), *activated))
+        .collect();
+    to_return.sort();
+    to_return
+}
+
+impl ChartData {
+    /// Fetches and stores all tx and balance information for all months and years
+    pub fn new(conn: &Connection) -> Self {
+        let mut all_txs = HashMap::new();
+        let mut all_balance = HashMap::new();
+        let mut all_dates = HashMap::new();
+        for x in 0..YEARS.len() {
+            for i in 0..MONTHS.len() {
+                let target_id = i as i32 + (x as i32 * 12);
+                let (t, b, _) = get_all_txs(conn, i, x);
+                let dates = t
+                    .iter()
+                    .map(|tx| NaiveDate::parse_from_str(&tx[0], "%Y-%m-%d").unwrap())
+                    .collect::<Vec<NaiveDate>>();
+                all_dates.insert(target_id, dates);
+                all_txs.insert(target_id, t);
+                all_balance.insert(target_id, b);
+            }
+        }
+
+        ChartData {
+            all_txs,
+            all_balance,
+            all_dates,
+            tx_methods: get_all_tx_methods(conn),
+            cached_lines: None,
+        }
+    }
+
+    /// Returns all dates of the transactions from the given month and year
+    pub fn get_all_dates(&self, mode: &IndexedData, month: usize, year: usize) -> Vec<NaiveDate> {
+        let mut to_return = vec![];
+
+        match mode.index {
+            // 0 = monthly mode. Select the data only of the given month year
+            0 => {
+                let target_id = month as i32 + (year as i32 * 12);
+                to_return.extend(&self.all_dates[&target_id]);
+            }
+            // 1 = yearly mode. Select the data of all months of the given year
+            1 => {
+                for i in 0..MONTHS.len() {
+                    let target_id = i as i32 + (year as i32 * 12);
+                    to_return.extend(&self.all_dates[&target_id]);
+                }
+            }
+            // 2 = all time mode. Select every single data
+            2 => {
+                for x in 0..YEARS.len() {
+                    for i in 0..MONTHS.len() {
+                        let target_id = i as i32 + (x as i32 * 12);
+                        to_return.extend(&self.all_dates[&target_id]);
+                    }
+                }
+            }
+            _ => {}
+        }
+        to_return
+    }
+
+    /// Returns the running total of income and expense within the given period. Each entry
+    /// contains a tx date with the cumulative income and expense up to and including that date.
+    /// Transfers and txs of methods deactivated on the chart are not counted.
+    pub fn get_cumulative_data<S: ::std::hash::BuildHasher>(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+        activated_methods: &HashMap<String, bool, S>,
+    ) -> Vec<(NaiveDate, f64, f64)> {
+        let (all_txs, _) = self.get_data(mode, month, year);
+
+        let mut to_return: Vec<(NaiveDate, f64, f64)> = Vec::new();
+        let mut total_income = 0.0;
+        let mut total_expense = 0.0;
+
+        let all_dates = self.get_all_dates(mode, month, year);
+
+        for (tx, date) in all_txs.into_iter().zip(all_dates) {
+            let tx_method = &tx[2];
+            let tx_type = &tx[4];
+
+            if activated_methods.get(tx_method) == Some(&true) {
+                let amount: f64 = tx[3].parse().unwrap();
+                match tx_type.as_str() {
+                    "Income" => total_income += amount,
+                    "Expense" => total_expense += amount,
+                    _ => {}
+                }
+            }
+
+            // Same date txs are merged into a single entry
+            if let Some(last_entry) = to_return.last_mut() {
+                if last_entry.0 == date {
+                    *last_entry = (date, total_income, total_expense);
+                    continue;
+                }
+            }
+            to_return.push((date, total_income, total_expense));
+        }
+
+        to_return
+    }
+
+    /// Returns the running total of expense of each given tag within the given period. Each entry
+    /// contains a tx date with the cumulative expense of every tag, in the same order as the given tags.
+    /// Txs with multiple tags are counted for each of their tags.
+    pub fn get_tag_data(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+        tags: &[String],
+    ) -> Vec<(NaiveDate, Vec<f64>)> {
+        let (all_txs, _) = self.get_data(mode, month, year);
+
+        let mut to_return: Vec<(NaiveDate, Vec<f64>)> = Vec::new();
+        let mut tag_totals = vec![0.0; tags.len()];
+
+        let all_dates = self.get_all_dates(mode, month, year);
+
+        for (tx, date) in all_txs.into_iter().zip(all_dates) {
+            if tx[4] == "Expense" {
+                let amount: f64 = tx[3].parse().unwrap();
+
+                for tx_tag in tx[5].split(',').map(str::trim) {
+                    if let Some(tag_index) = tags.iter().position(|tag| tag == tx_tag) {
+                        tag_totals[tag_index] += amount;
+                    }
+                }
+            }
+
+            // Same date txs are merged into a single entry
+            if let Some(last_entry) = to_return.last_mut() {
+                if last_entry.0 == date {
+                    last_entry.1 = tag_totals.clone();
+                    continue;
+                }
+            }
+            to_return.push((date, tag_totals.clone()));
+        }
+
+        to_return
+    }
+
+    /// Creates every line of the chart based on the chart view within the given period.
+    /// Days without any tx keep the value of the earlier day
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_chart_lines<S: ::std::hash::BuildHasher>(
+        &self,
+        chart_view: &ChartView,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+        chart_tags: &IndexedData,
+        activated_methods: &HashMap<String, bool, S>,
+        activated_tags: &HashMap<String, bool, S>,
+        method_colors: &HashMap<String, Rgb>,
+    ) -> ChartLines {
+        // the name of each line, whether it's activated and the line values on each tx date
+        let (line_names, lines_activated, line_data): (Vec<String>, Vec<bool>, _) = match chart_view
+        {
+            ChartView::Balance => {
+                let (_, all_balance) = self.get_data(mode, month, year);
+                let all_dates = self.get_all_dates(mode, month, year);
+
+                let mut line_data: Vec<(NaiveDate, Vec<f64>)> = Vec::new();
+
+                for (date, balance) in all_dates.into_iter().zip(all_balance) {
+                    let mut balances: Vec<f64> =
+                        balance.iter().map(|b| b.parse().unwrap()).collect();
+
+                    // The Total line is the sum of all activated tx methods
+                    let total = self
+                        .tx_methods
+                        .iter()
+                        .zip(&balances)
+                        .filter(|(method, _)| activated_methods.get(*method) == Some(&true))
+                        .map(|(_, balance)| balance)
+                        .sum();
+                    balances.push(total);
+
+                    // Same date txs are merged into a single entry
+                    if let Some(last_entry) = line_data.last_mut() {
+                        if last_entry.0 == date {
+                            last_entry.1 = balances;
+                            continue;
+                        }
+                    }
+                    line_data.push((date, balances));
+                }
+
+                let mut line_names = self.tx_methods.clone();
+                line_names.push("Total".to_string());
+
+                let lines_activated = line_names
+                    .iter()
+                    .map(|method| activated_methods.get(method) == Some(&true))
+                    .collect();
+
+                (line_names, lines_activated, line_data)
+            }
+            ChartView::Cumulative => (
+                vec![
+                    "Cumulative Income".to_string(),
+                    "Cumulative Expense".to_string(),
+                ],
+                vec![true, true],
+                self.get_cumulative_data(mode, month, year, activated_methods)
+                    .into_iter()
+                    .map(|(date, income, expense)| (date, vec![income, expense]))
+                    .collect(),
+            ),
+            ChartView::Tags => (
+                chart_tags.titles.clone(),
+                chart_tags
+                    .titles
+                    .iter()
+                    .map(|tag| activated_tags[tag])
+                    .collect(),
+                self.get_tag_data(mode, month, year, &chart_tags.titles),
+            ),
+        };
+
+        let colors = match chart_view {
+            ChartView::Cumulative => vec![Rgb(80, 250, 123), Rgb(255, 85, 85)],
+            ChartView::Balance => get_line_colors(&line_names, method_colors),
+            ChartView::Tags => get_line_colors(&line_names, &HashMap::new()),
+        };
+
+        let mut lines: Vec<ChartLine> = line_names
+            .into_iter()
+            .zip(lines_activated)
+            .zip(colors)
+            .map(|((name, activated), color)| ChartLine {
+                name,
+                color,
+                activated,
+                points: Vec::new(),
+            })
+            .collect();
+
+        let Some((start_date, _)) = line_data.first() else {
+            // there is no data to load, all lines start and end at 0
+            for line in &mut lines {
+                line.points.push((0.0, 0.0));
+            }
+            return ChartLines {
+                lines,
+                start_date: None,
+                last_point: 0,
+                step_days: 1,
+            };
+        };
+
+        let start_date = *start_date;
+        let final_date = line_data[line_data.len() - 1].0;
+
+        let mut checking_date = start_date;
+        let mut last_values = vec![0.0; lines.len()];
+        let mut current_axis = 0.0;
+        let mut data_num = 0;
+
+        while checking_date <= final_date {
+            if line_data[data_num].0 == checking_date {
+                last_values = line_data[data_num].1.clone();
+                data_num += 1;
+            }
+
+            for (line, value) in lines.iter_mut().zip(&last_values) {
+                line.points.push((current_axis, *value));
+            }
+
+            current_axis += 1.0;
+            checking_date += Duration::days(1);
+        }
+
+        ChartLines {
+            lines,
+            start_date: Some(start_date),
+            last_point: final_date.signed_duration_since(start_date).num_days() as usize,
+            step_days: 1,
+        }
+    }
+
+    /// Returns the chart lines of the given chart state. The lines are only created again
+    /// when the state changes, otherwise the earlier created lines are reused
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_cached_chart_lines<S: ::std::hash::BuildHasher>(
+        &mut self,
+        chart_view: &ChartView,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+        weekly: bool,
+        chart_tags: &IndexedData,
+        activated_methods: &HashMap<String, bool, S>,
+        activated_tags: &HashMap<String, bool, S>,
+        method_colors: &HashMap<String, Rgb>,
+    ) -> &ChartLines {
+        let key = ChartCacheKey {
+            chart_view: *chart_view,
+            mode: mode.index,
+            month,
+            year,
+            weekly,
+            activated_methods: sorted_activation(activated_methods),
+            activated_tags: sorted_activation(activated_tags),
+        };
+
+        let outdated = self
+            .cached_lines
+            .as_ref()
+            .map_or(true, |(cached_key, _)| *cached_key != key);
+
+        if outdated {
+            let chart_lines = self.get_chart_lines(
+                chart_view,
+                mode,
+                month,
+                year,
+                chart_tags,
+                activated_methods,
+                activated_tags,
+                method_colors,
+            );
+
+            let chart_lines = if weekly {
+                chart_lines.into_weekly()
+            } else {
+                chart_lines
+            };
+
+            self.cached_lines = Some((key, chart_lines));
+        }
+
+        &self.cached_lines.as_ref().unwrap().1
+    }
+
+    pub fn get_data(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+    ) -> (Vec<&Vec<String>>, Vec<&Vec<String>>) {
+        let mut to_return_tx = vec![];
+        let mut to_return_balance = vec![];
+
+        match mode.index {
+            // 0 = monthly mode. Select the data only of the given month year
+            0 => {
+                let target_id = month as i32 + (year as i32 * 12);
+                for i in &self.all_txs[&target_id] {
+                    to_return_tx.push(i);
+                }
+                for i in &self.all_balance[&target_id] {
+                    to_return_balance.push(i);
+                }
+            }
+            // 1 = yearly mode. Select the data of all months of the given year
+            1 => {
+                for i in 0..MONTHS.len() {
+                    let target_id = i as i32 + (year as i32 * 12);
+                    for i in &self.all_txs[&target_id] {
+                        to_return_tx.push(i);
+                    }
+
+                    for i in &self.all_balance[&target_id] {
+                        to_return_balance.push(i);
+                    }
+                }
+            }
+            // 2 = all time mode. Select every single data
+            2 => {
+                for x in 0..YEARS.len() {
+                    for i in 0..MONTHS.len() {
+                        let target_id = i as i32 + (x as i32 * 12);
+                        for i in &self.all_txs[&target_id] {
+                            to_return_tx.push(i);
+                        }
+
+                        for i in &self.all_balance[&target_id] {
+                            to_return_balance.push(i);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        (to_return_tx, to_return_balance)
+    }
+}
+
+/// Gives a color to each line. Lines with a pinned color use it while the rest get the default
+/// colors in order, so a line keeps its color when other lines are activated or deactivated
+fn get_line_colors(line_names: &[String], pinned_colors: &HashMap<String, Rgb>) -> Vec<Rgb> {
+    let mut color_list = LINE_COLORS.to_vec();
+
+    line_names
+        .iter()
+        .map(|name| {
+            if let Some(color) = pinned_colors.get(name) {
+                *color
+            } else {
+                color_list.pop().unwrap_or(EXTRA_LINE_COLOR)
+            }
+        })
+        .collect()
+}
+
+/// Returns the lowest and the highest point of a line. If the value repeats, the earliest point is taken
+pub fn get_line_extremes(points: &[(f64, f64)]) -> Option<((f64, f64), (f64, f64))> {
+    let first_point = *points.first()?;
+
+    let mut lowest_point = first_point;
+    let mut highest_point = first_point;
+
+    for point in points {
+        if point.1 < lowest_point.1 {
+            lowest_point = *point;
+        }
+        if point.1 > highest_point.1 {
+            highest_point = *point;
+        }
+    }
+
+    Some((lowest_point, highest_point))
+}
+
+/// Turns a chart value into its logarithmic scale value. Uses `log10(1 + |value|)` with the
+/// original sign so 0 and negative balances can still be plotted
+pub fn to_log_scale(value: f64) -> f64 {
+    value.signum() * value.abs().ln_1p() / std::f64::consts::LN_10
+}
+
+/// Reverses a value that was turned into the logarithmic scale by `to_log_scale`
+pub fn from_log_scale(value: f64) -> f64 {
+    value.signum() * (value.abs() * std::f64::consts::LN_10).exp_m1()
+}
@@ -0,0 +1,6 @@
+mod chart_data;
+
+pub use chart_data::{
+    from_log_scale, get_line_extremes, to_log_scale, ChartData, ChartLine, ChartLines, Rgb,
+    LINE_COLORS,
+};
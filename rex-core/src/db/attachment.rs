@@ -0,0 +1,39 @@
+use rusqlite::{Connection, Result};
+
+/// Returns the path of every file attached to the given tx in the order they were attached
+pub fn get_tx_attachments(id_num: i32, conn: &Connection) -> Vec<String> {
+    let mut query = conn
+        .prepare("SELECT path FROM tx_attachments WHERE id_num = ? ORDER BY attachment_id")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([id_num], |row| row.get(0))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Attaches a file path to the tx. A path that is already attached to it is not added again
+pub fn add_tx_attachment(id_num: i32, path: &str, conn: &Connection) -> Result<()> {
+    if get_tx_attachments(id_num, conn)
+        .iter()
+        .any(|attached| attached == path)
+    {
+        return Ok(());
+    }
+
+    conn.execute(
+        "INSERT INTO tx_attachments (id_num, path) VALUES (?, ?)",
+        (id_num, path),
+    )?;
+    Ok(())
+}
+
+/// Replaces every attachment of the tx with the given paths. An empty list removes all of them
+pub fn set_tx_attachments(id_num: i32, paths: &[String], conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM tx_attachments WHERE id_num = ?", [id_num])?;
+
+    for path in paths {
+        add_tx_attachment(id_num, path, conn)?;
+    }
+    Ok(())
+}
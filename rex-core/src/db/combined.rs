@@ -0,0 +1,96 @@
+use rusqlite::{Connection, Result};
+use std::path::PathBuf;
+
+use crate::db::create_db;
+use crate::tx_handler::insert_tx;
+
+/// The part of a tx that is copied to the combined db
+struct CombinedTx {
+    date: String,
+    details: String,
+    tx_method: String,
+    amount: String,
+    tx_type: String,
+    tags: String,
+    rate: Option<f64>,
+}
+
+/// Returns the tx methods of the attached db in their order
+fn get_attached_tx_methods(schema: &str, conn: &Connection) -> Result<Vec<String>> {
+    let mut statement =
+        conn.prepare("SELECT name FROM pragma_table_info('balance_all', ?1) ORDER BY cid")?;
+    let mut tx_methods = statement
+        .query_map([schema], |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+    // the first column is id_num
+    tx_methods.remove(0);
+    Ok(tx_methods)
+}
+
+fn get_attached_txs(schema: &str, conn: &Connection) -> Result<Vec<CombinedTx>> {
+    let mut statement = conn.prepare(&format!(
+        "SELECT t.date, t.details, t.tx_method, t.amount, t.tx_type, t.tags, r.rate
+        FROM {schema}.tx_all t LEFT JOIN {schema}.tx_exchange_rates r ON r.id_num = t.id_num
+        ORDER BY t.id_num"
+    ))?;
+
+    let rows = statement.query_map([], |row| {
+        Ok(CombinedTx {
+            date: row.get(0)?,
+            details: row.get(1)?,
+            tx_method: row.get(2)?,
+            amount: row.get(3)?,
+            tx_type: row.get(4)?,
+            tags: row.get(5)?,
+            rate: row.get(6)?,
+        })
+    })?;
+
+    rows.collect()
+}
+
+/// Creates an in-memory db with the txs of every given db. Tx methods with the same name are
+/// merged into one. Only the txs with their tags and exchange rates are copied
+pub fn create_combined_db(db_paths: &[PathBuf]) -> Result<Connection> {
+    let mut conn = Connection::open_in_memory()?;
+
+    let mut tx_methods: Vec<String> = Vec::new();
+    let mut txs = Vec::new();
+
+    // attached one at a time as only a few dbs can be attached at once
+    for db_path in db_paths {
+        conn.execute("ATTACH DATABASE ?1 AS profile", [db_path.to_string_lossy()])?;
+
+        for tx_method in get_attached_tx_methods("profile", &conn)? {
+            if !tx_methods.contains(&tx_method) {
+                tx_methods.push(tx_method);
+            }
+        }
+        txs.extend(get_attached_txs("profile", &conn)?);
+
+        conn.execute("DETACH DATABASE profile", [])?;
+    }
+
+    create_db(&tx_methods, &mut conn)?;
+
+    // the txs of a date stay in the order of their profile
+    txs.sort_by(|a, b| a.date.cmp(&b.date));
+
+    let sp = conn.savepoint()?;
+    for tx in &txs {
+        insert_tx(
+            &tx.date,
+            &tx.details,
+            &tx.tx_method,
+            &tx.amount,
+            &tx.tx_type,
+            &tx.tags,
+            None,
+            tx.rate,
+            &sp,
+        )?;
+    }
+    sp.commit()?;
+
+    Ok(conn)
+}
@@ -0,0 +1,101 @@
+use chrono::Local;
+use rusqlite::{Connection, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The amount and the currency a tx was entered in before it got converted to the base currency
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ForeignAmount {
+    pub amount: String,
+    pub currency: String,
+}
+
+/// Returns the foreign amount the given tx was entered in. `None` if it was entered
+/// in the base currency
+pub fn get_tx_foreign_amount(id_num: i32, conn: &Connection) -> Option<ForeignAmount> {
+    conn.query_row(
+        "SELECT amount, currency FROM tx_foreign_amounts WHERE id_num = ?",
+        [id_num],
+        |row| {
+            Ok(ForeignAmount {
+                amount: row.get(0)?,
+                currency: row.get(1)?,
+            })
+        },
+    )
+    .ok()
+}
+
+/// Saves the foreign amount of a tx, replacing the earlier one. `None` removes the saved one
+pub fn set_tx_foreign_amount(
+    id_num: i32,
+    foreign_amount: Option<&ForeignAmount>,
+    conn: &Connection,
+) -> Result<()> {
+    if let Some(foreign_amount) = foreign_amount {
+        conn.execute(
+            "INSERT OR REPLACE INTO tx_foreign_amounts (id_num, amount, currency) VALUES (?, ?, ?)",
+            (id_num, &foreign_amount.amount, &foreign_amount.currency),
+        )?;
+    } else {
+        conn.execute("DELETE FROM tx_foreign_amounts WHERE id_num = ?", [id_num])?;
+    }
+    Ok(())
+}
+
+/// Returns the last fetched rate of the currency with the date it was fetched on
+pub fn get_cached_rate(currency: &str, conn: &Connection) -> Option<(f64, String)> {
+    conn.query_row(
+        "SELECT rate, date FROM currency_rates WHERE currency = ?",
+        [currency],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .ok()
+}
+
+/// Saves a fetched rate of the currency with the current date
+pub fn cache_rate(currency: &str, rate: f64, conn: &Connection) -> Result<()> {
+    let today = Local::now().format("%Y-%m-%d").to_string();
+
+    conn.execute(
+        "INSERT OR REPLACE INTO currency_rates (currency, rate, date) VALUES (?, ?, ?)",
+        (currency, rate, today),
+    )?;
+    Ok(())
+}
+
+/// Returns the exchange rate of a transfer between tx methods of different currencies.
+/// `None` if the transfer was made without one
+pub fn get_tx_exchange_rate(id_num: i32, conn: &Connection) -> Option<f64> {
+    conn.query_row(
+        "SELECT rate FROM tx_exchange_rates WHERE id_num = ?",
+        [id_num],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+/// Returns the exchange rate of every transfer that has one by their `id_num`
+pub fn get_exchange_rates(conn: &Connection) -> HashMap<i32, f64> {
+    let mut query = conn
+        .prepare("SELECT id_num, rate FROM tx_exchange_rates")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Saves the exchange rate of a transfer, replacing the earlier one. `None` removes the saved one
+pub fn set_tx_exchange_rate(id_num: i32, rate: Option<f64>, conn: &Connection) -> Result<()> {
+    if let Some(rate) = rate {
+        conn.execute(
+            "INSERT OR REPLACE INTO tx_exchange_rates (id_num, rate) VALUES (?, ?)",
+            (id_num, rate),
+        )?;
+    } else {
+        conn.execute("DELETE FROM tx_exchange_rates WHERE id_num = ?", [id_num])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use chrono::NaiveDate;
+use rusqlite::{Connection, Result as sqlResult};
+
+use crate::db::{add_tx_method_columns, write_rebuilt_tables};
+use crate::outputs::DumpError;
+use crate::utility::get_all_tx_methods;
+
+/// The first line of every dump. Changes if the format of the lines ever changes
+const DUMP_HEADER: &str = "# Rex dump v1";
+
+/// The tables that point to a tx with its `id_num`. Emptied with `tx_all` when a dump is loaded
+const TX_CHILD_TABLES: [&str; 7] = [
+    "changes_all",
+    "tx_notes",
+    "tx_status",
+    "tx_splits",
+    "tx_attachments",
+    "tx_foreign_amounts",
+    "tx_exchange_rates",
+];
+
+/// A tx as it is written in a dump
+#[derive(Debug, PartialEq)]
+struct DumpTx {
+    date: String,
+    tx_type: String,
+    tx_method: String,
+    amount: String,
+    rate: Option<f64>,
+    tags: String,
+    details: String,
+    note: String,
+}
+
+/// Escapes the characters that would break a dump line apart
+fn escape_field(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape_field(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Returns every tx method and tx of the db as text. Each tx method and each tx is a single line
+/// of tab separated fields and the txs are sorted by their date and the order they were added in.
+/// The same data always gives the same text so it can be versioned and merged with git
+pub fn dump_db(conn: &Connection) -> sqlResult<String> {
+    let mut lines = vec![DUMP_HEADER.to_string()];
+
+    for tx_method in get_all_tx_methods(conn) {
+        lines.push(format!("method\t{}", escape_field(&tx_method)));
+    }
+
+    let mut statement = conn.prepare(
+        "SELECT t.date, t.tx_type, t.tx_method, t.amount, r.rate, t.tags, t.details, n.note
+        FROM tx_all t
+        LEFT JOIN tx_exchange_rates r ON r.id_num = t.id_num
+        LEFT JOIN tx_notes n ON n.id_num = t.id_num
+        ORDER BY t.date, t.id_num",
+    )?;
+
+    let rows = statement.query_map([], |row| {
+        Ok(DumpTx {
+            date: row.get(0)?,
+            tx_type: row.get(1)?,
+            tx_method: row.get(2)?,
+            amount: row.get(3)?,
+            rate: row.get(4)?,
+            tags: row.get(5)?,
+            details: row.get(6)?,
+            note: row.get::<_, Option<String>>(7)?.unwrap_or_default(),
+        })
+    })?;
+
+    for row in rows {
+        let tx = row?;
+        let fields = [
+            tx.date,
+            tx.tx_type,
+            tx.tx_method,
+            tx.amount,
+            tx.rate.map(|rate| rate.to_string()).unwrap_or_default(),
+            tx.tags,
+            tx.details,
+            tx.note,
+        ];
+
+        let fields = fields
+            .iter()
+            .map(|field| escape_field(field))
+            .collect::<Vec<String>>();
+        lines.push(format!("tx\t{}", fields.join("\t")));
+    }
+
+    lines.push(String::new());
+    Ok(lines.join("\n"))
+}
+
+/// Reads the tx methods and the txs of a dump. Empty lines and lines starting with # are skipped
+fn parse_dump(dump: &str) -> Result<(Vec<String>, Vec<DumpTx>), DumpError> {
+    let mut tx_methods = Vec::new();
+    let mut txs = Vec::new();
+
+    for (index, line) in dump.lines().enumerate() {
+        let line_num = index + 1;
+        let line = line.trim_end_matches('\r');
+
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields = line
+            .split('\t')
+            .map(unescape_field)
+            .collect::<Vec<String>>();
+
+        match fields[0].as_str() {
+            "method" if fields.len() == 2 && !fields[1].trim().is_empty() => {
+                tx_methods.push(fields[1].clone());
+            }
+            "tx" if fields.len() == 9 => {
+                let tx = DumpTx {
+                    date: fields[1].clone(),
+                    tx_type: fields[2].clone(),
+                    tx_method: fields[3].clone(),
+                    amount: fields[4].clone(),
+                    rate: if fields[5].is_empty() {
+                        None
+                    } else {
+                        Some(
+                            fields[5]
+                                .parse()
+                                .map_err(|_| DumpError::InvalidLine(line_num))?,
+                        )
+                    },
+                    tags: fields[6].clone(),
+                    details: fields[7].clone(),
+                    note: fields[8].clone(),
+                };
+
+                let valid_date = NaiveDate::parse_from_str(&tx.date, "%Y-%m-%d").is_ok();
+                let valid_amount = tx.amount.parse::<f64>().is_ok();
+                let valid_type = ["Income", "Expense", "Transfer"].contains(&tx.tx_type.as_str());
+                if !valid_date || !valid_amount || !valid_type {
+                    return Err(DumpError::InvalidLine(line_num));
+                }
+
+                let methods = if tx.tx_type == "Transfer" {
+                    let Some((from_method, to_method)) = tx.tx_method.split_once(" to ") else {
+                        return Err(DumpError::InvalidLine(line_num));
+                    };
+                    vec![from_method, to_method]
+                } else {
+                    vec![tx.tx_method.as_str()]
+                };
+
+                if let Some(method) = methods
+                    .iter()
+                    .find(|m| !tx_methods.contains(&m.to_string()))
+                {
+                    return Err(DumpError::UnknownTxMethod(line_num, method.to_string()));
+                }
+
+                txs.push(tx);
+            }
+            _ => return Err(DumpError::InvalidLine(line_num)),
+        }
+    }
+
+    if tx_methods.is_empty() {
+        return Err(DumpError::NoTxMethod);
+    }
+
+    Ok((tx_methods, txs))
+}
+
+/// Returns the tx methods of a dump. Used for creating a new db before a dump is loaded into it
+pub fn get_dump_tx_methods(dump: &str) -> Result<Vec<String>, DumpError> {
+    parse_dump(dump).map(|(tx_methods, _)| tx_methods)
+}
+
+/// Replaces every tx of the db with the txs of the dump and works out the balances again.
+/// Tx methods of the dump that the db does not have are added. Returns the number of loaded txs
+pub fn load_dump(dump: &str, conn: &mut Connection) -> Result<usize, DumpError> {
+    let (tx_methods, txs) = parse_dump(dump)?;
+
+    let current_methods = get_all_tx_methods(conn);
+    let new_methods = tx_methods
+        .into_iter()
+        .filter(|method| !current_methods.contains(method))
+        .collect::<Vec<String>>();
+
+    let sp = conn.savepoint()?;
+
+    add_tx_method_columns(&new_methods, &sp)?;
+
+    for table in TX_CHILD_TABLES {
+        sp.execute(&format!("DELETE FROM {table}"), [])?;
+    }
+    sp.execute("DELETE FROM tx_all", [])?;
+    // the loaded txs get the same ids on every machine
+    sp.execute("DELETE FROM sqlite_sequence WHERE name = 'tx_all'", [])?;
+
+    for tx in &txs {
+        sp.execute(
+            r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type, tags) VALUES (?, ?, ?, ?, ?, ?)"#,
+            [
+                &tx.date,
+                &tx.details,
+                &tx.tx_method,
+                &tx.amount,
+                &tx.tx_type,
+                &tx.tags,
+            ],
+        )?;
+        let id_num = sp.last_insert_rowid();
+
+        if let Some(rate) = tx.rate {
+            sp.execute(
+                "INSERT INTO tx_exchange_rates (id_num, rate) VALUES (?, ?)",
+                (id_num, rate),
+            )?;
+        }
+
+        if !tx.note.is_empty() {
+            sp.execute(
+                "INSERT INTO tx_notes (id_num, note) VALUES (?, ?)",
+                (id_num, &tx.note),
+            )?;
+        }
+    }
+
+    write_rebuilt_tables(&sp)?;
+    sp.commit()?;
+
+    Ok(txs.len())
+}
@@ -0,0 +1,57 @@
+use rusqlite::{Connection, Result};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// The first bytes of every plaintext SQLite db. Encrypted dbs start with random bytes instead
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Checks whether the db file is encrypted. Missing and empty files are not encrypted
+pub fn is_db_encrypted(db_path: &Path) -> bool {
+    let mut header = [0; 16];
+
+    File::open(db_path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|()| &header != SQLITE_HEADER)
+}
+
+/// Unlocks an encrypted db with the passphrase. Fails if the passphrase is wrong.
+/// Must be called before anything else is done with the connection
+pub fn unlock_db(passphrase: &str, conn: &Connection) -> Result<()> {
+    conn.pragma_update(None, "key", passphrase)?;
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })?;
+    Ok(())
+}
+
+/// Writes an encrypted copy of the plaintext db of the connection to the target path
+pub fn export_encrypted_db(target_path: &Path, passphrase: &str, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+        [&target_path.to_string_lossy(), passphrase],
+    )?;
+    let status = conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()));
+    conn.execute("DETACH DATABASE encrypted", [])?;
+    status
+}
+
+/// Attaches an encrypted db to the connection with the schema name so its tables can be read
+/// like `schema.tx_all`
+pub fn attach_encrypted_db(
+    db_path: &Path,
+    schema: &str,
+    passphrase: &str,
+    conn: &Connection,
+) -> Result<()> {
+    conn.execute(
+        &format!("ATTACH DATABASE ?1 AS {schema} KEY ?2"),
+        [&db_path.to_string_lossy(), passphrase],
+    )?;
+    Ok(())
+}
+
+/// Changes the passphrase of an encrypted db
+pub fn change_db_passphrase(passphrase: &str, conn: &Connection) -> Result<()> {
+    conn.pragma_update(None, "rekey", passphrase)
+}
@@ -0,0 +1,43 @@
+use chrono::Local;
+use rusqlite::{Connection, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// The size and the last modified time of a db file. File sync tools like Syncthing or Dropbox
+/// replace the file when the db was changed on another device which changes both of them
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DbFileState {
+    modified: Option<SystemTime>,
+    size: u64,
+}
+
+/// Returns the current state of the db file. None if the file cannot be read
+pub fn get_db_file_state(db_path: &Path) -> Option<DbFileState> {
+    let metadata = fs::metadata(db_path).ok()?;
+
+    Some(DbFileState {
+        modified: metadata.modified().ok(),
+        size: metadata.len(),
+    })
+}
+
+/// Returns the path the conflicting copy of the db gets saved to. The copy is kept next to the db
+/// like data.conflict-20240131-182005.sqlite
+pub fn get_conflict_copy_path(db_path: &Path) -> PathBuf {
+    let stem = db_path.file_stem().map_or_else(
+        || "data".to_string(),
+        |stem| stem.to_string_lossy().to_string(),
+    );
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+
+    db_path.with_file_name(format!("{stem}.conflict-{timestamp}.sqlite"))
+}
+
+/// Writes every data the connection can see to a new db file at the target path. The connection
+/// keeps reading the db it was opened with even after the file got replaced on the disk, so this
+/// saves the data of the current session
+pub fn save_db_copy(target_path: &Path, conn: &Connection) -> Result<()> {
+    conn.execute("VACUUM INTO ?", [target_path.to_string_lossy()])?;
+    Ok(())
+}
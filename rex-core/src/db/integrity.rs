@@ -0,0 +1,314 @@
+use rusqlite::{Connection, Result};
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::db::{clear_snapshots_from, get_exchange_rates, YEARS};
+use crate::utility::get_all_tx_methods;
+
+/// The balances of every month and the changes of every tx worked out from the txs alone
+pub struct RebuiltBalances {
+    /// The balance of each tx method at the end of every month that has a tx. Indexed by the
+    /// `id_num` of the month on `balance_all`
+    pub month_balances: HashMap<i32, Vec<f64>>,
+    /// The balance of each tx method after all txs
+    pub final_balance: Vec<f64>,
+    /// `(id_num, date, changes)` of every tx in the same format as `changes_all`
+    pub changes: Vec<(i32, String, Vec<String>)>,
+}
+
+/// Runs the sqlite integrity check and looks for rows that no longer match between the tx,
+/// balance and activity tables. Returns a line for every problem found
+pub fn check_db_integrity(conn: &Connection) -> Result<Vec<String>> {
+    let mut problems = Vec::new();
+
+    let mut statement = conn.prepare("PRAGMA integrity_check")?;
+    let rows = statement.query_map([], |row| row.get::<_, String>(0))?;
+    for row in rows {
+        let row = row?;
+        if row != "ok" {
+            problems.push(row);
+        }
+    }
+
+    // rows written while foreign keys were off or by other tools can still point nowhere
+    let mut statement = conn.prepare(
+        "SELECT \"table\", parent, COUNT(*) FROM pragma_foreign_key_check GROUP BY \"table\", parent",
+    )?;
+    let rows = statement.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (table, parent, total) = row?;
+        problems.push(format!(
+            "{total} row(s) of {table} point to a missing row of {parent}"
+        ));
+    }
+
+    let missing_changes: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM tx_all WHERE id_num NOT IN (SELECT id_num FROM changes_all)",
+        [],
+        |row| row.get(0),
+    )?;
+    if missing_changes != 0 {
+        problems.push(format!(
+            "{missing_changes} transaction(s) have no row in changes_all"
+        ));
+    }
+
+    let tx_methods = get_all_tx_methods(conn);
+    let mut statement = conn.prepare("SELECT id_num, tx_method FROM tx_all")?;
+    let rows = statement.query_map([], |row| {
+        Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for row in rows {
+        let (id_num, tx_method) = row?;
+        if tx_method
+            .split(" to ")
+            .any(|method| !tx_methods.iter().any(|m| m == method))
+        {
+            problems.push(format!(
+                "Transaction {id_num} uses the unknown tx method {tx_method}"
+            ));
+        }
+    }
+
+    // every month of every year plus the final balance row
+    let expected_rows = YEARS.len() as i64 * 12 + 1;
+    let balance_rows: i64 =
+        conn.query_row("SELECT COUNT(*) FROM balance_all", [], |row| row.get(0))?;
+    if balance_rows != expected_rows {
+        problems.push(format!(
+            "balance_all has {balance_rows} rows instead of {expected_rows}"
+        ));
+    }
+
+    Ok(problems)
+}
+
+/// Goes through every tx from the oldest one and works out the balances and the changes they
+/// lead to. Txs with an unknown tx method are skipped
+pub fn get_rebuilt_balances(conn: &Connection) -> Result<RebuiltBalances> {
+    let tx_methods = get_all_tx_methods(conn);
+    let rates = get_exchange_rates(conn);
+
+    let mut running_balance = vec![0.0; tx_methods.len()];
+    let mut month_balances = HashMap::new();
+    let mut changes = Vec::new();
+
+    let mut statement = conn.prepare(
+        "SELECT id_num, date, tx_method, amount, tx_type FROM tx_all ORDER BY date, id_num",
+    )?;
+    let rows = statement.query_map([], |row| {
+        Ok((
+            row.get::<_, i32>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+        ))
+    })?;
+
+    for row in rows {
+        let (id_num, date, tx_method, amount, tx_type) = row?;
+        let amount = amount.parse::<f64>().unwrap_or(0.0);
+
+        // (index of the method, balance change)
+        let method_changes = if tx_type == "Transfer" {
+            let Some((from_method, to_method)) = tx_method.split_once(" to ") else {
+                continue;
+            };
+            let to_amount = amount * rates.get(&id_num).copied().unwrap_or(1.0);
+
+            let from_index = tx_methods.iter().position(|m| m == from_method);
+            let to_index = tx_methods.iter().position(|m| m == to_method);
+            let (Some(from_index), Some(to_index)) = (from_index, to_index) else {
+                continue;
+            };
+            vec![(from_index, -amount), (to_index, to_amount)]
+        } else {
+            let Some(index) = tx_methods.iter().position(|m| m == &tx_method) else {
+                continue;
+            };
+            let change = if tx_type == "Expense" {
+                -amount
+            } else {
+                amount
+            };
+            vec![(index, change)]
+        };
+
+        let mut tx_changes = vec![format!("{:.2}", 0.0); tx_methods.len()];
+        for (index, change) in method_changes {
+            running_balance[index] += change;
+            tx_changes[index] = if change < 0.0 {
+                format!("↓{:.2}", change.abs())
+            } else {
+                format!("↑{change:.2}")
+            };
+        }
+
+        let splitted = date.split('-').collect::<Vec<&str>>();
+        let (year, month) = (
+            splitted[0].parse::<i32>().unwrap_or(2022) - 2022,
+            splitted[1].parse::<i32>().unwrap_or(1),
+        );
+
+        month_balances.insert(month + (year * 12), running_balance.clone());
+        changes.push((id_num, date, tx_changes));
+    }
+
+    Ok(RebuiltBalances {
+        month_balances,
+        final_balance: running_balance,
+        changes,
+    })
+}
+
+/// Compares the balance of every tx method on every month of `balance_all` with the balance
+/// worked out from the txs. Months without a tx carry the last balance the same way the rest of
+/// the app reads them. Returns `(id_num, tx_method, stored balance, expected balance)` for each
+/// mismatch. The last `id_num` is the final balance after all txs
+pub fn get_balance_divergence(conn: &Connection) -> Result<Vec<(i32, String, f64, f64)>> {
+    let tx_methods = get_all_tx_methods(conn);
+    let rebuilt = get_rebuilt_balances(conn)?;
+    let final_id = YEARS.len() as i32 * 12 + 1;
+
+    let columns = tx_methods
+        .iter()
+        .map(|method| format!(r#""{method}""#))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut statement = conn.prepare(&format!(
+        "SELECT id_num, {columns} FROM balance_all ORDER BY id_num"
+    ))?;
+    let stored_rows = statement
+        .query_map([], |row| {
+            let mut balance = Vec::new();
+            for i in 0..tx_methods.len() {
+                balance.push(row.get::<_, f64>(i + 1)?);
+            }
+            Ok((row.get::<_, i32>(0)?, balance))
+        })?
+        .collect::<Result<HashMap<i32, Vec<f64>>>>()?;
+
+    let empty_row = vec![0.0; tx_methods.len()];
+    let mut stored_carried = empty_row.clone();
+    let mut expected_carried = empty_row.clone();
+    let mut divergence = Vec::new();
+
+    for id_num in 1..=final_id {
+        let stored = stored_rows.get(&id_num).unwrap_or(&empty_row);
+        let expected = if id_num == final_id {
+            &rebuilt.final_balance
+        } else {
+            rebuilt.month_balances.get(&id_num).unwrap_or(&empty_row)
+        };
+
+        for (i, tx_method) in tx_methods.iter().enumerate() {
+            // a 0 balance means the month had no tx so the earlier balance is still valid
+            if id_num == final_id || stored[i] != 0.0 {
+                stored_carried[i] = stored[i];
+            }
+            if id_num == final_id || expected[i] != 0.0 {
+                expected_carried[i] = expected[i];
+            }
+
+            if format!("{:.2}", stored_carried[i]) != format!("{:.2}", expected_carried[i]) {
+                divergence.push((
+                    id_num,
+                    tx_method.to_string(),
+                    stored_carried[i],
+                    expected_carried[i],
+                ));
+            }
+        }
+    }
+
+    Ok(divergence)
+}
+
+/// Replaces `balance_all` and `changes_all` with the balances and the changes worked out from
+/// the txs. The month end snapshots are cleared so they get taken again
+pub fn rebuild_derived_tables(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    write_rebuilt_tables(&sp)?;
+    sp.commit()?;
+    info!("Rebuilt the balances and the changes from the txs");
+    Ok(())
+}
+
+/// Writes the rebuilt balances and changes without committing them
+pub(crate) fn write_rebuilt_tables(sp: &Connection) -> Result<()> {
+    let tx_methods = get_all_tx_methods(sp);
+    let rebuilt = get_rebuilt_balances(sp)?;
+
+    let columns = tx_methods
+        .iter()
+        .map(|method| format!(r#""{method}""#))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let set_balance = |balance: &[f64]| {
+        tx_methods
+            .iter()
+            .zip(balance.iter())
+            .map(|(method, value)| format!(r#""{method}" = {value:.2}"#))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    sp.execute("DELETE FROM balance_all", [])?;
+    sp.execute("DELETE FROM changes_all", [])?;
+
+    let zero_values = vec!["0.00"; tx_methods.len()].join(", ");
+    let expected_rows = YEARS.len() as i32 * 12 + 1;
+    for id_num in 1..=expected_rows {
+        sp.execute(
+            &format!("INSERT INTO balance_all (id_num, {columns}) VALUES (?, {zero_values})"),
+            [id_num],
+        )?;
+    }
+
+    // txs outside the supported years have no month row
+    for (id_num, balance) in rebuilt
+        .month_balances
+        .iter()
+        .filter(|(id_num, _)| (1..expected_rows).contains(*id_num))
+    {
+        sp.execute(
+            &format!(
+                "UPDATE balance_all SET {} WHERE id_num = ?",
+                set_balance(balance)
+            ),
+            [id_num],
+        )?;
+    }
+
+    sp.execute(
+        &format!(
+            "UPDATE balance_all SET {} WHERE id_num = ?",
+            set_balance(&rebuilt.final_balance)
+        ),
+        [expected_rows],
+    )?;
+
+    let placeholders = vec!["?"; tx_methods.len()].join(", ");
+    let changes_query =
+        format!("INSERT INTO changes_all (id_num, date, {columns}) VALUES (?, ?, {placeholders})");
+    for (id_num, date, tx_changes) in &rebuilt.changes {
+        let mut params: Vec<&dyn rusqlite::ToSql> = vec![id_num, date];
+        params.extend(
+            tx_changes
+                .iter()
+                .map(|change| change as &dyn rusqlite::ToSql),
+        );
+        sp.execute(&changes_query, params.as_slice())?;
+    }
+
+    clear_snapshots_from(0, sp)
+}
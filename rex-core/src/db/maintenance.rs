@@ -0,0 +1,88 @@
+use chrono::Local;
+use rusqlite::{Connection, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::db::save_db_copy;
+use crate::outputs::MaintenanceError;
+
+/// The size of the db and the number of rows in each of its tables
+#[derive(Debug, PartialEq)]
+pub struct DbStats {
+    /// The size of the db pages in bytes. The WAL file is not counted
+    pub size: i64,
+    /// The bytes taken by pages that no longer hold any data. VACUUM gives them back
+    pub free_size: i64,
+    /// Every table of the db with its number of rows sorted by the table name
+    pub tables: Vec<(String, i64)>,
+}
+
+/// Returns the size of the db and the row count of every table
+pub fn get_db_stats(conn: &Connection) -> Result<DbStats> {
+    let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+    let page_count: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+    let free_pages: i64 = conn.query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+
+    let mut statement = conn.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+        ORDER BY name",
+    )?;
+    let table_names = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+
+    let mut tables = Vec::new();
+    for name in table_names {
+        let rows: i64 =
+            conn.query_row(&format!(r#"SELECT COUNT(*) FROM "{name}""#), [], |row| {
+                row.get(0)
+            })?;
+        tables.push((name, rows));
+    }
+
+    Ok(DbStats {
+        size: page_size * page_count,
+        free_size: page_size * free_pages,
+        tables,
+    })
+}
+
+/// Rewrites the db file without the free pages which makes it smaller and less fragmented
+pub fn vacuum_db(conn: &Connection) -> Result<()> {
+    conn.execute("VACUUM", [])?;
+    info!("Vacuumed the db");
+    Ok(())
+}
+
+/// Updates the statistics SQLite uses to pick the indexes of a query
+pub fn analyze_db(conn: &Connection) -> Result<()> {
+    conn.execute("ANALYZE", [])?;
+    info!("Analyzed the db");
+    Ok(())
+}
+
+/// Builds every index of the db again from the table data
+pub fn reindex_db(conn: &Connection) -> Result<()> {
+    conn.execute("REINDEX", [])?;
+    info!("Reindexed the db");
+    Ok(())
+}
+
+/// Saves a copy of the db in the backups folder next to it like
+/// backups/data-20240131-182005.sqlite. Returns the path of the copy
+pub fn backup_db_now(db_path: &Path, conn: &Connection) -> Result<PathBuf, MaintenanceError> {
+    let stem = db_path.file_stem().map_or_else(
+        || "data".to_string(),
+        |stem| stem.to_string_lossy().to_string(),
+    );
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+
+    let mut backup_path = db_path.with_file_name("backups");
+    fs::create_dir_all(&backup_path).map_err(MaintenanceError::FailedBackupFolder)?;
+    backup_path.push(format!("{stem}-{timestamp}.sqlite"));
+
+    save_db_copy(&backup_path, conn).map_err(MaintenanceError::FailedBackup)?;
+    info!(path = %backup_path.display(), "Saved a backup of the db");
+    Ok(backup_path)
+}
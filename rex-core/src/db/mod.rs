@@ -0,0 +1,39 @@
+mod attachment;
+mod combined;
+mod currency;
+mod dump;
+mod encryption;
+mod file_state;
+mod integrity;
+mod maintenance;
+mod note;
+mod report;
+mod schedule;
+mod setup;
+mod snapshot;
+mod split;
+mod status;
+mod tag_rule;
+mod trash;
+mod tx_method;
+mod update;
+
+pub use attachment::*;
+pub use combined::*;
+pub use currency::*;
+pub use dump::*;
+pub use encryption::*;
+pub use file_state::*;
+pub use integrity::*;
+pub use maintenance::*;
+pub use note::*;
+pub use report::*;
+pub use schedule::*;
+pub use setup::*;
+pub use snapshot::*;
+pub use split::*;
+pub use status::*;
+pub use tag_rule::*;
+pub use trash::*;
+pub use tx_method::*;
+pub use update::*;
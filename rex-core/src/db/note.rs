@@ -0,0 +1,24 @@
+use rusqlite::{Connection, Result};
+
+/// Returns the long note saved for the given tx. Empty string if there is none
+pub fn get_tx_note(id_num: i32, conn: &Connection) -> String {
+    conn.query_row(
+        "SELECT note FROM tx_notes WHERE id_num = ?",
+        [id_num],
+        |row| row.get(0),
+    )
+    .unwrap_or_default()
+}
+
+/// Saves the long note of a tx, replacing the earlier one. An empty note removes the saved one
+pub fn set_tx_note(id_num: i32, note: &str, conn: &Connection) -> Result<()> {
+    if note.trim().is_empty() {
+        conn.execute("DELETE FROM tx_notes WHERE id_num = ?", [id_num])?;
+    } else {
+        conn.execute(
+            "INSERT OR REPLACE INTO tx_notes (id_num, note) VALUES (?, ?)",
+            (id_num, note),
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use rusqlite::types::ValueRef;
+use rusqlite::{Batch, Connection};
+
+use crate::outputs::ReportError;
+
+/// The column names and the rows a report query returned. Every value is turned into text
+#[derive(Debug, Default, PartialEq)]
+pub struct ReportResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Runs a saved report query and returns its result. Queries that would change the db and
+/// multiple statements are refused before anything is run
+pub fn run_report_query(query: &str, conn: &Connection) -> Result<ReportResult, ReportError> {
+    let mut batch = Batch::new(conn, query);
+
+    let Some(mut statement) = batch.next()? else {
+        return Err(ReportError::EmptyQuery);
+    };
+
+    if batch.next()?.is_some() {
+        return Err(ReportError::MultipleQueries);
+    }
+
+    // statements like ATTACH count as read only for SQLite so only queries are allowed
+    let first_word = query
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect::<String>()
+        .to_lowercase();
+    if !statement.readonly() || !["select", "with", "values"].contains(&first_word.as_str()) {
+        return Err(ReportError::NotReadOnly);
+    }
+
+    let columns = statement
+        .column_names()
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+    let total_columns = columns.len();
+
+    let mut rows = Vec::new();
+    let mut query_rows = statement.query([])?;
+
+    while let Some(row) = query_rows.next()? {
+        let mut values = Vec::with_capacity(total_columns);
+        for index in 0..total_columns {
+            let value = match row.get_ref(index)? {
+                ValueRef::Null => String::new(),
+                ValueRef::Integer(value) => value.to_string(),
+                ValueRef::Real(value) => format!("{value:.2}"),
+                ValueRef::Text(value) => String::from_utf8_lossy(value).to_string(),
+                ValueRef::Blob(value) => format!("{} bytes", value.len()),
+            };
+            values.push(value);
+        }
+        rows.push(values);
+    }
+
+    Ok(ReportResult { columns, rows })
+}
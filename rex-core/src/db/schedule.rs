@@ -0,0 +1,54 @@
+use rusqlite::{Connection, Result};
+
+/// Saves a tx to be added on its date. Returns the id of the scheduled tx
+pub fn add_scheduled_tx(
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tx_type: &str,
+    tags: &str,
+    conn: &Connection,
+) -> Result<i32> {
+    conn.execute(
+        "INSERT INTO scheduled_txs (date, details, tx_method, amount, tx_type, tags) VALUES (?, ?, ?, ?, ?, ?)",
+        [date, details, tx_method, amount, tx_type, tags],
+    )?;
+
+    conn.query_row(
+        "SELECT schedule_id FROM scheduled_txs ORDER BY schedule_id DESC LIMIT 1",
+        [],
+        |row| row.get(0),
+    )
+}
+
+/// Returns every scheduled tx ordered by their date. Each tx contains the date, details, tx method,
+/// amount, tx type, tags and the id of the scheduled tx in this order
+pub fn get_scheduled_txs(conn: &Connection) -> Vec<Vec<String>> {
+    let mut query = conn
+        .prepare(
+            "SELECT date, details, tx_method, amount, tx_type, tags, schedule_id FROM scheduled_txs ORDER BY date, schedule_id",
+        )
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| {
+            let mut tx = Vec::new();
+            for index in 0..6 {
+                tx.push(row.get::<_, String>(index)?);
+            }
+            tx.push(row.get::<_, i32>(6)?.to_string());
+            Ok(tx)
+        })
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Removes a scheduled tx
+pub fn delete_scheduled_tx(schedule_id: i32, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "DELETE FROM scheduled_txs WHERE schedule_id = ?",
+        [schedule_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,513 @@
+use rusqlite::{Connection, OpenFlags, Result, Savepoint};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+pub const YEARS: [&str; 16] = [
+    "2022", "2023", "2024", "2025", "2026", "2027", "2028", "2029", "2030", "2031", "2032", "2033",
+    "2034", "2035", "2036", "2037",
+];
+
+pub const MODES: [&str; 3] = ["Monthly", "Yearly", "All Time"];
+
+/// Creates the db that is used by this app
+pub fn create_db(tx_methods: &[String], conn: &mut Connection) -> Result<()> {
+    // add a save point to reverse commits if failed
+    let sp = conn.savepoint()?;
+
+    // tx_all table. Will contain all tx data
+    // Amount should have been REAL, old mistake, can't be bothered to migrate to REAL at this point
+    // Same for date column
+    // Also not too frequently searched by amount field anyway. Only when searching txs. Not a priority
+    sp.execute(
+        "CREATE TABLE tx_all (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount TEXT,
+        tx_type TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        tags TEXT
+    );",
+        [],
+    )?;
+
+    create_balances_table(tx_methods, &sp)?;
+
+    create_changes_table(tx_methods, &sp)?;
+
+    create_activities_table(&sp)?;
+
+    create_activity_txs_table(&sp)?;
+
+    create_snapshots_table(&sp)?;
+
+    create_notes_table(&sp)?;
+
+    create_status_table(&sp)?;
+
+    create_splits_table(&sp)?;
+
+    create_attachments_table(&sp)?;
+
+    create_currency_tables(&sp)?;
+
+    create_scheduled_table(&sp)?;
+
+    create_tx_method_info_table(&sp)?;
+
+    create_exchange_rates_table(&sp)?;
+
+    create_tag_rules_table(&sp)?;
+
+    create_trash_table(&sp)?;
+
+    create_missing_indexes(&sp)?;
+
+    create_tx_indexes(&sp)?;
+
+    sp.execute("CREATE UNIQUE INDEX all_tx_id_IDX ON tx_all (id_num);", [])?;
+
+    sp.execute(
+        "CREATE UNIQUE INDEX changes_all_id_IDX ON changes_all (id_num);",
+        [],
+    )?;
+    sp.execute(
+        "CREATE UNIQUE INDEX balance_all_id_num_IDX ON balance_all (id_num);",
+        [],
+    )?;
+
+    // fill up balance_all table with total year * 12 + 1 rows with 0 balance
+    let zero_values = vec!["0.00"; tx_methods.len()];
+
+    let highlighted_tx_methods = tx_methods
+        .iter()
+        .map(|method| format!("\"{method}\""))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let query = format!(
+        "INSERT INTO balance_all ({}) VALUES ({})",
+        highlighted_tx_methods,
+        zero_values.join(",")
+    );
+
+    for _i in YEARS {
+        for _a in 0..MONTHS.len() {
+            sp.execute(&query, [])?;
+        }
+    }
+
+    sp.execute(&query, [])?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// creates the `balance_all` table of the DB
+pub fn create_balances_table(tx_methods: &[String], sp: &Savepoint) -> Result<()> {
+    // balance_all table. Will contain tx methods as columns and their balances.
+    // each row represents 1 month.
+    let tx_methods_str = tx_methods
+        .iter()
+        .map(|method| format!(r#""{method}" REAL DEFAULT 0.00"#))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let query = format!(
+        "CREATE TABLE balance_all (
+            id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            {tx_methods_str}
+        );"
+    );
+
+    sp.execute(&query, [])?;
+
+    Ok(())
+}
+
+/// create the `changes_all` table of the DB
+pub fn create_changes_table(tx_methods: &[String], sp: &Savepoint) -> Result<()> {
+    // changes_all column. Will contain all balance changes with up and down arrows
+    let columns = tx_methods
+        .iter()
+        .map(|column_name| format!(r#""{column_name}" TEXT DEFAULT 0.00"#))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let query = format!(
+        "CREATE TABLE changes_all (
+            date TEXT,
+            id_num INTEGER NOT NULL PRIMARY KEY,
+            {columns},
+            CONSTRAINT changes_all_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+        );"
+    );
+
+    sp.execute(&query, [])?;
+
+    Ok(())
+}
+
+pub fn create_activities_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE activities (
+        date TEXT,
+        activity_type TEXT,
+        description TEXT,
+        activity_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT
+    );",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn create_activity_txs_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE activity_txs (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount TEXT,
+        tx_type TEXT,
+        tags TEXT,
+        id_num TEXT,
+        activity_num INTEGER NOT NULL,
+        insertion_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        CONSTRAINT activity_tx_FK FOREIGN KEY (activity_num) REFERENCES activities(activity_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `balance_snapshots` table of the DB. Each row contains the balance of a tx method
+/// at the end of a month. `period_id` matches the `id_num` of the month on `balance_all`
+pub fn create_snapshots_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE balance_snapshots (
+        period_id INTEGER NOT NULL,
+        tx_method TEXT NOT NULL,
+        balance REAL DEFAULT 0.00,
+        taken_at TEXT,
+        PRIMARY KEY (period_id, tx_method)
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `tx_notes` table of the DB. Contains the long notes of the txs that have one
+pub fn create_notes_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_notes (
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        note TEXT NOT NULL,
+        CONSTRAINT tx_notes_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `tx_status` table of the DB. Contains the status of the txs that are no longer pending
+pub fn create_status_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_status (
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        status TEXT NOT NULL,
+        CONSTRAINT tx_status_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `tx_splits` table of the DB. Links the txs that were added as parts of a single split tx.
+/// `split_id` is the `id_num` of the first part
+pub fn create_splits_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_splits (
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        split_id INTEGER NOT NULL,
+        CONSTRAINT tx_splits_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `tx_attachments` table of the DB. Each row is the path of a file attached to a tx
+pub fn create_attachments_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_attachments (
+        attachment_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        id_num INTEGER NOT NULL,
+        path TEXT NOT NULL,
+        CONSTRAINT tx_attachments_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `tx_foreign_amounts` table that keeps the amount and the currency a tx was entered in
+/// and the `currency_rates` table that caches the fetched currency rates
+pub fn create_currency_tables(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_foreign_amounts (
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        amount TEXT NOT NULL,
+        currency TEXT NOT NULL,
+        CONSTRAINT tx_foreign_amounts_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+    sp.execute(
+        "CREATE TABLE currency_rates (
+        currency TEXT NOT NULL PRIMARY KEY,
+        rate REAL NOT NULL,
+        date TEXT NOT NULL
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `scheduled_txs` table of the DB. Contains the txs that are added once their date arrives
+pub fn create_scheduled_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE scheduled_txs (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount TEXT,
+        tx_type TEXT,
+        tags TEXT,
+        schedule_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `tx_method_info` table of the DB. Contains the settings of the tx methods.
+/// Tx methods without a row use the default settings
+pub fn create_tx_method_info_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_method_info (
+        tx_method TEXT NOT NULL PRIMARY KEY,
+        archived INTEGER NOT NULL DEFAULT 0,
+        method_type TEXT NOT NULL DEFAULT 'Cash',
+        currency TEXT NOT NULL DEFAULT '',
+        color TEXT NOT NULL DEFAULT '',
+        credit_limit REAL NOT NULL DEFAULT 0,
+        method_group TEXT NOT NULL DEFAULT ''
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `tag_rules` table of the DB. Contains the rules that tag new txs based on their
+/// details, tx method or amount
+pub fn create_tag_rules_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tag_rules (
+        rule_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        field TEXT NOT NULL,
+        pattern TEXT NOT NULL,
+        tag TEXT NOT NULL
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the indexes of the tx columns the tables are filtered by. Dates are saved as ISO 8601
+/// text so the date ranges are compared in the same order they are indexed in
+pub fn create_tx_indexes(sp: &Savepoint) -> Result<()> {
+    sp.execute("CREATE INDEX tx_all_tags_idx ON tx_all(tags);", [])?;
+
+    sp.execute(
+        "CREATE INDEX tx_all_tx_method_idx ON tx_all(tx_method);",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE INDEX tx_all_date_id_num_idx ON tx_all(date, id_num);",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE INDEX changes_all_date_id_num_idx ON changes_all(date, id_num);",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `trashed_txs` table of the DB. Contains the deleted txs until they are restored
+/// or removed for good. `id_num` is the id the tx had before it was deleted. The status, split
+/// link, foreign amount and attachments of the tx are kept with it as their rows are deleted
+/// alongside the tx. `attachments` is a JSON list of the paths
+pub fn create_trash_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE trashed_txs (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount TEXT,
+        tx_type TEXT,
+        tags TEXT,
+        id_num INTEGER NOT NULL,
+        rate REAL,
+        note TEXT NOT NULL DEFAULT '',
+        status TEXT NOT NULL DEFAULT 'Pending',
+        split_id INTEGER,
+        foreign_amount TEXT,
+        foreign_currency TEXT,
+        attachments TEXT NOT NULL DEFAULT '[]',
+        deleted_at TEXT NOT NULL,
+        trash_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+/// creates the `tx_exchange_rates` table of the DB. Contains the rate of the transfers between tx methods
+/// of different currencies. The To Method receives the amount multiplied by the rate
+pub fn create_exchange_rates_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_exchange_rates (
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        rate REAL NOT NULL,
+        CONSTRAINT tx_exchange_rates_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+    Ok(())
+}
+
+pub fn create_missing_indexes(sp: &Savepoint) -> Result<()> {
+    sp.execute("CREATE INDEX activities_date_idx ON activities(date);", [])?;
+
+    sp.execute("CREATE INDEX tx_all_date_idx ON tx_all(date);", [])?;
+
+    sp.execute(
+        "CREATE INDEX activity_txs_date_idx ON activity_txs(date);",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE UNIQUE INDEX activities_activity_num_idx ON activities(activity_num);",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE INDEX activity_txs_activity_num_idx ON activity_txs(activity_num);",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE UNIQUE INDEX activity_txs_insertion_id_idx ON activity_txs(insertion_id);",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Sets the pragmas every connection of the app should use. WAL lets external readers such as
+/// backup scripts read the db while the app writes to it and the busy timeout makes a connection
+/// wait for a lock instead of failing with a database is locked error
+pub fn set_db_pragmas(conn: &Connection) -> Result<()> {
+    // journal_mode returns the mode that ended up being used
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    conn.busy_timeout(Duration::from_secs(5))
+}
+
+/// Opens the db without write permission so a backup or a db of someone else can be browsed
+/// without touching it. SQLite refuses every change made through the connection. The db is
+/// opened as immutable so the `-wal` and `-shm` files are not created beside it either. A db
+/// that already has a `-wal` file is in use or was not closed cleanly, so it is opened like
+/// `open_db_reader` to see the changes that are still in the WAL file
+pub fn open_read_only_db(db_path: &Path) -> Result<Connection> {
+    if get_wal_path(db_path).exists() {
+        return open_db_reader(db_path);
+    }
+
+    Connection::open_with_flags(
+        get_immutable_uri(db_path),
+        OpenFlags::SQLITE_OPEN_READ_ONLY
+            | OpenFlags::SQLITE_OPEN_URI
+            | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+}
+
+/// Opens another connection without write permission to a db that the app is using. It sees
+/// the changes of the other connections but needs the `-wal` and `-shm` files of a WAL db
+pub fn open_db_reader(db_path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+}
+
+/// Returns the location of the WAL file of the db like `data.sqlite-wal`
+fn get_wal_path(db_path: &Path) -> PathBuf {
+    let mut wal_path = db_path.as_os_str().to_owned();
+    wal_path.push("-wal");
+    PathBuf::from(wal_path)
+}
+
+/// Returns the URI that opens the db as immutable. The characters that have a meaning inside a
+/// URI are escaped
+fn get_immutable_uri(db_path: &Path) -> String {
+    let mut path = String::new();
+
+    for c in db_path.to_string_lossy().chars() {
+        match c {
+            '%' => path.push_str("%25"),
+            '?' => path.push_str("%3F"),
+            '#' => path.push_str("%23"),
+            '\\' if cfg!(windows) => path.push('/'),
+            c => path.push(c),
+        }
+    }
+
+    // an absolute path like C:/data.sqlite is written as /C:/data.sqlite
+    if cfg!(windows) && db_path.is_absolute() {
+        path.insert(0, '/');
+    }
+
+    format!("file:{path}?immutable=1")
+}
+
+/// Sets the pragmas of a read only connection. Must be set after an encrypted db is unlocked.
+/// `query_only` also stops the changes that do not write to the db file like a temp table
+pub fn set_read_only_pragmas(conn: &Connection) -> Result<()> {
+    conn.pragma_update(None, "query_only", true)?;
+    conn.busy_timeout(Duration::from_secs(5))
+}
+
+/// Moves everything in the WAL file to the db file so that a copy of the db file alone is
+/// complete
+pub fn checkpoint_db(conn: &Connection) -> Result<()> {
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+}
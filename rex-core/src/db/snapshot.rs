@@ -0,0 +1,172 @@
+use chrono::{Datelike, Local};
+use rusqlite::{Connection, Result};
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+use crate::db::{MONTHS, YEARS};
+use crate::utility::{
+    get_all_tx_methods, get_all_txs, get_last_time_balance, get_last_time_balance_uncached,
+};
+
+/// Records the balance of every tx method at the end of each completed month that
+/// does not have a snapshot yet. Returns the period of every month that was recorded
+pub fn take_balance_snapshots(conn: &mut Connection) -> Result<Vec<i32>> {
+    let tx_methods = get_all_tx_methods(conn);
+
+    let today = Local::now().date_naive();
+    let year_index = today.year() - 2022;
+
+    if year_index < 0 {
+        return Ok(Vec::new());
+    }
+
+    // the current month is still ongoing so only the earlier months are recorded
+    let last_period =
+        (today.month() as i32 - 1 + (year_index * 12)).min((YEARS.len() * MONTHS.len()) as i32);
+
+    let sp = conn.savepoint()?;
+
+    let mut statement = sp.prepare("SELECT DISTINCT period_id FROM balance_snapshots")?;
+    let recorded_periods = statement
+        .query_map([], |row| row.get::<_, i32>(0))?
+        .collect::<Result<Vec<i32>>>()?;
+    drop(statement);
+
+    let taken_at = today.to_string();
+    let mut recorded = Vec::new();
+
+    for period_id in 1..=last_period {
+        if recorded_periods.contains(&period_id) {
+            continue;
+        }
+
+        // going through the txs of the month also brings the month's balance_all row up to date
+        let (_, month_balances, _) = get_all_txs(
+            &sp,
+            (period_id as usize - 1) % 12,
+            (period_id as usize - 1) / 12,
+        );
+
+        let balances: Vec<f64> = if let Some(last_balance) = month_balances.last() {
+            last_balance.iter().map(|b| b.parse().unwrap()).collect()
+        } else {
+            // no txs in this month, the balance is the same as the earlier month
+            let last_time_balance = get_last_time_balance(
+                period_id as usize % 12,
+                period_id as usize / 12,
+                &tx_methods,
+                &sp,
+            );
+            tx_methods.iter().map(|m| last_time_balance[m]).collect()
+        };
+
+        for (method, balance) in tx_methods.iter().zip(balances) {
+            sp.execute(
+                "INSERT INTO balance_snapshots (period_id, tx_method, balance, taken_at) VALUES (?, ?, ?, ?)",
+                (period_id, method, balance, &taken_at),
+            )?;
+        }
+        recorded.push(period_id);
+    }
+
+    sp.commit()?;
+    if !recorded.is_empty() {
+        info!(periods = ?recorded, "Recorded month end balance snapshots");
+    }
+    Ok(recorded)
+}
+
+/// Returns the latest month that has a snapshot. None if nothing was recorded yet
+pub fn get_last_snapshot_period(conn: &Connection) -> Option<i32> {
+    conn.query_row("SELECT MAX(period_id) FROM balance_snapshots", [], |row| {
+        row.get(0)
+    })
+    .ok()
+    .flatten()
+}
+
+/// Returns the snapshot balance of the given month point if every tx method was recorded in it
+pub fn get_balance_snapshot(
+    period_id: i32,
+    tx_methods: &[String],
+    conn: &Connection,
+) -> Option<HashMap<String, f64>> {
+    let mut statement = conn
+        .prepare("SELECT tx_method, balance FROM balance_snapshots WHERE period_id = ?")
+        .ok()?;
+
+    let snapshot = statement
+        .query_map([period_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })
+        .ok()?
+        .filter_map(std::result::Result::ok)
+        .collect::<HashMap<String, f64>>();
+
+    if tx_methods
+        .iter()
+        .all(|method| snapshot.contains_key(method))
+    {
+        Some(snapshot)
+    } else {
+        None
+    }
+}
+
+/// Compares every saved snapshot with the balance found from the `balance_all` table.
+/// Returns `(period_id, tx_method, snapshot balance, actual balance)` for each mismatch
+pub fn get_snapshot_drift(conn: &Connection) -> Vec<(i32, String, f64, f64)> {
+    let tx_methods = get_all_tx_methods(conn);
+
+    let Ok(mut statement) = conn
+        .prepare("SELECT period_id, tx_method, balance FROM balance_snapshots ORDER BY period_id")
+    else {
+        return Vec::new();
+    };
+
+    let Ok(rows) = statement.query_map([], |row| {
+        Ok((
+            row.get::<_, i32>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, f64>(2)?,
+        ))
+    }) else {
+        return Vec::new();
+    };
+
+    let mut to_return = Vec::new();
+    let mut actual_balances: HashMap<i32, HashMap<String, f64>> = HashMap::new();
+
+    for (period_id, tx_method, balance) in rows.filter_map(std::result::Result::ok) {
+        let actual = actual_balances.entry(period_id).or_insert_with(|| {
+            get_last_time_balance_uncached(
+                period_id as usize % 12,
+                period_id as usize / 12,
+                &tx_methods,
+                conn,
+            )
+        });
+
+        // the method no longer exists
+        let Some(actual_balance) = actual.get(&tx_method) else {
+            continue;
+        };
+
+        if format!("{balance:.2}") != format!("{actual_balance:.2}") {
+            to_return.push((period_id, tx_method, balance, *actual_balance));
+        }
+    }
+
+    to_return
+}
+
+/// Removes all snapshots from the given month point and onwards. Used when a tx gets
+/// added or removed so the outdated balances are no longer used
+pub fn clear_snapshots_from(period_id: i32, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "DELETE FROM balance_snapshots WHERE period_id >= ?",
+        [period_id],
+    )?;
+    debug!(period_id, "Cleared the balance snapshots");
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use rusqlite::{Connection, Result};
+use std::collections::HashMap;
+
+/// Returns the `id_num` of every part of the split tx the given tx belongs to.
+/// Empty if the tx was not split
+pub fn get_split_parts(id_num: i32, conn: &Connection) -> Vec<i32> {
+    let mut query = conn
+        .prepare(
+            "SELECT id_num FROM tx_splits WHERE split_id = (SELECT split_id FROM tx_splits WHERE id_num = ?) ORDER BY id_num",
+        )
+        .expect("could not prepare statement");
+
+    query
+        .query_map([id_num], |row| row.get(0))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Links all the given txs as the parts of a single split tx
+pub fn link_split_parts(id_nums: &[i32], conn: &Connection) -> Result<()> {
+    let Some(split_id) = id_nums.first() else {
+        return Ok(());
+    };
+
+    for id_num in id_nums {
+        conn.execute(
+            "INSERT OR REPLACE INTO tx_splits (id_num, split_id) VALUES (?, ?)",
+            (id_num, split_id),
+        )?;
+    }
+    Ok(())
+}
+
+/// Links a tx to a split tx that already has a `split_id`. Used when a part is added back
+pub fn add_split_part(id_num: i32, split_id: i32, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO tx_splits (id_num, split_id) VALUES (?, ?)",
+        (id_num, split_id),
+    )?;
+    Ok(())
+}
+
+/// Returns the `split_id` of every tx that is a part of a split tx by their `id_num`
+pub fn get_all_split_ids(conn: &Connection) -> HashMap<i32, i32> {
+    let mut query = conn
+        .prepare("SELECT id_num, split_id FROM tx_splits")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,39 @@
+use rusqlite::{Connection, Result};
+use std::collections::HashMap;
+
+use crate::state::TxStatus;
+use crate::utility::get_method_changes;
+
+/// Returns the status of the given tx. Txs without a saved status are pending
+pub fn get_tx_status(id_num: i32, conn: &Connection) -> TxStatus {
+    conn.query_row(
+        "SELECT status FROM tx_status WHERE id_num = ?",
+        [id_num],
+        |row| row.get::<_, String>(0),
+    )
+    .map(|status| TxStatus::from_str(&status))
+    .unwrap_or(TxStatus::Pending)
+}
+
+/// Saves the status of a tx, replacing the earlier one. Pending txs do not keep a saved status
+pub fn set_tx_status(id_num: i32, status: TxStatus, conn: &Connection) -> Result<()> {
+    if status == TxStatus::Pending {
+        conn.execute("DELETE FROM tx_status WHERE id_num = ?", [id_num])?;
+    } else {
+        conn.execute(
+            "INSERT OR REPLACE INTO tx_status (id_num, status) VALUES (?, ?)",
+            (id_num, status.to_str()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns how much the pending txs that happened before the given date changed the balance
+/// of each tx method. `None` goes through every pending tx
+pub fn get_pending_changes(before: Option<&str>, conn: &Connection) -> HashMap<String, f64> {
+    get_method_changes(
+        "date < ? AND id_num NOT IN (SELECT id_num FROM tx_status)",
+        before.unwrap_or("9999-12-31"),
+        conn,
+    )
+}
@@ -0,0 +1,31 @@
+use rusqlite::{Connection, Result};
+
+/// Saves a rule that tags new txs whose field matches the pattern
+pub fn add_tag_rule(field: &str, pattern: &str, tag: &str, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tag_rules (field, pattern, tag) VALUES (?, ?, ?)",
+        [field, pattern, tag],
+    )?;
+    Ok(())
+}
+
+/// Returns every tag rule in the order they were added. Each rule contains the id of the rule,
+/// the field, the pattern and the tag in this order
+pub fn get_tag_rules(conn: &Connection) -> Vec<(i32, String, String, String)> {
+    let mut query = conn
+        .prepare("SELECT rule_id, field, pattern, tag FROM tag_rules ORDER BY rule_id")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Removes a tag rule
+pub fn delete_tag_rule(rule_id: i32, conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM tag_rules WHERE rule_id = ?", [rule_id])?;
+    Ok(())
+}
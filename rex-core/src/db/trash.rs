@@ -0,0 +1,114 @@
+use rusqlite::types::Type;
+use rusqlite::{Connection, Error, OptionalExtension, Result};
+
+use crate::db::{get_tx_attachments, ForeignAmount};
+use crate::state::TxStatus;
+
+/// A tx waiting in the trash with everything needed to add it back
+pub struct TrashedTx {
+    /// date, details, tx method, amount, tx type and tags in this order
+    pub tx: Vec<String>,
+    /// The `id_num` the tx had before it was deleted
+    pub id_num: i32,
+    pub rate: Option<f64>,
+    pub note: String,
+    pub status: TxStatus,
+    /// The `split_id` of the split tx it was a part of
+    pub split_id: Option<i32>,
+    pub foreign_amount: Option<ForeignAmount>,
+    pub attachments: Vec<String>,
+}
+
+/// Copies a tx to the trash along with its exchange rate, note, status, split link, foreign
+/// amount and attachments. Must be called before the tx is deleted
+pub fn add_trashed_tx(id_num: i32, deleted_at: &str, conn: &Connection) -> Result<()> {
+    let attachments = serde_json::to_string(&get_tx_attachments(id_num, conn))
+        .map_err(|e| Error::ToSqlConversionFailure(Box::new(e)))?;
+
+    conn.execute(
+        "INSERT INTO trashed_txs (date, details, tx_method, amount, tx_type, tags, id_num, rate, note,
+        status, split_id, foreign_amount, foreign_currency, attachments, deleted_at)
+        SELECT tx_all.date, tx_all.details, tx_all.tx_method, tx_all.amount, tx_all.tx_type, tx_all.tags,
+        tx_all.id_num, tx_exchange_rates.rate, COALESCE(tx_notes.note, ''), COALESCE(tx_status.status, 'Pending'),
+        tx_splits.split_id, tx_foreign_amounts.amount, tx_foreign_amounts.currency, ?3, ?2 FROM tx_all
+        LEFT JOIN tx_exchange_rates ON tx_exchange_rates.id_num = tx_all.id_num
+        LEFT JOIN tx_notes ON tx_notes.id_num = tx_all.id_num
+        LEFT JOIN tx_status ON tx_status.id_num = tx_all.id_num
+        LEFT JOIN tx_splits ON tx_splits.id_num = tx_all.id_num
+        LEFT JOIN tx_foreign_amounts ON tx_foreign_amounts.id_num = tx_all.id_num
+        WHERE tx_all.id_num = ?1",
+        (id_num, deleted_at, attachments),
+    )?;
+    Ok(())
+}
+
+/// Returns every tx in the trash with the latest deleted one first. Each tx contains the
+/// deletion time, date, details, tx method, amount, tx type, tags and the id of the trashed tx
+/// in this order
+pub fn get_trashed_txs(conn: &Connection) -> Vec<Vec<String>> {
+    let mut query = conn
+        .prepare(
+            "SELECT deleted_at, date, details, tx_method, amount, tx_type, tags, trash_id FROM trashed_txs ORDER BY trash_id DESC",
+        )
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| {
+            let mut tx = Vec::new();
+            for index in 0..7 {
+                tx.push(row.get::<_, String>(index)?);
+            }
+            tx.push(row.get::<_, i32>(7)?.to_string());
+            Ok(tx)
+        })
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Returns a tx in the trash by its id
+pub fn get_trashed_tx(trash_id: i32, conn: &Connection) -> Result<Option<TrashedTx>> {
+    conn.query_row(
+        "SELECT date, details, tx_method, amount, tx_type, tags, id_num, rate, note, status, split_id,
+        foreign_amount, foreign_currency, attachments FROM trashed_txs WHERE trash_id = ?",
+        [trash_id],
+        |row| {
+            let mut tx = Vec::new();
+            for index in 0..6 {
+                tx.push(row.get::<_, String>(index)?);
+            }
+
+            let foreign_amount = match (row.get(11)?, row.get(12)?) {
+                (Some(amount), Some(currency)) => Some(ForeignAmount { amount, currency }),
+                _ => None,
+            };
+
+            let attachments = row.get::<_, String>(13)?;
+            let attachments = serde_json::from_str(&attachments)
+                .map_err(|e| Error::FromSqlConversionFailure(13, Type::Text, Box::new(e)))?;
+
+            Ok(TrashedTx {
+                tx,
+                id_num: row.get(6)?,
+                rate: row.get(7)?,
+                note: row.get(8)?,
+                status: TxStatus::from_str(&row.get::<_, String>(9)?),
+                split_id: row.get(10)?,
+                foreign_amount,
+                attachments,
+            })
+        },
+    )
+    .optional()
+}
+
+/// Removes a tx from the trash for good
+pub fn delete_trashed_tx(trash_id: i32, conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM trashed_txs WHERE trash_id = ?", [trash_id])?;
+    Ok(())
+}
+
+/// Removes every tx from the trash for good
+pub fn empty_trash(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM trashed_txs", [])?;
+    Ok(())
+}
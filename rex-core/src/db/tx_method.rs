@@ -0,0 +1,218 @@
+use rusqlite::{Connection, Result};
+use std::collections::HashMap;
+
+use crate::state::MethodType;
+use crate::utility::get_all_tx_methods;
+
+/// The tag of the txs that set the starting balance of a tx method
+pub const STARTING_BALANCE_TAG: &str = "Starting Balance";
+
+/// Returns the tx methods that are archived. Archived methods are hidden from the Add Tx page,
+/// the balance sections and the chart but their txs and balances are kept
+pub fn get_archived_tx_methods(conn: &Connection) -> Vec<String> {
+    let mut query = conn
+        .prepare("SELECT tx_method FROM tx_method_info WHERE archived = 1")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| row.get(0))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Saves whether the tx method is archived
+pub fn set_tx_method_archived(tx_method: &str, archived: bool, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tx_method_info (tx_method, archived) VALUES (?1, ?2)
+        ON CONFLICT(tx_method) DO UPDATE SET archived = ?2",
+        (tx_method, archived),
+    )?;
+    Ok(())
+}
+
+/// Returns the type of every tx method. Methods without a saved type are Cash
+pub fn get_tx_method_types(conn: &Connection) -> HashMap<String, MethodType> {
+    let mut query = conn
+        .prepare("SELECT tx_method, method_type FROM tx_method_info")
+        .expect("could not prepare statement");
+
+    let saved_types: HashMap<String, String> = query
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default();
+
+    get_all_tx_methods(conn)
+        .into_iter()
+        .map(|method| {
+            let method_type = saved_types
+                .get(&method)
+                .and_then(|method_type| MethodType::from_str(method_type))
+                .unwrap_or(MethodType::Cash);
+            (method, method_type)
+        })
+        .collect()
+}
+
+/// Saves the type of the tx method
+pub fn set_tx_method_type(
+    tx_method: &str,
+    method_type: MethodType,
+    conn: &Connection,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tx_method_info (tx_method, method_type) VALUES (?1, ?2)
+        ON CONFLICT(tx_method) DO UPDATE SET method_type = ?2",
+        (tx_method, method_type.to_str()),
+    )?;
+    Ok(())
+}
+
+/// Returns the currency of every tx method that has one. Methods without a currency use the
+/// base currency
+pub fn get_tx_method_currencies(conn: &Connection) -> HashMap<String, String> {
+    let mut query = conn
+        .prepare("SELECT tx_method, currency FROM tx_method_info WHERE currency != ''")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Saves the currency of the tx method. An empty currency makes it use the base currency
+pub fn set_tx_method_currency(tx_method: &str, currency: &str, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tx_method_info (tx_method, currency) VALUES (?1, ?2)
+        ON CONFLICT(tx_method) DO UPDATE SET currency = ?2",
+        (tx_method, currency),
+    )?;
+    Ok(())
+}
+
+/// Returns the hex color code of every tx method that has a color picked for it
+pub fn get_saved_tx_method_colors(conn: &Connection) -> HashMap<String, String> {
+    let mut query = conn
+        .prepare("SELECT tx_method, color FROM tx_method_info WHERE color != ''")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Saves the hex color code of the tx method. An empty color removes the picked color
+pub fn set_tx_method_color(tx_method: &str, color: &str, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tx_method_info (tx_method, color) VALUES (?1, ?2)
+        ON CONFLICT(tx_method) DO UPDATE SET color = ?2",
+        (tx_method, color),
+    )?;
+    Ok(())
+}
+
+/// Returns the credit limit of every tx method that has one
+pub fn get_credit_limits(conn: &Connection) -> HashMap<String, f64> {
+    let mut query = conn
+        .prepare("SELECT tx_method, credit_limit FROM tx_method_info WHERE credit_limit > 0")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Saves the credit limit of the tx method. A limit of 0 removes it
+pub fn set_credit_limit(tx_method: &str, credit_limit: f64, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tx_method_info (tx_method, credit_limit) VALUES (?1, ?2)
+        ON CONFLICT(tx_method) DO UPDATE SET credit_limit = ?2",
+        (tx_method, credit_limit),
+    )?;
+    Ok(())
+}
+
+/// Returns the group of every tx method that is in one
+pub fn get_tx_method_groups(conn: &Connection) -> HashMap<String, String> {
+    let mut query = conn
+        .prepare("SELECT tx_method, method_group FROM tx_method_info WHERE method_group != ''")
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Saves the group of the tx method. An empty group removes it from its group
+pub fn set_tx_method_group(tx_method: &str, group: &str, conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT INTO tx_method_info (tx_method, method_group) VALUES (?1, ?2)
+        ON CONFLICT(tx_method) DO UPDATE SET method_group = ?2",
+        (tx_method, group),
+    )?;
+    Ok(())
+}
+
+/// Returns the starting balance of the tx method. It is the total of the txs of the method tagged
+/// as the starting balance
+pub fn get_starting_balance(tx_method: &str, conn: &Connection) -> f64 {
+    let mut query = conn
+        .prepare("SELECT amount, tx_type, tags FROM tx_all WHERE tx_method = ?")
+        .expect("could not prepare statement");
+
+    let rows = query
+        .query_map([tx_method], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map(|rows| rows.flatten().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    rows.into_iter()
+        .filter(|(_, _, tags)| tags.split(", ").any(|tag| tag == STARTING_BALANCE_TAG))
+        .map(|(amount, tx_type, _)| {
+            let amount = amount.parse::<f64>().unwrap_or(0.0);
+            if tx_type == "Expense" {
+                -amount
+            } else {
+                amount
+            }
+        })
+        .sum()
+}
+
+/// Returns the date of the earliest tx of the tx method including the transfers it is part of.
+/// A tx method name never contains ` to ` so the start or the end of a transfer is an exact match
+pub fn get_first_tx_date(tx_method: &str, conn: &Connection) -> Option<String> {
+    conn.query_row(
+        "SELECT MIN(date) FROM tx_all WHERE tx_method = ?1
+        OR substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+        OR substr(tx_method, -length(?1) - 4) = ' to ' || ?1",
+        [tx_method],
+        |row| row.get(0),
+    )
+    .ok()
+    .flatten()
+}
+
+/// Returns the `id_num` of every tx of the tx method including the transfers it is part of
+pub fn get_tx_method_tx_ids(tx_method: &str, conn: &Connection) -> Vec<i32> {
+    let mut query = conn
+        .prepare(
+            "SELECT id_num FROM tx_all WHERE tx_method = ?1
+            OR substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+            OR substr(tx_method, -length(?1) - 4) = ' to ' || ?1 ORDER BY id_num",
+        )
+        .expect("could not prepare statement");
+
+    query
+        .query_map([tx_method], |row| row.get(0))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
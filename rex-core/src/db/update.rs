@@ -0,0 +1,509 @@
+use rusqlite::{Connection, Result, Savepoint};
+
+use crate::db::{
+    create_activities_table, create_activity_txs_table, create_attachments_table,
+    create_balances_table, create_changes_table, create_currency_tables,
+    create_exchange_rates_table, create_missing_indexes, create_notes_table,
+    create_scheduled_table, create_snapshots_table, create_splits_table, create_status_table,
+    create_tag_rules_table, create_trash_table, create_tx_indexes, create_tx_method_info_table,
+};
+use crate::utility::get_all_tx_methods;
+
+/// adds new tx methods as columns on `balance_all` and `changes_all` tables. Gets called after
+/// successful handling of 'J' from the app
+pub fn add_new_tx_methods(tx_methods: &[String], conn: &mut Connection) -> Result<()> {
+    // add a save point to reverse commits if failed
+    let sp = conn.savepoint()?;
+    add_tx_method_columns(tx_methods, &sp)?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// Adds the balance and changes columns of the tx methods inside the given savepoint
+pub fn add_tx_method_columns(tx_methods: &[String], sp: &Savepoint) -> Result<()> {
+    for i in tx_methods {
+        let query = format!(r#"ALTER TABLE balance_all ADD COLUMN "{i}" REAL DEFAULT 0.00"#);
+        sp.execute(&query, [])?;
+    }
+
+    for i in tx_methods {
+        let query = format!(r#"ALTER TABLE changes_all ADD COLUMN "{i}" TEXT DEFAULT 0.00"#);
+        sp.execute(&query, [])?;
+    }
+    Ok(())
+}
+
+/// Adds the tags column inside the database. Used when the old database without the tags column is detected
+pub fn add_tags_column(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    sp.execute("ALTER TABLE tx_all ADD tags TEXT DEFAULT Unknown;", [])?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// Migrates existing database's `balance_all` column's data type from TEXT to REAL
+pub fn update_balance_type(conn: &mut Connection) -> Result<()> {
+    let all_methods = get_all_tx_methods(conn);
+
+    let sp = conn.savepoint()?;
+    let old_last_balance = get_last_balance(&sp, &all_methods);
+
+    // rename table
+    let query = "ALTER TABLE balance_all RENAME TO balance_all_old";
+    sp.execute(query, [])?;
+
+    // create the new updated balance_all table
+    create_balances_table(&all_methods, &sp)?;
+
+    let columns = all_methods
+        .iter()
+        .map(|column_name| format!(r#""{column_name}""#))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let values = all_methods
+        .iter()
+        .map(|method| format!(r#"CAST("{method}" as REAL)"#))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    // insert everything from old table to the new balance_all
+    let query = format!(
+        "INSERT INTO balance_all (id_num, {columns}) SELECT id_num, {values} FROM balance_all_old"
+    );
+
+    sp.execute(&query, [])?;
+    sp.execute("DROP TABLE balance_all_old", [])?;
+    sp.execute(
+        "CREATE UNIQUE INDEX balance_all_id_num_IDX ON balance_all (id_num);",
+        [],
+    )?;
+
+    // fill up `balance_all` table with total year * 12 + 1 rows with 0 balance for all columns
+    let zero_values = vec!["0.00"; all_methods.len()];
+
+    let highlighted_tx_methods = all_methods
+        .iter()
+        .map(|method| format!("\"{method}\""))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let query = format!(
+        "INSERT INTO balance_all ({}) VALUES ({})",
+        highlighted_tx_methods,
+        zero_values.join(",")
+    );
+
+    // the old db had 49 rows. So add 144 more to match total year * 12 + 1 rows
+    for _a in 0..144 {
+        sp.execute(&query, [])?;
+    }
+
+    // swap the values from 49 row of the old table to the new table at 193
+    let new_values = old_last_balance
+        .iter()
+        .enumerate()
+        .map(|(index, data)| format!(r#""{}" = {data}"#, all_methods[index]))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let query = format!("UPDATE balance_all SET {new_values} WHERE id_num = 193",);
+    sp.execute(&query, [])?;
+
+    let new_values = all_methods
+        .iter()
+        .map(|data| format!(r#""{data}" = 0.00"#,))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let query = format!("UPDATE balance_all SET {new_values} WHERE id_num = 49",);
+
+    sp.execute(&query, [])?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// return the last balance from the db
+fn get_last_balance(sp: &Savepoint, all_methods: &Vec<String>) -> Vec<String> {
+    let mut query =
+        format!("SELECT {all_methods:?} FROM balance_all ORDER BY id_num DESC LIMIT 1",);
+    query = query.replace('[', "");
+    query = query.replace(']', "");
+
+    let final_balance = sp.query_row(&query, [], |row| {
+        let mut final_data: Vec<String> = Vec::new();
+        for i in 0..all_methods.len() {
+            let row_data: String = row.get(i).unwrap();
+            final_data.push(row_data.to_string());
+        }
+        Ok(final_data)
+    });
+    final_balance.unwrap()
+}
+
+/// Updates the DB with the new tx method name. The balance columns, the txs, the activity txs,
+/// the scheduled txs, the trashed txs and the balance snapshots are all renamed inside a single
+/// savepoint
+pub fn rename_column(old_name: &str, new_name: &str, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    let query = format!(r#"ALTER TABLE balance_all RENAME COLUMN "{old_name}" TO "{new_name}""#);
+    sp.execute(&query, [])?;
+
+    let query = format!(r#"ALTER TABLE changes_all RENAME COLUMN "{old_name}" TO "{new_name}""#);
+    sp.execute(&query, [])?;
+
+    for (table, id_column) in [
+        ("tx_all", "id_num"),
+        ("activity_txs", "insertion_id"),
+        ("scheduled_txs", "schedule_id"),
+        ("trashed_txs", "trash_id"),
+    ] {
+        rename_tx_method_values(table, id_column, old_name, new_name, &sp)?;
+    }
+
+    sp.execute(
+        "UPDATE balance_snapshots SET tx_method = ?1 WHERE tx_method = ?2",
+        [new_name, old_name],
+    )?;
+
+    sp.execute(
+        "UPDATE tx_method_info SET tx_method = ?1 WHERE tx_method = ?2",
+        [new_name, old_name],
+    )?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Merges the source tx method into the target inside a single savepoint. Transfers between the
+/// two are removed, every other tx, scheduled tx, trashed tx and activity tx of the source is
+/// moved to the target and the balances of both are added together before the source columns
+/// are dropped
+pub fn merge_tx_method_columns(source: &str, target: &str, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    let between = [
+        format!("{source} to {target}"),
+        format!("{target} to {source}"),
+    ];
+
+    // the balance of the two methods together does not change by moving money between them
+    sp.execute(
+        "DELETE FROM tx_all WHERE tx_method IN (?1, ?2)",
+        [&between[0], &between[1]],
+    )?;
+    for table in ["scheduled_txs", "trashed_txs"] {
+        sp.execute(
+            &format!("DELETE FROM {table} WHERE tx_method IN (?1, ?2)"),
+            [&between[0], &between[1]],
+        )?;
+    }
+
+    let query = format!(
+        r#"UPDATE changes_all SET "{target}" = "{source}" WHERE id_num IN (SELECT id_num FROM tx_all
+        WHERE tx_method = ?1 OR substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+        OR substr(tx_method, -length(?1) - 4) = ' to ' || ?1)"#
+    );
+    sp.execute(&query, [source])?;
+
+    let query = format!(r#"UPDATE balance_all SET "{target}" = round("{target}" + "{source}", 2)"#);
+    sp.execute(&query, [])?;
+
+    for table in ["balance_all", "changes_all"] {
+        sp.execute(
+            &format!(r#"ALTER TABLE {table} DROP COLUMN "{source}""#),
+            [],
+        )?;
+    }
+
+    for (table, id_column) in [
+        ("tx_all", "id_num"),
+        ("activity_txs", "insertion_id"),
+        ("scheduled_txs", "schedule_id"),
+        ("trashed_txs", "trash_id"),
+    ] {
+        rename_tx_method_values(table, id_column, source, target, &sp)?;
+    }
+
+    sp.execute(
+        "UPDATE balance_snapshots SET balance = round(balance + COALESCE((SELECT source.balance
+        FROM balance_snapshots source WHERE source.period_id = balance_snapshots.period_id
+        AND source.tx_method = ?1), 0), 2) WHERE tx_method = ?2",
+        [source, target],
+    )?;
+    sp.execute(
+        "DELETE FROM balance_snapshots WHERE tx_method = ?",
+        [source],
+    )?;
+
+    sp.execute("DELETE FROM tx_method_info WHERE tx_method = ?", [source])?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Removes the tx method columns and every saved data of the tx method that is not a tx.
+/// Its txs must be removed or moved before this
+pub fn remove_tx_method_columns(tx_method: &str, sp: &Connection) -> Result<()> {
+    for table in ["scheduled_txs", "trashed_txs"] {
+        sp.execute(
+            &format!(
+                "DELETE FROM {table} WHERE tx_method = ?1
+                OR substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+                OR substr(tx_method, -length(?1) - 4) = ' to ' || ?1"
+            ),
+            [tx_method],
+        )?;
+    }
+
+    for table in ["balance_all", "changes_all"] {
+        sp.execute(
+            &format!(r#"ALTER TABLE {table} DROP COLUMN "{tx_method}""#),
+            [],
+        )?;
+    }
+
+    sp.execute(
+        "DELETE FROM balance_snapshots WHERE tx_method = ?",
+        [tx_method],
+    )?;
+    sp.execute(
+        "DELETE FROM tx_method_info WHERE tx_method = ?",
+        [tx_method],
+    )?;
+    Ok(())
+}
+
+/// Renames the tx method on every row of the table that uses it. Transfer txs are saved as
+/// `From to To` so only the side matching the old name is replaced
+fn rename_tx_method_values(
+    table: &str,
+    id_column: &str,
+    old_name: &str,
+    new_name: &str,
+    sp: &Savepoint,
+) -> Result<()> {
+    let mut statement = sp.prepare(&format!(
+        "SELECT {id_column}, tx_method FROM {table} WHERE instr(tx_method, ?1) > 0"
+    ))?;
+
+    let rows = statement
+        .query_map([old_name], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    let query = format!("UPDATE {table} SET tx_method = ?1 WHERE {id_column} = ?2");
+
+    for (id, tx_method) in rows {
+        let renamed = tx_method
+            .split(" to ")
+            .map(|method| if method == old_name { new_name } else { method })
+            .collect::<Vec<&str>>()
+            .join(" to ");
+
+        if renamed != tx_method {
+            sp.execute(&query, (renamed, id))?;
+        }
+    }
+    Ok(())
+}
+
+/// repositions tx method positions in the db
+pub fn reposition_column(tx_methods: &[String], conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    let query = "ALTER TABLE balance_all RENAME TO balance_all_old";
+    sp.execute(query, [])?;
+
+    let query = "ALTER TABLE changes_all RENAME TO changes_all_old";
+    sp.execute(query, [])?;
+
+    create_balances_table(tx_methods, &sp)?;
+    create_changes_table(tx_methods, &sp)?;
+
+    let columns = tx_methods
+        .iter()
+        .map(|column_name| format!(r#""{column_name}""#,))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let query = format!(
+        "INSERT INTO balance_all (id_num, {columns}) SELECT id_num, {columns} FROM balance_all_old"
+    );
+    sp.execute(&query, [])?;
+
+    let query = format!(
+        "INSERT INTO changes_all (date, id_num, {columns}) SELECT date, id_num, {columns} FROM changes_all_old"
+    );
+    sp.execute(&query, [])?;
+
+    sp.execute("DROP TABLE balance_all_old", [])?;
+    sp.execute("DROP TABLE changes_all_old", [])?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+/// Create new tables to migrate to the new form of database to include activities
+pub fn migrate_to_activities(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_activities_table(&sp)?;
+    create_activity_txs_table(&sp)?;
+    create_missing_indexes(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+/// Creates the month end balance snapshot table for databases that were created before it existed
+pub fn migrate_to_snapshots(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_snapshots_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_notes(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_notes_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_status(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_status_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_splits(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_splits_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_attachments(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_attachments_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_currencies(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_currency_tables(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_scheduled(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_scheduled_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_tx_method_info(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_tx_method_info_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+/// Adds a column to `tx_method_info`. Used when the table was created before the column existed
+pub fn add_tx_method_info_column(
+    column: &str,
+    definition: &str,
+    conn: &mut Connection,
+) -> Result<()> {
+    let sp = conn.savepoint()?;
+    sp.execute(
+        &format!("ALTER TABLE tx_method_info ADD COLUMN {column} {definition}"),
+        [],
+    )?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// Adds a column to `trashed_txs`. Used when the table was created before the column existed
+pub fn add_trash_column(column: &str, definition: &str, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    sp.execute(
+        &format!("ALTER TABLE trashed_txs ADD COLUMN {column} {definition}"),
+        [],
+    )?;
+    sp.commit()?;
+    Ok(())
+}
+
+pub fn migrate_to_exchange_rates(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_exchange_rates_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_tag_rules(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_tag_rules_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+pub fn migrate_to_trash(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_trash_table(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
+
+/// Adds the tag, tx method and date indexes of the txs for databases that were created before
+/// they existed
+pub fn migrate_to_tx_indexes(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    create_tx_indexes(&sp)?;
+
+    sp.commit()?;
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+//! Events sent to the user's automation whenever something happens to the db. Each event is a
+//! single JSON line described by `docs/events.schema.json`, like
+//! `{"event":"tx_deleted","id_nums":[12],"profile":"Default","time":"2024-05-12T10:00:00+02:00"}`
+
+use chrono::Local;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::io::{Result, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use tracing::warn;
+
+use crate::db::{MONTHS, YEARS};
+use crate::utility::{get_last_tx, get_tx_id_num, Config};
+
+/// A tx as it is sent with the events
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct EventTx {
+    pub id: i32,
+    /// In the YYYY-MM-DD format
+    pub date: String,
+    pub details: String,
+    /// The tx method or `From to To` for a transfer
+    pub tx_method: String,
+    pub amount: f64,
+    pub tx_type: String,
+    pub tags: Vec<String>,
+}
+
+impl EventTx {
+    /// Creates the tx out of a `tx_all` row of date, details, tx method, amount, tx type, tags
+    /// and `id_num`
+    fn from_row(row: &[String]) -> Self {
+        EventTx {
+            id: row[6].parse().unwrap_or_default(),
+            date: row[0].clone(),
+            details: row[1].clone(),
+            tx_method: row[2].clone(),
+            amount: row[3].parse().unwrap_or_default(),
+            tx_type: row[4].clone(),
+            tags: row[5].split(", ").map(ToString::to_string).collect(),
+        }
+    }
+}
+
+/// Something that happened to the db
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RexEvent {
+    TxAdded {
+        tx: EventTx,
+    },
+    /// The txs were deleted or moved to the trash
+    TxDeleted {
+        id_nums: Vec<i32>,
+    },
+    /// The month end balance of the month was recorded. The month is in the YYYY-MM format
+    MonthClosed {
+        month: String,
+    },
+    /// The db was copied to the backup locations
+    BackupDone {
+        paths: Vec<String>,
+    },
+}
+
+impl RexEvent {
+    /// The tx with the given `id_num` was added
+    #[must_use]
+    pub fn tx_added(id_num: i32, conn: &Connection) -> Self {
+        RexEvent::TxAdded {
+            tx: EventTx::from_row(&get_tx_id_num(id_num, conn)),
+        }
+    }
+
+    /// The newest tx of the db was added
+    #[must_use]
+    pub fn last_tx_added(conn: &Connection) -> Self {
+        RexEvent::TxAdded {
+            tx: EventTx::from_row(&get_last_tx(conn)),
+        }
+    }
+
+    /// The month of the month end balance snapshot period was closed. Period 1 is January 2022
+    #[must_use]
+    pub fn month_closed(period_id: i32) -> Self {
+        let index = (period_id.max(1) - 1) as usize;
+        let month = index % MONTHS.len() + 1;
+        let year = YEARS[(index / MONTHS.len()).min(YEARS.len() - 1)];
+
+        RexEvent::MonthClosed {
+            month: format!("{year}-{month:02}"),
+        }
+    }
+
+    /// The name the event is sent with like `tx_added`
+    #[must_use]
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            RexEvent::TxAdded { .. } => "tx_added",
+            RexEvent::TxDeleted { .. } => "tx_deleted",
+            RexEvent::MonthClosed { .. } => "month_closed",
+            RexEvent::BackupDone { .. } => "backup_done",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EventMessage<'a> {
+    #[serde(flatten)]
+    event: &'a RexEvent,
+    profile: &'a str,
+    time: String,
+}
+
+/// Sends the events of a profile to the script and the unix socket set in the config. Does
+/// nothing if neither is set
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventHook {
+    script: String,
+    socket: String,
+    profile: String,
+}
+
+impl EventHook {
+    #[must_use]
+    pub fn new(config: &Config, profile: &str) -> Self {
+        EventHook {
+            script: config.event_hook_script.trim().to_string(),
+            socket: config.event_hook_socket.trim().to_string(),
+            profile: profile.to_string(),
+        }
+    }
+
+    /// Returns the JSON line the event is sent as, without the line break
+    #[must_use]
+    pub fn get_message(&self, event: &RexEvent) -> String {
+        let message = EventMessage {
+            event,
+            profile: &self.profile,
+            time: Local::now().to_rfc3339(),
+        };
+
+        serde_json::to_string(&message).unwrap_or_default()
+    }
+
+    /// Starts the script with the event on its stdin and writes the event to the socket. Neither
+    /// the script nor the write to its stdin is waited for. Both are tried even if the other one
+    /// fails and the first error is returned
+    pub fn emit(&self, event: &RexEvent) -> Result<()> {
+        if self.script.is_empty() && self.socket.is_empty() {
+            return Ok(());
+        }
+
+        let message = format!("{}\n", self.get_message(event));
+
+        let script_status = if self.script.is_empty() {
+            Ok(())
+        } else {
+            self.run_script(event, &message)
+        };
+
+        let socket_status = if self.socket.is_empty() {
+            Ok(())
+        } else {
+            self.send_to_socket(&message)
+        };
+
+        let status = script_status.and(socket_status);
+        if let Err(e) = &status {
+            warn!(
+                event = event.get_name(),
+                "Failed to send the event. Error: {e}"
+            );
+        }
+        status
+    }
+
+    fn run_script(&self, event: &RexEvent, message: &str) -> Result<()> {
+        // the output would end up on top of the interface
+        let mut child = Command::new(&self.script)
+            .env("REX_EVENT", event.get_name())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        // a long event may not fit in the pipe and the script may never read it so it is written
+        // from another thread. Stdin is closed before waiting so the script sees the end of it
+        let stdin = child.stdin.take();
+        let message = message.to_string();
+        let name = event.get_name();
+        thread::spawn(move || {
+            if let Some(mut stdin) = stdin {
+                if let Err(e) = stdin.write_all(message.as_bytes()) {
+                    warn!(event = name, "Failed to send the event. Error: {e}");
+                }
+            }
+            child.wait()
+        });
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn send_to_socket(&self, message: &str) -> Result<()> {
+        use std::os::unix::net::UnixStream;
+        use std::time::Duration;
+
+        let mut stream = UnixStream::connect(&self.socket)?;
+        stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+        stream.write_all(message.as_bytes())
+    }
+
+    #[cfg(not(unix))]
+    fn send_to_socket(&self, _message: &str) -> Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Unix sockets are not supported on this system",
+        ))
+    }
+}
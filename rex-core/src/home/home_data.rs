@@ -0,0 +1,784 @@
+use chrono::{Duration, Local, NaiveDate};
+use rusqlite::{Connection, Result as sqlResult};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::db::{get_pending_changes, get_tx_status, set_tx_status};
+use crate::home::HomeFilter;
+use crate::state::{DateType, TxStatus};
+use crate::state::{HomeSortingType, SortingDirection};
+use crate::tx_handler::trash_tx;
+use crate::utility::{
+    get_all_tx_methods, get_balance_before, get_changes_after, get_changes_window,
+    get_last_balances, get_last_time_balance, get_period_totals, get_sql_dates, get_tx_count,
+    get_txs_window, update_month_balance,
+};
+
+/// The number of txs that are fetched from the db at once for the Home table
+pub const TX_WINDOW_SIZE: usize = 100;
+/// How close the Home table selection can get to the final fetched tx before the next window is fetched
+pub const TX_LOAD_DISTANCE: usize = 10;
+
+/// This struct stores the transaction data, balance, changes and the id num
+/// Data storing format is:
+///
+/// `all_tx` : `[[date, details, tx_method, amount, tx_type, tags],]`
+///
+/// `all_balance`: `[["123.00", "123.00"],]`
+///
+/// `all_changes`: `[["↓123.00", "↑123.00"],]`
+///
+/// `all_id_num` : `["1", "2", "3",]`
+///
+/// `all_status` : `[Pending, Cleared, Reconciled,]`
+///
+/// `sorted_index` : `[2, 0, 1,]`
+///
+/// The data is always kept in the chronological order. `sorted_index` maps each
+/// shown table row to the chronological index so the balance, changes and id of a row
+/// stays the same no matter how the table is sorted. Txs that do not pass the Home filter
+/// are left out of `sorted_index`
+///
+/// Only the first `TX_WINDOW_SIZE` txs of the period are fetched at first. The rest are
+/// fetched window by window with `load_more` as the table selection gets closer to the end.
+/// The period is either a month, a single ISO week or a custom date range
+///
+/// Txs dated after today are future txs. They are always shown at the end of the table and the
+/// final balance leaves them out while the projected balance counts them
+pub struct TransactionData {
+    all_tx: Vec<Vec<String>>,
+    all_balance: Vec<Vec<String>>,
+    all_changes: Vec<Vec<String>>,
+    all_id_num: Vec<String>,
+    all_status: Vec<TxStatus>,
+    sorted_index: Vec<usize>,
+    filtered: Vec<bool>,
+    filter_balance: bool,
+    /// Whether the pending txs are left out of the balance and the totals
+    cleared_balance: bool,
+    /// How much the pending txs before the period changed each tx method, in the tx method order
+    pending_before: Vec<f64>,
+    /// The start and the end date of the period the txs are from
+    dates: (String, String),
+    /// The month and year index of the period. `None` if the period is a week or a custom range
+    month: Option<(usize, usize)>,
+    total_txs: usize,
+    last_balance: HashMap<String, f64>,
+    /// The txs after this date are future txs
+    today: NaiveDate,
+    /// How much the future txs of every period change each tx method
+    future_changes: HashMap<String, f64>,
+}
+
+impl TransactionData {
+    /// Calls the db to fetch the first window of transaction data, transaction changes,
+    /// balances and id numbers from the given month and year index
+    pub fn new(month: usize, year: usize, conn: &Connection) -> Self {
+        let tx_methods = get_all_tx_methods(conn);
+        let dates = get_sql_dates(month, year, &DateType::Monthly);
+        let last_balance = get_last_time_balance(month, year, &tx_methods, conn);
+
+        TransactionData::new_period(dates, Some((month, year)), last_balance, conn)
+    }
+
+    /// Works the same way as `new` but fetches the txs of the ISO week that starts at the given date
+    pub fn new_weekly(week_start: NaiveDate, conn: &Connection) -> Self {
+        TransactionData::new_range(week_start, week_start + Duration::days(6), conn)
+    }
+
+    /// Works the same way as `new` but fetches the txs between the two dates. Both dates are included
+    pub fn new_range(from: NaiveDate, to: NaiveDate, conn: &Connection) -> Self {
+        let tx_methods = get_all_tx_methods(conn);
+        let dates = (from.to_string(), to.to_string());
+        let last_balance = get_balance_before(from, &tx_methods, conn);
+
+        TransactionData::new_period(dates, None, last_balance, conn)
+    }
+
+    fn new_period(
+        dates: (String, String),
+        month: Option<(usize, usize)>,
+        last_balance: HashMap<String, f64>,
+        conn: &Connection,
+    ) -> Self {
+        let today = Local::now().date_naive();
+        let mut tx_data = TransactionData {
+            all_tx: Vec::new(),
+            all_balance: Vec::new(),
+            all_changes: Vec::new(),
+            all_id_num: Vec::new(),
+            all_status: Vec::new(),
+            sorted_index: Vec::new(),
+            filtered: Vec::new(),
+            filter_balance: false,
+            cleared_balance: false,
+            pending_before: Vec::new(),
+            total_txs: get_tx_count(&dates, conn),
+            dates,
+            month,
+            last_balance,
+            today,
+            future_changes: get_changes_after(today, conn),
+        };
+        tx_data.load_more(conn);
+        tx_data
+    }
+
+    pub fn new_search(all_tx: Vec<Vec<String>>, all_id_num: Vec<String>) -> Self {
+        let total_txs = all_tx.len();
+        let sorted_index = (0..total_txs).collect();
+        let filtered = vec![true; total_txs];
+        TransactionData {
+            all_tx,
+            all_balance: Vec::new(),
+            all_changes: Vec::new(),
+            all_id_num,
+            all_status: vec![TxStatus::Pending; total_txs],
+            sorted_index,
+            filtered,
+            filter_balance: false,
+            cleared_balance: false,
+            pending_before: Vec::new(),
+            dates: (String::new(), String::new()),
+            month: None,
+            total_txs,
+            last_balance: HashMap::new(),
+            today: Local::now().date_naive(),
+            future_changes: HashMap::new(),
+        }
+    }
+
+    /// Fetches the next window of txs of the period. Returns false if every tx was already fetched.
+    /// The new txs are added at the end of the shown txs so sorting or filtering should be done
+    /// after fetching everything
+    pub fn load_more(&mut self, conn: &Connection) -> bool {
+        if self.is_fully_loaded() {
+            return false;
+        }
+
+        let offset = self.all_tx.len();
+
+        let (txs, balances, id_nums) = get_txs_window(
+            conn,
+            &self.dates,
+            offset,
+            Some(TX_WINDOW_SIZE),
+            &mut self.last_balance,
+        );
+        let changes = get_changes_window(&self.dates, offset, Some(TX_WINDOW_SIZE), conn);
+
+        // Ran out of txs earlier than expected, the count is outdated
+        if txs.is_empty() {
+            self.total_txs = self.all_tx.len();
+            return false;
+        }
+
+        self.sorted_index.extend(offset..offset + txs.len());
+        self.filtered.extend(vec![true; txs.len()]);
+        self.all_tx.extend(txs);
+        self.all_balance.extend(balances);
+        self.all_changes.extend(changes);
+        self.all_status.extend(
+            id_nums
+                .iter()
+                .map(|id_num| get_tx_status(id_num.parse().unwrap(), conn)),
+        );
+        self.all_id_num.extend(id_nums);
+
+        // pushes the final balance of the month to the db on the balance_all table
+        if let Some((month, year)) = self.month {
+            if self.is_fully_loaded() {
+                if let Some(final_balance) = self.all_balance.last() {
+                    update_month_balance(month, year, final_balance, conn);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Fetches every remaining tx of the period
+    pub fn load_all(&mut self, conn: &Connection) {
+        while self.load_more(conn) {}
+    }
+
+    /// Returns the number of txs that were fetched so far
+    pub fn get_txs_len(&self) -> usize {
+        self.all_tx.len()
+    }
+
+    /// Whether every tx of the period has been fetched
+    pub fn is_fully_loaded(&self) -> bool {
+        self.all_tx.len() >= self.total_txs
+    }
+
+    /// Sorts the shown order of the txs by the given column and direction. Txs with the same
+    /// value stay in the chronological order
+    pub fn sort_txs(&mut self, sort_type: &HomeSortingType, sort_direction: &SortingDirection) {
+        let column = sort_type.get_column();
+        let mut sorted_index = (0..self.all_tx.len())
+            .filter(|index| self.filtered[*index])
+            .collect::<Vec<usize>>();
+
+        // the data is already in the chronological order so the date sorting only goes by the index
+        sorted_index.sort_by(|a, b| {
+            let ordering = match sort_type {
+                HomeSortingType::ByDate => a.cmp(b),
+                HomeSortingType::ByAmount => {
+                    let first = self.all_tx[*a][column].parse::<f64>().unwrap();
+                    let second = self.all_tx[*b][column].parse::<f64>().unwrap();
+                    first.total_cmp(&second)
+                }
+                _ => self.all_tx[*a][column]
+                    .to_lowercase()
+                    .cmp(&self.all_tx[*b][column].to_lowercase()),
+            };
+
+            match sort_direction {
+                SortingDirection::Ascending => ordering,
+                SortingDirection::Descending => ordering.reverse(),
+            }
+        });
+
+        // the future txs stay at the end no matter the sorting
+        sorted_index.sort_by_key(|index| self.is_future(*index));
+
+        self.sorted_index = sorted_index;
+    }
+
+    /// Whether the tx of the chronological index is dated after today
+    fn is_future(&self, index: usize) -> bool {
+        NaiveDate::parse_from_str(&self.all_tx[index][0], "%Y-%m-%d")
+            .is_ok_and(|date| date > self.today)
+    }
+
+    /// Returns the table indexes of the shown future txs
+    pub fn get_future_positions(&self) -> HashSet<usize> {
+        (0..self.sorted_index.len())
+            .filter(|index| self.is_future(self.get_index(*index)))
+            .collect()
+    }
+
+    /// Marks the txs that pass the filter. The rest are removed from the shown txs
+    /// once they get sorted again
+    pub fn filter_txs(&mut self, home_filter: &HomeFilter) {
+        self.filtered = self
+            .all_tx
+            .iter()
+            .map(|tx| home_filter.is_match(tx))
+            .collect();
+        self.filter_balance = home_filter.is_active() && home_filter.filter_balance;
+    }
+
+    /// Sets whether the pending txs are left out of the balance and the totals. The pending txs
+    /// before the period are counted from the db so the txs of the period should be fully fetched
+    pub fn set_cleared_balance(&mut self, cleared_balance: bool, conn: &Connection) {
+        self.cleared_balance = cleared_balance;
+        self.pending_before = if cleared_balance {
+            let pending_changes = get_pending_changes(Some(&self.dates.0), conn);
+            get_all_tx_methods(conn)
+                .iter()
+                .map(|method| pending_changes.get(method).copied().unwrap_or(0.0))
+                .collect()
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Whether the tx of the chronological index is counted for the balance and the totals
+    fn is_counted(&self, index: usize) -> bool {
+        (!self.filter_balance || self.filtered[index])
+            && (!self.cleared_balance || self.all_status[index] != TxStatus::Pending)
+    }
+
+    /// Returns the changes of the txs that are left out of the balance before the period
+    fn get_hidden_before(&self, method_count: usize) -> Vec<f64> {
+        if self.pending_before.len() == method_count {
+            self.pending_before.clone()
+        } else {
+            vec![0.0; method_count]
+        }
+    }
+
+    /// Returns the chronological index of the given table index
+    fn get_index(&self, index: usize) -> usize {
+        self.sorted_index[index]
+    }
+
+    /// Returns the table indexes of the shown txs with the given `id_num`s
+    pub fn get_tx_positions(&self, id_nums: &HashSet<i32>) -> HashSet<usize> {
+        (0..self.sorted_index.len())
+            .filter(|index| id_nums.contains(&self.get_id_num(*index)))
+            .collect()
+    }
+
+    /// Returns the table index of the chronologically first shown tx that is on or after the given date
+    pub fn get_date_position(&self, date: NaiveDate) -> Option<usize> {
+        let target_index = (0..self.all_tx.len())
+            .filter(|index| self.filtered[*index])
+            .find(|index| {
+                NaiveDate::parse_from_str(&self.all_tx[*index][0], "%Y-%m-%d")
+                    .is_ok_and(|tx_date| tx_date >= date)
+            })?;
+
+        self.sorted_index
+            .iter()
+            .position(|index| *index == target_index)
+    }
+
+    /// Returns the table index of the tx with the given `id_num`
+    pub fn get_tx_position(&self, id_num: i32) -> Option<usize> {
+        (0..self.sorted_index.len()).position(|index| self.get_id_num(index) == id_num)
+    }
+
+    /// returns all the Transaction data in the order they are shown in the Home table
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.sorted_index
+            .iter()
+            .map(|index| self.all_tx[*index].clone())
+            .collect()
+    }
+
+    pub fn is_tx_empty(&self) -> bool {
+        self.sorted_index.is_empty()
+    }
+
+    /// Returns the total number of txs of the period including the filtered and the not fetched ones
+    pub fn get_total_txs(&self) -> usize {
+        self.total_txs
+    }
+
+    /// returns all the balance data for the given index. Index is of the
+    /// Home Table's selected index
+    pub fn get_balance(&self, index: usize) -> Vec<String> {
+        let mut balance_data = vec!["Balance".to_string()];
+        let mut total_balance = 0.0;
+        let index = self.get_index(index);
+
+        // the changes of the filtered out txs till this tx get removed from the balance
+        let mut hidden_changes = self.get_hidden_before(self.all_balance[index].len());
+        for hidden_index in (0..=index).filter(|i| !self.is_counted(*i)) {
+            self.add_changes_to(hidden_index, &mut hidden_changes);
+        }
+
+        for (i, hidden_change) in self.all_balance[index].iter().zip(hidden_changes) {
+            let num_balance = i.parse::<f64>().unwrap() - hidden_change;
+            total_balance += num_balance;
+            balance_data.push(format!("{num_balance:.2}"));
+        }
+        balance_data.push(format!("{total_balance:.2}"));
+        balance_data
+    }
+
+    /// Returns the balance after each tx in the order they are shown in the Home table.
+    /// The column is the index of the tx method. Any column after the tx methods returns the total balance
+    pub fn get_running_balances(&self, column: usize) -> Vec<String> {
+        let mut hidden_changes =
+            self.get_hidden_before(self.all_balance.first().map_or(0, Vec::len));
+        let mut running_balances = Vec::with_capacity(self.all_balance.len());
+
+        // goes through the txs chronologically once so the hidden changes don't get recounted for each tx
+        for (index, balance) in self.all_balance.iter().enumerate() {
+            if !self.is_counted(index) {
+                self.add_changes_to(index, &mut hidden_changes);
+            }
+
+            let balance = balance
+                .iter()
+                .zip(&hidden_changes)
+                .map(|(value, hidden_change)| value.parse::<f64>().unwrap() - hidden_change)
+                .collect::<Vec<f64>>();
+
+            let value = balance
+                .get(column)
+                .copied()
+                .unwrap_or_else(|| balance.iter().sum());
+            running_balances.push(format!("{value:.2}"));
+        }
+
+        self.sorted_index
+            .iter()
+            .map(|index| running_balances[*index].clone())
+            .collect()
+    }
+
+    /// Adds the changes of the tx of the chronological index to the given amounts of each tx method
+    fn add_changes_to(&self, index: usize, amounts: &mut [f64]) {
+        for (method_index, change) in self.all_changes[index].iter().enumerate() {
+            let amount = change.replace(['↑', '↓'], "").parse::<f64>().unwrap();
+            if change.starts_with('↑') {
+                amounts[method_index] += amount;
+            } else if change.starts_with('↓') {
+                amounts[method_index] -= amount;
+            }
+        }
+    }
+
+    /// returns the absolute final balance that is found after all transactions were counted for.
+    /// The value is saved in the DB at the final row. The future txs are left out alongside
+    /// the pending txs with the cleared balance
+    pub fn get_last_balance(&self, conn: &Connection) -> Vec<String> {
+        // the pending future txs are already left out with the future txs
+        let tomorrow = (self.today + Duration::days(1)).to_string();
+        self.get_final_balance(Some(&tomorrow), &self.future_changes, conn)
+    }
+
+    /// Returns the final balance with the future txs counted. `None` if there are no future txs
+    pub fn get_projected_balance(&self, conn: &Connection) -> Option<Vec<String>> {
+        if self.future_changes.is_empty() {
+            return None;
+        }
+        Some(self.get_final_balance(None, &HashMap::new(), conn))
+    }
+
+    /// Returns the final balance from the db without the given changes. The pending txs before
+    /// the date are also removed with the cleared balance
+    fn get_final_balance(
+        &self,
+        pending_before: Option<&str>,
+        hidden_changes: &HashMap<String, f64>,
+        conn: &Connection,
+    ) -> Vec<String> {
+        let mut balance_data = vec!["Balance".to_string()];
+        let db_data = get_last_balances(conn);
+        let pending_changes = if self.cleared_balance {
+            get_pending_changes(pending_before, conn)
+        } else {
+            HashMap::new()
+        };
+        let mut total_balance = 0.0;
+        for (i, method) in db_data.iter().zip(get_all_tx_methods(conn)) {
+            let num_balance = i.parse::<f64>().unwrap()
+                - pending_changes.get(&method).copied().unwrap_or(0.0)
+                - hidden_changes.get(&method).copied().unwrap_or(0.0);
+            total_balance += num_balance;
+            balance_data.push(format!("{num_balance:.2}",));
+        }
+        balance_data.push(format!("{total_balance:.2}",));
+        balance_data
+    }
+
+    /// returns all the changes data for the given index. Index is of the
+    /// Home Table's selected index
+    pub fn get_changes(&self, index: usize) -> Vec<String> {
+        let mut changes_data = vec!["Changes".to_string()];
+        for i in &self.all_changes[self.get_index(index)] {
+            changes_data.push(i.to_string());
+        }
+        changes_data
+    }
+
+    /// Returns the status of the tx of the given index
+    pub fn get_status(&self, index: usize) -> TxStatus {
+        self.all_status[self.get_index(index)]
+    }
+
+    /// Returns the status of every tx in the order they are shown in the Home table
+    pub fn get_statuses(&self) -> Vec<TxStatus> {
+        self.sorted_index
+            .iter()
+            .map(|index| self.all_status[*index])
+            .collect()
+    }
+
+    /// Returns the `id_num` of every tx in the order they are shown in the Home table
+    pub fn get_id_nums(&self) -> Vec<i32> {
+        (0..self.sorted_index.len())
+            .map(|index| self.get_id_num(index))
+            .collect()
+    }
+
+    /// Saves the new status of the tx of the given index to the db
+    pub fn set_status(
+        &mut self,
+        index: usize,
+        status: TxStatus,
+        conn: &Connection,
+    ) -> sqlResult<()> {
+        set_tx_status(self.get_id_num(index), status, conn)?;
+        let index = self.get_index(index);
+        self.all_status[index] = status;
+        Ok(())
+    }
+
+    /// Returns the `id_num` of the tx of the given index
+    pub fn get_id_num(&self, index: usize) -> i32 {
+        self.all_id_num[self.get_index(index)]
+            .parse::<i32>()
+            .unwrap()
+            .to_owned()
+    }
+
+    /// gets the ID Number of the selected table row and moves the transaction to the trash
+    pub fn del_tx(&self, index: usize, conn: &mut Connection) -> sqlResult<()> {
+        let target_id = self.get_id_num(index);
+        trash_tx(target_id, conn)
+    }
+
+    /// returns total incomes for the selected period by going through all the tx saved in the struct
+    // Computes the total income and returns it as a vector of strings.
+    pub fn get_total_income(&self, current_index: Option<usize>, conn: &Connection) -> Vec<String> {
+        // the txs that are not fetched yet are counted straight from the db
+        if current_index.is_none() && !self.is_fully_loaded() {
+            return self.get_period_total("Income", conn);
+        }
+
+        // Initialize the output vector with the title "Income".
+        let mut final_income = vec!["Income".to_string()];
+        let mut income_data = HashMap::new();
+
+        // Get all transaction methods from the database and set 0 as the default value
+        let all_tx_methods = get_all_tx_methods(conn);
+        for method in &all_tx_methods {
+            income_data.insert(method, 0.0);
+        }
+
+        // Compute the stopping index based on the current index, if present.
+        let mut stopping_index = -1;
+        if let Some(index) = current_index {
+            stopping_index = self.get_index(index) as i32;
+        }
+
+        // Iterate over all transactions and accumulate the total income.
+        let mut total_income = 0.0_f64;
+        for (tx_index, tx) in self.all_tx.iter().enumerate() {
+            let tx_type = &tx[4];
+
+            if tx_type == "Income" && self.is_counted(tx_index) {
+                let method = &tx[2];
+                let amount = tx[3].parse::<f64>().unwrap();
+                total_income += amount;
+                *income_data.get_mut(method).unwrap() += amount;
+            }
+
+            if stopping_index == 0 {
+                // We have reached the stopping index, so exit the loop early.
+                break;
+            }
+            // Decrement the stopping index and continue with the loop.
+            stopping_index -= 1;
+        }
+
+        for i in &all_tx_methods {
+            final_income.push(format!("{:.2}", income_data[i]));
+        }
+
+        // Add the computed total income to the output vector.
+        final_income.push(format!("{total_income:.2}"));
+        final_income
+    }
+
+    /// returns total expenses for the selected period by going through all the tx saved in the struct
+    // Computes the total expense and returns it as a vector of strings.
+    pub fn get_total_expense(
+        &self,
+        current_index: Option<usize>,
+        conn: &Connection,
+    ) -> Vec<String> {
+        // the txs that are not fetched yet are counted straight from the db
+        if current_index.is_none() && !self.is_fully_loaded() {
+            return self.get_period_total("Expense", conn);
+        }
+
+        // Initialize the output vector with the title "Expense".
+        let mut final_expense = vec!["Expense".to_string()];
+        let mut expense_data = HashMap::new();
+
+        // Get all transaction methods from the database and set 0 as the default value
+        let all_tx_methods = get_all_tx_methods(conn);
+        for method in &all_tx_methods {
+            expense_data.insert(method, 0.0);
+        }
+
+        // Compute the stopping index based on the current index, if present.
+        let mut stopping_index = -1;
+        if let Some(index) = current_index {
+            stopping_index = self.get_index(index) as i32;
+        }
+
+        // Iterate over all transactions and accumulate the total expense.
+        let mut total_expense = 0.0_f64;
+        for (tx_index, tx) in self.all_tx.iter().enumerate() {
+            let tx_type = &tx[4];
+
+            if tx_type == "Expense" && self.is_counted(tx_index) {
+                let method = &tx[2];
+                let amount = tx[3].parse::<f64>().unwrap();
+                total_expense += amount;
+                *expense_data.get_mut(method).unwrap() += amount;
+            }
+
+            if stopping_index == 0 {
+                // We have reached the stopping index, so exit the loop early.
+                break;
+            }
+            // Decrement the stopping index and continue with the loop.
+            stopping_index -= 1;
+        }
+
+        for i in &all_tx_methods {
+            final_expense.push(format!("{:.2}", expense_data[i]));
+        }
+
+        // Add the computed total expense to the output vector.
+        final_expense.push(format!("{total_expense:.2}"));
+        final_expense
+    }
+
+    /// Returns the total amount of the given tx type of the whole period from the db
+    fn get_period_total(&self, tx_type: &str, conn: &Connection) -> Vec<String> {
+        let period_totals = get_period_totals(&self.dates, tx_type, conn);
+
+        let mut final_total = vec![tx_type.to_string()];
+        let mut total = 0.0_f64;
+
+        for method in get_all_tx_methods(conn) {
+            let amount = period_totals.get(&method).copied().unwrap_or(0.0);
+            total += amount;
+            final_total.push(format!("{amount:.2}"));
+        }
+
+        final_total.push(format!("{total:.2}"));
+        final_total
+    }
+
+    pub fn get_tx(&self, index: usize) -> &Vec<String> {
+        &self.all_tx[self.get_index(index)]
+    }
+
+    /// Returns total expense accumulated till the current scrolling table index on the ongoing date
+    pub fn get_daily_expense(
+        &self,
+        current_index: Option<usize>,
+        conn: &Connection,
+    ) -> Vec<String> {
+        let all_tx_methods = get_all_tx_methods(conn);
+        let mut final_expense: Vec<String> = vec!["Daily Expense".to_string()];
+
+        let Some(mut index) = current_index else {
+            // Inclusive range to include the extra Total column value
+            for _ in 0..=all_tx_methods.len() {
+                final_expense.push("0.00".to_string());
+            }
+            return final_expense;
+        };
+        index = self.get_index(index);
+
+        let mut expense_data = HashMap::new();
+        let mut total_expense = 0.0_f64;
+
+        // Get all transaction methods, set 0 as the default value
+        for method in &all_tx_methods {
+            expense_data.insert(method, 0.0);
+        }
+
+        let ongoing_date = self.all_tx[index][0].clone();
+
+        // Keep reducing the ongoing index till 0 is hit or the date is different
+        loop {
+            let target_tx = &self.all_tx[index];
+
+            let tx_type = &target_tx[4];
+
+            if tx_type == "Expense" && self.is_counted(index) {
+                let date = &target_tx[0];
+                let amount = &target_tx[3];
+                let tx_method = &target_tx[2];
+
+                if date != &ongoing_date {
+                    break;
+                }
+
+                let parsed_amount = amount.parse::<f64>().unwrap();
+                total_expense += parsed_amount;
+                *expense_data.get_mut(tx_method).unwrap() += parsed_amount;
+            }
+
+            if index == 0 {
+                break;
+            }
+
+            index -= 1;
+        }
+
+        for i in &all_tx_methods {
+            final_expense.push(format!("{:.2}", expense_data[i]));
+        }
+
+        // Add the computed total expense to the output vector.
+        final_expense.push(format!("{total_expense:.2}"));
+        final_expense
+    }
+
+    /// Returns total income accumulated till the current scrolling table index on the ongoing date
+    pub fn get_daily_income(&self, current_index: Option<usize>, conn: &Connection) -> Vec<String> {
+        let all_tx_methods = get_all_tx_methods(conn);
+        let mut final_income: Vec<String> = vec!["Daily Income".to_string()];
+
+        let Some(mut index) = current_index else {
+            for _ in 0..=all_tx_methods.len() {
+                final_income.push("0.00".to_string());
+            }
+            return final_income;
+        };
+        index = self.get_index(index);
+
+        let mut income_data = HashMap::new();
+        let mut total_income = 0.0_f64;
+
+        // Get all transaction methods and set 0 as the default value
+        for method in &all_tx_methods {
+            income_data.insert(method, 0.0);
+        }
+
+        let ongoing_date = self.all_tx[index][0].clone();
+
+        // Keep reducing the ongoing index till 0 is hit or the date is different
+        loop {
+            let target_tx = &self.all_tx[index];
+
+            let tx_type = &target_tx[4];
+
+            if tx_type == "Income" && self.is_counted(index) {
+                let date = &target_tx[0];
+                let amount = &target_tx[3];
+                let tx_method = &target_tx[2];
+                if date != &ongoing_date {
+                    break;
+                }
+
+                let parsed_amount = amount.parse::<f64>().unwrap();
+                total_income += parsed_amount;
+                *income_data.get_mut(tx_method).unwrap() += parsed_amount;
+            }
+
+            if index == 0 {
+                break;
+            }
+            index -= 1;
+        }
+
+        for i in &all_tx_methods {
+            final_income.push(format!("{:.2}", income_data[i]));
+        }
+
+        // Add the computed total expense to the output vector.
+        final_income.push(format!("{total_income:.2}"));
+        final_income
+    }
+}
+
+/// Splits the table rows into the ranges of the txs that are on the same day. Only the consecutive
+/// rows are grouped together so the rows should be sorted by date
+pub fn get_day_groups(items: &[Vec<String>]) -> Vec<Range<usize>> {
+    let mut groups: Vec<Range<usize>> = Vec::new();
+
+    for (index, item) in items.iter().enumerate() {
+        match groups.last_mut() {
+            Some(group) if items[group.start][0] == item[0] => group.end = index + 1,
+            _ => groups.push(index..index + 1),
+        }
+    }
+
+    groups
+}
@@ -0,0 +1,129 @@
+use rusqlite::Connection;
+
+use crate::state::IndexedData;
+use crate::utility::get_all_tags;
+
+/// The last value of the tag filter. Lets only the txs without a tag or tagged as Unknown through
+pub const UNTAGGED_FILTER: &str = "Untagged";
+
+/// Stores the filters of the Home table. The first value of each filter is `All`
+/// which lets every tx through
+pub struct HomeFilter {
+    pub tags: IndexedData,
+    pub tx_methods: IndexedData,
+    pub tx_types: IndexedData,
+    /// Whether the filter bar is visible. Hiding it removes every filter
+    pub shown: bool,
+    /// Whether the balance and the income/expense rows only count the filtered txs
+    pub filter_balance: bool,
+    /// Only txs with the text in the details or the tags pass. Works without the filter bar
+    pub text: String,
+    /// Whether the key presses are going to the text filter
+    pub typing: bool,
+}
+
+impl HomeFilter {
+    pub fn new(conn: &Connection) -> Self {
+        let mut home_filter = HomeFilter {
+            tags: IndexedData {
+                titles: Vec::new(),
+                index: 0,
+            },
+            tx_methods: IndexedData::new_summary_tx_methods(conn),
+            tx_types: IndexedData {
+                titles: ["All", "Income", "Expense", "Transfer"]
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                index: 0,
+            },
+            shown: false,
+            filter_balance: false,
+            text: String::new(),
+            typing: false,
+        };
+        home_filter.reload(conn);
+        home_filter
+    }
+
+    /// Fetches the tags and the tx methods again and removes all filters.
+    /// The tags are followed by the untagged filter
+    pub fn reload(&mut self, conn: &Connection) {
+        let mut tags = vec!["All".to_string()];
+        tags.extend(
+            get_all_tags(conn)
+                .into_iter()
+                .filter(|tag| !tag.is_empty() && tag != "Unknown"),
+        );
+        tags.push(UNTAGGED_FILTER.to_string());
+
+        self.tags = IndexedData {
+            titles: tags,
+            index: 0,
+        };
+        self.tx_methods = IndexedData::new_summary_tx_methods(conn);
+        self.tx_types.index = 0;
+    }
+
+    /// Returns true if at least one filter is not on `All` or there is a text to filter with
+    pub fn is_active(&self) -> bool {
+        !self.text.is_empty()
+            || (self.shown
+                && (self.tags.index != 0 || self.tx_methods.index != 0 || self.tx_types.index != 0))
+    }
+
+    /// Checks whether the given tx passes the text filter and all the selected filters.
+    /// A transfer passes the tx method filter if either side of it is the method
+    pub fn is_match(&self, tx: &[String]) -> bool {
+        if !self.text.is_empty() {
+            let text = self.text.to_lowercase();
+            if !tx[1].to_lowercase().contains(&text) && !tx[5].to_lowercase().contains(&text) {
+                return false;
+            }
+        }
+
+        if !self.shown {
+            return true;
+        }
+
+        if let Some(tag) = get_selected(&self.tags) {
+            if self.tags.index == self.tags.titles.len() - 1 {
+                if !is_untagged(&tx[5]) {
+                    return false;
+                }
+            } else if !tx[5].split(',').any(|tx_tag| tx_tag.trim() == tag) {
+                return false;
+            }
+        }
+
+        if let Some(method) = get_selected(&self.tx_methods) {
+            if !tx[2].split(" to ").any(|tx_method| tx_method == method) {
+                return false;
+            }
+        }
+
+        if let Some(tx_type) = get_selected(&self.tx_types) {
+            if tx[4] != tx_type {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Returns true if the tags have no tag other than Unknown
+fn is_untagged(tags: &str) -> bool {
+    tags.split(',')
+        .map(str::trim)
+        .all(|tag| tag.is_empty() || tag == "Unknown")
+}
+
+/// Returns the selected value of the filter or `None` if it's on `All`
+fn get_selected(data: &IndexedData) -> Option<&str> {
+    if data.index == 0 {
+        None
+    } else {
+        Some(data.titles[data.index].as_str())
+    }
+}
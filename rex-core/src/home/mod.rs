@@ -0,0 +1,5 @@
+mod home_data;
+mod home_filter;
+
+pub use home_data::{get_day_groups, TransactionData, TX_LOAD_DISTANCE, TX_WINDOW_SIZE};
+pub use home_filter::{HomeFilter, UNTAGGED_FILTER};
@@ -0,0 +1,53 @@
+//! The data side of Rex without any terminal UI.
+//!
+//! Everything Rex does with its SQLite database lives here so other tools can work with the same
+//! database the TUI uses:
+//!
+//! - [`db`] creates, migrates, checks, dumps and loads the database along with the tables for
+//!   notes, attachments, splits, schedules, tag rules, the trash and the reports
+//! - [`tx_handler`] adds, edits, deletes and moves txs and tx methods. [`tx_handler::TxData`]
+//!   verifies user input before anything gets saved
+//! - [`home`], [`summary`], [`chart`] and [`activity`] compute what the pages show, like the
+//!   balances after every tx, the income and expense stats and the chart lines
+//! - [`events`] tells the user's scripts about added and deleted txs, closed months and backups
+//! - [`sync_handler`] syncs an encrypted copy of the database with a WebDAV server
+//! - [`api`] answers the token protected JSON requests of `rex serve` so other tools can list
+//!   and add txs
+//! - [`state`] has the selection, sorting and type enums the data and the pages share
+//! - [`utility`] holds the config, the profiles and the queries the rest are built on
+//! - [`outputs`] lists the errors and verification results every part returns
+//!
+//! Functions that change more than one row take a `&mut Connection` and do the work inside a
+//! savepoint so a failure leaves the database as it was. The changes and the failures are
+//! logged with `tracing` but no subscriber is set up here, the program using the crate decides
+//! where the logs go.
+//!
+//! ```no_run
+//! use rex_core::db::create_db;
+//! use rex_core::tx_handler::add_tx;
+//! use rex_core::utility::get_last_balances;
+//! use rusqlite::Connection;
+//!
+//! let mut conn = Connection::open("data.sqlite").unwrap();
+//! create_db(&["Bank".to_string(), "Cash".to_string()], &mut conn).unwrap();
+//!
+//! add_tx(
+//!     "2024-01-15", "Salary", "Bank", "1000", "Income", "Job", None, &mut conn,
+//! )
+//! .unwrap();
+//!
+//! let balances = get_last_balances(&conn);
+//! ```
+
+pub mod activity;
+pub mod api;
+pub mod chart;
+pub mod db;
+pub mod events;
+pub mod home;
+pub mod outputs;
+pub mod state;
+pub mod summary;
+pub mod sync_handler;
+pub mod tx_handler;
+pub mod utility;
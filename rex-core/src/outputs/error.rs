@@ -0,0 +1,1032 @@
+use chrono::NaiveDate;
+use rusqlite::Error as sqlError;
+use std::error::Error;
+use std::fmt::{self, Display, Result};
+use std::io::Error as ioError;
+use std::process::Output;
+
+use crate::outputs::NAType;
+
+#[derive(Debug)]
+pub enum TerminalExecutionError {
+    NotFound(Output),
+    ExecutionFailed(ioError),
+}
+
+impl Display for TerminalExecutionError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            TerminalExecutionError::NotFound(output) => write!(f, "Error while trying to run any console/terminal. Use a terminal/console to run the app. Output:\n\n{output:?}", ),
+            TerminalExecutionError::ExecutionFailed(error) => write!(f, "Error while processing commands. Use a terminal/console to run the app. Output: {error}", ),
+        }
+    }
+}
+
+impl Error for TerminalExecutionError {}
+
+#[derive(Debug)]
+pub enum UiHandlingError {
+    DrawingError(ioError),
+    PollingError(ioError),
+}
+
+impl Display for UiHandlingError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            UiHandlingError::DrawingError(err) => {
+                write!(f, "Error while trying to draw widgets. Error: {err}",)
+            }
+            UiHandlingError::PollingError(err) => {
+                write!(f, "Error while polling for keyboard input. {err}")
+            }
+        }
+    }
+}
+
+impl Error for UiHandlingError {}
+
+#[derive(Debug, PartialEq)]
+pub enum CheckingError {
+    EmptyDate,
+    EmptyMethod,
+    EmptyAmount,
+    EmptyTxType,
+    SameTxMethod,
+}
+
+impl Display for CheckingError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            CheckingError::EmptyDate => write!(f, "Date: Date cannot be empty"),
+            CheckingError::EmptyMethod => write!(f, "Tx Method: TX Method cannot be empty"),
+            CheckingError::EmptyAmount => write!(f, "Amount: Amount cannot be empty"),
+            CheckingError::EmptyTxType => write!(f, "Tx Type: Transaction Type cannot be empty"),
+            CheckingError::SameTxMethod => write!(
+                f,
+                "Tx Method: From and To methods cannot be the same for Transfer"
+            ),
+        }
+    }
+}
+
+impl Error for CheckingError {}
+
+#[derive(Debug, PartialEq)]
+pub enum SteppingError {
+    InvalidDate,
+    InvalidTxMethod,
+    InvalidAmount,
+    InvalidTxType,
+    InvalidTags,
+    UnknownBValue,
+}
+
+impl Display for SteppingError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            SteppingError::InvalidDate => {
+                write!(f, "Date: Failed to step due to invalid date format")
+            }
+            SteppingError::InvalidTxMethod => write!(
+                f,
+                "Tx Method: Failed to step as the tx method does not exists"
+            ),
+            SteppingError::InvalidAmount => {
+                write!(f, "Amount: Failed to step due to invalid amount format")
+            }
+            SteppingError::InvalidTxType => {
+                write!(f, "Tx Type: Failed to step due to invalid tx type")
+            }
+            SteppingError::InvalidTags => {
+                write!(f, "Tags: Failed to step as the tag does not exists")
+            }
+            SteppingError::UnknownBValue => write!(
+                f,
+                "Amount: Failed to step value. Value of B cannot be determined"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum JumpDateError {
+    InvalidDate(String),
+    OutOfRange(NaiveDate),
+    NoTxFound(NaiveDate),
+}
+
+impl Display for JumpDateError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            JumpDateError::InvalidDate(input) => write!(
+                f,
+                "Go To Date: '{input}' is not a valid date. Use a day of the month like 15, today or a full date like 2023-07-15"
+            ),
+            JumpDateError::OutOfRange(date) => write!(
+                f,
+                "Go To Date: {date} is outside of the supported years"
+            ),
+            JumpDateError::NoTxFound(date) => write!(
+                f,
+                "Go To Date: No transaction was found on or after {date} in the month"
+            ),
+        }
+    }
+}
+
+impl Error for JumpDateError {}
+
+#[derive(PartialEq, Debug)]
+pub enum DateRangeError {
+    InvalidRange(String),
+    InvalidDate(String),
+    OutOfRange(NaiveDate),
+    Reversed(NaiveDate, NaiveDate),
+}
+
+impl From<JumpDateError> for DateRangeError {
+    fn from(err: JumpDateError) -> Self {
+        match err {
+            JumpDateError::InvalidDate(input) => DateRangeError::InvalidDate(input),
+            JumpDateError::OutOfRange(date) | JumpDateError::NoTxFound(date) => {
+                DateRangeError::OutOfRange(date)
+            }
+        }
+    }
+}
+
+impl Display for DateRangeError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            DateRangeError::InvalidRange(input) => write!(
+                f,
+                "Date Range: '{input}' is not a valid range. Separate the dates with 'to' like 2023-01-28 to 2023-02-09"
+            ),
+            DateRangeError::InvalidDate(input) => write!(
+                f,
+                "Date Range: '{input}' is not a valid date. Use a day of the month like 15, today or a full date like 2023-07-15"
+            ),
+            DateRangeError::OutOfRange(date) => write!(
+                f,
+                "Date Range: {date} is outside of the supported years"
+            ),
+            DateRangeError::Reversed(from, to) => write!(
+                f,
+                "Date Range: The starting date {from} is after the ending date {to}"
+            ),
+        }
+    }
+}
+
+impl Error for DateRangeError {}
+
+#[derive(PartialEq, Debug)]
+pub enum QuickAddError {
+    MissingAmount(String),
+    MissingMethod(String),
+    InvalidDate(String),
+    OutOfRange(NaiveDate),
+    NotAccepted(NAType),
+}
+
+impl From<JumpDateError> for QuickAddError {
+    fn from(err: JumpDateError) -> Self {
+        match err {
+            JumpDateError::InvalidDate(input) => QuickAddError::InvalidDate(input),
+            JumpDateError::OutOfRange(date) | JumpDateError::NoTxFound(date) => {
+                QuickAddError::OutOfRange(date)
+            }
+        }
+    }
+}
+
+impl Display for QuickAddError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            QuickAddError::MissingAmount(input) => write!(
+                f,
+                "Quick Add: No amount was found in '{input}'. Example: 12-05 groceries 45.50 card #food"
+            ),
+            QuickAddError::MissingMethod(input) => write!(
+                f,
+                "Quick Add: No tx method was found after the amount in '{input}'. Example: 12-05 groceries 45.50 card #food"
+            ),
+            QuickAddError::InvalidDate(input) => write!(
+                f,
+                "Quick Add: '{input}' is not a valid date. Use a day and a month like 12-05, today or a full date like 2023-07-15"
+            ),
+            QuickAddError::OutOfRange(date) => write!(
+                f,
+                "Quick Add: {date} is outside of the supported years"
+            ),
+            QuickAddError::NotAccepted(err) => write!(f, "Quick Add: {err}"),
+        }
+    }
+}
+
+impl Error for QuickAddError {}
+
+#[derive(PartialEq, Debug)]
+pub enum SplitError {
+    InvalidPart(String),
+    NotAccepted(NAType),
+    ExceedsAmount(String),
+    TransferMethod,
+    Editing,
+}
+
+impl Display for SplitError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            SplitError::InvalidPart(part) => write!(
+                f,
+                "Split: Could not read '{part}'. Enter the tag followed by the amount like food 30, household 10"
+            ),
+            SplitError::NotAccepted(err) => write!(f, "Split: {err}"),
+            SplitError::ExceedsAmount(amount) => write!(
+                f,
+                "Split: The parts add up to more than the amount {amount} of the transaction"
+            ),
+            SplitError::TransferMethod => write!(
+                f,
+                "Split: The parts of a transfer cannot have their own tx method"
+            ),
+            SplitError::Editing => write!(
+                f,
+                "Split: An existing transaction cannot be split. Add it as a new transaction instead"
+            ),
+        }
+    }
+}
+
+impl Error for SplitError {}
+
+#[derive(PartialEq, Debug)]
+pub enum FeeError {
+    NotAccepted(NAType),
+    NotTransfer,
+    Split,
+    Editing,
+}
+
+impl Display for FeeError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            FeeError::NotAccepted(err) => write!(f, "Fee: {err}"),
+            FeeError::NotTransfer => write!(
+                f,
+                "Fee: Only a transfer can have a fee. Press E and leave it empty to remove it"
+            ),
+            FeeError::Split => write!(
+                f,
+                "Fee: A split transfer cannot have a fee. Remove the split or the fee"
+            ),
+            FeeError::Editing => write!(
+                f,
+                "Fee: An existing transaction cannot get a fee. Add the fee as a new expense instead"
+            ),
+        }
+    }
+}
+
+impl Error for FeeError {}
+
+#[derive(PartialEq, Debug)]
+pub enum CurrencyError {
+    NoBaseCurrency,
+    NoRate(String),
+    NotAccepted(NAType),
+}
+
+impl Display for CurrencyError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            CurrencyError::NoBaseCurrency => write!(
+                f,
+                "Currency: Set base_currency in config.json to enter amounts in other currencies"
+            ),
+            CurrencyError::NoRate(currency) => write!(
+                f,
+                "Currency: No rate found for {currency}. Add it to currency_rates in config.json"
+            ),
+            CurrencyError::NotAccepted(err) => write!(f, "Currency: {err}"),
+        }
+    }
+}
+
+impl Error for CurrencyError {}
+
+#[derive(Debug, PartialEq)]
+pub enum ExchangeRateError {
+    Missing(String, String),
+    Invalid(String),
+}
+
+impl Display for ExchangeRateError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ExchangeRateError::Missing(from, to) => {
+                let from = if from.is_empty() {
+                    "the base currency"
+                } else {
+                    from
+                };
+                let to = if to.is_empty() {
+                    "the base currency"
+                } else {
+                    to
+                };
+                write!(
+                    f,
+                    "Exchange Rate: Transfer from {from} to {to} needs an exchange rate. Press G to add it"
+                )
+            }
+            ExchangeRateError::Invalid(rate) => write!(
+                f,
+                "Exchange Rate: '{rate}' is not a valid rate. Use a number above 0 like 0.92"
+            ),
+        }
+    }
+}
+
+impl Error for ExchangeRateError {}
+
+#[derive(Debug)]
+pub enum ScheduleError {
+    NotFutureDate,
+    Editing,
+    SplitOrFee,
+    ExchangeRate,
+    FailedSave(sqlError),
+}
+
+impl Display for ScheduleError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ScheduleError::NotFutureDate => write!(
+                f,
+                "Schedule: Only a transaction dated after today can be scheduled. Press S to add it now"
+            ),
+            ScheduleError::Editing => write!(
+                f,
+                "Schedule: An existing transaction cannot be scheduled"
+            ),
+            ScheduleError::SplitOrFee => write!(
+                f,
+                "Schedule: A split transaction or a transfer with a fee cannot be scheduled"
+            ),
+            ScheduleError::ExchangeRate => write!(
+                f,
+                "Schedule: A transfer between different currencies cannot be scheduled"
+            ),
+            ScheduleError::FailedSave(e) => write!(
+                f,
+                "Schedule: Failed to save the scheduled transaction. Error: {e}"
+            ),
+        }
+    }
+}
+
+impl Error for ScheduleError {}
+
+#[derive(Debug)]
+pub enum TxMethodError {
+    Empty,
+    Restricted(String),
+    AlreadyExists(String),
+    ContainsTo,
+    ContainsQuote,
+    MissingNewName,
+    NotFound(String),
+    LastActive,
+    MissingType,
+    InvalidType(String),
+    MissingCurrency,
+    InvalidCurrency(String),
+    MissingBalance,
+    SameBalance(String),
+    MissingColor,
+    InvalidColor(String),
+    MissingLimit,
+    NotCredit(String),
+    MissingGroup,
+    MissingMergeTarget,
+    SameMethod,
+    CurrencyMismatch(String, String),
+    InvalidBalance(NAType),
+    FailedSave(sqlError),
+}
+
+impl Display for TxMethodError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            TxMethodError::Empty => write!(f, "Tx Method: The tx method name cannot be empty"),
+            TxMethodError::Restricted(name) => {
+                write!(
+                    f,
+                    "Tx Method: '{name}' is a restricted name. Use a different one"
+                )
+            }
+            TxMethodError::AlreadyExists(name) => {
+                write!(f, "Tx Method: A tx method named '{name}' already exists")
+            }
+            TxMethodError::ContainsTo => write!(
+                f,
+                "Tx Method: 'To' cannot be used as a word in a tx method name"
+            ),
+            TxMethodError::ContainsQuote => {
+                write!(f, "Tx Method: A tx method name cannot contain \"")
+            }
+            TxMethodError::MissingNewName => write!(
+                f,
+                "Tx Method: Add the new name after a comma like Cash, Wallet"
+            ),
+            TxMethodError::NotFound(name) => {
+                write!(f, "Tx Method: No tx method named '{name}' was found")
+            }
+            TxMethodError::LastActive => {
+                write!(f, "Tx Method: At least one tx method must stay unarchived")
+            }
+            TxMethodError::MissingType => {
+                write!(f, "Tx Method: Add the type after a comma like Card, Credit")
+            }
+            TxMethodError::InvalidType(method_type) => write!(
+                f,
+                "Tx Method: '{method_type}' is not a valid type. Use Cash, Bank, Credit or Savings"
+            ),
+            TxMethodError::MissingCurrency => write!(
+                f,
+                "Tx Method: Add the currency after a comma like Wallet, EUR"
+            ),
+            TxMethodError::InvalidCurrency(currency) => write!(
+                f,
+                "Tx Method: '{currency}' is not a valid currency. Use a 3 letter code like EUR"
+            ),
+            TxMethodError::MissingBalance => write!(
+                f,
+                "Tx Method: Add the new starting balance after a comma like Wallet, 750"
+            ),
+            TxMethodError::SameBalance(balance) => {
+                write!(f, "Tx Method: The starting balance is already {balance}")
+            }
+            TxMethodError::MissingColor => write!(
+                f,
+                "Tx Method: Add the color after a comma like Wallet, green or Wallet, #ff5555"
+            ),
+            TxMethodError::InvalidColor(color) => write!(
+                f,
+                "Tx Method: '{color}' is not a valid color. Use a hex code like #ff5555 or cyan, green, orange, pink, purple, red or yellow"
+            ),
+            TxMethodError::MissingLimit => write!(
+                f,
+                "Tx Method: Add the credit limit after a comma like Card, 5000"
+            ),
+            TxMethodError::NotCredit(name) => write!(
+                f,
+                "Tx Method: '{name}' is not a Credit tx method. Set its type to Credit first"
+            ),
+            TxMethodError::MissingGroup => write!(
+                f,
+                "Tx Method: Add the group after a comma like Wallet, Liquid"
+            ),
+            TxMethodError::MissingMergeTarget => write!(
+                f,
+                "Tx Method: Add the tx method to merge into after a comma like Old Card, Card"
+            ),
+            TxMethodError::SameMethod => {
+                write!(f, "Tx Method: A tx method cannot be merged into itself")
+            }
+            TxMethodError::CurrencyMismatch(source, target) => write!(
+                f,
+                "Tx Method: {source} and {target} use different currencies and cannot be merged"
+            ),
+            TxMethodError::InvalidBalance(err) => {
+                write!(f, "Tx Method: Invalid starting balance. {err}")
+            }
+            TxMethodError::FailedSave(e) => {
+                write!(f, "Tx Method: Failed to save the tx method. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for TxMethodError {}
+
+#[derive(Debug)]
+pub enum TagRuleError {
+    MissingTag,
+    MissingPattern,
+    InvalidField(String),
+    InvalidAmount(String),
+    NotFound(String),
+    FailedSave(sqlError),
+}
+
+impl Display for TagRuleError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            TagRuleError::MissingTag => write!(
+                f,
+                "Tag Rule: Enter the tag after a comma like details uber, Transport"
+            ),
+            TagRuleError::MissingPattern => write!(
+                f,
+                "Tag Rule: Enter a field and a pattern to match like details uber"
+            ),
+            TagRuleError::InvalidField(field) => write!(
+                f,
+                "Tag Rule: '{field}' is not a valid field. Fields: details, method, amount"
+            ),
+            TagRuleError::InvalidAmount(pattern) => write!(
+                f,
+                "Tag Rule: '{pattern}' is not a valid amount pattern. Use an amount like 45.50, >100 or <=20"
+            ),
+            TagRuleError::NotFound(number) => {
+                write!(f, "Tag Rule: No rule with the number '{number}' was found")
+            }
+            TagRuleError::FailedSave(e) => {
+                write!(f, "Tag Rule: Failed to save the tag rule. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for TagRuleError {}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Empty,
+    InvalidName(String),
+}
+
+impl Display for ProfileError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ProfileError::Empty => write!(f, "Profile: The profile name cannot be empty"),
+            ProfileError::InvalidName(name) => write!(
+                f,
+                "Profile: '{name}' is not a valid name. Use only letters, numbers, spaces, - and _"
+            ),
+        }
+    }
+}
+
+impl Error for ProfileError {}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    FailedRead(ioError),
+    InvalidConfig(serde_json::Error),
+    FailedCreate(ioError),
+}
+
+impl Display for ConfigError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ConfigError::FailedRead(e) => {
+                write!(f, "Config: Failed to read config.json. Error: {e}")
+            }
+            ConfigError::InvalidConfig(e) => {
+                write!(f, "Config: config.json is not valid. Error: {e}")
+            }
+            ConfigError::FailedCreate(e) => {
+                write!(f, "Config: Failed to create config.json. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+#[derive(Debug)]
+pub enum AttachmentError {
+    NotFound(String),
+    NoAttachment,
+    FailedOpen(String, ioError),
+    FailedSave(sqlError),
+}
+
+impl Display for AttachmentError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            AttachmentError::NotFound(path) => {
+                write!(f, "Attachment: No file was found at '{path}'")
+            }
+            AttachmentError::NoAttachment => write!(
+                f,
+                "Attachment: The selected transaction has no attached file. Press M to attach one"
+            ),
+            AttachmentError::FailedOpen(path, e) => {
+                write!(f, "Attachment: Failed to open '{path}'. Error: {e}")
+            }
+            AttachmentError::FailedSave(e) => {
+                write!(f, "Attachment: Failed to save the attachment. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for AttachmentError {}
+
+#[derive(Debug)]
+pub enum TxUpdateError {
+    FailedAddTx(sqlError),
+    FailedEditTx(sqlError),
+    FailedDeleteTx(sqlError),
+}
+
+impl Display for TxUpdateError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            TxUpdateError::FailedAddTx(e) => {
+                write!(
+                    f,
+                    "Delete Transaction: Something went wrong. Failed to delete transaction. Error: \n{e}",
+                )
+            }
+            TxUpdateError::FailedEditTx(e) => write!(
+                f,
+                "Edit Transaction: Something went wrong. Failed to edit transaction. Error: {e}",
+            ),
+            TxUpdateError::FailedDeleteTx(e) => {
+                write!(
+                    f,
+                    "Add Transaction: Something went wrong. Failed to add transaction. Error: {e}",
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TrashError {
+    NotFound,
+    MissingTxMethod(String),
+    FailedRestore(sqlError),
+}
+
+impl Display for TrashError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            TrashError::NotFound => write!(f, "Trash: The transaction is no longer in the trash"),
+            TrashError::MissingTxMethod(name) => write!(
+                f,
+                "Trash: The tx method '{name}' no longer exists. Add it again to restore the transaction"
+            ),
+            TrashError::FailedRestore(e) => {
+                write!(f, "Trash: Failed to restore the transaction. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for TrashError {}
+
+#[derive(Debug)]
+pub enum RetagError {
+    InvalidTag(String),
+    /// The `id_num` of the tx whose tags would not be accepted and why
+    NotAccepted(i32, NAType),
+    FailedRetag(sqlError),
+}
+
+impl Display for RetagError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            RetagError::InvalidTag(tag) => write!(
+                f,
+                "Retag: '{tag}' is not a valid tag. A tag cannot be empty or contain a comma"
+            ),
+            RetagError::NotAccepted(id_num, err) => write!(
+                f,
+                "Retag: No transaction was retagged. Transaction {id_num} would break the tag policy. {err}"
+            ),
+            RetagError::FailedRetag(e) => {
+                write!(f, "Retag: Failed to retag the transactions. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for RetagError {}
+
+#[derive(Debug)]
+pub enum SyncError {
+    NotSetUp,
+    FailedRequest(reqwest::Error),
+    UnexpectedStatus(u16),
+    RemoteChanged,
+    FailedSnapshot(sqlError),
+    FailedFile(ioError),
+    MissingTxMethod(String),
+}
+
+impl From<reqwest::Error> for SyncError {
+    fn from(value: reqwest::Error) -> Self {
+        SyncError::FailedRequest(value)
+    }
+}
+
+impl From<sqlError> for SyncError {
+    fn from(value: sqlError) -> Self {
+        SyncError::FailedSnapshot(value)
+    }
+}
+
+impl From<ioError> for SyncError {
+    fn from(value: ioError) -> Self {
+        SyncError::FailedFile(value)
+    }
+}
+
+impl Display for SyncError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            SyncError::NotSetUp => write!(f, "Sync: WebDAV sync is not set up yet"),
+            SyncError::FailedRequest(e) => {
+                write!(f, "Sync: Failed to reach the WebDAV server. Error: {e}")
+            }
+            SyncError::UnexpectedStatus(status) => write!(
+                f,
+                "Sync: The WebDAV server answered with status {status}. Check the url and the login"
+            ),
+            SyncError::RemoteChanged => write!(
+                f,
+                "Sync: Another device synced at the same time. Sync again to get its changes"
+            ),
+            SyncError::FailedSnapshot(e) => write!(
+                f,
+                "Sync: Failed to read the synced database. The passphrase may be wrong. Error: {e}"
+            ),
+            SyncError::FailedFile(e) => {
+                write!(f, "Sync: Failed to save the synced database. Error: {e}")
+            }
+            SyncError::MissingTxMethod(method) => write!(
+                f,
+                "Sync: The other device uses the tx method '{method}'. Add it here before syncing"
+            ),
+        }
+    }
+}
+
+impl Error for SyncError {}
+
+#[derive(Debug)]
+pub enum DumpError {
+    InvalidLine(usize),
+    UnknownTxMethod(usize, String),
+    NoTxMethod,
+    FailedLoad(sqlError),
+}
+
+impl From<sqlError> for DumpError {
+    fn from(value: sqlError) -> Self {
+        DumpError::FailedLoad(value)
+    }
+}
+
+impl Display for DumpError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            DumpError::InvalidLine(line) => {
+                write!(
+                    f,
+                    "Dump: Line {line} is not a valid tx method or transaction"
+                )
+            }
+            DumpError::UnknownTxMethod(line, method) => write!(
+                f,
+                "Dump: Line {line} uses the tx method '{method}' which is not listed in the dump"
+            ),
+            DumpError::NoTxMethod => write!(f, "Dump: The dump does not list any tx method"),
+            DumpError::FailedLoad(e) => {
+                write!(f, "Dump: Failed to load the dump. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for DumpError {}
+
+#[derive(Debug)]
+pub enum MaintenanceError {
+    FailedBackupFolder(ioError),
+    FailedBackup(sqlError),
+}
+
+impl Display for MaintenanceError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            MaintenanceError::FailedBackupFolder(e) => {
+                write!(
+                    f,
+                    "Maintenance: Failed to create the backups folder. Error: {e}"
+                )
+            }
+            MaintenanceError::FailedBackup(e) => {
+                write!(f, "Maintenance: Failed to save the backup. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for MaintenanceError {}
+
+#[derive(Debug)]
+pub enum ReportError {
+    NoReport,
+    EmptyQuery,
+    MultipleQueries,
+    NotReadOnly,
+    FailedQuery(sqlError),
+}
+
+impl From<sqlError> for ReportError {
+    fn from(value: sqlError) -> Self {
+        ReportError::FailedQuery(value)
+    }
+}
+
+impl Display for ReportError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ReportError::NoReport => write!(
+                f,
+                "Report: No report is saved yet. Add them to reports in config.json"
+            ),
+            ReportError::EmptyQuery => write!(f, "Report: The query of the report is empty"),
+            ReportError::MultipleQueries => {
+                write!(f, "Report: A report can only have a single query")
+            }
+            ReportError::NotReadOnly => write!(
+                f,
+                "Report: The query would change the database. Reports can only read data"
+            ),
+            ReportError::FailedQuery(e) => {
+                write!(f, "Report: Failed to run the query. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for ReportError {}
+
+#[derive(Debug)]
+pub enum ApiError {
+    InvalidRequest,
+    BodyTooLarge,
+    Unauthorized,
+    NotFound(String),
+    MethodNotAllowed(String),
+    InvalidBody(String),
+    InvalidPeriod(String),
+    NotAccepted(String),
+    FailedConnection(ioError),
+}
+
+impl ApiError {
+    /// The HTTP status code the error is answered with
+    #[must_use]
+    pub fn status(&self) -> u16 {
+        match self {
+            ApiError::InvalidRequest
+            | ApiError::InvalidBody(_)
+            | ApiError::InvalidPeriod(_)
+            | ApiError::NotAccepted(_)
+            | ApiError::FailedConnection(_) => 400,
+            ApiError::Unauthorized => 401,
+            ApiError::NotFound(_) => 404,
+            ApiError::MethodNotAllowed(_) => 405,
+            ApiError::BodyTooLarge => 413,
+        }
+    }
+}
+
+impl From<ioError> for ApiError {
+    fn from(value: ioError) -> Self {
+        ApiError::FailedConnection(value)
+    }
+}
+
+impl Display for ApiError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ApiError::InvalidRequest => write!(f, "API: The request is not a valid HTTP request"),
+            ApiError::BodyTooLarge => write!(f, "API: The request body is too large"),
+            ApiError::Unauthorized => write!(
+                f,
+                "API: Missing or wrong token. Send it as 'Authorization: Bearer <token>'"
+            ),
+            ApiError::NotFound(path) => write!(f, "API: There is no endpoint at '{path}'"),
+            ApiError::MethodNotAllowed(method) => {
+                write!(f, "API: The endpoint does not accept {method} requests")
+            }
+            ApiError::InvalidBody(e) => write!(f, "API: The request body is not valid. Error: {e}"),
+            ApiError::InvalidPeriod(value) => write!(
+                f,
+                "API: '{value}' is not a valid period. Use a month from 1 to 12 and a year like 2024"
+            ),
+            ApiError::NotAccepted(err) => write!(f, "API: {err}"),
+            ApiError::FailedConnection(e) => {
+                write!(f, "API: Failed to read the request. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for ApiError {}
+
+#[derive(Debug, PartialEq)]
+pub enum KeymapError {
+    UnknownAction(String),
+    InvalidKey(String, String),
+    ExtensionKey(String, String),
+    /// The key, the action it was bound to, the action already using it and the page
+    KeyTaken(String, String, String, String),
+    /// The key, the two actions it was bound to and the page
+    KeyShared(String, String, String, String),
+}
+
+impl Display for KeymapError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            KeymapError::UnknownAction(action) => {
+                write!(f, "Keymap: '{action}' is not an action of any page that can be bound")
+            }
+            KeymapError::InvalidKey(action, key) => write!(
+                f,
+                "Keymap: '{key}' of {action} is not a valid key. Use a key like 'a', 'ctrl+n', 'enter' or 'left'"
+            ),
+            KeymapError::ExtensionKey(action, key) => write!(
+                f,
+                "Keymap: '{key}' of {action} cannot be used as the F keys open the extension pages"
+            ),
+            KeymapError::KeyTaken(key, action, taken_by, page) => write!(
+                f,
+                "Keymap: '{key}' of {action} is already used by {taken_by} on the {page} page"
+            ),
+            KeymapError::KeyShared(key, action, other_action, page) => write!(
+                f,
+                "Keymap: '{key}' is bound to both {action} and {other_action} on the {page} page"
+            ),
+        }
+    }
+}
+
+impl Error for KeymapError {}
+
+#[derive(Debug, PartialEq)]
+pub enum ThemeError {
+    UnknownTheme(String),
+    UnknownColor(String),
+    /// The color name and its value
+    InvalidColor(String, String),
+}
+
+impl Display for ThemeError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ThemeError::UnknownTheme(name) => write!(
+                f,
+                "Theme: '{name}' is not a built-in theme. Use Dracula, Gruvbox, Solarized Dark, Solarized Light or Plain"
+            ),
+            ThemeError::UnknownColor(name) => write!(
+                f,
+                "Theme: '{name}' is not a color of the theme. Use background, text, border, selected, highlighted, header, expense, income or autofill"
+            ),
+            ThemeError::InvalidColor(name, color) => write!(
+                f,
+                "Theme: '{color}' of {name} is not a valid color. Use a hex color code like '#ff5555'"
+            ),
+        }
+    }
+}
+
+impl Error for ThemeError {}
@@ -1,9 +1,15 @@
 use std::fmt;
 
+use crate::state::DbChangeAction;
+
 pub enum HandlingOutput {
     QuitUi,
     TakeUserInput,
     PrintNewUpdate,
+    EditTxNote,
+    TxMethodsChanged,
+    SwitchProfile(String),
+    DbChanged(DbChangeAction),
 }
 
 #[derive(PartialEq, Debug)]
@@ -64,6 +70,9 @@ pub enum NAType {
     ParsingError(AType),
     InvalidBValue,
     NonExistingTag,
+    MissingTag,
+    TagNotAllowed(String),
+    TooManyTags(usize),
 }
 
 impl fmt::Display for NAType {
@@ -103,6 +112,13 @@ impl fmt::Display for NAType {
                 "Amount: TX Method cannot be empty. Value of B cannot be determined"
             ),
             NAType::NonExistingTag => write!(f, "Tags: Non-existing tags cannot be accepted"),
+            NAType::MissingTag => write!(f, "Tags: At least one tag is required"),
+            NAType::TagNotAllowed(tag) => {
+                write!(f, "Tags: '{tag}' is not in the allowed tags of the config")
+            }
+            NAType::TooManyTags(max) => {
+                write!(f, "Tags: A transaction can have at most {max} tags")
+            }
         }
     }
 }
@@ -0,0 +1,502 @@
+use chrono::prelude::Local;
+use chrono::Datelike;
+use rusqlite::Connection;
+
+use crate::db::{MODES, MONTHS, YEARS};
+use crate::utility::{get_active_tx_methods, get_all_tags, get_all_tx_methods, get_month_weeks};
+
+/// This struct takes anything inside a vector and adds an index it it.
+/// It is used for keeping track of the Months and Years current index.
+///
+/// titles: `["January", "February",]`
+
+pub struct IndexedData {
+    pub titles: Vec<String>,
+    pub index: usize,
+}
+
+impl IndexedData {
+    pub fn new_monthly() -> Self {
+        let month_index = Local::now().month() as usize - 1;
+        IndexedData {
+            titles: MONTHS.into_iter().map(ToString::to_string).collect(),
+            index: month_index,
+        }
+    }
+
+    pub fn new_yearly() -> Self {
+        let year_index = Local::now().year() as usize - 2022;
+        IndexedData {
+            titles: YEARS.into_iter().map(ToString::to_string).collect(),
+            index: year_index,
+        }
+    }
+
+    /// ISO weeks that have at least one day within the given month and year index.
+    /// Selects the ongoing week if it's one of them
+    pub fn new_weekly(month: usize, year: usize) -> Self {
+        let weeks = get_month_weeks(month, year);
+        let today = Local::now().date_naive();
+
+        let index = weeks
+            .iter()
+            .position(|week_start| today >= *week_start && (today - *week_start).num_days() < 7)
+            .unwrap_or(0);
+
+        IndexedData {
+            titles: weeks
+                .iter()
+                .map(|week_start| format!("Week {}", week_start.iso_week().week()))
+                .collect(),
+            index,
+        }
+    }
+
+    pub fn new_modes() -> Self {
+        IndexedData {
+            titles: MODES.into_iter().map(ToString::to_string).collect(),
+            index: 0,
+        }
+    }
+
+    pub fn new_tx_methods(conn: &Connection) -> Self {
+        IndexedData {
+            titles: get_all_tx_methods(conn),
+            index: 0,
+        }
+    }
+
+    /// Tx methods of the chart page alongside the Total line of all tx methods. Archived methods
+    /// cannot be toggled
+    pub fn new_chart_tx_methods(conn: &Connection) -> Self {
+        let mut titles = get_active_tx_methods(conn);
+        titles.push("Total".to_string());
+        IndexedData { titles, index: 0 }
+    }
+
+    /// Every tx method with an extra value at the start for selecting all of them
+    pub fn new_summary_tx_methods(conn: &Connection) -> Self {
+        let mut titles = vec!["All".to_string()];
+        titles.extend(get_all_tx_methods(conn));
+        IndexedData { titles, index: 0 }
+    }
+
+    pub fn new_tags(conn: &Connection) -> Self {
+        IndexedData {
+            titles: get_all_tags(conn),
+            index: 0,
+        }
+    }
+
+    /// Increases the current index by 1 or goes to 0 if at the final value
+    pub fn next(&mut self) {
+        self.index = (self.index + 1) % self.titles.len();
+    }
+
+    /// Decreases the current index by 1 or goes to final index if at 0
+    pub fn previous(&mut self) {
+        if self.index > 0 {
+            self.index -= 1;
+        } else {
+            self.index = self.titles.len() - 1;
+        }
+    }
+
+    pub fn set_index_zero(&mut self) {
+        self.index = 0;
+    }
+}
+
+/// This enum is used inside the Add Transaction page.
+/// This is targeted to be used to keep track which widget of the Add Transaction
+/// is currently being interacted with.
+pub enum TxTab {
+    Date,
+    Details,
+    FromMethod,
+    ToMethod,
+    Amount,
+    TxType,
+    Tags,
+    Nothing,
+}
+
+/// What happens after the db file was changed by another app while it was open
+pub enum DbChangeAction {
+    /// Opens the changed db. Anything saved in this session after the change is dropped
+    Reload,
+    /// Saves the db of this session over the changed one
+    KeepCurrent,
+    /// Saves the db of this session as a conflict copy next to it then opens the changed db
+    KeepBoth,
+}
+
+/// The summary table column the table is sorted by
+#[derive(PartialEq)]
+pub enum SortingType {
+    ByTags,
+    ByIncome,
+    ByExpense,
+    ByNet,
+    ByIncomePercentage,
+    ByExpensePercentage,
+    ByIncomeShare,
+}
+
+impl SortingType {
+    #[cfg(not(tarpaulin_include))]
+    pub fn next_type(&mut self) -> Self {
+        match self {
+            SortingType::ByTags => SortingType::ByIncome,
+            SortingType::ByIncome => SortingType::ByExpense,
+            SortingType::ByExpense => SortingType::ByNet,
+            SortingType::ByNet => SortingType::ByIncomePercentage,
+            SortingType::ByIncomePercentage => SortingType::ByExpensePercentage,
+            SortingType::ByExpensePercentage => SortingType::ByIncomeShare,
+            SortingType::ByIncomeShare => SortingType::ByTags,
+        }
+    }
+
+    /// The direction the column gets sorted by when it's first selected.
+    /// Tags start from A-Z and the amounts start from the biggest one
+    pub fn default_direction(&self) -> SortingDirection {
+        match self {
+            SortingType::ByTags => SortingDirection::Ascending,
+            _ => SortingDirection::Descending,
+        }
+    }
+}
+
+/// The Home table column the txs are sorted by
+#[derive(PartialEq)]
+pub enum HomeSortingType {
+    ByDate,
+    ByDetails,
+    ByAmount,
+    ByType,
+    ByTags,
+}
+
+impl HomeSortingType {
+    #[cfg(not(tarpaulin_include))]
+    pub fn next_type(&mut self) -> Self {
+        match self {
+            HomeSortingType::ByDate => HomeSortingType::ByDetails,
+            HomeSortingType::ByDetails => HomeSortingType::ByAmount,
+            HomeSortingType::ByAmount => HomeSortingType::ByType,
+            HomeSortingType::ByType => HomeSortingType::ByTags,
+            HomeSortingType::ByTags => HomeSortingType::ByDate,
+        }
+    }
+
+    /// The direction the column gets sorted by when it's first selected.
+    /// Amounts start from the biggest one, everything else from the start
+    pub fn default_direction(&self) -> SortingDirection {
+        match self {
+            HomeSortingType::ByAmount => SortingDirection::Descending,
+            _ => SortingDirection::Ascending,
+        }
+    }
+
+    /// The index of the tx column that is used for sorting
+    pub fn get_column(&self) -> usize {
+        match self {
+            HomeSortingType::ByDate => 0,
+            HomeSortingType::ByDetails => 1,
+            HomeSortingType::ByAmount => 3,
+            HomeSortingType::ByType => 4,
+            HomeSortingType::ByTags => 5,
+        }
+    }
+}
+
+/// The order of the sorted summary table column
+#[derive(PartialEq)]
+pub enum SortingDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortingDirection {
+    #[cfg(not(tarpaulin_include))]
+    pub fn reverse(&self) -> Self {
+        match self {
+            SortingDirection::Ascending => SortingDirection::Descending,
+            SortingDirection::Descending => SortingDirection::Ascending,
+        }
+    }
+
+    /// The arrow shown next to the sorted column header
+    #[cfg(not(tarpaulin_include))]
+    pub fn get_arrow(&self) -> &str {
+        match self {
+            SortingDirection::Ascending => "▲",
+            SortingDirection::Descending => "▼",
+        }
+    }
+}
+
+/// Whether a tx was matched against the bank statement yet. Every tx starts as pending
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TxStatus {
+    Pending,
+    Cleared,
+    Reconciled,
+}
+
+impl TxStatus {
+    #[cfg(not(tarpaulin_include))]
+    pub fn next(self) -> Self {
+        match self {
+            TxStatus::Pending => TxStatus::Cleared,
+            TxStatus::Cleared => TxStatus::Reconciled,
+            TxStatus::Reconciled => TxStatus::Pending,
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(data: &str) -> Self {
+        match data {
+            "Cleared" => TxStatus::Cleared,
+            "Reconciled" => TxStatus::Reconciled,
+            _ => TxStatus::Pending,
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            TxStatus::Pending => "Pending",
+            TxStatus::Cleared => "Cleared",
+            TxStatus::Reconciled => "Reconciled",
+        }
+    }
+
+    /// The glyph shown on the status column of the Home table
+    #[cfg(not(tarpaulin_include))]
+    pub fn get_glyph(self) -> &'static str {
+        match self {
+            TxStatus::Pending => "○",
+            TxStatus::Cleared => "✓",
+            TxStatus::Reconciled => "✓✓",
+        }
+    }
+}
+
+/// The kind of account a tx method is. Credit balances are shown as the owed amount and
+/// savings are left out of the spendable balance
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MethodType {
+    Cash,
+    Bank,
+    Credit,
+    Savings,
+}
+
+impl MethodType {
+    /// Returns the type with the given name. The name is not case sensitive
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(data: &str) -> Option<Self> {
+        [
+            MethodType::Cash,
+            MethodType::Bank,
+            MethodType::Credit,
+            MethodType::Savings,
+        ]
+        .into_iter()
+        .find(|method_type| method_type.to_str().eq_ignore_ascii_case(data.trim()))
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            MethodType::Cash => "Cash",
+            MethodType::Bank => "Bank",
+            MethodType::Credit => "Credit",
+            MethodType::Savings => "Savings",
+        }
+    }
+}
+
+/// A column of the Home table. The shown columns and their order come from the config
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HomeColumn {
+    Date,
+    Details,
+    TxMethod,
+    Amount,
+    TxType,
+    Tags,
+    Status,
+    Id,
+}
+
+impl HomeColumn {
+    /// The columns that are shown when the config does not pick any
+    pub fn default_columns() -> Vec<HomeColumn> {
+        vec![
+            HomeColumn::Date,
+            HomeColumn::Details,
+            HomeColumn::TxMethod,
+            HomeColumn::Amount,
+            HomeColumn::TxType,
+            HomeColumn::Tags,
+            HomeColumn::Status,
+        ]
+    }
+
+    /// Matches the column by its title while ignoring the case. Returns `None` for unknown columns
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(data: &str) -> Option<Self> {
+        match data.trim().to_lowercase().as_str() {
+            "date" => Some(HomeColumn::Date),
+            "details" => Some(HomeColumn::Details),
+            "tx method" | "method" => Some(HomeColumn::TxMethod),
+            "amount" => Some(HomeColumn::Amount),
+            "type" | "tx type" => Some(HomeColumn::TxType),
+            "tags" => Some(HomeColumn::Tags),
+            "status" => Some(HomeColumn::Status),
+            "id" => Some(HomeColumn::Id),
+            _ => None,
+        }
+    }
+
+    pub fn get_title(self) -> &'static str {
+        match self {
+            HomeColumn::Date => "Date",
+            HomeColumn::Details => "Details",
+            HomeColumn::TxMethod => "TX Method",
+            HomeColumn::Amount => "Amount",
+            HomeColumn::TxType => "Type",
+            HomeColumn::Tags => "Tags",
+            HomeColumn::Status => "Status",
+            HomeColumn::Id => "ID",
+        }
+    }
+
+    /// The index of the tx data the column shows. `None` if the column is not a part of the tx data
+    pub fn get_tx_column(self) -> Option<usize> {
+        match self {
+            HomeColumn::Date => Some(0),
+            HomeColumn::Details => Some(1),
+            HomeColumn::TxMethod => Some(2),
+            HomeColumn::Amount => Some(3),
+            HomeColumn::TxType => Some(4),
+            HomeColumn::Tags => Some(5),
+            HomeColumn::Status | HomeColumn::Id => None,
+        }
+    }
+
+    /// How much space the column takes compared to the other shown columns
+    pub fn get_width(self) -> u32 {
+        match self {
+            HomeColumn::Date | HomeColumn::TxType => 10,
+            HomeColumn::Details => 34,
+            HomeColumn::TxMethod | HomeColumn::Amount => 13,
+            HomeColumn::Tags => 18,
+            HomeColumn::Status | HomeColumn::Id => 5,
+        }
+    }
+}
+
+/// What the Chart page is currently plotting
+#[derive(Clone, Copy, PartialEq)]
+pub enum ChartView {
+    Balance,
+    Cumulative,
+    Tags,
+}
+
+impl ChartView {
+    #[cfg(not(tarpaulin_include))]
+    pub fn next_view(&mut self) -> Self {
+        match self {
+            ChartView::Balance => ChartView::Cumulative,
+            ChartView::Cumulative => ChartView::Tags,
+            ChartView::Tags => ChartView::Balance,
+        }
+    }
+}
+
+pub enum DateType {
+    Exact,
+    Monthly,
+    Yearly,
+}
+
+impl DateType {
+    #[cfg(not(tarpaulin_include))]
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Self {
+        match self {
+            DateType::Exact => DateType::Monthly,
+            DateType::Monthly => DateType::Yearly,
+            DateType::Yearly => DateType::Exact,
+        }
+    }
+}
+
+pub enum ActivityType {
+    NewTX,
+    EditTX(Option<i32>),
+    DeleteTX(Option<i32>),
+    IDNumSwap(Option<i32>, Option<i32>),
+    SearchTX(Option<u8>),
+    ScheduleTX,
+    PostScheduledTX,
+    RestoreTX(Option<i32>),
+}
+
+impl ActivityType {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(data: &str) -> Self {
+        match data {
+            "Add TX" => Self::NewTX,
+            "Edit TX" => Self::EditTX(None),
+            "Delete TX" => Self::DeleteTX(None),
+            "TX Position Swap" => Self::IDNumSwap(None, None),
+            "Search TX" => Self::SearchTX(None),
+            "Schedule TX" => Self::ScheduleTX,
+            "Post Scheduled TX" => Self::PostScheduledTX,
+            "Restore TX" => Self::RestoreTX(None),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn to_details(&self) -> String {
+        match self {
+            Self::NewTX => String::from("A new Transaction was added"),
+            Self::EditTX(id) => format!("A transaction was edited with ID {}", id.unwrap()),
+            Self::DeleteTX(id) => format!("A transaction was deleted with ID {}", id.unwrap()),
+            Self::IDNumSwap(id_1, id_2) => format!(
+                "Transaction with ID num {} and ID num {} was swapped",
+                id_1.unwrap(),
+                id_2.unwrap()
+            ),
+            Self::SearchTX(total) => {
+                if total.unwrap() == 1 {
+                    String::from("Transactions were searched with one field")
+                } else {
+                    String::from("Transactions were searched with multiple fields")
+                }
+            }
+            Self::ScheduleTX => String::from("A transaction was scheduled to be added on its date"),
+            Self::PostScheduledTX => String::from("A scheduled transaction was added on its date"),
+            Self::RestoreTX(id) => format!(
+                "A transaction was restored from the trash with ID {}",
+                id.unwrap()
+            ),
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        match self {
+            Self::NewTX => String::from("Add TX"),
+            Self::EditTX(_) => String::from("Edit TX"),
+            Self::DeleteTX(_) => String::from("Delete TX"),
+            Self::IDNumSwap(_, _) => String::from("TX Position Swap"),
+            Self::SearchTX(_) => String::from("Search TX"),
+            Self::ScheduleTX => String::from("Schedule TX"),
+            Self::PostScheduledTX => String::from("Post Scheduled TX"),
+            Self::RestoreTX(_) => String::from("Restore TX"),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod summary_data;
+
+pub use summary_data::{SummaryData, TagStats};
@@ -0,0 +1,1351 @@
+use chrono::{Datelike, Local, Months, NaiveDate};
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+
+use crate::db::{get_all_split_ids, MONTHS, YEARS};
+use crate::state::{IndexedData, SortingDirection, SortingType};
+use crate::utility::{
+    get_all_tx_methods, get_all_txs, get_sparkline, reverse_date_format, sort_table_data,
+};
+
+/// Number of txs shown of each tx type on the largest transactions table
+const LARGEST_TX_LIMIT: usize = 10;
+
+/// Number of months shown on the trend of each tag
+const TREND_MONTHS: usize = 12;
+
+type MyVec = Vec<Vec<String>>;
+type MyTuple = (
+    f64,
+    f64,
+    (f64, String, String),
+    (f64, String, String),
+    f64,
+    f64,
+);
+
+/// The lifetime statistics of a single tag
+#[derive(Debug, PartialEq)]
+pub struct TagStats {
+    pub tag: String,
+    pub total_txs: usize,
+    pub total_income: f64,
+    pub total_expense: f64,
+    /// Averaged over every month from the first tx of the tag to the current month
+    pub monthly_income: f64,
+    pub monthly_expense: f64,
+    /// The combined income and expense of each of the last 12 months up to the current month
+    pub trend: Vec<f64>,
+    pub first_date: String,
+    pub last_date: String,
+}
+
+impl TagStats {
+    /// Returns the statistics as the text of the tag statistics popup
+    #[must_use]
+    pub fn get_text(&self) -> String {
+        format!(
+            "Tag: {}\nTransactions: {}\nFirst: {}\nLast: {}\n\nTotal Income: {:.2}\nTotal Expense: {:.2}\nMonthly Income: {:.2}\nMonthly Expense: {:.2}\n\nLast {TREND_MONTHS} Months: {}",
+            self.tag,
+            self.total_txs,
+            self.first_date,
+            self.last_date,
+            self.total_income,
+            self.total_expense,
+            self.monthly_income,
+            self.monthly_expense,
+            get_sparkline(&self.trend),
+        )
+    }
+}
+
+/// Contains the necessary information to construct the Summary Page highlighting
+/// tag based expense and income information, biggest expense and income
+pub struct SummaryData {
+    /// Every tx except the transfers
+    all_txs: HashMap<i32, Vec<Vec<String>>>,
+    /// Every tx where each transfer is turned into an expense of the source
+    /// method and an income of the destination method
+    transfer_txs: HashMap<i32, Vec<Vec<String>>>,
+    include_transfers: bool,
+    /// The only tx method the summary is showing. None shows every tx method
+    tx_method: Option<String>,
+    /// The txs of `tx_method` when a tx method is selected
+    filtered_txs: HashMap<i32, Vec<Vec<String>>>,
+    /// The `split_id` of the txs that are parts of a split tx by their `id_num`
+    split_ids: HashMap<i32, i32>,
+}
+
+impl SummaryData {
+    /// Goes through all transactions to collect data for the summary
+    pub fn new(conn: &Connection) -> Self {
+        let mut all_txs = HashMap::new();
+        let mut transfer_txs = HashMap::new();
+
+        for x in 0..YEARS.len() {
+            for i in 0..MONTHS.len() {
+                let target_id = i as i32 + (x as i32 * 12);
+                let (txs, _, id_nums) = get_all_txs(conn, i, x);
+
+                let mut month_txs = Vec::new();
+                let mut month_transfer_txs = Vec::new();
+
+                // the id_num is kept at the end of each tx so the tx can be found later
+                for (mut tx, id_num) in txs.into_iter().zip(id_nums) {
+                    tx.push(id_num);
+
+                    if tx[4] == "Transfer" {
+                        let (from_method, to_method) = tx[2].split_once(" to ").unwrap();
+
+                        let mut expense_tx = tx.clone();
+                        expense_tx[2] = from_method.to_string();
+                        expense_tx[4] = String::from("Expense");
+
+                        let mut income_tx = tx.clone();
+                        income_tx[2] = to_method.to_string();
+                        income_tx[4] = String::from("Income");
+
+                        month_transfer_txs.push(expense_tx);
+                        month_transfer_txs.push(income_tx);
+                    } else {
+                        month_txs.push(tx.clone());
+                        month_transfer_txs.push(tx);
+                    }
+                }
+
+                all_txs.insert(target_id, month_txs);
+                transfer_txs.insert(target_id, month_transfer_txs);
+            }
+        }
+        SummaryData {
+            all_txs,
+            transfer_txs,
+            include_transfers: false,
+            tx_method: None,
+            filtered_txs: HashMap::new(),
+            split_ids: get_all_split_ids(conn),
+        }
+    }
+
+    /// Sets whether transfers are counted as an expense and an income of the related tx methods.
+    /// They are excluded by default
+    pub fn set_include_transfers(&mut self, include_transfers: bool) {
+        self.include_transfers = include_transfers;
+        self.filter_tx_method();
+    }
+
+    /// Sets the only tx method the summary will show. None shows every tx method
+    pub fn set_tx_method(&mut self, tx_method: Option<String>) {
+        self.tx_method = tx_method;
+        self.filter_tx_method();
+    }
+
+    /// Collects the txs of the selected tx method so they don't have to be filtered on every use
+    fn filter_tx_method(&mut self) {
+        let Some(tx_method) = &self.tx_method else {
+            self.filtered_txs = HashMap::new();
+            return;
+        };
+
+        let source_txs = if self.include_transfers {
+            &self.transfer_txs
+        } else {
+            &self.all_txs
+        };
+
+        self.filtered_txs = source_txs
+            .iter()
+            .map(|(target_id, txs)| {
+                let method_txs = txs
+                    .iter()
+                    .filter(|tx| &tx[2] == tx_method)
+                    .cloned()
+                    .collect();
+                (*target_id, method_txs)
+            })
+            .collect();
+    }
+
+    /// Returns the txs that are used for the summary based on whether transfers are included
+    /// and the selected tx method
+    fn get_txs(&self) -> &HashMap<i32, Vec<Vec<String>>> {
+        if self.tx_method.is_some() {
+            &self.filtered_txs
+        } else if self.include_transfers {
+            &self.transfer_txs
+        } else {
+            &self.all_txs
+        }
+    }
+
+    /// Iters through the given transactions to collect earning and expense data
+    fn get_data(
+        &self,
+        txs: &Vec<Vec<String>>,
+        method_earning: &mut HashMap<String, f64>,
+        method_expense: &mut HashMap<String, f64>,
+    ) -> MyTuple {
+        let mut total_income: f64 = 0.0;
+        let mut total_expense: f64 = 0.0;
+
+        let mut biggest_earning = (0.0, String::new(), String::new());
+        let mut biggest_expense = (0.0, String::new(), String::new());
+
+        let mut monthly_earning = 0.0;
+        let mut monthly_expense = 0.0;
+
+        for tx in txs {
+            let tx_date = &tx[0];
+            let tx_method = &tx[2];
+            let tx_amount: f64 = tx[3].parse().unwrap();
+            let tx_type = &tx[4];
+
+            match tx_type.as_str() {
+                "Income" => {
+                    if tx_amount > biggest_earning.0 {
+                        biggest_earning = (tx_amount, tx_method.to_string(), tx_date.to_string());
+                    };
+                    total_income += tx_amount;
+                    monthly_earning += tx_amount;
+
+                    *method_earning.get_mut(tx_method).unwrap() += tx_amount;
+                }
+                "Expense" => {
+                    if tx_amount > biggest_expense.0 {
+                        biggest_expense = (tx_amount, tx_method.to_string(), tx_date.to_string());
+                    };
+                    total_expense += tx_amount;
+                    monthly_expense += tx_amount;
+
+                    *method_expense.get_mut(tx_method).unwrap() += tx_amount;
+                }
+                _ => {}
+            }
+        }
+
+        (
+            total_income,
+            total_expense,
+            biggest_earning,
+            biggest_expense,
+            monthly_earning,
+            monthly_expense,
+        )
+    }
+
+    /// Returns a vector that will be used to creating table in the Summary UI
+    /// The vector contains tags and their income and expense data, sorted by the given column and direction.
+    /// `parent:child` tags are rolled up into their parent tag. The children are only included
+    /// right below their parent if the parent is expanded
+    pub fn get_table_data(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+        sort_type: &SortingType,
+        sort_direction: &SortingDirection,
+        expanded_tags: &HashSet<String>,
+    ) -> Vec<Vec<String>> {
+        let mut income_tags = HashMap::new();
+        let mut expense_tags = HashMap::new();
+        let mut net_tags = HashMap::new();
+        match mode.index {
+            // 0 = monthly mode. Select the data only of the given month year
+            0 => {
+                let target_id = month as i32 + (year as i32 * 12);
+
+                for tx_data in &self.get_txs()[&target_id] {
+                    let tx_amount: f64 = tx_data[3].parse().unwrap();
+                    let tx_type = &tx_data[4];
+                    let tx_tags = tx_data[5].split(", ").collect::<Vec<&str>>();
+
+                    // gather data by loop through each tx. If tag exists, add with the value, if not insert it
+                    match tx_type.as_str() {
+                        "Income" => {
+                            for tag in tx_tags {
+                                if income_tags.contains_key(tag) {
+                                    *income_tags.get_mut(tag).unwrap() += tx_amount;
+                                } else {
+                                    income_tags.insert(tag, tx_amount);
+                                }
+                            }
+                        }
+                        "Expense" => {
+                            for tag in tx_tags {
+                                if expense_tags.contains_key(tag) {
+                                    *expense_tags.get_mut(tag).unwrap() += tx_amount;
+                                } else {
+                                    expense_tags.insert(tag, tx_amount);
+                                }
+                            }
+                        }
+                        "Net" => {
+                            for tag in tx_tags {
+                                if net_tags.contains_key(tag) {
+                                    *net_tags.get_mut(tag).unwrap() += tx_amount;
+                                } else {
+                                    net_tags.insert(tag, tx_amount);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // 1 = yearly mode. Select the data of all months of the given year
+            1 => {
+                for i in 0..MONTHS.len() {
+                    let target_id = i as i32 + (year as i32 * 12);
+
+                    for tx_data in &self.get_txs()[&target_id] {
+                        let tx_amount: f64 = tx_data[3].parse().unwrap();
+                        let tx_type = &tx_data[4];
+                        let tx_tags = tx_data[5].split(", ").collect::<Vec<&str>>();
+
+                        // gather data by loop through each tx. If tag exists, add with the value, if not insert it
+                        match tx_type.as_str() {
+                            "Income" => {
+                                for tag in tx_tags {
+                                    if income_tags.contains_key(tag) {
+                                        *income_tags.get_mut(tag).unwrap() += tx_amount;
+                                    } else {
+                                        income_tags.insert(tag, tx_amount);
+                                    }
+                                }
+                            }
+                            "Expense" => {
+                                for tag in tx_tags {
+                                    if expense_tags.contains_key(tag) {
+                                        *expense_tags.get_mut(tag).unwrap() += tx_amount;
+                                    } else {
+                                        expense_tags.insert(tag, tx_amount);
+                                    }
+                                }
+                            }
+                            "Net" => {
+                                for tag in tx_tags {
+                                    if net_tags.contains_key(tag) {
+                                        *net_tags.get_mut(tag).unwrap() += tx_amount;
+                                    } else {
+                                        net_tags.insert(tag, tx_amount);
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            //  * 2 = all time mode. Select every single data
+            2 => {
+                for x in 0..YEARS.len() {
+                    for i in 0..MONTHS.len() {
+                        let target_id = i as i32 + (x as i32 * 12);
+
+                        for tx_data in &self.get_txs()[&target_id] {
+                            let tx_amount: f64 = tx_data[3].parse().unwrap();
+                            let tx_type = &tx_data[4];
+                            let tx_tags = tx_data[5].split(", ").collect::<Vec<&str>>();
+
+                            // gather data by loop through each tx. If tag exists, add with the value, if not insert it
+                            match tx_type.as_str() {
+                                "Income" => {
+                                    for tag in tx_tags {
+                                        if income_tags.contains_key(tag) {
+                                            *income_tags.get_mut(tag).unwrap() += tx_amount;
+                                        } else {
+                                            income_tags.insert(tag, tx_amount);
+                                        }
+                                    }
+                                }
+                                "Expense" => {
+                                    for tag in tx_tags {
+                                        if expense_tags.contains_key(tag) {
+                                            *expense_tags.get_mut(tag).unwrap() += tx_amount;
+                                        } else {
+                                            expense_tags.insert(tag, tx_amount);
+                                        }
+                                    }
+                                }
+                                "Net" => {
+                                    for tag in tx_tags {
+                                        if net_tags.contains_key(tag) {
+                                            *net_tags.get_mut(tag).unwrap() += tx_amount;
+                                        } else {
+                                            net_tags.insert(tag, tx_amount);
+                                        }
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        let table_data = self.generate_table_data(&income_tags, &expense_tags, &net_tags);
+        group_table_data(table_data, sort_type, sort_direction, expanded_tags)
+    }
+
+    /// Returns every tag within the given period that has at least one `parent:child` tag under it
+    pub fn get_parent_tags(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+    ) -> HashSet<String> {
+        self.get_period_txs(mode, month, year)
+            .iter()
+            .flat_map(|tx| tx[5].split(", "))
+            .filter_map(|tag| tag.split_once(':'))
+            .map(|(parent, _)| parent.to_string())
+            .collect()
+    }
+
+    /// Returns every tx within the given period
+    fn get_period_txs(&self, mode: &IndexedData, month: usize, year: usize) -> Vec<&Vec<String>> {
+        let target_ids: Vec<i32> = match mode.index {
+            // 0 = monthly mode. Select the data only of the given month year
+            0 => vec![month as i32 + (year as i32 * 12)],
+            // 1 = yearly mode. Select the data of all months of the given year
+            1 => (0..MONTHS.len())
+                .map(|i| i as i32 + (year as i32 * 12))
+                .collect(),
+            // 2 = all time mode. Select every single data
+            2 => (0..(YEARS.len() * MONTHS.len()) as i32).collect(),
+            _ => Vec::new(),
+        };
+
+        target_ids
+            .iter()
+            .flat_map(|target_id| &self.get_txs()[target_id])
+            .collect()
+    }
+
+    /// Joins the parts of every split tx into a single tx with the total amount, all the tx methods
+    /// and all the tags so a split payment is counted once. The id_num of the first part is kept
+    fn merge_split_parts(&self, txs: Vec<&Vec<String>>) -> MyVec {
+        let mut merged_txs: MyVec = Vec::new();
+        let mut merged_index = HashMap::new();
+
+        for tx in txs {
+            let split_id = tx[6]
+                .parse::<i32>()
+                .ok()
+                .and_then(|id_num| self.split_ids.get(&id_num));
+
+            let Some(split_id) = split_id else {
+                merged_txs.push(tx.clone());
+                continue;
+            };
+
+            // a split transfer turns into an expense and an income. Those are kept apart
+            let key = (*split_id, tx[4].clone());
+
+            if let Some(index) = merged_index.get(&key) {
+                let merged_tx: &mut Vec<String> = &mut merged_txs[*index];
+                let total = merged_tx[3].parse::<f64>().unwrap() + tx[3].parse::<f64>().unwrap();
+                merged_tx[3] = format!("{total:.2}");
+
+                for (column, separator) in [(2, " & "), (5, ", ")] {
+                    if !merged_tx[column]
+                        .split(separator)
+                        .any(|value| value == tx[column])
+                    {
+                        merged_tx[column] =
+                            format!("{}{separator}{}", merged_tx[column], tx[column]);
+                    }
+                }
+            } else {
+                merged_index.insert(key, merged_txs.len());
+                merged_txs.push(tx.clone());
+            }
+        }
+
+        merged_txs
+    }
+
+    /// Returns how the income and expense of the given tag is split across the tx methods
+    /// within the given period. Each row contains the method, income, expense and
+    /// the % of the tag's income and expense. Methods the tag was never used on are skipped
+    pub fn get_tag_method_data(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+        tag: &str,
+        tx_methods: &[String],
+    ) -> Vec<Vec<String>> {
+        let mut method_income = vec![0.0; tx_methods.len()];
+        let mut method_expense = vec![0.0; tx_methods.len()];
+
+        for tx in self.get_period_txs(mode, month, year) {
+            // a parent tag also counts every one of its child tags
+            let total_matches = tx[5]
+                .split(", ")
+                .filter(|tx_tag| is_same_tag(tx_tag, tag))
+                .count();
+
+            if total_matches == 0 {
+                continue;
+            }
+
+            let Some(method_index) = tx_methods.iter().position(|method| method == &tx[2]) else {
+                continue;
+            };
+
+            let tx_amount = tx[3].parse::<f64>().unwrap() * total_matches as f64;
+
+            match tx[4].as_str() {
+                "Income" => method_income[method_index] += tx_amount,
+                "Expense" => method_expense[method_index] += tx_amount,
+                _ => {}
+            }
+        }
+
+        let total_income: f64 = method_income.iter().sum();
+        let total_expense: f64 = method_expense.iter().sum();
+
+        let get_percentage = |value: f64, total: f64| {
+            if value == 0.0 {
+                format!("{:.2}", 0.0)
+            } else {
+                format!("{:.2}", (value / total) * 100.0)
+            }
+        };
+
+        tx_methods
+            .iter()
+            .zip(method_income)
+            .zip(method_expense)
+            .filter(|((_, income), expense)| *income != 0.0 || *expense != 0.0)
+            .map(|((method, income), expense)| {
+                vec![
+                    method.to_string(),
+                    format!("{income:.2}"),
+                    format!("{expense:.2}"),
+                    get_percentage(income, total_income),
+                    get_percentage(expense, total_expense),
+                ]
+            })
+            .collect()
+    }
+
+    /// Returns the month and year of the period right before the given one. Monthly mode goes
+    /// to the earlier month and yearly mode to the earlier year. All time mode has no earlier period
+    fn get_previous_period(
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+    ) -> Option<(usize, usize)> {
+        match mode.index {
+            0 if month > 0 => Some((month - 1, year)),
+            0 if year > 0 => Some((MONTHS.len() - 1, year - 1)),
+            1 if year > 0 => Some((month, year - 1)),
+            _ => None,
+        }
+    }
+
+    /// Compares each tag of the given table data with the period right before the selected one.
+    /// The total of a tag is its income and expense combined. Each row contains the total of the
+    /// earlier period and the % change from it, in the same order as the given table data
+    pub fn get_comparison_data(
+        &self,
+        table_data: &[Vec<String>],
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+    ) -> Vec<Vec<String>> {
+        let Some((previous_month, previous_year)) =
+            SummaryData::get_previous_period(mode, month, year)
+        else {
+            return vec![vec![String::from("-"), String::from("-")]; table_data.len()];
+        };
+
+        let mut previous_totals: HashMap<&str, f64> = HashMap::new();
+
+        for tx in self.get_period_txs(mode, previous_month, previous_year) {
+            if tx[4] != "Income" && tx[4] != "Expense" {
+                continue;
+            }
+
+            let tx_amount: f64 = tx[3].parse().unwrap();
+
+            for tag in tx[5].split(", ") {
+                *previous_totals.entry(tag).or_insert(0.0) += tx_amount;
+
+                // the parent tag is the total of its child tags
+                if let Some((parent, _)) = tag.split_once(':') {
+                    *previous_totals.entry(parent).or_insert(0.0) += tx_amount;
+                }
+            }
+        }
+
+        table_data
+            .iter()
+            .map(|row| {
+                let current_total = row[1].parse::<f64>().unwrap() + row[2].parse::<f64>().unwrap();
+                let previous_total = previous_totals.get(row[0].as_str()).copied().unwrap_or(0.0);
+
+                // a tag that did not exist earlier has nothing to compare with
+                let change = if previous_total == 0.0 {
+                    String::from("-")
+                } else {
+                    format!(
+                        "{:.2}",
+                        (current_total - previous_total) / previous_total * 100.0
+                    )
+                };
+
+                vec![format!("{previous_total:.2}"), change]
+            })
+            .collect()
+    }
+
+    /// Returns the monthly total of every tag for the 12 months that end on the last month of
+    /// the given period. All time mode ends on the current month. The total of a tag is its
+    /// income and expense combined and parent tags include their child tags
+    pub fn get_tag_trends(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+    ) -> HashMap<String, Vec<f64>> {
+        let last_month_id = match mode.index {
+            0 => month + year * MONTHS.len(),
+            1 => MONTHS.len() - 1 + year * MONTHS.len(),
+            _ => get_current_month_id(),
+        };
+
+        let mut to_return: HashMap<String, Vec<f64>> = HashMap::new();
+
+        for trend_index in 0..TREND_MONTHS {
+            // months before the first supported year are left at 0
+            let Some(target_id) = (last_month_id + trend_index + 1).checked_sub(TREND_MONTHS)
+            else {
+                continue;
+            };
+
+            for tx in &self.get_txs()[&(target_id as i32)] {
+                if tx[4] != "Income" && tx[4] != "Expense" {
+                    continue;
+                }
+
+                let tx_amount: f64 = tx[3].parse().unwrap();
+
+                for tag in tx[5].split(", ") {
+                    let mut trend_tags = vec![tag];
+
+                    if let Some((parent, _)) = tag.split_once(':') {
+                        trend_tags.push(parent);
+                    }
+
+                    for trend_tag in trend_tags {
+                        to_return
+                            .entry(trend_tag.to_string())
+                            .or_insert_with(|| vec![0.0; TREND_MONTHS])[trend_index] += tx_amount;
+                    }
+                }
+            }
+        }
+
+        to_return
+    }
+
+    /// Returns the lifetime statistics of the tag or None if no tx has it. A parent tag includes
+    /// its child tags
+    pub fn get_tag_stats(&self, tag: &str) -> Option<TagStats> {
+        let current_month_id = get_current_month_id();
+        let child_prefix = format!("{tag}:");
+
+        let mut total_txs = 0;
+        let mut total_income = 0.0;
+        let mut total_expense = 0.0;
+        let mut trend = vec![0.0; TREND_MONTHS];
+        let mut first_month_id = None;
+        let mut first_date: Option<NaiveDate> = None;
+        let mut last_date: Option<NaiveDate> = None;
+
+        for month_id in 0..MONTHS.len() * YEARS.len() {
+            for tx in &self.get_txs()[&(month_id as i32)] {
+                if !tx[5]
+                    .split(", ")
+                    .any(|tx_tag| tx_tag == tag || tx_tag.starts_with(&child_prefix))
+                {
+                    continue;
+                }
+
+                let tx_amount: f64 = tx[3].parse().unwrap();
+                match tx[4].as_str() {
+                    "Income" => total_income += tx_amount,
+                    "Expense" => total_expense += tx_amount,
+                    _ => continue,
+                }
+
+                total_txs += 1;
+                first_month_id.get_or_insert(month_id);
+
+                if let Some(trend_index) = (month_id + TREND_MONTHS)
+                    .checked_sub(current_month_id + 1)
+                    .filter(|index| *index < TREND_MONTHS)
+                {
+                    trend[trend_index] += tx_amount;
+                }
+
+                let date = NaiveDate::parse_from_str(&tx[0], "%Y-%m-%d").unwrap();
+                first_date = Some(first_date.map_or(date, |first| first.min(date)));
+                last_date = Some(last_date.map_or(date, |last| last.max(date)));
+            }
+        }
+
+        let (Some(first_month_id), Some(first_date), Some(last_date)) =
+            (first_month_id, first_date, last_date)
+        else {
+            return None;
+        };
+
+        // future txs extend the period up to their month
+        let last_month_id = current_month_id
+            .max(last_date.month0() as usize + (last_date.year() - 2022) as usize * MONTHS.len());
+        let total_months = (last_month_id - first_month_id + 1) as f64;
+
+        Some(TagStats {
+            tag: tag.to_string(),
+            total_txs,
+            total_income,
+            total_expense,
+            monthly_income: total_income / total_months,
+            monthly_expense: total_expense / total_months,
+            trend,
+            first_date: first_date.format("%d-%m-%Y").to_string(),
+            last_date: last_date.format("%d-%m-%Y").to_string(),
+        })
+    }
+
+    /// Returns the largest expenses followed by the largest incomes within the given period,
+    /// each sorted from the biggest amount. Each row contains the date, details, tx method,
+    /// amount, tx type and the `id_num` of the tx
+    pub fn get_largest_txs(&self, mode: &IndexedData, month: usize, year: usize) -> MyVec {
+        let period_txs = self.merge_split_parts(self.get_period_txs(mode, month, year));
+
+        let mut to_return = Vec::new();
+
+        for tx_type in ["Expense", "Income"] {
+            let mut txs = period_txs
+                .iter()
+                .filter(|tx| tx[4] == tx_type)
+                .collect::<Vec<_>>();
+
+            txs.sort_by(|a, b| {
+                let val_a: f64 = a[3].parse().unwrap();
+                let val_b: f64 = b[3].parse().unwrap();
+                val_b.partial_cmp(&val_a).unwrap()
+            });
+
+            for tx in txs.into_iter().take(LARGEST_TX_LIMIT) {
+                to_return.push(vec![
+                    tx[0].to_string(),
+                    tx[1].to_string(),
+                    tx[2].to_string(),
+                    tx[3].to_string(),
+                    tx[4].to_string(),
+                    tx[6].to_string(),
+                ]);
+            }
+        }
+
+        to_return
+    }
+
+    /// Returns various statistics of the txs within the given period. Each row contains the
+    /// name of the statistic and its value. The daily expense is averaged over every day of the
+    /// period up to today, including days without any tx
+    pub fn get_statistics(&self, mode: &IndexedData, month: usize, year: usize) -> MyVec {
+        let period_txs = self.merge_split_parts(self.get_period_txs(mode, month, year));
+
+        let mut total_expense = 0.0;
+        let mut total_income = 0.0;
+        let mut amounts: Vec<f64> = Vec::new();
+        let mut income_sources: Vec<&str> = Vec::new();
+        let mut daily_txs: Vec<(&str, usize)> = Vec::new();
+
+        for tx in &period_txs {
+            let tx_amount: f64 = tx[3].parse().unwrap();
+
+            match tx[4].as_str() {
+                "Expense" => total_expense += tx_amount,
+                "Income" => {
+                    total_income += tx_amount;
+                    for tag in tx[5].split(", ") {
+                        if !income_sources.contains(&tag) {
+                            income_sources.push(tag);
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            amounts.push(tx_amount);
+
+            // txs are ordered by date so same day txs are next to each other
+            match daily_txs.last_mut() {
+                Some(last_day) if last_day.0 == tx[0] => last_day.1 += 1,
+                _ => daily_txs.push((&tx[0], 1)),
+            }
+        }
+
+        let today = Local::now().date_naive();
+
+        let period_start = match mode.index {
+            0 => NaiveDate::from_ymd_opt(YEARS[year].parse().unwrap(), month as u32 + 1, 1),
+            1 => NaiveDate::from_ymd_opt(YEARS[year].parse().unwrap(), 1, 1),
+            _ => period_txs
+                .first()
+                .and_then(|tx| NaiveDate::parse_from_str(&tx[0], "%Y-%m-%d").ok()),
+        };
+
+        let period_end = match mode.index {
+            0 => period_start
+                .and_then(|date| date.checked_add_months(Months::new(1)))
+                .and_then(|date| date.pred_opt()),
+            1 => NaiveDate::from_ymd_opt(YEARS[year].parse().unwrap(), 12, 31),
+            _ => period_txs
+                .last()
+                .and_then(|tx| NaiveDate::parse_from_str(&tx[0], "%Y-%m-%d").ok())
+                .map(|date| date.max(today)),
+        };
+
+        // an ongoing period is only counted up to today
+        let total_days = match (period_start, period_end) {
+            (Some(start), Some(end)) => (end.min(today.max(start)) - start).num_days() + 1,
+            _ => 0,
+        };
+
+        let average_daily_expense = if total_days > 0 {
+            total_expense / total_days as f64
+        } else {
+            0.0
+        };
+
+        amounts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let median_amount = match amounts.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (amounts[len / 2 - 1] + amounts[len / 2]) / 2.0,
+            len => amounts[len / 2],
+        };
+
+        // the earliest day wins when multiple days have the same number of txs
+        let busiest_day = daily_txs
+            .iter()
+            .fold(None, |busiest: Option<&(&str, usize)>, day| match busiest {
+                Some(busiest) if busiest.1 >= day.1 => Some(busiest),
+                _ => Some(day),
+            })
+            .map_or(String::from("-"), |(date, total)| {
+                format!("{} ({total} txs)", reverse_date_format(date.to_string()))
+            });
+
+        let average_source_income = if income_sources.is_empty() {
+            0.0
+        } else {
+            total_income / income_sources.len() as f64
+        };
+
+        vec![
+            vec![
+                String::from("Average Daily Expense"),
+                format!("{average_daily_expense:.2}"),
+            ],
+            vec![
+                String::from("Median Tx Amount"),
+                format!("{median_amount:.2}"),
+            ],
+            vec![String::from("Total Txs"), period_txs.len().to_string()],
+            vec![String::from("Busiest Day"), busiest_day],
+            vec![
+                String::from("Average Income per Source"),
+                format!("{average_source_income:.2}"),
+            ],
+        ]
+    }
+
+    /// Returns a vector that will be used to highlight points such as largest transaction,
+    /// biggest income etc
+    pub fn get_tx_data(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+        conn: &Connection,
+    ) -> (MyVec, MyVec, MyVec, MyVec, MyVec) {
+        let all_methods = get_all_tx_methods(conn);
+        let mut total_income: f64 = 0.0;
+        let mut total_expense: f64 = 0.0;
+
+        // (Amount, Method, date)
+        let mut biggest_earning = (0.0, String::from("-"), String::from("-"));
+        let mut biggest_expense = (0.0, String::from("-"), String::from("-"));
+
+        let mut largest_monthly_earning = 0.0;
+        let mut largest_monthly_expense = 0.0;
+
+        // (Amount, Method, date)
+        let mut peak_earning = (0.0, String::from("-"));
+        let mut peak_expense = (0.0, String::from("-"));
+        let mut total_month_checked = 0.0;
+
+        // {Method Name, Amount}
+        let mut method_earning = HashMap::new();
+        let mut method_expense = HashMap::new();
+
+        for method in &all_methods {
+            method_earning.insert(method.to_string(), 0.0);
+            method_expense.insert(method.to_string(), 0.0);
+        }
+
+        match mode.index {
+            0 => {
+                let target_id = month as i32 + (year as i32 * 12);
+                let tx_data = &self.get_txs()[&target_id];
+                if !tx_data.is_empty() {
+                    total_month_checked += 1.0;
+                }
+
+                self.update_tx_data(
+                    tx_data,
+                    &mut total_income,
+                    &mut total_expense,
+                    &mut biggest_earning,
+                    &mut biggest_expense,
+                    &mut largest_monthly_earning,
+                    &mut largest_monthly_expense,
+                    &mut peak_earning,
+                    &mut peak_expense,
+                    &mut method_earning,
+                    &mut method_expense,
+                    month,
+                    year,
+                );
+            }
+            1 => {
+                for i in 0..MONTHS.len() {
+                    let target_id = i as i32 + (year as i32 * 12);
+                    let tx_data = &self.get_txs()[&target_id];
+                    if !tx_data.is_empty() {
+                        total_month_checked += 1.0;
+                    }
+
+                    self.update_tx_data(
+                        tx_data,
+                        &mut total_income,
+                        &mut total_expense,
+                        &mut biggest_earning,
+                        &mut biggest_expense,
+                        &mut largest_monthly_earning,
+                        &mut largest_monthly_expense,
+                        &mut peak_earning,
+                        &mut peak_expense,
+                        &mut method_earning,
+                        &mut method_expense,
+                        i,
+                        year,
+                    );
+                }
+            }
+            2 => {
+                for x in 0..YEARS.len() {
+                    for i in 0..MONTHS.len() {
+                        let target_id = i as i32 + (x as i32 * 12);
+                        let tx_data = &self.get_txs()[&target_id];
+                        if !tx_data.is_empty() {
+                            total_month_checked += 1.0;
+                        }
+
+                        self.update_tx_data(
+                            tx_data,
+                            &mut total_income,
+                            &mut total_expense,
+                            &mut biggest_earning,
+                            &mut biggest_expense,
+                            &mut largest_monthly_earning,
+                            &mut largest_monthly_expense,
+                            &mut peak_earning,
+                            &mut peak_expense,
+                            &mut method_earning,
+                            &mut method_expense,
+                            i,
+                            x,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let (income_percentage, expense_percentage) =
+            self.get_percentages(total_income, total_expense);
+
+        let average_income = if total_income != 0.0 {
+            total_income / total_month_checked
+        } else {
+            0.0
+        };
+
+        let average_expense = if total_income != 0.0 {
+            total_expense / total_month_checked
+        } else {
+            0.0
+        };
+
+        let mut method_data = Vec::new();
+
+        for method in &all_methods {
+            let earning_percentage = if method_earning[method] != 0.0 {
+                format!("{:.2}%", (method_earning[method] / total_income) * 100.0)
+            } else {
+                format!("{:.2}", 0.0)
+            };
+
+            let expense_percentage = if method_expense[method] != 0.0 {
+                format!("{:.2}%", (method_expense[method] / total_expense) * 100.0)
+            } else {
+                format!("{:.2}", 0.0)
+            };
+
+            let average_earning = if method_earning[method] != 0.0 {
+                format!("{:.2}", method_earning[method] / total_month_checked)
+            } else {
+                format!("{:.2}", 0.0)
+            };
+
+            let average_expense = if method_expense[method] != 0.0 {
+                format!("{:.2}", method_expense[method] / total_month_checked)
+            } else {
+                format!("{:.2}", 0.0)
+            };
+            method_data.push(vec![
+                method.to_string(),
+                format!("{:.2}", method_earning[method]),
+                format!("{:.2}", method_expense[method]),
+                earning_percentage,
+                expense_percentage,
+                average_earning,
+                average_expense,
+            ]);
+        }
+
+        let summary_data_1 = vec![
+            vec![
+                String::from("Total Income"),
+                format!("{:.2}", total_income),
+                income_percentage,
+            ],
+            vec![
+                String::from("Total Expense"),
+                format!("{:.2}", total_expense),
+                expense_percentage,
+            ],
+            vec![
+                String::from("Net"),
+                format!("{:.2}", total_income - total_expense),
+                String::from("-"),
+            ],
+        ];
+
+        let summary_data_2 = vec![
+            vec![
+                String::from("Average Income"),
+                format!("{:.2}", average_income),
+                String::from("-"),
+            ],
+            vec![
+                String::from("Average Expense"),
+                format!("{:.2}", average_expense),
+                String::from("-"),
+            ],
+        ];
+
+        let summary_data_3 = vec![
+            vec![
+                String::from("Largest Income"),
+                biggest_earning.2,
+                format!("{:.2}", biggest_earning.0),
+                biggest_earning.1,
+            ],
+            vec![
+                String::from("Largest Expense"),
+                biggest_expense.2,
+                format!("{:.2}", biggest_expense.0),
+                biggest_expense.1,
+            ],
+            vec![
+                String::from("Months Checked"),
+                total_month_checked.to_string(),
+                String::from("-"),
+                String::from("-"),
+            ],
+        ];
+
+        let summary_data_4 = vec![
+            vec![
+                String::from("Peak Earning"),
+                peak_earning.1,
+                format!("{:.2}", peak_earning.0),
+                String::from("-"),
+            ],
+            vec![
+                String::from("Peak Expense"),
+                peak_expense.1,
+                format!("{:.2}", peak_expense.0),
+                String::from("-"),
+            ],
+        ];
+
+        (
+            summary_data_1,
+            summary_data_2,
+            summary_data_3,
+            summary_data_4,
+            method_data,
+        )
+    }
+
+    /// Updates values based on the gathered data
+    #[allow(clippy::too_many_arguments)]
+    fn update_tx_data(
+        &self,
+        tx_data: &Vec<Vec<String>>,
+        total_income: &mut f64,
+        total_expense: &mut f64,
+        biggest_earning: &mut (f64, String, String),
+        biggest_expense: &mut (f64, String, String),
+        largest_monthly_earning: &mut f64,
+        largest_monthly_expense: &mut f64,
+        peak_earning: &mut (f64, String),
+        peak_expense: &mut (f64, String),
+        method_earning: &mut HashMap<String, f64>,
+        method_expense: &mut HashMap<String, f64>,
+        month: usize,
+        year: usize,
+    ) {
+        // gather all data from the given tx data
+        let (
+            current_total_income,
+            current_total_expense,
+            current_biggest_earning,
+            current_biggest_expense,
+            current_monthly_earning,
+            current_monthly_expense,
+        ) = self.get_data(tx_data, method_earning, method_expense);
+
+        *total_income += current_total_income;
+        *total_expense += current_total_expense;
+
+        if current_biggest_earning.0 > biggest_earning.0 {
+            *biggest_earning = current_biggest_earning;
+        }
+
+        if current_biggest_expense.0 > biggest_expense.0 {
+            *biggest_expense = current_biggest_expense;
+        }
+
+        if current_monthly_earning > *largest_monthly_earning {
+            *largest_monthly_earning = current_monthly_earning;
+            *peak_earning = (
+                *largest_monthly_earning,
+                format!("{}-{}", month + 1, YEARS[year]),
+            );
+        }
+
+        if current_monthly_expense > *largest_monthly_expense {
+            *largest_monthly_expense = current_monthly_expense;
+            *peak_expense = (
+                *largest_monthly_expense,
+                format!("{}-{}", month + 1, YEARS[year]),
+            );
+        }
+    }
+
+    /// Generates a vector to be used as table data from tag list
+    fn generate_table_data(
+        &self,
+        income_tags: &HashMap<&str, f64>,
+        expense_tags: &HashMap<&str, f64>,
+        net_tags: &HashMap<&str, f64>,
+    ) -> Vec<Vec<String>> {
+        let mut to_return = Vec::new();
+        let mut total_income = 0.0;
+        let mut total_expense = 0.0;
+
+        for (key, value) in income_tags {
+            let income = value;
+            let mut expense = 0.0;
+            let mut to_push = vec![(*key).to_string(), format!("{:.2}", value)];
+            total_income += value;
+
+            // if the same tag already exists on expense, get that value as well
+            if expense_tags.contains_key(key) {
+                expense = expense_tags[key];
+                to_push.push(format!("{:.2}", expense_tags[key]));
+                total_expense += expense_tags[key];
+            } else {
+                to_push.push(format!("{:.2}", 0.0));
+            }
+
+            to_push.push(format!("{:.2}", income - expense));
+            to_return.push(to_push);
+        }
+
+        for (key, value) in expense_tags {
+            let income = 0.0;
+            let expense = value;
+            // gather data only from the tags that didn't exist on Income tag list
+            if !income_tags.contains_key(key) {
+                let mut to_push = vec![
+                    (*key).to_string(),
+                    format!("{:.2}", 0.0),
+                    format!("{:.2}", value),
+                ];
+
+                to_push.push(format!("{:.2}", income - expense));
+                to_return.push(to_push);
+                total_expense += value;
+            }
+        }
+
+        for key in net_tags.keys() {
+            // gather data only from the tags that didn't exist on Income tag list
+            if !income_tags.contains_key(key) && !expense_tags.contains_key(key) {
+                to_return.push(vec![
+                    (*key).to_string(),
+                    format!("{:.2}", 0.0),
+                    format!("{:.2}", 0.0),
+                    format!("{:.2}", 0.0),
+                ]);
+            }
+        }
+        // we got the income and expense data earlier. Now need to loop again
+        // to gather the % data
+        for x in to_return.iter_mut() {
+            let income_percentage = if &x[1] != "0.00" {
+                let income = &x[1].parse::<f64>().unwrap();
+                format!("{:.2}", ((income / total_income) * 100.0))
+            } else {
+                format!("{:.2}", 0.0)
+            };
+
+            let expense_percentage = if &x[2] != "0.00" {
+                let expense = &x[2].parse::<f64>().unwrap();
+                format!("{:.2}", ((expense / total_expense) * 100.0))
+            } else {
+                format!("{:.2}", 0.0)
+            };
+
+            // how much of the period's income went to this tag
+            let income_share = if &x[2] != "0.00" && total_income != 0.0 {
+                let expense = &x[2].parse::<f64>().unwrap();
+                format!("{:.2}", ((expense / total_income) * 100.0))
+            } else {
+                format!("{:.2}", 0.0)
+            };
+
+            x.push(income_percentage);
+            x.push(expense_percentage);
+            x.push(income_share);
+        }
+
+        to_return
+    }
+
+    /// Takes 2 numbers and returns how much % are each of them
+    fn get_percentages(&self, value1: f64, value2: f64) -> (String, String) {
+        if value1 == 0.0 && value2 == 0.0 {
+            return (String::from("0.00"), String::from("0.00"));
+        }
+        let total = value1 + value2;
+        let percentage1 = (value1 / total) * 100.0;
+        let percentage2 = (value2 / total) * 100.0;
+        (format!("{percentage1:.2}%",), format!("{percentage2:.2}%",))
+    }
+}
+
+/// Whether the tx tag is the given tag or one of its `tag:child` tags
+fn is_same_tag(tx_tag: &str, tag: &str) -> bool {
+    tx_tag == tag
+        || tx_tag
+            .strip_prefix(tag)
+            .is_some_and(|child| child.starts_with(':'))
+}
+
+/// Rolls the `parent:child` tag rows up into their parent row by adding up every amount and %.
+/// The parent rows and the tags without a parent get sorted first. Then the sorted children
+/// of the expanded parents are placed right below them
+fn group_table_data(
+    table_data: Vec<Vec<String>>,
+    sort_type: &SortingType,
+    sort_direction: &SortingDirection,
+    expanded_tags: &HashSet<String>,
+) -> Vec<Vec<String>> {
+    let mut top_rows: Vec<Vec<String>> = Vec::new();
+    let mut child_rows: HashMap<String, Vec<Vec<String>>> = HashMap::new();
+
+    for row in table_data {
+        if let Some((parent, _)) = row[0].split_once(':') {
+            child_rows.entry(parent.to_string()).or_default().push(row);
+        } else {
+            top_rows.push(row);
+        }
+    }
+
+    for (parent, children) in &child_rows {
+        let parent_index = if let Some(index) = top_rows.iter().position(|row| &row[0] == parent) {
+            index
+        } else {
+            let mut new_row = vec![parent.to_string()];
+            new_row.extend(vec![format!("{:.2}", 0.0); 6]);
+            top_rows.push(new_row);
+            top_rows.len() - 1
+        };
+
+        let parent_row = &mut top_rows[parent_index];
+
+        for child in children {
+            for (index, value) in child.iter().enumerate().skip(1) {
+                let total =
+                    parent_row[index].parse::<f64>().unwrap() + value.parse::<f64>().unwrap();
+                parent_row[index] = format!("{total:.2}");
+            }
+        }
+    }
+
+    // same values keep the tag order
+    top_rows.sort();
+
+    let mut to_return = Vec::new();
+
+    for row in sort_table_data(top_rows, sort_type, sort_direction) {
+        let children = if expanded_tags.contains(&row[0]) {
+            child_rows.remove(&row[0])
+        } else {
+            None
+        };
+
+        to_return.push(row);
+
+        if let Some(mut children) = children {
+            children.sort();
+            to_return.extend(sort_table_data(children, sort_type, sort_direction));
+        }
+    }
+
+    to_return
+}
+
+/// Returns the balance id of the current month, capped at the last supported month
+fn get_current_month_id() -> usize {
+    let today = Local::now().date_naive();
+    let current_id = today.month0() as usize + (today.year() - 2022).max(0) as usize * MONTHS.len();
+    current_id.min(MONTHS.len() * YEARS.len() - 1)
+}
@@ -0,0 +1,221 @@
+use rusqlite::{Connection, OptionalExtension, Result as sqlResult};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::outputs::SyncError;
+use crate::tx_handler::{insert_tx, remove_tx};
+use crate::utility::get_all_tx_methods;
+
+/// The tables that point to a tx with its `id_num`
+const TX_ID_TABLES: [&str; 8] = [
+    "tx_all",
+    "changes_all",
+    "tx_notes",
+    "tx_status",
+    "tx_splits",
+    "tx_attachments",
+    "tx_foreign_amounts",
+    "tx_exchange_rates",
+];
+
+/// The number of txs a sync changed in the local db
+#[derive(Debug, Default, PartialEq)]
+pub struct MergeSummary {
+    pub added: usize,
+    pub edited: usize,
+    pub deleted: usize,
+}
+
+/// The part of a tx that gets synced. Notes, statuses and attachments stay on the device
+#[derive(Clone, Debug, PartialEq)]
+struct SyncedTx {
+    date: String,
+    details: String,
+    tx_method: String,
+    amount: String,
+    tx_type: String,
+    tags: String,
+    rate: Option<f64>,
+}
+
+/// Returns every tx of the attached db with the schema name by their `id_num`
+fn get_synced_txs(schema: &str, conn: &Connection) -> sqlResult<BTreeMap<i32, SyncedTx>> {
+    let mut statement = conn.prepare(&format!(
+        "SELECT t.id_num, t.date, t.details, t.tx_method, t.amount, t.tx_type, t.tags, r.rate
+        FROM {schema}.tx_all t LEFT JOIN {schema}.tx_exchange_rates r ON r.id_num = t.id_num"
+    ))?;
+
+    let rows = statement.query_map([], |row| {
+        Ok((
+            row.get(0)?,
+            SyncedTx {
+                date: row.get(1)?,
+                details: row.get(2)?,
+                tx_method: row.get(3)?,
+                amount: row.get(4)?,
+                tx_type: row.get(5)?,
+                tags: row.get(6)?,
+                rate: row.get(7)?,
+            },
+        ))
+    })?;
+
+    rows.collect()
+}
+
+/// Returns the highest `id_num` the attached db has ever given to a tx
+fn get_last_synced_id(schema: &str, conn: &Connection) -> sqlResult<i32> {
+    let sequence: Option<i32> = conn
+        .query_row(
+            &format!("SELECT seq FROM {schema}.sqlite_sequence WHERE name = 'tx_all'"),
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let last_id: Option<i32> = conn.query_row(
+        &format!("SELECT MAX(id_num) FROM {schema}.tx_all"),
+        [],
+        |row| row.get(0),
+    )?;
+
+    Ok(sequence.unwrap_or(0).max(last_id.unwrap_or(0)))
+}
+
+fn insert_synced_tx(id_num: i32, tx: &SyncedTx, conn: &Connection) -> sqlResult<()> {
+    insert_tx(
+        &tx.date,
+        &tx.details,
+        &tx.tx_method,
+        &tx.amount,
+        &tx.tx_type,
+        &tx.tags,
+        Some(&id_num.to_string()),
+        tx.rate,
+        conn,
+    )
+}
+
+/// Gives a tx a new `id_num` along with everything linked to it. The balances do not change
+fn move_tx_id(old_id: i32, new_id: i32, conn: &Connection) -> sqlResult<()> {
+    for table in TX_ID_TABLES {
+        conn.execute(
+            &format!("UPDATE {table} SET id_num = ?1 WHERE id_num = ?2"),
+            [new_id, old_id],
+        )?;
+    }
+    conn.execute(
+        "UPDATE tx_splits SET split_id = ?1 WHERE split_id = ?2",
+        [new_id, old_id],
+    )?;
+    Ok(())
+}
+
+/// Whether the local db has the same txs with the same ids as the attached db
+pub fn synced_txs_match(schema: &str, conn: &Connection) -> sqlResult<bool> {
+    Ok(get_synced_txs("main", conn)? == get_synced_txs(schema, conn)?)
+}
+
+/// Merges the txs of the attached remote db into the local db. The base is the attached db both
+/// sides had at the last sync. Without it every tx on both sides counts as new.
+///
+/// A tx that existed at the last sync takes the version of the side that changed it. If both
+/// sides changed it, the local version is kept. Txs added on both sides are all kept. The remote
+/// ones keep their ids so the ids stay the same on every device and the local ones are moved
+/// after them. A tx added on both sides with the same values is only kept once
+pub fn merge_synced_txs(
+    remote: &str,
+    base: Option<&str>,
+    conn: &mut Connection,
+) -> Result<MergeSummary, SyncError> {
+    let local_txs = get_synced_txs("main", conn)?;
+    let remote_txs = get_synced_txs(remote, conn)?;
+    let (base_txs, base_last_id) = match base {
+        Some(base) => (get_synced_txs(base, conn)?, get_last_synced_id(base, conn)?),
+        None => (BTreeMap::new(), 0),
+    };
+
+    let tx_methods = get_all_tx_methods(conn);
+    for tx in remote_txs.values() {
+        if let Some(method) = tx
+            .tx_method
+            .split(" to ")
+            .find(|method| !tx_methods.iter().any(|m| m == method))
+        {
+            return Err(SyncError::MissingTxMethod(method.to_string()));
+        }
+    }
+
+    let mut summary = MergeSummary::default();
+
+    let sp = conn.savepoint()?;
+    // txs are moved to new ids one table at a time
+    sp.pragma_update(None, "defer_foreign_keys", true)?;
+
+    let shared_ids = base_txs
+        .keys()
+        .chain(local_txs.range(..=base_last_id).map(|(id, _)| id))
+        .chain(remote_txs.range(..=base_last_id).map(|(id, _)| id))
+        .copied()
+        .collect::<BTreeSet<i32>>();
+
+    for id_num in shared_ids {
+        let base_tx = base_txs.get(&id_num);
+        let local_tx = local_txs.get(&id_num);
+        let remote_tx = remote_txs.get(&id_num);
+
+        // the local tx was changed since the last sync or both sides are the same
+        if local_tx != base_tx || local_tx == remote_tx {
+            continue;
+        }
+
+        match (local_tx, remote_tx) {
+            (Some(_), Some(remote_tx)) => {
+                remove_tx(id_num, &sp)?;
+                insert_synced_tx(id_num, remote_tx, &sp)?;
+                summary.edited += 1;
+            }
+            (Some(_), None) => {
+                remove_tx(id_num, &sp)?;
+                summary.deleted += 1;
+            }
+            (None, Some(remote_tx)) => {
+                insert_synced_tx(id_num, remote_tx, &sp)?;
+                summary.added += 1;
+            }
+            (None, None) => {}
+        }
+    }
+
+    let remote_new = remote_txs
+        .range(base_last_id + 1..)
+        .collect::<Vec<(&i32, &SyncedTx)>>();
+
+    if !remote_new.is_empty() {
+        let mut next_id =
+            get_last_synced_id("main", &sp)?.max(get_last_synced_id(remote, &sp)?) + 1;
+
+        let mut local_new = Vec::new();
+        for (id_num, local_tx) in local_txs.range(base_last_id + 1..) {
+            move_tx_id(*id_num, next_id, &sp)?;
+            local_new.push((next_id, local_tx));
+            next_id += 1;
+        }
+
+        for (id_num, remote_tx) in remote_new {
+            if let Some(index) = local_new
+                .iter()
+                .position(|(_, local_tx)| *local_tx == remote_tx)
+            {
+                let (local_id, _) = local_new.remove(index);
+                move_tx_id(local_id, *id_num, &sp)?;
+                continue;
+            }
+
+            insert_synced_tx(*id_num, remote_tx, &sp)?;
+            summary.added += 1;
+        }
+    }
+
+    sp.commit()?;
+
+    Ok(summary)
+}
@@ -0,0 +1,9 @@
+mod merge;
+mod settings;
+mod sync_db;
+mod webdav;
+
+pub use merge::*;
+pub use settings::*;
+pub use sync_db::*;
+pub use webdav::*;
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Result;
+use std::path::{Path, PathBuf};
+
+use crate::utility::write_private_file;
+
+/// The WebDAV server the db gets synced with. Saved as `sync_settings.json` in the same location
+/// as `config.json`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SyncSettings {
+    /// The folder on the server the synced db is kept in like
+    /// `https://cloud.example.com/remote.php/dav/files/user/Rex`
+    pub url: String,
+    pub username: String,
+    pub password: String,
+}
+
+/// Returns the location of `sync_settings.json` which is kept beside the original db location
+pub fn get_sync_settings_path(original_db_path: &Path) -> PathBuf {
+    let mut settings_path = original_db_path.to_owned();
+    settings_path.pop();
+    settings_path.push("sync_settings.json");
+    settings_path
+}
+
+/// Loads the saved WebDAV server. None if sync was never set up or the file could not be parsed
+pub fn get_sync_settings(original_db_path: &Path) -> Option<SyncSettings> {
+    fs::read_to_string(get_sync_settings_path(original_db_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Saves the WebDAV server the db gets synced with. Earlier settings are replaced. Only the user
+/// can read the file as it holds the password
+pub fn save_sync_settings(original_db_path: &Path, settings: &SyncSettings) -> Result<()> {
+    let content = serde_json::to_string_pretty(settings)?;
+    write_private_file(&get_sync_settings_path(original_db_path), &content)
+}
+
+/// Deletes `sync_settings.json` which turns sync off
+pub fn delete_sync_settings(original_db_path: &Path) -> Result<()> {
+    let settings_path = get_sync_settings_path(original_db_path);
+
+    if !settings_path.exists() {
+        return Ok(());
+    }
+
+    fs::remove_file(settings_path)
+}
@@ -0,0 +1,109 @@
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+
+use crate::db::{attach_encrypted_db, export_encrypted_db};
+use crate::outputs::SyncError;
+use crate::sync_handler::{
+    merge_synced_txs, synced_txs_match, MergeSummary, SyncSettings, WebDavClient,
+};
+
+const REMOTE_SCHEMA: &str = "sync_remote";
+const BASE_SCHEMA: &str = "sync_base";
+
+/// Returns the name the db is kept with on the server. Every profile has its own file
+fn get_remote_file_name(db_path: &Path) -> String {
+    let stem = db_path
+        .file_stem()
+        .map_or_else(|| "data".into(), |stem| stem.to_string_lossy());
+    format!("{stem}.rex-sync")
+}
+
+/// Syncs the db with the copy on the WebDAV server. The copy is encrypted with the passphrase.
+///
+/// If only one side changed since the last sync, the changed side wins. Otherwise the txs of both
+/// sides get merged one by one. The merged db is uploaded and a copy of it is kept next to the db
+/// to know what changed at the next sync. Returns what the sync did
+#[cfg(not(tarpaulin_include))]
+pub fn sync_db(
+    settings: &SyncSettings,
+    passphrase: &str,
+    db_path: &Path,
+    conn: &mut Connection,
+) -> Result<String, SyncError> {
+    let client = WebDavClient::new(settings)?;
+    let file_name = get_remote_file_name(db_path);
+    let base_path = db_path.with_extension("sync-base");
+    let remote_path = db_path.with_extension("sync-remote");
+
+    let remote = client.download(&file_name)?;
+
+    let mut summary = MergeSummary::default();
+    let mut in_sync = false;
+
+    if let Some(remote_file) = &remote {
+        fs::write(&remote_path, &remote_file.content)?;
+
+        let status = merge_remote_db(&remote_path, &base_path, passphrase, conn);
+
+        conn.execute(&format!("DETACH DATABASE {REMOTE_SCHEMA}"), [])
+            .ok();
+        conn.execute(&format!("DETACH DATABASE {BASE_SCHEMA}"), [])
+            .ok();
+        fs::remove_file(&remote_path).ok();
+
+        (summary, in_sync) = status?;
+    }
+
+    let synced_content = match remote {
+        Some(remote_file) if in_sync => remote_file.content,
+        _ => {
+            let local_path = db_path.with_extension("sync-local");
+            fs::remove_file(&local_path).ok();
+
+            let status = export_encrypted_db(&local_path, passphrase, conn)
+                .map_err(SyncError::from)
+                .and_then(|()| Ok(fs::read(&local_path)?));
+            fs::remove_file(&local_path).ok();
+
+            let content = status?;
+            client.upload(&file_name, content.clone(), remote.as_ref())?;
+            content
+        }
+    };
+
+    fs::write(&base_path, synced_content)?;
+
+    if summary == MergeSummary::default() {
+        Ok("Synced with the WebDAV server. No changes came from the other devices.".to_string())
+    } else {
+        Ok(format!(
+            "Synced with the WebDAV server. {} added, {} edited and {} deleted by the other devices.",
+            summary.added, summary.edited, summary.deleted
+        ))
+    }
+}
+
+/// Merges the downloaded db into the local one. Returns what changed and whether the local db
+/// now has the same txs as the downloaded one
+#[cfg(not(tarpaulin_include))]
+fn merge_remote_db(
+    remote_path: &Path,
+    base_path: &Path,
+    passphrase: &str,
+    conn: &mut Connection,
+) -> Result<(MergeSummary, bool), SyncError> {
+    attach_encrypted_db(remote_path, REMOTE_SCHEMA, passphrase, conn)?;
+
+    let base = if base_path.exists() {
+        attach_encrypted_db(base_path, BASE_SCHEMA, passphrase, conn)?;
+        Some(BASE_SCHEMA)
+    } else {
+        None
+    };
+
+    let summary = merge_synced_txs(REMOTE_SCHEMA, base, conn)?;
+    let in_sync = synced_txs_match(REMOTE_SCHEMA, conn)?;
+
+    Ok((summary, in_sync))
+}
@@ -0,0 +1,99 @@
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::header::{ETAG, IF_MATCH, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+use crate::outputs::SyncError;
+use crate::sync_handler::SyncSettings;
+
+/// A file downloaded from the WebDAV server
+pub struct RemoteFile {
+    pub content: Vec<u8>,
+    /// Changes every time the file is replaced on the server. Used to make sure no other device
+    /// uploaded in between a download and an upload
+    pub etag: Option<String>,
+}
+
+/// Downloads and uploads files in a single folder of a WebDAV server
+pub struct WebDavClient {
+    client: Client,
+    url: String,
+    username: String,
+    password: String,
+}
+
+impl WebDavClient {
+    #[cfg(not(tarpaulin_include))]
+    pub fn new(settings: &SyncSettings) -> Result<Self, SyncError> {
+        let client = Client::builder()
+            .user_agent("Rex")
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        Ok(WebDavClient {
+            client,
+            url: settings.url.trim().trim_end_matches('/').to_string(),
+            username: settings.username.clone(),
+            password: settings.password.clone(),
+        })
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn with_login(&self, request: RequestBuilder) -> RequestBuilder {
+        if self.username.is_empty() {
+            request
+        } else {
+            request.basic_auth(&self.username, Some(&self.password))
+        }
+    }
+
+    /// Downloads a file from the folder. None if the file is not on the server yet
+    #[cfg(not(tarpaulin_include))]
+    pub fn download(&self, file_name: &str) -> Result<Option<RemoteFile>, SyncError> {
+        let response = self
+            .with_login(self.client.get(format!("{}/{file_name}", self.url)))
+            .send()?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => {
+                let etag = response
+                    .headers()
+                    .get(ETAG)
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(ToString::to_string);
+                let content = response.bytes()?.to_vec();
+                Ok(Some(RemoteFile { content, etag }))
+            }
+            status => Err(SyncError::UnexpectedStatus(status.as_u16())),
+        }
+    }
+
+    /// Uploads a file to the folder in place of the downloaded one. The upload is refused if the
+    /// file on the server changed since it was downloaded or if it exists when nothing was
+    /// downloaded. Servers that send no etag cannot be checked
+    #[cfg(not(tarpaulin_include))]
+    pub fn upload(
+        &self,
+        file_name: &str,
+        content: Vec<u8>,
+        downloaded: Option<&RemoteFile>,
+    ) -> Result<(), SyncError> {
+        let request = self.client.put(format!("{}/{file_name}", self.url));
+        let request = match downloaded {
+            Some(RemoteFile {
+                etag: Some(etag), ..
+            }) => request.header(IF_MATCH, etag),
+            Some(_) => request,
+            None => request.header(IF_NONE_MATCH, "*"),
+        };
+
+        let response = self.with_login(request).body(content).send()?;
+
+        match response.status() {
+            StatusCode::PRECONDITION_FAILED => Err(SyncError::RemoteChanged),
+            status if status.is_success() => Ok(()),
+            status => Err(SyncError::UnexpectedStatus(status.as_u16())),
+        }
+    }
+}
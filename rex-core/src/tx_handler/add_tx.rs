@@ -1,6 +1,9 @@
 use rusqlite::{Connection, Result as sqlResult};
 use std::collections::HashMap;
+use tracing::debug;
 
+use crate::db::{clear_snapshots_from, set_tx_exchange_rate};
+use crate::tx_handler::tag_rule::apply_tag_rules;
 use crate::utility::{
     get_all_tx_methods, get_last_balance_id, get_last_balances, get_last_time_balance,
     get_last_tx_id,
@@ -12,6 +15,7 @@ use crate::utility::{
 /// - Calculate the absolute final balance
 /// - Find the Changes that happened due to the transaction
 /// - Push them to the database
+#[allow(clippy::too_many_arguments)]
 pub fn add_tx(
     date: &str,
     details: &str,
@@ -24,7 +28,117 @@ pub fn add_tx(
 ) -> sqlResult<()> {
     // create a connection and a savepoint
     let sp = conn.savepoint()?;
+    insert_tx(
+        date, details, tx_method, amount, tx_type, tags, id_num, None, &sp,
+    )?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// Adds a transfer between tx methods of different currencies. The To Method receives the amount
+/// multiplied by the rate
+#[allow(clippy::too_many_arguments)]
+pub fn add_exchange_transfer(
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tags: &str,
+    rate: f64,
+    id_num: Option<&str>,
+    conn: &mut Connection,
+) -> sqlResult<()> {
+    let sp = conn.savepoint()?;
+    insert_tx(
+        date,
+        details,
+        tx_method,
+        amount,
+        "Transfer",
+        tags,
+        id_num,
+        Some(rate),
+        &sp,
+    )?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// Adds a transfer and the fee paid for it in a single transaction. The fee is added as
+/// an expense of the tx method the transfer is made from and is tagged as fees.
+/// If either one fails none of them are added. `rate` is the exchange rate of a transfer between
+/// different currencies. Returns the `id_num` of the transfer and the fee
+#[allow(clippy::too_many_arguments)]
+pub fn add_transfer_with_fee(
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tags: &str,
+    fee: &str,
+    rate: Option<f64>,
+    conn: &mut Connection,
+) -> sqlResult<(i32, i32)> {
+    let sp = conn.savepoint()?;
+    let id_nums = insert_transfer_with_fee(date, details, tx_method, amount, tags, fee, rate, &sp)?;
+    sp.commit()?;
+    Ok(id_nums)
+}
+
+/// Does the insertion of a transfer and its fee on the given connection without committing
+/// anything. Returns the `id_num` of the transfer and the fee
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn insert_transfer_with_fee(
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tags: &str,
+    fee: &str,
+    rate: Option<f64>,
+    sp: &Connection,
+) -> sqlResult<(i32, i32)> {
+    insert_tx(
+        date, details, tx_method, amount, "Transfer", tags, None, rate, sp,
+    )?;
+    let transfer_id = get_last_tx_id(sp)?;
 
+    let from_method = tx_method.split(" to ").next().unwrap();
+    let fee_details = if details.is_empty() {
+        "Transfer fee".to_string()
+    } else {
+        format!("Transfer fee: {details}")
+    };
+    insert_tx(
+        date,
+        &fee_details,
+        from_method,
+        fee,
+        "Expense",
+        "fees",
+        None,
+        None,
+        sp,
+    )?;
+    let fee_id = get_last_tx_id(sp)?;
+
+    Ok((transfer_id, fee_id))
+}
+
+/// Does the insertion and the balance updates of a transaction on the given connection
+/// without committing anything
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn insert_tx(
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tx_type: &str,
+    tags: &str,
+    id_num: Option<&str>,
+    rate: Option<f64>,
+    sp: &Connection,
+) -> sqlResult<()> {
     // the process goes through 4 parts
     // Add the tx itself in the db
     // calculate the amount to add/subtract from the balance_all table
@@ -37,8 +151,10 @@ pub fn add_tx(
         let query = r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type, id_num, tags) VALUES (?, ?, ?, ?, ?, ?, ?)"#;
         sp.execute(query, [date, details, tx_method, amount, tx_type, id, tags])?;
     } else {
+        // only new txs go through the tag rules so edited tags are kept as they are
+        let tags = apply_tag_rules(details, tx_method, amount, tags, sp);
         let query = r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type, tags) VALUES (?, ?, ?, ?, ?, ?)"#;
-        sp.execute(query, [date, details, tx_method, amount, tx_type, tags])?;
+        sp.execute(query, [date, details, tx_method, amount, tx_type, &tags])?;
     }
 
     // 2025-05-10
@@ -62,13 +178,19 @@ pub fn add_tx(
 
     let target_id_num = month + (year * 12);
 
+    // month end balances from this point are going to change
+    clear_snapshots_from(target_id_num, sp)?;
+
     // This is necessary for the foreign key field in the changes_all table
     // and must align with the latest transaction id_num
-    let mut last_id = get_last_tx_id(&sp)?;
+    let mut last_id = get_last_tx_id(sp)?;
     if let Some(id) = id_num {
         last_id = id.parse().unwrap();
     }
-    let last_balance_id = get_last_balance_id(&sp)?;
+    let last_balance_id = get_last_balance_id(sp)?;
+
+    // the To Method of a transfer between different currencies receives the converted amount
+    set_tx_exchange_rate(last_id, rate, sp)?;
 
     // we have to get these following data to push to the database
     // new_balance_data: the working month balance after the transaction
@@ -78,20 +200,21 @@ pub fn add_tx(
     let mut new_changes_data = Vec::new();
     let mut last_balance_data = HashMap::new();
 
-    let all_tx_methods = get_all_tx_methods(&sp);
-    let last_balance = get_last_balances(&sp);
+    let all_tx_methods = get_all_tx_methods(sp);
+    let last_balance = get_last_balances(sp);
 
     // Retrieve the current month's balance for each transaction method.
     let mut current_month_balance =
-        get_last_time_balance(month as usize, year as usize, &all_tx_methods, &sp);
+        get_last_time_balance(month as usize, year as usize, &all_tx_methods, sp);
 
     let int_amount = amount.parse::<f64>().unwrap();
+    let to_amount = int_amount * rate.unwrap_or(1.0);
 
     // Update the current month's balance based on the transaction type.
     match tx_type {
         "Transfer" => {
             let new_balance_from = current_month_balance[&from_method] - int_amount;
-            let new_balance_to = current_month_balance[&to_method] + int_amount;
+            let new_balance_to = current_month_balance[&to_method] + to_amount;
 
             // Update the current month's balance for both the "from" and "to" methods.
             *current_month_balance.get_mut(&from_method).unwrap() = new_balance_from;
@@ -130,9 +253,9 @@ pub fn add_tx(
             let edited_balance = current_last_balance - int_amount;
             last_balance_data.insert(&from_method, format!("{edited_balance:.2}"));
         } else if tx_type == "Transfer" && all_tx_methods[i] == to_method {
-            current_change = format!("↑{:.2}", &int_amount);
+            current_change = format!("↑{:.2}", &to_amount);
 
-            let edited_balance = current_last_balance + int_amount;
+            let edited_balance = current_last_balance + to_amount;
             last_balance_data.insert(&to_method, format!("{edited_balance:.2}"));
         } else if tx_type != "Transfer" && all_tx_methods[i] == tx_method {
             if tx_type == "Expense" {
@@ -191,6 +314,16 @@ pub fn add_tx(
     sp.execute(&balance_query, [])?;
     sp.execute(&last_balance_query, [])?;
     sp.execute(&changes_query, [date])?;
-    sp.commit()?;
+
+    debug!(
+        id_num = last_id,
+        date,
+        tx_method,
+        amount,
+        tx_type,
+        month_balance = ?new_balance_data,
+        final_balance = ?last_balance_data,
+        "Inserted tx"
+    );
     Ok(())
 }
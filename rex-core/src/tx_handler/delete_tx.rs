@@ -1,24 +1,44 @@
 use rusqlite::{Connection, Result as sqlResult};
+use tracing::debug;
 
+use crate::db::{clear_snapshots_from, get_tx_exchange_rate};
 use crate::utility::{get_all_tx_methods, get_last_balance_id, get_last_balances};
 
 /// Updates the absolute final balance, balance data and deletes the selected transaction.
 /// Foreign key cascade takes care of the Changes data in the database.
 pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
     let sp = conn.savepoint()?;
+    remove_tx(id_num, &sp)?;
+    sp.commit()?;
+    Ok(())
+}
 
-    let tx_methods = get_all_tx_methods(&sp);
+/// Deletes all the given transactions in a single transaction. If any one of them fails
+/// none of them are deleted
+pub fn delete_txs(id_nums: &[i32], conn: &mut Connection) -> sqlResult<()> {
+    let sp = conn.savepoint()?;
+    for id_num in id_nums {
+        remove_tx(*id_num, &sp)?;
+    }
+    sp.commit()?;
+    Ok(())
+}
+
+/// Does the balance updates and the deletion of a transaction on the given connection
+/// without committing anything
+pub(crate) fn remove_tx(id_num: i32, conn: &Connection) -> sqlResult<()> {
+    let tx_methods = get_all_tx_methods(conn);
 
     // contains the data of the final row data before the tx gets deleted
-    let last_balance = get_last_balances(&sp);
-    let last_balance_id = get_last_balance_id(&sp)?;
+    let last_balance = get_last_balances(conn);
+    let last_balance_id = get_last_balance_id(conn)?;
 
     // will contain data of the updated final balance row data
     let mut final_last_balance = Vec::new();
 
     // get the deletion tx data
     let query = format!("SELECT * FROM tx_all Where id_num = {id_num}",);
-    let data = sp.query_row(&query, [], |row| {
+    let data = conn.query_row(&query, [], |row| {
         let final_data: Vec<String> = vec![row.get(0)?, row.get(2)?, row.get(3)?, row.get(4)?];
         Ok(final_data)
     })?;
@@ -35,6 +55,9 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
 
     let mut target_id_num = month + (year * 12);
 
+    // month end balances from this point are going to change
+    clear_snapshots_from(target_id_num, conn)?;
+
     let mut from_method = "";
     let mut to_method = "";
 
@@ -51,6 +74,8 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
 
     let amount = &data[2].parse::<f64>().unwrap();
     let tx_type: &str = &data[3];
+    // the To Method of a transfer between different currencies received the converted amount
+    let to_amount = amount * get_tx_exchange_rate(id_num, conn).unwrap_or(1.0);
 
     // loop through all rows in the balance_all table from the deletion point and update balance
     // basically there are 193 rows(at the time of writing) on balance_all table. each row = 1 month. if month 4 had balance of 100,
@@ -73,7 +98,7 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
             target_id_num
         );
 
-        let current_month_balance = sp.query_row(&query, [], |row| {
+        let current_month_balance = conn.query_row(&query, [], |row| {
             let mut final_data: Vec<String> = Vec::new();
             for i in 0..tx_methods.len() {
                 let row_data: f64 = row.get(i)?;
@@ -120,7 +145,7 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
                 updated_month_balance.push(format!("{current_amount:.2}",));
             } else if tx_methods[i] == to_method && current_month_balance[i] != "0.00" {
                 let mut current_amount = current_month_balance[i].parse::<f64>().unwrap();
-                current_amount -= amount;
+                current_amount -= to_amount;
                 updated_month_balance.push(format!("{current_amount:.2}",));
             } else {
                 updated_month_balance.push(format!(
@@ -140,7 +165,7 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
         let balance_query =
             format!("UPDATE balance_all SET {set_values} WHERE id_num = {target_id_num}");
 
-        sp.execute(&balance_query, [])?;
+        conn.execute(&balance_query, [])?;
 
         // 193 is the absolute final balance which we don't need to modify
         target_id_num += 1;
@@ -163,7 +188,7 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
         } else if tx_methods[i] == from_method && tx_type == "Transfer" {
             current_balance += amount;
         } else if tx_methods[i] == to_method && tx_type == "Transfer" {
-            current_balance -= amount;
+            current_balance -= to_amount;
         }
         final_last_balance.push(format!("{current_balance:.2}",));
     }
@@ -181,9 +206,18 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
         last_balance_id
     );
 
-    sp.execute(&last_balance_query, [])?;
-    sp.execute(&del_query, [])?;
-
-    sp.commit()?;
+    conn.execute(&last_balance_query, [])?;
+    conn.execute(&del_query, [])?;
+
+    debug!(
+        id_num,
+        date = data[0],
+        tx_method = source,
+        amount,
+        tx_type,
+        previous_final_balance = ?last_balance,
+        final_balance = ?final_last_balance,
+        "Removed tx"
+    );
     Ok(())
 }
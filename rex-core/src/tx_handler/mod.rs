@@ -0,0 +1,30 @@
+mod add_tx;
+mod delete_tx;
+mod quick_add;
+mod retag_tx;
+mod schedule_tx;
+mod split_tx;
+mod tag_rule;
+mod trash_tx;
+mod tx_data;
+mod tx_draft;
+mod tx_method;
+
+pub(crate) use add_tx::insert_tx;
+pub use add_tx::{add_exchange_transfer, add_transfer_with_fee, add_tx};
+pub(crate) use delete_tx::remove_tx;
+pub use delete_tx::{delete_tx, delete_txs};
+pub use quick_add::parse_quick_add;
+pub use retag_tx::{add_tag_to_txs, remove_tag_from_txs};
+pub use schedule_tx::post_scheduled_txs;
+pub use split_tx::{add_split_tx, get_split_details, SplitPart};
+pub use tag_rule::{get_tag_rules_text, remove_tag_rule, save_tag_rule};
+pub use trash_tx::{restore_tx, trash_tx, trash_txs};
+pub use tx_data::*;
+pub use tx_draft::{get_draft_path, load_draft, remove_draft, save_draft, TxDraft};
+pub use tx_method::{
+    add_tx_method, adjust_starting_balance, change_credit_limit, change_tx_method_color,
+    change_tx_method_currency, change_tx_method_group, change_tx_method_type, delete_tx_method,
+    get_deletion_details, get_merge_details, merge_tx_methods, rename_tx_method,
+    switch_tx_method_archive, verify_tx_method_name, MergeDetails,
+};
@@ -0,0 +1,121 @@
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+use crate::outputs::{QuickAddError, VerifyingOutput};
+use crate::state::DateType;
+use crate::tx_handler::TxData;
+use crate::utility::parse_jump_date;
+
+/// Turns a single line like `12-05 groceries 45.50 card #food` into a verified tx.
+///
+/// - The date comes first and is optional. Accepts a day and a month of the selected year,
+///   the values of `parse_jump_date` or nothing for today
+/// - The amount is the last number that has a tx method after it. A leading + makes it an income
+/// - The tx method comes after the amount. `from>to` makes it a transfer
+/// - Every word starting with # is a tag
+/// - The rest is the details
+pub fn parse_quick_add(
+    input: &str,
+    month: usize,
+    year: usize,
+    conn: &Connection,
+) -> Result<TxData, QuickAddError> {
+    let input = input.trim();
+
+    let mut tags = Vec::new();
+    let mut words = Vec::new();
+
+    for word in input.split_whitespace() {
+        match word.strip_prefix('#') {
+            Some(tag) if !tag.is_empty() => tags.push(tag),
+            Some(_) => {}
+            None => words.push(word),
+        }
+    }
+
+    // the final word is always a part of the tx method
+    let amount_index = (0..words.len().saturating_sub(1))
+        .rev()
+        .find(|index| is_amount(words[*index]))
+        .ok_or_else(|| {
+            if words.last().is_some_and(|word| is_amount(word)) {
+                QuickAddError::MissingMethod(input.to_string())
+            } else {
+                QuickAddError::MissingAmount(input.to_string())
+            }
+        })?;
+
+    let mut details_start = 0;
+    let date = match words.first() {
+        Some(word) if amount_index > 0 && is_date(word) => {
+            details_start = 1;
+            parse_quick_date(word, month, year)?
+        }
+        _ => parse_jump_date("today", month, year)?,
+    };
+
+    let details = words[details_start..amount_index].join(" ");
+    let method = words[amount_index + 1..].join(" ");
+    let mut amount = words[amount_index];
+
+    let income = amount.starts_with('+');
+    amount = amount.trim_start_matches('+');
+
+    let (from_method, to_method, tx_type) = match method.split_once('>') {
+        Some((from_method, to_method)) => (from_method.trim(), to_method.trim(), "Transfer"),
+        None if income => (method.as_str(), "", "Income"),
+        None => (method.as_str(), "", "Expense"),
+    };
+
+    let mut tx_data = TxData::filled(
+        &date.to_string(),
+        &details,
+        from_method,
+        to_method,
+        amount,
+        tx_type,
+        &tags.join(", "),
+    );
+
+    let mut statuses = vec![
+        tx_data.check_date(&DateType::Exact),
+        tx_data.check_from_method(conn),
+    ];
+    if tx_type == "Transfer" {
+        statuses.push(tx_data.check_to_method(conn));
+    }
+    statuses.push(tx_data.check_amount(false, conn));
+    statuses.push(tx_data.check_tx_type());
+    tx_data.check_tags();
+
+    for status in statuses {
+        if let VerifyingOutput::NotAccepted(err) = status {
+            return Err(QuickAddError::NotAccepted(err));
+        }
+    }
+
+    Ok(tx_data)
+}
+
+/// Whether the word can be the amount of the tx
+fn is_amount(word: &str) -> bool {
+    word.trim_start_matches('+').parse::<f64>().is_ok()
+}
+
+/// Whether the word is meant to be the date instead of the details
+fn is_date(word: &str) -> bool {
+    word.eq_ignore_ascii_case("today") || word.chars().all(|c| c.is_ascii_digit() || c == '-')
+}
+
+/// Parses the date of the quick add input. A day and a month like 12-05 goes to the selected year
+fn parse_quick_date(word: &str, month: usize, year: usize) -> Result<NaiveDate, QuickAddError> {
+    if let Some((day, day_month)) = word.split_once('-') {
+        if day.len() <= 2 && day_month.len() <= 2 {
+            let date = format!("{day}-{day_month}-{}", year + 2022);
+            return NaiveDate::parse_from_str(&date, "%d-%m-%Y")
+                .map_err(|_| QuickAddError::InvalidDate(word.to_string()));
+        }
+    }
+
+    Ok(parse_jump_date(word, month, year)?)
+}
@@ -0,0 +1,116 @@
+use rusqlite::Connection;
+
+use crate::outputs::{RetagError, VerifyingOutput};
+use crate::state::ActivityType;
+use crate::utility::{
+    add_new_activity, add_new_activity_tx, check_tag_policy, get_tx_id_num, Config,
+};
+
+/// Adds the tag to all the given transactions in a single transaction. Txs that are
+/// tagged as Unknown lose the Unknown tag and txs that already have it are left untouched
+pub fn add_tag_to_txs(
+    id_nums: &[i32],
+    tag: &str,
+    config: &Config,
+    conn: &mut Connection,
+) -> Result<(), RetagError> {
+    let tag = verify_retag_input(tag)?;
+
+    update_tx_tags(id_nums, config, conn, |tags| {
+        if !tags.iter().any(|existing| existing == tag) {
+            tags.retain(|existing| existing != "Unknown");
+            tags.push(tag.to_string());
+        }
+    })
+}
+
+/// Removes the tag from all the given transactions in a single transaction.
+/// A tx that is left with no tags gets tagged as Unknown
+pub fn remove_tag_from_txs(
+    id_nums: &[i32],
+    tag: &str,
+    config: &Config,
+    conn: &mut Connection,
+) -> Result<(), RetagError> {
+    let tag = verify_retag_input(tag)?;
+
+    update_tx_tags(id_nums, config, conn, |tags| {
+        tags.retain(|existing| existing != tag);
+    })
+}
+
+/// Returns the trimmed tag. Tags are separated by commas so the tag itself can't contain one
+fn verify_retag_input(tag: &str) -> Result<&str, RetagError> {
+    let trimmed = tag.trim();
+
+    if trimmed.is_empty() || trimmed.contains(',') {
+        return Err(RetagError::InvalidTag(tag.to_string()));
+    }
+    Ok(trimmed)
+}
+
+/// Runs the tag modification on the tags of every given tx and saves the result. Every changed
+/// tx is recorded on the activities. The new tags of every tx are checked against the tag
+/// policies of the config before anything is saved. If any one of them fails none of them are
+/// updated
+fn update_tx_tags<F: Fn(&mut Vec<String>)>(
+    id_nums: &[i32],
+    config: &Config,
+    conn: &mut Connection,
+    modify_tags: F,
+) -> Result<(), RetagError> {
+    let sp = conn.savepoint().map_err(RetagError::FailedRetag)?;
+
+    let mut changed_txs = Vec::new();
+
+    for id_num in id_nums {
+        let current_tags: String = sp
+            .query_row(
+                "SELECT tags FROM tx_all WHERE id_num = ?",
+                [id_num],
+                |row| row.get(0),
+            )
+            .map_err(RetagError::FailedRetag)?;
+
+        let mut tags = current_tags
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect::<Vec<String>>();
+
+        modify_tags(&mut tags);
+
+        let new_tags = if tags.is_empty() {
+            "Unknown".to_string()
+        } else {
+            tags.join(", ")
+        };
+
+        if new_tags == current_tags {
+            continue;
+        }
+
+        if let VerifyingOutput::NotAccepted(err) = check_tag_policy(&new_tags, config) {
+            return Err(RetagError::NotAccepted(*id_num, err));
+        }
+
+        changed_txs.push((*id_num, new_tags));
+    }
+
+    for (id_num, new_tags) in changed_txs {
+        let old_tx = get_tx_id_num(id_num, &sp);
+        sp.execute(
+            "UPDATE tx_all SET tags = ? WHERE id_num = ?",
+            (new_tags, id_num),
+        )
+        .map_err(RetagError::FailedRetag)?;
+
+        let activity_num = add_new_activity(ActivityType::EditTX(Some(id_num)), &sp)
+            .map_err(RetagError::FailedRetag)?;
+        add_new_activity_tx(&get_tx_id_num(id_num, &sp), activity_num, &sp)
+            .map_err(RetagError::FailedRetag)?;
+        add_new_activity_tx(&old_tx, activity_num, &sp).map_err(RetagError::FailedRetag)?;
+    }
+
+    sp.commit().map_err(RetagError::FailedRetag)
+}
@@ -0,0 +1,35 @@
+use chrono::NaiveDate;
+use rusqlite::{Connection, Result as sqlResult};
+
+use crate::db::{delete_scheduled_tx, get_scheduled_txs};
+use crate::state::ActivityType;
+use crate::tx_handler::add_tx::insert_tx;
+use crate::utility::{add_new_activity, add_new_activity_tx, get_last_tx_id, get_tx_id_num};
+
+/// Adds every scheduled tx that is dated on or before the given day and removes them from the
+/// scheduled txs. Each posted tx is recorded on the activities. Returns the `id_num` of the posted txs
+pub fn post_scheduled_txs(today: NaiveDate, conn: &mut Connection) -> sqlResult<Vec<i32>> {
+    let today = today.format("%Y-%m-%d").to_string();
+    let mut posted = Vec::new();
+
+    for tx in get_scheduled_txs(conn) {
+        if tx[0] > today {
+            break;
+        }
+
+        let sp = conn.savepoint()?;
+        insert_tx(
+            &tx[0], &tx[1], &tx[2], &tx[3], &tx[4], &tx[5], None, None, &sp,
+        )?;
+        delete_scheduled_tx(tx[6].parse().unwrap(), &sp)?;
+
+        let id_num = get_last_tx_id(&sp)?;
+        let activity_num = add_new_activity(ActivityType::PostScheduledTX, &sp)?;
+        add_new_activity_tx(&get_tx_id_num(id_num, &sp), activity_num, &sp)?;
+        sp.commit()?;
+
+        posted.push(id_num);
+    }
+
+    Ok(posted)
+}
@@ -0,0 +1,86 @@
+use rusqlite::{Connection, Result as sqlResult};
+use serde::{Deserialize, Serialize};
+
+use crate::db::{get_split_parts, link_split_parts};
+use crate::tx_handler::add_tx::insert_tx;
+use crate::utility::{get_last_tx_id, get_tx_id_num};
+
+/// A single part of a split tx
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SplitPart {
+    pub tags: String,
+    pub tx_method: String,
+    pub amount: String,
+}
+
+/// Adds every part of a split tx in a single transaction. Each part becomes its own tx
+/// with the given tags, tx method and amount and all of them are linked together.
+/// If any one of them fails none of them are added. Returns the `id_num` of the parts
+pub fn add_split_tx(
+    date: &str,
+    details: &str,
+    tx_type: &str,
+    parts: &[SplitPart],
+    conn: &mut Connection,
+) -> sqlResult<Vec<i32>> {
+    let sp = conn.savepoint()?;
+    let id_nums = insert_split_tx(date, details, tx_type, parts, &sp)?;
+    sp.commit()?;
+    Ok(id_nums)
+}
+
+/// Does the insertion and the linking of every part of a split tx on the given connection
+/// without committing anything. Returns the `id_num` of the parts
+pub(crate) fn insert_split_tx(
+    date: &str,
+    details: &str,
+    tx_type: &str,
+    parts: &[SplitPart],
+    sp: &Connection,
+) -> sqlResult<Vec<i32>> {
+    let mut id_nums = Vec::new();
+
+    for part in parts {
+        insert_tx(
+            date,
+            details,
+            &part.tx_method,
+            &part.amount,
+            tx_type,
+            &part.tags,
+            None,
+            None,
+            sp,
+        )?;
+        id_nums.push(get_last_tx_id(sp)?);
+    }
+
+    link_split_parts(&id_nums, sp)?;
+
+    Ok(id_nums)
+}
+
+/// Returns the text that shows every part of the split tx the given tx belongs to
+/// with the total amount. `None` if the tx was not split
+pub fn get_split_details(id_num: i32, conn: &Connection) -> Option<String> {
+    let parts = get_split_parts(id_num, conn);
+
+    if parts.len() < 2 {
+        return None;
+    }
+
+    let mut total = 0.0;
+    let mut lines = Vec::new();
+
+    for part_id in parts {
+        let tx = get_tx_id_num(part_id, conn);
+        total += tx[3].parse::<f64>().unwrap_or(0.0);
+        lines.push(format!("{}: {} ({})", tx[2], tx[3], tx[5]));
+    }
+
+    Some(format!(
+        "Split into {} parts. Total: {total:.2}\n{}",
+        lines.len(),
+        lines.join("\n")
+    ))
+}
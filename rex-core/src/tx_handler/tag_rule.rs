@@ -0,0 +1,152 @@
+use rusqlite::Connection;
+
+use crate::db::{add_tag_rule, delete_tag_rule, get_tag_rules};
+use crate::outputs::TagRuleError;
+
+/// The tx fields a tag rule can match against
+const RULE_FIELDS: [&str; 3] = ["details", "method", "amount"];
+
+/// Checks an input like `details uber, Transport` and saves it as a tag rule. `details` matches
+/// txs whose details contain the pattern, `method` matches the tx method or either side of a
+/// transfer and `amount` accepts an exact amount or a comparison like >100
+pub fn save_tag_rule(input: &str, conn: &Connection) -> Result<(), TagRuleError> {
+    let Some((rule, tag)) = input.split_once(',') else {
+        return Err(TagRuleError::MissingTag);
+    };
+
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(TagRuleError::MissingTag);
+    }
+
+    let Some((field, pattern)) = rule.trim().split_once(' ') else {
+        return Err(TagRuleError::MissingPattern);
+    };
+
+    let field = field.to_lowercase();
+    let pattern = pattern.trim();
+
+    if !RULE_FIELDS.contains(&field.as_str()) {
+        return Err(TagRuleError::InvalidField(field));
+    }
+
+    if pattern.is_empty() {
+        return Err(TagRuleError::MissingPattern);
+    }
+
+    if field == "amount" && parse_amount_pattern(pattern).is_none() {
+        return Err(TagRuleError::InvalidAmount(pattern.to_string()));
+    }
+
+    add_tag_rule(&field, pattern, tag, conn).map_err(TagRuleError::FailedSave)
+}
+
+/// Removes the tag rule with the given number as shown on the rule list
+pub fn remove_tag_rule(number: &str, conn: &Connection) -> Result<(), TagRuleError> {
+    let rules = get_tag_rules(conn);
+
+    let Some((rule_id, _, _, _)) = number
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|index| rules.get(index))
+    else {
+        return Err(TagRuleError::NotFound(number.trim().to_string()));
+    };
+
+    delete_tag_rule(*rule_id, conn).map_err(TagRuleError::FailedSave)
+}
+
+/// Returns the numbered list of the saved tag rules
+#[must_use]
+pub fn get_tag_rules_text(conn: &Connection) -> String {
+    let rules = get_tag_rules(conn);
+
+    if rules.is_empty() {
+        return "No tag rule is saved".to_string();
+    }
+
+    rules
+        .iter()
+        .enumerate()
+        .map(|(index, (_, field, pattern, tag))| {
+            format!("{}. {field} {pattern} → {tag}", index + 1)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Adds the tags of every matching rule to the tags of a new tx. Tags the tx already has are
+/// not repeated and Unknown is dropped once a rule adds a tag
+pub(crate) fn apply_tag_rules(
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tags: &str,
+    conn: &Connection,
+) -> String {
+    let rules = get_tag_rules(conn);
+
+    let mut all_tags = tags
+        .split(',')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect::<Vec<String>>();
+
+    for (_, field, pattern, tag) in rules {
+        if !rule_matches(&field, &pattern, details, tx_method, amount)
+            || all_tags
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&tag))
+        {
+            continue;
+        }
+
+        all_tags.retain(|existing| existing != "Unknown");
+        all_tags.push(tag);
+    }
+
+    if all_tags.is_empty() {
+        return tags.to_string();
+    }
+
+    all_tags.join(", ")
+}
+
+fn rule_matches(field: &str, pattern: &str, details: &str, tx_method: &str, amount: &str) -> bool {
+    match field {
+        "details" => details.to_lowercase().contains(&pattern.to_lowercase()),
+        "method" => tx_method
+            .split(" to ")
+            .any(|method| method.eq_ignore_ascii_case(pattern)),
+        "amount" => {
+            let (Some((operator, target)), Ok(amount)) =
+                (parse_amount_pattern(pattern), amount.parse::<f64>())
+            else {
+                return false;
+            };
+
+            match operator {
+                ">" => amount > target,
+                ">=" => amount >= target,
+                "<" => amount < target,
+                "<=" => amount <= target,
+                _ => (amount - target).abs() < 0.005,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Splits an amount pattern like >=100 to the comparison and the amount
+fn parse_amount_pattern(pattern: &str) -> Option<(&str, f64)> {
+    let operator = [">=", "<=", ">", "<", "="]
+        .into_iter()
+        .find(|operator| pattern.starts_with(operator))
+        .unwrap_or("=");
+
+    let target = pattern.strip_prefix(operator).unwrap_or(pattern).trim();
+
+    target.parse::<f64>().ok().map(|target| (operator, target))
+}
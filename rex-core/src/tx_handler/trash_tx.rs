@@ -0,0 +1,105 @@
+use chrono::Local;
+use rusqlite::{Connection, Result as sqlResult};
+use tracing::info;
+
+use crate::db::{
+    add_split_part, add_trashed_tx, delete_trashed_tx, get_trashed_tx, set_tx_attachments,
+    set_tx_foreign_amount, set_tx_note, set_tx_status,
+};
+use crate::outputs::TrashError;
+use crate::state::ActivityType;
+use crate::tx_handler::add_tx::insert_tx;
+use crate::tx_handler::delete_tx::remove_tx;
+use crate::utility::{add_new_activity, add_new_activity_tx, get_all_tx_methods, get_tx_id_num};
+
+/// Moves a tx to the trash. It is deleted like any other tx but can be restored later
+pub fn trash_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
+    trash_txs(&[id_num], conn)
+}
+
+/// Moves all the given txs to the trash in a single transaction alongside their activities.
+/// If any one of them fails none of them are moved
+pub fn trash_txs(id_nums: &[i32], conn: &mut Connection) -> sqlResult<()> {
+    let deleted_at = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let sp = conn.savepoint()?;
+    for id_num in id_nums {
+        let deleted_tx = get_tx_id_num(*id_num, &sp);
+        add_trashed_tx(*id_num, &deleted_at, &sp)?;
+        remove_tx(*id_num, &sp)?;
+
+        let activity_num = add_new_activity(ActivityType::DeleteTX(Some(*id_num)), &sp)?;
+        add_new_activity_tx(&deleted_tx, activity_num, &sp)?;
+    }
+    sp.commit()?;
+
+    info!(?id_nums, "Moved txs to the trash");
+    Ok(())
+}
+
+/// Adds a tx in the trash back with its exchange rate, note, status, split link, foreign amount,
+/// attachments and activity and removes it from the trash. It gets its old `id_num` back unless
+/// another tx took it. Returns the `id_num` of the restored tx
+pub fn restore_tx(trash_id: i32, conn: &mut Connection) -> Result<i32, TrashError> {
+    let trashed = get_trashed_tx(trash_id, conn)
+        .map_err(TrashError::FailedRestore)?
+        .ok_or(TrashError::NotFound)?;
+
+    let tx_methods = get_all_tx_methods(conn);
+    if let Some(missing) = trashed.tx[2]
+        .split(" to ")
+        .find(|method| !tx_methods.iter().any(|m| m == method))
+    {
+        return Err(TrashError::MissingTxMethod(missing.to_string()));
+    }
+
+    let id_taken = conn
+        .query_row(
+            "SELECT COUNT(*) FROM tx_all WHERE id_num = ?",
+            [trashed.id_num],
+            |row| row.get::<_, i32>(0),
+        )
+        .map_err(TrashError::FailedRestore)?
+        != 0;
+
+    let id_num = if id_taken {
+        conn.query_row("SELECT MAX(id_num) + 1 FROM tx_all", [], |row| {
+            row.get::<_, i32>(0)
+        })
+        .map_err(TrashError::FailedRestore)?
+    } else {
+        trashed.id_num
+    };
+
+    let sp = conn.savepoint().map_err(TrashError::FailedRestore)?;
+    insert_tx(
+        &trashed.tx[0],
+        &trashed.tx[1],
+        &trashed.tx[2],
+        &trashed.tx[3],
+        &trashed.tx[4],
+        &trashed.tx[5],
+        Some(&id_num.to_string()),
+        trashed.rate,
+        &sp,
+    )
+    .map_err(TrashError::FailedRestore)?;
+    set_tx_note(id_num, &trashed.note, &sp).map_err(TrashError::FailedRestore)?;
+    set_tx_status(id_num, trashed.status, &sp).map_err(TrashError::FailedRestore)?;
+    set_tx_foreign_amount(id_num, trashed.foreign_amount.as_ref(), &sp)
+        .map_err(TrashError::FailedRestore)?;
+    set_tx_attachments(id_num, &trashed.attachments, &sp).map_err(TrashError::FailedRestore)?;
+    if let Some(split_id) = trashed.split_id {
+        add_split_part(id_num, split_id, &sp).map_err(TrashError::FailedRestore)?;
+    }
+    delete_trashed_tx(trash_id, &sp).map_err(TrashError::FailedRestore)?;
+
+    let activity_num = add_new_activity(ActivityType::RestoreTX(Some(id_num)), &sp)
+        .map_err(TrashError::FailedRestore)?;
+    add_new_activity_tx(&get_tx_id_num(id_num, &sp), activity_num, &sp)
+        .map_err(TrashError::FailedRestore)?;
+    sp.commit().map_err(TrashError::FailedRestore)?;
+
+    info!(trash_id, id_num, "Restored tx from the trash");
+    Ok(id_num)
+}
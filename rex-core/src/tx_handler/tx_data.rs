@@ -1,18 +1,28 @@
 use chrono::prelude::Local;
-use rusqlite::Connection;
+use rusqlite::{Connection, Result as sqlResult};
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use tracing::warn;
 
+use crate::db::{
+    add_scheduled_tx, get_split_parts, get_tx_attachments, get_tx_exchange_rate,
+    get_tx_method_currencies, get_tx_status, link_split_parts, set_tx_attachments,
+    set_tx_foreign_amount, set_tx_note, set_tx_status, ForeignAmount,
+};
 use crate::outputs::{
-    CheckingError, ComparisonType, NAType, StepType, SteppingError, TxType, TxUpdateError,
-    VerifyingOutput,
+    AType, CheckingError, ComparisonType, CurrencyError, ExchangeRateError, FeeError, NAType,
+    ScheduleError, SplitError, StepType, SteppingError, TxType, TxUpdateError, VerifyingOutput,
 };
-use crate::page_handler::{ActivityType, DateType, TxTab};
-use crate::tx_handler::{add_tx, delete_tx};
+use crate::state::{ActivityType, DateType, TxTab};
+use crate::tx_handler::add_tx::{insert_transfer_with_fee, insert_tx};
+use crate::tx_handler::delete_tx::remove_tx;
+use crate::tx_handler::split_tx::insert_split_tx;
+use crate::tx_handler::{SplitPart, TxDraft};
 use crate::utility::traits::{AutoFiller, DataVerifier, FieldStepper};
 use crate::utility::{
     add_char_to, add_new_activity, add_new_activity_tx, check_comparison, get_all_tx_methods,
-    get_empty_changes, get_last_balances, get_last_tx, get_search_data, get_tx_id_num,
+    get_currency_rate, get_empty_changes, get_last_balances, get_last_tx, get_last_tx_id,
+    get_search_data, get_tx_id_num, Config,
 };
 
 /// Contains all data for a Transaction to work
@@ -24,11 +34,23 @@ pub struct TxData {
     amount: String,
     tx_type: String,
     tags: String,
+    note: String,
+    /// Every part the tx is split into
+    splits: Vec<SplitPart>,
+    /// Fee of a transfer that gets added as an expense of the From Method
+    fee: String,
+    /// How much the To Method receives for 1 of the From Method's currency on a transfer between
+    /// tx methods of different currencies. Empty if there is none
+    exchange_rate: String,
+    /// The amount and the currency the tx was entered in if it was not the base currency
+    foreign_amount: Option<ForeignAmount>,
     tx_status: Vec<String>,
     editing_tx: bool,
     id_num: i32,
     current_index: usize,
     autofill: String,
+    /// Earlier values of the fields with their cursor position that the edits can be undone to
+    undo_history: Vec<(usize, String, usize)>,
 }
 
 impl DataVerifier for TxData {}
@@ -37,6 +59,12 @@ impl AutoFiller for TxData {}
 
 impl FieldStepper for TxData {}
 
+impl Default for TxData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TxData {
     /// Creates an instance of the struct however the date field is
     /// edited with the current local date of the device.
@@ -51,11 +79,27 @@ impl TxData {
             amount: String::new(),
             tx_type: String::new(),
             tags: String::new(),
+            note: String::new(),
+            splits: Vec::new(),
+            fee: String::new(),
+            exchange_rate: String::new(),
+            foreign_amount: None,
             tx_status: Vec::new(),
             editing_tx: false,
             id_num: 0,
             current_index: 0,
             autofill: String::new(),
+            undo_history: Vec::new(),
+        }
+    }
+
+    /// Creates a new tx with the current date and the given tx method, tx type and tags
+    pub fn new_with_defaults(tx_method: &str, tx_type: &str, tags: &str) -> Self {
+        TxData {
+            from_method: tx_method.to_string(),
+            tx_type: tx_type.to_string(),
+            tags: tags.to_string(),
+            ..TxData::new()
         }
     }
 
@@ -68,16 +112,23 @@ impl TxData {
             amount: String::new(),
             tx_type: String::new(),
             tags: String::new(),
+            note: String::new(),
+            splits: Vec::new(),
+            fee: String::new(),
+            exchange_rate: String::new(),
+            foreign_amount: None,
             tx_status: Vec::new(),
             editing_tx: false,
             id_num: 0,
             current_index: 0,
             autofill: String::new(),
+            undo_history: Vec::new(),
         }
     }
 
     /// Used to adding custom pre-defined data inside the widgets of Add Transaction Page.
     /// Currently used on Editing transaction.
+    #[allow(clippy::too_many_arguments)]
     pub fn custom(
         date: &str,
         details: &str,
@@ -88,32 +139,170 @@ impl TxData {
         tags: &str,
         id_num: i32,
     ) -> Self {
-        let new_date = if date.is_empty() {
-            String::new()
-        } else {
-            let data = date.split('-').collect::<Vec<&str>>();
-            let year = data[2];
-            let month = data[1];
-            let day = data[0];
-            format!("{year}-{month}-{day}")
-        };
-
         TxData {
-            date: new_date,
+            date: date.to_string(),
             details: details.to_string(),
             from_method: from_method.to_string(),
             to_method: to_method.to_string(),
             amount: amount.to_string(),
             tx_type: tx_type.to_string(),
             tags: tags.to_string(),
+            note: String::new(),
+            splits: Vec::new(),
+            fee: String::new(),
+            exchange_rate: String::new(),
+            foreign_amount: None,
             tx_status: Vec::new(),
             editing_tx: true,
             id_num,
             current_index: 0,
             autofill: String::new(),
+            undo_history: Vec::new(),
         }
     }
 
+    /// Creates a new tx with every field already filled. The date is in the YYYY-MM-DD format.
+    /// Currently used on the quick add popup of the Home page.
+    pub fn filled(
+        date: &str,
+        details: &str,
+        from_method: &str,
+        to_method: &str,
+        amount: &str,
+        tx_type: &str,
+        tags: &str,
+    ) -> Self {
+        TxData {
+            date: date.to_string(),
+            details: details.to_string(),
+            from_method: from_method.to_string(),
+            to_method: to_method.to_string(),
+            amount: amount.to_string(),
+            tx_type: tx_type.to_string(),
+            tags: tags.to_string(),
+            ..TxData::new_empty()
+        }
+    }
+
+    /// Returns a new tx with the same fields as this one so it can be added again.
+    /// The note is not carried over
+    pub fn repeated(&self) -> Self {
+        TxData {
+            splits: self.splits.clone(),
+            fee: self.fee.clone(),
+            exchange_rate: self.exchange_rate.clone(),
+            foreign_amount: self.foreign_amount.clone(),
+            ..TxData::filled(
+                &self.date,
+                &self.details,
+                &self.from_method,
+                &self.to_method,
+                &self.amount,
+                &self.tx_type,
+                &self.tags,
+            )
+        }
+    }
+
+    /// Returns the fields as a draft that can be saved. None if nothing was typed in the form
+    /// besides the defaults like the date
+    pub fn get_draft(&self) -> Option<TxDraft> {
+        let has_input = [
+            &self.details,
+            &self.amount,
+            &self.note,
+            &self.fee,
+            &self.exchange_rate,
+        ]
+        .iter()
+        .any(|value| !value.is_empty())
+            || !self.splits.is_empty();
+
+        if !has_input {
+            return None;
+        }
+
+        Some(TxDraft {
+            date: self.date.clone(),
+            details: self.details.clone(),
+            from_method: self.from_method.clone(),
+            to_method: self.to_method.clone(),
+            amount: self.amount.clone(),
+            tx_type: self.tx_type.clone(),
+            tags: self.tags.clone(),
+            note: self.note.clone(),
+            splits: self.splits.clone(),
+            fee: self.fee.clone(),
+            exchange_rate: self.exchange_rate.clone(),
+            foreign_amount: self.foreign_amount.clone(),
+            editing_id: self.editing_tx.then_some(self.id_num),
+        })
+    }
+
+    /// Creates the form back from a saved draft
+    pub fn from_draft(draft: TxDraft) -> Self {
+        TxData {
+            note: draft.note,
+            splits: draft.splits,
+            fee: draft.fee,
+            exchange_rate: draft.exchange_rate,
+            foreign_amount: draft.foreign_amount,
+            editing_tx: draft.editing_id.is_some(),
+            id_num: draft.editing_id.unwrap_or_default(),
+            ..TxData::filled(
+                &draft.date,
+                &draft.details,
+                &draft.from_method,
+                &draft.to_method,
+                &draft.amount,
+                &draft.tx_type,
+                &draft.tags,
+            )
+        }
+    }
+
+    /// Returns whether the tx is an existing tx that is being edited
+    pub fn is_editing(&self) -> bool {
+        self.editing_tx
+    }
+
+    /// Returns a new tx for the next entry of a batch. Only the date, the tx methods and
+    /// the tx type are kept
+    pub fn batch_next(&self) -> Self {
+        TxData::filled(
+            &self.date,
+            "",
+            &self.from_method,
+            &self.to_method,
+            "",
+            &self.tx_type,
+            "",
+        )
+    }
+
+    /// Returns a new tx with the same fields as the last added tx of the db.
+    /// `None` if there are no txs
+    pub fn last_in_db(conn: &Connection) -> Option<Self> {
+        get_last_tx_id(conn).ok()?;
+
+        let last_tx = get_last_tx(conn);
+
+        let (from_method, to_method) = match last_tx[2].split_once(" to ") {
+            Some((from_method, to_method)) if last_tx[4] == "Transfer" => (from_method, to_method),
+            _ => (last_tx[2].as_str(), ""),
+        };
+
+        Some(TxData::filled(
+            &last_tx[0],
+            &last_tx[1],
+            from_method,
+            to_method,
+            &last_tx[3],
+            &last_tx[4],
+            &last_tx[5],
+        ))
+    }
+
     /// Returns all the data saved
     pub fn get_all_texts(&self) -> Vec<&str> {
         vec![
@@ -136,6 +325,26 @@ impl TxData {
         }
     }
 
+    /// Swaps the From and To Method of a transfer. Returns false without
+    /// changing anything if the tx is not a transfer
+    pub fn swap_tx_methods(&mut self) -> bool {
+        if self.tx_type != "Transfer" {
+            return false;
+        }
+        std::mem::swap(&mut self.from_method, &mut self.to_method);
+        true
+    }
+
+    /// Returns the long note of the tx
+    pub fn get_note(&self) -> &str {
+        &self.note
+    }
+
+    /// Replaces the long note of the tx
+    pub fn set_note(&mut self, note: String) {
+        self.note = note;
+    }
+
     pub fn get_tx_status(&self) -> &Vec<String> {
         &self.tx_status
     }
@@ -153,111 +362,523 @@ impl TxData {
 
     /// Insert or remove from date field according to the index point
     pub fn edit_date(&mut self, to_add: Option<char>) {
+        self.save_undo(&TxTab::Date);
         add_char_to(to_add, &mut self.current_index, &mut self.date);
     }
 
     /// Insert or remove from details field according to the index point
     pub fn edit_details(&mut self, to_add: Option<char>) {
+        self.save_undo(&TxTab::Details);
         add_char_to(to_add, &mut self.current_index, &mut self.details);
     }
 
     /// Insert or remove from from method field according to the index point
     pub fn edit_from_method(&mut self, to_add: Option<char>) {
+        self.save_undo(&TxTab::FromMethod);
         add_char_to(to_add, &mut self.current_index, &mut self.from_method);
     }
 
     /// Insert or remove from to method field according to the index point
     pub fn edit_to_method(&mut self, to_add: Option<char>) {
+        self.save_undo(&TxTab::ToMethod);
         add_char_to(to_add, &mut self.current_index, &mut self.to_method);
     }
 
     /// Insert or remove from amount field according to the index point
     pub fn edit_amount(&mut self, to_add: Option<char>) {
+        // the converted amount no longer matches the foreign amount once it is changed
+        self.foreign_amount = None;
+        self.save_undo(&TxTab::Amount);
         add_char_to(to_add, &mut self.current_index, &mut self.amount);
     }
 
     /// Insert or remove from tx type field according to the index point
     pub fn edit_tx_type(&mut self, to_add: Option<char>) {
+        self.save_undo(&TxTab::TxType);
         add_char_to(to_add, &mut self.current_index, &mut self.tx_type);
     }
 
     /// Insert or remove from tags field according to the index point
     pub fn edit_tags(&mut self, to_add: Option<char>) {
+        self.save_undo(&TxTab::Tags);
         add_char_to(to_add, &mut self.current_index, &mut self.tags);
     }
 
-    /// Takes all data and adds it as a transaction
-    pub fn add_tx(&mut self, conn: &mut Connection) -> Result<(), String> {
+    /// Takes all data and adds it as a transaction. The tx, its balance changes, its activity and
+    /// everything kept alongside it are saved in a single transaction so nothing is saved if any
+    /// one of them fails. Returns the `id_num` of every tx that was added, which is empty when an
+    /// edited tx is saved
+    pub fn add_tx(&mut self, conn: &mut Connection) -> Result<Vec<i32>, String> {
         if let Some(output) = self.check_all_fields() {
             return Err(output.to_string());
         }
 
         let tx_method = self.get_tx_method();
+        let rate = self.get_transfer_rate(conn).map_err(|e| e.to_string())?;
 
         if self.editing_tx {
             self.editing_tx = false;
-            // how saving an edited tx works
-            // delete the tx that was being edited from the db using the id_num ->
-            // add another tx using the new data but take the earlier id to add to the db
-            let deleted_tx = get_tx_id_num(self.id_num, conn);
-            let status = delete_tx(self.id_num, conn);
-            match status {
-                Ok(()) => {}
-                Err(e) => return Err(TxUpdateError::FailedEditTx(e).to_string()),
-            }
 
-            let id_num = self.id_num.to_string();
-            let status_add = add_tx(
+            let sp = conn
+                .savepoint()
+                .map_err(|e| TxUpdateError::FailedEditTx(e).to_string())?;
+            self.save_edited_tx(&tx_method, rate, &sp)
+                .and_then(|()| sp.commit())
+                .map(|()| Vec::new())
+                .map_err(|e| TxUpdateError::FailedEditTx(e).to_string())
+        } else if !self.fee.is_empty() && self.tx_type != "Transfer" {
+            Err(FeeError::NotTransfer.to_string())
+        } else if !self.splits.is_empty() && !self.fee.is_empty() {
+            Err(FeeError::Split.to_string())
+        } else {
+            let parts = if self.splits.is_empty() {
+                Vec::new()
+            } else {
+                self.get_split_txs().map_err(|e| e.to_string())?
+            };
+
+            let sp = conn
+                .savepoint()
+                .map_err(|e| TxUpdateError::FailedAddTx(e).to_string())?;
+            self.save_new_tx(&tx_method, rate, &parts, &sp)
+                .and_then(|id_nums| sp.commit().map(|()| id_nums))
+                .map_err(|e| TxUpdateError::FailedAddTx(e).to_string())
+        }
+    }
+
+    /// Replaces the tx that is being edited with the current data without committing anything.
+    /// How saving an edited tx works: delete the tx that was being edited from the db using the
+    /// `id_num` -> add another tx using the new data but take the earlier id to add to the db
+    fn save_edited_tx(
+        &self,
+        tx_method: &str,
+        rate: Option<f64>,
+        conn: &Connection,
+    ) -> sqlResult<()> {
+        let id_num = self.id_num.to_string();
+
+        let deleted_tx = get_tx_id_num(self.id_num, conn);
+        let tx_status = get_tx_status(self.id_num, conn);
+        let split_parts = get_split_parts(self.id_num, conn);
+        let attachments = get_tx_attachments(self.id_num, conn);
+
+        remove_tx(self.id_num, conn)?;
+        insert_tx(
+            &self.date,
+            &self.details,
+            tx_method,
+            &self.amount,
+            &self.tx_type,
+            &self.tags,
+            Some(&id_num),
+            rate,
+            conn,
+        )?;
+
+        let activity_num = add_new_activity(ActivityType::EditTX(Some(self.id_num)), conn)?;
+        let new_tx = vec![
+            self.date.as_str(),
+            &self.details,
+            tx_method,
+            &self.amount,
+            &self.tx_type,
+            &self.tags,
+            &id_num,
+        ];
+        add_new_activity_tx(&new_tx, activity_num, conn)?;
+        add_new_activity_tx(&deleted_tx, activity_num, conn)?;
+
+        // the earlier note, status, split link and attachments were removed alongside the deleted tx
+        set_tx_note(self.id_num, &self.note, conn)?;
+        set_tx_foreign_amount(self.id_num, self.foreign_amount.as_ref(), conn)?;
+        set_tx_status(self.id_num, tx_status, conn)?;
+        link_split_parts(&split_parts, conn)?;
+        set_tx_attachments(self.id_num, &attachments, conn)
+    }
+
+    /// Adds the current data as a new tx, a transfer with its fee or every part of a split tx
+    /// without committing anything. Returns the `id_num` of every added tx
+    fn save_new_tx(
+        &self,
+        tx_method: &str,
+        rate: Option<f64>,
+        parts: &[SplitPart],
+        conn: &Connection,
+    ) -> sqlResult<Vec<i32>> {
+        let id_nums = if !parts.is_empty() {
+            insert_split_tx(&self.date, &self.details, &self.tx_type, parts, conn)?
+        } else if !self.fee.is_empty() {
+            let (transfer_id, fee_id) = insert_transfer_with_fee(
                 &self.date,
                 &self.details,
-                &tx_method,
+                tx_method,
                 &self.amount,
-                &self.tx_type,
                 &self.tags,
-                Some(&id_num),
+                &self.fee,
+                rate,
                 conn,
-            );
-
-            match status_add {
-                Ok(()) => {
-                    let activity_num =
-                        add_new_activity(ActivityType::EditTX(Some(self.id_num)), conn);
-                    let new_tx = vec![
-                        &self.date,
-                        &self.details,
-                        &tx_method,
-                        &self.amount,
-                        &self.tx_type,
-                        &self.tags,
-                        &id_num,
-                    ];
-                    add_new_activity_tx(&new_tx, activity_num, conn);
-                    add_new_activity_tx(&deleted_tx, activity_num, conn);
-                    Ok(())
-                }
-                Err(e) => Err(TxUpdateError::FailedEditTx(e).to_string()),
-            }
+            )?;
+            vec![transfer_id, fee_id]
         } else {
-            let status = add_tx(
+            insert_tx(
                 &self.date,
                 &self.details,
-                &tx_method,
+                tx_method,
                 &self.amount,
                 &self.tx_type,
                 &self.tags,
                 None,
+                rate,
                 conn,
-            );
-            match status {
-                Ok(()) => {
-                    let activity_num = add_new_activity(ActivityType::NewTX, conn);
-                    let last_tx = get_last_tx(conn);
-                    add_new_activity_tx(&last_tx, activity_num, conn);
-                    Ok(())
+            )?;
+            vec![get_last_tx_id(conn)?]
+        };
+
+        let activity_num = add_new_activity(ActivityType::NewTX, conn)?;
+        for id_num in &id_nums {
+            add_new_activity_tx(&get_tx_id_num(*id_num, conn), activity_num, conn)?;
+        }
+
+        // the note and the foreign amount are kept on the first part of a split tx or on the
+        // transfer of a fee
+        set_tx_note(id_nums[0], &self.note, conn)?;
+        set_tx_foreign_amount(id_nums[0], self.foreign_amount.as_ref(), conn)?;
+        Ok(id_nums)
+    }
+
+    /// Returns the amount and the currency the tx was entered in. `None` if it was the base currency
+    pub fn get_foreign_amount(&self) -> Option<&ForeignAmount> {
+        self.foreign_amount.as_ref()
+    }
+
+    pub fn set_foreign_amount(&mut self, foreign_amount: Option<ForeignAmount>) {
+        self.foreign_amount = foreign_amount;
+    }
+
+    /// Converts an amount entered in another currency like `25 EUR` or `10*2 eur` to the base
+    /// currency and keeps the entered amount. Returns the rate that was used or `None`
+    /// if the amount is not in a foreign currency
+    pub fn convert_foreign_amount(
+        &mut self,
+        config: &Config,
+        conn: &Connection,
+    ) -> Result<Option<f64>, CurrencyError> {
+        let amount = self.amount.trim();
+        let code_start = amount
+            .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+            .len();
+        let (value, currency) = amount.split_at(code_start);
+
+        if currency.len() != 3 || value.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let currency = currency.to_uppercase();
+        let mut value = value.trim().to_string();
+
+        match self.verify_amount(&mut value) {
+            VerifyingOutput::Accepted(_) => {}
+            VerifyingOutput::NotAccepted(err) => return Err(CurrencyError::NotAccepted(err)),
+            VerifyingOutput::Nothing(_) => {
+                return Err(CurrencyError::NotAccepted(NAType::ParsingError(
+                    AType::Amount,
+                )))
+            }
+        }
+
+        if config.base_currency.is_empty() {
+            return Err(CurrencyError::NoBaseCurrency);
+        }
+
+        if currency.eq_ignore_ascii_case(&config.base_currency) {
+            self.amount = value;
+            self.foreign_amount = None;
+            return Ok(None);
+        }
+
+        let rate = get_currency_rate(&currency, config, conn)?;
+        let converted = value.parse::<f64>().unwrap() * rate;
+
+        self.amount = format!("{converted:.2}");
+        self.foreign_amount = Some(ForeignAmount {
+            amount: value,
+            currency,
+        });
+        self.go_current_index(&TxTab::Amount);
+        Ok(Some(rate))
+    }
+
+    /// Returns the fee of the transfer. Empty if there is none
+    pub fn get_fee(&self) -> &str {
+        &self.fee
+    }
+
+    /// Sets the fee of the transfer from an amount like `2.50` or `0.5 * 3`.
+    /// An empty text removes the fee
+    pub fn set_fee(&mut self, input: &str) -> Result<(), FeeError> {
+        if self.editing_tx {
+            return Err(FeeError::Editing);
+        }
+
+        let mut fee = input.trim().to_string();
+
+        if !fee.is_empty() {
+            if let VerifyingOutput::NotAccepted(err) = self.verify_amount(&mut fee) {
+                return Err(FeeError::NotAccepted(err));
+            }
+        }
+
+        self.fee = fee;
+        Ok(())
+    }
+
+    /// Returns the exchange rate of the transfer. Empty if there is none
+    pub fn get_exchange_rate(&self) -> &str {
+        &self.exchange_rate
+    }
+
+    /// Sets the exchange rate of a transfer between tx methods of different currencies.
+    /// An empty text removes the rate
+    pub fn set_exchange_rate(&mut self, input: &str) -> Result<(), ExchangeRateError> {
+        let rate = input.trim();
+
+        if !rate.is_empty() && !rate.parse::<f64>().is_ok_and(|rate| rate > 0.0) {
+            return Err(ExchangeRateError::Invalid(rate.to_string()));
+        }
+
+        self.exchange_rate = rate.to_string();
+        Ok(())
+    }
+
+    /// Loads the exchange rate the tx was saved with from the db
+    pub fn load_exchange_rate(&mut self, conn: &Connection) {
+        self.exchange_rate = get_tx_exchange_rate(self.id_num, conn)
+            .map(|rate| rate.to_string())
+            .unwrap_or_default();
+    }
+
+    /// Returns the exchange rate to save the tx with. `None` if the tx is not a transfer between
+    /// tx methods of different currencies. Tx methods without a currency use the base currency
+    pub fn get_transfer_rate(&self, conn: &Connection) -> Result<Option<f64>, ExchangeRateError> {
+        if self.tx_type != "Transfer" {
+            return Ok(None);
+        }
+
+        let currencies = get_tx_method_currencies(conn);
+        let from_currency = currencies
+            .get(&self.from_method)
+            .cloned()
+            .unwrap_or_default();
+        let to_currency = currencies.get(&self.to_method).cloned().unwrap_or_default();
+
+        if from_currency == to_currency {
+            return Ok(None);
+        }
+
+        match self.exchange_rate.parse::<f64>() {
+            Ok(rate) => Ok(Some(rate)),
+            Err(_) => Err(ExchangeRateError::Missing(from_currency, to_currency)),
+        }
+    }
+
+    /// Saves the tx to be added once its date arrives instead of adding it now.
+    /// Only a new tx dated after today can be scheduled
+    pub fn schedule_tx(&mut self, conn: &mut Connection) -> Result<(), String> {
+        if let Some(output) = self.check_all_fields() {
+            return Err(output.to_string());
+        }
+
+        if self.editing_tx {
+            return Err(ScheduleError::Editing.to_string());
+        }
+
+        if !self.splits.is_empty() || !self.fee.is_empty() {
+            return Err(ScheduleError::SplitOrFee.to_string());
+        }
+
+        // scheduled txs are saved without an exchange rate
+        if self.get_transfer_rate(conn) != Ok(None) {
+            return Err(ScheduleError::ExchangeRate.to_string());
+        }
+
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        if self.date <= today {
+            return Err(ScheduleError::NotFutureDate.to_string());
+        }
+
+        let tx_method = self.get_tx_method();
+
+        let save = |conn: &mut Connection| -> sqlResult<()> {
+            let sp = conn.savepoint()?;
+
+            let schedule_id = add_scheduled_tx(
+                &self.date,
+                &self.details,
+                &tx_method,
+                &self.amount,
+                &self.tx_type,
+                &self.tags,
+                &sp,
+            )?;
+
+            let activity_num = add_new_activity(ActivityType::ScheduleTX, &sp)?;
+            let scheduled_tx = [
+                self.date.clone(),
+                self.details.clone(),
+                tx_method.clone(),
+                self.amount.clone(),
+                self.tx_type.clone(),
+                self.tags.clone(),
+                format!("Scheduled {schedule_id}"),
+            ];
+            add_new_activity_tx(&scheduled_tx, activity_num, &sp)?;
+
+            sp.commit()
+        };
+
+        save(conn).map_err(|e| ScheduleError::FailedSave(e).to_string())
+    }
+
+    /// Returns every part the tx is split into
+    pub fn get_splits(&self) -> &Vec<SplitPart> {
+        &self.splits
+    }
+
+    /// Returns the split parts in the same format they are entered in like `food 30.00, @cash 10.00`
+    pub fn get_split_text(&self) -> String {
+        self.splits
+            .iter()
+            .map(|part| {
+                let mut values = Vec::new();
+                if !part.tags.is_empty() {
+                    values.push(part.tags.clone());
+                }
+                if !part.tx_method.is_empty() {
+                    values.push(format!("@{}", part.tx_method));
+                }
+                values.push(part.amount.clone());
+                values.join(" ")
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Splits the tx into parts from a text like `food 30, @cash 20, household @card 10`.
+    /// Every part is a tag, a tx method starting with @ or both followed by the amount of that part.
+    /// A part without a tag or a tx method uses the tags or the tx method field.
+    /// Whatever is left from the tx amount goes to the fields. An empty text removes the split
+    pub fn set_splits(&mut self, input: &str, conn: &Connection) -> Result<(), SplitError> {
+        if self.editing_tx {
+            return Err(SplitError::Editing);
+        }
+
+        let mut splits = Vec::new();
+
+        for part in input
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+        {
+            let Some((values, amount)) = part.rsplit_once(' ') else {
+                return Err(SplitError::InvalidPart(part.to_string()));
+            };
+
+            let (tags, tx_method) = match values.split_once('@') {
+                Some((tags, tx_method)) => (tags.trim(), tx_method.trim()),
+                None => (values.trim(), ""),
+            };
+
+            if (tags.is_empty() && tx_method.is_empty()) || values.matches('@').count() > 1 {
+                return Err(SplitError::InvalidPart(part.to_string()));
+            }
+
+            let mut tx_method = tx_method.to_string();
+            if !tx_method.is_empty() {
+                if let VerifyingOutput::NotAccepted(err) =
+                    self.verify_tx_method(&mut tx_method, conn)
+                {
+                    return Err(SplitError::NotAccepted(err));
+                }
+            }
+
+            let mut amount = amount.to_string();
+            match self.verify_amount(&mut amount) {
+                VerifyingOutput::Accepted(_) => {}
+                VerifyingOutput::NotAccepted(err) => return Err(SplitError::NotAccepted(err)),
+                VerifyingOutput::Nothing(_) => {
+                    return Err(SplitError::InvalidPart(part.to_string()))
                 }
-                Err(e) => Err(TxUpdateError::FailedAddTx(e).to_string()),
             }
+
+            splits.push(SplitPart {
+                tags: tags.to_string(),
+                tx_method,
+                amount,
+            });
         }
+
+        self.splits = splits;
+        Ok(())
+    }
+
+    /// Returns every part that will be added for a split tx with the empty tags and tx methods
+    /// taken from the fields. If the split parts do not cover the whole amount,
+    /// the rest is added as another part with the tags and the tx method of the fields
+    pub fn get_split_txs(&self) -> Result<Vec<SplitPart>, SplitError> {
+        if self.tx_type == "Transfer" && self.splits.iter().any(|part| !part.tx_method.is_empty()) {
+            return Err(SplitError::TransferMethod);
+        }
+
+        let total = self
+            .amount
+            .parse::<f64>()
+            .map_err(|_| SplitError::NotAccepted(NAType::ParsingError(AType::Amount)))?;
+
+        let split_total: f64 = self
+            .splits
+            .iter()
+            .map(|part| part.amount.parse::<f64>().unwrap_or(0.0))
+            .sum();
+
+        let remaining = total - split_total;
+
+        if remaining < -0.005 {
+            return Err(SplitError::ExceedsAmount(self.amount.clone()));
+        }
+
+        let tags = if self.tags.is_empty() {
+            "Unknown".to_string()
+        } else {
+            self.tags.clone()
+        };
+        let tx_method = self.get_tx_method();
+
+        let mut parts = self
+            .splits
+            .iter()
+            .map(|part| SplitPart {
+                tags: if part.tags.is_empty() {
+                    tags.clone()
+                } else {
+                    part.tags.clone()
+                },
+                tx_method: if part.tx_method.is_empty() {
+                    tx_method.clone()
+                } else {
+                    part.tx_method.clone()
+                },
+                amount: part.amount.clone(),
+            })
+            .collect::<Vec<SplitPart>>();
+
+        if remaining > 0.005 {
+            parts.push(SplitPart {
+                tags,
+                tx_method,
+                amount: format!("{remaining:.2}"),
+            });
+        }
+        Ok(parts)
     }
 
     pub fn get_search_tx(
@@ -280,20 +901,23 @@ impl TxData {
 
     /// Adds a value to tx status
     pub fn add_tx_status(&mut self, data: String) {
+        if data.starts_with("Error") {
+            warn!("{data}");
+        }
         if self.tx_status.len() == 30 {
             self.tx_status.remove(0);
         }
         self.tx_status.push(data);
     }
 
-    pub fn check_autofill(&mut self, current_tab: &TxTab, conn: &Connection) {
+    pub fn check_autofill(&mut self, current_tab: &TxTab, all_tags: &[String], conn: &Connection) {
         self.autofill.clear();
 
         self.autofill = match current_tab {
             TxTab::Details => self.autofill_details(&self.details, conn),
             TxTab::FromMethod => self.autofill_tx_method(&self.from_method, conn),
             TxTab::ToMethod => self.autofill_tx_method(&self.to_method, conn),
-            TxTab::Tags => self.autofill_tags(&self.tags, conn),
+            TxTab::Tags => self.autofill_tags_from(&self.tags, all_tags),
             _ => String::new(),
         }
     }
@@ -406,6 +1030,11 @@ impl TxData {
         self.go_current_index(&TxTab::Tags);
     }
 
+    /// Checks the inputted tags against the tag policies of the config
+    pub fn check_tag_constraints(&self, config: &Config) -> VerifyingOutput {
+        self.verify_tag_constraints(&self.tags, config)
+    }
+
     /// Checks the inputted tags to make sure it's properly separated by a comma
     pub fn check_tags_forced(&mut self, conn: &Connection) -> VerifyingOutput {
         let mut tags = self.tags.clone();
@@ -460,25 +1089,51 @@ impl TxData {
         false
     }
 
-    /// Checks for b on amount field to replace with the balance of the tx method field
+    /// Whether the amount field has anything that needs to be calculated
+    /// such as a calculation symbol, a balance or a suffix
+    pub fn is_amount_expression(&self) -> bool {
+        self.amount
+            .chars()
+            .any(|c| !c.is_ascii_digit() && !matches!(c, '.' | ' ' | '<' | '>' | '='))
+    }
+
+    /// Returns whether the amount uses the balance of the tx method field with b or a percentage
+    pub fn is_balance_amount(&self) -> bool {
+        let user_amount = self.amount.to_lowercase();
+        user_amount.contains('b') || user_amount.contains('%')
+    }
+
+    /// Returns the current balance of the tx method field. `None` if the method does not exist
+    pub fn get_from_method_balance(&self, conn: &Connection) -> Option<String> {
+        let all_methods = get_all_tx_methods(conn);
+        let last_balances = get_last_balances(conn);
+
+        all_methods
+            .iter()
+            .position(|method| *method == self.from_method)
+            .map(|index| last_balances[index].to_string())
+    }
+
+    /// Checks for b and percentages on amount field to replace with the balance of the tx method field.
+    /// `10%` becomes 10 percent of the balance
     fn check_b_field(&mut self, conn: &Connection) -> Result<(), VerifyingOutput> {
         self.check_suffixes();
         let user_amount = self.amount.to_lowercase();
+        let uses_balance = self.is_balance_amount();
 
         // 'b' represents the current balance of the original tx method
-        if user_amount.contains('b') && !self.from_method.is_empty() {
-            let all_methods = get_all_tx_methods(conn);
-
-            // get all the method's final balance, loop through the balances and match the tx method name
-            let last_balances = get_last_balances(conn);
+        if uses_balance && !self.from_method.is_empty() {
+            if let Some(balance) = self.get_from_method_balance(conn) {
+                // a negative balance is kept inside parentheses so the minus is not taken as a subtraction
+                let balance = if balance.starts_with('-') {
+                    format!("({balance})")
+                } else {
+                    balance
+                };
 
-            for x in 0..all_methods.len() {
-                if all_methods[x] == self.from_method {
-                    self.amount = user_amount.replace('b', &last_balances[x]);
-                    break;
-                }
+                self.amount = replace_percentages(&user_amount.replace('b', &balance), &balance);
             }
-        } else if user_amount.contains('b') && self.from_method.is_empty() {
+        } else if uses_balance && self.from_method.is_empty() {
             return Err(VerifyingOutput::NotAccepted(NAType::InvalidBValue));
         }
         Ok(())
@@ -598,6 +1253,92 @@ impl TxData {
         }
     }
 
+    /// Returns the position of the field for the undo history. `None` if no field is selected
+    fn get_field_position(current_tab: &TxTab) -> Option<usize> {
+        match current_tab {
+            TxTab::Date => Some(0),
+            TxTab::Details => Some(1),
+            TxTab::FromMethod => Some(2),
+            TxTab::ToMethod => Some(3),
+            TxTab::Amount => Some(4),
+            TxTab::TxType => Some(5),
+            TxTab::Tags => Some(6),
+            TxTab::Nothing => None,
+        }
+    }
+
+    fn get_field_mut(&mut self, position: usize) -> &mut String {
+        match position {
+            0 => &mut self.date,
+            1 => &mut self.details,
+            2 => &mut self.from_method,
+            3 => &mut self.to_method,
+            4 => &mut self.amount,
+            5 => &mut self.tx_type,
+            _ => &mut self.tags,
+        }
+    }
+
+    /// Saves the current value of the field so the next edit can be undone
+    fn save_undo(&mut self, current_tab: &TxTab) {
+        let Some(position) = TxData::get_field_position(current_tab) else {
+            return;
+        };
+
+        let value = self.get_field_mut(position).clone();
+
+        // repeated saves of the same value such as backspace on an empty field are skipped
+        if self.undo_history.last() == Some(&(position, value.clone(), self.current_index)) {
+            return;
+        }
+
+        if self.undo_history.len() >= 200 {
+            self.undo_history.remove(0);
+        }
+        self.undo_history
+            .push((position, value, self.current_index));
+    }
+
+    /// Restores the field to the value it had before the last edit on it.
+    /// Returns false if there is nothing to undo on the field
+    pub fn undo_field(&mut self, current_tab: &TxTab) -> bool {
+        let Some(position) = TxData::get_field_position(current_tab) else {
+            return false;
+        };
+
+        let Some(index) = self
+            .undo_history
+            .iter()
+            .rposition(|(field, _, _)| *field == position)
+        else {
+            return false;
+        };
+
+        let (_, value, cursor) = self.undo_history.remove(index);
+        *self.get_field_mut(position) = value;
+        self.current_index = cursor;
+
+        if position == 4 {
+            self.foreign_amount = None;
+        }
+        true
+    }
+
+    /// Removes the whole value of the field. Can be undone like any other edit
+    pub fn clear_field(&mut self, current_tab: &TxTab) {
+        let Some(position) = TxData::get_field_position(current_tab) else {
+            return;
+        };
+
+        self.save_undo(current_tab);
+        self.get_field_mut(position).clear();
+        self.current_index = 0;
+
+        if position == 4 {
+            self.foreign_amount = None;
+        }
+    }
+
     /// Moves index by one value to left
     pub fn move_index_left(&mut self, current_tab: &TxTab) {
         let data_len = self.get_data_len(current_tab);
@@ -727,6 +1468,7 @@ impl TxData {
         is_search: bool,
         conn: &Connection,
     ) -> Result<(), SteppingError> {
+        self.foreign_amount = None;
         if self.check_b_field(conn).is_err() {
             return Err(SteppingError::UnknownBValue);
         }
@@ -768,6 +1510,7 @@ impl TxData {
         is_search: bool,
         conn: &Connection,
     ) -> Result<(), SteppingError> {
+        self.foreign_amount = None;
         if self.check_b_field(conn).is_err() {
             return Err(SteppingError::UnknownBValue);
         }
@@ -827,9 +1570,9 @@ impl TxData {
         status
     }
 
-    /// Add a previously deleted tx again but with a new `id_num`
-    pub fn switch_tx_id(&self, new_id: i32, activity_num: i32, conn: &mut Connection) {
-        add_tx(
+    /// Add a previously deleted tx again but with a new `id_num` without committing anything
+    pub fn switch_tx_id(&self, new_id: i32, activity_num: i32, conn: &Connection) -> sqlResult<()> {
+        insert_tx(
             &self.date,
             &self.details,
             &self.get_tx_method(),
@@ -837,9 +1580,9 @@ impl TxData {
             &self.tx_type,
             &self.tags,
             Some(&new_id.to_string()),
+            self.get_transfer_rate(conn).ok().flatten(),
             conn,
-        )
-        .unwrap();
+        )?;
 
         add_new_activity_tx(
             &[
@@ -853,7 +1596,7 @@ impl TxData {
             ],
             activity_num,
             conn,
-        );
+        )
     }
 
     /// Whether the required fields for balance section data generate is filled up
@@ -976,3 +1719,28 @@ impl TxData {
         changes_data
     }
 }
+
+/// Replaces every number followed by `%` with the calculation of that percent of the balance
+fn replace_percentages(user_amount: &str, balance: &str) -> String {
+    let mut replaced = String::new();
+
+    for c in user_amount.chars() {
+        if c != '%' {
+            replaced.push(c);
+            continue;
+        }
+
+        let number_start = replaced
+            .trim_end()
+            .rfind(|c: char| !c.is_ascii_digit() && c != '.')
+            .map_or(0, |index| index + 1);
+        let number = replaced.trim_end()[number_start..].to_string();
+
+        // a % without a number before it has nothing to calculate
+        if !number.is_empty() {
+            replaced.truncate(number_start);
+            replaced.push_str(&format!("({number}*{balance}/100)"));
+        }
+    }
+    replaced
+}
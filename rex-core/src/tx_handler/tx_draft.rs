@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Result;
+use std::path::{Path, PathBuf};
+
+use crate::db::ForeignAmount;
+use crate::tx_handler::SplitPart;
+
+/// The fields of an Add Tx form that was not sent yet. It is saved beside the db while the form
+/// is being filled so it can be restored if Rex stops before the form is sent or cleared
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TxDraft {
+    pub date: String,
+    pub details: String,
+    pub from_method: String,
+    pub to_method: String,
+    pub amount: String,
+    pub tx_type: String,
+    pub tags: String,
+    pub note: String,
+    pub splits: Vec<SplitPart>,
+    pub fee: String,
+    pub exchange_rate: String,
+    pub foreign_amount: Option<ForeignAmount>,
+    /// The `id_num` of the tx that was being edited. None for a new tx
+    pub editing_id: Option<i32>,
+}
+
+impl TxDraft {
+    /// Returns the filled fields one per line like `Details: Lunch`
+    #[must_use]
+    pub fn get_summary(&self) -> String {
+        let tx_method = if self.to_method.is_empty() {
+            self.from_method.clone()
+        } else {
+            format!("{} to {}", self.from_method, self.to_method)
+        };
+
+        [
+            ("Date", self.date.as_str()),
+            ("Details", &self.details),
+            ("Tx Method", &tx_method),
+            ("Amount", &self.amount),
+            ("Tx Type", &self.tx_type),
+            ("Tags", &self.tags),
+            ("Note", &self.note),
+            ("Fee", &self.fee),
+            ("Exchange Rate", &self.exchange_rate),
+        ]
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<String>>()
+        .join("\n")
+    }
+}
+
+/// Returns the location of the draft file which is kept beside the db like `data.draft.json`.
+/// Every profile has its own
+#[must_use]
+pub fn get_draft_path(db_path: &Path) -> PathBuf {
+    let stem = db_path.file_stem().map_or_else(
+        || "data".to_string(),
+        |stem| stem.to_string_lossy().to_string(),
+    );
+    db_path.with_file_name(format!("{stem}.draft.json"))
+}
+
+/// Saves the draft to the file. It is written to a temporary file first so a crash while saving
+/// does not leave half of a draft behind
+pub fn save_draft(draft_path: &Path, draft: &TxDraft) -> Result<()> {
+    let temp_path = draft_path.with_extension("json.tmp");
+
+    fs::write(&temp_path, serde_json::to_string(draft)?)?;
+    fs::rename(temp_path, draft_path)
+}
+
+/// Loads the draft of the file. None if there is no draft or it could not be read
+#[must_use]
+pub fn load_draft(draft_path: &Path) -> Option<TxDraft> {
+    let content = fs::read_to_string(draft_path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Removes the draft file if there is one
+pub fn remove_draft(draft_path: &Path) {
+    fs::remove_file(draft_path).ok();
+}
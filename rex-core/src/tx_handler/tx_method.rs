@@ -0,0 +1,581 @@
+use chrono::Local;
+use rusqlite::{Connection, Result as sqlResult};
+use tracing::info;
+
+use crate::db::{
+    add_tx_method_columns, get_archived_tx_methods, get_first_tx_date, get_starting_balance,
+    get_tx_method_currencies, get_tx_method_groups, get_tx_method_tx_ids, get_tx_method_types,
+    merge_tx_method_columns, remove_tx_method_columns, rename_column, set_credit_limit,
+    set_tx_method_archived, set_tx_method_color, set_tx_method_currency, set_tx_method_group,
+    set_tx_method_type, STARTING_BALANCE_TAG,
+};
+use crate::outputs::{TxMethodError, VerifyingOutput};
+use crate::state::{ActivityType, MethodType};
+use crate::tx_handler::add_tx::insert_tx;
+use crate::tx_handler::delete_tx::remove_tx;
+use crate::tx_handler::TxData;
+use crate::utility::traits::DataVerifier;
+use crate::utility::{
+    add_new_activity, add_new_activity_tx, check_restricted, get_all_tx_methods, get_last_balances,
+    get_last_tx_id, get_tx_id_num, parse_hex_color,
+};
+
+/// Checks whether the name can be used for a new tx method and returns the trimmed name.
+/// `existing_methods` are the names that are already taken
+pub fn verify_tx_method_name(
+    name: &str,
+    existing_methods: &Vec<String>,
+) -> Result<String, TxMethodError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(TxMethodError::Empty);
+    }
+
+    // transfers are shown as `Method to Method` so the word would make them ambiguous
+    if name
+        .split_whitespace()
+        .any(|word| word.to_lowercase() == "to")
+    {
+        return Err(TxMethodError::ContainsTo);
+    }
+
+    // the name is used as a quoted column name
+    if name.contains('"') {
+        return Err(TxMethodError::ContainsQuote);
+    }
+
+    if check_restricted(name, None) {
+        return Err(TxMethodError::Restricted(name.to_string()));
+    }
+
+    if check_restricted(name, Some(existing_methods)) {
+        return Err(TxMethodError::AlreadyExists(name.to_string()));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Adds a new tx method from an input like `Savings` or `Savings, 500`. The optional starting
+/// balance is added as a tx dated today so it shows up like any other balance change.
+/// A negative starting balance is added as an expense. Returns the name of the new method
+pub fn add_tx_method(input: &str, conn: &mut Connection) -> Result<String, TxMethodError> {
+    let (name, balance) = match input.split_once(',') {
+        Some((name, balance)) => (name, balance.trim()),
+        None => (input, ""),
+    };
+
+    let name = verify_tx_method_name(name, &get_all_tx_methods(conn))?;
+
+    let tx_type = if balance.starts_with('-') {
+        "Expense"
+    } else {
+        "Income"
+    };
+
+    let mut amount = balance.to_string();
+    if let VerifyingOutput::NotAccepted(err) = TxData::new_empty().verify_amount(&mut amount) {
+        return Err(TxMethodError::InvalidBalance(err));
+    }
+
+    let has_balance = !amount.is_empty() && amount.parse::<f64>().unwrap_or(0.0) != 0.0;
+
+    let sp = conn.savepoint().map_err(TxMethodError::FailedSave)?;
+    add_tx_method_columns(&[name.clone()], &sp).map_err(TxMethodError::FailedSave)?;
+
+    if has_balance {
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        insert_tx(
+            &today,
+            "Starting balance",
+            &name,
+            &amount,
+            tx_type,
+            STARTING_BALANCE_TAG,
+            None,
+            None,
+            &sp,
+        )
+        .and_then(|()| log_new_tx(&sp))
+        .map_err(TxMethodError::FailedSave)?;
+    }
+    sp.commit().map_err(TxMethodError::FailedSave)?;
+
+    info!(
+        tx_method = name,
+        starting_balance = amount,
+        "Added tx method"
+    );
+    Ok(name)
+}
+
+/// Records the last added tx as a new tx on the activities
+fn log_new_tx(conn: &Connection) -> sqlResult<()> {
+    let id_num = get_last_tx_id(conn)?;
+    let activity_num = add_new_activity(ActivityType::NewTX, conn)?;
+    add_new_activity_tx(&get_tx_id_num(id_num, conn), activity_num, conn)
+}
+
+/// Changes the starting balance of a tx method from an input like `Wallet, 750`. The difference
+/// from the current starting balance is added as an adjustment tx on the date of the first tx
+/// of the method so every balance after it includes the change while the earlier txs stay as
+/// they are. Returns the name of the method and the added difference
+pub fn adjust_starting_balance(
+    input: &str,
+    conn: &mut Connection,
+) -> Result<(String, f64), TxMethodError> {
+    let Some((name, balance)) = input.split_once(',') else {
+        return Err(TxMethodError::MissingBalance);
+    };
+
+    let Some(method) = get_all_tx_methods(conn)
+        .into_iter()
+        .find(|method| method.eq_ignore_ascii_case(name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(name.trim().to_string()));
+    };
+
+    let mut amount = balance.trim().to_string();
+    if amount.is_empty() {
+        return Err(TxMethodError::MissingBalance);
+    }
+    if let VerifyingOutput::NotAccepted(err) = TxData::new_empty().verify_amount(&mut amount) {
+        return Err(TxMethodError::InvalidBalance(err));
+    }
+
+    let new_balance = amount.parse::<f64>().unwrap_or(0.0);
+    let difference = new_balance - get_starting_balance(&method, conn);
+
+    if (difference * 100.0).round() == 0.0 {
+        return Err(TxMethodError::SameBalance(format!("{new_balance:.2}")));
+    }
+
+    let tx_type = if difference < 0.0 {
+        "Expense"
+    } else {
+        "Income"
+    };
+    let date = get_first_tx_date(&method, conn)
+        .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+
+    let sp = conn.savepoint().map_err(TxMethodError::FailedSave)?;
+    insert_tx(
+        &date,
+        "Starting balance adjustment",
+        &method,
+        &format!("{:.2}", difference.abs()),
+        tx_type,
+        STARTING_BALANCE_TAG,
+        None,
+        None,
+        &sp,
+    )
+    .and_then(|()| log_new_tx(&sp))
+    .map_err(TxMethodError::FailedSave)?;
+    sp.commit().map_err(TxMethodError::FailedSave)?;
+
+    info!(
+        tx_method = method,
+        difference, "Adjusted the starting balance"
+    );
+    Ok((method, difference))
+}
+
+/// Renames a tx method from an input like `Cash, Wallet`. The old name is not case sensitive.
+/// Returns the old and the new name of the method
+pub fn rename_tx_method(
+    input: &str,
+    conn: &mut Connection,
+) -> Result<(String, String), TxMethodError> {
+    let Some((old_name, new_name)) = input.split_once(',') else {
+        return Err(TxMethodError::MissingNewName);
+    };
+
+    let mut other_methods = get_all_tx_methods(conn);
+
+    let Some(index) = other_methods
+        .iter()
+        .position(|method| method.eq_ignore_ascii_case(old_name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(old_name.trim().to_string()));
+    };
+
+    // the method itself is left out so only the casing of a name can be fixed too
+    let old_name = other_methods.remove(index);
+    let new_name = verify_tx_method_name(new_name, &other_methods)?;
+
+    rename_column(&old_name, &new_name, conn).map_err(TxMethodError::FailedSave)?;
+
+    Ok((old_name, new_name))
+}
+
+/// Archives the tx method or brings it back if it is already archived. The method name is not
+/// case sensitive. Returns the name of the method and whether it is archived now
+pub fn switch_tx_method_archive(
+    name: &str,
+    conn: &Connection,
+) -> Result<(String, bool), TxMethodError> {
+    let all_methods = get_all_tx_methods(conn);
+
+    let Some(method) = all_methods
+        .iter()
+        .find(|method| method.eq_ignore_ascii_case(name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(name.trim().to_string()));
+    };
+
+    let archived_methods = get_archived_tx_methods(conn);
+    let archive = !archived_methods.contains(method);
+
+    // at least one method must stay usable for adding new txs
+    if archive && archived_methods.len() + 1 >= all_methods.len() {
+        return Err(TxMethodError::LastActive);
+    }
+
+    set_tx_method_archived(method, archive, conn).map_err(TxMethodError::FailedSave)?;
+
+    Ok((method.to_string(), archive))
+}
+
+/// Changes the type of a tx method from an input like `Card, Credit`. The method name and the
+/// type are not case sensitive. Returns the name of the method and its new type
+pub fn change_tx_method_type(
+    input: &str,
+    conn: &Connection,
+) -> Result<(String, MethodType), TxMethodError> {
+    let Some((name, method_type)) = input.split_once(',') else {
+        return Err(TxMethodError::MissingType);
+    };
+
+    let Some(method) = get_all_tx_methods(conn)
+        .into_iter()
+        .find(|method| method.eq_ignore_ascii_case(name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(name.trim().to_string()));
+    };
+
+    let Some(method_type) = MethodType::from_str(method_type) else {
+        return Err(TxMethodError::InvalidType(method_type.trim().to_string()));
+    };
+
+    set_tx_method_type(&method, method_type, conn).map_err(TxMethodError::FailedSave)?;
+
+    Ok((method, method_type))
+}
+
+/// Changes the currency of a tx method from an input like `Wallet, EUR`. Leaving the currency
+/// empty makes the method use the base currency again. Returns the name of the method and its
+/// new currency
+pub fn change_tx_method_currency(
+    input: &str,
+    conn: &Connection,
+) -> Result<(String, String), TxMethodError> {
+    let Some((name, currency)) = input.split_once(',') else {
+        return Err(TxMethodError::MissingCurrency);
+    };
+
+    let Some(method) = get_all_tx_methods(conn)
+        .into_iter()
+        .find(|method| method.eq_ignore_ascii_case(name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(name.trim().to_string()));
+    };
+
+    let currency = currency.trim().to_uppercase();
+
+    if !currency.is_empty()
+        && (currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()))
+    {
+        return Err(TxMethodError::InvalidCurrency(currency));
+    }
+
+    set_tx_method_currency(&method, &currency, conn).map_err(TxMethodError::FailedSave)?;
+
+    Ok((method, currency))
+}
+
+/// Changes the color of a tx method from an input like `Wallet, green` or `Wallet, #ff5555`.
+/// Leaving the color empty brings back the default color. Returns the name of the method and the
+/// saved hex color code
+pub fn change_tx_method_color(
+    input: &str,
+    conn: &Connection,
+) -> Result<(String, String), TxMethodError> {
+    let Some((name, color)) = input.split_once(',') else {
+        return Err(TxMethodError::MissingColor);
+    };
+
+    let Some(method) = get_all_tx_methods(conn)
+        .into_iter()
+        .find(|method| method.eq_ignore_ascii_case(name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(name.trim().to_string()));
+    };
+
+    let color = color.trim().to_lowercase();
+
+    let color = match color.as_str() {
+        "" => String::new(),
+        "cyan" => "#8be9fd".to_string(),
+        "green" => "#50fa7b".to_string(),
+        "orange" => "#ffb86c".to_string(),
+        "pink" => "#ff79c6".to_string(),
+        "purple" => "#bd93f9".to_string(),
+        "red" => "#ff5555".to_string(),
+        "yellow" => "#f1fa8c".to_string(),
+        hex if parse_hex_color(hex).is_some() => hex.to_string(),
+        _ => return Err(TxMethodError::InvalidColor(color)),
+    };
+
+    set_tx_method_color(&method, &color, conn).map_err(TxMethodError::FailedSave)?;
+
+    Ok((method, color))
+}
+
+/// Changes the credit limit of a credit tx method from an input like `Card, 5000`. A limit of 0
+/// or an empty one removes it. Returns the name of the method and the new limit
+pub fn change_credit_limit(input: &str, conn: &Connection) -> Result<(String, f64), TxMethodError> {
+    let Some((name, limit)) = input.split_once(',') else {
+        return Err(TxMethodError::MissingLimit);
+    };
+
+    let Some((method, method_type)) = get_tx_method_types(conn)
+        .into_iter()
+        .find(|(method, _)| method.eq_ignore_ascii_case(name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(name.trim().to_string()));
+    };
+
+    if method_type != MethodType::Credit {
+        return Err(TxMethodError::NotCredit(method));
+    }
+
+    let limit = limit.trim();
+
+    let limit = if limit.is_empty() || limit.parse::<f64>() == Ok(0.0) {
+        0.0
+    } else {
+        let mut amount = limit.to_string();
+        if let VerifyingOutput::NotAccepted(err) = TxData::new_empty().verify_amount(&mut amount) {
+            return Err(TxMethodError::InvalidBalance(err));
+        }
+        amount.parse::<f64>().unwrap_or(0.0)
+    };
+
+    set_credit_limit(&method, limit, conn).map_err(TxMethodError::FailedSave)?;
+
+    Ok((method, limit))
+}
+
+/// Changes the group of a tx method from an input like `Wallet, Liquid`. An existing group is
+/// matched without caring about the case. Leaving the group empty removes the method from its
+/// group. Returns the name of the method and its new group
+pub fn change_tx_method_group(
+    input: &str,
+    conn: &Connection,
+) -> Result<(String, String), TxMethodError> {
+    let Some((name, group)) = input.split_once(',') else {
+        return Err(TxMethodError::MissingGroup);
+    };
+
+    let Some(method) = get_all_tx_methods(conn)
+        .into_iter()
+        .find(|method| method.eq_ignore_ascii_case(name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(name.trim().to_string()));
+    };
+
+    let group = group.trim();
+
+    // keep the casing of the group the other methods are already in
+    let group = get_tx_method_groups(conn)
+        .into_values()
+        .find(|existing| existing.eq_ignore_ascii_case(group))
+        .unwrap_or_else(|| group.to_string());
+
+    set_tx_method_group(&method, &group, conn).map_err(TxMethodError::FailedSave)?;
+
+    Ok((method, group))
+}
+
+/// What merging one tx method into another changes. Shown for confirmation before the merge
+#[derive(Debug, PartialEq)]
+pub struct MergeDetails {
+    pub source: String,
+    pub target: String,
+    pub moved_txs: usize,
+    pub removed_transfers: usize,
+    pub moved_scheduled: usize,
+    pub source_balance: f64,
+    pub target_balance: f64,
+}
+
+impl MergeDetails {
+    /// Returns the itemized changes of the merge to show on the confirmation popup
+    #[must_use]
+    pub fn get_summary(&self) -> String {
+        format!(
+            "Merge {} into {}?\n\n{}",
+            self.source,
+            self.target,
+            self.get_changes()
+        )
+    }
+
+    /// Returns the confirmation text of deleting the source method after moving its txs
+    #[must_use]
+    pub fn get_deletion_summary(&self) -> String {
+        format!(
+            "Delete {} and move its transactions to {}?\n\n{}",
+            self.source,
+            self.target,
+            self.get_changes()
+        )
+    }
+
+    fn get_changes(&self) -> String {
+        format!(
+            "{} transaction(s) move to {target}\n{} transfer(s) between them are removed\n{} scheduled transaction(s) move to {target}\nBalance: {:.2} + {:.2} = {:.2}\n{source} is removed after the merge",
+            self.moved_txs,
+            self.removed_transfers,
+            self.moved_scheduled,
+            self.target_balance,
+            self.source_balance,
+            self.target_balance + self.source_balance,
+            source = self.source,
+            target = self.target,
+        )
+    }
+}
+
+/// Checks an input like `Old Card, Card` for merging the first tx method into the second one
+/// and returns what the merge would change. The method names are not case sensitive
+pub fn get_merge_details(input: &str, conn: &Connection) -> Result<MergeDetails, TxMethodError> {
+    let Some((source, target)) = input.split_once(',') else {
+        return Err(TxMethodError::MissingMergeTarget);
+    };
+
+    let all_methods = get_all_tx_methods(conn);
+
+    let find_method = |name: &str| {
+        all_methods
+            .iter()
+            .position(|method| method.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| TxMethodError::NotFound(name.trim().to_string()))
+    };
+
+    let source_index = find_method(source)?;
+    let target_index = find_method(target)?;
+
+    if source_index == target_index {
+        return Err(TxMethodError::SameMethod);
+    }
+
+    let source = all_methods[source_index].clone();
+    let target = all_methods[target_index].clone();
+
+    let currencies = get_tx_method_currencies(conn);
+    let source_currency = currencies.get(&source).cloned().unwrap_or_default();
+    let target_currency = currencies.get(&target).cloned().unwrap_or_default();
+
+    if source_currency != target_currency {
+        return Err(TxMethodError::CurrencyMismatch(source, target));
+    }
+
+    let between = [
+        format!("{source} to {target}"),
+        format!("{target} to {source}"),
+    ];
+
+    let count_rows = |table: &str, condition: &str| -> Result<usize, TxMethodError> {
+        conn.query_row(
+            &format!("SELECT COUNT(*) FROM {table} WHERE {condition}"),
+            [&source, &between[0], &between[1]],
+            |row| row.get(0),
+        )
+        .map_err(TxMethodError::FailedSave)
+    };
+
+    let uses_source = "(tx_method = ?1 OR substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+        OR substr(tx_method, -length(?1) - 4) = ' to ' || ?1)";
+    let not_between = "tx_method NOT IN (?2, ?3)";
+    let is_between = "tx_method IN (?2, ?3)";
+
+    let moved_txs = count_rows("tx_all", &format!("{uses_source} AND {not_between}"))?;
+    let removed_transfers = count_rows("tx_all", is_between)?;
+    let moved_scheduled = count_rows("scheduled_txs", &format!("{uses_source} AND {not_between}"))?;
+
+    let last_balances = get_last_balances(conn);
+    let get_balance = |index: usize| {
+        last_balances
+            .get(index)
+            .and_then(|balance| balance.parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+
+    Ok(MergeDetails {
+        source_balance: get_balance(source_index),
+        target_balance: get_balance(target_index),
+        source,
+        target,
+        moved_txs,
+        removed_transfers,
+        moved_scheduled,
+    })
+}
+
+/// Merges the source tx method into the target. Every tx of the source is moved to the target,
+/// the transfers between them are removed and the source method is deleted
+pub fn merge_tx_methods(
+    source: &str,
+    target: &str,
+    conn: &mut Connection,
+) -> Result<(), TxMethodError> {
+    merge_tx_method_columns(source, target, conn).map_err(TxMethodError::FailedSave)?;
+
+    info!(source, target, "Merged tx methods");
+    Ok(())
+}
+
+/// Checks the name of a tx method that is going to be deleted. The name is not case sensitive.
+/// Returns the name of the method and how many txs it has
+pub fn get_deletion_details(
+    name: &str,
+    conn: &Connection,
+) -> Result<(String, usize), TxMethodError> {
+    let all_methods = get_all_tx_methods(conn);
+
+    let Some(method) = all_methods
+        .iter()
+        .find(|method| method.eq_ignore_ascii_case(name.trim()))
+    else {
+        return Err(TxMethodError::NotFound(name.trim().to_string()));
+    };
+
+    // at least one method must stay usable for adding new txs
+    let archived_methods = get_archived_tx_methods(conn);
+    if !all_methods
+        .iter()
+        .any(|other| other != method && !archived_methods.contains(other))
+    {
+        return Err(TxMethodError::LastActive);
+    }
+
+    Ok((method.to_string(), get_tx_method_tx_ids(method, conn).len()))
+}
+
+/// Deletes the tx method alongside every tx of it. The transfers it is part of are removed from
+/// the other tx methods too. Nothing is deleted if any part fails
+pub fn delete_tx_method(tx_method: &str, conn: &mut Connection) -> Result<(), TxMethodError> {
+    let id_nums = get_tx_method_tx_ids(tx_method, conn);
+
+    let sp = conn.savepoint().map_err(TxMethodError::FailedSave)?;
+    for id_num in id_nums {
+        remove_tx(id_num, &sp).map_err(TxMethodError::FailedSave)?;
+    }
+    remove_tx_method_columns(tx_method, &sp).map_err(TxMethodError::FailedSave)?;
+    sp.commit().map_err(TxMethodError::FailedSave)?;
+
+    info!(tx_method, "Deleted tx method");
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::chart::{Rgb, LINE_COLORS};
+use crate::db::get_saved_tx_method_colors;
+use crate::outputs::ConfigError;
+use crate::state::HomeColumn;
+use crate::tx_handler::TxData;
+use crate::utility::get_all_tx_methods;
+
+/// User editable settings of the app. Saved as `config.json` in the same location as
+/// `backup_paths.json`. Any missing field falls back to its default value
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    /// Milliseconds to wait for a key press between each frame of the initial page animation
+    pub initial_poll_ms: u64,
+    /// Milliseconds to wait for a key press between each frame of the home and chart page animation
+    pub animation_poll_ms: u64,
+    /// Seconds without any key press after which the app goes idle. 0 disables idle mode
+    pub idle_after_secs: u64,
+    /// Milliseconds to wait for a key press between each redraw while idle
+    pub idle_poll_ms: u64,
+    /// How fast the chart gets rendered progressively. 1 is the normal speed,
+    /// higher values are faster and 0 renders the full chart at once
+    pub chart_animation_speed: u8,
+    /// Hex color code such as `#ff5555` pinned to a tx method. Used on the chart lines, the
+    /// balance sections and the tx method selection. A color picked on the Home page takes its
+    /// place and methods without a color get one from the default chart colors
+    pub tx_method_colors: HashMap<String, String>,
+    /// The Home table columns in the order they are shown. Accepts Date, Details, TX Method,
+    /// Amount, Type, Tags, Status and ID
+    pub home_columns: Vec<String>,
+    /// Tx method the Add Tx page starts with. Empty or unknown methods leave the field empty
+    pub default_tx_method: String,
+    /// Tx type the Add Tx page starts with. Accepts Income, Expense and Transfer
+    pub default_tx_type: String,
+    /// Tags the Add Tx page starts with
+    pub default_tags: String,
+    /// The currency the amounts are saved in like `USD`. Amounts entered in other currencies
+    /// like `25 EUR` get converted to it
+    pub base_currency: String,
+    /// How much one unit of a currency like `EUR` is in the base currency
+    pub currency_rates: HashMap<String, f64>,
+    /// Whether the rates missing from `currency_rates` are fetched online. Fetched rates are
+    /// cached and fetched again at most once a day
+    pub fetch_currency_rates: bool,
+    /// Credit limit utilization percentage after which it is shown in red
+    pub credit_utilization_warning: f64,
+    /// Whether a tx must have at least one tag other than Unknown when it is entered
+    pub require_tag: bool,
+    /// The only tags a tx can be entered with. Empty allows every tag
+    pub allowed_tags: Vec<String>,
+    /// How many tags a tx can be entered with at most. 0 allows any number of tags
+    pub max_tags: usize,
+    /// Named SQL queries shown on the Reports page in the given order. Only queries that do not
+    /// change the db are run
+    pub reports: Vec<ReportQuery>,
+    /// Script started with every event like an added tx. The event is written to its stdin as a
+    /// JSON line described by `docs/events.schema.json` and its name is in `REX_EVENT`
+    pub event_hook_script: String,
+    /// Unix socket every event is written to as a JSON line
+    pub event_hook_socket: String,
+    /// Keys that replace the default key of an action like `{"GoAddTx": "n", "Quit": "ctrl+q"}`.
+    /// The default key of a remapped action stops working on the pages the action is on
+    pub key_bindings: HashMap<String, String>,
+    /// The built-in theme the interface is drawn with. Accepts Dracula, Gruvbox, Solarized Dark,
+    /// Solarized Light and Plain
+    pub theme: String,
+    /// Hex color codes that replace the colors of the theme like `{"background": "#1e1e2e"}`.
+    /// Accepts background, text, border, selected, highlighted, header, red, blue and gray
+    pub theme_colors: HashMap<String, String>,
+}
+
+/// A saved query of the Reports page like
+/// `{"name": "Food by month", "query": "SELECT substr(date, 1, 7), SUM(amount) FROM tx_all ..."}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct ReportQuery {
+    pub name: String,
+    pub query: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            initial_poll_ms: 40,
+            animation_poll_ms: 2,
+            idle_after_secs: 60,
+            idle_poll_ms: 1000,
+            chart_animation_speed: 1,
+            tx_method_colors: HashMap::new(),
+            home_columns: HomeColumn::default_columns()
+                .iter()
+                .map(|column| column.get_title().to_string())
+                .collect(),
+            default_tx_method: String::new(),
+            default_tx_type: String::new(),
+            default_tags: String::new(),
+            base_currency: String::new(),
+            currency_rates: HashMap::new(),
+            fetch_currency_rates: false,
+            credit_utilization_warning: 30.0,
+            require_tag: false,
+            allowed_tags: Vec::new(),
+            max_tags: 0,
+            reports: Vec::new(),
+            event_hook_script: String::new(),
+            event_hook_socket: String::new(),
+            key_bindings: HashMap::new(),
+            theme: "Dracula".to_string(),
+            theme_colors: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config from the given json file
+    pub fn load(config_path: &Path) -> Result<Self, ConfigError> {
+        let content = fs::read_to_string(config_path).map_err(ConfigError::FailedRead)?;
+        serde_json::from_str(&content).map_err(ConfigError::InvalidConfig)
+    }
+
+    /// Returns the color of every tx method. The color picked on the Home page comes first, then
+    /// the one pinned in the config. Methods without either get a default color based on their
+    /// position among all tx methods so it does not change with what is shown. Invalid color codes
+    /// are ignored
+    pub fn get_tx_method_colors(&self, conn: &Connection) -> HashMap<String, Rgb> {
+        let saved_colors = get_saved_tx_method_colors(conn);
+
+        get_all_tx_methods(conn)
+            .into_iter()
+            .enumerate()
+            .map(|(index, method)| {
+                let color = saved_colors
+                    .get(&method)
+                    .or_else(|| self.tx_method_colors.get(&method))
+                    .and_then(|color| parse_hex_color(color))
+                    .unwrap_or(LINE_COLORS[LINE_COLORS.len() - 1 - index % LINE_COLORS.len()]);
+                (method, color)
+            })
+            .collect()
+    }
+
+    /// Returns the shown Home table columns. Unknown and repeated columns are ignored
+    /// and the default columns are used if nothing is left
+    pub fn get_home_columns(&self) -> Vec<HomeColumn> {
+        let mut columns = Vec::new();
+
+        for column in self
+            .home_columns
+            .iter()
+            .filter_map(|c| HomeColumn::from_str(c))
+        {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+
+        if columns.is_empty() {
+            HomeColumn::default_columns()
+        } else {
+            columns
+        }
+    }
+
+    /// Returns a new tx for the Add Tx page filled with the default tx method, tx type and tags.
+    /// Defaults that are not valid are left empty
+    pub fn get_default_tx(&self, conn: &Connection) -> TxData {
+        let tx_method = get_all_tx_methods(conn)
+            .into_iter()
+            .find(|method| method.eq_ignore_ascii_case(self.default_tx_method.trim()))
+            .unwrap_or_default();
+
+        let tx_type = ["Income", "Expense", "Transfer"]
+            .into_iter()
+            .find(|tx_type| tx_type.eq_ignore_ascii_case(self.default_tx_type.trim()))
+            .unwrap_or_default();
+
+        TxData::new_with_defaults(&tx_method, tx_type, self.default_tags.trim())
+    }
+
+    /// Returns the rate set for the currency in the config. The currency code is not case sensitive
+    pub fn get_currency_rate(&self, currency: &str) -> Option<f64> {
+        self.currency_rates
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(currency))
+            .map(|(_, rate)| *rate)
+    }
+
+    /// Writes the config to the given json file
+    pub fn save(&self, config_path: &Path) -> std::io::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(config_path, content)
+    }
+}
+
+/// Turns a hex color code like `#ff5555` into a color
+pub fn parse_hex_color(color: &str) -> Option<Rgb> {
+    let hex = color.trim().strip_prefix('#')?;
+
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+
+    Some(Rgb(r, g, b))
+}
+
+/// Returns the location of `config.json` which is kept beside the original db location
+pub fn get_config_path(original_db_path: &Path) -> PathBuf {
+    let mut config_path = original_db_path.to_owned();
+    config_path.pop();
+    config_path.push("config.json");
+    config_path
+}
+
+/// Loads `config.json`. A new one with the default values is created if it does not exist
+/// so it can be edited by the user
+pub fn get_config(original_db_path: &Path) -> Result<Config, ConfigError> {
+    let config_path = get_config_path(original_db_path);
+
+    if !config_path.exists() {
+        let config = Config::default();
+        config
+            .save(&config_path)
+            .map_err(ConfigError::FailedCreate)?;
+        return Ok(config);
+    }
+
+    Config::load(&config_path)
+}
@@ -0,0 +1,89 @@
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::db::{get_archived_tx_methods, get_tx_method_groups, get_tx_method_types};
+use crate::state::MethodType;
+use crate::utility::get_all_tx_methods;
+
+/// The tx methods and the info about them that is needed on every drawn frame. Fetched once
+/// and kept until the tx methods change so drawing does not have to query the db
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TxMethodCache {
+    /// Every tx method in the order of the `balance_all` columns
+    pub all: Vec<String>,
+    /// The tx methods that are not archived
+    pub active: Vec<String>,
+    pub types: HashMap<String, MethodType>,
+    /// The group of every tx method that is in one
+    pub groups: HashMap<String, String>,
+}
+
+impl TxMethodCache {
+    #[must_use]
+    pub fn new(conn: &Connection) -> Self {
+        let all = get_all_tx_methods(conn);
+        let archived = get_archived_tx_methods(conn);
+        let active = all
+            .iter()
+            .filter(|method| !archived.contains(method))
+            .cloned()
+            .collect();
+
+        TxMethodCache {
+            all,
+            active,
+            types: get_tx_method_types(conn),
+            groups: get_tx_method_groups(conn),
+        }
+    }
+
+    /// Fetches everything again. Must be called after a tx method was added, removed or changed
+    pub fn reload(&mut self, conn: &Connection) {
+        *self = TxMethodCache::new(conn);
+    }
+
+    /// Returns the total balance of each tx method group from a Balance row that contains
+    /// every tx method. The groups are sorted by their name
+    #[must_use]
+    pub fn get_group_balances(&self, balance: &[String]) -> Vec<(String, f64)> {
+        if self.groups.is_empty() {
+            return Vec::new();
+        }
+
+        let mut group_balances: Vec<(String, f64)> = Vec::new();
+
+        for (method, value) in self.all.iter().zip(balance.iter().skip(1)) {
+            let Some(group) = self.groups.get(method) else {
+                continue;
+            };
+            let value = value.parse::<f64>().unwrap_or(0.0);
+
+            match group_balances.iter_mut().find(|(name, _)| name == group) {
+                Some((_, total)) => *total += value,
+                None => group_balances.push((group.to_string(), value)),
+            }
+        }
+
+        group_balances.sort_by(|a, b| a.0.cmp(&b.0));
+        group_balances
+    }
+
+    /// Returns the total balance without the savings tx methods from a Balance row that
+    /// contains every tx method. `None` if there are no savings methods
+    #[must_use]
+    pub fn get_spendable_balance(&self, balance: &[String]) -> Option<f64> {
+        if !self.types.values().any(|t| *t == MethodType::Savings) {
+            return None;
+        }
+
+        let spendable = self
+            .all
+            .iter()
+            .zip(balance.iter().skip(1))
+            .filter(|(method, _)| self.types.get(*method) != Some(&MethodType::Savings))
+            .filter_map(|(_, value)| value.parse::<f64>().ok())
+            .sum();
+
+        Some(spendable)
+    }
+}
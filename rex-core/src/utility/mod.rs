@@ -0,0 +1,12 @@
+mod config;
+mod method_cache;
+mod profile;
+mod sub_func;
+pub mod traits;
+mod utils;
+
+pub use config::*;
+pub use method_cache::*;
+pub use profile::*;
+pub use sub_func::*;
+pub use utils::*;
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Result as ioResult;
+use std::path::{Path, PathBuf};
+
+use crate::outputs::ProfileError;
+
+/// The profile that uses `data.sqlite` itself
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// The read only view of the txs of every profile together. Never saved as a profile of its own
+pub const COMBINED_PROFILE: &str = "All Profiles";
+
+#[derive(Serialize, Deserialize)]
+struct ProfileInfo {
+    profile: String,
+}
+
+/// Returns the path of the db of the profile. Profiles other than the default one are saved
+/// in the `profiles` folder next to `data.sqlite`
+pub fn get_profile_db_path(db_path: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        return db_path.to_owned();
+    }
+
+    let mut profile_path = db_path.to_owned();
+    profile_path.pop();
+    profile_path.push("profiles");
+    profile_path.push(format!("{profile}.sqlite"));
+    profile_path
+}
+
+/// Returns the default profile followed by every other profile with a db, sorted by name
+pub fn get_all_profiles(db_path: &Path) -> Vec<String> {
+    let mut profiles_dir = db_path.to_owned();
+    profiles_dir.pop();
+    profiles_dir.push("profiles");
+
+    let mut profiles = fs::read_dir(profiles_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "sqlite" {
+                        return None;
+                    }
+                    Some(path.file_stem()?.to_str()?.to_string())
+                })
+                .filter(|profile| profile != DEFAULT_PROFILE)
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+    profiles
+}
+
+/// Checks the name of a profile and returns it trimmed. An existing profile is matched
+/// without caring about the case. The name becomes a file name so only letters, numbers,
+/// spaces, - and _ are accepted
+pub fn verify_profile_name(name: &str, db_path: &Path) -> Result<String, ProfileError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(ProfileError::Empty);
+    }
+
+    if name.eq_ignore_ascii_case(COMBINED_PROFILE) {
+        return Ok(COMBINED_PROFILE.to_string());
+    }
+
+    if let Some(profile) = get_all_profiles(db_path)
+        .into_iter()
+        .find(|profile| profile.eq_ignore_ascii_case(name))
+    {
+        return Ok(profile);
+    }
+
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_')
+    {
+        return Err(ProfileError::InvalidName(name.to_string()));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Returns the profile that was used last. The default profile is used if none was saved
+pub fn get_active_profile(original_db_path: &Path) -> String {
+    let json_path = get_profile_json_path(original_db_path);
+
+    fs::read_to_string(json_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<ProfileInfo>(&content).ok())
+        .map_or_else(|| DEFAULT_PROFILE.to_string(), |info| info.profile)
+}
+
+/// Saves the profile to `profile.json` so the app starts with it next time
+pub fn save_active_profile(original_db_path: &Path, profile: &str) -> ioResult<()> {
+    let json_path = get_profile_json_path(original_db_path);
+
+    let info = ProfileInfo {
+        profile: profile.to_string(),
+    };
+
+    fs::write(json_path, serde_json::to_string(&info)?)
+}
+
+fn get_profile_json_path(original_db_path: &Path) -> PathBuf {
+    let mut json_path = original_db_path.to_owned();
+    json_path.pop();
+    json_path.push("profile.json");
+    json_path
+}
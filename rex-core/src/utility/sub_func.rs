@@ -0,0 +1,822 @@
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{Connection, MAIN_DB};
+use std::collections::HashMap;
+
+use crate::activity::{ActivityDetails, ActivityTx};
+use crate::db::{
+    get_balance_snapshot, get_exchange_rates, get_split_parts, get_tx_attachments,
+    get_tx_foreign_amount, get_tx_note, get_tx_status, link_split_parts, set_tx_attachments,
+    set_tx_foreign_amount, set_tx_note, set_tx_status,
+};
+use crate::outputs::ComparisonType;
+use crate::state::{ActivityType, DateType};
+use crate::tx_handler::{remove_tx, TxData};
+use crate::utility::{
+    add_new_activity, add_new_activity_tx, check_comparison, get_all_tx_methods, get_sql_dates,
+};
+
+/// Returns the balance of all methods based on year and month point.
+/// if the balance is empty/0 at the given point for any one of the methods
+/// it will try to find the balance for that method from one of the earlier points.
+/// Uses the month end snapshot of the point if one exists.
+pub fn get_last_time_balance(
+    month: usize,
+    year: usize,
+    tx_method: &Vec<String>,
+    conn: &Connection,
+) -> HashMap<String, f64> {
+    let target_id_num = month as i32 + (year as i32 * 12);
+
+    if let Some(snapshot) = get_balance_snapshot(target_id_num, tx_method, conn) {
+        return tx_method
+            .iter()
+            .map(|method| (method.to_string(), snapshot[method]))
+            .collect();
+    }
+
+    get_last_time_balance_uncached(month, year, tx_method, conn)
+}
+
+/// Works the same way as `get_last_time_balance` but always goes through the `balance_all` rows
+/// without checking the month end snapshots
+pub fn get_last_time_balance_uncached(
+    month: usize,
+    year: usize,
+    tx_method: &Vec<String>,
+    conn: &Connection,
+) -> HashMap<String, f64> {
+    // We can get the id_num of the month which is saved in the database based on the
+    // month and year index there is passed.
+    let target_id_num = month as i32 + (year as i32 * 12);
+
+    let mut final_value = HashMap::new();
+    for i in tx_method {
+        final_value.insert(i.to_string(), 0.0);
+    }
+
+    // balance_all starts at point 1. 1 means month 1, year 2022/0.
+    // There is no earlier balance than this
+    if target_id_num == 0 {
+        return final_value;
+    }
+
+    let mut checked_methods: Vec<&str> = Vec::new();
+
+    let tx_method_string = tx_method
+        .iter()
+        .map(|m| format!("\"{m}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // the process goes like this
+    // m1  m2  m3  id
+    //  0   0  10   1
+    // 10  10   0   2
+    // 10   0   0   3
+    // fetch all three rows, start checking from id 3
+    // m1 is already found, save that, go to the previous row, save m2,
+    // go to the previous row, save m3 -> break -> return the data
+
+    let query = format!(
+        "SELECT {tx_method_string} FROM balance_all WHERE id_num <= ? ORDER BY id_num DESC",
+    );
+
+    let mut stmt = conn.prepare_cached(&query).unwrap();
+    let mut rows = stmt.query([target_id_num]).unwrap();
+
+    while let Some(row) = rows.next().unwrap() {
+        // all methods checked = no longer necessary to check any more rows
+        if checked_methods.len() == tx_method.len() {
+            break;
+        }
+        // check each tx_method column in the current row
+        for (i, item) in tx_method.iter().enumerate() {
+            if !checked_methods.contains(&item.as_str()) {
+                let balance: f64 = row.get(i).unwrap();
+
+                // we only need non-zero balance
+                if balance != 0.0 {
+                    *final_value.get_mut(item).unwrap() = balance;
+                    checked_methods.push(item);
+                }
+            }
+        }
+    }
+
+    final_value
+}
+
+/// Returns the balance of all methods right before the given date. Starts from the balance of the
+/// previous month end and goes through the txs of the month that happened before the date
+pub fn get_balance_before(
+    date: NaiveDate,
+    tx_method: &Vec<String>,
+    conn: &Connection,
+) -> HashMap<String, f64> {
+    // there are no txs before the first supported date
+    let date = date.max(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+
+    let month = date.month0() as usize;
+    let year = (date.year() - 2022) as usize;
+
+    let mut balance = get_last_time_balance(month, year, tx_method, conn);
+
+    if date.day() > 1 {
+        let dates = (
+            date.with_day(1).unwrap().to_string(),
+            date.pred_opt().unwrap().to_string(),
+        );
+        get_txs_window(conn, &dates, 0, None, &mut balance);
+    }
+
+    balance
+}
+
+/// Returns how much the txs that match the condition changed the balance of each tx method.
+/// The condition goes to the WHERE clause of the query with the date as its only parameter
+pub fn get_method_changes(condition: &str, date: &str, conn: &Connection) -> HashMap<String, f64> {
+    let mut changes = HashMap::new();
+
+    let mut statement = conn
+        .prepare(&format!(
+            "SELECT tx_method, amount, tx_type, COALESCE(rate, 1.0) FROM tx_all
+            LEFT JOIN tx_exchange_rates USING (id_num) WHERE {condition}"
+        ))
+        .expect("could not prepare statement");
+
+    let rows = statement
+        .query_map([date], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, f64>(3)?,
+            ))
+        })
+        .unwrap();
+
+    for (tx_method, amount, tx_type, rate) in rows.flatten() {
+        let amount = amount.parse::<f64>().unwrap();
+
+        match tx_type.as_str() {
+            "Expense" => *changes.entry(tx_method).or_insert(0.0) -= amount,
+            "Income" => *changes.entry(tx_method).or_insert(0.0) += amount,
+            _ => {
+                let (from_method, to_method) = tx_method.split_once(" to ").unwrap();
+                *changes.entry(from_method.to_string()).or_insert(0.0) -= amount;
+                *changes.entry(to_method.to_string()).or_insert(0.0) += amount * rate;
+            }
+        }
+    }
+
+    changes
+}
+
+/// Returns how much the txs that are dated after the given date changed the balance of each tx method
+pub fn get_changes_after(date: NaiveDate, conn: &Connection) -> HashMap<String, f64> {
+    get_method_changes("date > ?", &date.to_string(), conn)
+}
+
+/// The functions sends all the changes that happened after transactions on the month and year provided
+pub fn get_all_changes(month: usize, year: usize, conn: &Connection) -> Vec<Vec<String>> {
+    let dates = get_sql_dates(month, year, &DateType::Monthly);
+    get_changes_window(&dates, 0, None, conn)
+}
+
+/// Works the same way as `get_all_changes` but only returns the changes of `limit` txs
+/// starting from `offset` within the given start and end date. `None` limit returns the rest of the txs
+pub fn get_changes_window(
+    dates: &(String, String),
+    offset: usize,
+    limit: Option<usize>,
+    conn: &Connection,
+) -> Vec<Vec<String>> {
+    let mut final_result = Vec::new();
+    let tx_methods = get_all_tx_methods(conn);
+
+    let (datetime_1, datetime_2) = dates;
+
+    let mut statement = conn
+        .prepare_cached("SELECT * FROM changes_all Where date BETWEEN date(?) AND date(?) ORDER BY date, id_num LIMIT ? OFFSET ?")
+        .expect("could not prepare statement");
+
+    let rows = statement
+        .query_map(
+            (datetime_1, datetime_2, get_sql_limit(limit), offset as i64),
+            |row| {
+                let mut balance_vec: Vec<String> = Vec::new();
+                // Why start at 2? Because the first two rows are date and id_num
+                for i in 2..tx_methods.len() + 2 {
+                    balance_vec.push(row.get(i).unwrap());
+                }
+                Ok(balance_vec)
+            },
+        )
+        .unwrap();
+
+    for i in rows {
+        final_result.push(i.unwrap());
+    }
+    final_result
+}
+
+/// Returns the total number of txs that were recorded within the given start and end date
+pub fn get_tx_count(dates: &(String, String), conn: &Connection) -> usize {
+    let (datetime_1, datetime_2) = dates;
+
+    conn.query_row(
+        "SELECT COUNT(*) FROM tx_all WHERE date BETWEEN date(?) AND date(?)",
+        [datetime_1, datetime_2],
+        |row| row.get::<_, i64>(0),
+    )
+    .unwrap_or(0) as usize
+}
+
+/// Returns the total amount of the given tx type of each tx method within the given start and end date.
+/// Transfers are not counted
+pub fn get_period_totals(
+    dates: &(String, String),
+    tx_type: &str,
+    conn: &Connection,
+) -> HashMap<String, f64> {
+    let (datetime_1, datetime_2) = dates;
+
+    let mut totals = HashMap::new();
+
+    let mut statement = conn
+        .prepare(
+            "SELECT tx_method, amount FROM tx_all WHERE date BETWEEN date(?) AND date(?) AND tx_type = ?",
+        )
+        .expect("could not prepare statement");
+
+    let rows = statement
+        .query_map((datetime_1, datetime_2, tx_type), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .unwrap();
+
+    for (tx_method, amount) in rows.flatten() {
+        *totals.entry(tx_method).or_insert(0.0) += amount.parse::<f64>().unwrap();
+    }
+
+    totals
+}
+
+/// SQLite takes a negative limit as no limit
+fn get_sql_limit(limit: Option<usize>) -> i64 {
+    limit.map_or(-1, |limit| limit as i64)
+}
+
+/// Used to retrieving all Transaction within a given date, balance and the `id_num` related to them.
+pub fn get_all_txs(
+    conn: &Connection,
+    month: usize,
+    year: usize,
+) -> (Vec<Vec<String>>, Vec<Vec<String>>, Vec<String>) {
+    // returns all transactions recorded within a given date + balance changes + the relevant id_num
+
+    let all_tx_methods = get_all_tx_methods(conn);
+
+    // we will go through the last month balances and add/subtract
+    // current month's transactions to the related tx method. After each tx calculation, add whatever
+    // balance for each tx method inside a vec to finally return them
+
+    let mut last_month_balance = get_last_time_balance(month, year, &all_tx_methods, conn);
+    let dates = get_sql_dates(month, year, &DateType::Monthly);
+
+    let (final_all_txs, final_all_balances, all_id_num) =
+        get_txs_window(conn, &dates, 0, None, &mut last_month_balance);
+
+    // pushes the final balance that was calculated just now to the db on the balance_all table
+    if let Some(final_balance) = final_all_balances.last() {
+        update_month_balance(month, year, final_balance, conn);
+    }
+
+    (final_all_txs, final_all_balances, all_id_num)
+}
+
+/// Retrieves `limit` txs within the given start and end date starting from `offset` alongside their balance
+/// and `id_num`. `last_balance` must be the balance right before the first tx of the window
+/// and gets updated to the balance after the final tx of it. `None` limit returns the rest of the txs
+pub fn get_txs_window(
+    conn: &Connection,
+    dates: &(String, String),
+    offset: usize,
+    limit: Option<usize>,
+    last_balance: &mut HashMap<String, f64>,
+) -> (Vec<Vec<String>>, Vec<Vec<String>>, Vec<String>) {
+    let all_tx_methods = get_all_tx_methods(conn);
+
+    let mut final_all_txs: Vec<Vec<String>> = Vec::new();
+    let mut final_all_balances: Vec<Vec<String>> = Vec::new();
+    let mut all_id_num = Vec::new();
+
+    let (datetime_1, datetime_2) = dates;
+
+    // preparing the query for db, getting the window of the transactions. Every reload runs the
+    // same query so the statement is kept in the cache of the connection
+    let mut statement = conn
+        .prepare_cached(
+            "SELECT * FROM tx_all WHERE date BETWEEN date(?) AND date(?) ORDER BY date, id_num LIMIT ? OFFSET ?",
+        )
+        .expect("could not prepare statement");
+
+    let rows = statement
+        .query_map(
+            (
+                &datetime_1,
+                &datetime_2,
+                get_sql_limit(limit),
+                offset as i64,
+            ),
+            |row| {
+                // collect the row data and put them in a vec
+                let id_num: i32 = row.get(5).unwrap();
+
+                Ok(vec![
+                    row.get(0).unwrap(),
+                    row.get(1).unwrap(),
+                    row.get(2).unwrap(),
+                    row.get(3).unwrap(),
+                    row.get(4).unwrap(),
+                    row.get(6).unwrap(),
+                    id_num.to_string(),
+                ])
+            },
+        )
+        .unwrap();
+
+    for i in rows.flatten() {
+        // data contains all tx data of a transaction
+        let mut data = i;
+        let id_num = &data.pop().unwrap();
+        all_id_num.push(id_num.to_string());
+        final_all_txs.push(data);
+    }
+
+    let exchange_rates = get_exchange_rates(conn);
+
+    for (i, id_num) in final_all_txs.iter().zip(&all_id_num) {
+        // this is where the calculation for the balance happens. We will loop through each tx,
+        // look at the tx type, tx method and add/subtract the amount on last month balance which was fetched earlier
+
+        // collect data inside variables
+        let tx_type = &i[4];
+        let amount = &i[3].to_string().parse::<f64>().unwrap();
+        let tx_method = &i[2];
+
+        // If the transaction is not a transfer, default balance goes to new_balance_from
+        // and new_balance_to remains empty. On transfer TX both of them are used
+
+        let mut new_balance_from: f64 = 0.0;
+        let mut new_balance_to: f64 = 0.0;
+
+        let mut from_method = String::new();
+        let mut to_method = String::new();
+
+        // add or subtract the amount based on the tx type
+        if tx_type == "Expense" {
+            new_balance_from = last_balance[tx_method] - amount;
+        } else if tx_type == "Income" {
+            new_balance_from = last_balance[tx_method] + amount;
+        } else if tx_type == "Transfer" {
+            let splitted = tx_method.split(" to ").collect::<Vec<&str>>();
+            from_method = splitted[0].to_string();
+            to_method = splitted[1].to_string();
+            new_balance_from = last_balance[&from_method] - amount;
+            // the To Method of a transfer between different currencies receives the converted amount
+            let rate = id_num
+                .parse()
+                .ok()
+                .and_then(|id_num: i32| exchange_rates.get(&id_num).copied())
+                .unwrap_or(1.0);
+            new_balance_to = last_balance[&to_method] + amount * rate;
+        }
+
+        // make changes to the balance map based on the tx
+        // for transfer TX first block executes
+        // new_balance_to != 0 means it's a transfer transaction
+        if new_balance_to == 0.0 {
+            *last_balance.get_mut(tx_method).unwrap() = new_balance_from;
+        } else {
+            *last_balance.get_mut(&from_method).unwrap() = new_balance_from;
+            *last_balance.get_mut(&to_method).unwrap() = new_balance_to;
+        }
+
+        // push all the changes gathered to the return variable
+        let mut to_push = vec![];
+        for i in &all_tx_methods {
+            to_push.push(format!("{:.2}", last_balance[i]));
+        }
+
+        final_all_balances.push(to_push);
+    }
+
+    (final_all_txs, final_all_balances, all_id_num)
+}
+
+/// Saves the balance after the final tx of the month on the `balance_all` table.
+/// Skipped on a read only connection as the saved balance is only a cache of the txs
+pub fn update_month_balance(
+    month: usize,
+    year: usize,
+    final_balance: &[String],
+    conn: &Connection,
+) {
+    if conn.is_readonly(MAIN_DB).unwrap_or(false) {
+        return;
+    }
+
+    let all_tx_methods = get_all_tx_methods(conn);
+    let target_id_num = month as i32 + 1 + (year as i32 * 12);
+
+    let balance_query = format!(
+        "UPDATE balance_all SET {} WHERE id_num = {}",
+        final_balance
+            .iter()
+            .enumerate()
+            .map(|(i, balance)| format!(r#""{}" = "{}""#, all_tx_methods[i], balance))
+            .collect::<Vec<String>>()
+            .join(", "),
+        target_id_num
+    );
+    conn.execute(&balance_query, [])
+        .expect("Error updating balance query");
+}
+
+/// Returns the absolute final balance or the last row on `balance_all` table.
+pub fn get_last_balances(conn: &Connection) -> Vec<String> {
+    let tx_method = get_all_tx_methods(conn);
+    let mut query = format!("SELECT {tx_method:?} FROM balance_all ORDER BY id_num DESC LIMIT 1");
+    query = query.replace('[', "");
+    query = query.replace(']', "");
+
+    let mut statement = conn
+        .prepare_cached(&query)
+        .expect("could not prepare statement");
+
+    let final_balance = statement.query_row([], |row| {
+        let mut final_data: Vec<String> = Vec::new();
+        for i in 0..tx_method.len() {
+            let row_data: f64 = row.get(i).unwrap();
+            final_data.push(row_data.to_string());
+        }
+        Ok(final_data)
+    });
+    final_balance.unwrap()
+}
+
+/// Creates the query to search for specific tx, gathers all rows and id numbers
+#[allow(clippy::too_many_arguments)]
+pub fn get_search_data(
+    date: &str,
+    details: &str,
+    from_method: &str,
+    to_method: &str,
+    amount: &str,
+    tx_type: &str,
+    tags: &str,
+    date_type: &DateType,
+    conn: &Connection,
+) -> (Vec<Vec<String>>, Vec<String>) {
+    let mut all_txs = Vec::new();
+    let mut all_ids = Vec::new();
+
+    // This will be used for the activity tx
+    let tx_method = if tx_type == "Transfer" && !from_method.is_empty() && !to_method.is_empty() {
+        format!("{from_method} to {to_method}").trim().to_string()
+    } else if tx_type == "Transfer" && !from_method.is_empty() && to_method.is_empty() {
+        format!("{from_method} to ?").trim().to_string()
+    } else if tx_type == "Transfer" && from_method.is_empty() && !to_method.is_empty() {
+        format!("? to {to_method}").trim().to_string()
+    } else if from_method.is_empty() && to_method.is_empty() {
+        String::new()
+    } else {
+        from_method.to_string()
+    };
+
+    let mut valid_fields = 0;
+
+    let mut query = "SELECT * FROM tx_all WHERE 1=1".to_string();
+
+    if !date.is_empty() {
+        valid_fields += 1;
+        match date_type {
+            DateType::Exact => query.push_str(&format!(r#" AND date = "{date}""#)),
+            DateType::Monthly => {
+                let splitted_date: Vec<usize> =
+                    date.split('-').map(|s| s.parse().unwrap()).collect();
+
+                let month_index = splitted_date[1] - 1;
+                let year_index = splitted_date[0] - 2022;
+
+                let (date_1, date_2) = get_sql_dates(month_index, year_index, date_type);
+
+                query.push_str(&format!(
+                    r#" AND date BETWEEN date("{date_1}") AND date("{date_2}")"#,
+                ));
+            }
+            DateType::Yearly => {
+                let year_index = date.parse::<usize>().unwrap() - 2022;
+
+                let (date_1, date_2) = get_sql_dates(0, year_index, date_type);
+
+                query.push_str(&format!(
+                    r#" AND date BETWEEN date("{date_1}") AND date("{date_2}")"#
+                ));
+            }
+        }
+    }
+
+    if !details.is_empty() {
+        valid_fields += 1;
+        query.push_str(&format!(r#" AND details LIKE "%{details}%""#,));
+    }
+
+    if !tx_type.is_empty() {
+        valid_fields += 1;
+        query.push_str(&format!(r#" AND tx_type = "{tx_type}""#,));
+    }
+
+    if !amount.is_empty() {
+        valid_fields += 1;
+        let comparison_type = check_comparison(amount);
+
+        let comparison_symbol = match comparison_type {
+            ComparisonType::BiggerThan => ">",
+            ComparisonType::SmallerThan => "<",
+            ComparisonType::Equal => "",
+            ComparisonType::EqualOrBigger => ">=",
+            ComparisonType::EqualOrSmaller => "<=",
+        };
+        let amount = amount.replace(comparison_symbol, "");
+
+        query.push_str(&format!(r#" AND {comparison_type} "{amount}""#));
+    }
+
+    if tx_type == "Transfer" {
+        // If neither are empty, look for exact matches
+        // Otherwise do partial matching
+        if !from_method.is_empty() && !to_method.is_empty() {
+            valid_fields += 1;
+            query.push_str(&format!(
+                r#" AND tx_method = "{from_method} to {to_method}""#
+            ));
+        } else if !from_method.is_empty() {
+            valid_fields += 1;
+            query.push_str(&format!(r#" AND tx_method LIKE "{from_method} to %" "#));
+        } else if !to_method.is_empty() {
+            valid_fields += 1;
+            query.push_str(&format!(r#" AND tx_method LIKE "% to {to_method}""#));
+        }
+    } else if tx_type != "Transfer" && !from_method.is_empty() {
+        valid_fields += 1;
+        query.push_str(&format!(r#" AND tx_method = "{from_method}""#));
+    }
+
+    if !tags.is_empty() {
+        valid_fields += 1;
+        let all_tags = tags.split(", ");
+        let tag_conditions = all_tags
+            .map(|tag| {
+                format!(
+                    r#"CASE 
+                          WHEN tags LIKE "{tag}, %" THEN 1
+                          WHEN tags LIKE "%, {tag}" THEN 1
+                          WHEN tags LIKE "%, {tag}," THEN 1
+                          WHEN tags = "{tag}" THEN 1
+                          ELSE 0
+                      END = 1"#,
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" OR ");
+        query.push_str(&format!(" AND ({tag_conditions})"));
+    }
+
+    let mut statement = conn.prepare(&query).unwrap();
+
+    let rows = statement
+        .query_map([], |row| {
+            let id_num: i32 = row.get(5).unwrap();
+
+            Ok(vec![
+                row.get(0).unwrap(),
+                row.get(1).unwrap(),
+                row.get(2).unwrap(),
+                row.get(3).unwrap(),
+                row.get(4).unwrap(),
+                row.get(6).unwrap(),
+                id_num.to_string(),
+            ])
+        })
+        .unwrap();
+
+    for i in rows.flatten() {
+        let mut data = i;
+        let id_num = &data.pop().unwrap();
+        all_ids.push(id_num.to_string());
+        all_txs.push(data);
+    }
+
+    let activity_type = ActivityType::SearchTX(Some(valid_fields));
+    let search_data = vec![date, details, &tx_method, amount, tx_type, tags, ""];
+
+    let activity_num = add_new_activity(activity_type, conn).unwrap();
+    add_new_activity_tx(&search_data, activity_num, conn).unwrap();
+
+    (all_txs, all_ids)
+}
+
+/// Switches `id_num` of 2 txs in the DB to switch the indexes of the value in the UI table
+pub fn switch_tx_index(
+    id_1: i32,
+    id_2: i32,
+    tx_1: &[String],
+    tx_2: &[String],
+    conn: &mut Connection,
+) {
+    let tx_type_1 = &tx_1[4];
+    let tx_type_2 = &tx_2[4];
+
+    let mut tx_data_1 = if tx_type_1 == "Transfer" {
+        let splitted_method = tx_1[2].split(" to ").collect::<Vec<&str>>();
+        let from_method = splitted_method[0];
+        let to_method = splitted_method[1];
+
+        TxData::custom(
+            &tx_1[0],
+            &tx_1[1],
+            from_method,
+            to_method,
+            &tx_1[3],
+            "Transfer",
+            &tx_1[5],
+            id_1,
+        )
+    } else {
+        TxData::custom(
+            &tx_1[0], &tx_1[1], &tx_1[2], "", &tx_1[3], &tx_1[4], &tx_1[5], id_1,
+        )
+    };
+
+    let mut tx_data_2 = if tx_type_2 == "Transfer" {
+        let splitted_method = tx_2[2].split(" to ").collect::<Vec<&str>>();
+        let from_method = splitted_method[0];
+        let to_method = splitted_method[1];
+
+        TxData::custom(
+            &tx_2[0],
+            &tx_2[1],
+            from_method,
+            to_method,
+            &tx_2[3],
+            "Transfer",
+            &tx_2[5],
+            id_2,
+        )
+    } else {
+        TxData::custom(
+            &tx_2[0], &tx_2[1], &tx_2[2], "", &tx_2[3], &tx_2[4], &tx_2[5], id_1,
+        )
+    };
+
+    tx_data_1.load_exchange_rate(conn);
+    tx_data_2.load_exchange_rate(conn);
+
+    // notes, statuses, split links, attachments and foreign amounts get removed alongside
+    // the txs so they are added back with the new id
+    let note_1 = get_tx_note(id_1, conn);
+    let note_2 = get_tx_note(id_2, conn);
+    let status_1 = get_tx_status(id_1, conn);
+    let status_2 = get_tx_status(id_2, conn);
+    let attachments_1 = get_tx_attachments(id_1, conn);
+    let attachments_2 = get_tx_attachments(id_2, conn);
+    let foreign_amount_1 = get_tx_foreign_amount(id_1, conn);
+    let foreign_amount_2 = get_tx_foreign_amount(id_2, conn);
+
+    let swap_id = |id_num: i32| match id_num {
+        x if x == id_1 => id_2,
+        x if x == id_2 => id_1,
+        x => x,
+    };
+    let split_parts = [get_split_parts(id_1, conn), get_split_parts(id_2, conn)].map(|parts| {
+        let mut parts = parts.into_iter().map(swap_id).collect::<Vec<i32>>();
+        parts.sort_unstable();
+        parts
+    });
+
+    // the txs are swapped in a single transaction so a failure leaves both of them untouched
+    let sp = conn.savepoint().unwrap();
+
+    remove_tx(id_1, &sp).unwrap();
+    remove_tx(id_2, &sp).unwrap();
+
+    let activity_num =
+        add_new_activity(ActivityType::IDNumSwap(Some(id_1), Some(id_2)), &sp).unwrap();
+
+    tx_data_1.switch_tx_id(id_2, activity_num, &sp).unwrap();
+    tx_data_2.switch_tx_id(id_1, activity_num, &sp).unwrap();
+
+    set_tx_note(id_2, &note_1, &sp).unwrap();
+    set_tx_note(id_1, &note_2, &sp).unwrap();
+    set_tx_status(id_2, status_1, &sp).unwrap();
+    set_tx_status(id_1, status_2, &sp).unwrap();
+    set_tx_attachments(id_2, &attachments_1, &sp).unwrap();
+    set_tx_attachments(id_1, &attachments_2, &sp).unwrap();
+    set_tx_foreign_amount(id_2, foreign_amount_1.as_ref(), &sp).unwrap();
+    set_tx_foreign_amount(id_1, foreign_amount_2.as_ref(), &sp).unwrap();
+
+    for parts in split_parts {
+        link_split_parts(&parts, &sp).unwrap();
+    }
+
+    sp.commit().unwrap();
+}
+
+/// Returns all activities recorded within a given month and a year and all the activity txs related to the activities
+pub fn get_all_activities(
+    month: usize,
+    year: usize,
+    conn: &Connection,
+) -> (Vec<ActivityDetails>, HashMap<i32, Vec<ActivityTx>>) {
+    let (datetime_1, datetime_2) = get_sql_dates(month, year, &DateType::Monthly);
+
+    let mut statement = conn
+        .prepare("SELECT * from activities WHERE date BETWEEN date(?) AND date(?)")
+        .unwrap();
+
+    // Activity tx fetching happens based on the minimum activity num and the maximum activity num
+    let mut min_activity_num = i32::MAX;
+    let mut max_activity_num = i32::MIN;
+
+    let rows: Vec<ActivityDetails> = statement
+        .query_map([datetime_1, datetime_2], |row| {
+            Ok(ActivityDetails::new(
+                row.get(0).unwrap(),
+                row.get(1).unwrap(),
+                row.get(2).unwrap(),
+                row.get(3).unwrap(),
+            ))
+        })
+        .unwrap()
+        .map(|wrapped_data| {
+            let data = wrapped_data.unwrap();
+            let id = data.activity_num();
+
+            if id > max_activity_num {
+                max_activity_num = id;
+            }
+
+            if id < min_activity_num {
+                min_activity_num = id;
+            }
+            data
+        })
+        .collect();
+
+    let activity_txs = get_all_activity_txs(min_activity_num, max_activity_num, conn);
+
+    (rows, activity_txs)
+}
+
+/// Returns all activity txs within the given activity number range
+pub fn get_all_activity_txs(
+    min_num: i32,
+    max_num: i32,
+    conn: &Connection,
+) -> HashMap<i32, Vec<ActivityTx>> {
+    let mut statement = conn
+        .prepare("SELECT * from activity_txs WHERE activity_num >= ? AND activity_num <= ?")
+        .unwrap();
+
+    // Contains data in the format {activity_num: Vec<ActivityTx>}
+    // In case of search or edit txs, a single activity can impact multiple txs
+    // The rest will always have only 1 tx
+    let mut activity_tx_data = HashMap::new();
+
+    for wrapped_data in statement
+        .query_map([min_num, max_num], |row| {
+            Ok(ActivityTx::new(
+                row.get(0).unwrap(),
+                row.get(1).unwrap(),
+                row.get(2).unwrap(),
+                row.get(3).unwrap(),
+                row.get(4).unwrap(),
+                row.get(5).unwrap(),
+                row.get(6).unwrap(),
+                row.get(7).unwrap(),
+                row.get(8).unwrap(),
+            ))
+        })
+        .unwrap()
+    {
+        let data = wrapped_data.unwrap();
+        activity_tx_data
+            .entry(data.activity_num())
+            .or_insert(Vec::new())
+            .push(data);
+    }
+
+    activity_tx_data
+}
@@ -1,9 +1,12 @@
-use crate::utility::{get_all_details, get_all_tags, get_all_tx_methods, get_best_match};
+use crate::utility::{
+    get_active_tx_methods, get_all_details, get_all_tags, get_best_match, get_details_by_frequency,
+    get_matching_tags,
+};
 use rusqlite::Connection;
 
 pub trait AutoFiller {
     fn autofill_tx_method(&self, user_input: &str, conn: &Connection) -> String {
-        let all_tx_methods = get_all_tx_methods(conn);
+        let all_tx_methods = get_active_tx_methods(conn);
         if !user_input.trim().is_empty() && !all_tx_methods.is_empty() {
             let best_match = get_best_match(user_input, &all_tx_methods);
 
@@ -18,8 +21,11 @@ pub trait AutoFiller {
     }
 
     fn autofill_tags(&self, user_input: &str, conn: &Connection) -> String {
-        let all_tags = get_all_tags(conn);
+        self.autofill_tags_from(user_input, &get_all_tags(conn))
+    }
 
+    /// Works the same way as `autofill_tags` but takes the tags from the given ones instead of the db
+    fn autofill_tags_from(&self, user_input: &str, all_tags: &[String]) -> String {
         if !user_input.trim().is_empty() && !all_tags.is_empty() {
             let splitted = user_input.split(',').map(str::trim).collect::<Vec<&str>>();
 
@@ -29,7 +35,11 @@ pub trait AutoFiller {
                 return String::new();
             }
 
-            let best_match = get_best_match(last_value, &all_tags);
+            // a tag that starts with the typed one is preferred over the closest match
+            let best_match = get_matching_tags(user_input, all_tags)
+                .into_iter()
+                .find(|tag| tag.to_lowercase().starts_with(&last_value.to_lowercase()))
+                .unwrap_or_else(|| get_best_match(last_value, all_tags));
 
             if best_match == last_value {
                 String::new()
@@ -41,7 +51,25 @@ pub trait AutoFiller {
         }
     }
 
+    /// Completes the details with the most used earlier details that starts with the input.
+    /// Falls back to the closest match if none of them starts with it
     fn autofill_details(&self, user_input: &str, conn: &Connection) -> String {
+        let input = user_input.trim().to_lowercase();
+
+        if !input.is_empty() {
+            let completion = get_details_by_frequency(conn)
+                .into_iter()
+                .find(|details| details.to_lowercase().starts_with(&input));
+
+            if let Some(completion) = completion {
+                return if completion == user_input.trim() {
+                    String::new()
+                } else {
+                    completion
+                };
+            }
+        }
+
         let all_details = get_all_details(conn);
         if !user_input.trim().is_empty() && !all_details.is_empty() {
             let best_match = get_best_match(user_input, &all_details);
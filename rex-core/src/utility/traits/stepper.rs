@@ -1,7 +1,7 @@
 use crate::outputs::{NAType, StepType, SteppingError, VerifyingOutput};
-use crate::page_handler::DateType;
+use crate::state::DateType;
 use crate::utility::traits::DataVerifier;
-use crate::utility::{get_all_tags, get_all_tx_methods};
+use crate::utility::{get_active_tx_methods, get_all_tags};
 use chrono::{Duration, NaiveDate};
 use rusqlite::Connection;
 
@@ -105,10 +105,14 @@ pub trait FieldStepper: DataVerifier {
         step_type: StepType,
         conn: &Connection,
     ) -> Result<(), SteppingError> {
-        let all_methods = get_all_tx_methods(conn);
+        let all_methods = get_active_tx_methods(conn);
         let verify_status: VerifyingOutput = self.verify_tx_method(user_method, conn);
 
         match verify_status {
+            // archived methods are still accepted but they are not stepped through
+            VerifyingOutput::Accepted(_) if !all_methods.contains(user_method) => {
+                *user_method = String::from(&all_methods[0]);
+            }
             VerifyingOutput::Accepted(_) => {
                 let current_method_index =
                     all_methods.iter().position(|e| e == user_method).unwrap();
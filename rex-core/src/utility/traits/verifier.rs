@@ -1,7 +1,11 @@
 use crate::outputs::{AType, NAType, VerifyingOutput};
-use crate::page_handler::DateType;
-use crate::utility::{get_all_tags, get_all_tx_methods, get_best_match};
+use crate::state::DateType;
+use crate::utility::{
+    check_tag_policy, evaluate_amount, expand_short_date, get_all_tags, get_all_tx_methods,
+    get_best_match, Config,
+};
 use chrono::naive::NaiveDate;
+use chrono::Local;
 use rusqlite::Connection;
 use std::cmp::Ordering;
 use std::collections::HashSet;
@@ -17,6 +21,7 @@ pub trait DataVerifier {
     /// - the date actually exists
     /// - removes any extra spaces and non-numeric characters
     /// - ensures proper char length for each part of the date
+    /// - expands shorthand dates like `t`, `y`, `-3` and `17` on exact dates
     ///
     /// Finally, tries to correct the date if it was not accepted by
     /// adding 0 if the beginning if the length is smaller than necessary
@@ -27,6 +32,13 @@ pub trait DataVerifier {
         if user_date.is_empty() {
             return VerifyingOutput::Nothing(AType::Date);
         }
+
+        if let DateType::Exact = date_type {
+            if let Some(date) = expand_short_date(user_date, Local::now().date_naive()) {
+                *user_date = date;
+            }
+        }
+
         *user_date = user_date
             .chars()
             .filter(|c| c.is_numeric() || *c == '-')
@@ -264,7 +276,7 @@ pub trait DataVerifier {
     ///
     /// - Amount is empty
     /// - Amount is zero or below
-    /// - Amount text contains a calculation symbol
+    /// - Amount text contains a calculation symbol or parentheses
     /// - contains any extra spaces
     /// - removes any extra spaces and non-numeric characters
     ///
@@ -276,11 +288,13 @@ pub trait DataVerifier {
             return VerifyingOutput::Nothing(AType::Amount);
         }
 
-        let calc_symbols = vec!['*', '/', '+', '-'];
+        let calc_symbols = ['*', '/', '+', '-'];
 
         *user_amount = user_amount
             .chars()
-            .filter(|c| c.is_numeric() || *c == '.' || calc_symbols.contains(c))
+            .filter(|c| {
+                c.is_numeric() || *c == '.' || *c == '(' || *c == ')' || calc_symbols.contains(c)
+            })
             .collect();
 
         // Already checked if the initial amount is empty.
@@ -290,103 +304,15 @@ pub trait DataVerifier {
         }
 
         // check if any of the symbols are present
-        if calc_symbols.iter().any(|s| user_amount.contains(*s)) {
-            // how it works:
-            // the calc_symbol intentionally starts with * and / so these calculations are done first
-            // start a main loop which will only run for the amount of times any one of them from calc_symbols is present
-            // loop over the symbols and check if the symbol is present in the string
-            // find the index of where the symbol is then take the number values from both side of the symbol
-            // example: 1+5*10. We start with *, we initially, we will work with 5*10
-            // isolate the numbers => do the calculation => replace the part of the string we are working with, with the result which is 50
-            // result: 1+50 => break the symbol checking loop and continue the main loop again so we start working with 1+50.
-
-            // get the amount of time the symbols were found in the amount string
-            let count = user_amount
-                .chars()
-                .filter(|c| calc_symbols.contains(c))
-                .count();
-
-            // remove all spaces for easier indexing
-            let mut working_value = user_amount.to_owned();
-
-            for _i in 0..count {
-                for symbol in &calc_symbols {
-                    if let Some(location) = working_value.find(*symbol) {
-                        // if a symbol is found, we want to store the values to its side to these variables.
-                        // example: 1+5 first_value = 1 last_value = 5
-                        let mut first_value = String::new();
-                        let mut last_value = String::new();
-
-                        // skip to symbol location + 1 index value and start taking chars from here until the end
-                        // of the string or until another cal symbol is encountered
-                        for char in working_value.chars().skip(location + 1) {
-                            if calc_symbols.contains(&char) {
-                                break;
-                            }
-                            last_value.push(char);
-                        }
-
-                        // do the same thing as before but this time, reverse the string
-                        for char in working_value
-                            .chars()
-                            .rev()
-                            .skip(working_value.len() - location)
-                        {
-                            if calc_symbols.contains(&char) {
-                                break;
-                            }
-                            first_value.push(char);
-                        }
-                        // un-reverse the string
-                        first_value = first_value.chars().rev().collect();
-
-                        // if either of them is empty, the one that is not empty is the value we want to use for using in replacement
-                        let final_value = if first_value.is_empty() || last_value.is_empty() {
-                            if first_value.is_empty() {
-                                last_value.to_string()
-                            } else {
-                                first_value.to_string()
-                            }
-                        } else {
-                            // if both value is intact, do the calculation and the result is for replacement
-                            let first_num: f64 = match first_value.parse() {
-                                Ok(v) => v,
-                                Err(_) => {
-                                    return VerifyingOutput::NotAccepted(NAType::ParsingError(
-                                        AType::Amount,
-                                    ))
-                                }
-                            };
-
-                            let last_num: f64 = match last_value.parse() {
-                                Ok(v) => v,
-                                Err(_) => {
-                                    return VerifyingOutput::NotAccepted(NAType::ParsingError(
-                                        AType::Amount,
-                                    ))
-                                }
-                            };
-
-                            match *symbol {
-                                '*' => format!("{:.2}", (first_num * last_num)),
-                                '/' => format!("{:.2}", (first_num / last_num)),
-                                '+' => format!("{:.2}", (first_num + last_num)),
-                                '-' => format!("{:.2}", (first_num - last_num)),
-                                _ => String::new(),
-                            }
-                        };
-
-                        // example: 1+5*10
-                        // if everything goes alright, first_value is 5, last_value is 10 and the symbol is *
-                        // replace 5*10 with the earlier result we got which is 50. Continue with 1+50 in the next loop
-                        working_value = working_value
-                            .replace(&format!("{first_value}{symbol}{last_value}"), &final_value);
-
-                        break;
-                    }
-                }
+        if user_amount.contains(|c: char| c == '(' || calc_symbols.contains(&c)) {
+            // symbols at the start or the end have nothing to calculate with. Ignore them
+            // so something like 100+ or -100 is taken as 100
+            let expression = user_amount.trim_matches(|c| calc_symbols.contains(&c));
+
+            match evaluate_amount(expression) {
+                Some(value) => *user_amount = format!("{value:.2}"),
+                None => return VerifyingOutput::NotAccepted(NAType::ParsingError(AType::Amount)),
             }
-            *user_amount = working_value;
         }
 
         // if dot is present but nothing after that, add 2 zero
@@ -547,4 +473,9 @@ pub trait DataVerifier {
             VerifyingOutput::NotAccepted(NAType::NonExistingTag)
         }
     }
+
+    /// Checks if the tags follow the tag policies of the config. See `check_tag_policy`
+    fn verify_tag_constraints(&self, user_tag: &str, config: &Config) -> VerifyingOutput {
+        check_tag_policy(user_tag, config)
+    }
 }
@@ -0,0 +1,1133 @@
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use rusqlite::{Connection, Result as sqlResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Result as ioResult, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use strsim::normalized_levenshtein;
+use tracing::warn;
+
+use crate::db::{
+    cache_rate, get_archived_tx_methods, get_cached_rate, get_credit_limits,
+    get_tx_method_currencies, get_tx_method_types, YEARS,
+};
+use crate::outputs::{
+    AType, AttachmentError, ComparisonType, CurrencyError, DateRangeError, JumpDateError, NAType,
+    VerifyingOutput,
+};
+use crate::state::{ActivityType, DateType, MethodType, SortingDirection, SortingType};
+use crate::utility::{Config, TxMethodCache};
+
+const RESTRICTED: [&str; 6] = ["Total", "Balance", "Changes", "Income", "Expense", "Cancel"];
+
+/// Added after the name of the credit tx methods on the balance sections
+pub const OWED_SUFFIX: &str = " (Owed)";
+
+/// Added before the credit utilization that is past the warning percentage
+pub const UTILIZATION_WARNING: &str = "⚠ ";
+
+/// Makes a call to the database to find out all the columns in the `balance_all` section
+/// so we can determine the number of TX Methods that has been added.
+/// return example: `["source_1", "source_2", "source_3"]`
+pub fn get_all_tx_methods(conn: &Connection) -> Vec<String> {
+    // returns all transaction methods added to the database. The columns are read from the table
+    // info instead of the statement as a cached statement only notices new columns once it runs
+    let mut statement = conn
+        .prepare_cached("SELECT name FROM pragma_table_info('balance_all') ORDER BY cid")
+        .expect("could not prepare statement");
+
+    let mut data: Vec<String> = statement
+        .query_map([], |row| row.get(0))
+        .expect("could not query the tx methods")
+        .flatten()
+        .collect();
+    // the first column is id_num
+    data.remove(0);
+    data
+}
+
+/// Returns the tx methods that are not archived
+pub fn get_active_tx_methods(conn: &Connection) -> Vec<String> {
+    let archived = get_archived_tx_methods(conn);
+    get_all_tx_methods(conn)
+        .into_iter()
+        .filter(|method| !archived.contains(method))
+        .collect()
+}
+
+/// Removes the columns of the archived tx methods from the rows of a balance section. Each row
+/// starts with the row name followed by a column for every tx method
+pub fn hide_archived_columns(rows: &mut [Vec<String>], conn: &Connection) {
+    let archived = get_archived_tx_methods(conn);
+
+    if archived.is_empty() {
+        return;
+    }
+
+    let hidden = get_all_tx_methods(conn)
+        .iter()
+        .enumerate()
+        .filter(|(_, method)| archived.contains(method))
+        .map(|(index, _)| index + 1)
+        .collect::<Vec<usize>>();
+
+    for row in rows {
+        *row = row
+            .drain(..)
+            .enumerate()
+            .filter(|(index, _)| !hidden.contains(index))
+            .map(|(_, value)| value)
+            .collect();
+    }
+}
+
+/// Returns the symbol of a currency code to show next to the balances. Codes without a known
+/// symbol are returned as they are
+#[must_use]
+pub fn get_currency_symbol(currency: &str) -> &str {
+    match currency {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" | "CNY" => "¥",
+        "INR" => "₹",
+        "BDT" => "৳",
+        "KRW" => "₩",
+        "RUB" => "₽",
+        "TRY" => "₺",
+        _ => currency,
+    }
+}
+
+/// Marks the tx methods that have their own currency with the currency symbol on the first row
+/// of a balance section
+pub fn show_method_currencies(rows: &mut [Vec<String>], conn: &Connection) {
+    let currencies = get_tx_method_currencies(conn);
+
+    if currencies.is_empty() {
+        return;
+    }
+
+    let Some(header) = rows.first_mut() else {
+        return;
+    };
+
+    for (index, method) in get_all_tx_methods(conn).iter().enumerate() {
+        let Some(currency) = currencies.get(method) else {
+            continue;
+        };
+
+        if let Some(label) = header.get_mut(index + 1) {
+            *label = format!("{label} ({})", get_currency_symbol(currency));
+        }
+    }
+}
+
+/// Adds a row with the used percentage of the credit limit of every credit tx method that has
+/// a limit to a balance section. Utilization past `warning_at` percent is marked as a warning.
+/// Must be used before the credit balances are flipped to the owed amount
+pub fn show_credit_utilization(rows: &mut Vec<Vec<String>>, warning_at: f64, conn: &Connection) {
+    let method_types = get_tx_method_types(conn);
+    let credit_limits = get_credit_limits(conn);
+
+    let all_methods = get_all_tx_methods(conn);
+
+    if !all_methods.iter().any(|method| {
+        method_types.get(method) == Some(&MethodType::Credit) && credit_limits.contains_key(method)
+    }) {
+        return;
+    }
+
+    let Some(balance) = rows.iter().find(|row| row[0] == "Balance") else {
+        return;
+    };
+
+    let mut utilization_row = vec!["Utilization".to_string()];
+
+    for (index, method) in all_methods.iter().enumerate() {
+        let limit = credit_limits.get(method);
+
+        let utilization = match (method_types.get(method), limit) {
+            (Some(MethodType::Credit), Some(limit)) => {
+                let owed = 0.0
+                    - balance
+                        .get(index + 1)
+                        .and_then(|value| value.parse::<f64>().ok())
+                        .unwrap_or(0.0);
+                let percentage = owed.max(0.0) / limit * 100.0;
+
+                if percentage > warning_at {
+                    format!("{UTILIZATION_WARNING}{percentage:.1}%")
+                } else {
+                    format!("{percentage:.1}%")
+                }
+            }
+            _ => String::new(),
+        };
+        utilization_row.push(utilization);
+    }
+
+    // the total column
+    utilization_row.push(String::new());
+    rows.push(utilization_row);
+}
+
+/// Shows the balance of the credit tx methods as the owed amount on the rows of a balance
+/// section. The credit methods are marked on the first row and their Balance row value is flipped
+pub fn show_credit_as_owed(rows: &mut [Vec<String>], conn: &Connection) {
+    let method_types = get_tx_method_types(conn);
+
+    for (index, method) in get_all_tx_methods(conn).iter().enumerate() {
+        if method_types.get(method) != Some(&MethodType::Credit) {
+            continue;
+        }
+
+        for row in rows.iter_mut() {
+            let Some(value) = row.get(index + 1).cloned() else {
+                continue;
+            };
+
+            if row[0].is_empty() {
+                row[index + 1] = format!("{value}{OWED_SUFFIX}");
+            } else if row[0] == "Balance" {
+                if let Ok(balance) = value.parse::<f64>() {
+                    row[index + 1] = format!("{:.2}", 0.0 - balance);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the total balance of each tx method group from a Balance row that contains every
+/// tx method. The groups are sorted by their name
+pub fn get_group_balances(balance: &[String], conn: &Connection) -> Vec<(String, f64)> {
+    TxMethodCache::new(conn).get_group_balances(balance)
+}
+
+/// Returns the total balance without the savings tx methods from a Balance row that contains
+/// every tx method. `None` if there are no savings methods
+pub fn get_spendable_balance(balance: &[String], conn: &Connection) -> Option<f64> {
+    TxMethodCache::new(conn).get_spendable_balance(balance)
+}
+
+/// Returns all unique tags from the db
+pub fn get_all_tags(conn: &Connection) -> Vec<String> {
+    let mut query = conn
+        .prepare("SELECT tags FROM tx_all")
+        .expect("could not prepare statement");
+
+    let mut tags_data: HashSet<String> = HashSet::new();
+
+    if let Ok(rows) = query.query_map([], |row| {
+        let row_data: String = row.get(0).unwrap();
+        let splitted = row_data.split(',');
+        let final_data = splitted
+            .into_iter()
+            .map(|s| s.trim().to_string())
+            .collect::<Vec<String>>();
+        Ok(final_data)
+    }) {
+        for inner_data in rows.flatten() {
+            for x in inner_data {
+                tags_data.insert(x);
+            }
+        }
+    }
+
+    let mut sorted_tags = tags_data.into_iter().collect::<Vec<String>>();
+    sorted_tags.sort();
+
+    sorted_tags
+}
+
+/// Returns the tags that match the final tag of the comma separated input. Tags starting with
+/// it come before the ones that only contain it and the already entered tags are left out
+pub fn get_matching_tags(user_input: &str, all_tags: &[String]) -> Vec<String> {
+    let mut entered_tags = user_input
+        .split(',')
+        .map(|tag| tag.trim().to_lowercase())
+        .collect::<Vec<String>>();
+    let fragment = entered_tags.pop().unwrap_or_default();
+
+    let (mut matching_tags, containing_tags): (Vec<String>, Vec<String>) = all_tags
+        .iter()
+        .filter(|tag| {
+            let tag = tag.to_lowercase();
+            tag.contains(&fragment) && !entered_tags.contains(&tag)
+        })
+        .cloned()
+        .partition(|tag| tag.to_lowercase().starts_with(&fragment));
+
+    matching_tags.extend(containing_tags);
+    matching_tags
+}
+
+/// Returns all unique details from the db
+pub fn get_all_details(conn: &Connection) -> Vec<String> {
+    let mut query = conn
+        .prepare("SELECT details FROM tx_all")
+        .expect("could not prepare statement");
+
+    let mut details_data: HashSet<String> = HashSet::new();
+
+    if let Ok(rows) = query.query_map([], |row| {
+        let row_data: String = row.get(0).unwrap();
+        let splitted = row_data.split(',');
+        let final_data = splitted
+            .into_iter()
+            .map(|s| s.trim().to_string())
+            .collect::<Vec<String>>();
+        Ok(final_data)
+    }) {
+        for inner_data in rows.flatten() {
+            for x in inner_data {
+                details_data.insert(x);
+            }
+        }
+    }
+
+    let mut sorted_details = details_data.into_iter().collect::<Vec<String>>();
+    sorted_details.sort();
+    sorted_details
+}
+
+/// Returns all unique details from the db starting from the most used one. Details that
+/// are used equally are ordered by the latest tx
+pub fn get_details_by_frequency(conn: &Connection) -> Vec<String> {
+    let mut query = conn
+        .prepare(
+            "SELECT details FROM tx_all WHERE details != '' GROUP BY details
+            ORDER BY COUNT(*) DESC, MAX(id_num) DESC",
+        )
+        .expect("could not prepare statement");
+
+    query
+        .query_map([], |row| row.get(0))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Gets all columns inside the `tx_all` table. Used to determine if the database needs to be migrated
+pub fn get_all_tx_columns(conn: &Connection) -> Vec<String> {
+    let column_names = conn
+        .prepare("SELECT * FROM tx_all")
+        .expect("could not prepare statement");
+
+    column_names
+        .column_names()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Returns all the column names of the given table
+pub fn get_table_columns(table: &str, conn: &Connection) -> Vec<String> {
+    conn.prepare(&format!("SELECT * FROM {table}"))
+        .map(|statement| {
+            statement
+                .column_names()
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the a vector with data required to create the Changes row for zero changes in the home page.
+pub fn get_empty_changes(conn: &Connection) -> Vec<String> {
+    // function for quick vec with 0 changes for adding in widget
+    let tx_methods = get_all_tx_methods(conn);
+    let mut changes = vec!["Changes".to_string()];
+    for _i in tx_methods {
+        changes.push(format!("{:.2}", 0.0));
+    }
+    changes
+}
+
+/// Returns the last `id_num` recorded by `tx_all` table
+pub fn get_last_tx_id(conn: &Connection) -> sqlResult<i32> {
+    let last_id: sqlResult<i32> = conn.query_row(
+        "SELECT id_num FROM tx_all ORDER BY id_num DESC LIMIT 1",
+        [],
+        |row| row.get(0),
+    );
+    last_id
+}
+
+/// Returns the last `id_num` recorded by `balance_all` table or the `id_num` of the absolute final balance
+pub fn get_last_balance_id(conn: &Connection) -> sqlResult<i32> {
+    let last_id: sqlResult<i32> = conn.query_row(
+        "SELECT id_num FROM balance_all ORDER BY id_num DESC LIMIT 1",
+        [],
+        |row| row.get(0),
+    );
+    last_id
+}
+
+/// Returns two dates based on the month and year index. used for the purpose of searching
+/// tx based on date
+pub fn get_sql_dates(month: usize, year: usize, date_type: &DateType) -> (String, String) {
+    match date_type {
+        DateType::Monthly => {
+            let datetime_1 = format!("{}-{:02}-01", YEARS[year], month + 1);
+            let last_date =
+                NaiveDate::from_ymd_opt(YEARS[year].parse().unwrap(), (month + 1) as u32, 1)
+                    .unwrap()
+                    .checked_add_months(Months::new(1))
+                    .unwrap()
+                    .pred_opt()
+                    .unwrap();
+            (datetime_1, last_date.to_string())
+        }
+        DateType::Yearly => {
+            let datetime_1 = format!("{}-01-01", YEARS[year]);
+            let datetime_2 = format!("{}-12-31", YEARS[year]);
+            (datetime_1, datetime_2)
+        }
+        DateType::Exact => (String::new(), String::new()),
+    }
+}
+
+/// Returns the Monday of every ISO week that has at least one day within the given month and year index.
+/// The first and the last week can start or end on the neighboring months
+pub fn get_month_weeks(month: usize, year: usize) -> Vec<NaiveDate> {
+    let month_start =
+        NaiveDate::from_ymd_opt(YEARS[year].parse().unwrap(), (month + 1) as u32, 1).unwrap();
+    let month_end = month_start
+        .checked_add_months(Months::new(1))
+        .unwrap()
+        .pred_opt()
+        .unwrap();
+
+    let mut week_start =
+        month_start - Days::new(u64::from(month_start.weekday().num_days_from_monday()));
+
+    let mut weeks = Vec::new();
+    while week_start <= month_end {
+        weeks.push(week_start);
+        week_start = week_start + Days::new(7);
+    }
+    weeks
+}
+
+/// Checks if the `balance_all` table is outdated
+pub fn check_old_balance_sql(conn: &Connection) -> bool {
+    let mut query = conn.prepare("PRAGMA table_info(balance_all)").unwrap();
+
+    let columns = query
+        .query_map([], |row| Ok((row.get(1).unwrap(), row.get(2).unwrap())))
+        .unwrap();
+
+    let mut result = false;
+
+    for column in columns {
+        let (name, data_type): (String, String) = column.unwrap();
+        if name != "id_num" && data_type == "TEXT" {
+            result = true;
+            break;
+        }
+    }
+    result
+}
+
+pub fn get_all_table_names(conn: &Connection) -> Vec<String> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type='table'")
+        .unwrap();
+    let table_names = stmt.query_map([], |row| row.get(0)).unwrap();
+
+    let mut result = Vec::new();
+    for table_name in table_names {
+        result.push(table_name.unwrap());
+    }
+
+    result
+}
+
+/// Returns the name of every index of the db
+pub fn get_all_index_names(conn: &Connection) -> Vec<String> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type='index'")
+        .unwrap();
+    let index_names = stmt.query_map([], |row| row.get(0)).unwrap();
+
+    let mut result = Vec::new();
+    for index_name in index_names {
+        result.push(index_name.unwrap());
+    }
+
+    result
+}
+
+/// Checks if the tags follow the tag policies of the config:
+///
+/// - At least one tag other than Unknown exists if a tag is required
+/// - All tags are in the allowed tags if any tag is allowed
+/// - The number of tags is not more than the maximum tags
+pub fn check_tag_policy(user_tag: &str, config: &Config) -> VerifyingOutput {
+    let tags = user_tag
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty() && *tag != "Unknown")
+        .collect::<Vec<&str>>();
+
+    if config.require_tag && tags.is_empty() {
+        return VerifyingOutput::NotAccepted(NAType::MissingTag);
+    }
+
+    if !config.allowed_tags.is_empty() {
+        if let Some(tag) = tags
+            .iter()
+            .find(|tag| !config.allowed_tags.iter().any(|allowed| allowed == *tag))
+        {
+            return VerifyingOutput::NotAccepted(NAType::TagNotAllowed((*tag).to_string()));
+        }
+    }
+
+    if config.max_tags != 0 && tags.len() > config.max_tags {
+        return VerifyingOutput::NotAccepted(NAType::TooManyTags(config.max_tags));
+    }
+
+    if tags.is_empty() {
+        VerifyingOutput::Nothing(AType::Tags)
+    } else {
+        VerifyingOutput::Accepted(AType::Tags)
+    }
+}
+
+/// Checks if the input is a restricted word or inside a given vector
+pub fn check_restricted(item: &str, restricted: Option<&Vec<String>>) -> bool {
+    if let Some(restricted_words) = restricted {
+        for restricted_item in restricted_words {
+            if restricted_item.to_lowercase() == item.to_lowercase() {
+                return true;
+            }
+        }
+    } else {
+        for &restricted_item in &RESTRICTED {
+            if restricted_item.to_lowercase() == item.to_lowercase() {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Uses Levenshtein algorithm to get the best match of a string in a vec of strings
+pub fn get_best_match(data: &str, matching_set: &[String]) -> String {
+    let mut best_match = &matching_set[0];
+    let mut best_score = -1.0;
+
+    for x in matching_set {
+        let new_score = normalized_levenshtein(&x.to_lowercase(), &data.to_lowercase());
+
+        if new_score > best_score {
+            best_match = x;
+            best_score = new_score;
+        }
+    }
+    best_match.to_string()
+}
+
+/// Calculates an amount expression with `+`, `-`, `*`, `/` and parentheses. `*` and `/`
+/// are done before `+` and `-`. Returns `None` if the expression is incomplete or invalid
+pub fn evaluate_amount(expression: &str) -> Option<f64> {
+    let chars = expression
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<Vec<char>>();
+    let mut position = 0;
+
+    let result = parse_sum(&chars, &mut position)?;
+
+    // something was left that could not be used in the calculation such as an extra `)`
+    if position != chars.len() || !result.is_finite() {
+        return None;
+    }
+    Some(result)
+}
+
+/// Parses values joined with `+` or `-` starting from the position
+fn parse_sum(chars: &[char], position: &mut usize) -> Option<f64> {
+    let mut total = parse_product(chars, position)?;
+
+    while let Some(symbol) = chars.get(*position).copied() {
+        match symbol {
+            '+' => {
+                *position += 1;
+                total += parse_product(chars, position)?;
+            }
+            '-' => {
+                *position += 1;
+                total -= parse_product(chars, position)?;
+            }
+            _ => break,
+        }
+    }
+    Some(total)
+}
+
+/// Parses values joined with `*` or `/` starting from the position
+fn parse_product(chars: &[char], position: &mut usize) -> Option<f64> {
+    let mut total = parse_value(chars, position)?;
+
+    while let Some(symbol) = chars.get(*position).copied() {
+        match symbol {
+            '*' => {
+                *position += 1;
+                total *= parse_value(chars, position)?;
+            }
+            '/' => {
+                *position += 1;
+                total /= parse_value(chars, position)?;
+            }
+            _ => break,
+        }
+    }
+    Some(total)
+}
+
+/// Parses a single number, a negative value or an expression inside parentheses
+fn parse_value(chars: &[char], position: &mut usize) -> Option<f64> {
+    match chars.get(*position)? {
+        '-' => {
+            *position += 1;
+            parse_value(chars, position).map(|value| -value)
+        }
+        '(' => {
+            *position += 1;
+            let value = parse_sum(chars, position)?;
+            if chars.get(*position) != Some(&')') {
+                return None;
+            }
+            *position += 1;
+            Some(value)
+        }
+        _ => {
+            let start = *position;
+            while chars
+                .get(*position)
+                .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+            {
+                *position += 1;
+            }
+            chars[start..*position]
+                .iter()
+                .collect::<String>()
+                .parse()
+                .ok()
+        }
+    }
+}
+
+/// Turns the values into a line of unicode blocks where the highest value gets the tallest block
+pub fn get_sparkline(values: &[f64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let highest_value = values.iter().copied().fold(0.0, f64::max);
+
+    values
+        .iter()
+        .map(|value| {
+            if highest_value <= 0.0 {
+                BLOCKS[0]
+            } else {
+                let index = (value / highest_value * (BLOCKS.len() - 1) as f64).round() as usize;
+                BLOCKS[index.min(BLOCKS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+/// Used for sorting summary table data by the given column and direction
+pub fn sort_table_data(
+    mut data: Vec<Vec<String>>,
+    sort_type: &SortingType,
+    sort_direction: &SortingDirection,
+) -> Vec<Vec<String>> {
+    // the index of the column that holds the value to sort by
+    let column = match sort_type {
+        SortingType::ByTags => 0,
+        SortingType::ByIncome => 1,
+        SortingType::ByExpense => 2,
+        SortingType::ByNet => 3,
+        SortingType::ByIncomePercentage => 4,
+        SortingType::ByExpensePercentage => 5,
+        SortingType::ByIncomeShare => 6,
+    };
+
+    data.sort_by(|a, b| {
+        let ordering = if column == 0 {
+            a.cmp(b)
+        } else {
+            let val_a: f64 = a[column].parse().unwrap();
+            let val_b: f64 = b[column].parse().unwrap();
+            val_a.partial_cmp(&val_b).unwrap()
+        };
+
+        match sort_direction {
+            SortingDirection::Ascending => ordering,
+            SortingDirection::Descending => ordering.reverse(),
+        }
+    });
+
+    data
+}
+
+/// Adds a char to the given index on the given string
+pub fn add_char_to(to_add: Option<char>, current_index: &mut usize, current_data: &mut String) {
+    if *current_index > current_data.len() {
+        *current_index = current_data.len();
+    } else {
+        match to_add {
+            Some(ch) => {
+                current_data.insert(*current_index, ch);
+                *current_index += 1;
+            }
+            None => {
+                if !current_data.is_empty() && *current_index != 0 {
+                    current_data.remove(*current_index - 1);
+                    *current_index -= 1;
+                }
+            }
+        }
+    }
+}
+
+/// Checks if the string contains any symbol indicating comparison
+pub fn check_comparison(input: &str) -> ComparisonType {
+    // Need to handle 2 letter ones first otherwise in case of >=
+    // it will match with >
+    if input.starts_with("<=") {
+        ComparisonType::EqualOrSmaller
+    } else if input.starts_with(">=") {
+        ComparisonType::EqualOrBigger
+    } else if input.starts_with('<') {
+        ComparisonType::SmallerThan
+    } else if input.starts_with('>') {
+        ComparisonType::BiggerThan
+    } else {
+        ComparisonType::Equal
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LocationInfo {
+    location: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BackupPaths {
+    locations: Vec<String>,
+}
+
+/// Checks if location.json exists and returns a path if it exists
+pub fn is_location_changed(working_dir: &PathBuf) -> Option<PathBuf> {
+    let mut json_path = working_dir.to_owned();
+    json_path.pop();
+    json_path.push("location.json");
+
+    if json_path.exists() {
+        let mut file = File::open(json_path).unwrap();
+        let mut file_content = String::new();
+        file.read_to_string(&mut file_content).unwrap();
+        let location_info: LocationInfo = serde_json::from_str(&file_content).unwrap();
+        Some(PathBuf::from(location_info.location))
+    } else {
+        None
+    }
+}
+
+/// Creates a location.json file to store the new app data location
+pub fn create_change_location_file(working_dir: &PathBuf, new_path: &Path) {
+    let mut target_dir = working_dir.to_owned();
+    target_dir.pop();
+
+    let location = LocationInfo {
+        location: new_path.to_str().unwrap().to_string(),
+    };
+
+    target_dir.push("location.json");
+
+    let mut file = File::create(target_dir).unwrap();
+
+    serde_json::to_writer(&mut file, &location).unwrap();
+}
+
+/// Create a `backup_paths.json` file to store the location of where backup db will be located
+pub fn create_backup_location_file(original_db_path: &PathBuf, backup_paths: Vec<PathBuf>) {
+    let mut target_dir = original_db_path.to_owned();
+    target_dir.pop();
+
+    let backup = BackupPaths {
+        locations: backup_paths
+            .into_iter()
+            .map(|path| path.to_str().unwrap().to_owned())
+            .collect(),
+    };
+
+    target_dir.push("backup_paths.json");
+    let mut file = File::create(target_dir).unwrap();
+    serde_json::to_writer(&mut file, &backup).unwrap();
+}
+
+/// Copies the latest DB to the backup location specified in `backend_paths.json`. Returns the
+/// files the DB was copied to
+pub fn save_backup_db(db_path: &PathBuf, original_db_path: &PathBuf) -> Vec<String> {
+    let mut json_path = original_db_path.to_owned();
+    json_path.pop();
+
+    json_path.push("backup_paths.json");
+
+    if !json_path.exists() {
+        return Vec::new();
+    }
+
+    let mut file = File::open(json_path).unwrap();
+    let mut file_content = String::new();
+    file.read_to_string(&mut file_content).unwrap();
+    let location_info: BackupPaths = serde_json::from_str(&file_content).unwrap();
+
+    let mut saved = Vec::new();
+
+    for path in location_info.locations {
+        let mut target_path = PathBuf::from(path);
+
+        if !target_path.exists() {
+            warn!("Failed to find path {}", target_path.to_string_lossy());
+            continue;
+        }
+        // each profile is backed up with its own file name
+        target_path.push(db_path.file_name().unwrap_or_default());
+        if let Err(e) = fs::copy(db_path, &target_path) {
+            warn!(
+                "Failed to copy DB to backup path {}. Error: {e:?}",
+                target_path.to_string_lossy()
+            );
+            continue;
+        }
+        saved.push(target_path.to_string_lossy().to_string());
+    }
+
+    saved
+}
+
+/// Deletes `backup_paths.json` which contains all locations where backup DB is located.
+pub fn delete_backup_db(original_db_path: &PathBuf) -> ioResult<()> {
+    let mut json_path = original_db_path.to_owned();
+    json_path.pop();
+
+    json_path.push("backup_paths.json");
+
+    if !json_path.exists() {
+        return Ok(());
+    }
+
+    fs::remove_file(json_path)
+}
+
+/// Deletes `locations.json` file which stores alternative location information of the DB.
+pub fn delete_location_change(original_db_path: &PathBuf) -> ioResult<()> {
+    let mut json_path = original_db_path.to_owned();
+    json_path.pop();
+
+    json_path.push("location.json");
+
+    if !json_path.exists() {
+        return Ok(());
+    }
+
+    fs::remove_file(json_path)
+}
+
+/// Writes the content to the file so only the user can read it. An existing file is replaced
+/// and its permissions are tightened before anything is written
+pub fn write_private_file(path: &Path, content: &str) -> ioResult<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    // the mode is only used when the file gets created
+    restrict_permissions(path)?;
+    file.write_all(content.as_bytes())
+}
+
+/// Lets only the user read and write the file
+#[cfg(unix)]
+pub fn restrict_permissions(path: &Path) -> ioResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+pub fn restrict_permissions(_path: &Path) -> ioResult<()> {
+    Ok(())
+}
+
+/// Returns a transaction detail from a given ID number
+pub fn get_tx_id_num(id_num: i32, conn: &Connection) -> Vec<String> {
+    let query = format!("SELECT * FROM tx_all WHERE id_num = {id_num}");
+
+    let tx_data = conn.query_row(&query, [], |row| {
+        let id_num: i32 = row.get(5).unwrap();
+        Ok(vec![
+            row.get(0).unwrap(),
+            row.get(1).unwrap(),
+            row.get(2).unwrap(),
+            row.get(3).unwrap(),
+            row.get(4).unwrap(),
+            row.get(6).unwrap(),
+            id_num.to_string(),
+        ])
+    });
+
+    tx_data.unwrap()
+}
+
+/// Returns the details of the last added transaction
+pub fn get_last_tx(conn: &Connection) -> Vec<String> {
+    let query = "SELECT * FROM tx_all ORDER BY id_num DESC LIMIT 1";
+
+    let tx_data = conn.query_row(query, [], |row| {
+        let id_num: i32 = row.get(5).unwrap();
+        Ok(vec![
+            row.get(0).unwrap(),
+            row.get(1).unwrap(),
+            row.get(2).unwrap(),
+            row.get(3).unwrap(),
+            row.get(4).unwrap(),
+            row.get(6).unwrap(),
+            id_num.to_string(),
+        ])
+    });
+
+    tx_data.unwrap()
+}
+
+/// Add a new activity row to the DB. Returns the activity num of the new row
+pub fn add_new_activity(activity_type: ActivityType, conn: &Connection) -> sqlResult<i32> {
+    let activity_type_str = activity_type.to_str();
+    let activity_details = activity_type.to_details();
+    let current_date = Local::now().date_naive().to_string();
+
+    conn.execute(
+        "INSERT INTO activities (date, activity_type, description) VALUES (?, ?, ?)",
+        [current_date, activity_type_str, activity_details],
+    )?;
+
+    // Fetch the latest row's activity num so this can be used to reference activity txs
+    let query = "SELECT activity_num FROM activities ORDER BY activity_num DESC LIMIT 1";
+
+    conn.query_row(query, [], |row| row.get(0))
+}
+
+/// Add a new tx that is related to a given activity number
+pub fn add_new_activity_tx<T: AsRef<str> + Display>(
+    tx_data: &[T],
+    activity_num: i32,
+    conn: &Connection,
+) -> sqlResult<()> {
+    let query = "INSERT INTO activity_txs
+    (date, details, tx_method, amount, tx_type, tags, id_num, activity_num)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)";
+
+    conn.execute(
+        query,
+        (
+            tx_data[0].as_ref(),
+            tx_data[1].as_ref(),
+            tx_data[2].as_ref(),
+            tx_data[3].as_ref(),
+            tx_data[4].as_ref(),
+            tx_data[5].as_ref(),
+            tx_data[6].as_ref(),
+            activity_num,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Turns the inputted path of a file to attach into an absolute path. A leading `~` is
+/// replaced with the home directory. Fails if there is no file on the path
+pub fn get_attachment_path(input: &str) -> Result<String, AttachmentError> {
+    let input = input.trim();
+
+    let path = match input.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map_or_else(|| PathBuf::from(input), |home| home.join(rest)),
+        None => PathBuf::from(input),
+    };
+
+    match fs::canonicalize(path) {
+        Ok(path) if path.is_file() => Ok(path.to_string_lossy().to_string()),
+        _ => Err(AttachmentError::NotFound(input.to_string())),
+    }
+}
+
+/// Returns how much one unit of the currency is in the base currency. The rate in the config is
+/// used first, then the rate fetched today. If fetching is enabled a new rate is fetched and cached,
+/// otherwise the last fetched rate is used
+pub fn get_currency_rate(
+    currency: &str,
+    config: &Config,
+    conn: &Connection,
+) -> Result<f64, CurrencyError> {
+    if let Some(rate) = config.get_currency_rate(currency) {
+        return Ok(rate);
+    }
+
+    let cached_rate = get_cached_rate(currency, conn);
+    let today = Local::now().format("%Y-%m-%d").to_string();
+
+    if let Some((rate, date)) = &cached_rate {
+        if *date == today {
+            return Ok(*rate);
+        }
+    }
+
+    if config.fetch_currency_rates {
+        if let Ok(Some(rate)) = fetch_currency_rate(currency, &config.base_currency) {
+            // failing to cache only means it gets fetched again next time
+            cache_rate(currency, rate, conn).ok();
+            return Ok(rate);
+        }
+    }
+
+    cached_rate
+        .map(|(rate, _)| rate)
+        .ok_or_else(|| CurrencyError::NoRate(currency.to_string()))
+}
+
+#[derive(Deserialize)]
+struct CurrencyRates {
+    rates: HashMap<String, f64>,
+}
+
+/// Fetches the latest rate of the currency in the base currency. `None` if the rate is not known
+#[cfg(not(tarpaulin_include))]
+fn fetch_currency_rate(currency: &str, base_currency: &str) -> Result<Option<f64>, reqwest::Error> {
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("Rex")
+        .timeout(Duration::from_secs(2))
+        .build()?;
+
+    let response: CurrencyRates = client
+        .get(format!(
+            "https://api.frankfurter.app/latest?from={currency}&to={base_currency}"
+        ))
+        .send()?
+        .error_for_status()?
+        .json()?;
+
+    Ok(response.rates.get(base_currency).copied())
+}
+
+/// Turns a shorthand date into a `YYYY-MM-DD` date based on the given day. Accepts `t` or `today`,
+/// `y` or `yesterday`, days ago like `-3` and a day of the current month like `17`.
+/// `None` if the input is not a shorthand date
+pub fn expand_short_date(input: &str, today: NaiveDate) -> Option<String> {
+    let input = input.trim().to_lowercase();
+
+    let date = match input.as_str() {
+        "t" | "today" => today,
+        "y" | "yesterday" => today.checked_sub_days(Days::new(1))?,
+        _ => {
+            if let Some(days) = input.strip_prefix('-') {
+                let days = days.parse::<u64>().ok()?;
+                today.checked_sub_days(Days::new(days))?
+            } else if input.len() <= 2 && input.chars().all(|c| c.is_ascii_digit()) {
+                // the day is verified later like any other date so 31 on a 30 day month is caught
+                return Some(format!(
+                    "{}-{:02}-{:0>2}",
+                    today.year(),
+                    today.month(),
+                    input
+                ));
+            } else {
+                return None;
+            }
+        }
+    };
+
+    Some(date.format("%Y-%m-%d").to_string())
+}
+
+/// Turns the input of the go to date popup into a date. Accepts a day of the given month and year
+/// index like `15`, `today`, `YYYY-MM-DD` or `DD-MM-YYYY`
+pub fn parse_jump_date(input: &str, month: usize, year: usize) -> Result<NaiveDate, JumpDateError> {
+    let input = input.trim().to_lowercase();
+
+    let date = if input == "today" {
+        Some(Local::now().date_naive())
+    } else if let Ok(day) = input.parse::<u32>() {
+        NaiveDate::from_ymd_opt(year as i32 + 2022, month as u32 + 1, day)
+    } else {
+        NaiveDate::parse_from_str(&input, "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(&input, "%d-%m-%Y"))
+            .ok()
+    };
+
+    let Some(date) = date else {
+        return Err(JumpDateError::InvalidDate(input));
+    };
+
+    let year_index = date.year() - 2022;
+    if year_index < 0 || year_index >= YEARS.len() as i32 {
+        return Err(JumpDateError::OutOfRange(date));
+    }
+
+    Ok(date)
+}
+
+/// Turns the input of the date range popup into a start and an end date. The dates are separated
+/// with `to` or a space and each side accepts the same values as `parse_jump_date`
+pub fn parse_date_range(
+    input: &str,
+    month: usize,
+    year: usize,
+) -> Result<(NaiveDate, NaiveDate), DateRangeError> {
+    let input = input.trim().to_lowercase();
+
+    let dates = if input.contains(" to ") {
+        input.split(" to ").collect::<Vec<&str>>()
+    } else {
+        input.split_whitespace().collect::<Vec<&str>>()
+    };
+
+    let [from, to] = dates[..] else {
+        return Err(DateRangeError::InvalidRange(input));
+    };
+
+    let from = parse_jump_date(from, month, year)?;
+    let to = parse_jump_date(to, month, year)?;
+
+    if from > to {
+        return Err(DateRangeError::Reversed(from, to));
+    }
+
+    Ok((from, to))
+}
+
+/// Will return the original value if either empty or does not have 2 dashes in the string
+pub fn reverse_date_format(date: String) -> String {
+    if date.is_empty() {
+        return date;
+    }
+    let collected_date = date.split('-').collect::<Vec<&str>>();
+    if collected_date.len() != 3 {
+        return date;
+    }
+    let new_date = format!(
+        "{}-{}-{}",
+        collected_date[2], collected_date[1], collected_date[0]
+    );
+    new_date
+}
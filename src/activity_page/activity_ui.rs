@@ -5,10 +5,8 @@ use ratatui::Frame;
 use thousands::Separable;
 
 use crate::activity_page::ActivityData;
-use crate::page_handler::{
-    ActivityTab, IndexedData, TableData, BACKGROUND, HEADER, SELECTED, TEXT,
-};
-use crate::utility::{create_tab, main_block, styled_block};
+use crate::page_handler::{ActivityTab, IndexedData, TableData, Theme};
+use crate::utility::{create_tab, main_block, reverse_date_format, styled_block};
 
 pub fn activity_ui(
     f: &mut Frame,
@@ -17,6 +15,7 @@ pub fn activity_ui(
     current_tab: &ActivityTab,
     activity_data: &ActivityData,
     table_data: &mut TableData,
+    theme: &Theme,
 ) {
     let activity_txs_data = activity_data.get_activity_txs(table_data.state.selected());
     let mut activity_txs_table = TableData::new(activity_txs_data);
@@ -88,7 +87,7 @@ pub fn activity_ui(
         ])
         .split(size);
 
-    f.render_widget(main_block(), size);
+    f.render_widget(main_block(theme), size);
 
     let mut table_name = "Activities".to_string();
 
@@ -98,29 +97,35 @@ pub fn activity_ui(
 
     let activity_header_cells = ["Created At", "Activity Type", "Description"]
         .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+        .map(|h| Cell::from(*h).style(Style::default().fg(theme.background)));
 
     let activity_tx_header_cells = activity_tx_header_vec
         .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+        .map(|h| Cell::from(*h).style(Style::default().fg(theme.background)));
 
     let activity_header = Row::new(activity_header_cells)
-        .style(Style::default().bg(HEADER))
+        .style(Style::default().bg(theme.header))
         .height(1)
         .bottom_margin(0);
 
     let activity_tx_header = Row::new(activity_tx_header_cells)
-        .style(Style::default().bg(HEADER))
+        .style(Style::default().bg(theme.header))
         .height(1)
         .bottom_margin(0);
 
     let activity_rows = table_data.items.iter().map(|item| {
         let height = 1;
-        let cells = item.iter().map(|c| Cell::from(c.separate_with_commas()));
+        let cells = item.iter().enumerate().map(|(index, c)| {
+            if index == 0 {
+                Cell::from(reverse_date_format(c.to_string()))
+            } else {
+                Cell::from(c.separate_with_commas())
+            }
+        });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().bg(BACKGROUND).fg(TEXT))
+            .style(Style::default().bg(theme.background).fg(theme.text))
     });
 
     let activity_tx_rows = activity_txs_table.items.iter().map(|item| {
@@ -133,13 +138,13 @@ pub fn activity_ui(
                 Cell::from(c.separate_with_commas())
             } else {
                 first_index_passed = true;
-                Cell::from(c.to_string())
+                Cell::from(reverse_date_format(c.to_string()))
             }
         });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().bg(BACKGROUND).fg(TEXT))
+            .style(Style::default().bg(theme.background).fg(theme.text))
     });
 
     let mut activity_table_area = Table::new(
@@ -151,29 +156,35 @@ pub fn activity_ui(
         ],
     )
     .header(activity_header)
-    .block(styled_block(&table_name));
+    .block(styled_block(&table_name, theme));
 
     let activity_txs_table_area = Table::new(activity_tx_rows, activity_tx_header_widths)
         .header(activity_tx_header)
-        .block(styled_block("TX Details"));
+        .block(styled_block("TX Details", theme));
 
-    let mut month_tab = create_tab(months, "Months");
-    let mut year_tab = create_tab(years, "Years");
+    let mut month_tab = create_tab(months, "Months", theme);
+    let mut year_tab = create_tab(years, "Years", theme);
 
     match current_tab {
         ActivityTab::Months => {
-            month_tab = month_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            month_tab = month_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
         ActivityTab::Years => {
-            year_tab = year_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            year_tab = year_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
         ActivityTab::List => {
             if table_data.state.selected().is_some() {
                 activity_table_area = activity_table_area
                     .highlight_symbol(">> ")
-                    .highlight_style(Style::default().bg(SELECTED));
+                    .highlight_style(Style::default().bg(theme.selected));
             }
         }
     }
@@ -1,5 +1,6 @@
-mod activity_data;
 mod activity_ui;
+mod page;
 
-pub use activity_data::{ActivityData, ActivityDetails, ActivityTx};
 pub use activity_ui::activity_ui;
+pub use page::ActivityPage;
+pub use rex_core::activity::*;
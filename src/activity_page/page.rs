@@ -0,0 +1,32 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::activity_page::activity_ui;
+use crate::key_checker::{activity_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+
+pub struct ActivityPage;
+
+impl Page for ActivityPage {
+    fn name(&self) -> &str {
+        "Activity"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, _context: &PageContext) {
+        let activity = &mut state.activity;
+
+        activity_ui(
+            f,
+            &activity.months,
+            &activity.years,
+            &activity.tab,
+            &activity.data,
+            &mut activity.table,
+            &state.theme,
+        );
+    }
+
+    fn get_action(&self, key: KeyEvent, _state: &AppState) -> Option<Action> {
+        activity_keys(key)
+    }
+}
@@ -1,25 +1,29 @@
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Cell, Paragraph, Row, Table};
+use ratatui::widgets::{Cell, List, ListItem, ListState, Paragraph, Row, Table};
 use ratatui::Frame;
-use rusqlite::Connection;
+use std::collections::HashMap;
 use thousands::Separable;
 
-use crate::home_page::BALANCE_BOLD;
+use crate::chart_page::Rgb;
+use crate::home_page::{get_label_method, BALANCE_BOLD, UTILIZATION_WARNING};
 use crate::outputs::TxType;
-use crate::page_handler::{HomeRow, TxTab, BACKGROUND, BLUE, BOX, GRAY, RED, TEXT};
+use crate::page_handler::{HomeRow, Theme, TxTab};
 use crate::tx_handler::TxData;
-use crate::utility::{get_all_tx_methods, main_block, styled_block};
+use crate::utility::{get_color, get_matching_tags, main_block, styled_block};
 
 /// The function draws the Add Transaction page of the interface.
 #[cfg(not(tarpaulin_include))]
+#[allow(clippy::too_many_arguments)]
 pub fn add_tx_ui(
     f: &mut Frame,
     to_reset: bool,
     balance: &mut [Vec<String>],
     add_tx_data: &TxData,
     add_tx_tab: &TxTab,
+    all_tags: &[String],
+    add_tx_batch: Option<usize>,
     width_data: &mut [Constraint],
     balance_load: &mut [f64],
     ongoing_balance: &mut Vec<String>,
@@ -28,9 +32,10 @@ pub fn add_tx_ui(
     ongoing_changes: &mut Vec<String>,
     last_changes: &mut Vec<String>,
     load_percentage: &mut f64,
-    conn: &Connection,
+    method_colors: &HashMap<String, Rgb>,
+    all_methods: &[String],
+    theme: &Theme,
 ) {
-    let all_methods = get_all_tx_methods(conn);
     // get the data to insert into the Status widget of this page
 
     let status_data = add_tx_data.get_tx_status();
@@ -54,8 +59,8 @@ pub fn add_tx_ui(
         .direction(Direction::Vertical)
         .margin(2)
         .constraints([
-            // Balance change section
-            Constraint::Length(5),
+            // Balance change section. The credit utilization row is only sometimes there
+            Constraint::Length(balance.len().max(3) as u16 + 2),
             // input chunk
             Constraint::Length(3),
             // details input chunk
@@ -94,7 +99,7 @@ pub fn add_tx_ui(
     };
 
     // creates border around the entire terminal
-    f.render_widget(main_block(), size);
+    f.render_widget(main_block(theme), size);
 
     // Entire balance section is copy paste from the Home UI
     if (*load_percentage + 0.004) <= 1.0 {
@@ -224,10 +229,18 @@ pub fn add_tx_ui(
             };
 
             if c.contains('↑') {
-                Cell::from(c).style(Style::default().fg(BLUE))
-            } else if c.contains('↓') {
-                Cell::from(c).style(Style::default().fg(RED))
-            } else if all_methods.contains(&c) || BALANCE_BOLD.contains(&c.as_str()) {
+                Cell::from(c).style(Style::default().fg(theme.income))
+            } else if c.contains('↓') || c.starts_with(UTILIZATION_WARNING) {
+                Cell::from(c).style(Style::default().fg(theme.expense))
+            } else if let Some(color) = method_colors.get(get_label_method(&c)) {
+                Cell::from(c).style(
+                    Style::default()
+                        .fg(get_color(*color))
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if all_methods.contains(&get_label_method(&c).to_string())
+                || BALANCE_BOLD.contains(&c.as_str())
+            {
                 Cell::from(c).style(Style::default().add_modifier(Modifier::BOLD))
             } else {
                 Cell::from(c)
@@ -236,12 +249,12 @@ pub fn add_tx_ui(
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().fg(TEXT))
+            .style(Style::default().fg(theme.text))
     });
 
     let balance_area = Table::new(bal_data, width_data.to_owned())
-        .block(styled_block("Balance Change"))
-        .style(Style::default().fg(BOX));
+        .block(styled_block("Balance Change", theme))
+        .style(Style::default().fg(theme.border));
 
     let mut status_text = vec![];
 
@@ -253,17 +266,21 @@ pub fn add_tx_ui(
             status_text.push(Line::from(vec![
                 Span::styled(
                     initial,
-                    Style::default().fg(RED).add_modifier(Modifier::BOLD),
+                    Style::default()
+                        .fg(theme.expense)
+                        .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(format!(":{rest}"), Style::default().fg(RED)),
+                Span::styled(format!(":{rest}"), Style::default().fg(theme.expense)),
             ]));
         } else {
             status_text.push(Line::from(vec![
                 Span::styled(
                     initial,
-                    Style::default().fg(BLUE).add_modifier(Modifier::BOLD),
+                    Style::default()
+                        .fg(theme.income)
+                        .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(format!(":{rest}"), Style::default().fg(BLUE)),
+                Span::styled(format!(":{rest}"), Style::default().fg(theme.income)),
             ]));
         }
     }
@@ -287,68 +304,73 @@ pub fn add_tx_ui(
         TxTab::Details => {
             details_text = Line::from(vec![
                 Span::from(format!("{} ", input_data[1])),
-                Span::styled(input_data[7], Style::default().fg(GRAY)),
+                Span::styled(input_data[7], Style::default().fg(theme.autofill)),
             ]);
         }
         TxTab::FromMethod => {
             from_method_text = Line::from(vec![
                 Span::from(format!("{} ", input_data[2])),
-                Span::styled(input_data[7], Style::default().fg(GRAY)),
+                Span::styled(input_data[7], Style::default().fg(theme.autofill)),
             ]);
         }
         TxTab::ToMethod => {
             to_method_text = Line::from(vec![
                 Span::from(format!("{} ", input_data[3])),
-                Span::styled(input_data[7], Style::default().fg(GRAY)),
+                Span::styled(input_data[7], Style::default().fg(theme.autofill)),
             ]);
         }
         TxTab::Tags => {
             tags_text = Line::from(vec![
                 Span::from(format!("{} ", input_data[6])),
-                Span::styled(input_data[7], Style::default().fg(GRAY)),
+                Span::styled(input_data[7], Style::default().fg(theme.autofill)),
             ]);
         }
         _ => {}
     }
 
+    let status_title = match add_tx_batch {
+        Some(total) => format!("Status - Batch Entry: {total} added"),
+        None => "Status".to_string(),
+    };
+
     let status_sec = Paragraph::new(status_text)
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
-        .block(styled_block("Status"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(styled_block(&status_title, theme))
         .alignment(Alignment::Left);
 
     let date_sec = Paragraph::new(date_text)
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
-        .block(styled_block("Date"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(styled_block("Date", theme))
         .alignment(Alignment::Left);
 
     let from_method_sec = Paragraph::new(from_method_text)
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
-        .block(styled_block(from_method_name))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(styled_block(from_method_name, theme))
         .alignment(Alignment::Left);
 
     let to_method_sec = Paragraph::new(to_method_text)
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
-        .block(styled_block("To Method"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(styled_block("To Method", theme))
         .alignment(Alignment::Left);
 
     let amount_sec = Paragraph::new(amount_text)
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
-        .block(styled_block("Amount"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(styled_block("Amount", theme))
         .alignment(Alignment::Left);
 
     let tx_type_sec = Paragraph::new(tx_type_text)
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
-        .block(styled_block("TX Type"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(styled_block("TX Type", theme))
         .alignment(Alignment::Left);
 
     let details_sec = Paragraph::new(details_text)
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
-        .block(styled_block("Details"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(styled_block("Details", theme))
         .alignment(Alignment::Left);
 
     let tags_sec = Paragraph::new(tags_text)
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
-        .block(styled_block("Tags"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(styled_block("Tags", theme))
         .alignment(Alignment::Left);
 
     // We will be adding a cursor based on which tab is selected + the selected index.
@@ -401,9 +423,66 @@ pub fn add_tx_ui(
         },
     }
 
+    // while a tx method field is selected, the methods are listed beside the status
+    // so they can be picked with the arrow keys instead of typing them. The tags field lists
+    // the tags matching the tag being typed with the one Tab completes to highlighted.
+    // A split tx lists its parts and a transfer with a fee shows the fee otherwise
+    let split_parts = add_tx_data
+        .get_split_txs()
+        .unwrap_or_else(|_| add_tx_data.get_splits().clone())
+        .iter()
+        .map(|part| format!("{} | {}: {}", part.tx_method, part.tags, part.amount))
+        .collect::<Vec<String>>();
+
+    let side_list = match add_tx_tab {
+        TxTab::FromMethod => Some(("TX Methods (Up/Down)", all_methods.to_vec(), input_data[2])),
+        TxTab::ToMethod => Some(("TX Methods (Up/Down)", all_methods.to_vec(), input_data[3])),
+        TxTab::Tags => Some((
+            "Matching Tags (Tab)",
+            get_matching_tags(input_data[6], all_tags),
+            input_data[7],
+        )),
+        _ if !add_tx_data.get_splits().is_empty() => Some(("Split Parts (X)", split_parts, "")),
+        _ if !add_tx_data.get_fee().is_empty() => Some((
+            "Transfer Fee (E)",
+            vec![format!(
+                "{} | fees: {}",
+                input_data[2],
+                add_tx_data.get_fee()
+            )],
+            "",
+        )),
+        _ => None,
+    };
+
     f.render_widget(balance_area, chunks[0]);
     f.render_widget(details_sec, chunks[2]);
-    f.render_widget(status_sec, chunks[3]);
+
+    if let Some((list_title, list_values, selected_value)) = side_list {
+        let status_chunk = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(75), Constraint::Percentage(25)])
+            .split(chunks[3]);
+
+        let list_items = list_values
+            .iter()
+            .map(|value| ListItem::new(value.as_str()))
+            .collect::<Vec<ListItem>>();
+
+        let side_list = List::new(list_items)
+            .block(styled_block(list_title, theme))
+            .style(Style::default().bg(theme.background).fg(theme.text))
+            .highlight_style(Style::default().bg(theme.selected))
+            .highlight_symbol(">> ");
+
+        let mut list_state = ListState::default()
+            .with_selected(list_values.iter().position(|value| value == selected_value));
+
+        f.render_widget(status_sec, status_chunk[0]);
+        f.render_stateful_widget(side_list, status_chunk[1], &mut list_state);
+    } else {
+        f.render_widget(status_sec, chunks[3]);
+    }
     f.render_widget(date_sec, input_chunk[0]);
     f.render_widget(tx_type_sec, input_chunk[1]);
     f.render_widget(from_method_sec, input_chunk[2]);
@@ -1,3 +1,5 @@
 mod add_tx_ui;
+mod page;
 
 pub use add_tx_ui::add_tx_ui;
+pub use page::AddTxPage;
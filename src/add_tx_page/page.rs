@@ -0,0 +1,51 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::add_tx_page::add_tx_ui;
+use crate::key_checker::{add_tx_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+
+pub struct AddTxPage;
+
+impl Page for AddTxPage {
+    fn name(&self) -> &str {
+        "Add Transaction"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, context: &PageContext) {
+        let AppState {
+            add_tx,
+            balance,
+            all_tags,
+            methods,
+            method_colors,
+            theme,
+            ..
+        } = state;
+
+        add_tx_ui(
+            f,
+            context.to_reset,
+            &mut balance.data,
+            &add_tx.data,
+            &add_tx.tab,
+            all_tags,
+            add_tx.batch,
+            &mut balance.widths,
+            &mut balance.balance_load,
+            &mut balance.ongoing_balance,
+            &mut balance.last_balance,
+            &mut balance.changes_load,
+            &mut balance.ongoing_changes,
+            &mut balance.last_changes,
+            &mut balance.load_percentage,
+            method_colors,
+            &methods.active,
+            theme,
+        );
+    }
+
+    fn get_action(&self, key: KeyEvent, state: &AppState) -> Option<Action> {
+        add_tx_keys(key, &state.add_tx.tab)
+    }
+}
@@ -0,0 +1,281 @@
+use chrono::Duration;
+use ratatui::style::Color;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Result as ioResult;
+use std::path::Path;
+
+use crate::chart_page::{from_log_scale, get_line_extremes, to_log_scale, ChartLines};
+use crate::page_handler::Theme;
+use crate::utility::get_color;
+
+const WIDTH: f64 = 1600.0;
+const HEIGHT: f64 = 900.0;
+const MARGIN_LEFT: f64 = 120.0;
+const MARGIN_RIGHT: f64 = 240.0;
+const MARGIN_TOP: f64 = 70.0;
+const MARGIN_BOTTOM: f64 = 70.0;
+
+/// The 16 ANSI colors as the xterm default palette draws them
+const ANSI_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Returns the rgb value of a color of the 256 color palette
+fn get_indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_COLORS[index as usize],
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { value * 40 + 55 };
+            let index = index - 16;
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        _ => {
+            let level = (index - 232) * 10 + 8;
+            (level, level, level)
+        }
+    }
+}
+
+/// Turns a color into a hex color code that can be used in svg. Named colors use the colors of
+/// the xterm default palette and the terminal default color is taken as white
+fn to_hex(color: Color) -> String {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) => get_indexed_rgb(index),
+        Color::Black => ANSI_COLORS[0],
+        Color::Red => ANSI_COLORS[1],
+        Color::Green => ANSI_COLORS[2],
+        Color::Yellow => ANSI_COLORS[3],
+        Color::Blue => ANSI_COLORS[4],
+        Color::Magenta => ANSI_COLORS[5],
+        Color::Cyan => ANSI_COLORS[6],
+        Color::Gray => ANSI_COLORS[7],
+        Color::DarkGray => ANSI_COLORS[8],
+        Color::LightRed => ANSI_COLORS[9],
+        Color::LightGreen => ANSI_COLORS[10],
+        Color::LightYellow => ANSI_COLORS[11],
+        Color::LightBlue => ANSI_COLORS[12],
+        Color::LightMagenta => ANSI_COLORS[13],
+        Color::LightCyan => ANSI_COLORS[14],
+        Color::White | Color::Reset => ANSI_COLORS[15],
+    };
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Escapes the characters that have special meaning in svg text
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Creates an svg image of every activated line of the chart using the app colors.
+/// Unlike the terminal chart, the full period is rendered without any animation
+pub fn get_chart_svg(
+    chart_lines: &ChartLines,
+    log_scale: bool,
+    title: &str,
+    theme: &Theme,
+) -> String {
+    let scale = |value: f64| {
+        if log_scale {
+            to_log_scale(value)
+        } else {
+            value
+        }
+    };
+
+    let activated_lines = chart_lines
+        .lines
+        .iter()
+        .filter(|line| line.activated)
+        .collect::<Vec<_>>();
+
+    let mut lowest_value = 0.0;
+    let mut highest_value = 0.0;
+
+    for line in &activated_lines {
+        for (_, value) in &line.points {
+            let value = scale(*value);
+            if value > highest_value {
+                highest_value = value;
+            } else if value < lowest_value {
+                lowest_value = value;
+            }
+        }
+    }
+
+    // same 10% extra space as the terminal chart
+    highest_value += highest_value * 10.0 / 100.0;
+    lowest_value -= lowest_value * 10.0 / 100.0;
+
+    // avoid dividing by 0 when every value is the same
+    if highest_value == lowest_value {
+        highest_value += 1.0;
+    }
+
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let last_point = chart_lines.last_point.max(1) as f64;
+
+    let to_x = |point: f64| MARGIN_LEFT + point / last_point * plot_width;
+    let to_y = |value: f64| {
+        MARGIN_TOP + (highest_value - value) / (highest_value - lowest_value) * plot_height
+    };
+
+    let background = to_hex(theme.background);
+    let text = to_hex(theme.text);
+    let axis = to_hex(theme.border);
+
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="monospace" font-size="16">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{background}"/>"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="40" fill="{text}" font-size="22" text-anchor="middle">{}</text>"#,
+        WIDTH / 2.0,
+        escape_text(title)
+    )
+    .unwrap();
+
+    // y axis labels and grid lines. 11 labels like the terminal chart
+    for i in 0..=10 {
+        let value = lowest_value + (highest_value - lowest_value) * f64::from(i) / 10.0;
+        let label = if log_scale {
+            from_log_scale(value)
+        } else {
+            value
+        };
+        let y = to_y(value);
+
+        writeln!(
+            svg,
+            r#"<line x1="{MARGIN_LEFT}" y1="{y:.2}" x2="{:.2}" y2="{y:.2}" stroke="{axis}" stroke-opacity="0.3"/>"#,
+            MARGIN_LEFT + plot_width
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{:.2}" fill="{text}" text-anchor="end">{label:.2}</text>"#,
+            MARGIN_LEFT - 10.0,
+            y + 5.0
+        )
+        .unwrap();
+    }
+
+    // x axis labels. 5 dates spread across the period
+    if let Some(start_date) = chart_lines.start_date {
+        for i in 0..=4 {
+            let point = (last_point * f64::from(i) / 4.0).round();
+            let date = start_date + Duration::days(point as i64 * chart_lines.step_days);
+
+            writeln!(
+                svg,
+                r#"<text x="{:.2}" y="{:.2}" fill="{text}" text-anchor="middle">{date}</text>"#,
+                to_x(point),
+                MARGIN_TOP + plot_height + 30.0
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(
+        svg,
+        r#"<rect x="{MARGIN_LEFT}" y="{MARGIN_TOP}" width="{plot_width}" height="{plot_height}" fill="none" stroke="{axis}"/>"#
+    )
+    .unwrap();
+
+    for (index, line) in activated_lines.iter().enumerate() {
+        let color = to_hex(get_color(line.color));
+
+        let points = line
+            .points
+            .iter()
+            .map(|(point, value)| format!("{:.2},{:.2}", to_x(*point), to_y(scale(*value))))
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        writeln!(
+            svg,
+            r#"<polyline points="{points}" fill="none" stroke="{color}" stroke-width="2"/>"#
+        )
+        .unwrap();
+
+        // lowest and highest point markers with their values
+        if let Some((lowest_point, highest_point)) = get_line_extremes(&line.points) {
+            for (point, y_offset) in [(lowest_point, 20.0), (highest_point, -10.0)] {
+                let (x, y) = (to_x(point.0), to_y(scale(point.1)));
+                writeln!(
+                    svg,
+                    r#"<circle cx="{x:.2}" cy="{y:.2}" r="5" fill="{color}"/>"#
+                )
+                .unwrap();
+                writeln!(
+                    svg,
+                    r#"<text x="{x:.2}" y="{:.2}" fill="{color}" text-anchor="middle">{:.2}</text>"#,
+                    y + y_offset,
+                    point.1
+                )
+                .unwrap();
+            }
+        }
+
+        // legend on the right side of the chart
+        let legend_x = MARGIN_LEFT + plot_width + 20.0;
+        let legend_y = MARGIN_TOP + 20.0 + index as f64 * 28.0;
+
+        writeln!(
+            svg,
+            r#"<rect x="{legend_x:.2}" y="{:.2}" width="16" height="16" fill="{color}"/>"#,
+            legend_y - 13.0
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{legend_y:.2}" fill="{text}">{}</text>"#,
+            legend_x + 26.0,
+            escape_text(&line.name)
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Saves the svg image of the chart to the given path
+pub fn export_chart_svg(
+    chart_lines: &ChartLines,
+    log_scale: bool,
+    title: &str,
+    path: &Path,
+    theme: &Theme,
+) -> ioResult<()> {
+    fs::write(path, get_chart_svg(chart_lines, log_scale, title, theme))
+}
@@ -1,34 +1,41 @@
-use chrono::{naive::NaiveDate, Duration};
+use chrono::Duration;
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::symbols::Marker;
-use ratatui::text::Span;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Axis, Block, Chart, Dataset, GraphType};
 use ratatui::Frame;
-use rusqlite::Connection;
 use std::collections::HashMap;
 
-use crate::chart_page::ChartData;
-use crate::page_handler::{ChartTab, IndexedData, BACKGROUND, BOX, SELECTED};
-use crate::utility::{create_tab, create_tab_activation, get_all_tx_methods, main_block};
+use crate::chart_page::{from_log_scale, get_line_extremes, to_log_scale, ChartData, Rgb};
+use crate::page_handler::{ChartTab, ChartView, IndexedData, Theme};
+use crate::utility::{create_tab, create_tab_activation, get_color, main_block};
 
 /// Creates the balance chart from the transactions
 #[cfg(not(tarpaulin_include))]
+#[allow(clippy::too_many_arguments)]
 pub fn chart_ui<S: ::std::hash::BuildHasher>(
     f: &mut Frame,
     months: &IndexedData,
     years: &IndexedData,
     mode_selection: &IndexedData,
     chart_tx_methods: &IndexedData,
-    chart_data: &ChartData,
+    chart_tags: &IndexedData,
+    chart_data: &mut ChartData,
     current_page: &ChartTab,
     chart_hidden_mode: bool,
+    chart_view: &ChartView,
+    chart_log_scale: bool,
+    chart_animation_speed: u8,
+    chart_weekly: bool,
     loop_remaining: &mut Option<f64>,
     chart_activated_methods: &HashMap<String, bool, S>,
-    conn: &Connection,
+    chart_activated_tags: &HashMap<String, bool, S>,
+    loading: bool,
+    method_colors: &HashMap<String, Rgb>,
+    theme: &Theme,
 ) {
     let size = f.size();
-    let (all_txs, all_balance) = chart_data.get_data(mode_selection, months.index, years.index);
 
     // divide the terminal into various chunks to draw the interface. This is a vertical chunk
     let mut main_layout = Layout::default().direction(Direction::Vertical).margin(2);
@@ -81,55 +88,54 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
     let chunks = main_layout.split(size);
 
     // creates border around the entire terminal
-    f.render_widget(main_block(), size);
+    f.render_widget(main_block(theme), size);
 
-    let mut month_tab = create_tab(months, "Months");
+    let mut month_tab = create_tab(months, "Months", theme);
 
-    let mut year_tab = create_tab(years, "Years");
+    let mut year_tab = create_tab(years, "Years", theme);
 
-    let mut mode_selection_tab = create_tab(mode_selection, "Modes");
+    let mut mode_selection_tab = create_tab(mode_selection, "Modes", theme);
 
-    let mut tx_method_selection_tab = create_tab_activation(
-        chart_tx_methods,
-        "Tx Method Selection",
+    // on the tag view, tags take the place of the tx methods
+    let mut tx_method_selection_tab = if let ChartView::Tags = chart_view {
+        create_tab_activation(
+            chart_tags,
+            "Tag Selection",
+            chart_activated_tags,
+            &HashMap::new(),
+            theme,
+        )
+    } else {
+        create_tab_activation(
+            chart_tx_methods,
+            "Tx Method Selection",
+            chart_activated_methods,
+            method_colors,
+            theme,
+        )
+    };
+
+    // the lines are only created again when the chart state changes, each frame only slices them
+    let chart_lines = chart_data.get_cached_chart_lines(
+        chart_view,
+        mode_selection,
+        months.index,
+        years.index,
+        chart_weekly,
+        chart_tags,
         chart_activated_methods,
+        chart_activated_tags,
+        method_colors,
     );
 
-    let all_tx_methods = get_all_tx_methods(conn);
-
-    // a vector containing another vector vec![X, Y] with coordinate of where to render chart points
-    let mut datasets: Vec<Vec<(f64, f64)>> = Vec::new();
-    let mut last_balances = Vec::new();
-
-    // adding default initial value if no data to load
-    if all_txs.is_empty() {
-        for _i in &all_tx_methods {
-            datasets.push(vec![(0.0, 0.0)]);
-            last_balances.push(0.0);
-        }
-    }
-
-    let mut lowest_balance = 0.0;
-    let mut highest_balance = 0.0;
-
-    let mut date_labels: Vec<String> = vec![];
-
-    let mut current_axis = 0.0;
-
-    // if there are no transactions, we will create an empty chart
-    if !all_txs.is_empty() {
-        // contains all dates of the transactions
-        let all_dates = chart_data.get_all_dates(mode_selection, months.index, years.index);
-
-        let mut checking_date = NaiveDate::parse_from_str(&all_txs[0][0], "%d-%m-%Y").unwrap();
-
-        // The final date where the loop will stop
-        let final_date =
-            NaiveDate::parse_from_str(&all_txs[all_txs.len() - 1][0], "%d-%m-%Y").unwrap();
-
-        // total days = number of loops required to render everything
-        let total_loop = final_date.signed_duration_since(checking_date).num_days() as f64;
+    // total points = number of loops required to render everything
+    let total_loop = chart_lines.last_point as f64;
 
+    // Only the balance view is animated. The other views are rendered at once.
+    // If there are no transactions, we will create an empty chart
+    if let (ChartView::Balance, Some(_), 1..) =
+        (chart_view, chart_lines.start_date, chart_animation_speed)
+    {
         // When chart ui is selected, start by rendering this amount of day worth of data,
         // then render_size * 2, 3 and so on until the final day is reached, creating a small animation.
         // Numbers were determined after checking with data filled db and with --release flag
@@ -151,8 +157,11 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
             1.0
         };
 
+        // a faster animation renders more days per loop
+        let render_size = render_size * f64::from(chart_animation_speed);
+
         // default value = Some(0). After each loop this value goes down by render_size. Once <=0, turn it into None.
-        // When it's None, it won't stop the loop in the middle.
+        // When it's None, everything gets rendered.
         if let Some(val) = loop_remaining {
             if *val == 0.0 {
                 if total_loop > render_size {
@@ -166,113 +175,100 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
                 *loop_remaining = None;
             }
         }
+    } else {
+        *loop_remaining = None;
+    }
 
-        // total times to loop this time. If None, then render everything
-        let mut to_loop = loop_remaining.as_mut().map(|val| total_loop - *val);
+    // total points to render this time. If None, then render everything
+    let visible_points = match loop_remaining {
+        Some(val) => (total_loop - *val).ceil().max(1.0),
+        None => total_loop + 1.0,
+    };
 
-        // labels of the x axis
-        date_labels.push(checking_date.to_string());
-        date_labels.push(final_date.to_string());
+    let current_axis = visible_points;
 
-        // data_num represents which index to check out from all the txs and balances data.
-        // to_add_again will become true in cases where two or more transactions shares the same date simultaneously.
-        // Same date transactions movements will be combined together into 1 chart location
+    // labels of the x axis
+    let mut date_labels: Vec<String> = vec![];
 
-        let mut to_add_again = false;
-        let mut data_num = 0;
-        loop {
-            if all_dates.contains(&checking_date) {
-                let current_balances = &all_balance[data_num];
+    if let Some(start_date) = chart_lines.start_date {
+        date_labels.push(start_date.to_string());
+        let last_visible_date =
+            start_date + Duration::days((visible_points as i64 - 1) * chart_lines.step_days);
+        date_labels.push(last_visible_date.to_string());
+    }
 
-                // default next_date in case there is no more next_date
-                let mut next_date = NaiveDate::from_ymd_opt(2040, 1, 1).unwrap();
+    // a vector containing another vector vec![X, Y] with coordinate of where to render chart points
+    let mut datasets: Vec<Vec<(f64, f64)>> = chart_lines
+        .lines
+        .iter()
+        .map(|line| {
+            line.points
+                .iter()
+                .take(visible_points as usize)
+                .copied()
+                .collect()
+        })
+        .collect();
 
-                if all_txs.len() > data_num + 1 {
-                    next_date =
-                        NaiveDate::parse_from_str(&all_txs[data_num + 1][0], "%d-%m-%Y").unwrap();
-                }
-                // new valid transactions so the earlier looped balance is not required.
-                // if no tx exists in a date, data from last_balances/previous valid date is used to compensate for it
-                last_balances = Vec::new();
-
-                for method_index in 0..all_tx_methods.len() {
-                    // keep track of the highest and the lowest point of the balance
-                    let current_balance = current_balances[method_index].parse::<f64>().unwrap();
-
-                    // We will not consider the highest/lowest balance if the method is currently deactivated on chart
-                    // We can't directly skip it because the dataset vector expects something in the index of this method
-                    // We will have the data but it just won't be shown on the chart
-                    if chart_activated_methods[&all_tx_methods[method_index]] {
-                        if current_balance > highest_balance {
-                            highest_balance = current_balance;
-                        } else if current_balance < lowest_balance {
-                            lowest_balance = current_balance;
-                        }
-                    }
-
-                    if to_add_again {
-                        // if to_add_again is true, means in the last loop, the date and the current date was the same
-                        // as the date is the same, the data needs to be merged thus using the same x y point in the chart.
-                        // pop the last one added and that to last_balance. If the next date is the same,
-                        // last_balance will be used to keep on merging the data.
-
-                        let (position, _balance) = datasets[method_index].pop().unwrap();
-                        let to_push = vec![(position, current_balance)];
-                        datasets[method_index].extend(to_push);
-                        last_balances.push(current_balance);
-                    } else {
-                        let to_push = vec![(current_axis, current_balance)];
-
-                        if datasets.get(method_index).is_some() {
-                            datasets[method_index].extend(to_push);
-                        } else {
-                            datasets.push(to_push);
-                        }
-
-                        last_balances.push(current_balance);
-                    }
-                }
+    let mut lowest_balance = 0.0;
+    let mut highest_balance = 0.0;
 
-                if next_date == checking_date {
-                    // the axis won't move if the next date is the same.
-                    to_add_again = true;
-                } else {
-                    to_add_again = false;
-                    current_axis += 1.0;
-                    checking_date += Duration::days(1);
-                }
+    // keep track of the highest and the lowest point of the lines.
+    // We will not consider the highest/lowest value if the line is currently deactivated on chart
+    for (line, points) in chart_lines.lines.iter().zip(&datasets) {
+        if !line.activated {
+            continue;
+        }
 
-                // successfully checked a transaction, we will check the new index in the next iteration
-                data_num += 1;
-            } else {
-                // as the date does not exist in the transaction list, we will use the last used balance and add a point in the chart
-                for method_index in 0..all_tx_methods.len() {
-                    let to_push = vec![(current_axis, last_balances[method_index])];
-                    datasets[method_index].extend(to_push);
-                }
-                current_axis += 1.0;
-                checking_date += Duration::days(1);
+        for (_, value) in points {
+            if *value > highest_balance {
+                highest_balance = *value;
+            } else if *value < lowest_balance {
+                lowest_balance = *value;
             }
+        }
+    }
 
-            if !to_add_again {
-                if let Some(val) = to_loop {
-                    // break the loop if total day amount is reached
-                    if val - 1.0 <= 0.0 {
-                        date_labels.pop().unwrap();
-                        date_labels.push(checking_date.to_string());
-                        break;
-                    }
-                    to_loop = Some(val - 1.0);
-                }
-            }
+    // the lowest and the highest point of each activated line get a marker on the chart
+    // and their values are shown below the chart
+    let mut marker_points: Vec<(Color, Vec<(f64, f64)>)> = Vec::new();
+    let mut extreme_spans = Vec::new();
 
-            if checking_date >= final_date + Duration::days(1) {
-                break;
+    for (line, points) in chart_lines.lines.iter().zip(&datasets) {
+        if !line.activated {
+            continue;
+        }
+
+        let Some((lowest_point, highest_point)) = get_line_extremes(points) else {
+            continue;
+        };
+
+        marker_points.push((get_color(line.color), vec![lowest_point, highest_point]));
+        extreme_spans.push(Span::styled(
+            format!(
+                "{} ▲{:.2} ▼{:.2}  ",
+                line.name, highest_point.1, lowest_point.1
+            ),
+            Style::default()
+                .fg(get_color(line.color))
+                .bg(theme.background),
+        ));
+    }
+
+    // on log scale every point is plotted by its log value. The labels are turned back to the
+    // actual value so they still show the balance
+    if chart_log_scale {
+        let marker_datasets = marker_points.iter_mut().map(|(_, points)| points);
+
+        for dataset in datasets.iter_mut().chain(marker_datasets) {
+            for point in dataset.iter_mut() {
+                point.1 = to_log_scale(point.1);
             }
         }
-    } else {
-        *loop_remaining = None;
+        highest_balance = to_log_scale(highest_balance);
+        lowest_balance = to_log_scale(lowest_balance);
     }
+
     // add a 10% extra value to the highest and the lowest balance
     // so the chart can properly render
     highest_balance += highest_balance * 10.0 / 100.0;
@@ -283,84 +279,126 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
     let mut to_add = lowest_balance;
 
     // go through the lowest balance and keep adding the difference until the highest point
-    let mut labels = vec![lowest_balance.to_string()];
+    let mut labels = if chart_log_scale {
+        vec![format!("{:.2}", from_log_scale(lowest_balance))]
+    } else {
+        vec![lowest_balance.to_string()]
+    };
     // 10 labels, so loop 10 times
     for _i in 0..10 {
         to_add += diff;
-        labels.push(format!("{to_add:.2}"));
+        if chart_log_scale {
+            labels.push(format!("{:.2}", from_log_scale(to_add)));
+        } else {
+            labels.push(format!("{to_add:.2}"));
+        }
     }
 
-    let mut color_list = vec![
-        Color::Rgb(139, 233, 253), // Cyan
-        Color::Rgb(80, 250, 123),  // Green
-        Color::Rgb(255, 184, 108), // Orange
-        Color::Rgb(255, 121, 198), // Pink
-        Color::Rgb(189, 147, 249), // Purple
-        Color::Rgb(255, 85, 85),   // Red
-    ];
-
+    // loop through the data that was added for each line and turn them into chart data
     let mut final_dataset = vec![];
 
-    // loop through the data that was added for each tx_method  and turn them into chart data
-    for i in 0..all_tx_methods.len() {
-        // run out of colors = cyan default
-        if color_list.is_empty() {
-            color_list.push(Color::Rgb(241, 250, 140));
-        }
-
-        if !chart_activated_methods[&all_tx_methods[i]] {
+    for (line, points) in chart_lines.lines.iter().zip(&datasets) {
+        if !line.activated {
             continue;
         }
 
         final_dataset.push(
             Dataset::default()
-                .name(all_tx_methods[i].clone())
+                .name(line.name.clone())
                 .marker(Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(
                     Style::default()
-                        .fg(color_list.pop().unwrap())
-                        .bg(BACKGROUND),
+                        .fg(get_color(line.color))
+                        .bg(theme.background),
                 )
-                .data(&datasets[i]),
+                .data(points),
         );
     }
 
+    for (color, points) in &marker_points {
+        final_dataset.push(
+            Dataset::default()
+                .marker(Marker::Block)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(*color).bg(theme.background))
+                .data(points),
+        );
+    }
+
+    // show the chart settings that are not on their default value
+    let mut chart_status = Vec::new();
+    if loading {
+        chart_status.push("Loading...".to_string());
+    }
+    if chart_weekly {
+        chart_status.push("Weekly".to_string());
+    }
+    if chart_log_scale {
+        chart_status.push("Log Scale".to_string());
+    }
+    match chart_animation_speed {
+        0 => chart_status.push("Animation Off".to_string()),
+        1 => {}
+        speed => chart_status.push(format!("Animation {speed}x")),
+    }
+
+    let mut chart_block =
+        Block::default().style(Style::default().bg(theme.background).fg(theme.border));
+    if !chart_status.is_empty() {
+        chart_block = chart_block.title(format!(" {} ", chart_status.join(" | ")));
+    }
+
     let chart = Chart::new(final_dataset)
-        .block(Block::default().style(Style::default().bg(BACKGROUND).fg(BOX)))
-        .style(Style::default().bg(BACKGROUND).fg(BOX))
+        .block(chart_block)
+        .style(Style::default().bg(theme.background).fg(theme.border))
         .x_axis(
             Axis::default()
-                .title(Span::styled("", Style::default().bg(BACKGROUND).fg(BOX)))
-                .style(Style::default().bg(BACKGROUND).fg(BOX))
+                .title(Line::from(extreme_spans))
+                .style(Style::default().bg(theme.background).fg(theme.border))
                 .bounds([0.0, current_axis - 1.0])
                 .labels(date_labels.iter().cloned().map(Span::from).collect()),
         )
         .y_axis(
             Axis::default()
-                .title(Span::styled("", Style::default().bg(BACKGROUND).fg(BOX)))
-                .style(Style::default().bg(BACKGROUND).fg(BOX))
+                .title(Span::styled(
+                    "",
+                    Style::default().bg(theme.background).fg(theme.border),
+                ))
+                .style(Style::default().bg(theme.background).fg(theme.border))
                 .bounds([lowest_balance, highest_balance])
                 .labels(labels.iter().cloned().map(Span::from).collect()),
         );
 
     match current_page {
         ChartTab::Months => {
-            month_tab = month_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            month_tab = month_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
 
         ChartTab::Years => {
-            year_tab = year_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            year_tab = year_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
         ChartTab::ModeSelection => {
-            mode_selection_tab = mode_selection_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            mode_selection_tab = mode_selection_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
         ChartTab::TxMethods => {
-            tx_method_selection_tab = tx_method_selection_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            tx_method_selection_tab = tx_method_selection_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
     }
 
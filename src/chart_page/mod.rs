@@ -1,5 +1,8 @@
-mod chart_data;
+mod chart_export;
 mod chart_ui;
+mod page;
 
-pub use chart_data::ChartData;
+pub use chart_export::{export_chart_svg, get_chart_svg};
 pub use chart_ui::chart_ui;
+pub use page::ChartPage;
+pub use rex_core::chart::*;
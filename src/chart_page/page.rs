@@ -0,0 +1,49 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::chart_page::chart_ui;
+use crate::key_checker::{chart_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+
+pub struct ChartPage;
+
+impl Page for ChartPage {
+    fn name(&self) -> &str {
+        "Chart"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, _context: &PageContext) {
+        let AppState {
+            chart,
+            method_colors,
+            theme,
+            ..
+        } = state;
+
+        chart_ui(
+            f,
+            &chart.months,
+            &chart.years,
+            &chart.modes,
+            &chart.tx_methods,
+            &chart.tags,
+            &mut chart.data,
+            &chart.tab,
+            chart.hidden_mode,
+            &chart.view,
+            chart.log_scale,
+            chart.animation_speed,
+            chart.weekly,
+            &mut chart.index,
+            &chart.activated_methods,
+            &chart.activated_tags,
+            chart.loading,
+            method_colors,
+            theme,
+        );
+    }
+
+    fn get_action(&self, key: KeyEvent, _state: &AppState) -> Option<Action> {
+        chart_keys(key)
+    }
+}
@@ -1,5 +0,0 @@
-mod setup;
-mod update;
-
-pub use setup::*;
-pub use update::*;
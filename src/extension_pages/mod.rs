@@ -0,0 +1,26 @@
+//! Optional pages that are only built with their cargo feature. Each enabled page is opened with
+//! an F key in the order they are added here, starting with F1
+//!
+//! To add a page, implement `page_handler::Page` for it in a new module, put the module behind a
+//! feature in `Cargo.toml` and push the page in `get_extension_pages`. Nothing else needs to
+//! change for the page to be drawn and get its keys
+
+#[cfg(feature = "overview-page")]
+mod overview_page;
+
+#[cfg(feature = "overview-page")]
+pub use overview_page::OverviewPage;
+
+use crate::page_handler::Page;
+
+/// Returns every extension page enabled at compile time
+#[must_use]
+#[allow(unused_mut, clippy::vec_init_then_push)]
+pub fn get_extension_pages() -> Vec<Box<dyn Page>> {
+    let mut pages: Vec<Box<dyn Page>> = Vec::new();
+
+    #[cfg(feature = "overview-page")]
+    pages.push(Box::new(OverviewPage::new()));
+
+    pages
+}
@@ -0,0 +1,168 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Row, Table};
+use ratatui::Frame;
+use rusqlite::Connection;
+use thousands::Separable;
+
+use crate::db::MONTHS;
+use crate::key_checker::Action;
+use crate::outputs::HandlingOutput;
+use crate::page_handler::{AppState, IndexedData, Page, PageContext};
+use crate::utility::{create_tab, main_block, styled_block};
+
+/// An example extension page. Shows the income, the expense and the net of every month of the
+/// selected year
+pub struct OverviewPage {
+    years: IndexedData,
+    /// The income and the expense of every month of the selected year
+    totals: Vec<(f64, f64)>,
+}
+
+impl OverviewPage {
+    #[must_use]
+    pub fn new() -> Self {
+        OverviewPage {
+            years: IndexedData::new_yearly(),
+            totals: Vec::new(),
+        }
+    }
+
+    fn reload(&mut self, conn: &Connection) {
+        self.totals = get_month_totals(self.years.index, conn);
+    }
+}
+
+impl Default for OverviewPage {
+    fn default() -> Self {
+        OverviewPage::new()
+    }
+}
+
+impl Page for OverviewPage {
+    fn name(&self) -> &str {
+        "Overview"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, _context: &PageContext) {
+        let theme = &state.theme;
+
+        let size = f.size();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(size);
+
+        f.render_widget(main_block(theme), size);
+        f.render_widget(create_tab(&self.years, "Years", theme), chunks[0]);
+
+        let header_cells = ["Month", "Income", "Expense", "Net"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(theme.background)));
+
+        let header = Row::new(header_cells)
+            .style(Style::default().bg(theme.header))
+            .height(1);
+
+        let rows = MONTHS
+            .iter()
+            .zip(&self.totals)
+            .map(|(month, (income, expense))| {
+                let net = income - expense;
+                let net_style = if net < 0.0 {
+                    Style::default().fg(theme.expense)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+
+                Row::new([
+                    Cell::from(*month),
+                    Cell::from(format!("{income:.2}").separate_with_commas()),
+                    Cell::from(format!("{expense:.2}").separate_with_commas()),
+                    Cell::from(format!("{net:.2}").separate_with_commas()).style(net_style),
+                ])
+                .style(Style::default().bg(theme.background).fg(theme.text))
+            });
+
+        let table = Table::new(rows, [Constraint::Percentage(25); 4])
+            .header(header)
+            .block(styled_block("Overview", theme));
+
+        f.render_widget(table, chunks[1]);
+    }
+
+    fn get_action(&self, key: KeyEvent, _state: &AppState) -> Option<Action> {
+        let action = match key.code {
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Char('f') => Action::GoHome,
+            KeyCode::Char('a') => Action::GoAddTx,
+            KeyCode::Char('r') => Action::GoChart,
+            KeyCode::Char('z') => Action::GoSummary,
+            KeyCode::Char('w') => Action::GoSearch,
+            KeyCode::Char('y') => Action::GoActivity,
+            KeyCode::Left | KeyCode::Right => Action::PageInput,
+            _ => return None,
+        };
+        Some(action)
+    }
+
+    fn open(&mut self, _state: &mut AppState, conn: &Connection) {
+        self.reload(conn);
+    }
+
+    fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        _state: &mut AppState,
+        conn: &mut Connection,
+    ) -> Option<HandlingOutput> {
+        match key.code {
+            KeyCode::Left => self.years.previous(),
+            KeyCode::Right => self.years.next(),
+            _ => return None,
+        }
+        self.reload(conn);
+        None
+    }
+}
+
+/// Returns the total income and expense of every month of the year index
+fn get_month_totals(year: usize, conn: &Connection) -> Vec<(f64, f64)> {
+    let mut totals = vec![(0.0, 0.0); MONTHS.len()];
+    let year = year + 2022;
+
+    let mut statement = conn
+        .prepare_cached(
+            "SELECT CAST(substr(date, 6, 2) AS INTEGER), amount, tx_type FROM tx_all
+            WHERE date BETWEEN date(?1 || '-01-01') AND date(?1 || '-12-31')",
+        )
+        .expect("could not prepare statement");
+
+    let rows = statement
+        .query_map([year.to_string()], |row| {
+            Ok((
+                row.get::<_, usize>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .unwrap();
+
+    for (month, amount, tx_type) in rows.flatten() {
+        let Some((income, expense)) = totals.get_mut(month.wrapping_sub(1)) else {
+            continue;
+        };
+        let amount = amount.parse::<f64>().unwrap_or_default();
+
+        match tx_type.as_str() {
+            "Income" => *income += amount,
+            "Expense" => *expense += amount,
+            _ => {}
+        }
+    }
+
+    totals
+}
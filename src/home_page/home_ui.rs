@@ -1,16 +1,23 @@
+use chrono::NaiveDate;
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::style::{Modifier, Style};
-use ratatui::widgets::{Cell, Row, Table};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Cell, Paragraph, Row, Table, TableState};
 use ratatui::Frame;
-use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
 use thousands::Separable;
 
+use crate::chart_page::Rgb;
+use crate::home_page::{get_day_groups, HomeFilter, OWED_SUFFIX, UTILIZATION_WARNING};
 use crate::page_handler::{
-    HomeRow, HomeTab, IndexedData, TableData, BACKGROUND, BLUE, BOX, HEADER, RED, SELECTED, TEXT,
+    HomeColumn, HomeRow, HomeSortingType, HomeTab, IndexedData, SortingDirection, TableData, Theme,
+    TxStatus,
+};
+use crate::utility::{
+    create_tab, get_color, main_block, reverse_date_format, styled_block, DEFAULT_PROFILE,
 };
-use crate::utility::{create_tab, get_all_tx_methods, main_block, styled_block};
 
-pub const BALANCE_BOLD: [&str; 7] = [
+pub const BALANCE_BOLD: [&str; 8] = [
     "Balance",
     "Changes",
     "Total",
@@ -18,18 +25,54 @@ pub const BALANCE_BOLD: [&str; 7] = [
     "Expense",
     "Daily Income",
     "Daily Expense",
+    "Utilization",
 ];
 
+/// Returns the tx method name from a balance section label by removing the owed and the
+/// currency markers added after it
+#[must_use]
+pub fn get_label_method(label: &str) -> &str {
+    let label = label.trim_end_matches(OWED_SUFFIX);
+
+    match label.rsplit_once(" (") {
+        Some((method, symbol)) if symbol.ends_with(')') && symbol.chars().count() <= 4 => method,
+        _ => label,
+    }
+}
+
 /// The function draws the Home page of the interface.
 #[cfg(not(tarpaulin_include))]
+#[allow(clippy::too_many_arguments)]
 pub fn home_ui(
     f: &mut Frame,
     to_reset: bool,
     months: &IndexedData,
     years: &IndexedData,
+    weeks: Option<&IndexedData>,
+    date_range: Option<(NaiveDate, NaiveDate)>,
     table: &mut TableData,
     balance: &mut [Vec<String>],
     current_tab: &HomeTab,
+    sort_type: &HomeSortingType,
+    sort_direction: &SortingDirection,
+    grouped: bool,
+    subtotals: bool,
+    collapsed_days: &HashSet<String>,
+    home_filter: &HomeFilter,
+    total_txs: usize,
+    marked_rows: &HashSet<usize>,
+    future_rows: &HashSet<usize>,
+    balance_column: Option<usize>,
+    running_balances: &[String],
+    statuses: &[TxStatus],
+    id_nums: &[i32],
+    columns: &[HomeColumn],
+    cleared_balance: bool,
+    spendable_balance: Option<f64>,
+    group_balances: &[(String, f64)],
+    profile: &str,
+    read_only: bool,
+    projected_balance: Option<&str>,
     width_data: &mut [Constraint],
     balance_load: &mut [f64],
     ongoing_balance: &mut Vec<String>,
@@ -50,41 +93,265 @@ pub fn home_ui(
     daily_ongoing_expense: &mut Vec<String>,
     daily_last_expense: &mut Vec<String>,
     load_percentage: &mut f64,
-    conn: &Connection,
+    method_colors: &HashMap<String, Rgb>,
+    all_methods: &[String],
+    theme: &Theme,
 ) {
-    let all_methods = get_all_tx_methods(conn);
     let size = f.size();
 
     // Used to highlight Changes on Balance section of Home Page
-    let selected_style_income = Style::default().fg(BLUE).add_modifier(Modifier::REVERSED);
-    let selected_style_expense = Style::default().fg(RED).add_modifier(Modifier::REVERSED);
+    let selected_style_income = Style::default()
+        .fg(theme.income)
+        .add_modifier(Modifier::REVERSED);
+    let selected_style_expense = Style::default()
+        .fg(theme.expense)
+        .add_modifier(Modifier::REVERSED);
 
     let mut table_name = "Transactions".to_string();
 
-    if !table.items.is_empty() {
-        table_name = format!("Transactions: {}", table.items.len());
+    let mut balance_name = if cleared_balance {
+        "Cleared Balance".to_string()
+    } else {
+        "Balance".to_string()
+    };
+
+    // savings are left out of the money that can be spent
+    if let Some(spendable_balance) = spendable_balance {
+        balance_name = format!(
+            "{balance_name} | Spendable: {}",
+            format!("{spendable_balance:.2}").separate_with_commas()
+        );
+    }
+
+    for (group, group_balance) in group_balances {
+        balance_name = format!(
+            "{balance_name} | {group}: {}",
+            format!("{group_balance:.2}").separate_with_commas()
+        );
+    }
+
+    // the projected balance is the final balance once the future txs happen
+    if let Some(projected_balance) = projected_balance {
+        balance_name = format!(
+            "{balance_name} | Projected: {}",
+            projected_balance.separate_with_commas()
+        );
+    }
+
+    if home_filter.is_active() {
+        table_name = format!("Transactions: {}/{}", table.items.len(), total_txs);
+    } else if !table.items.is_empty() {
+        // txs that are not fetched yet are also counted
+        table_name = format!("Transactions: {total_txs}");
+    }
+
+    if profile != DEFAULT_PROFILE {
+        table_name = format!("{profile} | {table_name}");
+    }
+
+    if read_only {
+        table_name = format!("Read Only | {table_name}");
+    }
+
+    // the text filter is shown with a cursor while it's being typed
+    if home_filter.typing || !home_filter.text.is_empty() {
+        let cursor = if home_filter.typing { "_" } else { "" };
+        table_name = format!("{table_name} | /{}{cursor}", home_filter.text);
+    }
+
+    // the custom range is shown since the month and the week tabs no longer match the txs
+    if let Some((from, to)) = date_range {
+        table_name = format!(
+            "{table_name} | {} to {}",
+            from.format("%d-%m-%Y"),
+            to.format("%d-%m-%Y")
+        );
+    }
+
+    if !marked_rows.is_empty() {
+        table_name = format!("{table_name} | Marked: {}", marked_rows.len());
     }
 
     // Transaction widget's top row/header to highlight what each data will mean
-    let header_cells = ["Date", "Details", "TX Method", "Amount", "Type", "Tags"]
+    // The sorted column gets an arrow unless it's the default chronological order
+    let chronological =
+        sort_type == &HomeSortingType::ByDate && sort_direction == &SortingDirection::Ascending;
+
+    let mut headers = columns
+        .iter()
+        .map(|column| {
+            let title = column.get_title();
+            if !chronological && column.get_tx_column() == Some(sort_type.get_column()) {
+                format!("{title} {}", sort_direction.get_arrow())
+            } else {
+                title.to_string()
+            }
+        })
+        .collect::<Vec<String>>();
+
+    // the running balance column is named after the tx method it follows
+    if let Some(column) = balance_column {
+        match all_methods.get(column) {
+            Some(method) => headers.push(format!("{method} Balance")),
+            None => headers.push("Balance".to_string()),
+        }
+    }
+
+    let header_cells = headers
         .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+        .map(|h| Cell::from(h.as_str()).style(Style::default().fg(theme.background)));
 
     let header = Row::new(header_cells)
-        .style(Style::default().bg(HEADER))
+        .style(Style::default().bg(theme.header))
         .height(1)
         .bottom_margin(0);
 
     // iter through table data and turn them into rows and columns
-    let rows = table.items.iter().map(|item| {
+    let rows = table.items.iter().enumerate().map(|(index, item)| {
         let height = 1;
-        let cells = item.iter().map(|c| Cell::from(c.separate_with_commas()));
+        let marked = marked_rows.contains(&index);
+        let mut cells = columns
+            .iter()
+            .map(|column| match column {
+                // marked rows get a marker before the date
+                HomeColumn::Date if marked => {
+                    Cell::from(format!("● {}", reverse_date_format(item[0].clone())))
+                }
+                HomeColumn::Date => Cell::from(reverse_date_format(item[0].clone())),
+                HomeColumn::Status => {
+                    let status = statuses.get(index).copied().unwrap_or(TxStatus::Pending);
+                    let cell = Cell::from(status.get_glyph());
+                    if status == TxStatus::Pending {
+                        cell
+                    } else {
+                        cell.style(Style::default().fg(theme.income))
+                    }
+                }
+                HomeColumn::Id => Cell::from(
+                    id_nums
+                        .get(index)
+                        .map_or(String::new(), ToString::to_string),
+                ),
+                _ => Cell::from(item[column.get_tx_column().unwrap()].separate_with_commas()),
+            })
+            .collect::<Vec<Cell>>();
+
+        // a negative running balance is highlighted to show where the balance dipped
+        if let Some(balance) = running_balances.get(index) {
+            let mut cell = Cell::from(balance.separate_with_commas());
+            if balance.starts_with('-') {
+                cell = cell.style(Style::default().fg(theme.expense));
+            }
+            cells.push(cell);
+        }
+
+        let mut row_style = Style::default().bg(theme.background).fg(theme.text);
+        if marked {
+            row_style = row_style.add_modifier(Modifier::BOLD);
+        }
+        // the future txs are not counted on the final balance yet
+        if future_rows.contains(&index) {
+            row_style = row_style.add_modifier(Modifier::DIM);
+        }
+
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().bg(BACKGROUND).fg(TEXT))
+            .style(row_style)
     });
 
+    // the day header and the subtotal rows are only added while sorted by date. The table state
+    // keeps pointing to the txs so a separate state with the shown row position is used for them
+    let by_date = sort_type == &HomeSortingType::ByDate;
+    let grouped = grouped && by_date;
+    let subtotals = subtotals && by_date;
+    let mut grouped_state = TableState::default();
+
+    let day_row_style = Style::default()
+        .bg(theme.background)
+        .fg(theme.header)
+        .add_modifier(Modifier::BOLD);
+
+    let rows = if grouped || subtotals {
+        let tx_rows = rows.collect::<Vec<Row>>();
+        let mut grouped_rows = Vec::new();
+
+        for day in get_day_groups(&table.items) {
+            let date = &table.items[day.start][0];
+            let collapsed = grouped && collapsed_days.contains(date);
+            let header_rows = usize::from(grouped);
+
+            if let Some(index) = table.state.selected().filter(|index| day.contains(index)) {
+                let row_index = if collapsed {
+                    grouped_rows.len()
+                } else {
+                    grouped_rows.len() + header_rows + index - day.start
+                };
+                grouped_state.select(Some(row_index));
+            }
+
+            let (day_income, day_expense) =
+                day.clone().fold((0.0, 0.0), |(income, expense), index| {
+                    let amount = table.items[index][3].parse::<f64>().unwrap();
+                    match table.items[index][4].as_str() {
+                        "Income" => (income + amount, expense),
+                        "Expense" => (income, expense + amount),
+                        _ => (income, expense),
+                    }
+                });
+            let day_total = day_income - day_expense;
+
+            // the empty cells keep the running balance column aligned
+            let get_day_row = |date_cell: String, details_cell: String| {
+                let mut cells = columns
+                    .iter()
+                    .map(|column| match column {
+                        HomeColumn::Date => Cell::from(date_cell.clone()),
+                        HomeColumn::Details => Cell::from(details_cell.clone()),
+                        HomeColumn::Amount => {
+                            Cell::from(format!("{day_total:+.2}").separate_with_commas())
+                        }
+                        _ => Cell::from(""),
+                    })
+                    .collect::<Vec<Cell>>();
+                if balance_column.is_some() {
+                    cells.push(Cell::from(""));
+                }
+                Row::new(cells)
+                    .height(1)
+                    .bottom_margin(0)
+                    .style(day_row_style)
+            };
+
+            if grouped {
+                let symbol = if collapsed { "▶" } else { "▼" };
+                grouped_rows.push(get_day_row(
+                    format!("{symbol} {}", reverse_date_format(date.clone())),
+                    format!("{} Transactions", day.len()),
+                ));
+            }
+
+            // a collapsed day only shows the header which already has the day total
+            if !collapsed {
+                grouped_rows.extend(tx_rows[day].iter().cloned());
+
+                if subtotals {
+                    grouped_rows.push(get_day_row(
+                        String::new(),
+                        format!(
+                            "Subtotal ↑{} ↓{}",
+                            format!("{day_income:.2}").separate_with_commas(),
+                            format!("{day_expense:.2}").separate_with_commas()
+                        ),
+                    ));
+                }
+            }
+        }
+        grouped_rows
+    } else {
+        rows.collect()
+    };
+
     // Decides how many chunks of spaces in the terminal will be.
     // Each constraint creates an empty space in the terminal with the given
     // length. The final one was given 0 as minimum value which is the Transaction
@@ -94,42 +361,54 @@ pub fn home_ui(
     // - The Balance tab
     // - The year tab
     // - The month tab
+    // - The week tab. Empty space outside of the week view
+    // - The filter bar. Empty space if hidden
     // - The transaction list/Table
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
         .constraints([
-            Constraint::Length(9),
+            // a row for each balance section row. The credit utilization row is only sometimes there
+            Constraint::Length(balance.len().max(7) as u16 + 2),
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(if weeks.is_some() { 3 } else { 0 }),
+            Constraint::Length(if home_filter.shown { 3 } else { 0 }),
             Constraint::Min(0),
         ])
         .split(size);
 
-    f.render_widget(main_block(), size);
+    f.render_widget(main_block(theme), size);
 
-    let mut month_tab = create_tab(months, "Months");
+    let mut month_tab = create_tab(months, "Months", theme);
 
-    let mut year_tab = create_tab(years, "Years");
+    let mut year_tab = create_tab(years, "Years", theme);
+
+    let mut week_tab = weeks.map(|weeks| create_tab(weeks, "Weeks", theme));
 
     // set up the table columns and their size
     // resizing the table headers to match a % of the
     // terminal space
 
-    let mut table_area = Table::new(
-        rows,
-        [
-            Constraint::Percentage(10),
-            Constraint::Percentage(37),
-            Constraint::Percentage(13),
-            Constraint::Percentage(13),
-            Constraint::Percentage(8),
-            Constraint::Percentage(18),
-        ],
-    )
-    .header(header)
-    .block(styled_block(&table_name));
+    // every shown column takes its share of the space. The running balance column comes last
+    let mut column_widths = columns
+        .iter()
+        .map(|column| column.get_width())
+        .collect::<Vec<u32>>();
+    if balance_column.is_some() {
+        column_widths.push(14);
+    }
+
+    let total_width = column_widths.iter().sum::<u32>();
+    let table_widths = column_widths
+        .iter()
+        .map(|width| Constraint::Ratio(*width, total_width))
+        .collect::<Vec<Constraint>>();
+
+    let mut table_area = Table::new(rows, table_widths)
+        .header(header)
+        .block(styled_block(&table_name, theme));
 
     if (*load_percentage + 0.004) <= 1.0 {
         *load_percentage += 0.004;
@@ -340,18 +619,12 @@ pub fn home_ui(
                                 if actual_data > *load_data {
                                     match row_type {
                                         HomeRow::TopRow => unreachable!(),
-                                        _ => {
-                                            *load_data =
-                                                *load_data + (load_difference * *load_percentage)
-                                        }
+                                        _ => *load_data += load_difference * *load_percentage,
                                     }
                                 } else if *load_data > actual_data {
                                     match row_type {
                                         HomeRow::TopRow => unreachable!(),
-                                        _ => {
-                                            *load_data =
-                                                *load_data - (load_difference * *load_percentage)
-                                        }
+                                        _ => *load_data -= load_difference * *load_percentage,
                                     }
                                 }
                             }
@@ -376,10 +649,18 @@ pub fn home_ui(
             };
 
             if c.contains('↑') {
-                Cell::from(c).style(Style::default().fg(BLUE))
-            } else if c.contains('↓') {
-                Cell::from(c).style(Style::default().fg(RED))
-            } else if all_methods.contains(&c) || BALANCE_BOLD.contains(&c.as_str()) {
+                Cell::from(c).style(Style::default().fg(theme.income))
+            } else if c.contains('↓') || c.starts_with(UTILIZATION_WARNING) {
+                Cell::from(c).style(Style::default().fg(theme.expense))
+            } else if let Some(color) = method_colors.get(get_label_method(&c)) {
+                Cell::from(c).style(
+                    Style::default()
+                        .fg(get_color(*color))
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if all_methods.contains(&get_label_method(&c).to_string())
+                || BALANCE_BOLD.contains(&c.as_str())
+            {
                 Cell::from(c).style(Style::default().add_modifier(Modifier::BOLD))
             } else {
                 Cell::from(c)
@@ -388,27 +669,43 @@ pub fn home_ui(
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().fg(TEXT))
+            .style(Style::default().fg(theme.text))
     });
 
     // use the acquired width data to allocated spaces
     // between columns on Balance widget.
     let balance_area = Table::new(bal_data, width_data.to_owned())
-        .block(styled_block("Balance"))
-        .style(Style::default().fg(BOX));
+        .block(styled_block(&balance_name, theme))
+        .style(Style::default().fg(theme.border));
 
     match current_tab {
         // previously added a black block to year and month widget if a value is not selected
         // Now we will turn that black block into green if a value is selected
         HomeTab::Months => {
-            month_tab = month_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            month_tab = month_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
 
         HomeTab::Years => {
-            year_tab = year_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            year_tab = year_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
+        HomeTab::Weeks => {
+            week_tab = week_tab.map(|tab| {
+                tab.highlight_style(
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .bg(theme.selected),
+                )
+            });
+        }
+        HomeTab::TagFilter | HomeTab::MethodFilter | HomeTab::TypeFilter => {}
         // changes the color of row based on Expense or Income tx type on Transaction widget.
         HomeTab::Table => {
             if let Some(a) = table.state.selected() {
@@ -418,16 +715,22 @@ pub fn home_ui(
                 } else if table.items[a][4] == "Income" {
                     table_area = table_area.highlight_style(selected_style_income);
                 } else if table.items[a][4] == "Transfer" {
-                    table_area = table_area.highlight_style(Style::default().bg(SELECTED));
+                    table_area = table_area.highlight_style(Style::default().bg(theme.selected));
                 }
             }
         }
     }
 
+    let table_state = if grouped || subtotals {
+        &mut grouped_state
+    } else {
+        &mut table.state
+    };
+
     // Always keep some items rendered on the upper side of the table
-    if let Some(index) = table.state.selected() {
+    if let Some(index) = table_state.selected() {
         if index > 10 {
-            *table.state.offset_mut() = index - 10;
+            *table_state.offset_mut() = index - 10;
         }
     }
 
@@ -437,6 +740,55 @@ pub fn home_ui(
     f.render_widget(month_tab, chunks[2]);
     f.render_widget(year_tab, chunks[1]);
 
+    if let Some(week_tab) = week_tab {
+        f.render_widget(week_tab, chunks[3]);
+    }
+
     // this one is different because the Transaction widget interface works differently
-    f.render_stateful_widget(table_area, chunks[3], &mut table.state);
+    if home_filter.shown {
+        let filter_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Percentage(35),
+                Constraint::Percentage(25),
+            ])
+            .split(chunks[4]);
+
+        let balance_status = if home_filter.filter_balance {
+            "Filtered Balance"
+        } else {
+            "Full Balance"
+        };
+
+        let filters = [
+            (&home_filter.tags, "Tag", HomeTab::TagFilter),
+            (&home_filter.tx_methods, "TX Method", HomeTab::MethodFilter),
+            (&home_filter.tx_types, "Type", HomeTab::TypeFilter),
+        ];
+
+        for (index, (data, name, tab)) in filters.into_iter().enumerate() {
+            let title = if index == 0 {
+                format!("{name} | {balance_status}")
+            } else {
+                name.to_string()
+            };
+
+            let mut text_style = Style::default().fg(theme.text);
+            if current_tab == &tab {
+                text_style = text_style.add_modifier(Modifier::BOLD).bg(theme.selected);
+            }
+
+            let filter_text = Paragraph::new(Line::from(Span::styled(
+                format!("◀ {} ▶", data.titles[data.index]),
+                text_style,
+            )))
+            .block(styled_block(&title, theme))
+            .style(Style::default().fg(theme.border));
+
+            f.render_widget(filter_text, filter_chunks[index]);
+        }
+    }
+
+    f.render_stateful_widget(table_area, chunks[5], table_state);
 }
@@ -1,5 +1,7 @@
-mod home_data;
 mod home_ui;
+mod page;
 
-pub use home_data::TransactionData;
-pub use home_ui::{home_ui, BALANCE_BOLD};
+pub use home_ui::{get_label_method, home_ui, BALANCE_BOLD};
+pub use page::HomePage;
+pub use rex_core::home::*;
+pub use rex_core::utility::{OWED_SUFFIX, UTILIZATION_WARNING};
@@ -0,0 +1,92 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::home_page::home_ui;
+use crate::key_checker::{home_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+
+pub struct HomePage;
+
+impl Page for HomePage {
+    fn name(&self) -> &str {
+        "Home"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, context: &PageContext) {
+        let AppState {
+            home,
+            balance,
+            methods,
+            method_colors,
+            theme,
+            ..
+        } = state;
+        let conn = context.conn;
+
+        let last_balance = home.tx_data.get_last_balance(conn);
+
+        home_ui(
+            f,
+            context.to_reset,
+            &home.months,
+            &home.years,
+            home.weekly.then_some(&home.weeks),
+            home.range,
+            &mut home.table,
+            &mut balance.data,
+            &home.tab,
+            &home.sort,
+            &home.sort_direction,
+            home.grouped,
+            home.subtotals,
+            &home.collapsed_days,
+            &home.filter,
+            home.tx_data.get_total_txs(),
+            &home.tx_data.get_tx_positions(&home.marked_txs),
+            &home.tx_data.get_future_positions(),
+            home.balance_column,
+            &home
+                .balance_column
+                .map_or_else(Vec::new, |column| home.tx_data.get_running_balances(column)),
+            &home.tx_data.get_statuses(),
+            &home.tx_data.get_id_nums(),
+            &home.columns,
+            home.cleared_balance,
+            methods.get_spendable_balance(&last_balance),
+            &methods.get_group_balances(&last_balance),
+            context.profile,
+            context.read_only,
+            home.tx_data
+                .get_projected_balance(conn)
+                .and_then(|balance| balance.last().cloned())
+                .as_deref(),
+            &mut balance.widths,
+            &mut balance.balance_load,
+            &mut balance.ongoing_balance,
+            &mut balance.last_balance,
+            &mut balance.changes_load,
+            &mut balance.ongoing_changes,
+            &mut balance.last_changes,
+            &mut balance.income_load,
+            &mut balance.ongoing_income,
+            &mut balance.last_income,
+            &mut balance.expense_load,
+            &mut balance.ongoing_expense,
+            &mut balance.last_expense,
+            &mut balance.daily_income_load,
+            &mut balance.daily_ongoing_income,
+            &mut balance.daily_last_income,
+            &mut balance.daily_expense_load,
+            &mut balance.daily_ongoing_expense,
+            &mut balance.daily_last_expense,
+            &mut balance.load_percentage,
+            method_colors,
+            &methods.all,
+            theme,
+        );
+    }
+
+    fn get_action(&self, key: KeyEvent, state: &AppState) -> Option<Action> {
+        home_keys(key, state)
+    }
+}
@@ -1,5 +1,7 @@
 mod initial_ui;
+mod page;
 mod version_checker;
 
 pub use initial_ui::initial_ui;
+pub use page::InitialPage;
 pub use version_checker::check_version;
@@ -0,0 +1,22 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::initial_page::initial_ui;
+use crate::key_checker::{initial_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+
+pub struct InitialPage;
+
+impl Page for InitialPage {
+    fn name(&self) -> &str {
+        "Initial"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, _context: &PageContext) {
+        initial_ui(f, state.starter_index, &state.theme);
+    }
+
+    fn get_action(&self, key: KeyEvent, _state: &AppState) -> Option<Action> {
+        initial_keys(key)
+    }
+}
@@ -0,0 +1,202 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::key_checker::popup_keys;
+use crate::page_handler::{AppState, Pages, PopupState};
+
+/// Everything a key press can do. Keys are turned into an action first using only the
+/// current state, then `InputKeyHandler::reduce` applies it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Exits the interface
+    Quit,
+    /// Leaves the interface to add a new tx method from the terminal
+    TakeUserInput,
+    /// Leaves the interface to write the Add Tx note in the external editor
+    EditTxNote,
+    GoHome,
+    GoAddTx,
+    GoChart,
+    GoSummary,
+    GoSearch,
+    GoActivity,
+    GoTrash,
+    /// Opens the Reports page or runs the selected report again
+    GoReports,
+    /// Opens the extension page of the index
+    GoPage(usize),
+    Left,
+    Right,
+    Up,
+    Down,
+    HelpPopup,
+    DeletionPopup,
+    NewTxMethodPopup,
+    RenameTxMethodPopup,
+    ArchiveTxMethodPopup,
+    TxMethodTypePopup,
+    TxMethodCurrencyPopup,
+    StartingBalancePopup,
+    MergeTxMethodsPopup,
+    TxMethodColorPopup,
+    CreditLimitPopup,
+    TxMethodGroupPopup,
+    DeleteTxMethodPopup,
+    TagRulesPopup,
+    SwitchProfilePopup,
+    IntegrityCheckPopup,
+    BalanceAuditPopup,
+    MaintenancePopup,
+    JumpDatePopup,
+    RetagPopup,
+    DateRangePopup,
+    QuickAddPopup,
+    AttachFilePopup,
+    ScheduledTxsPopup,
+    SplitTxPopup,
+    TransferFeePopup,
+    ExchangeRatePopup,
+    TxNotePopup,
+    TrashPurgePopup,
+    EmptyTrashPopup,
+    EditHomeTx,
+    MoveTxUp,
+    MoveTxDown,
+    ShowHomeTxDetails,
+    ChangeHomeSort,
+    HomeFilter,
+    HomeFilterBalance,
+    HomeTextFilter,
+    /// A key typed to the Home text filter
+    HomeFilterInput,
+    SwitchTxMark,
+    ChangeBalanceColumn,
+    ToggleHomeWeekly,
+    ToggleGroupDays,
+    SwitchDayCollapse,
+    ChangeTxStatus,
+    ToggleClearedBalance,
+    ToggleSubtotals,
+    OpenAttachments,
+    AddTx,
+    ScheduleTx,
+    ClearInput,
+    RepeatLastTx,
+    SwapTxMethods,
+    ToggleBatch,
+    SelectDateField,
+    /// Selects the input field of the pressed number
+    SelectField,
+    Autofill,
+    UndoFieldEdit,
+    ClearField,
+    /// A key typed to the selected input field
+    FieldInput,
+    ToggleChartHiddenMode,
+    ChangeChartView,
+    ToggleChartLogScale,
+    ExportChart,
+    ChangeChartAnimationSpeed,
+    ToggleChartWeekly,
+    ChangeChartMethodGroup,
+    SwitchChartLine,
+    ToggleSummaryHiddenMode,
+    ChangeSummarySort,
+    ToggleSummaryComparison,
+    ToggleSummaryLargestTxs,
+    ToggleSummaryTransfers,
+    ShowTagStats,
+    SearchTag,
+    SearchTx,
+    ChangeSearchDateType,
+    EditSearchTx,
+    ShowActivityTxDetails,
+    RestoreTrashTx,
+    ScrollPopupUp,
+    ScrollPopupDown,
+    ClosePopup,
+    /// A key sent to the open popup. The popup handles the typing and its own choices
+    PopupInput,
+    /// A popup key that writes to the db like the rebuild of the integrity report
+    PopupWrite,
+    /// A key the current page handles by itself
+    PageInput,
+    /// A key the current page handles by itself that writes to the db
+    PageWrite,
+}
+
+impl Action {
+    /// Whether the action can be moved to another key. The input actions depend on the key
+    /// that was pressed so they keep their keys
+    #[must_use]
+    pub fn is_bindable(&self) -> bool {
+        !matches!(
+            self,
+            Action::GoPage(_)
+                | Action::SelectField
+                | Action::FieldInput
+                | Action::HomeFilterInput
+                | Action::ScrollPopupUp
+                | Action::ScrollPopupDown
+                | Action::ClosePopup
+                | Action::PopupInput
+                | Action::PopupWrite
+                | Action::PageInput
+                | Action::PageWrite
+        )
+    }
+
+    /// Whether the action changes the db. These are ignored in read only mode
+    #[must_use]
+    pub fn writes_db(&self) -> bool {
+        matches!(
+            self,
+            Action::TakeUserInput
+                | Action::NewTxMethodPopup
+                | Action::RenameTxMethodPopup
+                | Action::ArchiveTxMethodPopup
+                | Action::TxMethodTypePopup
+                | Action::TxMethodCurrencyPopup
+                | Action::StartingBalancePopup
+                | Action::MergeTxMethodsPopup
+                | Action::TxMethodColorPopup
+                | Action::CreditLimitPopup
+                | Action::TxMethodGroupPopup
+                | Action::DeleteTxMethodPopup
+                | Action::TagRulesPopup
+                | Action::EditHomeTx
+                | Action::DeletionPopup
+                | Action::MoveTxUp
+                | Action::MoveTxDown
+                | Action::RetagPopup
+                | Action::ChangeTxStatus
+                | Action::QuickAddPopup
+                | Action::AttachFilePopup
+                | Action::AddTx
+                | Action::ScheduleTx
+                | Action::EditSearchTx
+                | Action::TrashPurgePopup
+                | Action::EmptyTrashPopup
+                | Action::RestoreTrashTx
+                | Action::PopupWrite
+                | Action::PageWrite
+        )
+    }
+}
+
+/// Turns the key press into the action it does on the current page and popup. F1 and the
+/// following F keys open the extension pages from any page. None if the key does nothing
+#[must_use]
+pub fn get_action(key: KeyEvent, state: &AppState, pages: &Pages) -> Option<Action> {
+    if !matches!(state.popup, PopupState::Nothing) {
+        return popup_keys(key, state);
+    }
+
+    if let KeyCode::F(number) = key.code {
+        let index = usize::from(number).checked_sub(1)?;
+        if index < pages.total_extensions() {
+            return Some(Action::GoPage(index));
+        }
+    }
+
+    pages.get(&state.page).get_action(key, state)
+}
@@ -1,33 +1,24 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
 
-use crate::key_checker::InputKeyHandler;
-use crate::outputs::HandlingOutput;
-use crate::page_handler::PopupState;
+use crate::key_checker::Action;
 
-pub fn activity_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
-    match handler.popup {
-        PopupState::Nothing => match handler.key.code {
-            KeyCode::Char('q') => return Some(HandlingOutput::QuitUi),
-            KeyCode::Char('f') => handler.go_home(),
-            KeyCode::Char('a') => handler.go_add_tx(),
-            KeyCode::Char('r') => handler.go_chart(),
-            KeyCode::Char('h') => handler.do_help_popup(),
-            KeyCode::Char('z') => handler.go_summary(),
-            KeyCode::Char('w') => handler.go_search(),
-            KeyCode::Char('v') => handler.show_activity_tx_details(),
-            KeyCode::Right => handler.handle_right_arrow(),
-            KeyCode::Left => handler.handle_left_arrow(),
-            KeyCode::Up => handler.handle_up_arrow(),
-            KeyCode::Down => handler.handle_down_arrow(),
-            _ => {}
-        },
-        PopupState::ActivityHelp => match handler.key.code {
-            KeyCode::Up => handler.popup_scroll_up(),
-            KeyCode::Down => handler.popup_scroll_down(),
-            _ => handler.do_empty_popup(),
-        },
-        _ => handler.do_empty_popup(),
-    }
-
-    None
+/// Turns the keys of the Activity page into the action they do
+#[must_use]
+pub fn activity_keys(key: KeyEvent) -> Option<Action> {
+    let action = match key.code {
+        KeyCode::Char('q') => Action::Quit,
+        KeyCode::Char('f') => Action::GoHome,
+        KeyCode::Char('a') => Action::GoAddTx,
+        KeyCode::Char('r') => Action::GoChart,
+        KeyCode::Char('h') => Action::HelpPopup,
+        KeyCode::Char('z') => Action::GoSummary,
+        KeyCode::Char('w') => Action::GoSearch,
+        KeyCode::Char('v') => Action::ShowActivityTxDetails,
+        KeyCode::Right => Action::Right,
+        KeyCode::Left => Action::Left,
+        KeyCode::Up => Action::Up,
+        KeyCode::Down => Action::Down,
+        _ => return None,
+    };
+    Some(action)
 }
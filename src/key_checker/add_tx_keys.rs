@@ -1,57 +1,55 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::key_checker::InputKeyHandler;
-use crate::outputs::HandlingOutput;
-use crate::page_handler::{PopupState, TxTab};
+use crate::key_checker::Action;
+use crate::page_handler::TxTab;
 
-/// Tracks the keys of the Add Tx page and calls relevant function based on it
-#[cfg(not(tarpaulin_include))]
-pub fn add_tx_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
-    match handler.popup {
-        // we don't want to move this interface while the popup is on
-        PopupState::Nothing => match handler.add_tx_tab {
-            TxTab::Nothing => match handler.key.code {
-                KeyCode::Char('q') => return Some(HandlingOutput::QuitUi),
-                KeyCode::Char('f') => handler.go_home(),
-                KeyCode::Char('r') => handler.go_chart(),
-                KeyCode::Char('z') => handler.go_summary(),
-                KeyCode::Char('h') => handler.do_help_popup(),
-                KeyCode::Char('s') => handler.add_tx(),
-                KeyCode::Char('w') => handler.go_search(),
-                KeyCode::Char('c') => handler.clear_input(),
-                KeyCode::Char('y') => handler.go_activity(),
-                KeyCode::Enter => handler.select_date_field(),
-                KeyCode::Char(c) => {
-                    if c.is_numeric() {
-                        handler.handle_number_press();
-                    }
-                }
-                _ => {}
-            },
-            _ => match handler.key.code {
-                KeyCode::Right => handler.handle_right_arrow(),
-                KeyCode::Left => handler.handle_left_arrow(),
-                KeyCode::Up => handler.handle_up_arrow(),
-                KeyCode::Down => handler.handle_down_arrow(),
-                KeyCode::Tab => handler.do_autofill(),
-                _ => match handler.add_tx_tab {
-                    TxTab::Date => handler.handle_date(),
-                    TxTab::Details => handler.handle_details(),
-                    TxTab::FromMethod | TxTab::ToMethod => handler.handle_tx_method(),
-                    TxTab::Amount => handler.handle_amount(),
-                    TxTab::TxType => handler.handle_tx_type(),
-                    TxTab::Tags => handler.handle_tags(),
-                    TxTab::Nothing => {}
-                },
-            },
+/// Turns the keys of the Add Tx page into the action they do
+#[must_use]
+pub fn add_tx_keys(key: KeyEvent, tab: &TxTab) -> Option<Action> {
+    let action = match tab {
+        TxTab::Nothing => match key.code {
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Char('f') => Action::GoHome,
+            KeyCode::Char('r') => Action::GoChart,
+            KeyCode::Char('z') => Action::GoSummary,
+            KeyCode::Char('h') => Action::HelpPopup,
+            KeyCode::Char('s') => Action::AddTx,
+            KeyCode::Char('p') => Action::ScheduleTx,
+            KeyCode::Char('u') => Action::ScheduledTxsPopup,
+            KeyCode::Char('w') => Action::GoSearch,
+            KeyCode::Char('c') => Action::ClearInput,
+            KeyCode::Char('l') => Action::RepeatLastTx,
+            KeyCode::Char('t') => Action::SwapTxMethods,
+            KeyCode::Char('b') => Action::ToggleBatch,
+            KeyCode::Char('x') => Action::SplitTxPopup,
+            KeyCode::Char('e') => Action::TransferFeePopup,
+            KeyCode::Char('g') => Action::ExchangeRatePopup,
+            KeyCode::Char('o') => Action::TxNotePopup,
+            KeyCode::Char('n') => Action::EditTxNote,
+            KeyCode::Char('y') => Action::GoActivity,
+            KeyCode::Enter => Action::SelectDateField,
+            KeyCode::Char(c) if c.is_numeric() => Action::SelectField,
+            _ => return None,
         },
-        PopupState::AddTxHelp => match handler.key.code {
-            KeyCode::Up => handler.popup_scroll_up(),
-            KeyCode::Down => handler.popup_scroll_down(),
-            _ => handler.do_empty_popup(),
-        },
-        _ => handler.do_empty_popup(),
-    }
+        _ => field_keys(key),
+    };
+    Some(action)
+}
 
-    None
+/// Turns the keys pressed while an input field of the Add Tx or the Search page is selected
+/// into the action they do
+#[must_use]
+pub fn field_keys(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Right => Action::Right,
+        KeyCode::Left => Action::Left,
+        KeyCode::Up => Action::Up,
+        KeyCode::Down => Action::Down,
+        KeyCode::Tab => Action::Autofill,
+        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Action::UndoFieldEdit
+        }
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::ClearField,
+        _ => Action::FieldInput,
+    }
 }
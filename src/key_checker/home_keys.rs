@@ -1,43 +1,70 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
 
-use crate::key_checker::InputKeyHandler;
-use crate::outputs::HandlingOutput;
-use crate::page_handler::PopupState;
+use crate::key_checker::Action;
+use crate::page_handler::AppState;
 
-/// Tracks the keys of the Home page and calls relevant function based on it
-#[cfg(not(tarpaulin_include))]
-pub fn home_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
-    match handler.popup {
-        PopupState::Nothing => match handler.key.code {
-            KeyCode::Char('q') => return Some(HandlingOutput::QuitUi),
-            KeyCode::Char('a') => handler.go_add_tx(),
-            KeyCode::Char('r') => handler.go_chart(),
-            KeyCode::Char('j') => return Some(HandlingOutput::TakeUserInput),
-            KeyCode::Char('h') => handler.do_help_popup(),
-            KeyCode::Char('z') => handler.go_summary(),
-            KeyCode::Char('w') => handler.go_search(),
-            KeyCode::Char('e') => handler.home_edit_tx(),
-            KeyCode::Char('d') => handler.do_deletion_popup(),
-            KeyCode::Char('y') => handler.go_activity(),
-            KeyCode::Char(',') => handler.switch_tx_index_up(),
-            KeyCode::Char('.') => handler.switch_tx_index_down(),
-            KeyCode::Char('v') => handler.show_home_tx_details(),
-            KeyCode::Right => handler.handle_right_arrow(),
-            KeyCode::Left => handler.handle_left_arrow(),
-            KeyCode::Up => handler.handle_up_arrow(),
-            KeyCode::Down => handler.handle_down_arrow(),
-            _ => {}
-        },
-        PopupState::TxDeletion => match handler.key.code {
-            KeyCode::Left | KeyCode::Right | KeyCode::Enter => handler.handle_deletion_popup(),
-            _ => {}
-        },
-        PopupState::HomeHelp => match handler.key.code {
-            KeyCode::Up => handler.popup_scroll_up(),
-            KeyCode::Down => handler.popup_scroll_down(),
-            _ => handler.do_empty_popup(),
-        },
-        _ => handler.do_empty_popup(),
+/// Turns the keys of the Home page into the action they do
+#[must_use]
+pub fn home_keys(key: KeyEvent, state: &AppState) -> Option<Action> {
+    if state.home.filter.typing {
+        return Some(Action::HomeFilterInput);
     }
-    None
+
+    let action = match key.code {
+        KeyCode::Char('q') => Action::Quit,
+        KeyCode::Char('a') => Action::GoAddTx,
+        KeyCode::Char('r') => Action::GoChart,
+        KeyCode::Char('j') => Action::TakeUserInput,
+        KeyCode::Char('J') => Action::NewTxMethodPopup,
+        KeyCode::Char('R') => Action::RenameTxMethodPopup,
+        KeyCode::Char('H') => Action::ArchiveTxMethodPopup,
+        KeyCode::Char('T') => Action::TxMethodTypePopup,
+        KeyCode::Char('C') => Action::TxMethodCurrencyPopup,
+        KeyCode::Char('B') => Action::StartingBalancePopup,
+        KeyCode::Char('U') => Action::MergeTxMethodsPopup,
+        KeyCode::Char('K') => Action::TxMethodColorPopup,
+        KeyCode::Char('L') => Action::CreditLimitPopup,
+        KeyCode::Char('G') => Action::TxMethodGroupPopup,
+        KeyCode::Char('D') => Action::DeleteTxMethodPopup,
+        KeyCode::Char('A') => Action::TagRulesPopup,
+        KeyCode::Char('P') => Action::SwitchProfilePopup,
+        KeyCode::Char('I') => Action::IntegrityCheckPopup,
+        KeyCode::Char('V') => Action::BalanceAuditPopup,
+        KeyCode::Char('O') => Action::MaintenancePopup,
+        KeyCode::Char('X') => Action::GoTrash,
+        KeyCode::Char('Q') => Action::GoReports,
+        KeyCode::Char('h') => Action::HelpPopup,
+        KeyCode::Char('z') => Action::GoSummary,
+        KeyCode::Char('w') => Action::GoSearch,
+        KeyCode::Char('e') => Action::EditHomeTx,
+        KeyCode::Char('d') => Action::DeletionPopup,
+        KeyCode::Char('y') => Action::GoActivity,
+        KeyCode::Char(',') => Action::MoveTxUp,
+        KeyCode::Char('.') => Action::MoveTxDown,
+        KeyCode::Char('v') => Action::ShowHomeTxDetails,
+        KeyCode::Char('x') => Action::ChangeHomeSort,
+        KeyCode::Char('g') => Action::HomeFilter,
+        KeyCode::Char('b') => Action::HomeFilterBalance,
+        KeyCode::Char('t') => Action::JumpDatePopup,
+        KeyCode::Char(' ') => Action::SwitchTxMark,
+        KeyCode::Char('l') => Action::RetagPopup,
+        KeyCode::Char('n') => Action::ChangeBalanceColumn,
+        KeyCode::Char('k') => Action::ToggleHomeWeekly,
+        KeyCode::Char('o') => Action::DateRangePopup,
+        KeyCode::Char('/') => Action::HomeTextFilter,
+        KeyCode::Char('c') => Action::ToggleGroupDays,
+        KeyCode::Char('s') => Action::ChangeTxStatus,
+        KeyCode::Char('u') => Action::ToggleClearedBalance,
+        KeyCode::Char('i') => Action::QuickAddPopup,
+        KeyCode::Char('p') => Action::ToggleSubtotals,
+        KeyCode::Char('m') => Action::OpenAttachments,
+        KeyCode::Char('M') => Action::AttachFilePopup,
+        KeyCode::Enter => Action::SwitchDayCollapse,
+        KeyCode::Right => Action::Right,
+        KeyCode::Left => Action::Left,
+        KeyCode::Up => Action::Up,
+        KeyCode::Down => Action::Down,
+        _ => return None,
+    };
+    Some(action)
 }
@@ -1,28 +1,12 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
 
-use crate::key_checker::InputKeyHandler;
-use crate::outputs::HandlingOutput;
-use crate::page_handler::PopupState;
+use crate::key_checker::Action;
 
-/// Tracks the keys of the Initial page and calls relevant function based on it
-#[cfg(not(tarpaulin_include))]
-pub fn initial_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
-    match handler.popup {
-        PopupState::Nothing => match handler.key.code {
-            KeyCode::Char('q') => return Some(HandlingOutput::QuitUi),
-            _ => handler.go_home(),
-        },
-        PopupState::NewUpdate(_) => match handler.key.code {
-            KeyCode::Enter => {
-                if let Err(e) = handler.handle_update_popup() {
-                    return Some(e);
-                }
-            }
-            KeyCode::Up => handler.popup_scroll_up(),
-            KeyCode::Down => handler.popup_scroll_down(),
-            _ => handler.do_empty_popup(),
-        },
-        _ => handler.do_empty_popup(),
+/// Turns the keys of the Initial page into the action they do
+#[must_use]
+pub fn initial_keys(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('q') => Some(Action::Quit),
+        _ => Some(Action::GoHome),
     }
-    None
 }
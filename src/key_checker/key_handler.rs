@@ -1,21 +1,44 @@
+use chrono::{Datelike, Local, NaiveDate};
 use crossterm::event::{KeyCode, KeyEvent};
 use rusqlite::Connection;
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::mem;
+use std::ops::Range;
+use std::path::Path;
 
 use crate::activity_page::ActivityData;
-use crate::chart_page::ChartData;
-use crate::home_page::TransactionData;
+use crate::chart_page::{export_chart_svg, ChartData};
+use crate::db::{
+    add_tx_attachment, analyze_db, backup_db_now, check_db_integrity, delete_trashed_tx,
+    empty_trash, get_balance_divergence, get_db_stats, get_scheduled_txs, get_split_parts,
+    get_trashed_txs, get_tx_attachments, get_tx_foreign_amount, get_tx_method_groups, get_tx_note,
+    rebuild_derived_tables, reindex_db, set_tx_attachments, vacuum_db, MONTHS, YEARS,
+};
+use crate::events::{EventHook, RexEvent};
+use crate::home_page::{get_day_groups, TransactionData, TX_LOAD_DISTANCE};
+use crate::key_checker::Action;
 use crate::outputs::TxType;
-use crate::outputs::{HandlingOutput, TxUpdateError, VerifyingOutput};
+use crate::outputs::{
+    AttachmentError, HandlingOutput, JumpDateError, SteppingError, TxUpdateError, VerifyingOutput,
+};
 use crate::page_handler::{
-    ActivityTab, ActivityType, ChartTab, CurrentUi, DateType, DeletionStatus, HomeTab, IndexedData,
-    PopupState, SortingType, SummaryTab, TableData, TxTab,
+    ActivityTab, AppState, ChartTab, ChartView, CurrentUi, DateType, DbChangeAction, DbRequest,
+    DbWorker, DeletionStatus, HomeSortingType, HomeTab, IndexedData, Pages, PopupState,
+    SortingDirection, SummaryTab, TableData, TxTab,
 };
 use crate::summary_page::SummaryData;
-use crate::tx_handler::TxData;
+use crate::tx_handler::{
+    add_tag_to_txs, add_tx_method, adjust_starting_balance, change_credit_limit,
+    change_tx_method_color, change_tx_method_currency, change_tx_method_group,
+    change_tx_method_type, delete_tx_method, get_deletion_details, get_draft_path,
+    get_merge_details, get_split_details, get_tag_rules_text, merge_tx_methods, parse_quick_add,
+    post_scheduled_txs, remove_draft, remove_tag_from_txs, remove_tag_rule, rename_tx_method,
+    restore_tx, save_tag_rule, switch_tx_method_archive, trash_txs, TxData,
+};
 use crate::utility::{
-    add_new_activity, add_new_activity_tx, get_all_tx_methods, get_empty_changes, sort_table_data,
-    switch_tx_index,
+    get_active_log_dir, get_all_tags, get_all_tx_methods, get_attachment_path, get_empty_changes,
+    get_last_log_lines, get_month_weeks, hide_archived_columns, parse_date_range, parse_jump_date,
+    show_credit_as_owed, show_credit_utilization, show_method_currencies, switch_tx_index, Config,
 };
 
 /// Stores all the data that is required to handle
@@ -23,177 +46,350 @@ use crate::utility::{
 /// entire app
 pub struct InputKeyHandler<'a> {
     pub key: KeyEvent,
-    pub page: &'a mut CurrentUi,
-    balance_data: &'a mut Vec<Vec<String>>,
-    pub popup: &'a mut PopupState,
-    pub add_tx_tab: &'a mut TxTab,
-    chart_tab: &'a mut ChartTab,
-    summary_tab: &'a mut SummaryTab,
-    home_tab: &'a mut HomeTab,
-    add_tx_data: &'a mut TxData,
-    all_tx_data: &'a mut TransactionData,
-    chart_data: &'a mut ChartData,
-    summary_data: &'a mut SummaryData,
-    table: &'a mut TableData,
-    summary_table: &'a mut TableData,
-    home_months: &'a mut IndexedData,
-    home_years: &'a mut IndexedData,
-    chart_months: &'a mut IndexedData,
-    chart_years: &'a mut IndexedData,
-    chart_modes: &'a mut IndexedData,
-    chart_tx_methods: &'a mut IndexedData,
-    summary_months: &'a mut IndexedData,
-    summary_years: &'a mut IndexedData,
-    summary_modes: &'a mut IndexedData,
-    summary_sort: &'a mut SortingType,
-    search_data: &'a mut TxData,
-    search_date_type: &'a mut DateType,
-    pub search_tab: &'a mut TxTab,
-    search_table: &'a mut TableData,
-    search_txs: &'a mut TransactionData,
-    activity_months: &'a mut IndexedData,
-    activity_years: &'a mut IndexedData,
-    activity_tab: &'a mut ActivityTab,
-    activity_data: &'a mut ActivityData,
-    activity_table: &'a mut TableData,
+    /// The state of every page that the key press can change
+    pub state: &'a mut AppState,
+    config: &'a Config,
     total_tags: usize,
-    chart_index: &'a mut Option<f64>,
-    chart_hidden_mode: &'a mut bool,
-    summary_hidden_mode: &'a mut bool,
-    deletion_status: &'a mut DeletionStatus,
-    ongoing_balance: &'a mut Vec<String>,
-    ongoing_changes: &'a mut Vec<String>,
-    ongoing_income: &'a mut Vec<String>,
-    ongoing_expense: &'a mut Vec<String>,
-    daily_ongoing_income: &'a mut Vec<String>,
-    daily_ongoing_expense: &'a mut Vec<String>,
-    chart_activated_methods: &'a mut HashMap<String, bool>,
-    popup_scroll_position: &'a mut usize,
-    max_popup_scroll: &'a mut usize,
+    /// The name of the profile whose db is open
+    profile: &'a str,
+    /// Every profile that can be switched to
+    profiles: &'a [String],
+    /// The path of the open db file
+    db_path: &'a Path,
+    /// Whether the db was opened with --read-only. Keys that would change it are ignored
+    read_only: bool,
+    /// Loads the chart and the summary data on another thread. None if the db could not be
+    /// opened there so the data is loaded right away instead
+    worker: Option<&'a DbWorker>,
+    /// Every page of the interface. Used to open the extension pages and pass them their keys
+    pages: &'a mut Pages,
     conn: &'a mut Connection,
 }
 
 impl<'a> InputKeyHandler<'a> {
     #[cfg(not(tarpaulin_include))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         key: KeyEvent,
-        page: &'a mut CurrentUi,
-        balance_data: &'a mut Vec<Vec<String>>,
-        popup: &'a mut PopupState,
-        add_tx_tab: &'a mut TxTab,
-        chart_tab: &'a mut ChartTab,
-        summary_tab: &'a mut SummaryTab,
-        home_tab: &'a mut HomeTab,
-        add_tx_data: &'a mut TxData,
-        all_tx_data: &'a mut TransactionData,
-        chart_data: &'a mut ChartData,
-        summary_data: &'a mut SummaryData,
-        table: &'a mut TableData,
-        summary_table: &'a mut TableData,
-        home_months: &'a mut IndexedData,
-        home_years: &'a mut IndexedData,
-        chart_months: &'a mut IndexedData,
-        chart_years: &'a mut IndexedData,
-        chart_modes: &'a mut IndexedData,
-        chart_tx_methods: &'a mut IndexedData,
-        summary_months: &'a mut IndexedData,
-        summary_years: &'a mut IndexedData,
-        summary_modes: &'a mut IndexedData,
-        summary_sort: &'a mut SortingType,
-        search_data: &'a mut TxData,
-        search_date_type: &'a mut DateType,
-        search_tab: &'a mut TxTab,
-        search_table: &'a mut TableData,
-        search_txs: &'a mut TransactionData,
-        activity_months: &'a mut IndexedData,
-        activity_years: &'a mut IndexedData,
-        activity_tab: &'a mut ActivityTab,
-        activity_data: &'a mut ActivityData,
-        activity_table: &'a mut TableData,
-        chart_index: &'a mut Option<f64>,
-        chart_hidden_mode: &'a mut bool,
-        summary_hidden_mode: &'a mut bool,
-        deletion_status: &'a mut DeletionStatus,
-        ongoing_balance: &'a mut Vec<String>,
-        ongoing_changes: &'a mut Vec<String>,
-        ongoing_income: &'a mut Vec<String>,
-        ongoing_expense: &'a mut Vec<String>,
-        daily_ongoing_income: &'a mut Vec<String>,
-        daily_ongoing_expense: &'a mut Vec<String>,
-        chart_activated_methods: &'a mut HashMap<String, bool>,
-        popup_scroll_position: &'a mut usize,
-        max_popup_scroll: &'a mut usize,
+        state: &'a mut AppState,
+        config: &'a Config,
+        profile: &'a str,
+        profiles: &'a [String],
+        db_path: &'a Path,
+        read_only: bool,
+        worker: Option<&'a DbWorker>,
+        pages: &'a mut Pages,
         conn: &'a mut Connection,
     ) -> InputKeyHandler<'a> {
-        let total_tags = summary_data
-            .get_table_data(summary_modes, summary_months.index, summary_years.index)
+        let total_tags = state
+            .summary
+            .data
+            .get_table_data(
+                &state.summary.modes,
+                state.summary.months.index,
+                state.summary.years.index,
+                &state.summary.sort,
+                &state.summary.sort_direction,
+                &state.summary.expanded_tags,
+            )
             .len();
         InputKeyHandler {
             key,
-            page,
-            balance_data,
-            popup,
-            add_tx_tab,
-            chart_tab,
-            summary_tab,
-            home_tab,
-            add_tx_data,
-            all_tx_data,
-            chart_data,
-            summary_data,
-            table,
-            summary_table,
-            home_months,
-            home_years,
-            chart_months,
-            chart_years,
-            chart_modes,
-            chart_tx_methods,
-            summary_months,
-            summary_years,
-            summary_modes,
-            summary_sort,
-            search_data,
-            search_date_type,
-            search_tab,
-            search_table,
-            search_txs,
-            activity_months,
-            activity_years,
-            activity_tab,
-            activity_data,
-            activity_table,
+            state,
+            config,
             total_tags,
-            chart_index,
-            chart_hidden_mode,
-            summary_hidden_mode,
-            deletion_status,
-            ongoing_balance,
-            ongoing_changes,
-            ongoing_income,
-            ongoing_expense,
-            daily_ongoing_income,
-            daily_ongoing_expense,
-            chart_activated_methods,
-            popup_scroll_position,
-            max_popup_scroll,
+            profile,
+            profiles,
+            db_path,
+            read_only,
+            worker,
+            pages,
             conn,
         }
     }
 
+    /// Sends the event to the event hook of the profile. A failing hook does not stop the change
+    /// that was already saved
+    fn emit_event(&self, event: &RexEvent) {
+        EventHook::new(self.config, self.profile).emit(event).ok();
+    }
+
+    /// Applies the action of the key press to the state. Actions that change the db are
+    /// ignored in read only mode. Returns an output if it needs to be handled outside the UI
+    #[cfg(not(tarpaulin_include))]
+    pub fn reduce(&mut self, action: Action) -> Option<HandlingOutput> {
+        if self.read_only && action.writes_db() {
+            self.do_read_only_popup();
+            return None;
+        }
+
+        match action {
+            Action::Quit => return Some(HandlingOutput::QuitUi),
+            Action::TakeUserInput => return Some(HandlingOutput::TakeUserInput),
+            Action::EditTxNote => return Some(HandlingOutput::EditTxNote),
+            Action::GoHome => self.go_home(),
+            Action::GoAddTx => self.go_add_tx(),
+            Action::GoChart => self.go_chart(),
+            Action::GoSummary => self.go_summary(),
+            Action::GoSearch => self.go_search(),
+            Action::GoActivity => self.go_activity(),
+            Action::GoTrash => self.go_trash(),
+            Action::GoReports => self.go_reports(),
+            Action::GoPage(index) => self.go_page(index),
+            Action::Left => self.handle_left_arrow(),
+            Action::Right => self.handle_right_arrow(),
+            Action::Up => self.handle_up_arrow(),
+            Action::Down => self.handle_down_arrow(),
+            Action::HelpPopup => self.do_help_popup(),
+            Action::DeletionPopup => self.do_deletion_popup(),
+            Action::NewTxMethodPopup => self.do_new_tx_method_popup(),
+            Action::RenameTxMethodPopup => self.do_rename_tx_method_popup(),
+            Action::ArchiveTxMethodPopup => self.do_archive_tx_method_popup(),
+            Action::TxMethodTypePopup => self.do_tx_method_type_popup(),
+            Action::TxMethodCurrencyPopup => self.do_tx_method_currency_popup(),
+            Action::StartingBalancePopup => self.do_starting_balance_popup(),
+            Action::MergeTxMethodsPopup => self.do_merge_tx_methods_popup(),
+            Action::TxMethodColorPopup => self.do_tx_method_color_popup(),
+            Action::CreditLimitPopup => self.do_credit_limit_popup(),
+            Action::TxMethodGroupPopup => self.do_tx_method_group_popup(),
+            Action::DeleteTxMethodPopup => self.do_delete_tx_method_popup(),
+            Action::TagRulesPopup => self.do_tag_rules_popup(),
+            Action::SwitchProfilePopup => self.do_switch_profile_popup(),
+            Action::IntegrityCheckPopup => self.do_integrity_check_popup(),
+            Action::BalanceAuditPopup => self.do_balance_audit_popup(),
+            Action::MaintenancePopup => self.do_maintenance_popup(),
+            Action::JumpDatePopup => self.do_jump_date_popup(),
+            Action::RetagPopup => self.do_retag_popup(),
+            Action::DateRangePopup => self.do_date_range_popup(),
+            Action::QuickAddPopup => self.do_quick_add_popup(),
+            Action::AttachFilePopup => self.do_attach_file_popup(),
+            Action::ScheduledTxsPopup => self.do_scheduled_txs_popup(),
+            Action::SplitTxPopup => self.do_split_tx_popup(),
+            Action::TransferFeePopup => self.do_transfer_fee_popup(),
+            Action::ExchangeRatePopup => self.do_exchange_rate_popup(),
+            Action::TxNotePopup => self.do_tx_note_popup(),
+            Action::TrashPurgePopup => self.do_trash_purge_popup(),
+            Action::EmptyTrashPopup => self.do_empty_trash_popup(),
+            Action::EditHomeTx => self.home_edit_tx(),
+            Action::MoveTxUp => self.switch_tx_index_up(),
+            Action::MoveTxDown => self.switch_tx_index_down(),
+            Action::ShowHomeTxDetails => self.show_home_tx_details(),
+            Action::ChangeHomeSort => self.change_home_sort(),
+            Action::HomeFilter => self.do_home_filter(),
+            Action::HomeFilterBalance => self.do_home_filter_balance(),
+            Action::HomeTextFilter => self.do_home_text_filter(),
+            Action::HomeFilterInput => self.handle_home_text_filter(),
+            Action::SwitchTxMark => self.switch_home_tx_mark(),
+            Action::ChangeBalanceColumn => self.change_home_balance_column(),
+            Action::ToggleHomeWeekly => self.do_home_weekly(),
+            Action::ToggleGroupDays => self.do_home_group_days(),
+            Action::SwitchDayCollapse => self.switch_home_day_collapse(),
+            Action::ChangeTxStatus => self.change_home_tx_status(),
+            Action::ToggleClearedBalance => self.do_home_cleared_balance(),
+            Action::ToggleSubtotals => self.do_home_subtotals(),
+            Action::OpenAttachments => self.open_home_tx_attachments(),
+            Action::AddTx => self.add_tx(),
+            Action::ScheduleTx => self.schedule_tx(),
+            Action::ClearInput => self.clear_input(),
+            Action::RepeatLastTx => self.repeat_last_tx(),
+            Action::SwapTxMethods => self.swap_tx_methods(),
+            Action::ToggleBatch => self.toggle_add_tx_batch(),
+            Action::SelectDateField => self.select_date_field(),
+            Action::SelectField => self.handle_number_press(),
+            Action::Autofill => self.do_autofill(),
+            Action::UndoFieldEdit => self.undo_field_edit(),
+            Action::ClearField => self.clear_field(),
+            Action::FieldInput => self.handle_field_input(),
+            Action::ToggleChartHiddenMode => self.do_chart_hidden_mode(),
+            Action::ChangeChartView => self.change_chart_view(),
+            Action::ToggleChartLogScale => self.do_chart_log_scale(),
+            Action::ExportChart => self.export_chart(),
+            Action::ChangeChartAnimationSpeed => self.change_chart_animation_speed(),
+            Action::ToggleChartWeekly => self.do_chart_weekly(),
+            Action::ChangeChartMethodGroup => self.change_chart_method_group(),
+            Action::SwitchChartLine => self.switch_chart_tx_method_activation(),
+            Action::ToggleSummaryHiddenMode => self.do_summary_hidden_mode(),
+            Action::ChangeSummarySort => self.change_summary_sort(),
+            Action::ToggleSummaryComparison => self.do_summary_comparison(),
+            Action::ToggleSummaryLargestTxs => self.do_summary_largest_txs(),
+            Action::ToggleSummaryTransfers => self.do_summary_include_transfers(),
+            Action::ShowTagStats => self.show_summary_tag_stats(),
+            Action::SearchTag => self.search_tag(),
+            Action::SearchTx => self.search_tx(),
+            Action::ChangeSearchDateType => self.change_search_date_type(),
+            Action::EditSearchTx => self.search_edit_tx(),
+            Action::ShowActivityTxDetails => self.show_activity_tx_details(),
+            Action::RestoreTrashTx => self.restore_trash_tx(),
+            Action::ScrollPopupUp => self.popup_scroll_up(),
+            Action::ScrollPopupDown => self.popup_scroll_down(),
+            Action::ClosePopup => self.do_empty_popup(),
+            Action::PopupInput | Action::PopupWrite => return self.handle_popup_input(),
+            Action::PageInput | Action::PageWrite => return self.handle_page_input(),
+        }
+        None
+    }
+
+    /// Sends the key to the input field that is selected on the Add Tx or the Search page
+    #[cfg(not(tarpaulin_include))]
+    fn handle_field_input(&mut self) {
+        let tab = match self.state.page {
+            CurrentUi::AddTx => &self.state.add_tx.tab,
+            CurrentUi::Search => &self.state.search.tab,
+            _ => return,
+        };
+
+        match tab {
+            TxTab::Date => self.handle_date(),
+            TxTab::Details => self.handle_details(),
+            TxTab::FromMethod | TxTab::ToMethod => self.handle_tx_method(),
+            TxTab::Amount => self.handle_amount(),
+            TxTab::TxType => self.handle_tx_type(),
+            TxTab::Tags => self.handle_tags(),
+            TxTab::Nothing => {}
+        }
+    }
+
+    /// Sends the key to the current page for the keys it handles by itself
+    #[cfg(not(tarpaulin_include))]
+    fn handle_page_input(&mut self) -> Option<HandlingOutput> {
+        self.pages
+            .get_mut(&self.state.page)
+            .handle_key(self.key, self.state, self.conn)
+    }
+
+    /// Sends the key to the open popup. Popups that change the tx methods or switch the
+    /// profile return the output that has to be handled outside the UI
+    #[cfg(not(tarpaulin_include))]
+    fn handle_popup_input(&mut self) -> Option<HandlingOutput> {
+        let methods_changed = match self.state.popup {
+            PopupState::NewUpdate(_) => {
+                return self.handle_update_popup().err();
+            }
+            PopupState::DbChanged => return self.handle_db_changed_popup(),
+            PopupState::RecoverForm(_) => {
+                self.handle_recover_form_popup();
+                false
+            }
+            PopupState::SwitchProfile(_, _) => {
+                return self
+                    .handle_switch_profile_popup()
+                    .map(HandlingOutput::SwitchProfile);
+            }
+            PopupState::TxDeletion | PopupState::MarkedTxDeletion(_) => {
+                self.handle_deletion_popup();
+                false
+            }
+            PopupState::TrashPurge | PopupState::EmptyTrash => {
+                self.handle_trash_purge_popup();
+                false
+            }
+            PopupState::JumpToDate(_) => {
+                self.handle_jump_date_popup();
+                false
+            }
+            PopupState::MarkedTxRetag(_) => {
+                self.handle_retag_popup();
+                false
+            }
+            PopupState::DateRange(_) => {
+                self.handle_date_range_popup();
+                false
+            }
+            PopupState::QuickAdd(_) => {
+                self.handle_quick_add_popup();
+                false
+            }
+            PopupState::AttachFile(_) => {
+                self.handle_attach_file_popup();
+                false
+            }
+            PopupState::SplitTx(_) => {
+                self.handle_split_tx_popup();
+                false
+            }
+            PopupState::TransferFee(_) => {
+                self.handle_transfer_fee_popup();
+                false
+            }
+            PopupState::ExchangeRate(_) => {
+                self.handle_exchange_rate_popup();
+                false
+            }
+            PopupState::TxNote(_) => {
+                self.handle_tx_note_popup();
+                false
+            }
+            PopupState::StartingBalance(_) => {
+                self.handle_starting_balance_popup();
+                false
+            }
+            PopupState::MergeTxMethods(_) => {
+                self.handle_merge_tx_methods_popup();
+                false
+            }
+            PopupState::DeleteTxMethod(_) => {
+                self.handle_delete_tx_method_popup();
+                false
+            }
+            PopupState::DeleteMethodTarget(_, _, _) => {
+                self.handle_delete_method_target_popup();
+                false
+            }
+            PopupState::TagRules(_, _) => {
+                self.handle_tag_rules_popup();
+                false
+            }
+            PopupState::Maintenance(_) => {
+                self.handle_maintenance_popup();
+                false
+            }
+            PopupState::CreditLimit(_) => {
+                self.handle_credit_limit_popup();
+                false
+            }
+            PopupState::TxMethodGroup(_) => {
+                self.handle_tx_method_group_popup();
+                false
+            }
+            PopupState::NewTxMethod(_) => self.handle_new_tx_method_popup(),
+            PopupState::RenameTxMethod(_) => self.handle_rename_tx_method_popup(),
+            PopupState::ArchiveTxMethod(_) => self.handle_archive_tx_method_popup(),
+            PopupState::TxMethodType(_) => self.handle_tx_method_type_popup(),
+            PopupState::TxMethodCurrency(_) => self.handle_tx_method_currency_popup(),
+            PopupState::ConfirmMerge(_, _, _) => self.handle_confirm_merge_popup(),
+            PopupState::ConfirmMethodDeletion(_, _, _) => {
+                self.handle_confirm_method_deletion_popup()
+            }
+            PopupState::IntegrityReport(_) | PopupState::BalanceAudit(_) => {
+                self.handle_balance_report_popup()
+            }
+            PopupState::TxMethodColor(_) => self.handle_tx_method_color_popup(),
+            _ => {
+                self.do_empty_popup();
+                false
+            }
+        };
+
+        if methods_changed {
+            Some(HandlingOutput::TxMethodsChanged)
+        } else {
+            None
+        }
+    }
+
     /// Moves the interface to Home page and
     /// resets any selected widget/data from Add Tx or Transfer
     /// page to Nothing
     #[cfg(not(tarpaulin_include))]
     pub fn go_home_reset(&mut self) {
-        match self.page {
+        match self.state.page {
             CurrentUi::AddTx => {
-                *self.add_tx_data = TxData::new();
-                *self.add_tx_tab = TxTab::Nothing;
+                self.state.add_tx.data = self.config.get_default_tx(self.conn);
+                self.state.add_tx.tab = TxTab::Nothing;
             }
             CurrentUi::Search => {
-                *self.search_data = TxData::new_empty();
-                *self.search_tab = TxTab::Nothing;
+                self.state.search.data = TxData::new_empty();
+                self.state.search.tab = TxTab::Nothing;
             }
             _ => {}
         }
@@ -203,15 +399,17 @@ impl<'a> InputKeyHandler<'a> {
     /// Moves the interface to Home page
     #[cfg(not(tarpaulin_include))]
     pub fn go_home(&mut self) {
-        *self.page = CurrentUi::Home;
+        self.state.page = CurrentUi::Home;
         self.reload_home_balance_load();
     }
 
     /// Moves the interface to Add Tx page
     #[cfg(not(tarpaulin_include))]
     pub fn go_add_tx(&mut self) {
-        *self.page = CurrentUi::AddTx;
-        self.add_tx_data
+        self.state.page = CurrentUi::AddTx;
+        self.state
+            .add_tx
+            .data
             .add_tx_status("Info: Entering Normal Transaction mode.".to_string());
         self.reload_add_tx_balance_load();
     }
@@ -219,64 +417,98 @@ impl<'a> InputKeyHandler<'a> {
     /// Moves the interface to Search page
     #[cfg(not(tarpaulin_include))]
     pub fn go_search(&mut self) {
-        *self.page = CurrentUi::Search;
+        self.state.page = CurrentUi::Search;
     }
 
     /// Moves the interface to Summary page
     #[cfg(not(tarpaulin_include))]
     pub fn go_summary(&mut self) {
-        *self.page = CurrentUi::Summary;
-        self.summary_modes.set_index_zero();
-        self.summary_months.set_index_zero();
-        self.summary_years.set_index_zero();
-        *self.summary_tab = SummaryTab::ModeSelection;
-        *self.summary_hidden_mode = false;
+        self.state.page = CurrentUi::Summary;
+        self.state.summary.modes.set_index_zero();
+        self.state.summary.months.set_index_zero();
+        self.state.summary.years.set_index_zero();
+        self.state.summary.tx_methods.set_index_zero();
+        self.state.summary.data.set_tx_method(None);
+        self.state.summary.tab = SummaryTab::ModeSelection;
+        self.state.summary.hidden_mode = false;
+        self.state.summary.largest_txs = false;
         self.reload_summary();
     }
 
     /// Moves the interface to Chart page
     #[cfg(not(tarpaulin_include))]
     pub fn go_chart(&mut self) {
-        *self.page = CurrentUi::Chart;
-        self.chart_modes.set_index_zero();
-        self.chart_years.set_index_zero();
-        self.chart_months.set_index_zero();
-        *self.chart_tab = ChartTab::ModeSelection;
-        *self.chart_hidden_mode = false;
+        self.state.page = CurrentUi::Chart;
+        self.state.chart.modes.set_index_zero();
+        self.state.chart.years.set_index_zero();
+        self.state.chart.months.set_index_zero();
+        self.state.chart.tab = ChartTab::ModeSelection;
+        self.state.chart.hidden_mode = false;
         self.reload_chart_index();
     }
 
     #[cfg(not(tarpaulin_include))]
     pub fn go_activity(&mut self) {
-        *self.page = CurrentUi::Activity;
+        self.state.page = CurrentUi::Activity;
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_trash(&mut self) {
+        self.state.page = CurrentUi::Trash;
+        self.reload_trash_table();
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_reports(&mut self) {
+        self.state.page = CurrentUi::Reports;
+        self.reload_report_table();
+    }
+
+    /// Moves the interface to the extension page of the index
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_page(&mut self, index: usize) {
+        if index >= self.pages.total_extensions() {
+            return;
+        }
+
+        self.state.page = CurrentUi::Extension(index);
+        self.pages
+            .get_mut(&self.state.page)
+            .open(self.state, self.conn);
     }
 
     /// Turns on help popup
     #[cfg(not(tarpaulin_include))]
     pub fn do_help_popup(&mut self) {
-        match self.page {
-            CurrentUi::Home => *self.popup = PopupState::HomeHelp,
-            CurrentUi::AddTx => *self.popup = PopupState::AddTxHelp,
-            CurrentUi::Chart => *self.popup = PopupState::ChartHelp,
-            CurrentUi::Summary => *self.popup = PopupState::SummaryHelp,
-            CurrentUi::Search => *self.popup = PopupState::SearchHelp,
-            CurrentUi::Activity => *self.popup = PopupState::ActivityHelp,
-            CurrentUi::Initial => {}
+        match self.state.page {
+            CurrentUi::Home => self.state.popup = PopupState::HomeHelp,
+            CurrentUi::AddTx => self.state.popup = PopupState::AddTxHelp,
+            CurrentUi::Chart => self.state.popup = PopupState::ChartHelp,
+            CurrentUi::Summary => self.state.popup = PopupState::SummaryHelp,
+            CurrentUi::Search => self.state.popup = PopupState::SearchHelp,
+            CurrentUi::Activity => self.state.popup = PopupState::ActivityHelp,
+            CurrentUi::Trash => self.state.popup = PopupState::TrashHelp,
+            CurrentUi::Reports => self.state.popup = PopupState::ReportsHelp,
+            CurrentUi::Initial | CurrentUi::Extension(_) => {}
         }
     }
 
     /// Turns on deletion confirmation popup
     #[cfg(not(tarpaulin_include))]
     pub fn do_deletion_popup(&mut self) {
-        match self.page {
+        match self.state.page {
             CurrentUi::Home => {
-                if self.table.state.selected().is_some() {
-                    *self.popup = PopupState::TxDeletion;
+                // marked txs get deleted together instead of the selected one
+                if !self.state.home.marked_txs.is_empty() {
+                    self.state.popup =
+                        PopupState::MarkedTxDeletion(self.state.home.marked_txs.len());
+                } else if self.state.home.table.state.selected().is_some() {
+                    self.state.popup = PopupState::TxDeletion;
                 }
             }
             CurrentUi::Search => {
-                if self.search_table.state.selected().is_some() {
-                    *self.popup = PopupState::TxDeletion;
+                if self.state.search.table.state.selected().is_some() {
+                    self.state.popup = PopupState::TxDeletion;
                 }
             }
             _ => {}
@@ -286,28 +518,146 @@ impl<'a> InputKeyHandler<'a> {
     /// Removes popup status
     #[cfg(not(tarpaulin_include))]
     pub fn do_empty_popup(&mut self) {
-        *self.popup = PopupState::Nothing;
+        self.state.popup = PopupState::Nothing;
         self.reload_popup_scroll_position();
     }
 
     /// Hides chart top widgets
     #[cfg(not(tarpaulin_include))]
     pub fn do_chart_hidden_mode(&mut self) {
-        *self.chart_hidden_mode = !*self.chart_hidden_mode;
+        self.state.chart.hidden_mode = !self.state.chart.hidden_mode;
+    }
+
+    /// Cycles the chart between the balance, the cumulative income/expense and the tag view
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_chart_view(&mut self) {
+        self.state.chart.view = self.state.chart.view.next_view();
+        self.reload_chart_index();
+    }
+
+    /// Switches the chart y axis between linear and logarithmic scale
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_chart_log_scale(&mut self) {
+        self.state.chart.log_scale = !self.state.chart.log_scale;
+    }
+
+    /// Switches the chart between daily and weekly points
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_chart_weekly(&mut self) {
+        self.state.chart.weekly = !self.state.chart.weekly;
+        self.reload_chart_index();
+    }
+
+    /// Cycles the chart animation speed between 1x, 2x, 4x and no animation
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_chart_animation_speed(&mut self) {
+        self.state.chart.animation_speed = match self.state.chart.animation_speed {
+            0 => 1,
+            1 => 2,
+            2 | 3 => 4,
+            _ => 0,
+        };
+        self.reload_chart_index();
+    }
+
+    /// Saves the chart that is currently being shown as an SVG image
+    #[cfg(not(tarpaulin_include))]
+    pub fn export_chart(&mut self) {
+        let mut chart_lines = self.state.chart.data.get_chart_lines(
+            &self.state.chart.view,
+            &self.state.chart.modes,
+            self.state.chart.months.index,
+            self.state.chart.years.index,
+            &self.state.chart.tags,
+            &self.state.chart.activated_methods,
+            &self.state.chart.activated_tags,
+            &self.state.method_colors,
+        );
+
+        if self.state.chart.weekly {
+            chart_lines = chart_lines.into_weekly();
+        }
+
+        let period = match self.state.chart.modes.index {
+            0 => format!(
+                "{} {}",
+                MONTHS[self.state.chart.months.index], YEARS[self.state.chart.years.index]
+            ),
+            1 => YEARS[self.state.chart.years.index].to_string(),
+            _ => "All Time".to_string(),
+        };
+        let title = match self.state.chart.view {
+            ChartView::Balance => format!("Balance - {period}"),
+            ChartView::Cumulative => format!("Cumulative Income and Expense - {period}"),
+            ChartView::Tags => format!("Tag Spending - {period}"),
+        };
+
+        let mut export_path = dirs::picture_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        export_path.push(format!(
+            "rex_chart_{}.svg",
+            Local::now().format("%Y-%m-%d_%H-%M-%S")
+        ));
+
+        let status = match export_chart_svg(
+            &chart_lines,
+            self.state.chart.log_scale,
+            &title,
+            &export_path,
+            &self.state.theme,
+        ) {
+            Ok(()) => format!("Chart saved at {}", export_path.display()),
+            Err(e) => format!("Failed to save the chart. Error: {e}"),
+        };
+
+        self.state.popup = PopupState::ChartExported(status);
+    }
+
+    /// Switches the summary table between the tags and the largest transactions of the period
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_summary_largest_txs(&mut self) {
+        self.state.summary.largest_txs = !self.state.summary.largest_txs;
+        self.reload_summary();
+    }
+
+    /// Makes the summary only show the selected tx method of the tx method tab
+    #[cfg(not(tarpaulin_include))]
+    fn select_summary_tx_method(&mut self) {
+        let tx_method = self.state.summary.selected_tx_method();
+        self.state.summary.data.set_tx_method(tx_method);
+        self.reload_summary();
+    }
+
+    /// Switches whether transfers are counted on the summary and recreates the table
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_summary_include_transfers(&mut self) {
+        self.state.summary.include_transfers = !self.state.summary.include_transfers;
+        self.state
+            .summary
+            .data
+            .set_include_transfers(self.state.summary.include_transfers);
+        self.reload_summary_table();
+    }
+
+    /// Shows or hides the comparison columns of the summary table
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_summary_comparison(&mut self) {
+        self.state.summary.comparison = !self.state.summary.comparison;
     }
 
     /// Hides summary top widgets
     #[cfg(not(tarpaulin_include))]
     pub fn do_summary_hidden_mode(&mut self) {
-        *self.summary_hidden_mode = !*self.summary_hidden_mode;
+        self.state.summary.hidden_mode = !self.state.summary.hidden_mode;
 
-        if *self.summary_hidden_mode {
-            *self.summary_tab = SummaryTab::Table;
+        if self.state.summary.hidden_mode {
+            self.state.summary.tab = SummaryTab::Table;
             if self.total_tags > 0 {
-                self.summary_table.state.select(Some(0));
+                self.state.summary.table.state.select(Some(0));
             }
         } else {
-            *self.summary_tab = SummaryTab::ModeSelection;
+            self.state.summary.tab = SummaryTab::ModeSelection;
         }
     }
 
@@ -318,35 +668,91 @@ impl<'a> InputKeyHandler<'a> {
             // If there is a new version, Enter will try to open the default browser with this link
             open::that("https://github.com/WaffleMixer/Rex/releases/latest")
                 .map_err(|_| HandlingOutput::PrintNewUpdate)?;
-            *self.popup = PopupState::Nothing;
+            self.state.popup = PopupState::Nothing;
             Ok(())
         } else {
-            *self.popup = PopupState::Nothing;
+            self.state.popup = PopupState::Nothing;
             Ok(())
         }
     }
 
+    /// Turns on the popup explaining that nothing can be changed in read only mode
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_read_only_popup(&mut self) {
+        self.state.popup = PopupState::ReadOnly;
+    }
+
+    /// Handles the popup that shows up after the db file was changed by another app.
+    /// Every choice needs the db to be opened again so it is handled outside the UI
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_db_changed_popup(&mut self) -> Option<HandlingOutput> {
+        let action = match self.key.code {
+            KeyCode::Char('q') => return Some(HandlingOutput::QuitUi),
+            KeyCode::Char('r') => DbChangeAction::Reload,
+            // the read only db file must not be replaced
+            KeyCode::Char('k') if !self.read_only => DbChangeAction::KeepCurrent,
+            KeyCode::Char('c') => DbChangeAction::KeepBoth,
+            _ => return None,
+        };
+
+        self.state.popup = PopupState::Nothing;
+        Some(HandlingOutput::DbChanged(action))
+    }
+
+    /// Handles the key presses of the popup that offers the unsent Add Tx form of the last run.
+    /// Resuming opens the Add Tx page with the form filled again
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_recover_form_popup(&mut self) {
+        match self.key.code {
+            KeyCode::Char('r') | KeyCode::Enter => {
+                let PopupState::RecoverForm(draft) =
+                    mem::replace(&mut self.state.popup, PopupState::Nothing)
+                else {
+                    return;
+                };
+
+                self.state.add_tx.data = TxData::from_draft(*draft);
+                self.state.add_tx.tab = TxTab::Nothing;
+                self.go_add_tx();
+                self.state
+                    .add_tx
+                    .data
+                    .add_tx_status("Info: The unsent transaction was restored.".to_string());
+            }
+            KeyCode::Char('d') => {
+                remove_draft(&get_draft_path(self.db_path));
+                self.state.popup = PopupState::Nothing;
+            }
+            _ => {}
+        }
+    }
+
     #[cfg(not(tarpaulin_include))]
     pub fn search_tx(&mut self) {
-        if self.search_data.check_all_empty() {
-            self.search_data
+        if self.state.search.data.check_all_empty() {
+            self.state
+                .search
+                .data
                 .add_tx_status("Search: All fields cannot be empty".to_string());
         } else {
             let search_txs = self
-                .search_data
-                .get_search_tx(self.search_date_type, self.conn);
+                .state
+                .search
+                .data
+                .get_search_tx(&self.state.search.date_type, self.conn);
 
             if search_txs.0.is_empty() {
-                self.search_data.add_tx_status(
+                self.state.search.data.add_tx_status(
                     "Search: No transactions found with the provided input".to_string(),
                 );
             } else {
-                *self.search_txs = TransactionData::new_search(search_txs.0.clone(), search_txs.1);
-                *self.search_table = TableData::new(search_txs.0);
-                self.search_table.state.select(Some(0));
-                self.search_data.add_tx_status(format!(
+                self.state.search.txs =
+                    TransactionData::new_search(search_txs.0.clone(), search_txs.1);
+                self.state.search.table = TableData::new(search_txs.0);
+                self.state.search.table.state.select(Some(0));
+                self.state.search.data.add_tx_status(format!(
                     "Search: Found {} Transactions",
-                    self.search_table.items.len()
+                    self.state.search.table.items.len()
                 ));
             }
             self.reload_activity_table();
@@ -356,36 +762,150 @@ impl<'a> InputKeyHandler<'a> {
     /// Adds new tx and reloads home and chart data
     #[cfg(not(tarpaulin_include))]
     pub fn add_tx(&mut self) {
-        let status = self.add_tx_data.add_tx(self.conn);
+        let added_tx = self.state.add_tx.data.repeated();
+        let is_editing = self.state.add_tx.data.is_editing();
+
+        let tag_status = self.state.add_tx.data.check_tag_constraints(self.config);
+        if let VerifyingOutput::NotAccepted(_) = tag_status {
+            self.state.add_tx.data.add_tx_status(tag_status.to_string());
+            return;
+        }
+
+        let status = self.state.add_tx.data.add_tx(self.conn);
 
         match status {
-            Ok(()) => {
-                self.go_home_reset();
+            Ok(id_nums) => {
+                self.state.add_tx.last_added_tx = Some(added_tx);
+                // a transfer with a fee and a split tx add more than one tx
+                for id_num in id_nums {
+                    self.emit_event(&RexEvent::tx_added(id_num, self.conn));
+                }
+
+                // batch entry stays on the page with the next entry ready on the details field.
+                // Edited txs still go back to the Home page
+                let in_batch =
+                    if let (Some(total), false) = (self.state.add_tx.batch.as_mut(), is_editing) {
+                        *total += 1;
+                        let status = format!("Batch: Transaction {total} added.");
+                        self.state.add_tx.data = self.state.add_tx.data.batch_next();
+                        self.state.add_tx.data.add_tx_status(status);
+                        self.state.add_tx.tab = TxTab::Details;
+                        self.go_correct_index();
+                        true
+                    } else {
+                        self.go_home_reset();
+                        false
+                    };
                 // we just added a new tx, select the month tab again + reload the data of balance and table widgets to get updated data
-                *self.home_tab = HomeTab::Months;
+                self.state.home.tab = HomeTab::Months;
                 self.reload_home_table();
                 self.reload_chart_data();
                 self.reload_summary_data();
+                self.reload_all_tags();
                 self.reset_search_data();
                 self.reload_activity_table();
+
+                // the home reload replaced the shared balance rows with the Home ones
+                if in_batch {
+                    self.reload_add_tx_balance_data();
+                }
+            }
+            Err(e) => self.state.add_tx.data.add_tx_status(e),
+        }
+    }
+
+    /// Adds the scheduled txs whose date has arrived since the app was started and reloads the
+    /// pages that show them
+    #[cfg(not(tarpaulin_include))]
+    pub fn post_due_scheduled_txs(&mut self, today: NaiveDate) {
+        let posted = match post_scheduled_txs(today, self.conn) {
+            Ok(posted) => posted,
+            Err(e) => {
+                self.state.queue_popup(PopupState::ScheduledTxs(format!(
+                    "Failed to add the scheduled transactions. Error: {e}"
+                )));
+                return;
+            }
+        };
+
+        if posted.is_empty() {
+            return;
+        }
+
+        for id_num in &posted {
+            self.emit_event(&RexEvent::tx_added(*id_num, self.conn));
+        }
+
+        self.reload_home_table();
+        self.reload_chart_data();
+        self.reload_summary_data();
+        self.reload_all_tags();
+        self.reload_activity_table();
+
+        // the home reload replaced the shared balance rows with the Home ones
+        if matches!(self.state.page, CurrentUi::AddTx) {
+            self.reload_add_tx_balance_data();
+        }
+
+        self.state.queue_popup(PopupState::ScheduledTxs(format!(
+            "Added {} scheduled transaction(s) as their date has arrived.",
+            posted.len()
+        )));
+    }
+
+    /// Saves the Add Tx tx to be added once its date arrives
+    #[cfg(not(tarpaulin_include))]
+    pub fn schedule_tx(&mut self) {
+        let date = self.state.add_tx.data.get_all_texts()[0].to_string();
+
+        match self.state.add_tx.data.schedule_tx(self.conn) {
+            Ok(()) => {
+                self.state.add_tx.data = self.config.get_default_tx(self.conn);
+                self.state.add_tx.data.add_tx_status(format!(
+                    "Schedule: Transaction scheduled. It will be added on {date}."
+                ));
+                self.reload_activity_table();
             }
-            Err(e) => self.add_tx_data.add_tx_status(e),
+            Err(e) => self.state.add_tx.data.add_tx_status(e),
         }
     }
 
+    /// Opens a popup that lists every scheduled tx
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_scheduled_txs_popup(&mut self) {
+        let scheduled_txs = get_scheduled_txs(self.conn);
+
+        let text = if scheduled_txs.is_empty() {
+            "No transaction is scheduled. Fill the fields with a date after today and press P to schedule one".to_string()
+        } else {
+            scheduled_txs
+                .iter()
+                .map(|tx| {
+                    format!(
+                        "{} | {} | {} | {} {} | {}",
+                        tx[0], tx[1], tx[2], tx[4], tx[3], tx[5]
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+
+        self.state.popup = PopupState::ScheduledTxs(text);
+    }
+
     /// Based on transaction Selected, opens Add Tx page and
     /// allocates the data of the tx to the input boxes
     #[cfg(not(tarpaulin_include))]
     pub fn home_edit_tx(&mut self) {
-        if let Some(a) = self.table.state.selected() {
-            let target_data = self.all_tx_data.get_tx(a);
-            let target_id_num = self.all_tx_data.get_id_num(a);
+        if let Some(a) = self.state.home.table.state.selected() {
+            let target_data = self.state.home.tx_data.get_tx(a);
+            let target_id_num = self.state.home.tx_data.get_id_num(a);
             let tx_type = &target_data[4];
 
             // based on what kind of transaction is selected, passes the tx data to the struct
             // and change the current interface
             if tx_type != "Transfer" {
-                *self.add_tx_data = TxData::custom(
+                self.state.add_tx.data = TxData::custom(
                     &target_data[0],
                     &target_data[1],
                     &target_data[2],
@@ -395,13 +915,13 @@ impl<'a> InputKeyHandler<'a> {
                     &target_data[5],
                     target_id_num,
                 );
-                *self.page = CurrentUi::AddTx;
+                self.state.page = CurrentUi::AddTx;
             } else {
                 let splitted_method = target_data[2].split(" to ").collect::<Vec<&str>>();
                 let from_method = splitted_method[0];
                 let to_method = splitted_method[1];
 
-                *self.add_tx_data = TxData::custom(
+                self.state.add_tx.data = TxData::custom(
                     &target_data[0],
                     &target_data[1],
                     from_method,
@@ -411,9 +931,18 @@ impl<'a> InputKeyHandler<'a> {
                     &target_data[5],
                     target_id_num,
                 );
-                *self.page = CurrentUi::AddTx;
+                self.state.page = CurrentUi::AddTx;
             }
-            self.add_tx_data.add_tx_status(
+            self.state
+                .add_tx
+                .data
+                .set_note(get_tx_note(target_id_num, self.conn));
+            self.state
+                .add_tx
+                .data
+                .set_foreign_amount(get_tx_foreign_amount(target_id_num, self.conn));
+            self.state.add_tx.data.load_exchange_rate(self.conn);
+            self.state.add_tx.data.add_tx_status(
                 "Info: Entering Transaction edit mode. Press C to reset.".to_string(),
             );
             self.reload_add_tx_balance_load();
@@ -423,420 +952,2284 @@ impl<'a> InputKeyHandler<'a> {
     /// Deletes the selected transaction and reloads pages
     #[cfg(not(tarpaulin_include))]
     pub fn home_delete_tx(&mut self) {
-        if let Some(index) = self.table.state.selected() {
-            let mut tx_data = self.all_tx_data.get_tx(index).to_owned();
-            let id_num = self.all_tx_data.get_id_num(index);
-            tx_data.push(id_num.to_string());
-
-            let status = self.all_tx_data.del_tx(index, self.conn);
+        if let Some(index) = self.state.home.table.state.selected() {
+            let id_num = self.state.home.tx_data.get_id_num(index);
+            let status = self.state.home.tx_data.del_tx(index, self.conn);
             match status {
                 Ok(()) => {
+                    self.emit_event(&RexEvent::TxDeleted {
+                        id_nums: vec![id_num],
+                    });
+
                     // transaction deleted so reload the data again
                     self.reload_home_table();
                     self.reload_chart_data();
                     self.reload_summary_data();
+                    self.reload_all_tags();
                     self.reset_search_data();
                     self.reload_activity_table();
 
                     if index == 0 {
-                        self.table.state.select(None);
-                        *self.home_tab = HomeTab::Months;
+                        self.state.home.table.state.select(None);
+                        self.state.home.tab = HomeTab::Months;
                     } else {
-                        self.table.state.select(Some(index - 1));
+                        self.state.home.table.state.select(Some(index - 1));
                     }
-
-                    let activity_num =
-                        add_new_activity(ActivityType::DeleteTX(Some(id_num)), self.conn);
-                    add_new_activity_tx(&tx_data, activity_num, self.conn);
                 }
                 Err(err) => {
-                    *self.popup =
+                    self.state.popup =
                         PopupState::DeleteFailed(TxUpdateError::FailedDeleteTx(err).to_string());
                 }
             }
         }
     }
 
-    /// Handles all number key presses and selects relevant input field
+    /// Marks or unmarks the selected tx on the Home table for deleting them together
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_number_press(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => match self.add_tx_data.get_tx_type() {
-                TxType::IncomeExpense => match self.key.code {
-                    KeyCode::Char('1') => *self.add_tx_tab = TxTab::Date,
-                    KeyCode::Char('2') => *self.add_tx_tab = TxTab::Details,
-                    KeyCode::Char('3') => *self.add_tx_tab = TxTab::TxType,
-                    KeyCode::Char('4') => *self.add_tx_tab = TxTab::FromMethod,
-                    KeyCode::Char('5') => *self.add_tx_tab = TxTab::Amount,
-                    KeyCode::Char('6') => *self.add_tx_tab = TxTab::Tags,
-                    _ => {}
-                },
-                TxType::Transfer => match self.key.code {
-                    KeyCode::Char('1') => *self.add_tx_tab = TxTab::Date,
-                    KeyCode::Char('2') => *self.add_tx_tab = TxTab::Details,
-                    KeyCode::Char('3') => *self.add_tx_tab = TxTab::TxType,
-                    KeyCode::Char('4') => *self.add_tx_tab = TxTab::FromMethod,
-                    KeyCode::Char('5') => *self.add_tx_tab = TxTab::ToMethod,
-                    KeyCode::Char('6') => *self.add_tx_tab = TxTab::Amount,
-                    KeyCode::Char('7') => *self.add_tx_tab = TxTab::Tags,
-                    _ => {}
-                },
-            },
-            CurrentUi::Search => match self.search_data.get_tx_type() {
-                TxType::IncomeExpense => match self.key.code {
-                    KeyCode::Char('1') => *self.search_tab = TxTab::Date,
-                    KeyCode::Char('2') => *self.search_tab = TxTab::Details,
-                    KeyCode::Char('3') => *self.search_tab = TxTab::TxType,
-                    KeyCode::Char('4') => *self.search_tab = TxTab::FromMethod,
-                    KeyCode::Char('5') => *self.search_tab = TxTab::Amount,
-                    KeyCode::Char('6') => *self.search_tab = TxTab::Tags,
-                    _ => {}
-                },
-                TxType::Transfer => match self.key.code {
-                    KeyCode::Char('1') => *self.search_tab = TxTab::Date,
-                    KeyCode::Char('2') => *self.search_tab = TxTab::Details,
-                    KeyCode::Char('3') => *self.search_tab = TxTab::TxType,
-                    KeyCode::Char('4') => *self.search_tab = TxTab::FromMethod,
-                    KeyCode::Char('5') => *self.search_tab = TxTab::ToMethod,
-                    KeyCode::Char('6') => *self.search_tab = TxTab::Amount,
-                    KeyCode::Char('7') => *self.search_tab = TxTab::Tags,
-                    _ => {}
-                },
-            },
-            _ => {}
+    pub fn switch_home_tx_mark(&mut self) {
+        if let Some(index) = self.state.home.table.state.selected() {
+            let id_num = self.state.home.tx_data.get_id_num(index);
+
+            if !self.state.home.marked_txs.remove(&id_num) {
+                self.state.home.marked_txs.insert(id_num);
+            }
         }
-        self.go_correct_index();
-        self.check_autofill();
     }
 
-    /// Handles left arrow key press for multiple pages
+    /// Switches the Home page between showing a single month and a single ISO week of the month
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_left_arrow(&mut self) {
-        match self.page {
-            CurrentUi::Home => match self.home_tab {
-                HomeTab::Months => {
-                    self.home_months.previous();
-                    self.reload_home_table();
-                }
-                HomeTab::Years => {
-                    self.home_years.previous();
-                    self.home_months.set_index_zero();
-                    self.reload_home_table();
-                }
-                HomeTab::Table => {}
-            },
-            CurrentUi::AddTx => self.add_tx_data.move_index_left(self.add_tx_tab),
-            CurrentUi::Search => self.search_data.move_index_left(self.search_tab),
-            CurrentUi::Chart => {
-                if !*self.chart_hidden_mode {
-                    match self.chart_tab {
-                        ChartTab::ModeSelection => {
-                            self.chart_modes.previous();
-                            self.reload_chart_index();
-                        }
-                        ChartTab::Years => {
-                            self.chart_years.previous();
-                            self.chart_months.set_index_zero();
-                            self.reload_chart_index();
-                        }
-                        ChartTab::Months => {
-                            self.chart_months.previous();
-                            self.reload_chart_index();
-                        }
-                        ChartTab::TxMethods => {
-                            self.chart_tx_methods.previous();
-                        }
-                    }
-                }
-            }
-            CurrentUi::Summary => {
-                if !*self.summary_hidden_mode {
-                    match self.summary_tab {
-                        SummaryTab::ModeSelection => {
-                            self.summary_modes.previous();
-                            self.reload_summary();
-                        }
-                        SummaryTab::Years => {
-                            self.summary_months.set_index_zero();
-                            self.summary_years.previous();
-                            self.reload_summary();
-                        }
-                        SummaryTab::Months => {
-                            self.summary_months.previous();
-                            self.reload_summary();
-                        }
-                        SummaryTab::Table => {}
-                    }
-                }
+    pub fn do_home_weekly(&mut self) {
+        self.state.home.weekly = !self.state.home.weekly;
+        if self.state.home.tab == HomeTab::Weeks {
+            self.state.home.tab = HomeTab::Months;
+        }
+        self.reload_home_weeks();
+        self.reload_home_period();
+    }
+
+    /// Cycles the running balance column of the Home table from hidden to the total balance
+    /// and then to each tx method
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_home_balance_column(&mut self) {
+        let total_column = get_all_tx_methods(self.conn).len();
+
+        self.state.home.balance_column = match self.state.home.balance_column {
+            None => Some(total_column),
+            Some(column) if column == total_column => Some(0),
+            Some(column) if column + 1 < total_column => Some(column + 1),
+            Some(_) => None,
+        };
+    }
+
+    /// Cycles the status of the selected tx from pending to cleared and then to reconciled
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_home_tx_status(&mut self) {
+        if self.state.home.tab != HomeTab::Table {
+            return;
+        }
+
+        if let Some(index) = self.state.home.table.state.selected() {
+            let status = self.state.home.tx_data.get_status(index).next();
+            if self
+                .state
+                .home
+                .tx_data
+                .set_status(index, status, self.conn)
+                .is_ok()
+            {
+                self.reload_home_balance_data();
             }
-            CurrentUi::Activity => match self.activity_tab {
-                ActivityTab::Years => {
-                    self.activity_months.set_index_zero();
-                    self.activity_years.previous();
-                    self.reload_activity_table();
-                }
-                ActivityTab::Months => {
-                    self.activity_months.previous();
-                    self.reload_activity_table();
-                }
-                ActivityTab::List => {}
-            },
-            CurrentUi::Initial => {}
         }
     }
 
-    /// Handles right arrow key press for multiple pages
+    /// Switches the Home balance between every tx and only the cleared and reconciled txs
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_right_arrow(&mut self) {
-        match self.page {
-            CurrentUi::Home => match self.home_tab {
-                HomeTab::Months => {
-                    self.home_months.next();
-                    self.reload_home_table();
-                }
-                HomeTab::Years => {
-                    self.home_years.next();
-                    self.home_months.set_index_zero();
-                    self.reload_home_table();
-                }
-                HomeTab::Table => {}
-            },
-            CurrentUi::AddTx => self.add_tx_data.move_index_right(self.add_tx_tab),
-            CurrentUi::Search => self.search_data.move_index_right(self.search_tab),
-            CurrentUi::Chart => {
-                if !*self.chart_hidden_mode {
-                    match self.chart_tab {
-                        ChartTab::ModeSelection => {
-                            self.chart_modes.next();
-                            self.reload_chart_index();
-                        }
-                        ChartTab::Years => {
-                            self.chart_years.next();
-                            self.chart_months.set_index_zero();
-                            self.reload_chart_index();
-                        }
-                        ChartTab::Months => {
-                            self.chart_months.next();
-                            self.reload_chart_index();
-                        }
-                        ChartTab::TxMethods => {
-                            self.chart_tx_methods.next();
-                        }
-                    }
-                }
+    pub fn do_home_cleared_balance(&mut self) {
+        self.state.home.cleared_balance = !self.state.home.cleared_balance;
+        self.reload_home_table();
+    }
+
+    /// Deletes all the marked transactions at once and reloads pages
+    #[cfg(not(tarpaulin_include))]
+    pub fn home_delete_marked_txs(&mut self) {
+        let mut id_nums = self
+            .state
+            .home
+            .marked_txs
+            .iter()
+            .copied()
+            .collect::<Vec<i32>>();
+        id_nums.sort_unstable();
+
+        match trash_txs(&id_nums, self.conn) {
+            Ok(()) => {
+                self.emit_event(&RexEvent::TxDeleted { id_nums });
+
+                self.state.home.marked_txs.clear();
+                self.state.home.table.state.select(None);
+                self.state.home.tab = HomeTab::Months;
+
+                self.reload_home_table();
+                self.reload_chart_data();
+                self.reload_summary_data();
+                self.reload_all_tags();
+                self.reset_search_data();
+                self.reload_activity_table();
             }
-            CurrentUi::Summary => match self.summary_tab {
-                SummaryTab::ModeSelection => {
-                    self.summary_modes.next();
-                    self.reload_summary();
-                }
-                SummaryTab::Years => {
-                    self.summary_months.set_index_zero();
-                    self.summary_years.next();
-                    self.reload_summary();
-                }
-                SummaryTab::Months => {
-                    self.summary_months.next();
-                    self.reload_summary();
+            Err(err) => {
+                self.state.popup =
+                    PopupState::DeleteFailed(TxUpdateError::FailedDeleteTx(err).to_string());
+            }
+        }
+    }
+
+    /// Opens every file attached to the selected tx on the Home table with the default app of the file
+    #[cfg(not(tarpaulin_include))]
+    pub fn open_home_tx_attachments(&mut self) {
+        let Some(index) = self.state.home.table.state.selected() else {
+            return;
+        };
+
+        let attachments = get_tx_attachments(self.state.home.tx_data.get_id_num(index), self.conn);
+
+        if attachments.is_empty() {
+            self.state.popup =
+                PopupState::AttachmentFailed(AttachmentError::NoAttachment.to_string());
+            return;
+        }
+
+        for path in attachments {
+            if let Err(e) = open::that(&path) {
+                self.state.popup =
+                    PopupState::AttachmentFailed(AttachmentError::FailedOpen(path, e).to_string());
+                return;
+            }
+        }
+    }
+
+    /// Opens the popup for attaching a file to the selected tx on the Home table
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_attach_file_popup(&mut self) {
+        if self.state.home.table.state.selected().is_some() {
+            self.state.popup = PopupState::AttachFile(String::new());
+        }
+    }
+
+    /// Handles the key presses of the attach file popup. An empty input removes every attachment
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_attach_file_popup(&mut self) {
+        let PopupState::AttachFile(input) = &mut self.state.popup else {
+            return;
+        };
+
+        let Some(index) = self.state.home.table.state.selected() else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => {
+                let id_num = self.state.home.tx_data.get_id_num(index);
+
+                let status = if input.trim().is_empty() {
+                    set_tx_attachments(id_num, &[], self.conn).map_err(AttachmentError::FailedSave)
+                } else {
+                    get_attachment_path(input).and_then(|path| {
+                        add_tx_attachment(id_num, &path, self.conn)
+                            .map_err(AttachmentError::FailedSave)
+                    })
+                };
+
+                self.state.popup = match status {
+                    Ok(()) => PopupState::Nothing,
+                    Err(e) => PopupState::AttachmentFailed(e.to_string()),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens the popup for adding a new tx method
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_new_tx_method_popup(&mut self) {
+        self.state.popup = PopupState::NewTxMethod(String::new());
+    }
+
+    /// Handles the key presses of the new tx method popup. Returns true once the method is added
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_new_tx_method_popup(&mut self) -> bool {
+        let PopupState::NewTxMethod(input) = &mut self.state.popup else {
+            return false;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match add_tx_method(input, self.conn) {
+                Ok(_) => {
+                    self.state.popup = PopupState::Nothing;
+                    self.reload_tx_methods();
+                    return true;
                 }
-                SummaryTab::Table => {}
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
             },
-            CurrentUi::Activity => match self.activity_tab {
-                ActivityTab::Years => {
-                    self.activity_months.set_index_zero();
-                    self.activity_years.next();
-                    self.reload_activity_table();
+            _ => {}
+        }
+        false
+    }
+
+    /// Opens the popup for renaming a tx method
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_rename_tx_method_popup(&mut self) {
+        self.state.popup = PopupState::RenameTxMethod(String::new());
+    }
+
+    /// Handles the key presses of the rename tx method popup. Returns true once the method is renamed
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_rename_tx_method_popup(&mut self) -> bool {
+        let PopupState::RenameTxMethod(input) = &mut self.state.popup else {
+            return false;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match rename_tx_method(input, self.conn) {
+                Ok(_) => {
+                    self.state.popup = PopupState::Nothing;
+                    self.reload_tx_methods();
+                    return true;
                 }
-                ActivityTab::Months => {
-                    self.activity_months.next();
-                    self.reload_activity_table();
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+            },
+            _ => {}
+        }
+        false
+    }
+
+    /// Opens the popup for archiving a tx method or bringing it back
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_archive_tx_method_popup(&mut self) {
+        self.state.popup = PopupState::ArchiveTxMethod(String::new());
+    }
+
+    /// Handles the key presses of the archive tx method popup. Returns true once the archive
+    /// status of the method is changed
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_archive_tx_method_popup(&mut self) -> bool {
+        let PopupState::ArchiveTxMethod(input) = &mut self.state.popup else {
+            return false;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match switch_tx_method_archive(input, self.conn) {
+                Ok(_) => {
+                    self.state.popup = PopupState::Nothing;
+                    self.reload_tx_methods();
+                    return true;
                 }
-                ActivityTab::List => {}
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
             },
-            CurrentUi::Initial => {}
+            _ => {}
         }
+        false
     }
 
-    /// Handles up arrow key press for multiple pages
+    /// Opens the popup for changing the type of a tx method
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_up_arrow(&mut self) {
-        match self.page {
-            CurrentUi::Home => self.do_home_up(),
-            CurrentUi::AddTx => self.do_add_tx_up(),
-            CurrentUi::Summary => self.do_summary_up(),
-            CurrentUi::Chart => self.do_chart_up(),
-            CurrentUi::Search => self.do_search_up(),
-            CurrentUi::Activity => self.do_activity_up(),
-            CurrentUi::Initial => {}
+    pub fn do_tx_method_type_popup(&mut self) {
+        self.state.popup = PopupState::TxMethodType(String::new());
+    }
+
+    /// Handles the key presses of the tx method type popup. Returns true once the type is changed
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tx_method_type_popup(&mut self) -> bool {
+        let PopupState::TxMethodType(input) = &mut self.state.popup else {
+            return false;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match change_tx_method_type(input, self.conn) {
+                Ok(_) => {
+                    self.state.popup = PopupState::Nothing;
+                    self.reload_tx_methods();
+                    return true;
+                }
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+            },
+            _ => {}
         }
-        self.check_autofill();
+        false
     }
 
-    /// Handles down arrow key press for multiple pages
+    /// Opens the popup for changing the currency of a tx method
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_down_arrow(&mut self) {
-        match self.page {
-            CurrentUi::Home => self.do_home_down(),
-            CurrentUi::AddTx => self.do_add_tx_down(),
-            CurrentUi::Summary => self.do_summary_down(),
-            CurrentUi::Chart => self.do_chart_down(),
-            CurrentUi::Search => self.do_search_down(),
-            CurrentUi::Activity => self.do_activity_down(),
-            CurrentUi::Initial => {}
+    pub fn do_tx_method_currency_popup(&mut self) {
+        self.state.popup = PopupState::TxMethodCurrency(String::new());
+    }
+
+    /// Handles the key presses of the tx method currency popup. Returns true once the currency
+    /// is changed
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tx_method_currency_popup(&mut self) -> bool {
+        let PopupState::TxMethodCurrency(input) = &mut self.state.popup else {
+            return false;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match change_tx_method_currency(input, self.conn) {
+                Ok(_) => {
+                    self.state.popup = PopupState::Nothing;
+                    self.reload_tx_methods();
+                    return true;
+                }
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+            },
+            _ => {}
         }
-        self.check_autofill();
+        false
     }
 
-    /// Checks and verifies date field
+    /// Opens the popup for changing the starting balance of a tx method
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_date(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => self.check_add_tx_date(),
-            CurrentUi::Search => self.check_search_date(),
+    pub fn do_starting_balance_popup(&mut self) {
+        self.state.popup = PopupState::StartingBalance(String::new());
+    }
+
+    /// Handles the key presses of the starting balance popup. The adjustment is added as a tx
+    /// so the tables and the balances are reloaded after it
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_starting_balance_popup(&mut self) {
+        let PopupState::StartingBalance(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match adjust_starting_balance(input, self.conn) {
+                Ok(_) => {
+                    self.state.popup = PopupState::Nothing;
+                    self.reload_home_table();
+                    self.reload_chart_data();
+                    self.reload_summary_data();
+                    self.reload_all_tags();
+                    self.reset_search_data();
+                    self.reload_activity_table();
+                }
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+            },
             _ => {}
         }
     }
 
-    /// Checks and verifies details field
+    /// Opens the popup for changing the color of a tx method
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_details(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => self.check_add_tx_details(),
-            CurrentUi::Search => self.check_search_details(),
+    pub fn do_tx_method_color_popup(&mut self) {
+        self.state.popup = PopupState::TxMethodColor(String::new());
+    }
+
+    /// Handles the key presses of the tx method color popup. Returns true once the color is changed
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tx_method_color_popup(&mut self) -> bool {
+        let PopupState::TxMethodColor(input) = &mut self.state.popup else {
+            return false;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match change_tx_method_color(input, self.conn) {
+                Ok(_) => {
+                    self.state.popup = PopupState::Nothing;
+                    return true;
+                }
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+            },
             _ => {}
         }
-        self.check_autofill();
+        false
     }
 
-    /// Checks and verifies tx method field
+    /// Opens the popup for changing the credit limit of a credit tx method
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_tx_method(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => match self.add_tx_tab {
-                TxTab::FromMethod => self.check_add_tx_from(),
-                TxTab::ToMethod => self.check_add_tx_to(),
-                _ => {}
+    pub fn do_credit_limit_popup(&mut self) {
+        self.state.popup = PopupState::CreditLimit(String::new());
+    }
+
+    /// Handles the key presses of the credit limit popup
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_credit_limit_popup(&mut self) {
+        let PopupState::CreditLimit(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match change_credit_limit(input, self.conn) {
+                Ok(_) => {
+                    self.state.popup = PopupState::Nothing;
+                    self.reload_home_balance_data();
+                    self.reload_add_tx_balance_data();
+                }
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
             },
-            CurrentUi::Search => match self.search_tab {
-                TxTab::FromMethod => self.check_search_from(),
-                TxTab::ToMethod => self.check_search_to(),
-                _ => {}
+            _ => {}
+        }
+    }
+
+    /// Opens the popup for changing the group of a tx method
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_tx_method_group_popup(&mut self) {
+        self.state.popup = PopupState::TxMethodGroup(String::new());
+    }
+
+    /// Handles the key presses of the tx method group popup
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tx_method_group_popup(&mut self) {
+        let PopupState::TxMethodGroup(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match change_tx_method_group(input, self.conn) {
+                Ok(_) => {
+                    // the group balances are drawn from the cached groups
+                    self.state.methods.reload(self.conn);
+                    self.state.popup = PopupState::Nothing;
+                }
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
             },
             _ => {}
         }
-        self.check_autofill();
     }
 
-    /// Checks and verifies amount field
+    /// Opens the popup for choosing the tx methods to merge
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_amount(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => self.check_add_tx_amount(),
-            CurrentUi::Search => self.check_search_amount(),
+    pub fn do_merge_tx_methods_popup(&mut self) {
+        self.state.popup = PopupState::MergeTxMethods(String::new());
+    }
+
+    /// Handles the key presses of the merge tx methods popup. Opens the confirmation popup with
+    /// the itemized changes once both methods are valid
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_merge_tx_methods_popup(&mut self) {
+        let PopupState::MergeTxMethods(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match get_merge_details(input, self.conn) {
+                Ok(details) => {
+                    self.state.deletion_status = DeletionStatus::No;
+                    self.state.popup = PopupState::ConfirmMerge(
+                        details.source.clone(),
+                        details.target.clone(),
+                        details.get_summary(),
+                    );
+                }
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+            },
             _ => {}
         }
     }
 
-    // Checks and verifies tx type field
+    /// Handles the key presses of the merge confirmation popup. Returns true once the methods
+    /// are merged
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_tx_type(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => self.check_add_tx_type(),
-            CurrentUi::Search => self.check_search_type(),
+    pub fn handle_confirm_merge_popup(&mut self) -> bool {
+        let PopupState::ConfirmMerge(source, target, _) = &mut self.state.popup else {
+            return false;
+        };
+
+        match self.key.code {
+            KeyCode::Left | KeyCode::Right => {
+                self.state.deletion_status = self.state.deletion_status.next()
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match self.state.deletion_status {
+                DeletionStatus::Yes => match merge_tx_methods(source, target, self.conn) {
+                    Ok(()) => {
+                        self.state.popup = PopupState::Nothing;
+                        self.reload_tx_methods();
+                        self.reset_search_data();
+                        self.reload_activity_table();
+                        return true;
+                    }
+                    Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+                },
+                DeletionStatus::No => self.state.popup = PopupState::Nothing,
+            },
             _ => {}
         }
+        false
     }
 
-    /// Checks and verifies tags field
+    /// Opens the first step of deleting a tx method where the method is chosen
     #[cfg(not(tarpaulin_include))]
-    pub fn handle_tags(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => self.check_add_tx_tags(),
-            CurrentUi::Search => self.check_search_tags(),
+    pub fn do_delete_tx_method_popup(&mut self) {
+        self.state.popup = PopupState::DeleteTxMethod(String::new());
+    }
+
+    /// Handles the key presses of the tx method deletion popup. Asks where the txs should go
+    /// if the method has any, otherwise goes straight to the confirmation
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_delete_tx_method_popup(&mut self) {
+        let PopupState::DeleteTxMethod(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match get_deletion_details(input, self.conn) {
+                Ok((method, 0)) => {
+                    self.state.deletion_status = DeletionStatus::No;
+                    let summary = format!(
+                        "Delete {method}?\n\nIt has no transactions. This cannot be undone"
+                    );
+                    self.state.popup = PopupState::ConfirmMethodDeletion(method, None, summary);
+                }
+                Ok((method, total_txs)) => {
+                    self.state.popup =
+                        PopupState::DeleteMethodTarget(method, total_txs, String::new());
+                }
+                Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+            },
             _ => {}
         }
-        self.check_autofill();
     }
 
-    /// Resets all input boxes on Add Tx and Transfer page
+    /// Handles the key presses of the step where the txs of the method being deleted are either
+    /// moved to another method or deleted. Opens the confirmation with the itemized changes
     #[cfg(not(tarpaulin_include))]
-    pub fn clear_input(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => *self.add_tx_data = TxData::new(),
-            CurrentUi::Search => {
-                *self.search_data = TxData::new_empty();
-                self.reset_search_data();
+    pub fn handle_delete_method_target_popup(&mut self) {
+        let PopupState::DeleteMethodTarget(method, total_txs, input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => {
+                self.state.deletion_status = DeletionStatus::No;
+
+                if input.trim().eq_ignore_ascii_case("delete") {
+                    let summary = format!(
+                        "Delete {method} and its {total_txs} transactions?\n\nTransfers with other tx methods are removed from them too. This cannot be undone"
+                    );
+                    self.state.popup =
+                        PopupState::ConfirmMethodDeletion(method.to_string(), None, summary);
+                    return;
+                }
+
+                match get_merge_details(&format!("{method}, {input}"), self.conn) {
+                    Ok(details) => {
+                        let summary = details.get_deletion_summary();
+                        self.state.popup = PopupState::ConfirmMethodDeletion(
+                            details.source,
+                            Some(details.target),
+                            summary,
+                        );
+                    }
+                    Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles the key presses of the tx method deletion confirmation popup. Returns true once
+    /// the method is deleted
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_confirm_method_deletion_popup(&mut self) -> bool {
+        let PopupState::ConfirmMethodDeletion(method, target, _) = &mut self.state.popup else {
+            return false;
+        };
+
+        match self.key.code {
+            KeyCode::Left | KeyCode::Right => {
+                self.state.deletion_status = self.state.deletion_status.next()
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match self.state.deletion_status {
+                DeletionStatus::Yes => {
+                    let result = match target {
+                        Some(target) => merge_tx_methods(method, target, self.conn),
+                        None => delete_tx_method(method, self.conn),
+                    };
+
+                    match result {
+                        Ok(()) => {
+                            self.state.popup = PopupState::Nothing;
+                            self.reload_tx_methods();
+                            self.reset_search_data();
+                            self.reload_activity_table();
+                            return true;
+                        }
+                        Err(e) => self.state.popup = PopupState::TxMethodFailed(e.to_string()),
+                    }
+                }
+                DeletionStatus::No => self.state.popup = PopupState::Nothing,
+            },
+            _ => {}
+        }
+        false
+    }
+
+    /// Checks the db for problems and opens the report popup with them
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_integrity_check_popup(&mut self) {
+        let report = match check_db_integrity(self.conn) {
+            Ok(problems) if problems.is_empty() => "No problems were found.".to_string(),
+            Ok(problems) => format!(
+                "{} problem(s) found:\n\n{}",
+                problems.len(),
+                problems.join("\n")
+            ),
+            Err(e) => format!("Failed to check the database. Error: {e}"),
+        };
+
+        self.state.popup = PopupState::IntegrityReport(format!(
+            "{report}\n\nShift + R: Rebuild the balances and the changes from the transactions"
+        ));
+    }
+
+    /// Compares the stored balances with the balances worked out from the txs and opens the
+    /// report popup with the months that do not match
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_balance_audit_popup(&mut self) {
+        let report = match get_balance_divergence(self.conn) {
+            Ok(divergence) if divergence.is_empty() => {
+                "Every balance matches the transactions.".to_string()
+            }
+            Ok(divergence) => {
+                let final_id = YEARS.len() as i32 * 12 + 1;
+                let mut months: Vec<String> = Vec::new();
+                let mut lines = Vec::new();
+
+                for (id_num, tx_method, stored, expected) in &divergence {
+                    let period = if *id_num == final_id {
+                        "Final balance".to_string()
+                    } else {
+                        let month = MONTHS[(*id_num as usize - 1) % 12];
+                        let year = YEARS[(*id_num as usize - 1) / 12];
+                        format!("{month} {year}")
+                    };
+                    if !months.contains(&period) {
+                        months.push(period.clone());
+                    }
+                    lines.push(format!(
+                        "{period} {tx_method}: Saved {stored:.2} Expected {expected:.2}"
+                    ));
+                }
+
+                format!(
+                    "{} month(s) do not match the transactions:\n\n{}",
+                    months.len(),
+                    lines.join("\n")
+                )
+            }
+            Err(e) => format!("Failed to verify the balances. Error: {e}"),
+        };
+
+        self.state.popup = PopupState::BalanceAudit(format!(
+            "{report}\n\nShift + R: Rebuild the balances and the changes from the transactions"
+        ));
+    }
+
+    /// Handles the key presses of the integrity report and the balance audit popup. Returns true
+    /// once the balances and the changes are rebuilt
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_balance_report_popup(&mut self) -> bool {
+        match self.key.code {
+            KeyCode::Up => self.popup_scroll_up(),
+            KeyCode::Down => self.popup_scroll_down(),
+            KeyCode::Char('R') => {
+                self.reload_popup_scroll_position();
+                let status = rebuild_derived_tables(self.conn);
+
+                let text = match &status {
+                    Ok(()) => "The balances and the changes were rebuilt from the transactions."
+                        .to_string(),
+                    Err(e) => {
+                        format!("Failed to rebuild the balances. Nothing was changed. Error: {e}")
+                    }
+                };
+
+                // the popup stays open with the result
+                if let PopupState::IntegrityReport(report) | PopupState::BalanceAudit(report) =
+                    &mut self.state.popup
+                {
+                    *report = text;
+                }
+
+                if status.is_ok() {
+                    self.reload_tx_methods();
+                    self.reset_search_data();
+                    return true;
+                }
+            }
+            _ => self.do_empty_popup(),
+        }
+        false
+    }
+
+    /// Opens the maintenance popup with the size of the db and the row count of every table
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_maintenance_popup(&mut self) {
+        self.state.popup = PopupState::Maintenance(self.get_maintenance_text(None));
+    }
+
+    /// Handles the key presses of the maintenance popup. The popup stays open with the result of
+    /// the task on top of the updated statistics
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_maintenance_popup(&mut self) {
+        let result = match self.key.code {
+            KeyCode::Up => return self.popup_scroll_up(),
+            KeyCode::Down => return self.popup_scroll_down(),
+            KeyCode::Char('c') => match vacuum_db(self.conn) {
+                Ok(()) => "The database was compacted.".to_string(),
+                Err(e) => format!("Failed to compact the database. Error: {e}"),
+            },
+            KeyCode::Char('a') => match analyze_db(self.conn) {
+                Ok(()) => "The query statistics were updated.".to_string(),
+                Err(e) => format!("Failed to update the query statistics. Error: {e}"),
+            },
+            KeyCode::Char('r') => match reindex_db(self.conn) {
+                Ok(()) => "Every index was rebuilt.".to_string(),
+                Err(e) => format!("Failed to rebuild the indexes. Error: {e}"),
+            },
+            KeyCode::Char('b') => match backup_db_now(self.db_path, self.conn) {
+                Ok(path) => {
+                    let path = path.to_string_lossy().to_string();
+                    self.emit_event(&RexEvent::BackupDone {
+                        paths: vec![path.clone()],
+                    });
+                    format!("Backup saved to {path}")
+                }
+                Err(e) => e.to_string(),
+            },
+            KeyCode::Char('l') => {
+                self.reload_popup_scroll_position();
+                self.state.popup = PopupState::ViewLog(get_log_text());
+                return;
+            }
+            _ => return self.do_empty_popup(),
+        };
+
+        self.reload_popup_scroll_position();
+        self.state.popup = PopupState::Maintenance(self.get_maintenance_text(Some(result)));
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_maintenance_text(&mut self, result: Option<String>) -> String {
+        let format_size = |bytes: i64| {
+            let bytes = bytes as f64;
+            if bytes >= 1024.0 * 1024.0 {
+                format!("{:.2} MB", bytes / 1024.0 / 1024.0)
+            } else {
+                format!("{:.2} KB", bytes / 1024.0)
+            }
+        };
+
+        let stats = match get_db_stats(self.conn) {
+            Ok(stats) => {
+                let name_width = stats
+                    .tables
+                    .iter()
+                    .map(|(name, _)| name.len())
+                    .max()
+                    .unwrap_or(0);
+                let rows = stats
+                    .tables
+                    .iter()
+                    .map(|(name, rows)| format!("{name:<name_width$}  {rows}"))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+
+                format!(
+                    "Size: {} ({} unused)\n\nRows:\n{rows}",
+                    format_size(stats.size),
+                    format_size(stats.free_size)
+                )
+            }
+            Err(e) => format!("Failed to read the database statistics. Error: {e}"),
+        };
+
+        let keys = "C: Compact the database and give the unused space back
+A: Update the statistics used for picking indexes
+R: Rebuild every index
+B: Save a backup to the backups folder next to the database
+L: View the latest lines of the log";
+
+        match result {
+            Some(result) => format!("{result}\n\n{stats}\n\n{keys}"),
+            None => format!("{stats}\n\n{keys}"),
+        }
+    }
+
+    /// Opens the popup for switching to another profile with the list of the profiles
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_switch_profile_popup(&mut self) {
+        let profiles = self
+            .profiles
+            .iter()
+            .map(|profile| {
+                if profile == self.profile {
+                    format!("{profile} (Active)")
+                } else {
+                    profile.to_string()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        self.state.popup = PopupState::SwitchProfile(profiles, String::new());
+    }
+
+    /// Handles the key presses of the switch profile popup. Returns the profile to switch to
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_switch_profile_popup(&mut self) -> Option<String> {
+        let PopupState::SwitchProfile(_, input) = &mut self.state.popup else {
+            return None;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => {
+                let profile = input.trim().to_string();
+                self.state.popup = PopupState::Nothing;
+
+                if !profile.is_empty() && !profile.eq_ignore_ascii_case(self.profile) {
+                    return Some(profile);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Opens the tag rule editor with the list of the saved rules
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_tag_rules_popup(&mut self) {
+        self.state.popup = PopupState::TagRules(get_tag_rules_text(self.conn), String::new());
+    }
+
+    /// Handles the key presses of the tag rule editor. Input starting with - removes the rule
+    /// with that number, anything else is saved as a new rule. The editor stays open after
+    /// each change
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tag_rules_popup(&mut self) {
+        let PopupState::TagRules(_, input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => {
+                let result = match input.trim().strip_prefix('-') {
+                    Some(number) => remove_tag_rule(number, self.conn),
+                    None => save_tag_rule(input, self.conn),
+                };
+
+                match result {
+                    Ok(()) => self.do_tag_rules_popup(),
+                    Err(e) => self.state.popup = PopupState::TagRuleFailed(e.to_string()),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens the popup for adding or removing a tag on all the marked txs
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_retag_popup(&mut self) {
+        if !self.state.home.marked_txs.is_empty() {
+            self.state.popup = PopupState::MarkedTxRetag(String::new());
+        }
+    }
+
+    /// Handles the key presses of the retag popup
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_retag_popup(&mut self) {
+        let PopupState::MarkedTxRetag(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => {
+                let input = input.trim().to_string();
+                self.state.popup = PopupState::Nothing;
+                self.home_retag_marked_txs(&input);
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds the tag to all the marked txs or removes it if the input starts with `-`
+    #[cfg(not(tarpaulin_include))]
+    fn home_retag_marked_txs(&mut self, input: &str) {
+        let (tag, remove) = match input.strip_prefix('-') {
+            Some(tag) => (tag, true),
+            None => (input.strip_prefix('+').unwrap_or(input), false),
+        };
+
+        let mut id_nums = self
+            .state
+            .home
+            .marked_txs
+            .iter()
+            .copied()
+            .collect::<Vec<i32>>();
+        id_nums.sort_unstable();
+
+        let status = if remove {
+            remove_tag_from_txs(&id_nums, tag, self.config, self.conn)
+        } else {
+            add_tag_to_txs(&id_nums, tag, self.config, self.conn)
+        };
+
+        match status {
+            Ok(()) => {
+                self.reload_home_table();
+                self.reload_chart_data();
+                self.reload_summary_data();
+                self.reload_all_tags();
+                self.reset_search_data();
+                self.reload_activity_table();
+            }
+            Err(err) => self.state.popup = PopupState::RetagFailed(err.to_string()),
+        }
+    }
+
+    /// Handles all number key presses and selects relevant input field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_number_press(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => match self.state.add_tx.data.get_tx_type() {
+                TxType::IncomeExpense => match self.key.code {
+                    KeyCode::Char('1') => self.state.add_tx.tab = TxTab::Date,
+                    KeyCode::Char('2') => self.state.add_tx.tab = TxTab::Details,
+                    KeyCode::Char('3') => self.state.add_tx.tab = TxTab::TxType,
+                    KeyCode::Char('4') => self.state.add_tx.tab = TxTab::FromMethod,
+                    KeyCode::Char('5') => self.state.add_tx.tab = TxTab::Amount,
+                    KeyCode::Char('6') => self.state.add_tx.tab = TxTab::Tags,
+                    _ => {}
+                },
+                TxType::Transfer => match self.key.code {
+                    KeyCode::Char('1') => self.state.add_tx.tab = TxTab::Date,
+                    KeyCode::Char('2') => self.state.add_tx.tab = TxTab::Details,
+                    KeyCode::Char('3') => self.state.add_tx.tab = TxTab::TxType,
+                    KeyCode::Char('4') => self.state.add_tx.tab = TxTab::FromMethod,
+                    KeyCode::Char('5') => self.state.add_tx.tab = TxTab::ToMethod,
+                    KeyCode::Char('6') => self.state.add_tx.tab = TxTab::Amount,
+                    KeyCode::Char('7') => self.state.add_tx.tab = TxTab::Tags,
+                    _ => {}
+                },
+            },
+            CurrentUi::Search => match self.state.search.data.get_tx_type() {
+                TxType::IncomeExpense => match self.key.code {
+                    KeyCode::Char('1') => self.state.search.tab = TxTab::Date,
+                    KeyCode::Char('2') => self.state.search.tab = TxTab::Details,
+                    KeyCode::Char('3') => self.state.search.tab = TxTab::TxType,
+                    KeyCode::Char('4') => self.state.search.tab = TxTab::FromMethod,
+                    KeyCode::Char('5') => self.state.search.tab = TxTab::Amount,
+                    KeyCode::Char('6') => self.state.search.tab = TxTab::Tags,
+                    _ => {}
+                },
+                TxType::Transfer => match self.key.code {
+                    KeyCode::Char('1') => self.state.search.tab = TxTab::Date,
+                    KeyCode::Char('2') => self.state.search.tab = TxTab::Details,
+                    KeyCode::Char('3') => self.state.search.tab = TxTab::TxType,
+                    KeyCode::Char('4') => self.state.search.tab = TxTab::FromMethod,
+                    KeyCode::Char('5') => self.state.search.tab = TxTab::ToMethod,
+                    KeyCode::Char('6') => self.state.search.tab = TxTab::Amount,
+                    KeyCode::Char('7') => self.state.search.tab = TxTab::Tags,
+                    _ => {}
+                },
+            },
+            _ => {}
+        }
+        self.go_correct_index();
+        self.check_autofill();
+    }
+
+    /// Handles left arrow key press for multiple pages
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_left_arrow(&mut self) {
+        match self.state.page {
+            CurrentUi::Home => match self.state.home.tab {
+                HomeTab::Months => {
+                    self.state.home.months.previous();
+                    self.reload_home_weeks();
+                    self.reload_home_period();
+                }
+                HomeTab::Years => {
+                    self.state.home.years.previous();
+                    self.state.home.months.set_index_zero();
+                    self.reload_home_weeks();
+                    self.reload_home_period();
+                }
+                HomeTab::Weeks => {
+                    self.state.home.weeks.previous();
+                    self.reload_home_period();
+                }
+                HomeTab::TagFilter => {
+                    self.state.home.filter.tags.previous();
+                    self.filter_home_table();
+                }
+                HomeTab::MethodFilter => {
+                    self.state.home.filter.tx_methods.previous();
+                    self.filter_home_table();
+                }
+                HomeTab::TypeFilter => {
+                    self.state.home.filter.tx_types.previous();
+                    self.filter_home_table();
+                }
+                HomeTab::Table => {}
+            },
+            CurrentUi::AddTx => self
+                .state
+                .add_tx
+                .data
+                .move_index_left(&self.state.add_tx.tab),
+            CurrentUi::Search => self
+                .state
+                .search
+                .data
+                .move_index_left(&self.state.search.tab),
+            CurrentUi::Chart => {
+                if !self.state.chart.hidden_mode {
+                    match self.state.chart.tab {
+                        ChartTab::ModeSelection => {
+                            self.state.chart.modes.previous();
+                            self.reload_chart_index();
+                        }
+                        ChartTab::Years => {
+                            self.state.chart.years.previous();
+                            self.state.chart.months.set_index_zero();
+                            self.reload_chart_index();
+                        }
+                        ChartTab::Months => {
+                            self.state.chart.months.previous();
+                            self.reload_chart_index();
+                        }
+                        ChartTab::TxMethods => {
+                            if let ChartView::Tags = self.state.chart.view {
+                                if !self.state.chart.tags.titles.is_empty() {
+                                    self.state.chart.tags.previous();
+                                }
+                            } else {
+                                self.state.chart.tx_methods.previous();
+                            }
+                        }
+                    }
+                }
+            }
+            CurrentUi::Summary => {
+                if !self.state.summary.hidden_mode {
+                    match self.state.summary.tab {
+                        SummaryTab::ModeSelection => {
+                            self.state.summary.modes.previous();
+                            self.reload_summary();
+                        }
+                        SummaryTab::TxMethods => {
+                            self.state.summary.tx_methods.previous();
+                            self.select_summary_tx_method();
+                        }
+                        SummaryTab::Years => {
+                            self.state.summary.months.set_index_zero();
+                            self.state.summary.years.previous();
+                            self.reload_summary();
+                        }
+                        SummaryTab::Months => {
+                            self.state.summary.months.previous();
+                            self.reload_summary();
+                        }
+                        SummaryTab::Table => {}
+                    }
+                }
+            }
+            CurrentUi::Activity => match self.state.activity.tab {
+                ActivityTab::Years => {
+                    self.state.activity.months.set_index_zero();
+                    self.state.activity.years.previous();
+                    self.reload_activity_table();
+                }
+                ActivityTab::Months => {
+                    self.state.activity.months.previous();
+                    self.reload_activity_table();
+                }
+                ActivityTab::List => {}
+            },
+            CurrentUi::Reports => {
+                self.state.reports.data.previous(self.config.reports.len());
+                self.reload_report_table();
+            }
+            CurrentUi::Trash | CurrentUi::Initial | CurrentUi::Extension(_) => {}
+        }
+    }
+
+    /// Handles right arrow key press for multiple pages
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_right_arrow(&mut self) {
+        match self.state.page {
+            CurrentUi::Home => match self.state.home.tab {
+                HomeTab::Months => {
+                    self.state.home.months.next();
+                    self.reload_home_weeks();
+                    self.reload_home_period();
+                }
+                HomeTab::Years => {
+                    self.state.home.years.next();
+                    self.state.home.months.set_index_zero();
+                    self.reload_home_weeks();
+                    self.reload_home_period();
+                }
+                HomeTab::Weeks => {
+                    self.state.home.weeks.next();
+                    self.reload_home_period();
+                }
+                HomeTab::TagFilter => {
+                    self.state.home.filter.tags.next();
+                    self.filter_home_table();
+                }
+                HomeTab::MethodFilter => {
+                    self.state.home.filter.tx_methods.next();
+                    self.filter_home_table();
+                }
+                HomeTab::TypeFilter => {
+                    self.state.home.filter.tx_types.next();
+                    self.filter_home_table();
+                }
+                HomeTab::Table => {}
+            },
+            CurrentUi::AddTx => self
+                .state
+                .add_tx
+                .data
+                .move_index_right(&self.state.add_tx.tab),
+            CurrentUi::Search => self
+                .state
+                .search
+                .data
+                .move_index_right(&self.state.search.tab),
+            CurrentUi::Chart => {
+                if !self.state.chart.hidden_mode {
+                    match self.state.chart.tab {
+                        ChartTab::ModeSelection => {
+                            self.state.chart.modes.next();
+                            self.reload_chart_index();
+                        }
+                        ChartTab::Years => {
+                            self.state.chart.years.next();
+                            self.state.chart.months.set_index_zero();
+                            self.reload_chart_index();
+                        }
+                        ChartTab::Months => {
+                            self.state.chart.months.next();
+                            self.reload_chart_index();
+                        }
+                        ChartTab::TxMethods => {
+                            if let ChartView::Tags = self.state.chart.view {
+                                if !self.state.chart.tags.titles.is_empty() {
+                                    self.state.chart.tags.next();
+                                }
+                            } else {
+                                self.state.chart.tx_methods.next();
+                            }
+                        }
+                    }
+                }
+            }
+            CurrentUi::Summary => match self.state.summary.tab {
+                SummaryTab::ModeSelection => {
+                    self.state.summary.modes.next();
+                    self.reload_summary();
+                }
+                SummaryTab::TxMethods => {
+                    self.state.summary.tx_methods.next();
+                    self.select_summary_tx_method();
+                }
+                SummaryTab::Years => {
+                    self.state.summary.months.set_index_zero();
+                    self.state.summary.years.next();
+                    self.reload_summary();
+                }
+                SummaryTab::Months => {
+                    self.state.summary.months.next();
+                    self.reload_summary();
+                }
+                SummaryTab::Table => {}
+            },
+            CurrentUi::Activity => match self.state.activity.tab {
+                ActivityTab::Years => {
+                    self.state.activity.months.set_index_zero();
+                    self.state.activity.years.next();
+                    self.reload_activity_table();
+                }
+                ActivityTab::Months => {
+                    self.state.activity.months.next();
+                    self.reload_activity_table();
+                }
+                ActivityTab::List => {}
+            },
+            CurrentUi::Reports => {
+                self.state.reports.data.next(self.config.reports.len());
+                self.reload_report_table();
+            }
+            CurrentUi::Trash | CurrentUi::Initial | CurrentUi::Extension(_) => {}
+        }
+    }
+
+    /// Handles up arrow key press for multiple pages
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_up_arrow(&mut self) {
+        match self.state.page {
+            CurrentUi::Home => self.do_home_up(),
+            CurrentUi::AddTx => self.do_add_tx_up(),
+            CurrentUi::Summary => self.do_summary_up(),
+            CurrentUi::Chart => self.do_chart_up(),
+            CurrentUi::Search => self.do_search_up(),
+            CurrentUi::Activity => self.do_activity_up(),
+            CurrentUi::Trash => {
+                if !self.state.trash.table.items.is_empty() {
+                    self.state.trash.table.previous();
+                }
+            }
+            CurrentUi::Reports => {
+                if !self.state.reports.table.items.is_empty() {
+                    self.state.reports.table.previous();
+                }
+            }
+            CurrentUi::Initial | CurrentUi::Extension(_) => {}
+        }
+        self.check_autofill();
+    }
+
+    /// Handles down arrow key press for multiple pages
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_down_arrow(&mut self) {
+        match self.state.page {
+            CurrentUi::Home => self.do_home_down(),
+            CurrentUi::AddTx => self.do_add_tx_down(),
+            CurrentUi::Summary => self.do_summary_down(),
+            CurrentUi::Chart => self.do_chart_down(),
+            CurrentUi::Search => self.do_search_down(),
+            CurrentUi::Activity => self.do_activity_down(),
+            CurrentUi::Trash => {
+                if !self.state.trash.table.items.is_empty() {
+                    self.state.trash.table.next();
+                }
+            }
+            CurrentUi::Reports => {
+                if !self.state.reports.table.items.is_empty() {
+                    self.state.reports.table.next();
+                }
+            }
+            CurrentUi::Initial | CurrentUi::Extension(_) => {}
+        }
+        self.check_autofill();
+    }
+
+    /// Checks and verifies date field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_date(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self.check_add_tx_date(),
+            CurrentUi::Search => self.check_search_date(),
+            _ => {}
+        }
+    }
+
+    /// Checks and verifies details field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_details(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self.check_add_tx_details(),
+            CurrentUi::Search => self.check_search_details(),
+            _ => {}
+        }
+        self.check_autofill();
+    }
+
+    /// Checks and verifies tx method field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tx_method(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => match self.state.add_tx.tab {
+                TxTab::FromMethod => self.check_add_tx_from(),
+                TxTab::ToMethod => self.check_add_tx_to(),
+                _ => {}
+            },
+            CurrentUi::Search => match self.state.search.tab {
+                TxTab::FromMethod => self.check_search_from(),
+                TxTab::ToMethod => self.check_search_to(),
+                _ => {}
+            },
+            _ => {}
+        }
+        self.check_autofill();
+    }
+
+    /// Checks and verifies amount field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_amount(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self.check_add_tx_amount(),
+            CurrentUi::Search => self.check_search_amount(),
+            _ => {}
+        }
+    }
+
+    // Checks and verifies tx type field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tx_type(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self.check_add_tx_type(),
+            CurrentUi::Search => self.check_search_type(),
+            _ => {}
+        }
+    }
+
+    /// Checks and verifies tags field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tags(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self.check_add_tx_tags(),
+            CurrentUi::Search => self.check_search_tags(),
+            _ => {}
+        }
+        self.check_autofill();
+    }
+
+    /// Resets all input boxes on Add Tx and Transfer page
+    #[cfg(not(tarpaulin_include))]
+    pub fn clear_input(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self.state.add_tx.data = self.config.get_default_tx(self.conn),
+            CurrentUi::Search => {
+                self.state.search.data = TxData::new_empty();
+                self.reset_search_data();
+            }
+            _ => {}
+        }
+    }
+
+    /// Fills the Add Tx fields with the last tx added in this session
+    /// or with the last tx of the db if none were added yet
+    #[cfg(not(tarpaulin_include))]
+    pub fn repeat_last_tx(&mut self) {
+        let last_tx = match &self.state.add_tx.last_added_tx {
+            Some(tx_data) => Some(tx_data.repeated()),
+            None => TxData::last_in_db(self.conn),
+        };
+
+        if let Some(tx_data) = last_tx {
+            self.state.add_tx.data = tx_data;
+            self.state
+                .add_tx
+                .data
+                .add_tx_status("Info: Filled with the last added transaction.".to_string());
+        } else {
+            self.state
+                .add_tx
+                .data
+                .add_tx_status("Info: No transaction has been added yet.".to_string());
+        }
+    }
+
+    /// Turns batch entry on or off. While on, adding a tx keeps the page open with
+    /// the date, the tx methods and the tx type of the added tx
+    #[cfg(not(tarpaulin_include))]
+    pub fn toggle_add_tx_batch(&mut self) {
+        let status = if self.state.add_tx.batch.is_some() {
+            self.state.add_tx.batch = None;
+            "Batch: Batch entry turned off."
+        } else {
+            self.state.add_tx.batch = Some(0);
+            "Batch: Batch entry turned on. The date and the tx method are kept after each save."
+        };
+        self.state.add_tx.data.add_tx_status(status.to_string());
+    }
+
+    /// Swaps the From and To Method fields of the Add Tx transfer
+    #[cfg(not(tarpaulin_include))]
+    pub fn swap_tx_methods(&mut self) {
+        let status = if self.state.add_tx.data.swap_tx_methods() {
+            "Info: Swapped the From and To Method."
+        } else {
+            "Info: Only a transfer has a To Method to swap with."
+        };
+        self.state.add_tx.data.add_tx_status(status.to_string());
+    }
+
+    /// Opens the text area popup for editing the long note of the Add Tx tx
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_tx_note_popup(&mut self) {
+        self.state.popup = PopupState::TxNote(self.state.add_tx.data.get_note().to_string());
+    }
+
+    /// Handle key inputs for the note popup. Esc saves the note to the tx and closes the popup
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_tx_note_popup(&mut self) {
+        let PopupState::TxNote(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Enter => input.push('\n'),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => {
+                let note = input.trim_end().to_string();
+                if note != self.state.add_tx.data.get_note() {
+                    self.state.add_tx.data.set_note(note);
+                    self.state
+                        .add_tx
+                        .data
+                        .add_tx_status("Info: Transaction note updated.".to_string());
+                }
+                self.state.popup = PopupState::Nothing;
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens the popup for splitting the Add Tx tx into parts with the current parts filled in
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_split_tx_popup(&mut self) {
+        self.state.popup = PopupState::SplitTx(self.state.add_tx.data.get_split_text());
+    }
+
+    /// Handle key inputs for the split tx popup. The popup stays open if the parts cannot be used
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_split_tx_popup(&mut self) {
+        let PopupState::SplitTx(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match self.state.add_tx.data.set_splits(input, self.conn) {
+                Ok(()) => {
+                    let status = match self.state.add_tx.data.get_splits().len() {
+                        0 => "Split: Transaction is no longer split".to_string(),
+                        total_parts => {
+                            format!("Split: Transaction split into {total_parts} part(s)")
+                        }
+                    };
+                    self.state.add_tx.data.add_tx_status(status);
+                    self.state.popup = PopupState::Nothing;
+                }
+                Err(err) => self.state.add_tx.data.add_tx_status(err.to_string()),
+            },
+            _ => {}
+        }
+    }
+
+    /// Opens the popup for adding a fee to the Add Tx transfer with the current fee filled in
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_transfer_fee_popup(&mut self) {
+        self.state.popup = PopupState::TransferFee(self.state.add_tx.data.get_fee().to_string());
+    }
+
+    /// Handle key inputs for the transfer fee popup. The popup stays open if the fee cannot be used
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_transfer_fee_popup(&mut self) {
+        let PopupState::TransferFee(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match self.state.add_tx.data.set_fee(input) {
+                Ok(()) => {
+                    let status = match self.state.add_tx.data.get_fee() {
+                        "" => "Fee: Transfer no longer has a fee".to_string(),
+                        fee => format!("Fee: A fee of {fee} will be added with the transfer"),
+                    };
+                    self.state.add_tx.data.add_tx_status(status);
+                    self.state.popup = PopupState::Nothing;
+                }
+                Err(err) => self.state.add_tx.data.add_tx_status(err.to_string()),
+            },
+            _ => {}
+        }
+    }
+
+    /// Opens the popup for the exchange rate of the Add Tx transfer with the current rate filled in
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_exchange_rate_popup(&mut self) {
+        self.state.popup =
+            PopupState::ExchangeRate(self.state.add_tx.data.get_exchange_rate().to_string());
+    }
+
+    /// Handle key inputs for the exchange rate popup. The popup stays open if the rate is invalid
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_exchange_rate_popup(&mut self) {
+        let PopupState::ExchangeRate(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => match self.state.add_tx.data.set_exchange_rate(input) {
+                Ok(()) => {
+                    let status = match self.state.add_tx.data.get_exchange_rate() {
+                        "" => "Exchange Rate: Transfer no longer has an exchange rate".to_string(),
+                        rate => {
+                            format!("Exchange Rate: The To Method receives the amount × {rate}")
+                        }
+                    };
+                    self.state.add_tx.data.add_tx_status(status);
+                    self.state.popup = PopupState::Nothing;
+                }
+                Err(err) => self.state.add_tx.data.add_tx_status(err.to_string()),
+            },
+            _ => {}
+        }
+    }
+
+    /// Undoes the last edit on the selected field of Add Tx or Search
+    #[cfg(not(tarpaulin_include))]
+    pub fn undo_field_edit(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => {
+                if !self.state.add_tx.data.undo_field(&self.state.add_tx.tab) {
+                    self.state
+                        .add_tx
+                        .data
+                        .add_tx_status("Info: Nothing to undo on this field.".to_string());
+                }
+            }
+            CurrentUi::Search => {
+                if !self.state.search.data.undo_field(&self.state.search.tab) {
+                    self.state
+                        .search
+                        .data
+                        .add_tx_status("Info: Nothing to undo on this field.".to_string());
+                }
+            }
+            _ => {}
+        }
+        self.check_autofill();
+    }
+
+    /// Removes the whole value of the selected field of Add Tx or Search
+    #[cfg(not(tarpaulin_include))]
+    pub fn clear_field(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self.state.add_tx.data.clear_field(&self.state.add_tx.tab),
+            CurrentUi::Search => self.state.search.data.clear_field(&self.state.search.tab),
+            _ => {}
+        }
+        self.check_autofill();
+    }
+
+    /// Takes the autofill value and adds it to the relevant field
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_autofill(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self
+                .state
+                .add_tx
+                .data
+                .accept_autofill(&self.state.add_tx.tab),
+            CurrentUi::Search => self
+                .state
+                .search
+                .data
+                .accept_autofill(&self.state.search.tab),
+            _ => {}
+        }
+    }
+
+    /// No field selected on add tx or search but enter is pressed then
+    /// select the Date field
+    #[cfg(not(tarpaulin_include))]
+    pub fn select_date_field(&mut self) {
+        match self.state.page {
+            CurrentUi::AddTx => self.state.add_tx.tab = TxTab::Date,
+            CurrentUi::Search => self.state.search.tab = TxTab::Date,
+            _ => {}
+        }
+        self.go_correct_index();
+    }
+
+    /// Reverses the direction of the sorted column on the Home table. Once both directions
+    /// were used, moves to the next column. The selected tx stays selected
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_home_sort(&mut self) {
+        if self.state.home.sort_direction == self.state.home.sort.default_direction() {
+            self.state.home.sort_direction = self.state.home.sort_direction.reverse();
+        } else {
+            self.state.home.sort = self.state.home.sort.next_type();
+            self.state.home.sort_direction = self.state.home.sort.default_direction();
+        }
+
+        let selected_id = self
+            .state
+            .home
+            .table
+            .state
+            .selected()
+            .map(|index| self.state.home.tx_data.get_id_num(index));
+
+        if !self.is_home_chronological() {
+            self.state.home.tx_data.load_all(self.conn);
+        }
+        self.state
+            .home
+            .tx_data
+            .sort_txs(&self.state.home.sort, &self.state.home.sort_direction);
+        self.state.home.table = TableData::new(self.state.home.tx_data.get_txs());
+
+        if let Some(id_num) = selected_id {
+            self.state
+                .home
+                .table
+                .state
+                .select(self.state.home.tx_data.get_tx_position(id_num));
+        }
+        self.reload_home_balance_data();
+    }
+
+    /// Shows or hides the filter bar of the Home page. Hiding it removes all filters
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_home_filter(&mut self) {
+        self.state.home.filter.shown = !self.state.home.filter.shown;
+        self.state.home.filter.reload(self.conn);
+
+        if !self.state.home.filter.shown
+            && [
+                HomeTab::TagFilter,
+                HomeTab::MethodFilter,
+                HomeTab::TypeFilter,
+            ]
+            .contains(&self.state.home.tab)
+        {
+            self.state.home.tab = HomeTab::Months;
+        }
+        if self.state.home.tab == HomeTab::Table {
+            self.state.home.tab = HomeTab::Months;
+        }
+        self.filter_home_table();
+    }
+
+    /// Shows or hides the day header rows on the Home table. The rows are only grouped
+    /// while the table is sorted by date
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_home_group_days(&mut self) {
+        self.state.home.grouped = !self.state.home.grouped;
+        self.state.home.collapsed_days.clear();
+
+        // the final day of a window could continue on the next one
+        if self.state.home.grouped {
+            self.fetch_home_txs(true);
+        }
+    }
+
+    /// Shows or hides a subtotal row after each day on the Home table with the day's income
+    /// and expense. The rows are only added while the table is sorted by date
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_home_subtotals(&mut self) {
+        self.state.home.subtotals = !self.state.home.subtotals;
+
+        // the final day of a window could continue on the next one
+        if self.state.home.subtotals {
+            self.fetch_home_txs(true);
+        }
+    }
+
+    /// Collapses or expands the day of the selected tx on the Home table
+    #[cfg(not(tarpaulin_include))]
+    pub fn switch_home_day_collapse(&mut self) {
+        if self.state.home.tab != HomeTab::Table || self.get_home_day_groups().is_none() {
+            return;
+        }
+
+        if let Some(index) = self.state.home.table.state.selected() {
+            let date = self.state.home.table.items[index][0].clone();
+
+            if !self.state.home.collapsed_days.remove(&date) {
+                self.state.home.collapsed_days.insert(date);
+            }
+            self.select_home_day_start();
+            self.reload_home_balance_data();
+        }
+    }
+
+    /// Returns the day groups of the Home table rows if the rows are grouped
+    #[cfg(not(tarpaulin_include))]
+    fn get_home_day_groups(&self) -> Option<Vec<Range<usize>>> {
+        if self.state.home.grouped && self.state.home.sort == HomeSortingType::ByDate {
+            Some(get_day_groups(&self.state.home.table.items))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the rows of the day of the given row if the day is collapsed
+    #[cfg(not(tarpaulin_include))]
+    fn get_collapsed_home_day(&self, index: usize) -> Option<Range<usize>> {
+        self.get_home_day_groups()?
+            .into_iter()
+            .find(|day| day.contains(&index))
+            .filter(|day| {
+                self.state
+                    .home
+                    .collapsed_days
+                    .contains(&self.state.home.table.items[day.start][0])
+            })
+    }
+
+    /// The final row of the Home table that can be selected. A collapsed final day
+    /// can only be selected by its first row
+    #[cfg(not(tarpaulin_include))]
+    fn get_home_last_row(&self) -> Option<usize> {
+        let last_row = self.state.home.table.items.len().checked_sub(1)?;
+
+        match self.get_collapsed_home_day(last_row) {
+            Some(day) => Some(day.start),
+            None => Some(last_row),
+        }
+    }
+
+    /// Moves the selection to the first row of the day if the selected day is collapsed
+    #[cfg(not(tarpaulin_include))]
+    fn select_home_day_start(&mut self) {
+        if let Some(index) = self.state.home.table.state.selected() {
+            if let Some(day) = self.get_collapsed_home_day(index) {
+                self.state.home.table.state.select(Some(day.start));
+            }
+        }
+    }
+
+    /// Starts typing to the text filter of the Home table if the table is selected
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_home_text_filter(&mut self) {
+        if self.state.home.tab == HomeTab::Table {
+            self.state.home.filter.typing = true;
+        }
+    }
+
+    /// Whether the key presses should go to the Home table text filter
+    #[cfg(not(tarpaulin_include))]
+    pub fn is_home_filter_typing(&self) -> bool {
+        self.state.home.filter.typing
+    }
+
+    /// Handles the key presses while typing to the Home table text filter. The shown txs
+    /// are filtered after every change and Esc removes the filter
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_home_text_filter(&mut self) {
+        match self.key.code {
+            KeyCode::Char(a) => self.state.home.filter.text.push(a),
+            KeyCode::Backspace => {
+                if self.state.home.filter.text.pop().is_none() {
+                    return;
+                }
+            }
+            KeyCode::Esc => {
+                self.state.home.filter.typing = false;
+                self.state.home.filter.text.clear();
+            }
+            KeyCode::Enter => {
+                self.state.home.filter.typing = false;
+                return;
+            }
+            _ => return,
+        }
+
+        self.filter_home_table();
+
+        if !self.state.home.table.items.is_empty() {
+            self.state.home.table.state.select(Some(0));
+        }
+        self.reload_home_balance_data();
+    }
+
+    /// Switches whether the balance and the income/expense rows only count the filtered txs
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_home_filter_balance(&mut self) {
+        if !self.state.home.filter.shown {
+            return;
+        }
+        self.state.home.filter.filter_balance = !self.state.home.filter.filter_balance;
+        let selected = self.state.home.table.state.selected();
+        self.state.home.tx_data.filter_txs(&self.state.home.filter);
+        self.state.home.table.state.select(selected);
+        self.reload_home_balance_data();
+    }
+
+    /// Whether the Home table is showing the txs in the same order they were added in
+    fn is_home_chronological(&self) -> bool {
+        self.state.home.sort == HomeSortingType::ByDate
+            && self.state.home.sort_direction == SortingDirection::Ascending
+    }
+
+    /// Reverses the direction of the sorted column on the summary page. Once both directions
+    /// were used, moves to the next column
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_summary_sort(&mut self) {
+        // the largest transactions are always sorted by their amount
+        if self.state.summary.largest_txs {
+            return;
+        }
+
+        if self.state.summary.sort_direction == self.state.summary.sort.default_direction() {
+            self.state.summary.sort_direction = self.state.summary.sort_direction.reverse();
+        } else {
+            self.state.summary.sort = self.state.summary.sort.next_type();
+            self.state.summary.sort_direction = self.state.summary.sort.default_direction();
+        }
+        self.reload_summary_table();
+    }
+
+    /// Creates the summary table again with the current sorting and expanded tags
+    /// while keeping the selected row
+    #[cfg(not(tarpaulin_include))]
+    fn reload_summary_table(&mut self) {
+        let sorted_data = if self.state.summary.largest_txs {
+            self.state.summary.data.get_largest_txs(
+                &self.state.summary.modes,
+                self.state.summary.months.index,
+                self.state.summary.years.index,
+            )
+        } else {
+            self.state.summary.data.get_table_data(
+                &self.state.summary.modes,
+                self.state.summary.months.index,
+                self.state.summary.years.index,
+                &self.state.summary.sort,
+                &self.state.summary.sort_direction,
+                &self.state.summary.expanded_tags,
+            )
+        };
+        let selection_status = self.state.summary.table.state.selected();
+        self.total_tags = sorted_data.len();
+        self.state.summary.table = TableData::new(sorted_data);
+        self.state.summary.table.state.select(selection_status);
+    }
+
+    /// If Enter is pressed on Summary page while a tag is selected
+    /// go to search page and search for it. Parent tags get expanded or collapsed instead
+    #[cfg(not(tarpaulin_include))]
+    pub fn search_tag(&mut self) {
+        if self.state.summary.largest_txs {
+            self.go_summary_tx();
+            return;
+        }
+
+        if let SummaryTab::Table = self.state.summary.tab {
+            if let Some(index) = self.state.summary.table.state.selected() {
+                let tag_name = &self.state.summary.table.items[index][0];
+
+                let parent_tags = self.state.summary.data.get_parent_tags(
+                    &self.state.summary.modes,
+                    self.state.summary.months.index,
+                    self.state.summary.years.index,
+                );
+
+                if parent_tags.contains(tag_name) {
+                    if !self.state.summary.expanded_tags.remove(tag_name) {
+                        self.state
+                            .summary
+                            .expanded_tags
+                            .insert(tag_name.to_string());
+                    }
+                    self.reload_summary_table();
+                    return;
+                }
+
+                let search_param = TxData::custom("", "", "", "", "", "", tag_name, 0);
+                self.state.search.data = search_param;
+                self.go_search();
+                self.search_tx();
+            }
+        }
+    }
+
+    /// Opens a popup with the lifetime statistics of the selected tag on the Summary table
+    #[cfg(not(tarpaulin_include))]
+    pub fn show_summary_tag_stats(&mut self) {
+        if self.state.summary.largest_txs {
+            return;
+        }
+
+        if let SummaryTab::Table = self.state.summary.tab {
+            if let Some(index) = self.state.summary.table.state.selected() {
+                let tag_name = &self.state.summary.table.items[index][0];
+
+                if let Some(tag_stats) = self.state.summary.data.get_tag_stats(tag_name) {
+                    self.state.popup = PopupState::TagStats(tag_stats.get_text());
+                }
+            }
+        }
+    }
+
+    /// Opens the Home page on the month of the selected tx of the largest transactions table
+    /// with the tx selected
+    #[cfg(not(tarpaulin_include))]
+    fn go_summary_tx(&mut self) {
+        if let SummaryTab::Table = self.state.summary.tab {
+            if let Some(index) = self.state.summary.table.state.selected() {
+                let tx = &self.state.summary.table.items[index];
+                let date = NaiveDate::parse_from_str(&tx[0], "%Y-%m-%d").unwrap();
+                let id_num: i32 = tx[5].parse().unwrap();
+
+                if !YEARS.iter().any(|year| *year == date.year().to_string()) {
+                    return;
+                }
+
+                self.select_home_period(date);
+
+                self.fetch_home_txs(true);
+                let tx_index = self.state.home.tx_data.get_tx_position(id_num);
+
+                self.state.home.table.state.select(tx_index);
+                self.state.home.tab = HomeTab::Table;
+                self.go_home();
+            }
+        }
+    }
+
+    /// Opens the popup for showing the txs of a custom date range on the Home page
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_date_range_popup(&mut self) {
+        self.state.popup = PopupState::DateRange(String::new());
+    }
+
+    /// Handles the key presses of the date range popup. An empty input removes the range
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_date_range_popup(&mut self) {
+        let PopupState::DateRange(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => {
+                if input.trim().is_empty() {
+                    self.state.popup = PopupState::Nothing;
+                    self.reload_home_period();
+                    return;
+                }
+
+                match parse_date_range(
+                    input,
+                    self.state.home.months.index,
+                    self.state.home.years.index,
+                ) {
+                    Ok(range) => {
+                        self.state.popup = PopupState::Nothing;
+                        self.state.home.range = Some(range);
+                        self.state.home.tab = HomeTab::Months;
+                        self.state.home.table.state.select(None);
+                        self.reload_home_table();
+                    }
+                    Err(err) => self.state.popup = PopupState::DateRangeFailed(err.to_string()),
+                }
             }
             _ => {}
         }
     }
 
-    /// Takes the autofill value and adds it to the relevant field
+    /// Opens the popup for adding a tx from a single line on the Home page
     #[cfg(not(tarpaulin_include))]
-    pub fn do_autofill(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => self.add_tx_data.accept_autofill(self.add_tx_tab),
-            CurrentUi::Search => self.search_data.accept_autofill(self.search_tab),
-            _ => {}
-        }
+    pub fn do_quick_add_popup(&mut self) {
+        self.state.popup = PopupState::QuickAdd(String::new());
     }
 
-    /// No field selected on add tx or search but enter is pressed then
-    /// select the Date field
+    /// Handles the key presses of the quick add popup. The tx is added once the input is accepted
     #[cfg(not(tarpaulin_include))]
-    pub fn select_date_field(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => *self.add_tx_tab = TxTab::Date,
-            CurrentUi::Search => *self.search_tab = TxTab::Date,
+    pub fn handle_quick_add_popup(&mut self) {
+        let PopupState::QuickAdd(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => {
+                let status = parse_quick_add(
+                    input,
+                    self.state.home.months.index,
+                    self.state.home.years.index,
+                    self.conn,
+                )
+                .map_err(|err| err.to_string())
+                .and_then(|mut tx_data| {
+                    if let VerifyingOutput::NotAccepted(err) =
+                        tx_data.check_tag_constraints(self.config)
+                    {
+                        return Err(err.to_string());
+                    }
+                    let added_tx = tx_data.repeated();
+                    tx_data.add_tx(self.conn).map(|id_nums| (added_tx, id_nums))
+                });
+
+                match status {
+                    Ok((added_tx, id_nums)) => {
+                        self.state.add_tx.last_added_tx = Some(added_tx);
+                        for id_num in id_nums {
+                            self.emit_event(&RexEvent::tx_added(id_num, self.conn));
+                        }
+                        self.state.popup = PopupState::Nothing;
+                        self.reload_home_table();
+                        self.reload_chart_data();
+                        self.reload_summary_data();
+                        self.reload_all_tags();
+                        self.reset_search_data();
+                        self.reload_activity_table();
+                    }
+                    Err(err) => self.state.popup = PopupState::QuickAddFailed(err),
+                }
+            }
             _ => {}
         }
-        self.go_correct_index();
     }
 
-    /// Cycles through tag, income, expense table sorting on summary page
+    /// Opens the popup for selecting the first tx on or after a date on the Home table
     #[cfg(not(tarpaulin_include))]
-    pub fn change_summary_sort(&mut self) {
-        *self.summary_sort = self.summary_sort.next_type();
-        let summary_data = self.summary_table.items.clone();
-        let sorted_data = sort_table_data(summary_data, self.summary_sort);
-        let selection_status = self.summary_table.state.selected();
-        *self.summary_table = TableData::new(sorted_data);
-        self.summary_table.state.select(selection_status);
+    pub fn do_jump_date_popup(&mut self) {
+        self.state.popup = PopupState::JumpToDate(String::new());
     }
 
-    /// If Enter is pressed on Summary page while a tag is selected
-    /// go to search page and search for it
+    /// Handles the key presses of the go to date popup
     #[cfg(not(tarpaulin_include))]
-    pub fn search_tag(&mut self) {
-        if let SummaryTab::Table = self.summary_tab {
-            if let Some(index) = self.summary_table.state.selected() {
-                let tag_name = &self.summary_table.items[index][0];
-                let search_param = TxData::custom("", "", "", "", "", "", tag_name, 0);
-                *self.search_data = search_param;
-                self.go_search();
-                self.search_tx();
+    pub fn handle_jump_date_popup(&mut self) {
+        let PopupState::JumpToDate(input) = &mut self.state.popup else {
+            return;
+        };
+
+        match self.key.code {
+            KeyCode::Char(a) => input.push(a),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.state.popup = PopupState::Nothing,
+            KeyCode::Enter => {
+                let input = input.to_string();
+                self.state.popup = match self.jump_to_date(&input) {
+                    Ok(()) => PopupState::Nothing,
+                    Err(err) => PopupState::JumpFailed(err.to_string()),
+                };
             }
+            _ => {}
         }
     }
 
+    /// Selects the first tx on or after the given date on the Home table.
+    /// Moves to the month or the week of the date first if it's not the selected one
+    #[cfg(not(tarpaulin_include))]
+    fn jump_to_date(&mut self, input: &str) -> Result<(), JumpDateError> {
+        let date = parse_jump_date(
+            input,
+            self.state.home.months.index,
+            self.state.home.years.index,
+        )?;
+
+        self.select_home_period(date);
+        self.fetch_home_txs(true);
+
+        let Some(tx_index) = self.state.home.tx_data.get_date_position(date) else {
+            return Err(JumpDateError::NoTxFound(date));
+        };
+
+        self.state.home.table.state.select(Some(tx_index));
+        self.state.home.tab = HomeTab::Table;
+        self.reload_home_balance_data();
+        Ok(())
+    }
+
     /// Handle keypress when deletion popup is turned on
     #[cfg(not(tarpaulin_include))]
     pub fn handle_deletion_popup(&mut self) {
         match self.key.code {
-            KeyCode::Left | KeyCode::Right => *self.deletion_status = self.deletion_status.next(),
-            KeyCode::Enter => match self.deletion_status {
-                DeletionStatus::Yes => match self.page {
+            KeyCode::Left | KeyCode::Right => {
+                self.state.deletion_status = self.state.deletion_status.next()
+            }
+            KeyCode::Enter => match self.state.deletion_status {
+                DeletionStatus::Yes => match self.state.page {
                     CurrentUi::Home => {
-                        self.home_delete_tx();
-                        *self.popup = PopupState::Nothing;
+                        let marked_deletion =
+                            matches!(self.state.popup, PopupState::MarkedTxDeletion(_));
+                        self.state.popup = PopupState::Nothing;
+                        if marked_deletion {
+                            self.home_delete_marked_txs();
+                        } else {
+                            self.home_delete_tx();
+                        }
                     }
                     CurrentUi::Search => {
                         self.search_delete_tx();
-                        *self.popup = PopupState::Nothing;
+                        self.state.popup = PopupState::Nothing;
                     }
                     _ => {}
                 },
-                DeletionStatus::No => *self.popup = PopupState::Nothing,
+                DeletionStatus::No => self.state.popup = PopupState::Nothing,
+            },
+            _ => {}
+        }
+    }
+
+    /// Adds the selected tx of the Trash page back with its original ID if it's still free
+    #[cfg(not(tarpaulin_include))]
+    pub fn restore_trash_tx(&mut self) {
+        let Some(index) = self.state.trash.table.state.selected() else {
+            return;
+        };
+        let trash_id = self.state.trash.table.items[index][7].parse().unwrap();
+
+        match restore_tx(trash_id, self.conn) {
+            Ok(id_num) => {
+                self.emit_event(&RexEvent::tx_added(id_num, self.conn));
+                self.reload_trash_table();
+                self.reload_home_table();
+                self.reload_chart_data();
+                self.reload_summary_data();
+                self.reload_all_tags();
+                self.reset_search_data();
+                self.reload_activity_table();
+            }
+            Err(e) => self.state.popup = PopupState::TrashFailed(e.to_string()),
+        }
+    }
+
+    /// Turns on the confirmation popup for removing the selected tx from the trash for good
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_trash_purge_popup(&mut self) {
+        if self.state.trash.table.state.selected().is_some() {
+            self.state.deletion_status = DeletionStatus::No;
+            self.state.popup = PopupState::TrashPurge;
+        }
+    }
+
+    /// Turns on the confirmation popup for removing every tx from the trash for good
+    #[cfg(not(tarpaulin_include))]
+    pub fn do_empty_trash_popup(&mut self) {
+        if !self.state.trash.table.items.is_empty() {
+            self.state.deletion_status = DeletionStatus::No;
+            self.state.popup = PopupState::EmptyTrash;
+        }
+    }
+
+    /// Handles the key presses of the trash purge and the empty trash confirmation popup
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_trash_purge_popup(&mut self) {
+        match self.key.code {
+            KeyCode::Left | KeyCode::Right => {
+                self.state.deletion_status = self.state.deletion_status.next()
+            }
+            KeyCode::Enter => match self.state.deletion_status {
+                DeletionStatus::Yes => {
+                    let status = match (&self.state.popup, self.state.trash.table.state.selected())
+                    {
+                        (PopupState::EmptyTrash, _) => empty_trash(self.conn),
+                        (_, Some(index)) => delete_trashed_tx(
+                            self.state.trash.table.items[index][7].parse().unwrap(),
+                            self.conn,
+                        ),
+                        (_, None) => Ok(()),
+                    };
+
+                    match status {
+                        Ok(()) => {
+                            self.state.popup = PopupState::Nothing;
+                            self.reload_trash_table();
+                        }
+                        Err(e) => self.state.popup = PopupState::TrashFailed(e.to_string()),
+                    }
+                }
+                DeletionStatus::No => self.state.popup = PopupState::Nothing,
             },
             _ => {}
         }
@@ -845,22 +3238,22 @@ impl<'a> InputKeyHandler<'a> {
     /// Cycles through available date types
     #[cfg(not(tarpaulin_include))]
     pub fn change_search_date_type(&mut self) {
-        *self.search_date_type = self.search_date_type.next();
-        self.search_data.clear_date();
+        self.state.search.date_type = self.state.search.date_type.next();
+        self.state.search.data.clear_date();
     }
 
     /// Start editing tx from a search result
     #[cfg(not(tarpaulin_include))]
     pub fn search_edit_tx(&mut self) {
-        if let Some(a) = self.search_table.state.selected() {
-            let target_data = &self.search_txs.get_tx(a);
-            let target_id_num = self.search_txs.get_id_num(a);
+        if let Some(a) = self.state.search.table.state.selected() {
+            let target_data = &self.state.search.txs.get_tx(a);
+            let target_id_num = self.state.search.txs.get_id_num(a);
             let tx_type = &target_data[4];
 
             // based on what kind of transaction is selected, passes the tx data to the struct
             // and changes the current interface
             if tx_type != "Transfer" {
-                *self.add_tx_data = TxData::custom(
+                self.state.add_tx.data = TxData::custom(
                     &target_data[0],
                     &target_data[1],
                     &target_data[2],
@@ -870,13 +3263,13 @@ impl<'a> InputKeyHandler<'a> {
                     &target_data[5],
                     target_id_num,
                 );
-                *self.page = CurrentUi::AddTx;
+                self.state.page = CurrentUi::AddTx;
             } else {
                 let splitted_method = target_data[2].split(" to ").collect::<Vec<&str>>();
                 let from_method = splitted_method[0];
                 let to_method = splitted_method[1];
 
-                *self.add_tx_data = TxData::custom(
+                self.state.add_tx.data = TxData::custom(
                     &target_data[0],
                     &target_data[1],
                     from_method,
@@ -886,7 +3279,7 @@ impl<'a> InputKeyHandler<'a> {
                     &target_data[5],
                     target_id_num,
                 );
-                *self.page = CurrentUi::AddTx;
+                self.state.page = CurrentUi::AddTx;
             }
             self.reload_add_tx_balance_load();
         }
@@ -895,18 +3288,25 @@ impl<'a> InputKeyHandler<'a> {
     /// Delete a transaction from search page
     #[cfg(not(tarpaulin_include))]
     pub fn search_delete_tx(&mut self) {
-        if let Some(index) = self.search_table.state.selected() {
-            let status = self.search_txs.del_tx(index, self.conn);
+        if let Some(index) = self.state.search.table.state.selected() {
+            let id_num = self.state.search.txs.get_id_num(index);
+            let status = self.state.search.txs.del_tx(index, self.conn);
             match status {
                 Ok(()) => {
+                    self.emit_event(&RexEvent::TxDeleted {
+                        id_nums: vec![id_num],
+                    });
+
                     // transaction deleted so reload the data again
                     self.reload_home_table();
                     self.reload_chart_data();
                     self.reload_summary_data();
+                    self.reload_all_tags();
                     self.reset_search_data();
+                    self.reload_activity_table();
                 }
                 Err(err) => {
-                    *self.popup =
+                    self.state.popup =
                         PopupState::DeleteFailed(TxUpdateError::FailedDeleteTx(err).to_string());
                 }
             }
@@ -915,22 +3315,27 @@ impl<'a> InputKeyHandler<'a> {
 
     #[cfg(not(tarpaulin_include))]
     pub fn switch_tx_index_up(&mut self) {
-        if let Some(index) = self.table.state.selected() {
+        // the position of the txs can only be switched while they are shown chronologically
+        if !self.is_home_chronological() {
+            return;
+        }
+
+        if let Some(index) = self.state.home.table.state.selected() {
             // Don't do anything if there is 1 or less items or is selecting the first index which can't be moved up
-            if self.table.items.len() <= 1 || index == 0 {
+            if self.state.home.table.items.len() <= 1 || index == 0 {
                 return;
             }
 
-            let selected_tx = self.all_tx_data.get_tx(index);
-            let previous_tx = self.all_tx_data.get_tx(index - 1);
+            let selected_tx = self.state.home.tx_data.get_tx(index);
+            let previous_tx = self.state.home.tx_data.get_tx(index - 1);
 
             if selected_tx[0] != previous_tx[0] {
                 // If both are not in the same date, no switching can happen
                 return;
             }
 
-            let selected_tx_id = self.all_tx_data.get_id_num(index);
-            let previous_tx_id = self.all_tx_data.get_id_num(index - 1);
+            let selected_tx_id = self.state.home.tx_data.get_id_num(index);
+            let previous_tx_id = self.state.home.tx_data.get_id_num(index - 1);
 
             switch_tx_index(
                 selected_tx_id,
@@ -942,53 +3347,86 @@ impl<'a> InputKeyHandler<'a> {
 
             self.reload_home_table();
             self.reload_activity_table();
-            self.table.state.select(Some(index - 1));
+            self.state.home.table.state.select(Some(index - 1));
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     pub fn switch_tx_index_down(&mut self) {
-        if let Some(index) = self.table.state.selected() {
+        if !self.is_home_chronological() {
+            return;
+        }
+
+        if let Some(index) = self.state.home.table.state.selected() {
             // Don't do anything if there is 1 or less items or is selecting the last index which can't be moved up
-            if self.table.items.len() <= 1 || index == self.table.items.len() - 1 {
+            if self.state.home.table.items.len() <= 1
+                || index == self.state.home.table.items.len() - 1
+            {
                 return;
             }
 
-            let selected_tx = self.all_tx_data.get_tx(index);
-            let next_tx = self.all_tx_data.get_tx(index + 1);
+            let selected_tx = self.state.home.tx_data.get_tx(index);
+            let next_tx = self.state.home.tx_data.get_tx(index + 1);
 
             if selected_tx[0] != next_tx[0] {
                 // If both are not in the same date, no switching can happen
                 return;
             }
 
-            let selected_tx_id = self.all_tx_data.get_id_num(index);
-            let next_tx_id = self.all_tx_data.get_id_num(index + 1);
+            let selected_tx_id = self.state.home.tx_data.get_id_num(index);
+            let next_tx_id = self.state.home.tx_data.get_id_num(index + 1);
 
             switch_tx_index(selected_tx_id, next_tx_id, selected_tx, next_tx, self.conn);
 
             self.reload_home_table();
             self.reload_activity_table();
-            self.table.state.select(Some(index + 1));
+            self.state.home.table.state.select(Some(index + 1));
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     /// Opens a popup that shows the details of the selected transaction on the Home page
     pub fn show_home_tx_details(&mut self) {
-        if let Some(index) = self.table.state.selected() {
-            let selected_tx = self.all_tx_data.get_tx(index);
+        if let Some(index) = self.state.home.table.state.selected() {
+            let selected_tx = self.state.home.tx_data.get_tx(index);
             let tx_details = &selected_tx[1];
+            let id_num = self.state.home.tx_data.get_id_num(index);
+
+            // a split tx keeps its note on the first part
+            let split_parts = get_split_parts(id_num, self.conn);
+            let tx_note = get_tx_note(*split_parts.first().unwrap_or(&id_num), self.conn);
+
+            let mut popup_text = tx_details.to_string();
+
+            if let Some(split_details) = get_split_details(id_num, self.conn) {
+                popup_text = format!("{popup_text}\n\n{split_details}");
+            }
+
+            if !tx_note.is_empty() {
+                popup_text = format!("{popup_text}\n\nNote:\n{tx_note}");
+            }
+
+            if let Some(foreign_amount) = get_tx_foreign_amount(id_num, self.conn) {
+                popup_text = format!(
+                    "{popup_text}\n\nEntered as {} {}",
+                    foreign_amount.amount, foreign_amount.currency
+                );
+            }
 
-            *self.popup = PopupState::ShowDetails(tx_details.to_string());
+            let attachments = get_tx_attachments(id_num, self.conn);
+            if !attachments.is_empty() {
+                popup_text = format!("{popup_text}\n\nAttachments:\n{}", attachments.join("\n"));
+            }
+
+            self.state.popup = PopupState::ShowDetails(popup_text);
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     /// Opens a popup that shows the details of the selected activity tx details on the Activity page
     pub fn show_activity_tx_details(&mut self) {
-        if let Some(index) = self.activity_table.state.selected() {
-            let activity_txs = self.activity_data.get_activity_txs(Some(index));
+        if let Some(index) = self.state.activity.table.state.selected() {
+            let activity_txs = self.state.activity.data.get_activity_txs(Some(index));
 
             let mut popup_text = String::new();
 
@@ -1004,20 +3442,40 @@ impl<'a> InputKeyHandler<'a> {
                 let tx_details = &activity_txs[0][1];
                 popup_text += &format!("Transaction 1: {tx_details}");
             }
-            *self.popup = PopupState::ShowDetails(popup_text);
+            self.state.popup = PopupState::ShowDetails(popup_text);
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     pub fn switch_chart_tx_method_activation(&mut self) {
-        if !*self.chart_hidden_mode {
-            if let ChartTab::TxMethods = self.chart_tab {
-                let selected_index = self.chart_tx_methods.index;
-                let all_tx_methods = get_all_tx_methods(self.conn);
+        if !self.state.chart.hidden_mode {
+            if let ChartTab::TxMethods = self.state.chart.tab {
+                if let ChartView::Tags = self.state.chart.view {
+                    if let Some(selected_tag) = self
+                        .state
+                        .chart
+                        .tags
+                        .titles
+                        .get(self.state.chart.tags.index)
+                    {
+                        let activation_status = self
+                            .state
+                            .chart
+                            .activated_tags
+                            .get_mut(selected_tag)
+                            .unwrap();
+                        *activation_status = !*activation_status;
+                        self.reload_chart_index();
+                    }
+                    return;
+                }
 
-                let selected_method = &all_tx_methods[selected_index];
+                let selected_index = self.state.chart.tx_methods.index;
+                let selected_method = &self.state.chart.tx_methods.titles[selected_index];
                 let activation_status = self
-                    .chart_activated_methods
+                    .state
+                    .chart
+                    .activated_methods
                     .get_mut(selected_method)
                     .unwrap();
                 *activation_status = !*activation_status;
@@ -1026,21 +3484,71 @@ impl<'a> InputKeyHandler<'a> {
         }
     }
 
+    /// Shows only the tx methods of the next tx method group on the chart. After the last group
+    /// every tx method is shown again
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_chart_method_group(&mut self) {
+        if let ChartView::Tags = self.state.chart.view {
+            return;
+        }
+
+        let method_groups = get_tx_method_groups(self.conn);
+
+        let mut groups = method_groups.values().cloned().collect::<Vec<String>>();
+        groups.sort();
+        groups.dedup();
+
+        let shown_methods = self
+            .state
+            .chart
+            .activated_methods
+            .iter()
+            .filter(|(_, activated)| **activated)
+            .map(|(method, _)| method.to_string())
+            .collect::<HashSet<String>>();
+
+        // the group that is being shown is the one whose methods are the only ones activated
+        let current_group = groups.iter().position(|group| {
+            let group_methods = method_groups
+                .iter()
+                .filter(|(method, method_group)| {
+                    *method_group == group
+                        && self.state.chart.activated_methods.contains_key(*method)
+                })
+                .map(|(method, _)| method.to_string())
+                .collect::<HashSet<String>>();
+            group_methods == shown_methods
+        });
+
+        let next_group = match current_group {
+            Some(index) => groups.get(index + 1),
+            None => groups.first(),
+        };
+
+        for (method, activated) in self.state.chart.activated_methods.iter_mut() {
+            *activated = match next_group {
+                Some(group) => method_groups.get(method) == Some(group),
+                None => method != "Total",
+            };
+        }
+        self.reload_chart_index();
+    }
+
     #[cfg(not(tarpaulin_include))]
     pub fn popup_scroll_up(&mut self) {
-        if *self.popup_scroll_position != 0 {
-            *self.popup_scroll_position -= 1;
+        if self.state.popup_scroll_position != 0 {
+            self.state.popup_scroll_position -= 1;
         } else {
-            *self.popup_scroll_position = *self.max_popup_scroll;
+            self.state.popup_scroll_position = self.state.max_popup_scroll;
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     pub fn popup_scroll_down(&mut self) {
-        if self.popup_scroll_position < self.max_popup_scroll {
-            *self.popup_scroll_position += 1;
+        if self.state.popup_scroll_position < self.state.max_popup_scroll {
+            self.state.popup_scroll_position += 1;
         } else {
-            *self.popup_scroll_position = 0;
+            self.state.popup_scroll_position = 0;
         };
     }
 }
@@ -1049,143 +3557,182 @@ impl<'a> InputKeyHandler<'a> {
     /// Handle Arrow Up key press on the Home page
     #[cfg(not(tarpaulin_include))]
     fn do_home_up(&mut self) {
-        match &self.home_tab {
-            HomeTab::Table => {
-                // Do not select any table rows in the table section If
-                // there is no transaction
-                // if arrow key up is pressed and table index is 0, select the Month widget
-                // else just select the upper index of the table
-                if self.all_tx_data.is_tx_empty() {
-                    *self.home_tab = self.home_tab.change_tab_up();
-                } else if self.table.state.selected() == Some(0) {
-                    *self.home_tab = HomeTab::Months;
-                    self.table.state.select(None);
-                } else if !self.all_tx_data.is_tx_empty() {
-                    self.table.previous();
-                }
-            }
-            HomeTab::Years => {
-                // Do not select any table rows in the table section If
-                // there is no transaction
-                if self.all_tx_data.is_tx_empty() {
-                    *self.home_tab = self.home_tab.change_tab_down();
-                } else {
-                    // Move to the selected value on table widget
-                    // to the last row if pressed up on Year section
-                    self.table.state.select(Some(self.table.items.len() - 1));
-                    *self.home_tab = self.home_tab.change_tab_up();
-                }
+        // if arrow key up is pressed and table index is not 0, just select the upper index of the table
+        if self.state.home.tab == HomeTab::Table && !self.state.home.tx_data.is_tx_empty() {
+            if self.state.home.table.state.selected() != Some(0) {
+                self.state.home.table.previous();
+                self.select_home_day_start();
+                self.reload_home_balance_data();
+                return;
+            }
+            self.state.home.table.state.select(None);
+        }
+
+        let filter_shown = self.state.home.filter.shown;
+        let weekly = self.state.home.weekly;
+        let mut new_tab = self.state.home.tab.change_tab_up(filter_shown, weekly);
+
+        // Do not select any table rows in the table section If there is no transaction
+        if new_tab == HomeTab::Table {
+            if self.state.home.tx_data.is_tx_empty() {
+                new_tab = new_tab.change_tab_up(filter_shown, weekly);
+            } else {
+                // Move to the selected value on table widget
+                // to the last row if pressed up on Year section
+                self.fetch_home_txs(true);
+                self.state
+                    .home
+                    .table
+                    .state
+                    .select(Some(self.state.home.table.items.len() - 1));
+                self.select_home_day_start();
             }
-            HomeTab::Months => *self.home_tab = self.home_tab.change_tab_up(),
         }
+
+        self.state.home.tab = new_tab;
         self.reload_home_balance_data();
     }
 
     /// Handle Arrow Down key press on the Home page
     #[cfg(not(tarpaulin_include))]
     fn do_home_down(&mut self) {
-        match &self.home_tab {
-            HomeTab::Table => {
-                // Do not proceed to the table section If
-                // there is no transaction
-                // if arrow key down is pressed and table index is final, select the year widget
-                // else just select the next index of the table
-                if self.all_tx_data.is_tx_empty() {
-                    *self.home_tab = self.home_tab.change_tab_down();
-                } else if self.table.state.selected() == Some(self.table.items.len() - 1) {
-                    *self.home_tab = HomeTab::Years;
-                    self.table.state.select(None);
-                } else if !self.all_tx_data.is_tx_empty() {
-                    self.table.next();
-                }
-            }
-            HomeTab::Months => {
-                // Do not select any table rows in the table section If
-                // there is no transaction
-                if self.all_tx_data.is_tx_empty() {
-                    *self.home_tab = self.home_tab.change_tab_up();
-                } else {
-                    *self.home_tab = self.home_tab.change_tab_down();
-                    self.table.state.select(Some(0));
-                };
+        // if arrow key down is pressed and table index is not final, just select the next index of the table
+        if self.state.home.tab == HomeTab::Table && !self.state.home.tx_data.is_tx_empty() {
+            if self.get_home_last_row() != self.state.home.table.state.selected() {
+                self.state.home.table.next();
+
+                // the rows of a collapsed day are skipped. The day header and subtotal rows
+                // are only added while drawing so the selection never lands on them
+                if let Some(index) = self.state.home.table.state.selected() {
+                    if let Some(day) = self.get_collapsed_home_day(index) {
+                        if day.start != index {
+                            self.state.home.table.state.select(Some(day.end));
+                        }
+                    }
+                }
+
+                // fetch the next window of txs before the selection reaches the end
+                if let Some(index) = self.state.home.table.state.selected() {
+                    if index + TX_LOAD_DISTANCE >= self.state.home.table.items.len() {
+                        self.fetch_home_txs(false);
+                    }
+                }
+
+                self.reload_home_balance_data();
+                return;
+            }
+            self.state.home.table.state.select(None);
+        }
+
+        let filter_shown = self.state.home.filter.shown;
+        let weekly = self.state.home.weekly;
+        let mut new_tab = self.state.home.tab.change_tab_down(filter_shown, weekly);
+
+        // Do not proceed to the table section If there is no transaction
+        if new_tab == HomeTab::Table {
+            if self.state.home.tx_data.is_tx_empty() {
+                new_tab = new_tab.change_tab_down(filter_shown, weekly);
+            } else {
+                self.state.home.table.state.select(Some(0));
             }
-            HomeTab::Years => *self.home_tab = self.home_tab.change_tab_down(),
         }
+
+        self.state.home.tab = new_tab;
         self.reload_home_balance_data();
     }
 
     /// Handle Arrow Up key press on the Summary page
     #[cfg(not(tarpaulin_include))]
     fn do_summary_up(&mut self) {
-        if !*self.summary_hidden_mode {
-            match self.summary_modes.index {
-                0 => match self.summary_tab {
+        if !self.state.summary.hidden_mode {
+            match self.state.summary.modes.index {
+                0 => match self.state.summary.tab {
                     SummaryTab::Table => {
-                        if self.summary_table.state.selected() == Some(0) {
-                            *self.summary_tab = self.summary_tab.change_tab_up_monthly();
+                        if self.state.summary.table.state.selected() == Some(0) {
+                            self.state.summary.tab = self.state.summary.tab.change_tab_up_monthly();
                         } else {
-                            self.summary_table.previous();
+                            self.state.summary.table.previous();
                         }
                     }
                     SummaryTab::ModeSelection => {
                         if self.total_tags > 0 {
-                            self.summary_table.state.select(Some(self.total_tags - 1));
-                            *self.summary_tab = self.summary_tab.change_tab_up_monthly();
+                            self.state
+                                .summary
+                                .table
+                                .state
+                                .select(Some(self.total_tags - 1));
+                            self.state.summary.tab = self.state.summary.tab.change_tab_up_monthly();
                         } else {
-                            *self.summary_tab = self.summary_tab.change_tab_up_monthly();
-                            *self.summary_tab = self.summary_tab.change_tab_up_monthly();
-                            self.summary_table.state.select(None);
+                            self.state.summary.tab = self.state.summary.tab.change_tab_up_monthly();
+                            self.state.summary.tab = self.state.summary.tab.change_tab_up_monthly();
+                            self.state.summary.table.state.select(None);
                         }
                     }
-                    _ => *self.summary_tab = self.summary_tab.change_tab_up_monthly(),
+                    _ => self.state.summary.tab = self.state.summary.tab.change_tab_up_monthly(),
                 },
-                1 => match self.summary_tab {
+                1 => match self.state.summary.tab {
                     SummaryTab::Table => {
-                        if self.summary_table.state.selected() == Some(0) {
-                            *self.summary_tab = self.summary_tab.change_tab_up_yearly();
+                        if self.state.summary.table.state.selected() == Some(0) {
+                            self.state.summary.tab = self.state.summary.tab.change_tab_up_yearly();
                         } else {
-                            self.summary_table.previous();
+                            self.state.summary.table.previous();
                         }
                     }
                     SummaryTab::ModeSelection => {
                         if self.total_tags > 0 {
-                            self.summary_table.state.select(Some(self.total_tags - 1));
-                            *self.summary_tab = self.summary_tab.change_tab_up_yearly();
+                            self.state
+                                .summary
+                                .table
+                                .state
+                                .select(Some(self.total_tags - 1));
+                            self.state.summary.tab = self.state.summary.tab.change_tab_up_yearly();
                         } else {
-                            *self.summary_tab = self.summary_tab.change_tab_up_yearly();
-                            *self.summary_tab = self.summary_tab.change_tab_up_yearly();
-                            self.summary_table.state.select(None);
+                            self.state.summary.tab = self.state.summary.tab.change_tab_up_yearly();
+                            self.state.summary.tab = self.state.summary.tab.change_tab_up_yearly();
+                            self.state.summary.table.state.select(None);
                         }
                     }
-                    _ => *self.summary_tab = self.summary_tab.change_tab_up_yearly(),
+                    _ => self.state.summary.tab = self.state.summary.tab.change_tab_up_yearly(),
                 },
-                2 => match self.summary_tab {
+                2 => match self.state.summary.tab {
                     SummaryTab::Table => {
-                        if self.summary_table.state.selected() == Some(0) {
-                            *self.summary_tab = self.summary_tab.change_tab_up_all_time();
+                        if self.state.summary.table.state.selected() == Some(0) {
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_up_all_time();
                         } else {
-                            self.summary_table.previous();
+                            self.state.summary.table.previous();
                         }
                     }
                     SummaryTab::ModeSelection => {
                         if self.total_tags > 0 {
-                            self.summary_table.state.select(Some(self.total_tags - 1));
-                            *self.summary_tab = self.summary_tab.change_tab_up_all_time();
+                            self.state
+                                .summary
+                                .table
+                                .state
+                                .select(Some(self.total_tags - 1));
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_up_all_time();
                         } else {
-                            *self.summary_tab = self.summary_tab.change_tab_up_all_time();
-                            *self.summary_tab = self.summary_tab.change_tab_up_all_time();
-                            self.summary_table.state.select(None);
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_up_all_time();
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_up_all_time();
+                            self.state.summary.table.state.select(None);
                         }
                     }
-                    _ => {}
+                    _ => self.state.summary.tab = self.state.summary.tab.change_tab_up_all_time(),
                 },
                 _ => {}
             }
         } else if self.total_tags > 0 {
-            if self.summary_table.state.selected() == Some(0) {
-                self.summary_table.state.select(Some(self.total_tags - 1));
+            if self.state.summary.table.state.selected() == Some(0) {
+                self.state
+                    .summary
+                    .table
+                    .state
+                    .select(Some(self.total_tags - 1));
             } else {
-                self.summary_table.previous();
+                self.state.summary.table.previous();
             }
         }
     }
@@ -1193,75 +3740,87 @@ impl<'a> InputKeyHandler<'a> {
     /// Handle Arrow Down key press on the Summary page
     #[cfg(not(tarpaulin_include))]
     fn do_summary_down(&mut self) {
-        if !*self.summary_hidden_mode {
-            match self.summary_modes.index {
-                0 => match self.summary_tab {
+        if !self.state.summary.hidden_mode {
+            match self.state.summary.modes.index {
+                0 => match self.state.summary.tab {
                     SummaryTab::Table => {
-                        if self.summary_table.state.selected() == Some(self.total_tags - 1) {
-                            *self.summary_tab = self.summary_tab.change_tab_down_monthly();
+                        if self.state.summary.table.state.selected() == Some(self.total_tags - 1) {
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_monthly();
                         } else {
-                            self.summary_table.next();
+                            self.state.summary.table.next();
                         }
                     }
                     SummaryTab::Months => {
                         if self.total_tags > 0 {
-                            self.summary_table.state.select(Some(0));
-                            *self.summary_tab = self.summary_tab.change_tab_down_monthly();
+                            self.state.summary.table.state.select(Some(0));
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_monthly();
                         } else {
-                            *self.summary_tab = self.summary_tab.change_tab_down_monthly();
-                            *self.summary_tab = self.summary_tab.change_tab_down_monthly();
-                            self.summary_table.state.select(None);
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_monthly();
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_monthly();
+                            self.state.summary.table.state.select(None);
                         }
                     }
-                    _ => *self.summary_tab = self.summary_tab.change_tab_down_monthly(),
+                    _ => self.state.summary.tab = self.state.summary.tab.change_tab_down_monthly(),
                 },
-                1 => match self.summary_tab {
+                1 => match self.state.summary.tab {
                     SummaryTab::Table => {
-                        if self.summary_table.state.selected() == Some(self.total_tags - 1) {
-                            *self.summary_tab = self.summary_tab.change_tab_down_yearly();
+                        if self.state.summary.table.state.selected() == Some(self.total_tags - 1) {
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_yearly();
                         } else {
-                            self.summary_table.next();
+                            self.state.summary.table.next();
                         }
                     }
                     SummaryTab::Years => {
                         if self.total_tags > 0 {
-                            self.summary_table.state.select(Some(0));
-                            *self.summary_tab = self.summary_tab.change_tab_down_yearly();
+                            self.state.summary.table.state.select(Some(0));
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_yearly();
                         } else {
-                            *self.summary_tab = self.summary_tab.change_tab_down_yearly();
-                            *self.summary_tab = self.summary_tab.change_tab_down_yearly();
-                            self.summary_table.state.select(None);
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_yearly();
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_yearly();
+                            self.state.summary.table.state.select(None);
                         };
                     }
-                    _ => *self.summary_tab = self.summary_tab.change_tab_down_yearly(),
+                    _ => self.state.summary.tab = self.state.summary.tab.change_tab_down_yearly(),
                 },
-                2 => match self.summary_tab {
+                2 => match self.state.summary.tab {
                     SummaryTab::Table => {
-                        if self.summary_table.state.selected() == Some(self.total_tags - 1) {
-                            *self.summary_tab = self.summary_tab.change_tab_down_all_time();
+                        if self.state.summary.table.state.selected() == Some(self.total_tags - 1) {
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_all_time();
                         } else {
-                            self.summary_table.next();
+                            self.state.summary.table.next();
                         }
                     }
-                    SummaryTab::ModeSelection => {
+                    SummaryTab::TxMethods => {
                         if self.total_tags > 0 {
-                            self.summary_table.state.select(Some(0));
-                            *self.summary_tab = self.summary_tab.change_tab_down_all_time();
+                            self.state.summary.table.state.select(Some(0));
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_all_time();
                         } else {
-                            *self.summary_tab = self.summary_tab.change_tab_down_all_time();
-                            *self.summary_tab = self.summary_tab.change_tab_down_all_time();
-                            self.summary_table.state.select(None);
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_all_time();
+                            self.state.summary.tab =
+                                self.state.summary.tab.change_tab_down_all_time();
+                            self.state.summary.table.state.select(None);
                         }
                     }
-                    _ => {}
+                    _ => self.state.summary.tab = self.state.summary.tab.change_tab_down_all_time(),
                 },
                 _ => {}
             }
         } else if self.total_tags > 0 {
-            if self.summary_table.state.selected() == Some(self.total_tags - 1) {
-                self.summary_table.state.select(Some(0));
+            if self.state.summary.table.state.selected() == Some(self.total_tags - 1) {
+                self.state.summary.table.state.select(Some(0));
             } else {
-                self.summary_table.next();
+                self.state.summary.table.next();
             }
         }
     }
@@ -1269,11 +3828,11 @@ impl<'a> InputKeyHandler<'a> {
     /// Handle Arrow Up key press on the chart page
     #[cfg(not(tarpaulin_include))]
     fn do_chart_up(&mut self) {
-        if !*self.chart_hidden_mode {
-            match self.chart_modes.index {
-                0 => *self.chart_tab = self.chart_tab.change_tab_up_monthly(),
-                1 => *self.chart_tab = self.chart_tab.change_tab_up_yearly(),
-                2 => *self.chart_tab = self.chart_tab.change_tab_up_all_time(),
+        if !self.state.chart.hidden_mode {
+            match self.state.chart.modes.index {
+                0 => self.state.chart.tab = self.state.chart.tab.change_tab_up_monthly(),
+                1 => self.state.chart.tab = self.state.chart.tab.change_tab_up_yearly(),
+                2 => self.state.chart.tab = self.state.chart.tab.change_tab_up_all_time(),
                 _ => {}
             }
         }
@@ -1282,11 +3841,11 @@ impl<'a> InputKeyHandler<'a> {
     /// Handle Arrow Down key press on the chart page
     #[cfg(not(tarpaulin_include))]
     fn do_chart_down(&mut self) {
-        if !*self.chart_hidden_mode {
-            match self.chart_modes.index {
-                0 => *self.chart_tab = self.chart_tab.change_tab_down_monthly(),
-                1 => *self.chart_tab = self.chart_tab.change_tab_down_yearly(),
-                2 => *self.chart_tab = self.chart_tab.change_tab_down_all_time(),
+        if !self.state.chart.hidden_mode {
+            match self.state.chart.modes.index {
+                0 => self.state.chart.tab = self.state.chart.tab.change_tab_down_monthly(),
+                1 => self.state.chart.tab = self.state.chart.tab.change_tab_down_yearly(),
+                2 => self.state.chart.tab = self.state.chart.tab.change_tab_down_all_time(),
                 _ => {}
             }
         }
@@ -1297,28 +3856,28 @@ impl<'a> InputKeyHandler<'a> {
     fn check_add_tx_date(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.add_tx_data.check_date(&DateType::Exact);
-                self.add_tx_data.add_tx_status(status.to_string());
+                let status = self.state.add_tx.data.check_date(&DateType::Exact);
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::Details;
+                        self.state.add_tx.tab = TxTab::Details;
                         self.go_correct_index();
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
             KeyCode::Esc => {
-                let status = self.add_tx_data.check_date(&DateType::Exact);
-                self.add_tx_data.add_tx_status(status.to_string());
+                let status = self.state.add_tx.data.check_date(&DateType::Exact);
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::Nothing;
+                        self.state.add_tx.tab = TxTab::Nothing;
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.add_tx_data.edit_date(None),
-            KeyCode::Char(a) => self.add_tx_data.edit_date(Some(a)),
+            KeyCode::Backspace => self.state.add_tx.data.edit_date(None),
+            KeyCode::Char(a) => self.state.add_tx.data.edit_date(Some(a)),
             _ => {}
         }
     }
@@ -1328,12 +3887,12 @@ impl<'a> InputKeyHandler<'a> {
     fn check_add_tx_details(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                *self.add_tx_tab = TxTab::TxType;
+                self.state.add_tx.tab = TxTab::TxType;
                 self.go_correct_index();
             }
-            KeyCode::Esc => *self.add_tx_tab = TxTab::Nothing,
-            KeyCode::Backspace => self.add_tx_data.edit_details(None),
-            KeyCode::Char(a) => self.add_tx_data.edit_details(Some(a)),
+            KeyCode::Esc => self.state.add_tx.tab = TxTab::Nothing,
+            KeyCode::Backspace => self.state.add_tx.data.edit_details(None),
+            KeyCode::Char(a) => self.state.add_tx.data.edit_details(Some(a)),
             _ => {}
         }
     }
@@ -1343,11 +3902,11 @@ impl<'a> InputKeyHandler<'a> {
     fn check_add_tx_type(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.add_tx_data.check_tx_type();
-                self.add_tx_data.add_tx_status(status.to_string());
+                let status = self.state.add_tx.data.check_tx_type();
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::FromMethod;
+                        self.state.add_tx.tab = TxTab::FromMethod;
                         self.go_correct_index();
                         self.reload_add_tx_balance_data();
                     }
@@ -1355,18 +3914,18 @@ impl<'a> InputKeyHandler<'a> {
                 }
             }
             KeyCode::Esc => {
-                let status = self.add_tx_data.check_tx_type();
-                self.add_tx_data.add_tx_status(status.to_string());
+                let status = self.state.add_tx.data.check_tx_type();
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::Nothing;
+                        self.state.add_tx.tab = TxTab::Nothing;
                         self.reload_add_tx_balance_data();
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.add_tx_data.edit_tx_type(None),
-            KeyCode::Char(a) => self.add_tx_data.edit_tx_type(Some(a)),
+            KeyCode::Backspace => self.state.add_tx.data.edit_tx_type(None),
+            KeyCode::Char(a) => self.state.add_tx.data.edit_tx_type(Some(a)),
             _ => {}
         }
     }
@@ -1376,13 +3935,13 @@ impl<'a> InputKeyHandler<'a> {
     fn check_add_tx_from(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.add_tx_data.check_from_method(self.conn);
-                self.add_tx_data.add_tx_status(status.to_string());
+                let status = self.state.add_tx.data.check_from_method(self.conn);
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        match self.add_tx_data.get_tx_type() {
-                            TxType::IncomeExpense => *self.add_tx_tab = TxTab::Amount,
-                            TxType::Transfer => *self.add_tx_tab = TxTab::ToMethod,
+                        match self.state.add_tx.data.get_tx_type() {
+                            TxType::IncomeExpense => self.state.add_tx.tab = TxTab::Amount,
+                            TxType::Transfer => self.state.add_tx.tab = TxTab::ToMethod,
                         }
                         self.go_correct_index();
                     }
@@ -1390,18 +3949,18 @@ impl<'a> InputKeyHandler<'a> {
                 }
             }
             KeyCode::Esc => {
-                let status = self.add_tx_data.check_from_method(self.conn);
-                self.add_tx_data.add_tx_status(status.to_string());
+                let status = self.state.add_tx.data.check_from_method(self.conn);
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::Nothing;
+                        self.state.add_tx.tab = TxTab::Nothing;
                         self.reload_add_tx_balance_data();
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.add_tx_data.edit_from_method(None),
-            KeyCode::Char(a) => self.add_tx_data.edit_from_method(Some(a)),
+            KeyCode::Backspace => self.state.add_tx.data.edit_from_method(None),
+            KeyCode::Char(a) => self.state.add_tx.data.edit_from_method(Some(a)),
             _ => {}
         }
     }
@@ -1411,11 +3970,11 @@ impl<'a> InputKeyHandler<'a> {
     fn check_add_tx_to(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.add_tx_data.check_to_method(self.conn);
-                self.add_tx_data.add_tx_status(status.to_string());
+                let status = self.state.add_tx.data.check_to_method(self.conn);
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::Amount;
+                        self.state.add_tx.tab = TxTab::Amount;
                         self.go_correct_index();
                         self.reload_add_tx_balance_data();
                     }
@@ -1423,18 +3982,18 @@ impl<'a> InputKeyHandler<'a> {
                 }
             }
             KeyCode::Esc => {
-                let status = self.add_tx_data.check_to_method(self.conn);
-                self.add_tx_data.add_tx_status(status.to_string());
+                let status = self.state.add_tx.data.check_to_method(self.conn);
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::Nothing;
+                        self.state.add_tx.tab = TxTab::Nothing;
                         self.reload_add_tx_balance_data();
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.add_tx_data.edit_to_method(None),
-            KeyCode::Char(a) => self.add_tx_data.edit_to_method(Some(a)),
+            KeyCode::Backspace => self.state.add_tx.data.edit_to_method(None),
+            KeyCode::Char(a) => self.state.add_tx.data.edit_to_method(Some(a)),
             _ => {}
         }
     }
@@ -1443,30 +4002,89 @@ impl<'a> InputKeyHandler<'a> {
     fn check_add_tx_amount(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.add_tx_data.check_amount(false, self.conn);
-                self.add_tx_data.add_tx_status(status.to_string());
+                // a converted foreign amount is shown first and gets accepted on the next Enter
+                match self
+                    .state
+                    .add_tx
+                    .data
+                    .convert_foreign_amount(self.config, self.conn)
+                {
+                    Ok(Some(rate)) => {
+                        let amount = self.state.add_tx.data.get_all_texts()[4].to_string();
+                        if let Some(foreign_amount) = self.state.add_tx.data.get_foreign_amount() {
+                            let status = format!(
+                                "Amount: Converted {} {} to {amount} at the rate {rate}. Press Enter to accept",
+                                foreign_amount.amount, foreign_amount.currency
+                            );
+                            self.state.add_tx.data.add_tx_status(status);
+                        }
+                        return;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        self.state.add_tx.data.add_tx_status(e.to_string());
+                        return;
+                    }
+                }
+
+                // the result of a calculation is shown first and gets accepted on the next Enter
+                let is_expression = self.state.add_tx.data.is_amount_expression();
+                // show which balance a percentage or b was calculated from
+                let used_balance = if self.state.add_tx.data.is_balance_amount() {
+                    self.state.add_tx.data.get_from_method_balance(self.conn)
+                } else {
+                    None
+                };
+                let status = self.state.add_tx.data.check_amount(false, self.conn);
                 match status {
+                    VerifyingOutput::Accepted(_) if is_expression => {
+                        let amount = self.state.add_tx.data.get_all_texts()[4].to_string();
+                        let status = match used_balance {
+                            Some(balance) => {
+                                let method = self.state.add_tx.data.get_all_texts()[2].to_string();
+                                format!("Amount: Calculated to {amount} from the {method} balance of {balance}. Press Enter to accept")
+                            }
+                            None => {
+                                format!("Amount: Calculated to {amount}. Press Enter to accept")
+                            }
+                        };
+                        self.state.add_tx.data.add_tx_status(status);
+                        self.go_correct_index();
+                    }
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::Tags;
+                        self.state.add_tx.data.add_tx_status(status.to_string());
+                        self.state.add_tx.tab = TxTab::Tags;
                         self.go_correct_index();
                         self.reload_add_tx_balance_data();
                     }
-                    VerifyingOutput::NotAccepted(_) => {}
+                    VerifyingOutput::NotAccepted(_) => {
+                        self.state.add_tx.data.add_tx_status(status.to_string());
+                    }
                 }
             }
             KeyCode::Esc => {
-                let status = self.add_tx_data.check_amount(false, self.conn);
-                self.add_tx_data.add_tx_status(status.to_string());
+                if let Err(e) = self
+                    .state
+                    .add_tx
+                    .data
+                    .convert_foreign_amount(self.config, self.conn)
+                {
+                    self.state.add_tx.data.add_tx_status(e.to_string());
+                    return;
+                }
+
+                let status = self.state.add_tx.data.check_amount(false, self.conn);
+                self.state.add_tx.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.add_tx_tab = TxTab::Nothing;
+                        self.state.add_tx.tab = TxTab::Nothing;
                         self.reload_add_tx_balance_data();
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.add_tx_data.edit_amount(None),
-            KeyCode::Char(a) => self.add_tx_data.edit_amount(Some(a)),
+            KeyCode::Backspace => self.state.add_tx.data.edit_amount(None),
+            KeyCode::Char(a) => self.state.add_tx.data.edit_amount(Some(a)),
             _ => {}
         }
     }
@@ -1476,11 +4094,17 @@ impl<'a> InputKeyHandler<'a> {
     fn check_add_tx_tags(&mut self) {
         match self.key.code {
             KeyCode::Enter | KeyCode::Esc => {
-                *self.add_tx_tab = TxTab::Nothing;
-                self.add_tx_data.check_tags();
+                self.state.add_tx.data.check_tags();
+                let status = self.state.add_tx.data.check_tag_constraints(self.config);
+
+                if let VerifyingOutput::NotAccepted(_) = status {
+                    self.state.add_tx.data.add_tx_status(status.to_string());
+                } else {
+                    self.state.add_tx.tab = TxTab::Nothing;
+                }
             }
-            KeyCode::Backspace => self.add_tx_data.edit_tags(None),
-            KeyCode::Char(a) => self.add_tx_data.edit_tags(Some(a)),
+            KeyCode::Backspace => self.state.add_tx.data.edit_tags(None),
+            KeyCode::Char(a) => self.state.add_tx.data.edit_tags(Some(a)),
             _ => {}
         }
     }
@@ -1490,28 +4114,36 @@ impl<'a> InputKeyHandler<'a> {
     fn check_search_date(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.search_data.check_date(self.search_date_type);
-                self.search_data.add_tx_status(status.to_string());
+                let status = self
+                    .state
+                    .search
+                    .data
+                    .check_date(&self.state.search.date_type);
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Details;
+                        self.state.search.tab = TxTab::Details;
                         self.go_correct_index();
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
             KeyCode::Esc => {
-                let status = self.search_data.check_date(self.search_date_type);
-                self.search_data.add_tx_status(status.to_string());
+                let status = self
+                    .state
+                    .search
+                    .data
+                    .check_date(&self.state.search.date_type);
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Nothing;
+                        self.state.search.tab = TxTab::Nothing;
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.search_data.edit_date(None),
-            KeyCode::Char(a) => self.search_data.edit_date(Some(a)),
+            KeyCode::Backspace => self.state.search.data.edit_date(None),
+            KeyCode::Char(a) => self.state.search.data.edit_date(Some(a)),
             _ => {}
         }
     }
@@ -1521,12 +4153,12 @@ impl<'a> InputKeyHandler<'a> {
     fn check_search_details(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                *self.search_tab = TxTab::TxType;
+                self.state.search.tab = TxTab::TxType;
                 self.go_correct_index();
             }
-            KeyCode::Esc => *self.search_tab = TxTab::Nothing,
-            KeyCode::Backspace => self.search_data.edit_details(None),
-            KeyCode::Char(a) => self.search_data.edit_details(Some(a)),
+            KeyCode::Esc => self.state.search.tab = TxTab::Nothing,
+            KeyCode::Backspace => self.state.search.data.edit_details(None),
+            KeyCode::Char(a) => self.state.search.data.edit_details(Some(a)),
             _ => {}
         }
     }
@@ -1536,28 +4168,28 @@ impl<'a> InputKeyHandler<'a> {
     fn check_search_type(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.search_data.check_tx_type();
-                self.search_data.add_tx_status(status.to_string());
+                let status = self.state.search.data.check_tx_type();
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::FromMethod;
+                        self.state.search.tab = TxTab::FromMethod;
                         self.go_correct_index();
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
             KeyCode::Esc => {
-                let status = self.search_data.check_tx_type();
-                self.search_data.add_tx_status(status.to_string());
+                let status = self.state.search.data.check_tx_type();
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Nothing;
+                        self.state.search.tab = TxTab::Nothing;
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.search_data.edit_tx_type(None),
-            KeyCode::Char(a) => self.search_data.edit_tx_type(Some(a)),
+            KeyCode::Backspace => self.state.search.data.edit_tx_type(None),
+            KeyCode::Char(a) => self.state.search.data.edit_tx_type(Some(a)),
             _ => {}
         }
     }
@@ -1567,13 +4199,13 @@ impl<'a> InputKeyHandler<'a> {
     fn check_search_from(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.search_data.check_from_method(self.conn);
-                self.search_data.add_tx_status(status.to_string());
+                let status = self.state.search.data.check_from_method(self.conn);
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        match self.search_data.get_tx_type() {
-                            TxType::IncomeExpense => *self.search_tab = TxTab::Amount,
-                            TxType::Transfer => *self.search_tab = TxTab::ToMethod,
+                        match self.state.search.data.get_tx_type() {
+                            TxType::IncomeExpense => self.state.search.tab = TxTab::Amount,
+                            TxType::Transfer => self.state.search.tab = TxTab::ToMethod,
                         }
                         self.go_correct_index();
                     }
@@ -1581,17 +4213,17 @@ impl<'a> InputKeyHandler<'a> {
                 }
             }
             KeyCode::Esc => {
-                let status = self.search_data.check_from_method(self.conn);
-                self.search_data.add_tx_status(status.to_string());
+                let status = self.state.search.data.check_from_method(self.conn);
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Nothing;
+                        self.state.search.tab = TxTab::Nothing;
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.search_data.edit_from_method(None),
-            KeyCode::Char(a) => self.search_data.edit_from_method(Some(a)),
+            KeyCode::Backspace => self.state.search.data.edit_from_method(None),
+            KeyCode::Char(a) => self.state.search.data.edit_from_method(Some(a)),
             _ => {}
         }
     }
@@ -1601,28 +4233,28 @@ impl<'a> InputKeyHandler<'a> {
     fn check_search_to(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.search_data.check_to_method(self.conn);
-                self.search_data.add_tx_status(status.to_string());
+                let status = self.state.search.data.check_to_method(self.conn);
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Amount;
+                        self.state.search.tab = TxTab::Amount;
                         self.go_correct_index();
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
             KeyCode::Esc => {
-                let status = self.search_data.check_to_method(self.conn);
-                self.search_data.add_tx_status(status.to_string());
+                let status = self.state.search.data.check_to_method(self.conn);
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Nothing;
+                        self.state.search.tab = TxTab::Nothing;
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.search_data.edit_to_method(None),
-            KeyCode::Char(a) => self.search_data.edit_to_method(Some(a)),
+            KeyCode::Backspace => self.state.search.data.edit_to_method(None),
+            KeyCode::Char(a) => self.state.search.data.edit_to_method(Some(a)),
             _ => {}
         }
     }
@@ -1632,28 +4264,39 @@ impl<'a> InputKeyHandler<'a> {
     fn check_search_amount(&mut self) {
         match self.key.code {
             KeyCode::Enter => {
-                let status = self.search_data.check_amount(true, self.conn);
-                self.search_data.add_tx_status(status.to_string());
+                // the result of a calculation is shown first and gets accepted on the next Enter
+                let is_expression = self.state.search.data.is_amount_expression();
+                let status = self.state.search.data.check_amount(true, self.conn);
                 match status {
+                    VerifyingOutput::Accepted(_) if is_expression => {
+                        let amount = self.state.search.data.get_all_texts()[4].to_string();
+                        self.state.search.data.add_tx_status(format!(
+                            "Amount: Calculated to {amount}. Press Enter to accept"
+                        ));
+                        self.go_correct_index();
+                    }
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Tags;
+                        self.state.search.data.add_tx_status(status.to_string());
+                        self.state.search.tab = TxTab::Tags;
                         self.go_correct_index();
                     }
-                    VerifyingOutput::NotAccepted(_) => {}
+                    VerifyingOutput::NotAccepted(_) => {
+                        self.state.search.data.add_tx_status(status.to_string());
+                    }
                 }
             }
             KeyCode::Esc => {
-                let status = self.search_data.check_amount(true, self.conn);
-                self.search_data.add_tx_status(status.to_string());
+                let status = self.state.search.data.check_amount(true, self.conn);
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Nothing;
+                        self.state.search.tab = TxTab::Nothing;
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.search_data.edit_amount(None),
-            KeyCode::Char(a) => self.search_data.edit_amount(Some(a)),
+            KeyCode::Backspace => self.state.search.data.edit_amount(None),
+            KeyCode::Char(a) => self.state.search.data.edit_amount(Some(a)),
             _ => {}
         }
     }
@@ -1663,17 +4306,17 @@ impl<'a> InputKeyHandler<'a> {
     fn check_search_tags(&mut self) {
         match self.key.code {
             KeyCode::Enter | KeyCode::Esc => {
-                let status = self.search_data.check_tags_forced(self.conn);
-                self.search_data.add_tx_status(status.to_string());
+                let status = self.state.search.data.check_tags_forced(self.conn);
+                self.state.search.data.add_tx_status(status.to_string());
                 match status {
                     VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
-                        *self.search_tab = TxTab::Nothing;
+                        self.state.search.tab = TxTab::Nothing;
                     }
                     VerifyingOutput::NotAccepted(_) => {}
                 }
             }
-            KeyCode::Backspace => self.search_data.edit_tags(None),
-            KeyCode::Char(a) => self.search_data.edit_tags(Some(a)),
+            KeyCode::Backspace => self.state.search.data.edit_tags(None),
+            KeyCode::Char(a) => self.state.search.data.edit_tags(Some(a)),
             _ => {}
         }
     }
@@ -1681,48 +4324,207 @@ impl<'a> InputKeyHandler<'a> {
     /// Reload Home page's table data by fetching from the DB
     #[cfg(not(tarpaulin_include))]
     fn reload_home_table(&mut self) {
-        *self.all_tx_data =
-            TransactionData::new(self.home_months.index, self.home_years.index, self.conn);
-        *self.table = TableData::new(self.all_tx_data.get_txs());
+        // the txs that were already fetched are fetched again so the selected row stays valid
+        let fetched_txs = self.state.home.table.items.len();
+
+        self.state.home.tx_data = if let Some((from, to)) = self.state.home.range {
+            TransactionData::new_range(from, to, self.conn)
+        } else if self.state.home.weekly {
+            let week_start =
+                get_month_weeks(self.state.home.months.index, self.state.home.years.index)
+                    [self.state.home.weeks.index];
+            TransactionData::new_weekly(week_start, self.conn)
+        } else {
+            TransactionData::new(
+                self.state.home.months.index,
+                self.state.home.years.index,
+                self.conn,
+            )
+        };
+
+        // sorting, filtering, grouping, subtotals and the cleared balance needs every tx of the month
+        if !self.is_home_chronological()
+            || self.state.home.filter.is_active()
+            || self.state.home.grouped
+            || self.state.home.subtotals
+            || self.state.home.cleared_balance
+        {
+            self.state.home.tx_data.load_all(self.conn);
+        }
+        self.state
+            .home
+            .tx_data
+            .set_cleared_balance(self.state.home.cleared_balance, self.conn);
+        while self.state.home.tx_data.get_txs_len() < fetched_txs {
+            if !self.state.home.tx_data.load_more(self.conn) {
+                break;
+            }
+        }
+        self.state.home.tx_data.filter_txs(&self.state.home.filter);
+        self.state
+            .home
+            .tx_data
+            .sort_txs(&self.state.home.sort, &self.state.home.sort_direction);
+        self.state.home.table = TableData::new(self.state.home.tx_data.get_txs());
+        self.retain_shown_marks();
+        self.reload_home_balance_data();
+    }
+
+    /// Selects the month of the date on the Home page alongside the week of it
+    /// on the week view. The table is only reloaded if the period changes
+    #[cfg(not(tarpaulin_include))]
+    fn select_home_period(&mut self, date: NaiveDate) {
+        // the custom range stays if the date is within it
+        if let Some((from, to)) = self.state.home.range {
+            if from <= date && date <= to {
+                return;
+            }
+        }
+
+        let month_index = date.month0() as usize;
+        let year_index = (date.year() - 2022) as usize;
+
+        let mut changed = self.state.home.range.take().is_some();
+
+        if self.state.home.months.index != month_index || self.state.home.years.index != year_index
+        {
+            self.state.home.months.index = month_index;
+            self.state.home.years.index = year_index;
+            self.reload_home_weeks();
+            changed = true;
+        }
+
+        if self.state.home.weekly {
+            let week_index = get_month_weeks(month_index, year_index)
+                .iter()
+                .position(|week_start| (date - *week_start).num_days() < 7)
+                .unwrap();
+
+            if self.state.home.weeks.index != week_index {
+                self.state.home.weeks.index = week_index;
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.reload_home_table();
+        }
+    }
+
+    /// Removes the custom date range of the Home page and shows the selected month or week again
+    #[cfg(not(tarpaulin_include))]
+    fn reload_home_period(&mut self) {
+        self.state.home.range = None;
+        self.reload_home_table();
+    }
+
+    /// Fetches the weeks of the selected Home month again
+    #[cfg(not(tarpaulin_include))]
+    fn reload_home_weeks(&mut self) {
+        self.state.home.weeks =
+            IndexedData::new_weekly(self.state.home.months.index, self.state.home.years.index);
+    }
+
+    /// Removes the marks of the txs that are no longer shown on the Home table
+    #[cfg(not(tarpaulin_include))]
+    fn retain_shown_marks(&mut self) {
+        let shown_ids = (0..self.state.home.table.items.len())
+            .map(|index| self.state.home.tx_data.get_id_num(index))
+            .collect::<HashSet<i32>>();
+        self.state
+            .home
+            .marked_txs
+            .retain(|id_num| shown_ids.contains(id_num));
+    }
+
+    /// Fetches the next window or all the remaining txs of the Home table month
+    /// and adds them to the table while keeping the selected row
+    #[cfg(not(tarpaulin_include))]
+    fn fetch_home_txs(&mut self, fetch_all: bool) {
+        if fetch_all {
+            if self.state.home.tx_data.is_fully_loaded() {
+                return;
+            }
+            self.state.home.tx_data.load_all(self.conn);
+        } else if !self.state.home.tx_data.load_more(self.conn) {
+            return;
+        }
+
+        let selected = self.state.home.table.state.selected();
+        self.state.home.table = TableData::new(self.state.home.tx_data.get_txs());
+        self.state.home.table.state.select(selected);
+    }
+
+    /// Applies the Home filter again on the current month's txs
+    #[cfg(not(tarpaulin_include))]
+    fn filter_home_table(&mut self) {
+        if self.state.home.filter.is_active() {
+            self.state.home.tx_data.load_all(self.conn);
+        }
+        self.state.home.tx_data.filter_txs(&self.state.home.filter);
+        self.state
+            .home
+            .tx_data
+            .sort_txs(&self.state.home.sort, &self.state.home.sort_direction);
+        self.state.home.table = TableData::new(self.state.home.tx_data.get_txs());
+        self.retain_shown_marks();
         self.reload_home_balance_data();
     }
 
     /// Reset summary table data by recreating it from gathered Summary Data
     #[cfg(not(tarpaulin_include))]
     fn reload_summary(&mut self) {
-        let summary_table = self.summary_data.get_table_data(
-            self.summary_modes,
-            self.summary_months.index,
-            self.summary_years.index,
-        );
-        self.total_tags = summary_table.len();
-        *self.summary_table = TableData::new(summary_table);
-        *self.summary_sort = SortingType::ByTags;
+        self.state.summary.reset_table();
+        self.total_tags = self.state.summary.table.items.len();
     }
 
-    /// Reload summary data by fetching from the DB
+    /// Reload summary data by fetching from the DB. The db worker fetches it if there is one
     #[cfg(not(tarpaulin_include))]
     fn reload_summary_data(&mut self) {
-        *self.summary_data = SummaryData::new(self.conn);
+        // the table keeps the old data until the worker sends the new one
+        if let Some(worker) = self.worker {
+            if worker.request(DbRequest::Summary) {
+                self.state.summary.loading = true;
+                return;
+            }
+        }
+
+        let data = SummaryData::new(self.conn);
+        self.state.summary.set_data(data);
+        self.total_tags = self.state.summary.table.items.len();
+    }
+
+    /// Fetch the tags again for the tag suggestions as txs may have added or removed some
+    #[cfg(not(tarpaulin_include))]
+    fn reload_all_tags(&mut self) {
+        self.state.all_tags = get_all_tags(self.conn);
     }
 
-    /// Reload chart data by fetching from the DB
+    /// Reload chart data by fetching from the DB. The db worker fetches it if there is one
     #[cfg(not(tarpaulin_include))]
     fn reload_chart_data(&mut self) {
-        *self.chart_data = ChartData::new(self.conn);
+        if let Some(worker) = self.worker {
+            if worker.request(DbRequest::Chart) {
+                self.state.chart.loading = true;
+                return;
+            }
+        }
+
+        let data = ChartData::new(self.conn);
+        self.state.chart.set_data(data, self.conn);
     }
 
     /// Restart the animation index of the chart
     #[cfg(not(tarpaulin_include))]
     fn reload_chart_index(&mut self) {
-        *self.chart_index = Some(0.0);
+        self.state.chart.index = Some(0.0);
     }
 
     /// Reset all currently shown search related data to nothing
     #[cfg(not(tarpaulin_include))]
     fn reset_search_data(&mut self) {
-        *self.search_table = TableData::new(Vec::new());
-        *self.search_txs = TransactionData::new_search(Vec::new(), Vec::new());
+        self.state.search.table = TableData::new(Vec::new());
+        self.state.search.txs = TransactionData::new_search(Vec::new(), Vec::new());
     }
 
     /// Force add home page's balance load to start from 0.0
@@ -1730,86 +4532,168 @@ impl<'a> InputKeyHandler<'a> {
     fn reload_home_balance_load(&mut self) {
         // 0 for all methods + 1 more for the total balance column
         let balance_data = vec![String::from("0.0"); get_all_tx_methods(self.conn).len() + 1];
-        *self.ongoing_balance = balance_data.clone();
-        *self.ongoing_changes = vec![String::from("0.0"); balance_data.len()];
-        *self.ongoing_expense = balance_data.clone();
-        *self.ongoing_income = balance_data.clone();
-        *self.daily_ongoing_expense = balance_data.clone();
-        *self.daily_ongoing_income = balance_data.clone();
+        self.state.balance.ongoing_balance = balance_data.clone();
+        self.state.balance.ongoing_changes = vec![String::from("0.0"); balance_data.len()];
+        self.state.balance.ongoing_expense = balance_data.clone();
+        self.state.balance.ongoing_income = balance_data.clone();
+        self.state.balance.daily_ongoing_expense = balance_data.clone();
+        self.state.balance.daily_ongoing_income = balance_data.clone();
         self.reload_home_balance_data();
     }
 
+    /// Fetches every data that depends on the tx methods again after a tx method was changed.
+    /// Existing methods keep their chart activation status
+    #[cfg(not(tarpaulin_include))]
+    fn reload_tx_methods(&mut self) {
+        self.state.chart.tx_methods = IndexedData::new_chart_tx_methods(self.conn);
+        let activated_methods = self
+            .state
+            .chart
+            .tx_methods
+            .titles
+            .iter()
+            .map(|method| {
+                let status = self
+                    .state
+                    .chart
+                    .activated_methods
+                    .get(method)
+                    .copied()
+                    .unwrap_or(method != "Total");
+                (method.to_string(), status)
+            })
+            .collect();
+        self.state.chart.activated_methods = activated_methods;
+
+        self.state.summary.tx_methods = IndexedData::new_summary_tx_methods(self.conn);
+        self.state.home.filter.reload(self.conn);
+        self.reload_all_tags();
+        self.reload_home_table();
+        self.reload_chart_data();
+        self.reload_summary_data();
+        self.reload_home_balance_load();
+        self.reload_add_tx_balance_load();
+    }
+
     /// Reload activity data by fetching from the DB
     #[cfg(not(tarpaulin_include))]
     fn reload_activity_table(&mut self) {
-        *self.activity_data = ActivityData::new(
-            self.activity_months.index,
-            self.activity_years.index,
+        self.state.activity.data = ActivityData::new(
+            self.state.activity.months.index,
+            self.state.activity.years.index,
             self.conn,
         );
-        *self.activity_table = TableData::new(self.activity_data.get_txs());
+        self.state.activity.table = TableData::new(self.state.activity.data.get_txs());
+    }
+
+    /// Runs the query of the shown report again and selects its first row
+    #[cfg(not(tarpaulin_include))]
+    fn reload_report_table(&mut self) {
+        self.state.reports.table = TableData::new(
+            self.state
+                .reports
+                .data
+                .load(&self.config.reports, self.conn),
+        );
+
+        if !self.state.reports.table.items.is_empty() {
+            self.state.reports.table.state.select(Some(0));
+        }
+    }
+
+    /// Reload the trash txs and keep the selection within the table
+    #[cfg(not(tarpaulin_include))]
+    fn reload_trash_table(&mut self) {
+        let selected = self.state.trash.table.state.selected();
+        self.state.trash.table = TableData::new(get_trashed_txs(self.conn));
+
+        if !self.state.trash.table.items.is_empty() {
+            let index =
+                selected.map_or(0, |index| index.min(self.state.trash.table.items.len() - 1));
+            self.state.trash.table.state.select(Some(index));
+        }
     }
 
     /// Move the cursor for text fields to the correct position, if it's misplaced
     #[cfg(not(tarpaulin_include))]
     fn go_correct_index(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => self.add_tx_data.go_current_index(self.add_tx_tab),
-            CurrentUi::Search => self.search_data.go_current_index(self.search_tab),
+        match self.state.page {
+            CurrentUi::AddTx => self
+                .state
+                .add_tx
+                .data
+                .go_current_index(&self.state.add_tx.tab),
+            CurrentUi::Search => self
+                .state
+                .search
+                .data
+                .go_current_index(&self.state.search.tab),
             _ => {}
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     fn do_add_tx_up(&mut self) {
-        let status = match self.add_tx_tab {
-            TxTab::Date => self.add_tx_data.do_date_up(&DateType::Exact),
-            TxTab::FromMethod => self.add_tx_data.do_from_method_up(self.conn),
-            TxTab::ToMethod => self.add_tx_data.do_to_method_up(self.conn),
-            TxTab::Amount => self.add_tx_data.do_amount_up(false, self.conn),
-            TxTab::TxType => self.add_tx_data.do_tx_type_up(),
-            TxTab::Tags => self.add_tx_data.do_tags_up(self.conn),
+        let status = match self.state.add_tx.tab {
+            TxTab::Date => self.state.add_tx.data.do_date_up(&DateType::Exact),
+            TxTab::FromMethod => self.state.add_tx.data.do_from_method_up(self.conn),
+            TxTab::ToMethod => self.state.add_tx.data.do_to_method_up(self.conn),
+            TxTab::Amount => self.state.add_tx.data.do_amount_up(false, self.conn),
+            TxTab::TxType => self.state.add_tx.data.do_tx_type_up(),
+            TxTab::Tags => self.state.add_tx.data.do_tags_up(self.conn),
             _ => Ok(()),
         };
 
+        // an unknown method is already replaced with the closest one on the method picker
         if let Err(e) = status {
-            self.add_tx_data.add_tx_status(e.to_string());
+            if e != SteppingError::InvalidTxMethod {
+                self.state.add_tx.data.add_tx_status(e.to_string());
+            }
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     fn do_add_tx_down(&mut self) {
-        let status = match self.add_tx_tab {
-            TxTab::Date => self.add_tx_data.do_date_down(&DateType::Exact),
-            TxTab::FromMethod => self.add_tx_data.do_from_method_down(self.conn),
-            TxTab::ToMethod => self.add_tx_data.do_to_method_down(self.conn),
-            TxTab::Amount => self.add_tx_data.do_amount_down(false, self.conn),
-            TxTab::TxType => self.add_tx_data.do_tx_type_down(),
-            TxTab::Tags => self.add_tx_data.do_tags_down(self.conn),
+        let status = match self.state.add_tx.tab {
+            TxTab::Date => self.state.add_tx.data.do_date_down(&DateType::Exact),
+            TxTab::FromMethod => self.state.add_tx.data.do_from_method_down(self.conn),
+            TxTab::ToMethod => self.state.add_tx.data.do_to_method_down(self.conn),
+            TxTab::Amount => self.state.add_tx.data.do_amount_down(false, self.conn),
+            TxTab::TxType => self.state.add_tx.data.do_tx_type_down(),
+            TxTab::Tags => self.state.add_tx.data.do_tags_down(self.conn),
             _ => Ok(()),
         };
 
+        // an unknown method is already replaced with the closest one on the method picker
         if let Err(e) = status {
-            self.add_tx_data.add_tx_status(e.to_string());
+            if e != SteppingError::InvalidTxMethod {
+                self.state.add_tx.data.add_tx_status(e.to_string());
+            }
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     fn do_search_up(&mut self) {
-        let status = match self.search_tab {
-            TxTab::Date => self.search_data.do_date_up(self.search_date_type),
-            TxTab::FromMethod => self.search_data.do_from_method_up(self.conn),
-            TxTab::ToMethod => self.search_data.do_to_method_up(self.conn),
-            TxTab::Amount => self.search_data.do_amount_up(true, self.conn),
-            TxTab::TxType => self.search_data.do_tx_type_up(),
-            TxTab::Tags => self.search_data.do_tags_up(self.conn),
+        let status = match self.state.search.tab {
+            TxTab::Date => self
+                .state
+                .search
+                .data
+                .do_date_up(&self.state.search.date_type),
+            TxTab::FromMethod => self.state.search.data.do_from_method_up(self.conn),
+            TxTab::ToMethod => self.state.search.data.do_to_method_up(self.conn),
+            TxTab::Amount => self.state.search.data.do_amount_up(true, self.conn),
+            TxTab::TxType => self.state.search.data.do_tx_type_up(),
+            TxTab::Tags => self.state.search.data.do_tags_up(self.conn),
             TxTab::Nothing => {
-                if self.search_table.state.selected() == Some(0) {
-                    self.search_table
+                if self.state.search.table.state.selected() == Some(0) {
+                    self.state
+                        .search
+                        .table
                         .state
-                        .select(Some(self.search_table.items.len() - 1));
-                } else if !self.search_txs.is_tx_empty() {
-                    self.search_table.previous();
+                        .select(Some(self.state.search.table.items.len() - 1));
+                } else if !self.state.search.txs.is_tx_empty() {
+                    self.state.search.table.previous();
                 }
                 Ok(())
             }
@@ -1817,24 +4701,30 @@ impl<'a> InputKeyHandler<'a> {
         };
 
         if let Err(e) = status {
-            self.search_data.add_tx_status(e.to_string());
+            self.state.search.data.add_tx_status(e.to_string());
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     fn do_search_down(&mut self) {
-        let status = match self.search_tab {
-            TxTab::Date => self.search_data.do_date_down(self.search_date_type),
-            TxTab::FromMethod => self.search_data.do_from_method_down(self.conn),
-            TxTab::ToMethod => self.search_data.do_to_method_down(self.conn),
-            TxTab::Amount => self.search_data.do_amount_down(true, self.conn),
-            TxTab::TxType => self.search_data.do_tx_type_down(),
-            TxTab::Tags => self.search_data.do_tags_down(self.conn),
+        let status = match self.state.search.tab {
+            TxTab::Date => self
+                .state
+                .search
+                .data
+                .do_date_down(&self.state.search.date_type),
+            TxTab::FromMethod => self.state.search.data.do_from_method_down(self.conn),
+            TxTab::ToMethod => self.state.search.data.do_to_method_down(self.conn),
+            TxTab::Amount => self.state.search.data.do_amount_down(true, self.conn),
+            TxTab::TxType => self.state.search.data.do_tx_type_down(),
+            TxTab::Tags => self.state.search.data.do_tags_down(self.conn),
             TxTab::Nothing => {
-                if self.search_table.state.selected() == Some(self.search_table.items.len() - 1) {
-                    self.search_table.state.select(Some(0));
-                } else if !self.search_txs.is_tx_empty() {
-                    self.search_table.next();
+                if self.state.search.table.state.selected()
+                    == Some(self.state.search.table.items.len() - 1)
+                {
+                    self.state.search.table.state.select(Some(0));
+                } else if !self.state.search.txs.is_tx_empty() {
+                    self.state.search.table.next();
                 }
                 Ok(())
             }
@@ -1842,32 +4732,34 @@ impl<'a> InputKeyHandler<'a> {
         };
 
         if let Err(e) = status {
-            self.search_data.add_tx_status(e.to_string());
+            self.state.search.data.add_tx_status(e.to_string());
         }
     }
 
     #[cfg(not(tarpaulin_include))]
     fn do_activity_up(&mut self) {
-        match self.activity_tab {
+        match self.state.activity.tab {
             ActivityTab::Years => {
-                if self.activity_data.is_activity_empty() {
-                    *self.activity_tab = self.activity_tab.change_tab_down();
+                if self.state.activity.data.is_activity_empty() {
+                    self.state.activity.tab = self.state.activity.tab.change_tab_down();
                 } else {
-                    *self.activity_tab = self.activity_tab.change_tab_up();
-                    self.activity_table
+                    self.state.activity.tab = self.state.activity.tab.change_tab_up();
+                    self.state
+                        .activity
+                        .table
                         .state
-                        .select(Some(self.activity_table.items.len() - 1));
+                        .select(Some(self.state.activity.table.items.len() - 1));
                 }
             }
             ActivityTab::Months => {
-                *self.activity_tab = self.activity_tab.change_tab_up();
+                self.state.activity.tab = self.state.activity.tab.change_tab_up();
             }
             ActivityTab::List => {
-                if self.activity_table.state.selected() == Some(0) {
-                    self.activity_table.state.select(None);
-                    *self.activity_tab = self.activity_tab.change_tab_up();
+                if self.state.activity.table.state.selected() == Some(0) {
+                    self.state.activity.table.state.select(None);
+                    self.state.activity.tab = self.state.activity.tab.change_tab_up();
                 } else {
-                    self.activity_table.previous();
+                    self.state.activity.table.previous();
                 }
             }
         }
@@ -1875,25 +4767,26 @@ impl<'a> InputKeyHandler<'a> {
 
     #[cfg(not(tarpaulin_include))]
     fn do_activity_down(&mut self) {
-        match self.activity_tab {
+        match self.state.activity.tab {
             ActivityTab::Years => {
-                *self.activity_tab = self.activity_tab.change_tab_down();
+                self.state.activity.tab = self.state.activity.tab.change_tab_down();
             }
             ActivityTab::Months => {
-                if self.activity_data.is_activity_empty() {
-                    *self.activity_tab = self.activity_tab.change_tab_up();
+                if self.state.activity.data.is_activity_empty() {
+                    self.state.activity.tab = self.state.activity.tab.change_tab_up();
                 } else {
-                    *self.activity_tab = self.activity_tab.change_tab_down();
-                    self.activity_table.state.select(Some(0));
+                    self.state.activity.tab = self.state.activity.tab.change_tab_down();
+                    self.state.activity.table.state.select(Some(0));
                 }
             }
             ActivityTab::List => {
-                if self.activity_table.state.selected() == Some(self.activity_table.items.len() - 1)
+                if self.state.activity.table.state.selected()
+                    == Some(self.state.activity.table.items.len() - 1)
                 {
-                    *self.activity_tab = self.activity_tab.change_tab_down();
-                    self.activity_table.state.select(None);
+                    self.state.activity.tab = self.state.activity.tab.change_tab_down();
+                    self.state.activity.table.state.select(None);
                 } else {
-                    self.activity_table.next();
+                    self.state.activity.table.next();
                 }
             }
         }
@@ -1901,9 +4794,21 @@ impl<'a> InputKeyHandler<'a> {
 
     #[cfg(not(tarpaulin_include))]
     fn check_autofill(&mut self) {
-        match self.page {
-            CurrentUi::AddTx => self.add_tx_data.check_autofill(self.add_tx_tab, self.conn),
-            CurrentUi::Search => self.search_data.check_autofill(self.search_tab, self.conn),
+        match self.state.page {
+            CurrentUi::AddTx => {
+                self.state.add_tx.data.check_autofill(
+                    &self.state.add_tx.tab,
+                    &self.state.all_tags,
+                    self.conn,
+                );
+            }
+            CurrentUi::Search => {
+                self.state.search.data.check_autofill(
+                    &self.state.search.tab,
+                    &self.state.all_tags,
+                    self.conn,
+                );
+            }
             _ => {}
         }
     }
@@ -1911,8 +4816,8 @@ impl<'a> InputKeyHandler<'a> {
     /// Reset scroll position to 0
     #[cfg(not(tarpaulin_include))]
     fn reload_popup_scroll_position(&mut self) {
-        *self.popup_scroll_position = 0;
-        *self.max_popup_scroll = 0;
+        self.state.popup_scroll_position = 0;
+        self.state.max_popup_scroll = 0;
     }
 
     /// Update add home page balance section data that is being shown on the UI
@@ -1922,40 +4827,56 @@ impl<'a> InputKeyHandler<'a> {
         balance_data[0].extend(get_all_tx_methods(self.conn));
         balance_data[0].extend(vec!["Total".to_string()]);
 
-        let current_table_index = self.table.state.selected();
+        let current_table_index = self.state.home.table.state.selected();
 
         match current_table_index {
             // pass out the current index to get the necessary balance & changes data
             Some(a) => {
-                balance_data.push(self.all_tx_data.get_balance(a));
-                balance_data.push(self.all_tx_data.get_changes(a));
+                balance_data.push(self.state.home.tx_data.get_balance(a));
+                balance_data.push(self.state.home.tx_data.get_changes(a));
             }
             // if none selected, get empty changes + the absolute final balance
             None => {
-                balance_data.push(self.all_tx_data.get_last_balance(self.conn));
+                balance_data.push(self.state.home.tx_data.get_last_balance(self.conn));
                 balance_data.push(get_empty_changes(self.conn));
             }
         }
 
         // total income, total expense, daily income, daily expense data based on the selected index.
         balance_data.push(
-            self.all_tx_data
+            self.state
+                .home
+                .tx_data
                 .get_total_income(current_table_index, self.conn),
         );
         balance_data.push(
-            self.all_tx_data
+            self.state
+                .home
+                .tx_data
                 .get_total_expense(current_table_index, self.conn),
         );
         balance_data.push(
-            self.all_tx_data
+            self.state
+                .home
+                .tx_data
                 .get_daily_income(current_table_index, self.conn),
         );
         balance_data.push(
-            self.all_tx_data
+            self.state
+                .home
+                .tx_data
                 .get_daily_expense(current_table_index, self.conn),
         );
 
-        *self.balance_data = balance_data;
+        show_credit_utilization(
+            &mut balance_data,
+            self.config.credit_utilization_warning,
+            self.conn,
+        );
+        show_method_currencies(&mut balance_data, self.conn);
+        show_credit_as_owed(&mut balance_data, self.conn);
+        hide_archived_columns(&mut balance_data, self.conn);
+        self.state.balance.data = balance_data;
     }
 
     /// Force add tx page's balance load to start from 0.0
@@ -1963,8 +4884,8 @@ impl<'a> InputKeyHandler<'a> {
     fn reload_add_tx_balance_load(&mut self) {
         // 0 for all methods + 1 more for the total balance column
         let ongoing_data = vec![String::from("0.0"); get_all_tx_methods(self.conn).len() + 1];
-        *self.ongoing_balance = ongoing_data.clone();
-        *self.ongoing_changes = vec![String::from("0.0"); ongoing_data.len()];
+        self.state.balance.ongoing_balance = ongoing_data.clone();
+        self.state.balance.ongoing_changes = vec![String::from("0.0"); ongoing_data.len()];
         self.reload_add_tx_balance_data();
     }
 
@@ -1975,9 +4896,43 @@ impl<'a> InputKeyHandler<'a> {
         balance_data[0].extend(get_all_tx_methods(self.conn));
         balance_data[0].extend(vec!["Total".to_string()]);
 
-        balance_data.push(self.add_tx_data.generate_balance_section(self.conn));
-        balance_data.push(self.add_tx_data.generate_changes_section(self.conn));
+        balance_data.push(self.state.add_tx.data.generate_balance_section(self.conn));
+        balance_data.push(self.state.add_tx.data.generate_changes_section(self.conn));
 
-        *self.balance_data = balance_data;
+        show_credit_utilization(
+            &mut balance_data,
+            self.config.credit_utilization_warning,
+            self.conn,
+        );
+        show_method_currencies(&mut balance_data, self.conn);
+        show_credit_as_owed(&mut balance_data, self.conn);
+        hide_archived_columns(&mut balance_data, self.conn);
+        self.state.balance.data = balance_data;
+    }
+}
+
+/// The number of lines the view log popup shows
+const LOG_POPUP_LINES: usize = 200;
+
+/// Returns the latest lines of the log with the newest line on top
+#[cfg(not(tarpaulin_include))]
+fn get_log_text() -> String {
+    let Some(log_dir) = get_active_log_dir() else {
+        return "Logging could not be started so there is nothing to show.".to_string();
+    };
+
+    match get_last_log_lines(log_dir, LOG_POPUP_LINES) {
+        Ok(lines) if lines.is_empty() => {
+            format!(
+                "Nothing was logged yet. Logs are saved in {}",
+                log_dir.display()
+            )
+        }
+        Ok(lines) => format!(
+            "Logs are saved in {}\n\n{}",
+            log_dir.display(),
+            lines.join("\n")
+        ),
+        Err(e) => format!("Failed to read the log. Error: {e}"),
     }
 }
@@ -0,0 +1,293 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+use crate::key_checker::Action;
+use crate::outputs::KeymapError;
+use crate::page_handler::{AppState, Pages, PopupState};
+
+/// The named keys the pages may use. F keys are left out as they open the extension pages
+const NAMED_KEYS: [(&str, KeyCode); 14] = [
+    ("enter", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("esc", KeyCode::Esc),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("insert", KeyCode::Insert),
+];
+
+/// A key with only the modifiers that change what it does. Shift is left out as it is already
+/// part of the character like `A`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    #[must_use]
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        KeyBinding {
+            code,
+            modifiers: modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+
+    /// Parses a key like `a`, `A`, `space`, `enter`, `f5` or `ctrl+n`. Only the named keys are
+    /// not case sensitive
+    #[must_use]
+    pub fn parse(key: &str) -> Option<Self> {
+        let mut key = key.trim();
+        let mut modifiers = KeyModifiers::NONE;
+
+        loop {
+            let lowercase = key.to_lowercase();
+            if lowercase.starts_with("ctrl+") {
+                modifiers |= KeyModifiers::CONTROL;
+                key = &key[5..];
+            } else if lowercase.starts_with("alt+") {
+                modifiers |= KeyModifiers::ALT;
+                key = &key[4..];
+            } else {
+                break;
+            }
+        }
+
+        let lowercase = key.to_lowercase();
+        let named_key = NAMED_KEYS
+            .iter()
+            .find(|(name, _)| *name == lowercase)
+            .map(|(_, code)| *code);
+
+        let code = if let Some(code) = named_key {
+            code
+        } else if lowercase == "space" {
+            KeyCode::Char(' ')
+        } else if let Some(number) = lowercase
+            .strip_prefix('f')
+            .and_then(|number| number.parse::<u8>().ok())
+        {
+            if !(1..=12).contains(&number) {
+                return None;
+            }
+            KeyCode::F(number)
+        } else {
+            let mut chars = key.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            KeyCode::Char(c)
+        };
+
+        Some(KeyBinding::new(code, modifiers))
+    }
+
+    /// Whether the key is an F key. F keys open the extension pages from every page
+    #[must_use]
+    pub fn is_f_key(&self) -> bool {
+        matches!(self.code, KeyCode::F(_))
+    }
+
+    #[must_use]
+    pub fn to_event(self) -> KeyEvent {
+        KeyEvent::new(self.code, self.modifiers)
+    }
+}
+
+impl From<KeyEvent> for KeyBinding {
+    fn from(key: KeyEvent) -> Self {
+        KeyBinding::new(key.code, key.modifiers)
+    }
+}
+
+/// Every key without a modifier the pages may do something with
+fn get_page_keys() -> Vec<KeyBinding> {
+    (' '..='~')
+        .map(KeyCode::Char)
+        .chain(NAMED_KEYS.iter().map(|(_, code)| *code))
+        .map(|code| KeyBinding::new(code, KeyModifiers::NONE))
+        .collect()
+}
+
+/// Moves the actions of the pages to the keys set in the config. Only the keys of the pages are
+/// remapped, the keys typed to the input fields and the popups keep doing what they did
+#[derive(Default)]
+pub struct Keymap {
+    /// The keys of every page by the page name with the action they are for and the default key
+    /// of that action. The default key is None for the keys that no longer do anything as their
+    /// action was moved to another key
+    pages: HashMap<String, HashMap<KeyBinding, (Action, Option<KeyBinding>)>>,
+}
+
+impl Keymap {
+    /// Creates the keymap from the action name and key pairs of the config like `GoAddTx` and
+    /// `n`. The pages are checked with the given state so it must be the starting one where no
+    /// input field is selected. The bindings that could not be used are returned as errors and
+    /// their actions keep the default key
+    #[must_use]
+    pub fn new(
+        bindings: &HashMap<String, String>,
+        state: &AppState,
+        pages: &Pages,
+    ) -> (Self, Vec<KeymapError>) {
+        let page_keys = get_page_keys();
+
+        // the default keys of every action on each page
+        let page_actions = pages
+            .get_all_pages()
+            .map(|page| {
+                let mut actions: HashMap<Action, Vec<KeyBinding>> = HashMap::new();
+                for key in &page_keys {
+                    if let Some(action) = page
+                        .get_action(key.to_event(), state)
+                        .filter(Action::is_bindable)
+                    {
+                        actions.entry(action).or_default().push(*key);
+                    }
+                }
+                (page.name(), actions)
+            })
+            .collect::<Vec<(&str, HashMap<Action, Vec<KeyBinding>>)>>();
+
+        let mut errors = Vec::new();
+
+        // sorted so the errors are always in the same order
+        let mut bindings = bindings.iter().collect::<Vec<(&String, &String)>>();
+        bindings.sort();
+
+        let mut parsed_bindings = Vec::new();
+
+        for (name, key) in bindings {
+            let Some(action) = page_actions
+                .iter()
+                .flat_map(|(_, actions)| actions.keys())
+                .find(|action| format!("{action:?}") == *name)
+            else {
+                errors.push(KeymapError::UnknownAction(name.to_string()));
+                continue;
+            };
+
+            let Some(binding) = KeyBinding::parse(key) else {
+                errors.push(KeymapError::InvalidKey(name.to_string(), key.to_string()));
+                continue;
+            };
+
+            // the extension page of the key would never open again
+            if binding.is_f_key() {
+                errors.push(KeymapError::ExtensionKey(name.to_string(), key.to_string()));
+                continue;
+            }
+
+            parsed_bindings.push((*action, binding, key.trim()));
+        }
+
+        let mut keymap = Keymap::default();
+
+        for (page_name, actions) in page_actions {
+            let page_bindings = parsed_bindings
+                .iter()
+                .filter(|(action, _, _)| actions.contains_key(action))
+                .collect::<Vec<_>>();
+
+            let mut valid_bindings = Vec::new();
+
+            for (action, binding, key) in &page_bindings {
+                if let Some((other_action, _, _)) =
+                    page_bindings
+                        .iter()
+                        .find(|(other_action, other_binding, _)| {
+                            other_action != action && other_binding == binding
+                        })
+                {
+                    // both bindings are skipped but reported once
+                    if format!("{action:?}") < format!("{other_action:?}") {
+                        errors.push(KeymapError::KeyShared(
+                            key.to_string(),
+                            format!("{action:?}"),
+                            format!("{other_action:?}"),
+                            page_name.to_string(),
+                        ));
+                    }
+                    continue;
+                }
+
+                // the key is free if the action using it was moved to another key
+                if let Some(taken_by) = actions.iter().find_map(|(other_action, keys)| {
+                    (other_action != action
+                        && keys.contains(binding)
+                        && !parsed_bindings
+                            .iter()
+                            .any(|(moved_action, _, _)| moved_action == other_action))
+                    .then_some(other_action)
+                }) {
+                    errors.push(KeymapError::KeyTaken(
+                        key.to_string(),
+                        format!("{action:?}"),
+                        format!("{taken_by:?}"),
+                        page_name.to_string(),
+                    ));
+                    continue;
+                }
+
+                valid_bindings.push((*action, *binding));
+            }
+
+            let mut keys = HashMap::new();
+
+            // the default keys stop working first so another action can be moved to them
+            for (action, _) in &valid_bindings {
+                for default_key in &actions[action] {
+                    keys.insert(*default_key, (*action, None));
+                }
+            }
+
+            for (action, binding) in valid_bindings {
+                keys.insert(binding, (action, Some(actions[&action][0])));
+            }
+
+            if !keys.is_empty() {
+                keymap.pages.insert(page_name.to_string(), keys);
+            }
+        }
+
+        (keymap, errors)
+    }
+
+    /// Returns the default key the pressed key stands for on the current page. None if the key
+    /// does nothing as its action was moved to another key
+    #[must_use]
+    pub fn translate(&self, key: KeyEvent, state: &AppState, pages: &Pages) -> Option<KeyEvent> {
+        if !matches!(state.popup, PopupState::Nothing) {
+            return Some(key);
+        }
+
+        let page = pages.get(&state.page);
+
+        let Some((action, default_key)) = self
+            .pages
+            .get(page.name())
+            .and_then(|keys| keys.get(&KeyBinding::from(key)))
+        else {
+            return Some(key);
+        };
+
+        // while an input field is selected the keys are typed instead
+        match default_key {
+            Some(default_key)
+                if page.get_action(default_key.to_event(), state) == Some(*action) =>
+            {
+                Some(default_key.to_event())
+            }
+            None if page.get_action(key, state) == Some(*action) => None,
+            _ => Some(key),
+        }
+    }
+}
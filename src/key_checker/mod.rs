@@ -1,17 +1,27 @@
+mod action;
 mod activity_keys;
 mod add_tx_keys;
 mod chart_keys;
 mod home_keys;
 mod initial_keys;
 mod key_handler;
+mod keymap;
+mod popup_keys;
+mod report_keys;
 mod search_keys;
 mod summary_keys;
+mod trash_keys;
 
+pub use action::{get_action, Action};
 pub use activity_keys::activity_keys;
-pub use add_tx_keys::add_tx_keys;
+pub use add_tx_keys::{add_tx_keys, field_keys};
 pub use chart_keys::chart_keys;
 pub use home_keys::home_keys;
 pub use initial_keys::initial_keys;
 pub use key_handler::InputKeyHandler;
+pub use keymap::{KeyBinding, Keymap};
+pub use popup_keys::popup_keys;
+pub use report_keys::report_keys;
 pub use search_keys::search_keys;
 pub use summary_keys::summary_keys;
+pub use trash_keys::trash_keys;
@@ -0,0 +1,92 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::key_checker::Action;
+use crate::page_handler::{AppState, PopupState};
+
+/// Turns the keys pressed while a popup is open into the action they do.
+/// Popups that only show a text are closed by any key
+#[must_use]
+pub fn popup_keys(key: KeyEvent, state: &AppState) -> Option<Action> {
+    let action = match &state.popup {
+        PopupState::Nothing => return None,
+        PopupState::NewUpdate(_) => match key.code {
+            KeyCode::Enter => Action::PopupInput,
+            KeyCode::Up => Action::ScrollPopupUp,
+            KeyCode::Down => Action::ScrollPopupDown,
+            _ => Action::ClosePopup,
+        },
+        PopupState::HomeHelp
+        | PopupState::AddTxHelp
+        | PopupState::ChartHelp
+        | PopupState::SummaryHelp
+        | PopupState::SearchHelp
+        | PopupState::ActivityHelp
+        | PopupState::TrashHelp
+        | PopupState::ReportsHelp
+        | PopupState::ViewLog(_) => match key.code {
+            KeyCode::Up => Action::ScrollPopupUp,
+            KeyCode::Down => Action::ScrollPopupDown,
+            _ => Action::ClosePopup,
+        },
+        PopupState::TxDeletion
+        | PopupState::MarkedTxDeletion(_)
+        | PopupState::TrashPurge
+        | PopupState::EmptyTrash => match key.code {
+            KeyCode::Left | KeyCode::Right | KeyCode::Enter => Action::PopupInput,
+            _ => return None,
+        },
+        PopupState::IntegrityReport(_) | PopupState::BalanceAudit(_) => match key.code {
+            KeyCode::Char('R') => Action::PopupWrite,
+            _ => Action::PopupInput,
+        },
+        PopupState::Maintenance(_) => match key.code {
+            KeyCode::Char('c' | 'a' | 'r') => Action::PopupWrite,
+            _ => Action::PopupInput,
+        },
+        PopupState::JumpToDate(_)
+        | PopupState::MarkedTxRetag(_)
+        | PopupState::DateRange(_)
+        | PopupState::QuickAdd(_)
+        | PopupState::SplitTx(_)
+        | PopupState::TransferFee(_)
+        | PopupState::AttachFile(_)
+        | PopupState::NewTxMethod(_)
+        | PopupState::RenameTxMethod(_)
+        | PopupState::ArchiveTxMethod(_)
+        | PopupState::TxMethodType(_)
+        | PopupState::TxMethodCurrency(_)
+        | PopupState::StartingBalance(_)
+        | PopupState::MergeTxMethods(_)
+        | PopupState::TxMethodColor(_)
+        | PopupState::CreditLimit(_)
+        | PopupState::TxMethodGroup(_)
+        | PopupState::ConfirmMerge(_, _, _)
+        | PopupState::DeleteTxMethod(_)
+        | PopupState::DeleteMethodTarget(_, _, _)
+        | PopupState::ConfirmMethodDeletion(_, _, _)
+        | PopupState::TagRules(_, _)
+        | PopupState::SwitchProfile(_, _)
+        | PopupState::ExchangeRate(_)
+        | PopupState::TxNote(_)
+        | PopupState::RecoverForm(_)
+        | PopupState::DbChanged => Action::PopupInput,
+        PopupState::DeleteFailed(_)
+        | PopupState::TrashFailed(_)
+        | PopupState::RetagFailed(_)
+        | PopupState::ShowDetails(_)
+        | PopupState::ChartExported(_)
+        | PopupState::JumpFailed(_)
+        | PopupState::DateRangeFailed(_)
+        | PopupState::QuickAddFailed(_)
+        | PopupState::AttachmentFailed(_)
+        | PopupState::ScheduledTxs(_)
+        | PopupState::TagRuleFailed(_)
+        | PopupState::TagStats(_)
+        | PopupState::ReadOnly
+        | PopupState::KeymapErrors(_)
+        | PopupState::ThemeErrors(_)
+        | PopupState::InvalidConfig(_)
+        | PopupState::TxMethodFailed(_) => Action::ClosePopup,
+    };
+    Some(action)
+}
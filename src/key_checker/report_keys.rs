@@ -0,0 +1,25 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::key_checker::Action;
+
+/// Turns the keys of the Reports page into the action they do
+#[must_use]
+pub fn report_keys(key: KeyEvent) -> Option<Action> {
+    let action = match key.code {
+        KeyCode::Char('q') => Action::Quit,
+        KeyCode::Char('f') => Action::GoHome,
+        KeyCode::Char('a') => Action::GoAddTx,
+        KeyCode::Char('r') => Action::GoChart,
+        KeyCode::Char('h') => Action::HelpPopup,
+        KeyCode::Char('z') => Action::GoSummary,
+        KeyCode::Char('w') => Action::GoSearch,
+        KeyCode::Char('y') => Action::GoActivity,
+        KeyCode::Enter => Action::GoReports,
+        KeyCode::Right => Action::Right,
+        KeyCode::Left => Action::Left,
+        KeyCode::Up => Action::Up,
+        KeyCode::Down => Action::Down,
+        _ => return None,
+    };
+    Some(action)
+}
@@ -1,14 +1,17 @@
 mod activity_page;
 mod add_tx_page;
 pub mod chart_page;
-pub mod db;
+mod extension_pages;
 pub mod home_page;
 mod initial_page;
-mod key_checker;
-pub mod outputs;
+pub mod key_checker;
 pub mod page_handler;
 mod popup_page;
+mod report_page;
 mod search_page;
 pub mod summary_page;
-pub mod tx_handler;
+pub mod testing;
+mod trash_page;
 pub mod utility;
+
+pub use rex_core::{api, db, events, outputs, sync_handler, tx_handler};
@@ -1,7 +1,9 @@
 use dirs::data_local_dir;
-use rex_tui::page_handler::initialize_app;
-use std::env::{current_dir, set_current_dir};
+use rex_tui::page_handler::{dump_app_db, initialize_app, load_app_db, serve_app_db};
+use rex_tui::utility::{get_log_dir, start_logging};
+use std::env::{args, current_dir, set_current_dir};
 use std::fs;
+use std::path::PathBuf;
 
 fn main() {
     if let Some(dir) = data_local_dir() {
@@ -16,8 +18,91 @@ fn main() {
         fs::create_dir_all(&working_path).unwrap();
         set_current_dir(&working_path).unwrap();
 
+        // --profile <name> starts the app with the given profile instead of the last used one
+        let start_profile = args().skip_while(|arg| arg != "--profile").nth(1);
+
+        // --read-only [file] opens the profile db or the given db file without write permission
+        let read_only = args().any(|arg| arg == "--read-only");
+        let read_only_file = args()
+            .skip_while(|arg| arg != "--read-only")
+            .nth(1)
+            .filter(|arg| !arg.starts_with("--"))
+            .map(PathBuf::from);
+
+        // --verbose adds the debug lines like the query timings to the log file
+        let verbose = args().any(|arg| arg == "--verbose");
+
+        // every argument that is not part of --profile <name>, --read-only [file] or --verbose
+        let mut commands = Vec::new();
+        let mut all_args = args().skip(1).peekable();
+        while let Some(arg) = all_args.next() {
+            if arg == "--profile" {
+                all_args.next();
+            } else if arg == "--read-only" {
+                all_args.next_if(|arg| !arg.starts_with("--"));
+            } else if arg != "--verbose" {
+                commands.push(arg);
+            }
+        }
+
         working_path.push("data.sqlite");
-        if initialize_app(&working_path, &original_dir).is_err() {
+
+        // Rex works the same without the logs so a failure here is ignored
+        start_logging(&get_log_dir(&working_path), verbose).ok();
+        tracing::info!(
+            version = env!("CARGO_PKG_VERSION"),
+            ?commands,
+            "Rex started"
+        );
+
+        // rex dump [file], rex load <file> and rex serve [--listen <address>] run without
+        // starting the tui
+        let status = match commands.first().map(String::as_str) {
+            Some("dump") => dump_app_db(
+                &working_path,
+                &original_dir,
+                start_profile.as_deref(),
+                commands.get(1).map(String::as_str),
+            ),
+            Some("load") => {
+                let Some(source) = commands.get(1) else {
+                    println!("Usage: rex load <file>");
+                    std::process::exit(1);
+                };
+                load_app_db(
+                    &working_path,
+                    &original_dir,
+                    start_profile.as_deref(),
+                    source,
+                )
+            }
+            Some("serve") => {
+                let listen = commands
+                    .iter()
+                    .skip_while(|arg| *arg != "--listen")
+                    .nth(1)
+                    .map_or("127.0.0.1:8750", String::as_str);
+                serve_app_db(&working_path, start_profile.as_deref(), listen)
+            }
+            _ => initialize_app(
+                &working_path,
+                &original_dir,
+                start_profile.as_deref(),
+                read_only,
+                read_only_file.as_deref(),
+            ),
+        };
+
+        if let Err(e) = status {
+            tracing::error!("Rex stopped with an error: {e}");
+
+            // the tui shows its own errors
+            if matches!(
+                commands.first().map(String::as_str),
+                Some("dump" | "load" | "serve")
+            ) {
+                println!("{e}");
+            }
             std::process::exit(1);
         }
     } else {
@@ -0,0 +1,581 @@
+use chrono::NaiveDate;
+use ratatui::layout::Constraint;
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::activity_page::ActivityData;
+use crate::chart_page::{ChartData, Rgb};
+use crate::db::get_trashed_txs;
+use crate::home_page::{HomeFilter, TransactionData};
+use crate::page_handler::{
+    ActivityTab, ChartTab, ChartView, CurrentUi, DateType, DbResponse, DeletionStatus, HomeColumn,
+    HomeSortingType, HomeTab, IndexedData, PopupState, SortingDirection, SortingType, SummaryTab,
+    TableData, Theme, TxTab,
+};
+use crate::report_page::ReportData;
+use crate::summary_page::SummaryData;
+use crate::tx_handler::TxData;
+use crate::utility::{
+    get_active_tx_methods, get_all_tags, get_all_tx_methods, Config, TxMethodCache,
+};
+
+/// Everything the interface shows and the keys change. Each page keeps its own state so the
+/// draw and the key handling only need this struct and the db connection
+pub struct AppState {
+    /// The page which is currently selected. Starts on the initial page
+    pub page: CurrentUi,
+    pub popup: PopupState,
+    /// Popups waiting for the shown one to close like the ones shown at the start
+    pub popup_queue: VecDeque<PopupState>,
+    /// The selected option of the tx deletion popup. Starts on Yes
+    pub deletion_status: DeletionStatus,
+    pub popup_scroll_position: usize,
+    pub max_popup_scroll: usize,
+    /// The REX loading index of the initial page
+    pub starter_index: usize,
+    /// Every tag of the db for the tag suggestions. Only fetched again after the txs change
+    pub all_tags: Vec<String>,
+    /// The colors every page and popup is drawn with. Replaced with the theme of the config
+    /// at the start
+    pub theme: Theme,
+    /// The color of every tx method
+    pub method_colors: HashMap<String, Rgb>,
+    /// The tx methods and their info used while drawing. Fetched again once they change
+    pub methods: TxMethodCache,
+    pub balance: BalanceState,
+    pub home: HomeState,
+    pub add_tx: AddTxState,
+    pub chart: ChartState,
+    pub summary: SummaryState,
+    pub search: SearchState,
+    pub activity: ActivityState,
+    pub trash: TrashState,
+    pub reports: ReportState,
+}
+
+/// The balance sections of the Home and the Add Tx page. The shown values move towards the
+/// actual ones a little on every frame until `load_percentage` reaches 1.0
+pub struct BalanceState {
+    /// The rows of the balance section
+    pub data: Vec<Vec<String>>,
+    /// The space each column of the balance section takes
+    pub widths: Vec<Constraint>,
+    /// A single value keeps track of the load % of every row
+    pub load_percentage: f64,
+    /// The balance currently shown on the Balance row
+    pub balance_load: Vec<f64>,
+    /// The actual balance that is being shown
+    pub ongoing_balance: Vec<String>,
+    /// The balance of the earlier selected row. The shown balance starts from here
+    pub last_balance: Vec<String>,
+    pub changes_load: Vec<f64>,
+    pub ongoing_changes: Vec<String>,
+    pub last_changes: Vec<String>,
+    pub income_load: Vec<f64>,
+    pub ongoing_income: Vec<String>,
+    pub last_income: Vec<String>,
+    pub expense_load: Vec<f64>,
+    pub ongoing_expense: Vec<String>,
+    pub last_expense: Vec<String>,
+    pub daily_income_load: Vec<f64>,
+    pub daily_ongoing_income: Vec<String>,
+    pub daily_last_income: Vec<String>,
+    pub daily_expense_load: Vec<f64>,
+    pub daily_ongoing_expense: Vec<String>,
+    pub daily_last_expense: Vec<String>,
+}
+
+pub struct HomeState {
+    pub months: IndexedData,
+    pub years: IndexedData,
+    /// The ISO weeks of the selected month shown on the week view
+    pub weeks: IndexedData,
+    /// The selected widget. Starts on the month selection
+    pub tab: HomeTab,
+    /// Every tx of the selected period alongside the balance after each of them
+    pub tx_data: TransactionData,
+    pub table: TableData,
+    /// How the table is sorted. Starts in the chronological order
+    pub sort: HomeSortingType,
+    pub sort_direction: SortingDirection,
+    /// The filters of the table. Hidden at the start
+    pub filter: HomeFilter,
+    /// id_num of the txs that are marked on the table
+    pub marked_txs: HashSet<i32>,
+    /// The tx method index of the running balance column. Hidden at the start
+    pub balance_column: Option<usize>,
+    /// Whether a single ISO week of the selected month is shown
+    pub weekly: bool,
+    /// The custom start and end date shown instead of the month or the week
+    pub range: Option<(NaiveDate, NaiveDate)>,
+    /// Whether the rows are grouped under day header rows
+    pub grouped: bool,
+    pub collapsed_days: HashSet<String>,
+    pub cleared_balance: bool,
+    pub subtotals: bool,
+    /// The shown table columns from the config
+    pub columns: Vec<HomeColumn>,
+}
+
+pub struct AddTxState {
+    pub tab: TxTab,
+    /// The values of the input fields
+    pub data: TxData,
+    /// The last tx that was added in this session. Used for repeating it
+    pub last_added_tx: Option<TxData>,
+    /// Total txs added since batch entry was turned on. None while batch entry is off
+    pub batch: Option<usize>,
+}
+
+pub struct ChartState {
+    pub months: IndexedData,
+    pub years: IndexedData,
+    pub modes: IndexedData,
+    pub tx_methods: IndexedData,
+    pub tags: IndexedData,
+    pub tab: ChartTab,
+    pub data: ChartData,
+    /// The point the chart is currently rendered up to. None while the animation is running
+    pub index: Option<f64>,
+    pub hidden_mode: bool,
+    /// What the chart is currently plotting
+    pub view: ChartView,
+    /// Whether the y axis is in logarithmic scale
+    pub log_scale: bool,
+    /// How fast the chart animation is. 0 = no animation
+    pub animation_speed: u8,
+    /// Whether the points are merged into weeks
+    pub weekly: bool,
+    /// Whether a tx method line is shown. The Total line starts hidden
+    pub activated_methods: HashMap<String, bool>,
+    /// Whether a tag line of the tag view is shown
+    pub activated_tags: HashMap<String, bool>,
+    /// Whether the db worker is loading the chart data again
+    pub loading: bool,
+}
+
+pub struct SummaryState {
+    pub months: IndexedData,
+    pub years: IndexedData,
+    pub modes: IndexedData,
+    pub tx_methods: IndexedData,
+    pub tab: SummaryTab,
+    pub data: SummaryData,
+    pub table: TableData,
+    /// The column the table is sorted by
+    pub sort: SortingType,
+    pub sort_direction: SortingDirection,
+    pub hidden_mode: bool,
+    /// Whether each tag is compared with the earlier period
+    pub comparison: bool,
+    /// Whether the largest txs are shown instead of the tags
+    pub largest_txs: bool,
+    /// Whether transfers are counted as expense and income
+    pub include_transfers: bool,
+    /// The parent tags that are showing their child tags
+    pub expanded_tags: HashSet<String>,
+    /// Whether the db worker is loading the summary data again
+    pub loading: bool,
+}
+
+pub struct SearchState {
+    pub tab: TxTab,
+    /// The values of the input fields
+    pub data: TxData,
+    pub date_type: DateType,
+    pub txs: TransactionData,
+    pub table: TableData,
+}
+
+pub struct ActivityState {
+    pub months: IndexedData,
+    pub years: IndexedData,
+    pub tab: ActivityTab,
+    /// Every activity of the selected month alongside the txs involved in them
+    pub data: ActivityData,
+    pub table: TableData,
+}
+
+pub struct TrashState {
+    pub table: TableData,
+}
+
+pub struct ReportState {
+    /// The shown report. Its query runs once the page is opened
+    pub data: ReportData,
+    pub table: TableData,
+}
+
+impl AppState {
+    /// Shows the popup right away if no other popup is shown, otherwise once the earlier ones
+    /// are closed
+    pub fn queue_popup(&mut self, popup: PopupState) {
+        if matches!(self.popup, PopupState::Nothing) {
+            self.popup = popup;
+        } else {
+            self.popup_queue.push_back(popup);
+        }
+    }
+
+    /// Shows the next queued popup once the shown popup is closed
+    pub fn show_queued_popup(&mut self) {
+        if matches!(self.popup, PopupState::Nothing) {
+            if let Some(popup) = self.popup_queue.pop_front() {
+                self.popup = popup;
+                self.popup_scroll_position = 0;
+            }
+        }
+    }
+
+    /// Whether the popup is shown or waiting to be shown
+    #[must_use]
+    pub fn has_popup(&self, is_popup: impl Fn(&PopupState) -> bool) -> bool {
+        is_popup(&self.popup) || self.popup_queue.iter().any(is_popup)
+    }
+
+    /// Whether a page is waiting for the db worker to send its data
+    #[must_use]
+    pub fn is_loading(&self) -> bool {
+        self.chart.loading || self.summary.loading
+    }
+
+    /// Fetches everything that depends on the tx methods again after one of them changed.
+    /// The balance sections need a column for every tx method
+    pub fn reload_tx_methods(&mut self, config: &Config, conn: &Connection) {
+        self.balance.reset_columns(conn);
+        self.method_colors = config.get_tx_method_colors(conn);
+        self.methods.reload(conn);
+    }
+
+    /// Puts the data the db worker loaded into the page it was loaded for
+    pub fn apply_db_response(&mut self, response: DbResponse, conn: &Connection) {
+        match response {
+            DbResponse::Chart(data) => self.chart.set_data(data, conn),
+            DbResponse::Summary(data) => self.summary.set_data(data),
+        }
+    }
+
+    /// Loads the starting state of every page. Shows the new version popup if there is one
+    pub fn new(new_version_data: &Option<Vec<String>>, config: &Config, conn: &Connection) -> Self {
+        let popup = if let Some(data) = new_version_data {
+            PopupState::NewUpdate(data.to_owned())
+        } else {
+            PopupState::Nothing
+        };
+
+        AppState {
+            page: CurrentUi::Initial,
+            popup,
+            popup_queue: VecDeque::new(),
+            deletion_status: DeletionStatus::Yes,
+            popup_scroll_position: 0,
+            max_popup_scroll: 0,
+            starter_index: 0,
+            all_tags: get_all_tags(conn),
+            theme: Theme::default(),
+            method_colors: config.get_tx_method_colors(conn),
+            methods: TxMethodCache::new(conn),
+            balance: BalanceState::new(conn),
+            home: HomeState::new(config, conn),
+            add_tx: AddTxState::new(config, conn),
+            chart: ChartState::new(config, conn),
+            summary: SummaryState::new(conn),
+            search: SearchState::new(),
+            activity: ActivityState::new(conn),
+            trash: TrashState::new(conn),
+            reports: ReportState::new(),
+        }
+    }
+}
+
+impl BalanceState {
+    pub fn new(conn: &Connection) -> Self {
+        let mut state = BalanceState {
+            data: Vec::new(),
+            widths: Vec::new(),
+            load_percentage: 0.0,
+            balance_load: Vec::new(),
+            ongoing_balance: Vec::new(),
+            last_balance: Vec::new(),
+            changes_load: Vec::new(),
+            ongoing_changes: Vec::new(),
+            last_changes: Vec::new(),
+            income_load: Vec::new(),
+            ongoing_income: Vec::new(),
+            last_income: Vec::new(),
+            expense_load: Vec::new(),
+            ongoing_expense: Vec::new(),
+            last_expense: Vec::new(),
+            daily_income_load: Vec::new(),
+            daily_ongoing_income: Vec::new(),
+            daily_last_income: Vec::new(),
+            daily_expense_load: Vec::new(),
+            daily_ongoing_expense: Vec::new(),
+            daily_last_expense: Vec::new(),
+        };
+        state.reset_columns(conn);
+        state
+    }
+
+    /// Gives every row a column for each tx method. Used after the tx methods change
+    pub fn reset_columns(&mut self, conn: &Connection) {
+        let total_values = get_all_tx_methods(conn).len() + 1;
+        for load in [
+            &mut self.balance_load,
+            &mut self.changes_load,
+            &mut self.income_load,
+            &mut self.expense_load,
+            &mut self.daily_income_load,
+            &mut self.daily_expense_load,
+        ] {
+            *load = vec![0.0; total_values];
+        }
+        self.widths = get_balance_widths(conn);
+    }
+}
+
+impl HomeState {
+    pub fn new(config: &Config, conn: &Connection) -> Self {
+        let months = IndexedData::new_monthly();
+        let years = IndexedData::new_yearly();
+        let weeks = IndexedData::new_weekly(months.index, years.index);
+        let tx_data = TransactionData::new(months.index, years.index, conn);
+        let table = TableData::new(tx_data.get_txs());
+
+        HomeState {
+            months,
+            years,
+            weeks,
+            tab: HomeTab::Months,
+            tx_data,
+            table,
+            sort: HomeSortingType::ByDate,
+            sort_direction: SortingDirection::Ascending,
+            filter: HomeFilter::new(conn),
+            marked_txs: HashSet::new(),
+            balance_column: None,
+            weekly: false,
+            range: None,
+            grouped: false,
+            collapsed_days: HashSet::new(),
+            cleared_balance: false,
+            subtotals: false,
+            columns: config.get_home_columns(),
+        }
+    }
+}
+
+impl AddTxState {
+    pub fn new(config: &Config, conn: &Connection) -> Self {
+        AddTxState {
+            tab: TxTab::Nothing,
+            data: config.get_default_tx(conn),
+            last_added_tx: None,
+            batch: None,
+        }
+    }
+}
+
+impl ChartState {
+    pub fn new(config: &Config, conn: &Connection) -> Self {
+        let tx_methods = IndexedData::new_chart_tx_methods(conn);
+        let tags = IndexedData::new_tags(conn);
+
+        let activated_methods = tx_methods
+            .titles
+            .iter()
+            .map(|s| (s.to_string(), s != "Total"))
+            .collect();
+
+        let activated_tags = tags
+            .titles
+            .iter()
+            .map(|tag| (tag.to_string(), true))
+            .collect();
+
+        ChartState {
+            months: IndexedData::new_monthly(),
+            years: IndexedData::new_yearly(),
+            modes: IndexedData::new_modes(),
+            tx_methods,
+            tags,
+            tab: ChartTab::ModeSelection,
+            data: ChartData::new(conn),
+            index: None,
+            hidden_mode: false,
+            view: ChartView::Balance,
+            log_scale: false,
+            animation_speed: config.chart_animation_speed,
+            weekly: false,
+            activated_methods,
+            activated_tags,
+            loading: false,
+        }
+    }
+
+    /// Replaces the chart data with the newly loaded one. New tags may have been added or old
+    /// ones removed so the tags are fetched again. Existing tags keep their activation status
+    pub fn set_data(&mut self, data: ChartData, conn: &Connection) {
+        self.data = data;
+        self.loading = false;
+
+        self.tags = IndexedData::new_tags(conn);
+        self.activated_tags = self
+            .tags
+            .titles
+            .iter()
+            .map(|tag| {
+                let status = self.activated_tags.get(tag).copied().unwrap_or(true);
+                (tag.to_string(), status)
+            })
+            .collect();
+    }
+}
+
+impl SummaryState {
+    pub fn new(conn: &Connection) -> Self {
+        let months = IndexedData::new_monthly();
+        let years = IndexedData::new_yearly();
+        let modes = IndexedData::new_modes();
+        let sort = SortingType::ByTags;
+        let sort_direction = SortingDirection::Ascending;
+        let expanded_tags = HashSet::new();
+        let data = SummaryData::new(conn);
+
+        let table = TableData::new(data.get_table_data(
+            &modes,
+            months.index,
+            years.index,
+            &sort,
+            &sort_direction,
+            &expanded_tags,
+        ));
+
+        SummaryState {
+            months,
+            years,
+            modes,
+            tx_methods: IndexedData::new_summary_tx_methods(conn),
+            tab: SummaryTab::ModeSelection,
+            data,
+            table,
+            sort,
+            sort_direction,
+            hidden_mode: false,
+            comparison: false,
+            largest_txs: false,
+            include_transfers: false,
+            expanded_tags,
+            loading: false,
+        }
+    }
+
+    /// The tx method the summary is showing. None if every tx method is selected
+    #[must_use]
+    pub fn selected_tx_method(&self) -> Option<String> {
+        if self.tx_methods.index == 0 {
+            None
+        } else {
+            Some(self.tx_methods.titles[self.tx_methods.index].to_string())
+        }
+    }
+
+    /// Sorts the table by the tags again, collapses every parent tag and fills the table
+    /// from the summary data
+    pub fn reset_table(&mut self) {
+        self.sort = SortingType::ByTags;
+        self.sort_direction = SortingDirection::Ascending;
+        self.expanded_tags.clear();
+
+        let rows = if self.largest_txs {
+            self.data
+                .get_largest_txs(&self.modes, self.months.index, self.years.index)
+        } else {
+            self.data.get_table_data(
+                &self.modes,
+                self.months.index,
+                self.years.index,
+                &self.sort,
+                &self.sort_direction,
+                &self.expanded_tags,
+            )
+        };
+        self.table = TableData::new(rows);
+    }
+
+    /// Replaces the summary data with the newly loaded one and fills the table from it
+    pub fn set_data(&mut self, data: SummaryData) {
+        self.data = data;
+        self.loading = false;
+
+        self.data.set_include_transfers(self.include_transfers);
+        self.data.set_tx_method(self.selected_tx_method());
+        self.reset_table();
+    }
+}
+
+impl Default for SearchState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchState {
+    pub fn new() -> Self {
+        SearchState {
+            tab: TxTab::Nothing,
+            data: TxData::new_empty(),
+            date_type: DateType::Exact,
+            txs: TransactionData::new_search(Vec::new(), Vec::new()),
+            table: TableData::new(Vec::new()),
+        }
+    }
+}
+
+impl ActivityState {
+    pub fn new(conn: &Connection) -> Self {
+        let months = IndexedData::new_monthly();
+        let years = IndexedData::new_yearly();
+        let data = ActivityData::new(months.index, years.index, conn);
+        let table = TableData::new(data.get_txs());
+
+        ActivityState {
+            months,
+            years,
+            tab: ActivityTab::Years,
+            data,
+            table,
+        }
+    }
+}
+
+impl TrashState {
+    pub fn new(conn: &Connection) -> Self {
+        TrashState {
+            table: TableData::new(get_trashed_txs(conn)),
+        }
+    }
+}
+
+impl Default for ReportState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReportState {
+    pub fn new() -> Self {
+        ReportState {
+            data: ReportData::new(),
+            table: TableData::new(Vec::new()),
+        }
+    }
+}
+
+/// Returns the % of space each column should take in the Balance section based on the total
+/// transaction methods/columns available
+fn get_balance_widths(conn: &Connection) -> Vec<Constraint> {
+    let total_columns = get_active_tx_methods(conn).len() + 2;
+    let width_percent = (100 / total_columns) as u16;
+    vec![Constraint::Percentage(width_percent); total_columns]
+}
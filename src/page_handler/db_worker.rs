@@ -0,0 +1,83 @@
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::Instant;
+use tracing::debug;
+
+use crate::chart_page::ChartData;
+use crate::db::{is_db_encrypted, open_db_reader, set_read_only_pragmas};
+use crate::summary_page::SummaryData;
+
+/// The data the db worker can be asked to load
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbRequest {
+    Chart,
+    Summary,
+}
+
+/// The data the db worker loaded. Sent back in the order it was requested
+pub enum DbResponse {
+    Chart(ChartData),
+    Summary(SummaryData),
+}
+
+/// Loads the data of the heavy queries on another thread so the interface keeps drawing and
+/// taking keys while they run. The thread has its own read only connection to the db file and
+/// stops once the worker is dropped
+pub struct DbWorker {
+    requests: Sender<DbRequest>,
+    responses: Receiver<DbResponse>,
+}
+
+impl DbWorker {
+    /// Starts the worker thread. None if the db could not be opened a second time.
+    /// Encrypted dbs are not opened as the passphrase is not kept after the start
+    #[must_use]
+    pub fn new(db_path: &Path) -> Option<Self> {
+        if is_db_encrypted(db_path) {
+            return None;
+        }
+
+        let conn = open_db_reader(db_path).ok()?;
+        set_read_only_pragmas(&conn).ok()?;
+
+        let (requests, request_receiver) = channel();
+        let (response_sender, responses) = channel();
+
+        thread::spawn(move || {
+            // ends once the worker is dropped and the channel closes
+            for request in request_receiver {
+                let start = Instant::now();
+                let response = match request {
+                    DbRequest::Chart => DbResponse::Chart(ChartData::new(&conn)),
+                    DbRequest::Summary => DbResponse::Summary(SummaryData::new(&conn)),
+                };
+                debug!(
+                    ?request,
+                    elapsed_ms = start.elapsed().as_millis(),
+                    "Loaded the worker data"
+                );
+
+                if response_sender.send(response).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Some(DbWorker {
+            requests,
+            responses,
+        })
+    }
+
+    /// Asks the worker to load the data. Returns false if the worker thread has stopped
+    pub fn request(&self, request: DbRequest) -> bool {
+        self.requests.send(request).is_ok()
+    }
+
+    /// The next data the worker has loaded, if any. Does not wait for it
+    #[must_use]
+    pub fn try_recv(&self) -> Option<DbResponse> {
+        self.responses.try_recv().ok()
+    }
+}
@@ -1,20 +1,35 @@
 use atty::Stream;
+use chrono::Local;
 use rusqlite::Connection;
 use std::env::set_current_dir;
 use std::error::Error;
 use std::fs::{self, File};
 use std::io::prelude::*;
-use std::path::PathBuf;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
 use std::process;
+use tracing::error;
 
-use crate::db::{add_new_tx_methods, rename_column, reposition_column};
+use crate::api::{get_api_token, serve_api};
+use crate::db::{
+    add_new_tx_methods, change_db_passphrase, checkpoint_db, create_combined_db, create_db,
+    dump_db, export_encrypted_db, get_conflict_copy_path, get_dump_tx_methods, is_db_encrypted,
+    load_dump, open_read_only_db, rename_column, reposition_column, save_db_copy, set_db_pragmas,
+    set_read_only_pragmas, unlock_db,
+};
+use crate::events::{EventHook, RexEvent};
 use crate::initial_page::check_version;
-use crate::outputs::HandlingOutput;
-use crate::page_handler::{start_app, ResetType, UserInputType};
+use crate::outputs::{HandlingOutput, SyncError};
+use crate::page_handler::{start_app, DbChangeAction, ResetType, UserInputType};
+use crate::sync_handler::{delete_sync_settings, get_sync_settings, save_sync_settings, sync_db};
+use crate::tx_handler::post_scheduled_txs;
 use crate::utility::{
-    check_n_create_db, check_old_sql, create_backup_location_file, create_change_location_file,
-    delete_backup_db, delete_location_change, enter_tui_interface, exit_tui_interface,
-    is_location_changed, save_backup_db, start_taking_input, start_terminal, start_timer,
+    ask_db_passphrase, check_balance_snapshots, check_n_create_db, check_old_sql,
+    create_backup_location_file, create_change_location_file, delete_backup_db,
+    delete_location_change, enter_tui_interface, exit_tui_interface, get_active_profile,
+    get_all_profiles, get_config, get_profile_db_path, is_location_changed, save_active_profile,
+    save_backup_db, start_taking_input, start_terminal, start_timer, verify_profile_name, Config,
+    COMBINED_PROFILE,
 };
 
 /// Initialize the tui loop
@@ -22,6 +37,9 @@ use crate::utility::{
 pub fn initialize_app(
     original_db_path: &PathBuf,
     original_dir: &PathBuf,
+    start_profile: Option<&str>,
+    read_only: bool,
+    read_only_file: Option<&Path>,
 ) -> Result<(), Box<dyn Error>> {
     let new_version_available = check_version()?;
 
@@ -38,25 +56,56 @@ pub fn initialize_app(
         }
     }
 
-    // If the location was changed/json file found, change the db directory.
-    let db_path = if let Some(mut location) = is_location_changed(original_db_path) {
-        set_current_dir(&location).unwrap();
-        location.push("data.sqlite");
-        location
-    } else {
-        original_db_path.clone()
-    };
-    // create a new db if not found. If there is an error, delete the failed data.sqlite file and exit
-    check_n_create_db(&db_path)?;
+    let (base_db_path, mut profile) = get_start_profile(original_db_path, start_profile);
+    let mut db_path = get_profile_db_path(&base_db_path, &profile);
 
-    let mut conn = Connection::open(&db_path)?;
+    // a db file given with --read-only is opened in place of the profile db
+    if let Some(file) = read_only_file {
+        db_path = original_dir.join(file);
+        profile = file.file_name().map_or_else(
+            || profile.clone(),
+            |name| name.to_string_lossy().to_string(),
+        );
+    }
 
-    // initiates migration if old database is detected.
-    check_old_sql(&mut conn);
+    // a broken config does not stop the app. The defaults are used and the error is shown
+    // once the interface starts
+    let (config, mut config_error) = match get_config(original_db_path) {
+        Ok(config) => (config, None),
+        Err(e) => {
+            error!("{e}");
+            (Config::default(), Some(e.to_string()))
+        }
+    };
+    let mut events = EventHook::new(&config, &profile);
+
+    let mut conn = if profile == COMBINED_PROFILE {
+        open_combined_db(&base_db_path)?
+    } else {
+        open_profile_db(&db_path, read_only, &events)?
+    };
+    if !read_only && profile != COMBINED_PROFILE {
+        if let Err(e) = save_active_profile(original_db_path, &profile) {
+            error!("Failed to save the active profile. Error: {e}");
+        }
+    }
 
     loop {
+        let profiles = get_all_profiles(&base_db_path);
+        // the combined view of every profile can only be looked at
+        let session_read_only = read_only || profile == COMBINED_PROFILE;
         let mut terminal = enter_tui_interface()?;
-        let result = start_app(&mut terminal, &new_version_available, &mut conn);
+        let result = start_app(
+            &mut terminal,
+            &new_version_available,
+            &config,
+            config_error.take(),
+            &profile,
+            &profiles,
+            &db_path,
+            session_read_only,
+            &mut conn,
+        );
         exit_tui_interface()?;
 
         match result {
@@ -99,14 +148,22 @@ pub fn initialize_app(
                     UserInputType::CancelledOperation => {
                         start_timer("Operation Cancelled.");
                     }
-                    UserInputType::SetNewLocation(mut target_path) => {
-                        create_change_location_file(&db_path, &target_path);
+                    UserInputType::SetNewLocation(target_path) => {
+                        create_change_location_file(&base_db_path, &target_path);
+                        checkpoint_db(&conn).ok();
 
-                        target_path.push("data.sqlite");
-                        let file_copy_status = fs::copy(&db_path, target_path);
+                        // every profile moves to the new location
+                        let file_copy_status = profiles.iter().try_for_each(|profile| {
+                            let source = get_profile_db_path(&base_db_path, profile);
+                            let target = get_profile_db_path(&target_path.join("data.sqlite"), profile);
+                            if let Some(parent) = target.parent() {
+                                fs::create_dir_all(parent)?;
+                            }
+                            fs::copy(source, target).map(|_| ())
+                        });
 
                         match file_copy_status {
-                            Ok(_) => {
+                            Ok(()) => {
                                 start_timer("New location set successfully. The app must be restarted for it to take effect. It will exit after this.");
                                 process::exit(0)
                             }
@@ -121,6 +178,37 @@ pub fn initialize_app(
 
                         start_timer("Backup DB path locations set successfully.");
                     }
+                    UserInputType::EncryptDB(passphrase) => {
+                        match encrypt_profile_db(&passphrase, &db_path, &mut conn) {
+                            Ok(()) => start_timer("Database passphrase set successfully."),
+                            Err(e) => {
+                                println!("Error while encrypting the database. Error: {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::SetupSync(settings) => {
+                        match save_sync_settings(original_db_path, &settings) {
+                            Ok(()) => start_timer("WebDAV sync set up successfully. Sync from the same menu to upload the database."),
+                            Err(e) => {
+                                println!("Error while saving the sync settings. Error: {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::SyncDB(passphrase) => {
+                        let status = get_sync_settings(original_db_path)
+                            .ok_or(SyncError::NotSetUp)
+                            .and_then(|settings| sync_db(&settings, &passphrase, &db_path, &mut conn));
+
+                        match status {
+                            Ok(message) => start_timer(message),
+                            Err(e) => {
+                                println!("{e}");
+                                start_timer("");
+                            }
+                        }
+                    }
                     UserInputType::ResetData(reset_type) => {
 
                         match reset_type {
@@ -136,6 +224,15 @@ pub fn initialize_app(
                                     }
                                 }
                             }
+                            ResetType::Sync => {
+                                match delete_sync_settings(original_db_path) {
+                                    Ok(()) => start_timer("Sync settings removed successfully."),
+                                    Err(e) => {
+                                        println!("Error while trying to delete the sync settings. Error: {e:?}");
+                                        start_timer("");
+                                    }
+                                }
+                            }
                             ResetType::BackupDB => {
                                 match delete_backup_db(original_db_path) {
                                     Ok(()) => start_timer("Backup DB Path removed successfully."),
@@ -150,10 +247,92 @@ pub fn initialize_app(
                     UserInputType::InvalidInput => unreachable!()
                 },
                 HandlingOutput::QuitUi => {
-                    save_backup_db(&db_path, original_db_path);
+                    // a read only db may be an old backup that must not replace the newer ones
+                    if !session_read_only {
+                        checkpoint_db(&conn).ok();
+                        backup_profile_db(&db_path, original_db_path, &events);
+                    }
                     break;
                 },
-                HandlingOutput::PrintNewUpdate => println!("Could not open browser.\n\nLatest Version Link: https://github.com/TheRustyPickle/Rex/releases/latest")
+                HandlingOutput::SwitchProfile(name) => {
+                    let new_profile = match verify_profile_name(&name, &base_db_path) {
+                        Ok(new_profile) => new_profile,
+                        Err(e) => {
+                            println!("{e}");
+                            start_timer("");
+                            continue;
+                        }
+                    };
+
+                    // the db being closed gets backed up like it would on quit
+                    if !session_read_only {
+                        checkpoint_db(&conn).ok();
+                        backup_profile_db(&db_path, original_db_path, &events);
+                    }
+
+                    let new_db_path = get_profile_db_path(&base_db_path, &new_profile);
+                    let new_events = EventHook::new(&config, &new_profile);
+                    let new_conn = if new_profile == COMBINED_PROFILE {
+                        open_combined_db(&base_db_path)
+                    } else {
+                        open_profile_db(&new_db_path, read_only, &new_events)
+                    };
+
+                    match new_conn {
+                        Ok(new_conn) => {
+                            conn = new_conn;
+                            db_path = new_db_path;
+                            profile = new_profile;
+                            events = new_events;
+                            if !read_only && profile != COMBINED_PROFILE {
+                                if let Err(e) = save_active_profile(original_db_path, &profile) {
+                                    error!("Failed to save the active profile. Error: {e}");
+                                }
+                            }
+                            start_timer(format!("Switched to the {profile} profile."));
+                        }
+                        Err(e) => {
+                            println!("Failed to open the {new_profile} profile. Error: {e}");
+                            start_timer("");
+                        }
+                    }
+                }
+                HandlingOutput::DbChanged(action) => {
+                    // the data of this session gets saved before the db is closed
+                    let status = match action {
+                        DbChangeAction::Reload => Ok(("Reloaded the changed database.".to_string(), None)),
+                        DbChangeAction::KeepCurrent => {
+                            let current_path = db_path.with_extension("current");
+                            fs::remove_file(&current_path).ok();
+                            save_db_copy(&current_path, &conn).map(|()| {
+                                ("Saved the data of this session over the changed database.".to_string(), Some(current_path))
+                            })
+                        }
+                        DbChangeAction::KeepBoth => {
+                            let copy_path = get_conflict_copy_path(&db_path);
+                            save_db_copy(&copy_path, &conn).map(|()| {
+                                (format!("Saved the data of this session to {}. Opened the changed database.", copy_path.display()), None)
+                            })
+                        }
+                    };
+
+                    match status {
+                        Ok((message, replacement)) => {
+                            if let Err(e) = reload_profile_db(&db_path, replacement.as_deref(), read_only, &events, &mut conn) {
+                                println!("Failed to open the database again. Error: {e}");
+                                process::exit(1);
+                            }
+                            start_timer(message);
+                        }
+                        Err(e) => {
+                            println!("Failed to save the data of this session. Error: {e}");
+                            start_timer("");
+                        }
+                    }
+                }
+                HandlingOutput::PrintNewUpdate => println!("Could not open browser.\n\nLatest Version Link: https://github.com/TheRustyPickle/Rex/releases/latest"),
+                // handled within the ui loop
+                HandlingOutput::EditTxNote | HandlingOutput::TxMethodsChanged => {}
             },
             Err(error) => {
                 println!("{error}");
@@ -164,3 +343,282 @@ pub fn initialize_app(
 
     Ok(())
 }
+
+/// Returns the base db path and the profile the app starts with. If the location was changed the
+/// working directory moves to it. The profile given at startup takes the place of the last used one
+#[cfg(not(tarpaulin_include))]
+fn get_start_profile(original_db_path: &PathBuf, start_profile: Option<&str>) -> (PathBuf, String) {
+    // If the location was changed/json file found, change the db directory.
+    let base_db_path = if let Some(mut location) = is_location_changed(original_db_path) {
+        set_current_dir(&location).unwrap();
+        location.push("data.sqlite");
+        location
+    } else {
+        original_db_path.clone()
+    };
+
+    let profile = match start_profile {
+        Some(name) => match verify_profile_name(name, &base_db_path) {
+            Ok(profile) => profile,
+            Err(e) => {
+                println!("{e}");
+                process::exit(1);
+            }
+        },
+        None => get_active_profile(original_db_path),
+    };
+
+    (base_db_path, profile)
+}
+
+/// Writes the text dump of the profile db to the given file or prints it if no file was given
+#[cfg(not(tarpaulin_include))]
+pub fn dump_app_db(
+    original_db_path: &PathBuf,
+    original_dir: &Path,
+    start_profile: Option<&str>,
+    target: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let (base_db_path, profile) = get_start_profile(original_db_path, start_profile);
+    let db_path = get_profile_db_path(&base_db_path, &profile);
+
+    if !db_path.exists() {
+        return Err(format!("No database was found for the profile '{profile}'").into());
+    }
+
+    let conn = open_profile_db(&db_path, true, &EventHook::default())?;
+    let dump = dump_db(&conn)?;
+
+    match target {
+        Some(target) => {
+            fs::write(original_dir.join(target), dump)?;
+            println!("Saved the dump of the profile '{profile}' to {target}");
+        }
+        None => print!("{dump}"),
+    }
+    Ok(())
+}
+
+/// Replaces the txs of the profile db with the ones in the dump file. A new db is created with the
+/// tx methods of the dump if the profile does not have one yet
+#[cfg(not(tarpaulin_include))]
+pub fn load_app_db(
+    original_db_path: &PathBuf,
+    original_dir: &Path,
+    start_profile: Option<&str>,
+    source: &str,
+) -> Result<(), Box<dyn Error>> {
+    let dump = fs::read_to_string(original_dir.join(source))?;
+
+    let (base_db_path, profile) = get_start_profile(original_db_path, start_profile);
+    let db_path = get_profile_db_path(&base_db_path, &profile);
+
+    if !db_path.exists() {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let tx_methods = get_dump_tx_methods(&dump)?;
+        let mut conn = Connection::open(&db_path)?;
+        let status = create_db(&tx_methods, &mut conn);
+        conn.close().map_err(|(_, e)| e)?;
+
+        if let Err(e) = status {
+            fs::remove_file(&db_path)?;
+            return Err(e.into());
+        }
+    }
+
+    let events = EventHook::new(&get_config(original_db_path)?, &profile);
+    let mut conn = open_profile_db(&db_path, false, &events)?;
+    let total_tx = load_dump(&dump, &mut conn)?;
+    checkpoint_db(&conn)?;
+
+    println!("Loaded {total_tx} transaction(s) into the profile '{profile}'");
+    Ok(())
+}
+
+/// Answers the JSON API requests of companion tools on the given address with the profile db
+/// until the process is stopped. The requests must carry the token saved in `api_token.txt`
+#[cfg(not(tarpaulin_include))]
+pub fn serve_app_db(
+    original_db_path: &PathBuf,
+    start_profile: Option<&str>,
+    listen: &str,
+) -> Result<(), Box<dyn Error>> {
+    let (base_db_path, profile) = get_start_profile(original_db_path, start_profile);
+    let db_path = get_profile_db_path(&base_db_path, &profile);
+
+    if !db_path.exists() {
+        return Err(format!("No database was found for the profile '{profile}'").into());
+    }
+
+    let config = get_config(original_db_path)?;
+    let events = EventHook::new(&config, &profile);
+    let token = get_api_token(original_db_path)?;
+
+    let mut conn = open_profile_db(&db_path, false, &events)?;
+    let listener = TcpListener::bind(listen)?;
+
+    println!("Serving the profile '{profile}' on http://{listen}");
+    println!(
+        "Send the header 'Authorization: Bearer {token}' with every request. Press Ctrl+C to stop"
+    );
+
+    serve_api(&listener, &token, &config, &events, &mut conn)?;
+    Ok(())
+}
+
+/// Copies the db to the backup locations and tells the event hook where it was copied to
+#[cfg(not(tarpaulin_include))]
+fn backup_profile_db(db_path: &PathBuf, original_db_path: &PathBuf, events: &EventHook) {
+    let paths = save_backup_db(db_path, original_db_path);
+
+    if !paths.is_empty() {
+        events.emit(&RexEvent::BackupDone { paths }).ok();
+    }
+}
+
+/// Opens the db of a profile and gets it ready to be used. A new db is created if it does not
+/// exist. Old dbs are migrated, the scheduled txs whose date has arrived are added and the month
+/// end balance snapshots are checked. The added txs and the closed months go to the event hook. A read only db is opened as it is without any of them
+#[cfg(not(tarpaulin_include))]
+fn open_profile_db(
+    db_path: &PathBuf,
+    read_only: bool,
+    events: &EventHook,
+) -> Result<Connection, Box<dyn Error>> {
+    if read_only {
+        if !db_path.exists() {
+            return Err(format!("No database was found at {}", db_path.display()).into());
+        }
+
+        let encrypted = is_db_encrypted(db_path);
+        let conn = open_read_only_db(db_path)?;
+
+        if encrypted {
+            ask_db_passphrase(&conn)?;
+        }
+
+        set_read_only_pragmas(&conn)?;
+        return Ok(conn);
+    }
+
+    if let Some(parent) = db_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // create a new db if not found. If there is an error, delete the failed db file and exit
+    check_n_create_db(db_path)?;
+
+    let encrypted = is_db_encrypted(db_path);
+    let mut conn = Connection::open(db_path)?;
+
+    if encrypted {
+        ask_db_passphrase(&conn)?;
+    }
+
+    set_db_pragmas(&conn)?;
+
+    // initiates migration if old database is detected.
+    check_old_sql(&mut conn);
+
+    // adds the scheduled txs whose date has arrived
+    match post_scheduled_txs(Local::now().date_naive(), &mut conn) {
+        Ok(posted) if !posted.is_empty() => {
+            for id_num in &posted {
+                events.emit(&RexEvent::tx_added(*id_num, &conn)).ok();
+            }
+            start_timer(format!("Added {} scheduled transaction(s).", posted.len()));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            println!("Failed to add the scheduled transactions. Error: {e}");
+            start_timer("");
+        }
+    }
+
+    // records month end balances and checks whether the older ones are still valid
+    check_balance_snapshots(events, &mut conn);
+
+    Ok(conn)
+}
+
+/// Opens an in-memory db with the txs of every profile. Encrypted profiles cannot be read
+/// without their passphrase so they stop it from opening
+#[cfg(not(tarpaulin_include))]
+fn open_combined_db(base_db_path: &Path) -> Result<Connection, Box<dyn Error>> {
+    let mut db_paths = Vec::new();
+
+    for profile in get_all_profiles(base_db_path) {
+        let db_path = get_profile_db_path(base_db_path, &profile);
+        if !db_path.exists() {
+            continue;
+        }
+
+        if is_db_encrypted(&db_path) {
+            return Err(
+                format!("The {profile} profile is encrypted and cannot be combined").into(),
+            );
+        }
+        db_paths.push(db_path);
+    }
+
+    let conn = create_combined_db(&db_paths)?;
+    set_read_only_pragmas(&conn)?;
+    Ok(conn)
+}
+
+/// Closes the db and opens it again so the changes another app made to the file show up.
+/// The replacement db takes the place of the db file while it is closed
+#[cfg(not(tarpaulin_include))]
+fn reload_profile_db(
+    db_path: &PathBuf,
+    replacement: Option<&Path>,
+    read_only: bool,
+    events: &EventHook,
+    conn: &mut Connection,
+) -> Result<(), Box<dyn Error>> {
+    // sync apps replace the file instead of writing into it so the connection still has the
+    // old file open. Closing it only touches that old file
+    let old_conn = std::mem::replace(conn, Connection::open_in_memory()?);
+    old_conn.close().map_err(|(_, e)| e)?;
+
+    if let Some(replacement) = replacement {
+        fs::rename(replacement, db_path)?;
+    }
+
+    *conn = open_profile_db(db_path, read_only, events)?;
+    Ok(())
+}
+
+/// Encrypts the plaintext db with the passphrase by exporting an encrypted copy and replacing
+/// the db file with it. Already encrypted dbs only get the passphrase changed
+#[cfg(not(tarpaulin_include))]
+fn encrypt_profile_db(
+    passphrase: &str,
+    db_path: &PathBuf,
+    conn: &mut Connection,
+) -> Result<(), Box<dyn Error>> {
+    if is_db_encrypted(db_path) {
+        change_db_passphrase(passphrase, conn)?;
+        return Ok(());
+    }
+
+    let encrypted_path = db_path.with_extension("encrypted");
+    if let Err(e) = export_encrypted_db(&encrypted_path, passphrase, conn) {
+        fs::remove_file(&encrypted_path).ok();
+        return Err(e.into());
+    }
+
+    // the plaintext db must be closed before its file gets replaced
+    let old_conn = std::mem::replace(conn, Connection::open_in_memory()?);
+    old_conn.close().map_err(|(_, e)| e)?;
+
+    fs::rename(&encrypted_path, db_path)?;
+
+    *conn = Connection::open(db_path)?;
+    unlock_db(passphrase, conn)?;
+    set_db_pragmas(conn)?;
+    Ok(())
+}
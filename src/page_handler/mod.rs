@@ -1,7 +1,16 @@
+mod app_state;
+mod db_worker;
 mod initializer;
+mod page;
+mod theme;
 mod ui_handler;
 mod ui_state;
 
-pub use initializer::initialize_app;
+pub use app_state::*;
+pub use db_worker::*;
+pub use initializer::{dump_app_db, initialize_app, load_app_db, serve_app_db};
+pub use page::{Page, PageContext, Pages};
+pub use rex_core::state::*;
+pub use theme::*;
 pub use ui_handler::*;
 pub use ui_state::*;
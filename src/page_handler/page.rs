@@ -0,0 +1,145 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use rusqlite::Connection;
+
+use crate::activity_page::ActivityPage;
+use crate::add_tx_page::AddTxPage;
+use crate::chart_page::ChartPage;
+use crate::extension_pages::get_extension_pages;
+use crate::home_page::HomePage;
+use crate::initial_page::InitialPage;
+use crate::key_checker::Action;
+use crate::outputs::HandlingOutput;
+use crate::page_handler::{AppState, CurrentUi};
+use crate::report_page::ReportsPage;
+use crate::search_page::SearchPage;
+use crate::summary_page::SummaryPage;
+use crate::trash_page::TrashPage;
+use crate::utility::Config;
+
+/// Everything other than the app state a page may need while drawing
+pub struct PageContext<'a> {
+    /// Whether the balance loading should start from the beginning
+    pub to_reset: bool,
+    pub config: &'a Config,
+    /// The name of the profile whose db is open
+    pub profile: &'a str,
+    pub read_only: bool,
+    pub conn: &'a Connection,
+}
+
+/// A page of the interface. The built-in pages keep their state in `AppState` while extension
+/// pages can keep theirs inside the struct that implements this
+pub trait Page {
+    /// The name of the page
+    fn name(&self) -> &str;
+
+    /// Draws the page. The popup is drawn on top of it afterwards
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, context: &PageContext);
+
+    /// Turns the key press into the action it does on this page. None if the key does nothing.
+    /// Keys the page handles by itself should return `Action::PageInput` or `Action::PageWrite`
+    fn get_action(&self, key: KeyEvent, state: &AppState) -> Option<Action>;
+
+    /// Called every time the page is opened
+    fn open(&mut self, _state: &mut AppState, _conn: &Connection) {}
+
+    /// Handles a key that was turned into `Action::PageInput` or `Action::PageWrite`. Only
+    /// `PageWrite` keys are allowed to change the db, they are ignored in read only mode
+    fn handle_key(
+        &mut self,
+        _key: KeyEvent,
+        _state: &mut AppState,
+        _conn: &mut Connection,
+    ) -> Option<HandlingOutput> {
+        None
+    }
+}
+
+/// Every page of the interface. The built-in pages come first in the order of `CurrentUi`
+/// followed by the extension pages enabled with cargo features
+pub struct Pages {
+    pages: Vec<Box<dyn Page>>,
+}
+
+/// The number of pages that are not extensions
+const BUILT_IN_PAGES: usize = 9;
+
+impl Pages {
+    /// Creates the built-in pages and every extension page enabled at compile time
+    #[must_use]
+    pub fn new() -> Self {
+        Pages::with_extensions(get_extension_pages())
+    }
+
+    /// Creates the built-in pages and the given extension pages
+    #[must_use]
+    pub fn with_extensions(extensions: Vec<Box<dyn Page>>) -> Self {
+        let mut pages: Vec<Box<dyn Page>> = vec![
+            Box::new(InitialPage),
+            Box::new(HomePage),
+            Box::new(AddTxPage),
+            Box::new(ChartPage),
+            Box::new(SummaryPage),
+            Box::new(SearchPage),
+            Box::new(ActivityPage),
+            Box::new(TrashPage),
+            Box::new(ReportsPage),
+        ];
+        pages.extend(extensions);
+
+        Pages { pages }
+    }
+
+    fn get_index(page: &CurrentUi) -> usize {
+        match page {
+            CurrentUi::Initial => 0,
+            CurrentUi::Home => 1,
+            CurrentUi::AddTx => 2,
+            CurrentUi::Chart => 3,
+            CurrentUi::Summary => 4,
+            CurrentUi::Search => 5,
+            CurrentUi::Activity => 6,
+            CurrentUi::Trash => 7,
+            CurrentUi::Reports => 8,
+            CurrentUi::Extension(index) => BUILT_IN_PAGES + index,
+        }
+    }
+
+    /// Returns the page. Falls back to the Home page for an extension that does not exist
+    #[must_use]
+    pub fn get(&self, page: &CurrentUi) -> &dyn Page {
+        let index = Pages::get_index(page);
+        self.pages
+            .get(index)
+            .unwrap_or(&self.pages[Pages::get_index(&CurrentUi::Home)])
+            .as_ref()
+    }
+
+    /// Returns the page. Falls back to the Home page for an extension that does not exist
+    pub fn get_mut(&mut self, page: &CurrentUi) -> &mut dyn Page {
+        let index = if Pages::get_index(page) < self.pages.len() {
+            Pages::get_index(page)
+        } else {
+            Pages::get_index(&CurrentUi::Home)
+        };
+        self.pages[index].as_mut()
+    }
+
+    /// Every page besides the Initial page which is only shown at the start
+    pub fn get_all_pages(&self) -> impl Iterator<Item = &dyn Page> {
+        self.pages.iter().skip(1).map(AsRef::as_ref)
+    }
+
+    /// The number of extension pages. Extension 0 is opened with F1, 1 with F2 and so on
+    #[must_use]
+    pub fn total_extensions(&self) -> usize {
+        self.pages.len() - BUILT_IN_PAGES
+    }
+}
+
+impl Default for Pages {
+    fn default() -> Self {
+        Pages::new()
+    }
+}
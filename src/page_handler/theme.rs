@@ -0,0 +1,158 @@
+use ratatui::style::Color;
+
+use crate::outputs::ThemeError;
+use crate::utility::{get_color, parse_hex_color, Config};
+
+/// The names of the built-in themes the `theme` of the config accepts
+pub const THEME_NAMES: [&str; 5] = [
+    "Dracula",
+    "Gruvbox",
+    "Solarized Dark",
+    "Solarized Light",
+    "Plain",
+];
+
+/// The colors the interface is drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub background: Color,
+    pub text: Color,
+    /// The borders and the titles of the boxes
+    pub border: Color,
+    /// The background of the selected row and tab
+    pub selected: Color,
+    /// The background of the selected item of a list
+    pub highlighted: Color,
+    /// The background of the table headers
+    pub header: Color,
+    /// Expenses, errors and the negative values
+    pub expense: Color,
+    /// Incomes and the positive values
+    pub income: Color,
+    /// The autofill suggestions
+    pub autofill: Color,
+}
+
+impl Theme {
+    pub const DRACULA: Theme = Theme {
+        background: Color::Rgb(40, 42, 54),
+        text: Color::Rgb(248, 248, 242),
+        border: Color::Rgb(98, 114, 164),
+        selected: Color::Rgb(98, 114, 164),
+        highlighted: Color::Rgb(68, 71, 90),
+        header: Color::Rgb(98, 114, 164),
+        expense: Color::Rgb(255, 85, 85),
+        income: Color::Rgb(248, 248, 242),
+        autofill: Color::Rgb(241, 250, 140),
+    };
+
+    pub const GRUVBOX: Theme = Theme {
+        background: Color::Rgb(40, 40, 40),
+        text: Color::Rgb(235, 219, 178),
+        border: Color::Rgb(146, 131, 116),
+        selected: Color::Rgb(80, 73, 69),
+        highlighted: Color::Rgb(60, 56, 54),
+        header: Color::Rgb(69, 133, 136),
+        expense: Color::Rgb(251, 73, 52),
+        income: Color::Rgb(131, 165, 152),
+        autofill: Color::Rgb(250, 189, 47),
+    };
+
+    pub const SOLARIZED_DARK: Theme = Theme {
+        background: Color::Rgb(0, 43, 54),
+        text: Color::Rgb(147, 161, 161),
+        border: Color::Rgb(38, 139, 210),
+        selected: Color::Rgb(88, 110, 117),
+        highlighted: Color::Rgb(7, 54, 66),
+        header: Color::Rgb(38, 139, 210),
+        expense: Color::Rgb(220, 50, 47),
+        income: Color::Rgb(42, 161, 152),
+        autofill: Color::Rgb(181, 137, 0),
+    };
+
+    pub const SOLARIZED_LIGHT: Theme = Theme {
+        background: Color::Rgb(253, 246, 227),
+        text: Color::Rgb(88, 110, 117),
+        border: Color::Rgb(38, 139, 210),
+        selected: Color::Rgb(147, 161, 161),
+        highlighted: Color::Rgb(238, 232, 213),
+        header: Color::Rgb(38, 139, 210),
+        expense: Color::Rgb(220, 50, 47),
+        income: Color::Rgb(42, 161, 152),
+        autofill: Color::Rgb(181, 137, 0),
+    };
+
+    /// Uses only the 16 colors of the terminal so the colors follow the terminal's own theme
+    pub const PLAIN: Theme = Theme {
+        background: Color::Black,
+        text: Color::White,
+        border: Color::Blue,
+        selected: Color::DarkGray,
+        highlighted: Color::DarkGray,
+        header: Color::Blue,
+        expense: Color::Red,
+        income: Color::Cyan,
+        autofill: Color::Yellow,
+    };
+
+    /// Returns the built-in theme of the name. The name is not case sensitive
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let theme = match name.trim().to_lowercase().as_str() {
+            "dracula" => Theme::DRACULA,
+            "gruvbox" => Theme::GRUVBOX,
+            "solarized dark" => Theme::SOLARIZED_DARK,
+            "solarized light" => Theme::SOLARIZED_LIGHT,
+            "plain" => Theme::PLAIN,
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// Returns the theme of the config with the colors of `theme_colors` on top of it alongside
+    /// the parts of the config that could not be used. Unknown themes fall back to Dracula
+    #[must_use]
+    pub fn from_config(config: &Config) -> (Self, Vec<ThemeError>) {
+        let mut errors = Vec::new();
+
+        let mut theme = Theme::from_name(&config.theme).unwrap_or_else(|| {
+            errors.push(ThemeError::UnknownTheme(config.theme.clone()));
+            Theme::default()
+        });
+
+        // sorted so the errors are always shown in the same order
+        let mut theme_colors = config.theme_colors.iter().collect::<Vec<_>>();
+        theme_colors.sort();
+
+        for (name, color) in theme_colors {
+            let target = match name.trim().to_lowercase().as_str() {
+                "background" => &mut theme.background,
+                "text" => &mut theme.text,
+                "border" => &mut theme.border,
+                "selected" => &mut theme.selected,
+                "highlighted" => &mut theme.highlighted,
+                "header" => &mut theme.header,
+                "expense" => &mut theme.expense,
+                "income" => &mut theme.income,
+                "autofill" => &mut theme.autofill,
+                _ => {
+                    errors.push(ThemeError::UnknownColor(name.clone()));
+                    continue;
+                }
+            };
+
+            match parse_hex_color(color) {
+                Some(color) => *target = get_color(color),
+                None => errors.push(ThemeError::InvalidColor(name.clone(), color.clone())),
+            }
+        }
+
+        (theme, errors)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DRACULA
+    }
+}
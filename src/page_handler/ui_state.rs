@@ -1,11 +1,8 @@
-use chrono::prelude::Local;
-use chrono::Datelike;
 use ratatui::widgets::TableState;
-use rusqlite::Connection;
 use std::path::PathBuf;
 
-use crate::db::{MODES, MONTHS, YEARS};
-use crate::utility::get_all_tx_methods;
+use crate::sync_handler::SyncSettings;
+use crate::tx_handler::TxDraft;
 
 /// The struct stores all transaction data for the Transaction widget
 /// and creates an index to keep track of which transactions row is selected
@@ -60,114 +57,80 @@ impl TableData {
     }
 }
 
-/// This struct takes anything inside a vector and adds an index it it.
-/// It is used for keeping track of the Months and Years current index.
-///
-/// titles: `["January", "February",]`
-
-pub struct IndexedData {
-    pub titles: Vec<String>,
-    pub index: usize,
-}
-
-impl IndexedData {
-    pub fn new_monthly() -> Self {
-        let month_index = Local::now().month() as usize - 1;
-        IndexedData {
-            titles: MONTHS.into_iter().map(ToString::to_string).collect(),
-            index: month_index,
-        }
-    }
-
-    pub fn new_yearly() -> Self {
-        let year_index = Local::now().year() as usize - 2022;
-        IndexedData {
-            titles: YEARS.into_iter().map(ToString::to_string).collect(),
-            index: year_index,
-        }
-    }
-
-    pub fn new_modes() -> Self {
-        IndexedData {
-            titles: MODES.into_iter().map(ToString::to_string).collect(),
-            index: 0,
-        }
-    }
-
-    pub fn new_tx_methods(conn: &Connection) -> Self {
-        IndexedData {
-            titles: get_all_tx_methods(conn),
-            index: 0,
-        }
-    }
-
-    /// Increases the current index by 1 or goes to 0 if at the final value
-    pub fn next(&mut self) {
-        self.index = (self.index + 1) % self.titles.len();
-    }
-
-    /// Decreases the current index by 1 or goes to final index if at 0
-    pub fn previous(&mut self) {
-        if self.index > 0 {
-            self.index -= 1;
-        } else {
-            self.index = self.titles.len() - 1;
-        }
-    }
-
-    pub fn set_index_zero(&mut self) {
-        self.index = 0;
-    }
-}
-
 /// The enum is used to keep track of which tab is currently set at active
 /// or being interacted with in the Home page. There are 3 interact-able widgets
-/// in the home page thus three values. The goal is to keep them cycling through
-/// all values.
+/// in the home page thus three values plus the week tab on the week view and
+/// the 3 filters when the filter bar is shown.
+/// The goal is to keep them cycling through all values.
+#[derive(PartialEq)]
 pub enum HomeTab {
     Years,
     Months,
+    Weeks,
+    TagFilter,
+    MethodFilter,
+    TypeFilter,
     Table,
 }
 
 impl HomeTab {
     /// Moves the current selected tab to the upper value. If at the 1st value, the
-    /// the final value is selected.
+    /// the final value is selected. The filters are skipped if the filter bar is hidden
+    /// and the week tab is skipped outside of the week view
     #[cfg(not(tarpaulin_include))]
-    pub fn change_tab_up(&mut self) -> Self {
+    pub fn change_tab_up(&mut self, filter_shown: bool, weekly: bool) -> Self {
+        let period_tab = if weekly {
+            HomeTab::Weeks
+        } else {
+            HomeTab::Months
+        };
+
         match &self {
             HomeTab::Years => HomeTab::Table,
             HomeTab::Months => HomeTab::Years,
-            HomeTab::Table => HomeTab::Months,
+            HomeTab::Weeks => HomeTab::Months,
+            HomeTab::TagFilter => period_tab,
+            HomeTab::MethodFilter => HomeTab::TagFilter,
+            HomeTab::TypeFilter => HomeTab::MethodFilter,
+            HomeTab::Table => {
+                if filter_shown {
+                    HomeTab::TypeFilter
+                } else {
+                    period_tab
+                }
+            }
         }
     }
 
     /// Moves the current selected tab to the bottom value. If at the last value, the
-    /// the 1st value is selected.
+    /// the 1st value is selected. The filters are skipped if the filter bar is hidden
+    /// and the week tab is skipped outside of the week view
     #[cfg(not(tarpaulin_include))]
-    pub fn change_tab_down(&mut self) -> Self {
+    pub fn change_tab_down(&mut self, filter_shown: bool, weekly: bool) -> Self {
+        let after_period = if filter_shown {
+            HomeTab::TagFilter
+        } else {
+            HomeTab::Table
+        };
+
         match &self {
             HomeTab::Years => HomeTab::Months,
-            HomeTab::Months => HomeTab::Table,
+            HomeTab::Months => {
+                if weekly {
+                    HomeTab::Weeks
+                } else {
+                    after_period
+                }
+            }
+            HomeTab::Weeks => after_period,
+            HomeTab::TagFilter => HomeTab::MethodFilter,
+            HomeTab::MethodFilter => HomeTab::TypeFilter,
+            HomeTab::TypeFilter => HomeTab::Table,
             HomeTab::Table => HomeTab::Years,
         }
     }
 }
 
-/// This enum is used inside the Add Transaction page.
-/// This is targeted to be used to keep track which widget of the Add Transaction
-/// is currently being interacted with.
-pub enum TxTab {
-    Date,
-    Details,
-    FromMethod,
-    ToMethod,
-    Amount,
-    TxType,
-    Tags,
-    Nothing,
-}
-
 /// Shows the currently active page in the terminal. Used to properly
 /// direct key presses to the relevant structs and widget selection.
 pub enum CurrentUi {
@@ -178,6 +141,10 @@ pub enum CurrentUi {
     Summary,
     Search,
     Activity,
+    Trash,
+    Reports,
+    /// The extension page enabled with a cargo feature. Opened with F1 for the first one
+    Extension(usize),
 }
 
 /// Indicates which popup is currently on and is being shown in the screen
@@ -189,12 +156,86 @@ pub enum PopupState {
     SummaryHelp,
     SearchHelp,
     ActivityHelp,
+    TrashHelp,
+    ReportsHelp,
     DeleteFailed(String),
     TxDeletion,
+    MarkedTxDeletion(usize),
+    TrashPurge,
+    EmptyTrash,
+    TrashFailed(String),
+    MarkedTxRetag(String),
+    RetagFailed(String),
     ShowDetails(String),
+    ChartExported(String),
+    JumpToDate(String),
+    JumpFailed(String),
+    DateRange(String),
+    DateRangeFailed(String),
+    QuickAdd(String),
+    QuickAddFailed(String),
+    SplitTx(String),
+    TransferFee(String),
+    AttachFile(String),
+    AttachmentFailed(String),
+    ScheduledTxs(String),
+    NewTxMethod(String),
+    RenameTxMethod(String),
+    ArchiveTxMethod(String),
+    TxMethodType(String),
+    TxMethodCurrency(String),
+    StartingBalance(String),
+    MergeTxMethods(String),
+    TxMethodColor(String),
+    CreditLimit(String),
+    TxMethodGroup(String),
+    ConfirmMerge(String, String, String),
+    DeleteTxMethod(String),
+    DeleteMethodTarget(String, usize, String),
+    ConfirmMethodDeletion(String, Option<String>, String),
+    TagRules(String, String),
+    TagRuleFailed(String),
+    TagStats(String),
+    SwitchProfile(String, String),
+    IntegrityReport(String),
+    BalanceAudit(String),
+    Maintenance(String),
+    ReadOnly,
+    TxMethodFailed(String),
+    ExchangeRate(String),
+    TxNote(String),
+    ViewLog(String),
+    /// The Add Tx form that was not sent when Rex last stopped
+    RecoverForm(Box<TxDraft>),
+    /// The key bindings of the config that could not be used
+    KeymapErrors(String),
+    /// The theme and the theme colors of the config that could not be used
+    ThemeErrors(String),
+    /// Why config.json could not be loaded. The default config is used in its place
+    InvalidConfig(String),
+    DbChanged,
     Nothing,
 }
 
+impl PopupState {
+    /// Returns the error of the popups that show a failed action
+    #[must_use]
+    pub fn get_failure(&self) -> Option<&str> {
+        match self {
+            PopupState::DeleteFailed(err)
+            | PopupState::TrashFailed(err)
+            | PopupState::RetagFailed(err)
+            | PopupState::JumpFailed(err)
+            | PopupState::DateRangeFailed(err)
+            | PopupState::QuickAddFailed(err)
+            | PopupState::AttachmentFailed(err)
+            | PopupState::TxMethodFailed(err)
+            | PopupState::TagRuleFailed(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
 pub enum ChartTab {
     ModeSelection,
     Years,
@@ -276,6 +317,7 @@ impl ChartTab {
 
 pub enum SummaryTab {
     ModeSelection,
+    TxMethods,
     Years,
     Months,
     Table,
@@ -288,7 +330,8 @@ impl SummaryTab {
     pub fn change_tab_up_monthly(&mut self) -> Self {
         match &self {
             SummaryTab::ModeSelection => SummaryTab::Table,
-            SummaryTab::Years => SummaryTab::ModeSelection,
+            SummaryTab::TxMethods => SummaryTab::ModeSelection,
+            SummaryTab::Years => SummaryTab::TxMethods,
             SummaryTab::Months => SummaryTab::Years,
             SummaryTab::Table => SummaryTab::Months,
         }
@@ -299,7 +342,8 @@ impl SummaryTab {
     #[cfg(not(tarpaulin_include))]
     pub fn change_tab_down_monthly(&mut self) -> Self {
         match &self {
-            SummaryTab::ModeSelection => SummaryTab::Years,
+            SummaryTab::ModeSelection => SummaryTab::TxMethods,
+            SummaryTab::TxMethods => SummaryTab::Years,
             SummaryTab::Years => SummaryTab::Months,
             SummaryTab::Months => SummaryTab::Table,
             SummaryTab::Table => SummaryTab::ModeSelection,
@@ -312,7 +356,8 @@ impl SummaryTab {
     pub fn change_tab_up_yearly(&mut self) -> Self {
         match &self {
             SummaryTab::ModeSelection => SummaryTab::Table,
-            SummaryTab::Years => SummaryTab::ModeSelection,
+            SummaryTab::TxMethods => SummaryTab::ModeSelection,
+            SummaryTab::Years => SummaryTab::TxMethods,
             SummaryTab::Table => SummaryTab::Years,
             SummaryTab::Months => SummaryTab::Months,
         }
@@ -323,7 +368,8 @@ impl SummaryTab {
     #[cfg(not(tarpaulin_include))]
     pub fn change_tab_down_yearly(&mut self) -> Self {
         match &self {
-            SummaryTab::ModeSelection => SummaryTab::Years,
+            SummaryTab::ModeSelection => SummaryTab::TxMethods,
+            SummaryTab::TxMethods => SummaryTab::Years,
             SummaryTab::Years => SummaryTab::Table,
             SummaryTab::Table => SummaryTab::ModeSelection,
             SummaryTab::Months => SummaryTab::Months,
@@ -336,7 +382,8 @@ impl SummaryTab {
     pub fn change_tab_up_all_time(&mut self) -> Self {
         match &self {
             SummaryTab::ModeSelection => SummaryTab::Table,
-            SummaryTab::Table => SummaryTab::ModeSelection,
+            SummaryTab::TxMethods => SummaryTab::ModeSelection,
+            SummaryTab::Table => SummaryTab::TxMethods,
             SummaryTab::Years => SummaryTab::Years,
             SummaryTab::Months => SummaryTab::Months,
         }
@@ -347,7 +394,8 @@ impl SummaryTab {
     #[cfg(not(tarpaulin_include))]
     pub fn change_tab_down_all_time(&mut self) -> Self {
         match &self {
-            SummaryTab::ModeSelection => SummaryTab::Table,
+            SummaryTab::ModeSelection => SummaryTab::TxMethods,
+            SummaryTab::TxMethods => SummaryTab::Table,
             SummaryTab::Table => SummaryTab::ModeSelection,
             SummaryTab::Years => SummaryTab::Years,
             SummaryTab::Months => SummaryTab::Months,
@@ -363,12 +411,16 @@ pub enum UserInputType {
     CancelledOperation,
     ResetData(ResetType),
     BackupDBPath(Vec<PathBuf>),
+    EncryptDB(String),
+    SetupSync(SyncSettings),
+    SyncDB(String),
     InvalidInput,
 }
 
 pub enum ResetType {
     NewLocation,
     BackupDB,
+    Sync,
 }
 
 impl UserInputType {
@@ -380,31 +432,18 @@ impl UserInputType {
             "3" => UserInputType::RepositionTxMethod(Vec::new()),
             "4" => UserInputType::SetNewLocation(PathBuf::new()),
             "5" => UserInputType::BackupDBPath(Vec::new()),
+            #[cfg(feature = "encryption")]
+            "6" => UserInputType::EncryptDB(String::new()),
+            #[cfg(feature = "encryption")]
+            "7" => UserInputType::SetupSync(SyncSettings::default()),
+            #[cfg(feature = "encryption")]
+            "8" => UserInputType::SyncDB(String::new()),
             "cancel" => UserInputType::CancelledOperation,
             _ => UserInputType::InvalidInput,
         }
     }
 }
 
-pub enum SortingType {
-    ByTags,
-    ByIncome,
-    ByExpense,
-    ByNet,
-}
-
-impl SortingType {
-    #[cfg(not(tarpaulin_include))]
-    pub fn next_type(&mut self) -> Self {
-        match self {
-            SortingType::ByTags => SortingType::ByIncome,
-            SortingType::ByIncome => SortingType::ByExpense,
-            SortingType::ByExpense => SortingType::ByTags,
-            SortingType::ByNet => SortingType::ByNet,
-        }
-    }
-}
-
 pub enum DeletionStatus {
     Yes,
     No,
@@ -412,6 +451,7 @@ pub enum DeletionStatus {
 
 impl DeletionStatus {
     #[cfg(not(tarpaulin_include))]
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Self {
         match self {
             DeletionStatus::Yes => DeletionStatus::No,
@@ -420,23 +460,6 @@ impl DeletionStatus {
     }
 }
 
-pub enum DateType {
-    Exact,
-    Monthly,
-    Yearly,
-}
-
-impl DateType {
-    #[cfg(not(tarpaulin_include))]
-    pub fn next(&mut self) -> Self {
-        match self {
-            DateType::Exact => DateType::Monthly,
-            DateType::Monthly => DateType::Yearly,
-            DateType::Yearly => DateType::Exact,
-        }
-    }
-}
-
 #[derive(PartialEq)]
 pub enum HomeRow {
     Balance,
@@ -494,54 +517,3 @@ impl ActivityTab {
         }
     }
 }
-
-pub enum ActivityType {
-    NewTX,
-    EditTX(Option<i32>),
-    DeleteTX(Option<i32>),
-    IDNumSwap(Option<i32>, Option<i32>),
-    SearchTX(Option<u8>),
-}
-
-impl ActivityType {
-    pub fn from_str(data: &str) -> Self {
-        match data {
-            "Add TX" => Self::NewTX,
-            "Edit TX" => Self::EditTX(None),
-            "Delete TX" => Self::DeleteTX(None),
-            "TX Position Swap" => Self::IDNumSwap(None, None),
-            "Search TX" => Self::SearchTX(None),
-            _ => unreachable!(),
-        }
-    }
-
-    pub fn to_details(&self) -> String {
-        match self {
-            Self::NewTX => String::from("A new Transaction was added"),
-            Self::EditTX(id) => format!("A transaction was edited with ID {}", id.unwrap()),
-            Self::DeleteTX(id) => format!("A transaction was deleted with ID {}", id.unwrap()),
-            Self::IDNumSwap(id_1, id_2) => format!(
-                "Transaction with ID num {} and ID num {} was swapped",
-                id_1.unwrap(),
-                id_2.unwrap()
-            ),
-            Self::SearchTX(total) => {
-                if total.unwrap() == 1 {
-                    String::from("Transactions were searched with one field")
-                } else {
-                    String::from("Transactions were searched with multiple fields")
-                }
-            }
-        }
-    }
-
-    pub fn to_str(&self) -> String {
-        match self {
-            Self::NewTX => String::from("Add TX"),
-            Self::EditTX(_) => String::from("Edit TX"),
-            Self::DeleteTX(_) => String::from("Delete TX"),
-            Self::IDNumSwap(_, _) => String::from("TX Position Swap"),
-            Self::SearchTX(_) => String::from("Search TX"),
-        }
-    }
-}
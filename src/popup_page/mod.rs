@@ -2,4 +2,6 @@ mod popup_data;
 mod popup_ui;
 
 pub use popup_data::{PopupData, A, F, H, Q, R, V, W, Y, Z};
-pub use popup_ui::{create_deletion_popup, create_popup};
+pub use popup_ui::{
+    create_deletion_popup, create_input_popup, create_popup, create_text_area_popup,
+};
@@ -1,7 +1,11 @@
 use ratatui::Frame;
 
-use crate::page_handler::{DeletionStatus, PopupState};
-use crate::popup_page::{create_deletion_popup, create_popup};
+use crate::page_handler::{DeletionStatus, PopupState, Theme};
+use crate::popup_page::{
+    create_deletion_popup, create_input_popup, create_popup, create_text_area_popup,
+};
+use crate::tx_handler::TxDraft;
+use crate::utility::COMBINED_PROFILE;
 
 pub const F: &str = "F: Home Page";
 pub const A: &str = "A: Add Transaction Page";
@@ -11,7 +15,7 @@ pub const Y: &str = "Y: Activity Page";
 pub const W: &str = "W: Search Page";
 pub const Q: &str = "Q: Quit";
 pub const H: &str = "H: Show help";
-pub const V: &str = "V: Show selected transaction details";
+pub const V: &str = "V: Show selected transaction details and note";
 
 /// Stores data to create a new popup
 pub struct PopupData<'a> {
@@ -37,6 +41,7 @@ impl<'a> PopupData<'a> {
         deletion_status: &DeletionStatus,
         popup_scroll_position: usize,
         max_popup_scroll: &mut usize,
+        theme: &Theme,
     ) {
         let status = match popup_type {
             PopupState::NewUpdate(data) => self.get_new_update_text(data),
@@ -47,14 +52,284 @@ impl<'a> PopupData<'a> {
             PopupState::DeleteFailed(err) => self.get_delete_failed_text(err),
             PopupState::SearchHelp => self.get_search_help_text(),
             PopupState::ActivityHelp => self.get_activity_help_text(),
+            PopupState::TrashHelp => self.get_trash_help_text(),
+            PopupState::ReportsHelp => self.get_reports_help_text(),
+            PopupState::TrashFailed(err) => self.get_trash_failed_text(err),
             PopupState::ShowDetails(details) => {
                 self.get_transaction_details_text(details.to_string())
             }
-            PopupState::Nothing | PopupState::TxDeletion => String::new(),
+            PopupState::ChartExported(status) => self.get_chart_exported_text(status),
+            PopupState::JumpFailed(err) => self.get_jump_failed_text(err),
+            PopupState::RetagFailed(err) => self.get_retag_failed_text(err),
+            PopupState::DateRangeFailed(err) => self.get_date_range_failed_text(err),
+            PopupState::QuickAddFailed(err) => self.get_quick_add_failed_text(err),
+            PopupState::AttachmentFailed(err) => self.get_attachment_failed_text(err),
+            PopupState::ScheduledTxs(txs) => self.get_scheduled_txs_text(txs),
+            PopupState::TxMethodFailed(err) => self.get_tx_method_failed_text(err),
+            PopupState::TagRuleFailed(err) => self.get_tag_rule_failed_text(err),
+            PopupState::TagStats(stats) => self.get_tag_stats_text(stats),
+            PopupState::IntegrityReport(report) => self.get_integrity_report_text(report),
+            PopupState::BalanceAudit(report) => self.get_balance_audit_text(report),
+            PopupState::Maintenance(report) => self.get_maintenance_text(report),
+            PopupState::ViewLog(log) => self.get_view_log_text(log),
+            PopupState::RecoverForm(draft) => self.get_recover_form_text(draft),
+            PopupState::KeymapErrors(errors) => self.get_keymap_errors_text(errors),
+            PopupState::ThemeErrors(errors) => self.get_theme_errors_text(errors),
+            PopupState::InvalidConfig(err) => self.get_invalid_config_text(err),
+            PopupState::DbChanged => self.get_db_changed_text(),
+            PopupState::ReadOnly => self.get_read_only_text(),
+            PopupState::Nothing
+            | PopupState::TxDeletion
+            | PopupState::MarkedTxDeletion(_)
+            | PopupState::TrashPurge
+            | PopupState::EmptyTrash
+            | PopupState::JumpToDate(_)
+            | PopupState::MarkedTxRetag(_)
+            | PopupState::DateRange(_)
+            | PopupState::QuickAdd(_)
+            | PopupState::SplitTx(_)
+            | PopupState::TransferFee(_)
+            | PopupState::AttachFile(_)
+            | PopupState::NewTxMethod(_)
+            | PopupState::RenameTxMethod(_)
+            | PopupState::ArchiveTxMethod(_)
+            | PopupState::TxMethodType(_)
+            | PopupState::TxMethodCurrency(_)
+            | PopupState::StartingBalance(_)
+            | PopupState::MergeTxMethods(_)
+            | PopupState::TxMethodColor(_)
+            | PopupState::CreditLimit(_)
+            | PopupState::TxMethodGroup(_)
+            | PopupState::ConfirmMerge(_, _, _)
+            | PopupState::DeleteTxMethod(_)
+            | PopupState::DeleteMethodTarget(_, _, _)
+            | PopupState::ConfirmMethodDeletion(_, _, _)
+            | PopupState::TagRules(_, _)
+            | PopupState::SwitchProfile(_, _)
+            | PopupState::ExchangeRate(_)
+            | PopupState::TxNote(_) => String::new(),
         };
 
         if let PopupState::TxDeletion = popup_type {
-            create_deletion_popup(f, deletion_status);
+            create_deletion_popup(
+                f,
+                "TX Deletion",
+                deletion_status,
+                "Are you sure you want to delete this transaction?",
+                theme,
+            );
+        } else if let PopupState::MarkedTxDeletion(total) = popup_type {
+            create_deletion_popup(
+                f,
+                "TX Deletion",
+                deletion_status,
+                &format!("Are you sure you want to delete {total} marked transactions?"),
+                theme,
+            );
+        } else if let PopupState::TrashPurge = popup_type {
+            create_deletion_popup(
+                f,
+                "Trash",
+                deletion_status,
+                "Are you sure you want to remove this transaction from the trash for good?",
+                theme,
+            );
+        } else if let PopupState::EmptyTrash = popup_type {
+            create_deletion_popup(
+                f,
+                "Empty Trash",
+                deletion_status,
+                "Are you sure you want to remove every transaction in the trash for good?",
+                theme,
+            );
+        } else if let PopupState::ConfirmMerge(_, _, summary) = popup_type {
+            create_deletion_popup(f, "Merge Tx Methods", deletion_status, summary, theme);
+        } else if let PopupState::ConfirmMethodDeletion(_, _, summary) = popup_type {
+            create_deletion_popup(f, "Delete Tx Method", deletion_status, summary, theme);
+        } else if let PopupState::TagRules(rules, input) = popup_type {
+            create_input_popup(
+                f,
+                "Tag Rules",
+                &format!("{rules}\n\nNew transactions matching a rule get its tag. Add a rule like details uber, Transport or method Card, Card or amount >100, Big. Enter -1 to remove the 1st rule"),
+                input,
+                theme,
+            );
+        } else if let PopupState::SwitchProfile(profiles, input) = popup_type {
+            create_input_popup(
+                f,
+                "Switch Profile",
+                &format!("{profiles}\n\nEnter the profile to switch to. A new name creates a new profile with its own database. {COMBINED_PROFILE} shows the transactions of every profile together without changing them"),
+                input,
+                theme,
+            );
+        } else if let PopupState::DeleteTxMethod(input) = popup_type {
+            create_input_popup(
+                f,
+                "Delete Tx Method",
+                "Enter the name of the tx method to delete. The next step asks what happens to its transactions",
+                input,
+                theme,
+            );
+        } else if let PopupState::DeleteMethodTarget(method, total_txs, input) = popup_type {
+            create_input_popup(
+                f,
+                "Delete Tx Method",
+                &format!("{method} has {total_txs} transactions. Enter the tx method to move them to or enter delete to delete them with it"),
+                input,
+                theme,
+            );
+        } else if let PopupState::JumpToDate(input) = popup_type {
+            create_input_popup(
+                f,
+                "Go To Date",
+                "Enter a day of the month like 15, today or a full date like 2023-07-15",
+                input,
+                theme,
+            );
+        } else if let PopupState::DateRange(input) = popup_type {
+            create_input_popup(
+                f,
+                "Date Range",
+                "Enter the start and the end date like 2023-01-28 to 2023-02-09. Leave empty to go back to the month",
+                input,
+                theme,
+            );
+        } else if let PopupState::QuickAdd(input) = popup_type {
+            create_input_popup(
+                f,
+                "Quick Add",
+                "Enter a transaction like 12-05 groceries 45.50 card #food. Use +45.50 for income and card>cash for transfer",
+                input,
+                theme,
+            );
+        } else if let PopupState::TxNote(input) = popup_type {
+            create_text_area_popup(
+                f,
+                "Transaction Note",
+                "Write the long note of the transaction. It is saved alongside the transaction",
+                input,
+                theme,
+            );
+        } else if let PopupState::NewTxMethod(input) = popup_type {
+            create_input_popup(
+                f,
+                "New Tx Method",
+                "Enter the name of the new tx method like Savings. Add a starting balance after a comma like Savings, 500",
+                input,
+                theme,
+            );
+        } else if let PopupState::RenameTxMethod(input) = popup_type {
+            create_input_popup(
+                f,
+                "Rename Tx Method",
+                "Enter the current name of the tx method and the new name after a comma like Cash, Wallet",
+                input,
+                theme,
+            );
+        } else if let PopupState::ArchiveTxMethod(input) = popup_type {
+            create_input_popup(
+                f,
+                "Archive Tx Method",
+                "Enter the name of the tx method to archive. Archived methods are hidden but their txs are kept. Enter an archived method to bring it back",
+                input,
+                theme,
+            );
+        } else if let PopupState::TxMethodType(input) = popup_type {
+            create_input_popup(
+                f,
+                "Tx Method Type",
+                "Enter the tx method and its type after a comma like Card, Credit. Types: Cash, Bank, Credit, Savings",
+                input,
+                theme,
+            );
+        } else if let PopupState::TxMethodCurrency(input) = popup_type {
+            create_input_popup(
+                f,
+                "Tx Method Currency",
+                "Enter the tx method and its currency after a comma like Wallet, EUR. Leave the currency empty to use the base currency",
+                input,
+                theme,
+            );
+        } else if let PopupState::StartingBalance(input) = popup_type {
+            create_input_popup(
+                f,
+                "Starting Balance",
+                "Enter the tx method and its new starting balance after a comma like Wallet, 750. The difference is added as an adjustment on the date of its first transaction",
+                input,
+                theme,
+            );
+        } else if let PopupState::MergeTxMethods(input) = popup_type {
+            create_input_popup(
+                f,
+                "Merge Tx Methods",
+                "Enter the tx method to merge and the tx method to merge it into after a comma like Old Card, Card",
+                input,
+                theme,
+            );
+        } else if let PopupState::TxMethodColor(input) = popup_type {
+            create_input_popup(
+                f,
+                "Tx Method Color",
+                "Enter the tx method and its color after a comma like Wallet, green or Wallet, #ff5555. Leave the color empty to use the default one",
+                input,
+                theme,
+            );
+        } else if let PopupState::CreditLimit(input) = popup_type {
+            create_input_popup(
+                f,
+                "Credit Limit",
+                "Enter the credit tx method and its limit after a comma like Card, 5000. The used percentage of the limit is shown on the balance section. Use 0 to remove the limit",
+                input,
+                theme,
+            );
+        } else if let PopupState::TxMethodGroup(input) = popup_type {
+            create_input_popup(
+                f,
+                "Tx Method Group",
+                "Enter the tx method and its group after a comma like Wallet, Liquid. The balance of each group is shown in the Balance title. Leave the group empty to remove it",
+                input,
+                theme,
+            );
+        } else if let PopupState::AttachFile(input) = popup_type {
+            create_input_popup(
+                f,
+                "Attach File",
+                "Enter the path of a file to attach to the selected transaction like ~/receipts/tv.pdf. Leave empty to remove every attachment",
+                input,
+                theme,
+            );
+        } else if let PopupState::SplitTx(input) = popup_type {
+            create_input_popup(
+                f,
+                "Split Transaction",
+                "Enter the tag, the tx method or both with the amount of each part like food 30, @cash 20, household @card 10. The rest goes to the fields. Leave empty to remove the split",
+                input,
+                theme,
+            );
+        } else if let PopupState::TransferFee(input) = popup_type {
+            create_input_popup(
+                f,
+                "Transfer Fee",
+                "Enter the fee paid for the transfer like 2.50. It is added as an expense of the From Method tagged as fees. Leave empty to remove the fee",
+                input,
+                theme,
+            );
+        } else if let PopupState::ExchangeRate(input) = popup_type {
+            create_input_popup(
+                f,
+                "Exchange Rate",
+                "Enter how much of the To Method currency one unit of the From Method currency buys like 0.92. Needed for transfers between different currencies",
+                input,
+                theme,
+            );
+        } else if let PopupState::MarkedTxRetag(input) = popup_type {
+            create_input_popup(
+                f,
+                "Retag Marked",
+                "Enter a tag to add to all marked transactions. Start with - to remove it instead",
+                input,
+                theme,
+            );
         } else if !status.is_empty() {
             let new_line_count = status.split('\n').count();
             *max_popup_scroll = if new_line_count > 5 {
@@ -62,7 +337,7 @@ impl<'a> PopupData<'a> {
             } else {
                 new_line_count
             };
-            create_popup(f, self.title, &status, popup_scroll_position);
+            create_popup(f, self.title, &status, popup_scroll_position, theme);
         }
     }
 
@@ -85,7 +360,7 @@ Enter: Redirect to the new version",
             "This page is for adding new transactions. Following are the supported keys here. \
 On Transfer transaction there will be one additional field pushing Tags to the key 7. 
 
-1: Date         Example: 2022-05-12, YYYY-MM-DD
+1: Date         Example: 2022-05-12, YYYY-MM-DD. Also t, y, -3 for 3 days ago or 17 for a day of this month
 2: TX details   Example: For Grocery, Salary
 5: TX Type      Example: Income/Expense/I/E
 3: TX Method    Example: Cash, Bank, Card
@@ -93,22 +368,39 @@ On Transfer transaction there will be one additional field pushing Tags to the k
 6: Tags         Example: Food, Car. Add a Comma for a new tag
 
 S: Save the inputted data as a Transaction
+P: Schedule a transaction dated after today. It is added when the app is started on or after its date
+U: Show every scheduled transaction
 Enter: Submit field and continue. Also selects the first field if nothing is selected
 Esc: Stop editing field
 Tab: Accept Autocompletion. Pressing again will remove the autocompleted value
+Ctrl Z: Undo the last edit on the selected field
+Ctrl U: Clear the selected field
+Details are completed with the most used earlier details that start with the typed text
+The existing tags matching the last typed tag are listed beside the fields on the Tags field
 
-Arrow Up/Down: Steps value up/down by 1 when available
+Arrow Up/Down: Steps value up/down by 1 when available. Picks the next tx method from the list on the method fields
 Arrow Left/Right: Move cursor on input fields
 
 C: Clear all fields
+L: Fill the fields with the last added transaction to add a similar one
+T: Swap the From and To Method of a transfer
+B: Toggle batch entry. Saving keeps the page open with the date and the tx method kept for the next one
+X: Split the transaction into parts with their own tags, tx methods and amounts
+E: Add a fee to a transfer. The fee is added alongside it as an expense of the From Method
+G: Set the exchange rate of a transfer between tx methods of different currencies
+O: Write or edit the long note of the transaction in a popup. Shown in the transaction details
+N: Write or edit the long note of the transaction with the editor set in $EDITOR
 b: On amount field 'b' gets replaced with the current balance of Tx Method field
 k: On amount field 'k' is considered as 1000 or a thousand
 m: On amount field 'm' is considered as 1,000,000 or a million
-Calculation: Amount field supports simple calculation with +, -, *, /
+Calculation: Amount field supports calculation with +, -, *, / and parentheses
+%: On amount field '10%' is considered as 10 percent of the current balance of Tx Method field
+Enter on a calculation shows the result first. Press Enter again to accept it
+Currency: '25 EUR' is converted to the base currency set in config.json and the entered amount is kept
 Tags: This field can be treated as the category of this transaction.
-Empty tags field gets replaced with Unknown. Separate more than 1 tags with a comma
+Empty tags field gets replaced with Unknown. Separate more than 1 tags with a comma. require_tag, allowed_tags and max_tags in config.json limit the accepted tags
 
-Example amount: 100 + b, b + b, 5 * b, 1.2k + 1m
+Example amount: 100 + b, b + b, 5 * b, 1.2k + 1m, (12.99 * 3) + 4.50, 10%
 
 {F}
 {R}
@@ -130,7 +422,15 @@ Example amount: 100 + b, b + b, 5 * b, 1.2k + 1m
 Following are the supported keys here
 
 R: Hides the top widgets for full chart view
-Space: Enable/Disable tx method from the chart
+C: Cycle between balance, cumulative income/expense and tag spending view
+L: Switch the y axis between linear and logarithmic scale
+E: Export the current chart as an SVG image to the Pictures directory
+S: Cycle chart animation speed between 1x, 2x, 4x and off
+K: Switch between daily and weekly (ISO week) points
+G: Show only the tx methods of the next tx method group. After the last group every tx method is shown again
+Space: Enable/Disable tx method or tag from the chart. Total shows the sum of all enabled tx methods
+
+The highest and the lowest point of each line are marked on the chart with their values shown below it
 
 Arrow Up/Down: Cycle widgets
 Arrow Left/Right: Move value of the widget
@@ -152,12 +452,21 @@ Arrow Left/Right: Move value of the widget
         format!(
             "This page shows various information based on all transactions \
             and is for tracking incomes and expenses based on tags \
-            Transfer Transaction are not shown here
+            Transfer Transaction are not shown here unless enabled
 
 Following are the supported keys here
 
-X: Sorts table by Tag, Total Income or Total Expense
+X: Reverses the sorting direction of the table column. Moves to the next column after both directions
 Z: Hides the top widgets for full view
+C: Compares each tag with the earlier month or year
+L: Switches the table between the tags and the largest transactions
+I: Shows the lifetime total, the monthly average, the trend of the last 12 months and the first and the last transaction of the selected tag
+T: Switches whether transfers are counted as an expense and an income of the related methods
+
+Selecting a tag on the table shows how it is split across the tx methods
+Tags written as parent:child are added up under the parent tag. Enter on a parent tag expands or collapses it
+The trend column shows the total of each tag in the last 12 months ending at the selected period
+Enter on a largest transaction opens it on the Home page
 
 Arrow Up/Down: Cycle widgets/table value
 Arrow Left/Right: Move value of the widget
@@ -178,17 +487,53 @@ Arrow Left/Right: Move value of the widget
         self.set_title("Help");
         format!("This is the Home page where all txs added so far, the balances and the changes are shown
 
-J: Take user input for various actions
+J: Take user input for various actions. Shift + J adds a new tx method with an optional starting balance
+Shift + R: Rename a tx method everywhere it is used
+Shift + H: Archive a tx method to hide it or bring back an archived one
+Shift + T: Set the type of a tx method. Credit balances are shown as the owed amount and Savings are left out of the spendable balance
+Shift + C: Set the currency of a tx method. Transfers between different currencies need an exchange rate
+Shift + B: Change the starting balance of a tx method. The difference is added as an adjustment transaction
+Shift + U: Merge a tx method into another. Its transactions and balance move to the other one before it is removed
+Shift + K: Set the color of a tx method used on the balance sections, the chart lines and the tx method selection
+Shift + L: Set the credit limit of a credit tx method. The used percentage turns red after credit_utilization_warning in config.json
+Shift + G: Put a tx method in a group like Liquid or Invested. The balance of each group is shown in the Balance title
+Shift + P: Switch to another profile like Business with its own database. The profile is shown in the Transactions title
+Shift + A: Edit the tag rules. New transactions get the tag of every rule their details, tx method or amount matches
+Shift + D: Delete a tx method. Its transactions are moved to another tx method or deleted with it after a confirmation
+Shift + I: Check the database for problems. The balances and the changes can be rebuilt from the transactions afterwards
+Shift + V: Verify the saved balance of every month against the transactions. Shift + R on the result fixes them
+Shift + O: Database maintenance. Shows the size and the row counts and can compact, reindex or back up the database
+Shift + X: Trash Page. Deleted transactions can be restored or removed for good there
+Shift + Q: Reports Page. Shows the result of the SQL queries saved as reports in config.json
 E: Edit the selected transaction on the table
-D: Delete the selected transaction on the table
+D: Delete the selected transaction on the table. It is moved to the trash
 ,: Swaps the location of the selected transaction with the transaction above it
 .: Swaps the location of the selected transaction with the transaction below it
+X: Reverses the sorting direction of the table column. Moves to the next column after both directions
+G: Shows or hides the filter bar for filtering the table by tag, tx method and tx type. The Untagged tag filter lists the transactions without a tag so they can be fixed
+B: Switches whether the balance, income and expense rows only count the filtered transactions
+T: Selects the first transaction on or after a date. Accepts a day of the month, today or a full date
+Space: Marks or unmarks the selected transaction. D deletes all marked transactions together when any is marked
+L: Adds a tag to all marked transactions. Start the tag with - to remove it from them instead
+N: Shows the balance after each transaction as a table column. Cycles through the total balance and each tx method
+K: Switches between showing the whole month and a single week of the month. The week is selected from the Weeks tab
+O: Shows the transactions between two dates. Changing the month, the year or the week goes back to them
+/: Filters the table by the details or the tags while typing when the table is selected. Enter keeps the filter, Esc removes it
+C: Groups the table rows under a header row for each day with the day's income minus expense. Only works when sorted by date
+Enter: Collapses or expands the day of the selected transaction while the rows are grouped
+P: Adds a subtotal row after each day with the day's income and expense. Only works when sorted by date
+S: Cycles the status of the selected transaction between pending ○, cleared ✓ and reconciled ✓✓
+U: Switches the balance between every transaction and only the cleared and reconciled ones
+I: Adds a transaction from a single line like 12-05 groceries 45.50 card #food without opening the Add Transaction page
+M: Opens the files attached to the selected transaction. Shift + M attaches a file like a receipt to it
 {V}
 
 Arrow Up/Down: Cycle widgets/table value
 Arrow Left/Right: Move value of the widget
 
-Swapping transaction location will only work if they are on the same date. 
+Swapping transaction location will only work if they are on the same date and the table is sorted by date. 
+
+Transactions dated after today are dimmed and kept at the end of the table. The balance leaves them out until their date while the projected balance in the Balance title counts them
 
 {A}
 {R}
@@ -206,6 +551,162 @@ Swapping transaction location will only work if they are on the same date.
         err.to_string()
     }
 
+    #[cfg(not(tarpaulin_include))]
+    fn get_chart_exported_text(&mut self, status: &str) -> String {
+        self.set_title("Chart Export");
+        status.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_jump_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Go To Date Failed");
+        err.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_date_range_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Date Range Failed");
+        err.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_quick_add_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Quick Add Failed");
+        err.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_attachment_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Attachment Failed");
+        err.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_tx_method_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Tx Method Failed");
+        err.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_tag_rule_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Tag Rule Failed");
+        err.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_tag_stats_text(&mut self, stats: &str) -> String {
+        self.set_title("Tag Statistics");
+        stats.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_integrity_report_text(&mut self, report: &str) -> String {
+        self.set_title("Integrity Check");
+        report.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_balance_audit_text(&mut self, report: &str) -> String {
+        self.set_title("Verify Balances");
+        report.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_maintenance_text(&mut self, report: &str) -> String {
+        self.set_title("Maintenance");
+        report.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_view_log_text(&mut self, log: &str) -> String {
+        self.set_title("Log");
+        log.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_recover_form_text(&mut self, draft: &TxDraft) -> String {
+        self.set_title("Unsent Transaction");
+
+        let form = if draft.editing_id.is_some() {
+            "A transaction was being edited"
+        } else {
+            "A new transaction was being filled"
+        };
+
+        format!(
+            "{form} when Rex last stopped and it was never saved.
+
+{}
+
+R/Enter: Resume in the Add Transaction page
+D: Discard it",
+            draft.get_summary()
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_keymap_errors_text(&mut self, errors: &str) -> String {
+        self.set_title("Key Bindings");
+        format!(
+            "These key bindings of config.json could not be used. Their actions keep the default key.
+
+{errors}"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_theme_errors_text(&mut self, errors: &str) -> String {
+        self.set_title("Theme");
+        format!(
+            "These theme settings of config.json could not be used. The colors of the theme are used in their place.
+
+{errors}"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_invalid_config_text(&mut self, err: &str) -> String {
+        self.set_title("Config");
+        format!(
+            "config.json could not be loaded so the default settings are used. Fix the file and restart Rex to use it.
+
+{err}"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_read_only_text(&mut self) -> String {
+        self.set_title("Read Only");
+        "Rex was started with --read-only. The database can be browsed but nothing in it can be changed."
+            .to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_db_changed_text(&mut self) -> String {
+        self.set_title("Database Changed");
+        "The database file was changed outside of Rex while it was open. \
+This usually happens when a sync app like Syncthing or Dropbox brings in changes made on another device.
+
+R: Reload the changed database. Anything saved here after the change is dropped
+K: Keep the data of this session and save it over the changed database
+C: Keep both. The data of this session is saved as a conflict copy next to the database \
+and the changed database is opened
+Q: Quit without changing anything"
+            .to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_scheduled_txs_text(&mut self, txs: &str) -> String {
+        self.set_title("Scheduled Transactions");
+        txs.to_string()
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_retag_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Retag Failed");
+        err.to_string()
+    }
+
     #[cfg(not(tarpaulin_include))]
     fn get_search_help_text(&mut self) -> String {
         self.set_title("Help");
@@ -213,7 +714,7 @@ Swapping transaction location will only work if they are on the same date.
             "This page is for searching transactions. \
             On Transfer transaction there will be one additional field pushing Tags to the key 7.
 
-1: Date         Example: 2022-05-12, YYYY-MM-DD
+1: Date         Example: 2022-05-12, YYYY-MM-DD. Also t, y, -3 for 3 days ago or 17 for a day of this month
 2: TX details   Example: For Grocery, Salary
 5: TX Type      Example: Income/Expense/I/E
 3: TX Method    Example: Cash, Bank, Card
@@ -228,6 +729,8 @@ X: Cycle date type for searching with exact date, month based or year based
 Enter: Submit field and continue. Also selects the first field if nothing is selected
 Esc: Stop editing field
 Tab: Accept Autocompletion. Pressing again will remove the autocompleted value
+Ctrl Z: Undo the last edit on the selected field
+Ctrl U: Clear the selected field
 
 Arrow Up/Down: Steps value up/down by 1
 Arrow Left/Right: Move cursor on input fields
@@ -235,9 +738,11 @@ C: Clear all fields
 b: On amount field 'b' gets replaced with the current balance of Tx Method field
 k: On amount field 'k' is considered as 1000 or a thousand
 m: On amount field 'm' is considered as 1,000,000 or a million
-Calculation: Amount field supports simple calculation with +, -, *, /
+Calculation: Amount field supports calculation with +, -, *, / and parentheses
+%: On amount field '10%' is considered as 10 percent of the current balance of Tx Method field
+Enter on a calculation shows the result first. Press Enter again to accept it
 
-Example amount: 100 + b, b + b, 5 * b, 1.2k + 5m
+Example amount: 100 + b, b + b, 5 * b, 1.2k + 5m, (12.99 * 3) + 4.50, 10%
 
 Details Field: If details field is filled up, it will try to find transactions \
                 that matches the given input. It doesn't have to be an exact match
@@ -284,6 +789,67 @@ Arrow Left/Right: Move value of the widget
         )
     }
 
+    fn get_trash_help_text(&mut self) -> String {
+        self.set_title("Help");
+        format!(
+            "This page shows the deleted transactions, the latest one first. \
+            A restored transaction gets back its ID if no other transaction took it.
+
+Following are the supported keys here
+
+Enter: Restore the selected transaction
+D: Remove the selected transaction from the trash for good
+Shift + D: Remove every transaction from the trash for good
+
+Arrow Up/Down: Select a transaction
+
+{F}
+{A}
+{R}
+{Z}
+{W}
+{Y}
+{H}
+{Q}
+"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_reports_help_text(&mut self) -> String {
+        self.set_title("Help");
+        format!(
+            "This page shows the result of the reports saved in config.json. \
+            Each report has a name and an SQL query that reads the database like
+
+\"reports\": [{{\"name\": \"Spent by tag\", \"query\": \"SELECT tags, SUM(amount) FROM tx_all WHERE tx_type = 'Expense' GROUP BY tags\"}}]
+
+Queries that would change the database are not run.
+
+Following are the supported keys here
+
+Arrow Left/Right: Switch to the previous or the next report
+Arrow Up/Down: Select a row
+Enter: Run the report again
+
+{F}
+{A}
+{R}
+{Z}
+{W}
+{Y}
+{H}
+{Q}
+"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_trash_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Trash Failed");
+        err.to_string()
+    }
+
     fn get_transaction_details_text(&mut self, details: String) -> String {
         self.set_title("Transaction Details");
         details
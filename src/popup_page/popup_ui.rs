@@ -6,12 +6,12 @@ use ratatui::widgets::{
 };
 use ratatui::Frame;
 
-use crate::page_handler::{DeletionStatus, BACKGROUND, BLUE, BOX, HIGHLIGHTED, RED, TEXT};
+use crate::page_handler::{DeletionStatus, Theme};
 use crate::utility::create_bolded_text;
 
 /// Creates a popup on top of a window with the given size, title and text attributes
 #[cfg(not(tarpaulin_include))]
-pub fn create_popup(f: &mut Frame, title: &str, text: &str, position: usize) {
+pub fn create_popup(f: &mut Frame, title: &str, text: &str, position: usize, theme: &Theme) {
     let size = f.size();
     let x_value = 60;
     let y_value = 60;
@@ -25,7 +25,7 @@ pub fn create_popup(f: &mut Frame, title: &str, text: &str, position: usize) {
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .style(Style::default().bg(BACKGROUND).fg(BOX));
+        .style(Style::default().bg(theme.background).fg(theme.border));
 
     // returns an area where we can add anything like a normal window.
     let area = centered_rect(x_value, y_value, size);
@@ -40,15 +40,15 @@ pub fn create_popup(f: &mut Frame, title: &str, text: &str, position: usize) {
     f.render_widget(block, area);
 
     let help_sec = Paragraph::new(Text::from(text))
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
+        .style(Style::default().bg(theme.background).fg(theme.text))
         .wrap(Wrap::default())
         .scroll((position as u16, 0));
 
     let dismiss_sec = Paragraph::new("Use Arrow Keys To Scroll. Press Any Other Key To Dismiss")
         .style(
             Style::default()
-                .bg(BACKGROUND)
-                .fg(RED)
+                .bg(theme.background)
+                .fg(theme.expense)
                 .add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center);
@@ -64,22 +64,28 @@ pub fn create_popup(f: &mut Frame, title: &str, text: &str, position: usize) {
 }
 
 #[cfg(not(tarpaulin_include))]
-pub fn create_deletion_popup(f: &mut Frame, deletion_status: &DeletionStatus) {
-    let text = "Are you sure you want to delete this transaction?";
-    let title = "TX Deletion";
+pub fn create_deletion_popup(
+    f: &mut Frame,
+    title: &str,
+    deletion_status: &DeletionStatus,
+    text: &str,
+    theme: &Theme,
+) {
     let size = f.size();
 
+    // multi line confirmations need more room above the options
+    let y_value = if text.lines().count() > 1 { 50 } else { 25 };
+
     let title = Span::styled(title, Style::default().add_modifier(Modifier::BOLD));
     let text = create_bolded_text(text);
 
     // determines the size of the popup window
     let x_value = 40;
-    let y_value = 25;
 
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .style(Style::default().bg(BACKGROUND).fg(BOX));
+        .style(Style::default().bg(theme.background).fg(theme.border));
 
     // returns an area where we can add anything like a normal window.
     let area = centered_rect(x_value, y_value, size);
@@ -100,20 +106,22 @@ pub fn create_deletion_popup(f: &mut Frame, deletion_status: &DeletionStatus) {
     f.render_widget(block, area);
 
     let deletion_text = Paragraph::new(Text::from(text))
-        .style(Style::default().bg(BACKGROUND).fg(TEXT))
+        .style(Style::default().bg(theme.background).fg(theme.text))
         .alignment(Alignment::Center);
 
     let yes_text = match deletion_status {
         DeletionStatus::Yes => Span::styled(
             " Yes ",
             Style::default()
-                .fg(RED)
+                .fg(theme.expense)
                 .add_modifier(Modifier::BOLD)
-                .bg(HIGHLIGHTED),
+                .bg(theme.highlighted),
         ),
         DeletionStatus::No => Span::styled(
             " Yes ",
-            Style::default().fg(RED).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.expense)
+                .add_modifier(Modifier::BOLD),
         ),
     };
 
@@ -121,13 +129,15 @@ pub fn create_deletion_popup(f: &mut Frame, deletion_status: &DeletionStatus) {
         DeletionStatus::No => Span::styled(
             " No ",
             Style::default()
-                .fg(BLUE)
+                .fg(theme.income)
                 .add_modifier(Modifier::BOLD)
-                .bg(HIGHLIGHTED),
+                .bg(theme.highlighted),
         ),
         DeletionStatus::Yes => Span::styled(
             " No ",
-            Style::default().fg(BLUE).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.income)
+                .add_modifier(Modifier::BOLD),
         ),
     };
 
@@ -140,6 +150,124 @@ pub fn create_deletion_popup(f: &mut Frame, deletion_status: &DeletionStatus) {
     f.render_widget(no_sec, selection_chunk[1]);
 }
 
+/// Creates a popup with a single line input field below the given text
+#[cfg(not(tarpaulin_include))]
+pub fn create_input_popup(f: &mut Frame, title: &str, text: &str, input: &str, theme: &Theme) {
+    let size = f.size();
+
+    let title = Span::styled(title, Style::default().add_modifier(Modifier::BOLD));
+    let text = create_bolded_text(text);
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background).fg(theme.border));
+
+    // longer texts like a list get more room
+    let height = if text.len() > 1 { 50 } else { 25 };
+    let area = centered_rect(50, height, size);
+
+    let new_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let text_sec = Paragraph::new(Text::from(text))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .wrap(Wrap::default())
+        .alignment(Alignment::Center);
+
+    let input_sec = Paragraph::new(format!("{input}|"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().bg(theme.background).fg(theme.border)),
+        );
+
+    let dismiss_sec = Paragraph::new("Enter: Confirm. Esc: Cancel")
+        .style(
+            Style::default()
+                .bg(theme.background)
+                .fg(theme.expense)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(text_sec, new_chunks[0]);
+    f.render_widget(input_sec, new_chunks[1]);
+    f.render_widget(dismiss_sec, new_chunks[2]);
+}
+
+/// Creates a popup with a multi-line text area. The end of the text stays visible
+/// when it gets longer than the area
+#[cfg(not(tarpaulin_include))]
+pub fn create_text_area_popup(f: &mut Frame, title: &str, text: &str, input: &str, theme: &Theme) {
+    let size = f.size();
+
+    let title = Span::styled(title, Style::default().add_modifier(Modifier::BOLD));
+    let text = create_bolded_text(text);
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background).fg(theme.border));
+
+    let area = centered_rect(60, 60, size);
+
+    let new_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let text_sec = Paragraph::new(Text::from(text))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .wrap(Wrap::default())
+        .alignment(Alignment::Center);
+
+    // 2 lines are taken by the borders
+    let visible_lines = new_chunks[1].height.saturating_sub(2);
+    let total_lines = input.split('\n').count() as u16;
+
+    let input_sec = Paragraph::new(format!("{input}|"))
+        .style(Style::default().bg(theme.background).fg(theme.text))
+        .scroll((total_lines.saturating_sub(visible_lines), 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().bg(theme.background).fg(theme.border)),
+        );
+
+    let dismiss_sec = Paragraph::new("Enter: New line. Esc: Save and close")
+        .style(
+            Style::default()
+                .bg(theme.background)
+                .fg(theme.expense)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(text_sec, new_chunks[0]);
+    f.render_widget(input_sec, new_chunks[1]);
+    f.render_widget(dismiss_sec, new_chunks[2]);
+}
+
 /// The function takes certain parameters to create an empty space in the layout
 /// and returns an area where we can place various widgets. Taken from tui-rs examples.
 /// This is used as a popup for helpful information.
@@ -0,0 +1,7 @@
+mod page;
+mod report_data;
+mod report_ui;
+
+pub use page::ReportsPage;
+pub use report_data::ReportData;
+pub use report_ui::report_ui;
@@ -0,0 +1,30 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::key_checker::{report_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+use crate::report_page::report_ui;
+
+pub struct ReportsPage;
+
+impl Page for ReportsPage {
+    fn name(&self) -> &str {
+        "Reports"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, context: &PageContext) {
+        let reports = &mut state.reports;
+
+        report_ui(
+            f,
+            &context.config.reports,
+            &reports.data,
+            &mut reports.table,
+            &state.theme,
+        );
+    }
+
+    fn get_action(&self, key: KeyEvent, _state: &AppState) -> Option<Action> {
+        report_keys(key)
+    }
+}
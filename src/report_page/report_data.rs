@@ -0,0 +1,87 @@
+use rusqlite::Connection;
+
+use crate::db::run_report_query;
+use crate::outputs::ReportError;
+use crate::utility::ReportQuery;
+
+/// Stores the shown report of the Reports page and the result of its query
+pub struct ReportData {
+    /// The index of the shown report among the reports of the config
+    index: usize,
+    /// The column names of the last result
+    columns: Vec<String>,
+    /// Why the last query returned nothing
+    error: Option<String>,
+}
+
+impl Default for ReportData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReportData {
+    pub fn new() -> Self {
+        ReportData {
+            index: 0,
+            columns: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Runs the query of the shown report and returns its rows. The column names are kept for
+    /// the table header and failures are kept to be shown in place of the table
+    pub fn load(&mut self, reports: &[ReportQuery], conn: &Connection) -> Vec<Vec<String>> {
+        self.columns.clear();
+        self.error = None;
+
+        if reports.is_empty() {
+            self.error = Some(ReportError::NoReport.to_string());
+            return Vec::new();
+        }
+
+        // the config may have less reports after it was reloaded
+        self.index = self.index.min(reports.len() - 1);
+
+        match run_report_query(&reports[self.index].query, conn) {
+            Ok(result) => {
+                self.columns = result.columns;
+                result.rows
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                Vec::new()
+            }
+        }
+    }
+
+    /// Moves to the next report. Goes to the first one after the last report
+    pub fn next(&mut self, total_reports: usize) {
+        if total_reports != 0 {
+            self.index = (self.index + 1) % total_reports;
+        }
+    }
+
+    /// Moves to the previous report. Goes to the last one before the first report
+    pub fn previous(&mut self, total_reports: usize) {
+        if total_reports != 0 {
+            self.index = (self.index + total_reports - 1) % total_reports;
+        }
+    }
+
+    /// Returns the title of the report table like Food by month (1/3)
+    pub fn get_title(&self, reports: &[ReportQuery]) -> String {
+        match reports.get(self.index) {
+            Some(report) => format!("{} ({}/{})", report.name, self.index + 1, reports.len()),
+            None => "Reports".to_string(),
+        }
+    }
+
+    pub fn get_columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn get_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
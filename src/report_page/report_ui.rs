@@ -0,0 +1,71 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Paragraph, Row, Table, Wrap};
+use ratatui::Frame;
+
+use crate::page_handler::{TableData, Theme};
+use crate::report_page::ReportData;
+use crate::utility::{main_block, styled_block, ReportQuery};
+
+/// The interface of the Reports page. Shows the result of a saved report query as a table
+pub fn report_ui(
+    f: &mut Frame,
+    reports: &[ReportQuery],
+    report_data: &ReportData,
+    table_data: &mut TableData,
+    theme: &Theme,
+) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Min(0)])
+        .split(size);
+
+    f.render_widget(main_block(theme), size);
+
+    let title = report_data.get_title(reports);
+
+    if let Some(error) = report_data.get_error() {
+        let error_text = Paragraph::new(error)
+            .style(Style::default().bg(theme.background).fg(theme.text))
+            .block(styled_block(&title, theme))
+            .wrap(Wrap { trim: true });
+        f.render_widget(error_text, chunks[0]);
+        return;
+    }
+
+    let columns = report_data.get_columns();
+
+    let header_cells = columns
+        .iter()
+        .map(|h| Cell::from(h.to_string()).style(Style::default().fg(theme.background)));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(theme.header))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table_data.items.iter().map(|item| {
+        let cells = item.iter().map(|c| Cell::from(c.to_string()));
+        Row::new(cells)
+            .height(1)
+            .bottom_margin(0)
+            .style(Style::default().bg(theme.background).fg(theme.text))
+    });
+
+    // every column gets the same width
+    let total_columns = columns.len().max(1) as u32;
+    let widths = vec![Constraint::Ratio(1, total_columns); columns.len()];
+
+    let table_name = format!("{title}: {} row(s)", table_data.items.len());
+
+    let table_area = Table::new(rows, widths)
+        .header(header)
+        .block(styled_block(&table_name, theme))
+        .highlight_symbol(">> ")
+        .highlight_style(Style::default().bg(theme.selected));
+
+    f.render_stateful_widget(table_area, chunks[0], &mut table_data.state);
+}
@@ -1,3 +1,5 @@
+mod page;
 mod search_ui;
 
+pub use page::SearchPage;
 pub use search_ui::search_ui;
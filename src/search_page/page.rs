@@ -0,0 +1,31 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::key_checker::{search_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+use crate::search_page::search_ui;
+
+pub struct SearchPage;
+
+impl Page for SearchPage {
+    fn name(&self) -> &str {
+        "Search"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, _context: &PageContext) {
+        let search = &mut state.search;
+
+        search_ui(
+            f,
+            &search.data,
+            &search.tab,
+            &mut search.table,
+            &search.date_type,
+            &state.theme,
+        );
+    }
+
+    fn get_action(&self, key: KeyEvent, state: &AppState) -> Option<Action> {
+        search_keys(key, &state.search.tab)
+    }
+}
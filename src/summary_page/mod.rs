@@ -1,5 +1,6 @@
-mod summary_data;
+mod page;
 mod summary_ui;
 
-pub use summary_data::SummaryData;
+pub use page::SummaryPage;
+pub use rex_core::summary::*;
 pub use summary_ui::summary_ui;
@@ -0,0 +1,49 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::key_checker::{summary_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+use crate::summary_page::summary_ui;
+
+pub struct SummaryPage;
+
+impl Page for SummaryPage {
+    fn name(&self) -> &str {
+        "Summary"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, context: &PageContext) {
+        let AppState {
+            summary,
+            methods,
+            theme,
+            ..
+        } = state;
+
+        summary_ui(
+            f,
+            &summary.months,
+            &summary.years,
+            &summary.modes,
+            &summary.tx_methods,
+            &summary.data,
+            &mut summary.table,
+            &summary.tab,
+            summary.hidden_mode,
+            &summary.sort,
+            &summary.sort_direction,
+            summary.comparison,
+            summary.largest_txs,
+            summary.include_transfers,
+            &summary.expanded_tags,
+            summary.loading,
+            &methods.all,
+            context.conn,
+            theme,
+        );
+    }
+
+    fn get_action(&self, key: KeyEvent, _state: &AppState) -> Option<Action> {
+        summary_keys(key)
+    }
+}
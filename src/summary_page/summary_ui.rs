@@ -3,27 +3,38 @@ use ratatui::style::{Modifier, Style};
 use ratatui::widgets::{Cell, Row, Table};
 use ratatui::Frame;
 use rusqlite::Connection;
+use std::collections::HashSet;
 use thousands::Separable;
 
 use crate::page_handler::{
-    IndexedData, SortingType, SummaryTab, TableData, BACKGROUND, BOX, HEADER, SELECTED, TEXT,
+    IndexedData, SortingDirection, SortingType, SummaryTab, TableData, Theme,
 };
 use crate::summary_page::SummaryData;
-use crate::utility::{create_tab, get_all_tx_methods, main_block, styled_block};
+use crate::utility::{create_tab, get_sparkline, main_block, reverse_date_format, styled_block};
 
 /// The function draws the Summary page of the interface.
 #[cfg(not(tarpaulin_include))]
+#[allow(clippy::too_many_arguments)]
 pub fn summary_ui(
     f: &mut Frame,
     months: &IndexedData,
     years: &IndexedData,
     mode_selection: &IndexedData,
+    summary_tx_methods: &IndexedData,
     summary_data: &SummaryData,
     table_data: &mut TableData,
     current_page: &SummaryTab,
     summary_hidden_mode: bool,
     summary_sort: &SortingType,
+    summary_sort_direction: &SortingDirection,
+    summary_comparison: bool,
+    summary_largest_txs: bool,
+    summary_include_transfers: bool,
+    summary_expanded_tags: &HashSet<String>,
+    loading: bool,
+    all_methods: &[String],
     conn: &Connection,
+    theme: &Theme,
 ) {
     let (summary_data_1, summary_data_2, summary_data_3, summary_data_4, method_data) =
         summary_data.get_tx_data(mode_selection, months.index, years.index, conn);
@@ -33,43 +44,65 @@ pub fn summary_ui(
     let mut summary_table_3 = TableData::new(summary_data_3);
     let mut summary_table_4 = TableData::new(summary_data_4);
     let mut method_table = TableData::new(method_data);
+    let statistics_data = summary_data.get_statistics(mode_selection, months.index, years.index);
 
     let size = f.size();
 
-    let tag_header = if let SortingType::ByTags = summary_sort {
-        "Tags▼"
-    } else {
-        "Tags"
+    // the sorted column gets an arrow showing the sorting direction
+    let get_header = |header: &str, sort_type: SortingType| {
+        if *summary_sort == sort_type {
+            format!("{header}{}", summary_sort_direction.get_arrow())
+        } else {
+            header.to_string()
+        }
     };
 
-    let total_income_header = if let SortingType::ByIncome = summary_sort {
-        "Total Income▼"
+    let mut header_cells = if summary_largest_txs {
+        ["Date", "Details", "Tx Method", "Amount", "Tx Type"]
+            .into_iter()
+            .map(String::from)
+            .collect()
     } else {
-        "Total Income"
+        vec![
+            get_header("Tags", SortingType::ByTags),
+            get_header("Total Income", SortingType::ByIncome),
+            get_header("Total Expense", SortingType::ByExpense),
+            get_header("Net", SortingType::ByNet),
+            get_header("Income %", SortingType::ByIncomePercentage),
+            get_header("Expense %", SortingType::ByExpensePercentage),
+            get_header("Of Income %", SortingType::ByIncomeShare),
+        ]
     };
 
-    let total_expense_header = if let SortingType::ByExpense = summary_sort {
-        "Total Expense▼"
+    let data_columns = header_cells.len();
+
+    // the monthly trend of each tag of the last 12 months
+    let tag_trends = if summary_largest_txs {
+        None
     } else {
-        "Total Expense"
+        header_cells.push(String::from("Trend"));
+        Some(summary_data.get_tag_trends(mode_selection, months.index, years.index))
     };
 
-    let net_header = if let SortingType::ByNet = summary_sort {
-        "Net"
+    // the total of each tag in the earlier period and the % change from it
+    let comparison_data = if summary_comparison && !summary_largest_txs {
+        header_cells.push(String::from("Previous"));
+        header_cells.push(String::from("Change %"));
+        summary_data.get_comparison_data(
+            &table_data.items,
+            mode_selection,
+            months.index,
+            years.index,
+        )
     } else {
-        "Net"
+        Vec::new()
     };
 
-    let header_cells = [
-        tag_header,
-        total_income_header,
-        total_expense_header,
-        net_header,
-        "Income %",
-        "Expense %",
-    ]
-    .into_iter()
-    .map(|h| Cell::from(h).style(Style::default().fg(BACKGROUND)));
+    let total_columns = header_cells.len() as u16;
+
+    let header_cells = header_cells
+        .into_iter()
+        .map(|h| Cell::from(h).style(Style::default().fg(theme.background)));
 
     let method_header_cells = [
         "Method",
@@ -81,19 +114,19 @@ pub fn summary_ui(
         "Average Expense",
     ]
     .iter()
-    .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+    .map(|h| Cell::from(*h).style(Style::default().fg(theme.background)));
 
     let header = Row::new(header_cells)
-        .style(Style::default().bg(HEADER))
+        .style(Style::default().bg(theme.header))
         .height(1)
         .bottom_margin(0);
 
     let method_header = Row::new(method_header_cells)
-        .style(Style::default().bg(HEADER))
+        .style(Style::default().bg(theme.header))
         .height(1)
         .bottom_margin(0);
 
-    let method_len = get_all_tx_methods(conn).len() as u16;
+    let method_len = all_methods.len() as u16;
 
     let mut main_layout = Layout::default().direction(Direction::Vertical).margin(2);
     let mut summary_layout = Layout::default().direction(Direction::Horizontal);
@@ -104,8 +137,11 @@ pub fn summary_ui(
             Constraint::Length(9),
             Constraint::Min(0),
         ]);
-        summary_layout =
-            summary_layout.constraints([Constraint::Percentage(50), Constraint::Percentage(50)]);
+        summary_layout = summary_layout.constraints([
+            Constraint::Percentage(35),
+            Constraint::Percentage(35),
+            Constraint::Percentage(30),
+        ]);
     } else {
         match mode_selection.index {
             0 => {
@@ -113,33 +149,45 @@ pub fn summary_ui(
                     Constraint::Length(3),
                     Constraint::Length(3),
                     Constraint::Length(3),
+                    Constraint::Length(3),
                     Constraint::Length(method_len + 3),
                     Constraint::Length(9),
                     Constraint::Min(0),
                 ]);
-                summary_layout = summary_layout
-                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)]);
+                summary_layout = summary_layout.constraints([
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(30),
+                ]);
             }
             1 => {
                 main_layout = main_layout.constraints([
+                    Constraint::Length(3),
                     Constraint::Length(3),
                     Constraint::Length(3),
                     Constraint::Length(method_len + 3),
                     Constraint::Length(9),
                     Constraint::Min(0),
                 ]);
-                summary_layout = summary_layout
-                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)]);
+                summary_layout = summary_layout.constraints([
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(30),
+                ]);
             }
             2 => {
                 main_layout = main_layout.constraints([
+                    Constraint::Length(3),
                     Constraint::Length(3),
                     Constraint::Length(method_len + 3),
                     Constraint::Length(9),
                     Constraint::Min(0),
                 ]);
-                summary_layout = summary_layout
-                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)]);
+                summary_layout = summary_layout.constraints([
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(30),
+                ]);
             }
             _ => {}
         };
@@ -149,7 +197,7 @@ pub fn summary_ui(
     let summary_chunk = if summary_hidden_mode {
         summary_layout.split(chunks[1])
     } else {
-        summary_layout.split(chunks[4 - mode_selection.index])
+        summary_layout.split(chunks[5 - mode_selection.index])
     };
 
     let left_summary = Layout::default()
@@ -162,22 +210,63 @@ pub fn summary_ui(
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
         .split(summary_chunk[1]);
 
-    f.render_widget(main_block(), size);
+    f.render_widget(main_block(theme), size);
 
-    let mut month_tab = create_tab(months, "Months");
+    let mut month_tab = create_tab(months, "Months", theme);
 
-    let mut year_tab = create_tab(years, "Years");
+    let mut year_tab = create_tab(years, "Years", theme);
 
-    let mut mode_selection_tab = create_tab(mode_selection, "Modes");
+    let mut mode_selection_tab = create_tab(mode_selection, "Modes", theme);
+
+    let mut tx_method_tab = create_tab(summary_tx_methods, "Tx Methods", theme);
 
     // Goes through all tags provided and creates row for the table
-    let rows = table_data.items.iter().map(|item| {
+    let parent_tags = summary_data.get_parent_tags(mode_selection, months.index, years.index);
+
+    // parent tags show whether they are expanded and child tags are moved under the parent
+    let get_tag_name = |tag: &str| {
+        if let Some((_, child)) = tag.split_once(':') {
+            format!("  └ {child}")
+        } else if summary_expanded_tags.contains(tag) {
+            format!("▼ {tag}")
+        } else if parent_tags.contains(tag) {
+            format!("▶ {tag}")
+        } else {
+            tag.to_string()
+        }
+    };
+
+    let rows = table_data.items.iter().enumerate().map(|(i, item)| {
         let height = 1;
-        let cells = item.iter().map(|c| Cell::from(c.separate_with_commas()));
+        let trend = tag_trends.as_ref().map(|trends| {
+            trends
+                .get(&item[0])
+                .map_or_else(String::new, |trend| get_sparkline(trend))
+        });
+
+        let cells = item
+            .iter()
+            .take(data_columns)
+            .cloned()
+            .chain(trend)
+            .chain(comparison_data.get(i).into_iter().flatten().cloned())
+            .enumerate()
+            .map(|(j, c)| {
+                // the details of a tx are shown as they are
+                if summary_largest_txs && j == 0 {
+                    Cell::from(reverse_date_format(c))
+                } else if summary_largest_txs && j != 3 {
+                    Cell::from(c.to_string())
+                } else if j == 0 {
+                    Cell::from(get_tag_name(&c))
+                } else {
+                    Cell::from(c.separate_with_commas())
+                }
+            });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().fg(TEXT))
+            .style(Style::default().fg(theme.text))
     });
 
     let summary_rows_1 = summary_table_1.items.iter().map(|item| {
@@ -185,14 +274,14 @@ pub fn summary_ui(
         let cells = item.iter().enumerate().map(|(j, c)| {
             let mut cell = Cell::from(c.separate_with_commas());
             if j == 0 {
-                cell = cell.style(Style::default().fg(TEXT).add_modifier(Modifier::BOLD));
+                cell = cell.style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD));
             }
             cell
         });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().fg(TEXT))
+            .style(Style::default().fg(theme.text))
     });
 
     let summary_rows_2 = summary_table_2.items.iter().map(|item| {
@@ -200,29 +289,34 @@ pub fn summary_ui(
         let cells = item.iter().enumerate().map(|(j, c)| {
             let mut cell = Cell::from(c.separate_with_commas());
             if j == 0 {
-                cell = cell.style(Style::default().fg(TEXT).add_modifier(Modifier::BOLD));
+                cell = cell.style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD));
             }
             cell
         });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().fg(TEXT))
+            .style(Style::default().fg(theme.text))
     });
 
     let summary_rows_3 = summary_table_3.items.iter().map(|item| {
         let height = 1;
         let cells = item.iter().enumerate().map(|(j, c)| {
-            let mut cell = Cell::from(c.separate_with_commas());
+            // the second column has the date of the largest txs
+            let mut cell = if j == 1 {
+                Cell::from(reverse_date_format(c.to_string()))
+            } else {
+                Cell::from(c.separate_with_commas())
+            };
             if j == 0 {
-                cell = cell.style(Style::default().fg(TEXT).add_modifier(Modifier::BOLD));
+                cell = cell.style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD));
             }
             cell
         });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().fg(TEXT))
+            .style(Style::default().fg(theme.text))
     });
 
     let summary_rows_4 = summary_table_4.items.iter().map(|item| {
@@ -230,14 +324,28 @@ pub fn summary_ui(
         let cells = item.iter().enumerate().map(|(j, c)| {
             let mut cell = Cell::from(c.separate_with_commas());
             if j == 0 {
-                cell = cell.style(Style::default().fg(TEXT).add_modifier(Modifier::BOLD));
+                cell = cell.style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD));
             }
             cell
         });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().fg(TEXT))
+            .style(Style::default().fg(theme.text))
+    });
+
+    let statistics_rows = statistics_data.iter().map(|item| {
+        let cells = item.iter().enumerate().map(|(j, c)| {
+            let mut cell = Cell::from(c.separate_with_commas());
+            if j == 0 {
+                cell = cell.style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD));
+            }
+            cell
+        });
+        Row::new(cells)
+            .height(1)
+            .bottom_margin(0)
+            .style(Style::default().fg(theme.text))
     });
 
     let method_rows = method_table.items.iter().map(|item| {
@@ -245,30 +353,37 @@ pub fn summary_ui(
         let cells = item.iter().enumerate().map(|(j, c)| {
             let mut cell = Cell::from(c.separate_with_commas());
             if j == 0 {
-                cell = cell.style(Style::default().fg(TEXT).add_modifier(Modifier::BOLD));
+                cell = cell.style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD));
             }
             cell
         });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
-            .style(Style::default().fg(TEXT))
+            .style(Style::default().fg(theme.text))
     });
 
+    let mut table_title = if summary_largest_txs {
+        String::from("Largest Transactions")
+    } else {
+        String::from("Tags")
+    };
+
+    if summary_include_transfers {
+        table_title.push_str(" | Transfers Included");
+    }
+
+    if loading {
+        table_title.push_str(" | Loading...");
+    }
+
     let mut table_area = Table::new(
         rows,
-        [
-            Constraint::Percentage(100/6),
-            Constraint::Percentage(100/6),
-            Constraint::Percentage(100/6),
-            Constraint::Percentage(100/6),
-            Constraint::Percentage(100/6),
-            Constraint::Percentage(100/6),
-        ],
+        vec![Constraint::Percentage(100 / total_columns); total_columns as usize],
     )
     .header(header)
-    .block(styled_block("Tags"))
-    .style(Style::default().fg(BOX));
+    .block(styled_block(&table_title, theme))
+    .style(Style::default().fg(theme.border));
 
     let summary_area_1 = Table::new(
         summary_rows_1,
@@ -278,8 +393,8 @@ pub fn summary_ui(
             Constraint::Percentage(33),
         ],
     )
-    .block(styled_block(""))
-    .style(Style::default().fg(BOX));
+    .block(styled_block("", theme))
+    .style(Style::default().fg(theme.border));
 
     let summary_area_2 = Table::new(
         summary_rows_2,
@@ -289,8 +404,8 @@ pub fn summary_ui(
             Constraint::Percentage(33),
         ],
     )
-    .block(styled_block(""))
-    .style(Style::default().fg(BOX));
+    .block(styled_block("", theme))
+    .style(Style::default().fg(theme.border));
 
     let summary_area_3 = Table::new(
         summary_rows_3,
@@ -301,8 +416,8 @@ pub fn summary_ui(
             Constraint::Percentage(25),
         ],
     )
-    .block(styled_block(""))
-    .style(Style::default().fg(BOX));
+    .block(styled_block("", theme))
+    .style(Style::default().fg(theme.border));
 
     let summary_area_4 = Table::new(
         summary_rows_4,
@@ -313,8 +428,15 @@ pub fn summary_ui(
             Constraint::Percentage(25),
         ],
     )
-    .block(styled_block(""))
-    .style(Style::default().fg(BOX));
+    .block(styled_block("", theme))
+    .style(Style::default().fg(theme.border));
+
+    let statistics_area = Table::new(
+        statistics_rows,
+        [Constraint::Percentage(55), Constraint::Percentage(45)],
+    )
+    .block(styled_block("Statistics", theme))
+    .style(Style::default().fg(theme.border));
 
     let method_area = Table::new(
         method_rows,
@@ -329,28 +451,44 @@ pub fn summary_ui(
         ],
     )
     .header(method_header)
-    .block(styled_block(""))
-    .style(Style::default().fg(BOX));
+    .block(styled_block("", theme))
+    .style(Style::default().fg(theme.border));
 
     match current_page {
         // previously added a black block to year and month widget if a value is not selected
         // Now we will turn that black block into green if a value is selected
         SummaryTab::Months => {
-            month_tab = month_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            month_tab = month_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
 
         SummaryTab::Years => {
-            year_tab = year_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            year_tab = year_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
         SummaryTab::ModeSelection => {
-            mode_selection_tab = mode_selection_tab
-                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+            mode_selection_tab = mode_selection_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
+        }
+        SummaryTab::TxMethods => {
+            tx_method_tab = tx_method_tab.highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme.selected),
+            );
         }
         SummaryTab::Table => {
             table_area = table_area
-                .highlight_style(Style::default().bg(SELECTED))
+                .highlight_style(Style::default().bg(theme.selected))
                 .highlight_symbol(">> ");
         }
     }
@@ -362,35 +500,106 @@ pub fn summary_ui(
         }
     }
 
+    // the tag table shares its space with the method breakdown of the selected tag
+    let table_chunk = if summary_hidden_mode {
+        chunks[2]
+    } else {
+        chunks[6 - mode_selection.index]
+    };
+
+    let selected_tag = table_data
+        .state
+        .selected()
+        .filter(|_| !summary_largest_txs)
+        .and_then(|index| table_data.items.get(index))
+        .map(|row| row[0].clone());
+
+    if let Some(tag) = selected_tag {
+        let table_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(table_chunk);
+
+        let breakdown_data = summary_data.get_tag_method_data(
+            mode_selection,
+            months.index,
+            years.index,
+            &tag,
+            all_methods,
+        );
+
+        let breakdown_header_cells = ["Method", "Income", "Expense", "Income %", "Expense %"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(theme.background)));
+
+        let breakdown_header = Row::new(breakdown_header_cells)
+            .style(Style::default().bg(theme.header))
+            .height(1)
+            .bottom_margin(0);
+
+        let breakdown_rows = breakdown_data.iter().map(|item| {
+            let cells = item.iter().enumerate().map(|(j, c)| {
+                let mut cell = Cell::from(c.separate_with_commas());
+                if j == 0 {
+                    cell = cell.style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD));
+                }
+                cell
+            });
+            Row::new(cells)
+                .height(1)
+                .bottom_margin(0)
+                .style(Style::default().fg(theme.text))
+        });
+
+        let breakdown_title = format!("{tag} by Method");
+        let breakdown_area = Table::new(
+            breakdown_rows,
+            [
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ],
+        )
+        .header(breakdown_header)
+        .block(styled_block(&breakdown_title, theme))
+        .style(Style::default().fg(theme.border));
+
+        f.render_widget(breakdown_area, table_layout[1]);
+        f.render_stateful_widget(table_area, table_layout[0], &mut table_data.state);
+    } else {
+        f.render_stateful_widget(table_area, table_chunk, &mut table_data.state);
+    }
+
     if summary_hidden_mode {
         f.render_stateful_widget(summary_area_1, left_summary[0], &mut summary_table_1.state);
         f.render_stateful_widget(summary_area_2, left_summary[1], &mut summary_table_2.state);
         f.render_stateful_widget(summary_area_3, right_summary[0], &mut summary_table_3.state);
         f.render_stateful_widget(summary_area_4, right_summary[1], &mut summary_table_4.state);
-        f.render_stateful_widget(table_area, chunks[2], &mut table_data.state);
+        f.render_widget(statistics_area, summary_chunk[2]);
         f.render_stateful_widget(method_area, chunks[0], &mut method_table.state);
     } else {
         f.render_widget(mode_selection_tab, chunks[0]);
+        f.render_widget(tx_method_tab, chunks[1]);
         f.render_stateful_widget(summary_area_1, left_summary[0], &mut summary_table_1.state);
         f.render_stateful_widget(summary_area_2, left_summary[1], &mut summary_table_2.state);
         f.render_stateful_widget(summary_area_3, right_summary[0], &mut summary_table_3.state);
         f.render_stateful_widget(summary_area_4, right_summary[1], &mut summary_table_4.state);
+        f.render_widget(statistics_area, summary_chunk[2]);
 
         match mode_selection.index {
             0 => {
-                f.render_widget(year_tab, chunks[1]);
-                f.render_widget(month_tab, chunks[2]);
-                f.render_stateful_widget(table_area, chunks[5], &mut table_data.state);
-                f.render_stateful_widget(method_area, chunks[3], &mut method_table.state);
+                f.render_widget(year_tab, chunks[2]);
+                f.render_widget(month_tab, chunks[3]);
+                f.render_stateful_widget(method_area, chunks[4], &mut method_table.state);
             }
             1 => {
-                f.render_widget(year_tab, chunks[1]);
-                f.render_stateful_widget(table_area, chunks[4], &mut table_data.state);
-                f.render_stateful_widget(method_area, chunks[2], &mut method_table.state);
+                f.render_widget(year_tab, chunks[2]);
+                f.render_stateful_widget(method_area, chunks[3], &mut method_table.state);
             }
             2 => {
-                f.render_stateful_widget(table_area, chunks[3], &mut table_data.state);
-                f.render_stateful_widget(method_area, chunks[1], &mut method_table.state);
+                f.render_stateful_widget(method_area, chunks[2], &mut method_table.state);
             }
             _ => {}
         }
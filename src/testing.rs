@@ -0,0 +1,226 @@
+//! Helpers for testing the interface without a terminal or a db file. The db lives in memory
+//! and the keys are sent through the same path the running app uses:
+//!
+//! ```
+//! use crossterm::event::KeyCode;
+//! use rex_tui::page_handler::{CurrentUi, HomeTab};
+//! use rex_tui::testing::{create_sample_db, TestApp};
+//!
+//! let mut app = TestApp::new(create_sample_db());
+//! app.press_keys(&[KeyCode::Enter, KeyCode::Down]);
+//!
+//! assert!(matches!(app.state.page, CurrentUi::Home));
+//! assert!(app.state.home.tab == HomeTab::Table);
+//! assert_eq!(app.state.home.table.state.selected(), Some(0));
+//! ```
+
+use chrono::{Datelike, Local, NaiveDate};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::db::create_db;
+use crate::key_checker::{get_action, InputKeyHandler, Keymap};
+use crate::outputs::{HandlingOutput, KeymapError};
+use crate::page_handler::{draw_app, AppState, Pages};
+use crate::tx_handler::add_tx;
+use crate::utility::Config;
+
+/// The tx methods of the test dbs
+pub const TEST_TX_METHODS: [&str; 2] = ["Cash", "Bank"];
+
+/// The number of txs `create_sample_db` adds
+pub const SAMPLE_TXS: usize = 5;
+
+/// Creates an in-memory db with the schema and the tx methods of `TEST_TX_METHODS`. Foreign
+/// keys are enforced like in the app
+#[must_use]
+pub fn create_memory_db() -> Connection {
+    let mut conn = Connection::open_in_memory().expect("could not open an in-memory db");
+    conn.execute("PRAGMA foreign_keys = ON", [])
+        .expect("could not enable foreign keys");
+
+    let tx_methods = TEST_TX_METHODS.map(ToString::to_string);
+
+    create_db(&tx_methods, &mut conn).expect("could not create the db");
+    conn
+}
+
+/// Creates an in-memory db with `SAMPLE_TXS` txs in the current month
+#[must_use]
+pub fn create_sample_db() -> Connection {
+    let mut conn = create_memory_db();
+    add_sample_txs(SAMPLE_TXS, &mut conn);
+    conn
+}
+
+/// Adds the given number of txs to the first 5 days of the current month so the Home page
+/// shows them right away. Every 5th tx is an income to Bank, the rest are expenses from Cash
+pub fn add_sample_txs(total: usize, conn: &mut Connection) {
+    let today = Local::now().date_naive();
+
+    for index in 0..total {
+        let date = today
+            .with_day(index as u32 % 5 + 1)
+            .expect("the first 5 days exist in every month");
+
+        let (tx_method, amount, tx_type, tags) = if index % 5 == 0 {
+            ("Bank", "1000.00", "Income", "Salary")
+        } else {
+            ("Cash", "10.00", "Expense", "Food")
+        };
+
+        add_tx(
+            &date.to_string(),
+            &format!("Sample tx {}", index + 1),
+            tx_method,
+            amount,
+            tx_type,
+            tags,
+            None,
+            conn,
+        )
+        .expect("could not add the sample tx");
+    }
+}
+
+/// The app state of a db that can be driven with key presses like the running app
+pub struct TestApp {
+    pub state: AppState,
+    pub pages: Pages,
+    pub keymap: Keymap,
+    pub config: Config,
+    pub read_only: bool,
+    pub conn: Connection,
+    profiles: Vec<String>,
+}
+
+impl TestApp {
+    /// Starts on the Initial page with the default config and the built-in pages. Foreign keys
+    /// are enforced like `start_app` does
+    #[must_use]
+    pub fn new(conn: Connection) -> Self {
+        conn.execute("PRAGMA foreign_keys = ON", [])
+            .expect("could not enable foreign keys");
+
+        let config = Config::default();
+        let state = AppState::new(&None, &config, &conn);
+
+        TestApp {
+            state,
+            pages: Pages::with_extensions(Vec::new()),
+            keymap: Keymap::default(),
+            config,
+            read_only: false,
+            conn,
+            profiles: vec!["Default".to_string()],
+        }
+    }
+
+    /// Moves the actions to the keys like the `key_bindings` of the config. Must be called before
+    /// any key is pressed. Returns the bindings that could not be used
+    pub fn set_key_bindings(&mut self, bindings: &[(&str, &str)]) -> Vec<KeymapError> {
+        let bindings = bindings
+            .iter()
+            .map(|(action, key)| (action.to_string(), key.to_string()))
+            .collect::<HashMap<String, String>>();
+
+        let (keymap, errors) = Keymap::new(&bindings, &self.state, &self.pages);
+        self.keymap = keymap;
+        errors
+    }
+
+    /// Sends the key press the same way the running app does. Returns the output that would be
+    /// handled outside the interface like quitting
+    pub fn press(&mut self, key: KeyEvent) -> Option<HandlingOutput> {
+        let key = self.keymap.translate(key, &self.state, &self.pages)?;
+        let action = get_action(key, &self.state, &self.pages)?;
+
+        let output = InputKeyHandler::new(
+            key,
+            &mut self.state,
+            &self.config,
+            "Default",
+            &self.profiles,
+            Path::new(":memory:"),
+            self.read_only,
+            None,
+            &mut self.pages,
+            &mut self.conn,
+        )
+        .reduce(action);
+        self.state.show_queued_popup();
+
+        if let Some(HandlingOutput::TxMethodsChanged) = output {
+            self.state.reload_tx_methods(&self.config, &self.conn);
+            return None;
+        }
+        output
+    }
+
+    /// Moves the app to a new day the same way the running app notices the date change
+    pub fn change_date(&mut self, today: NaiveDate) {
+        InputKeyHandler::new(
+            KeyEvent::from(KeyCode::Null),
+            &mut self.state,
+            &self.config,
+            "Default",
+            &self.profiles,
+            Path::new(":memory:"),
+            self.read_only,
+            None,
+            &mut self.pages,
+            &mut self.conn,
+        )
+        .post_due_scheduled_txs(today);
+        self.state.show_queued_popup();
+    }
+
+    /// Presses the keys one after another without any modifier. Stops at the first key that
+    /// returns an output and returns it
+    pub fn press_keys(&mut self, keys: &[KeyCode]) -> Option<HandlingOutput> {
+        for key in keys {
+            if let Some(output) = self.press(KeyEvent::new(*key, KeyModifiers::NONE)) {
+                return Some(output);
+            }
+        }
+        None
+    }
+
+    /// Presses the key of every character of the text
+    pub fn type_text(&mut self, text: &str) -> Option<HandlingOutput> {
+        let keys = text.chars().map(KeyCode::Char).collect::<Vec<KeyCode>>();
+        self.press_keys(&keys)
+    }
+
+    /// Draws the current page and popup on a screen of the given size and returns its lines
+    pub fn draw(&mut self, width: u16, height: u16) -> Vec<String> {
+        let mut terminal =
+            Terminal::new(TestBackend::new(width, height)).expect("could not create the terminal");
+
+        terminal
+            .draw(|f| {
+                draw_app(
+                    f,
+                    &mut self.state,
+                    &mut self.pages,
+                    true,
+                    &self.config,
+                    "Default",
+                    self.read_only,
+                    &self.conn,
+                );
+            })
+            .expect("could not draw the app");
+
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content
+            .chunks(usize::from(buffer.area.width))
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect()
+    }
+}
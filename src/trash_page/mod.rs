@@ -0,0 +1,5 @@
+mod page;
+mod trash_ui;
+
+pub use page::TrashPage;
+pub use trash_ui::trash_ui;
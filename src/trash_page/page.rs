@@ -0,0 +1,22 @@
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+
+use crate::key_checker::{trash_keys, Action};
+use crate::page_handler::{AppState, Page, PageContext};
+use crate::trash_page::trash_ui;
+
+pub struct TrashPage;
+
+impl Page for TrashPage {
+    fn name(&self) -> &str {
+        "Trash"
+    }
+
+    fn draw(&mut self, f: &mut Frame, state: &mut AppState, _context: &PageContext) {
+        trash_ui(f, &mut state.trash.table, &state.theme);
+    }
+
+    fn get_action(&self, key: KeyEvent, _state: &AppState) -> Option<Action> {
+        trash_keys(key)
+    }
+}
@@ -0,0 +1,81 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Row, Table};
+use ratatui::Frame;
+use thousands::Separable;
+
+use crate::page_handler::{TableData, Theme};
+use crate::utility::{main_block, reverse_date_format, styled_block};
+
+/// The interface of the Trash page. Lists the deleted txs that can be restored
+pub fn trash_ui(f: &mut Frame, table_data: &mut TableData, theme: &Theme) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Min(0)])
+        .split(size);
+
+    f.render_widget(main_block(theme), size);
+
+    let table_name = if table_data.items.is_empty() {
+        "Trash".to_string()
+    } else {
+        format!("Trash: {}", table_data.items.len())
+    };
+
+    let header_cells = [
+        "Deleted At",
+        "Date",
+        "Details",
+        "TX Method",
+        "Amount",
+        "Type",
+        "Tags",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(theme.background)));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(theme.header))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table_data.items.iter().map(|item| {
+        // the last item is the id of the trashed tx which is not shown
+        let cells = item.iter().take(7).enumerate().map(|(index, c)| {
+            // only the amount gets the commas. The dates would get broken by them
+            if index == 1 {
+                Cell::from(reverse_date_format(c.to_string()))
+            } else if index == 4 {
+                Cell::from(c.separate_with_commas())
+            } else {
+                Cell::from(c.to_string())
+            }
+        });
+        Row::new(cells)
+            .height(1)
+            .bottom_margin(0)
+            .style(Style::default().bg(theme.background).fg(theme.text))
+    });
+
+    let table_area = Table::new(
+        rows,
+        [
+            Constraint::Percentage(15),
+            Constraint::Percentage(10),
+            Constraint::Percentage(30),
+            Constraint::Percentage(15),
+            Constraint::Percentage(10),
+            Constraint::Percentage(8),
+            Constraint::Percentage(12),
+        ],
+    )
+    .header(header)
+    .block(styled_block(&table_name, theme))
+    .highlight_symbol(">> ")
+    .highlight_style(Style::default().bg(theme.selected));
+
+    f.render_stateful_widget(table_area, chunks[0], &mut table_data.state);
+}
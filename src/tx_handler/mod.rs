@@ -1,7 +0,0 @@
-mod add_tx;
-mod delete_tx;
-mod tx_data;
-
-pub use add_tx::add_tx;
-pub use delete_tx::delete_tx;
-pub use tx_data::*;
@@ -0,0 +1,96 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::level_filters::LevelFilter;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+
+/// The log files are named like `rex.2024-05-12.log`
+const LOG_PREFIX: &str = "rex";
+const LOG_SUFFIX: &str = "log";
+
+/// A new file is started every day and only the newest files are kept
+const MAX_LOG_FILES: usize = 7;
+
+/// The folder the logs are written to. Only set once logging has started
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Returns the logs folder next to the db file
+#[must_use]
+pub fn get_log_dir(original_db_path: &Path) -> PathBuf {
+    let mut log_dir = original_db_path.to_owned();
+    log_dir.pop();
+    log_dir.push("logs");
+    log_dir
+}
+
+/// Starts writing the logs to a daily rotating file inside the folder. Info and above are
+/// written normally, `verbose` adds the debug lines like the query timings. Every line is
+/// written right away so nothing is lost when Rex exits early
+pub fn start_logging(log_dir: &Path, verbose: bool) -> io::Result<()> {
+    fs::create_dir_all(log_dir)?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix(LOG_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    let level = if verbose {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::INFO
+    };
+
+    tracing_subscriber::fmt()
+        .with_writer(appender)
+        .with_ansi(false)
+        .with_max_level(level)
+        .try_init()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    LOG_DIR.get_or_init(|| log_dir.to_owned());
+    Ok(())
+}
+
+/// Returns the newest log file inside the folder
+#[must_use]
+pub fn get_latest_log_file(log_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(log_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(LOG_PREFIX) && name.ends_with(&format!(".{LOG_SUFFIX}"))
+                })
+        })
+        // the date in the name sorts the same way as the time
+        .max()
+}
+
+/// Returns the last lines of the newest log file. The newest line comes first
+pub fn get_last_log_lines(log_dir: &Path, total: usize) -> io::Result<Vec<String>> {
+    let Some(log_file) = get_latest_log_file(log_dir) else {
+        return Ok(Vec::new());
+    };
+
+    let content = fs::read_to_string(log_file)?;
+
+    Ok(content
+        .lines()
+        .rev()
+        .take(total)
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Returns the folder the logs are being written to. None if logging could not be started
+#[must_use]
+pub fn get_active_log_dir() -> Option<&'static Path> {
+    LOG_DIR.get().map(PathBuf::as_path)
+}
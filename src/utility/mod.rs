@@ -1,6 +1,8 @@
+mod logging;
 mod sub_func;
-pub mod traits;
 mod utils;
 
+pub use logging::*;
+pub use rex_core::utility::*;
 pub use sub_func::*;
 pub use utils::*;
@@ -1,268 +1,35 @@
 use rusqlite::Connection;
 use std::collections::{HashMap, HashSet};
+use std::error::Error;
 use std::fs;
 use std::io::stdout;
 use std::path::PathBuf;
 use std::process::Command;
 
-use crate::activity_page::{ActivityDetails, ActivityTx};
-use crate::outputs::{ComparisonType, TerminalExecutionError};
-use crate::page_handler::{ActivityType, DateType, ResetType, UserInputType};
-use crate::tx_handler::{delete_tx, TxData};
+use crate::db::unlock_db;
+use crate::outputs::TerminalExecutionError;
+use crate::page_handler::{ResetType, UserInputType};
+use crate::sync_handler::SyncSettings;
 use crate::utility::{
-    add_new_activity, add_new_activity_tx, check_comparison, check_restricted, clear_terminal,
-    flush_output, get_all_tx_methods, get_sql_dates, reverse_date_format, take_input,
+    check_restricted, clear_terminal, flush_output, get_all_tx_methods, take_hidden_input,
+    take_input,
 };
 
-/// Returns the balance of all methods based on year and month point.
-/// if the balance is empty/0 at the given point for any one of the methods
-/// it will try to find the balance for that method from one of the earlier points.
-pub fn get_last_time_balance(
-    month: usize,
-    year: usize,
-    tx_method: &Vec<String>,
-    conn: &Connection,
-) -> HashMap<String, f64> {
-    // We can get the id_num of the month which is saved in the database based on the
-    // month and year index there is passed.
-    let target_id_num = month as i32 + (year as i32 * 12);
-
-    let mut final_value = HashMap::new();
-    for i in tx_method {
-        final_value.insert(i.to_string(), 0.0);
-    }
-
-    // balance_all starts at point 1. 1 means month 1, year 2022/0.
-    // There is no earlier balance than this
-    if target_id_num == 0 {
-        return final_value;
-    }
-
-    let mut checked_methods: Vec<&str> = Vec::new();
-
-    let tx_method_string = tx_method
-        .iter()
-        .map(|m| format!("\"{m}\""))
-        .collect::<Vec<_>>()
-        .join(", ");
-
-    // the process goes like this
-    // m1  m2  m3  id
-    //  0   0  10   1
-    // 10  10   0   2
-    // 10   0   0   3
-    // fetch all three rows, start checking from id 3
-    // m1 is already found, save that, go to the previous row, save m2,
-    // go to the previous row, save m3 -> break -> return the data
-
-    let query = format!(
-        "SELECT {tx_method_string} FROM balance_all WHERE id_num <= ? ORDER BY id_num DESC",
-    );
-
-    let mut stmt = conn.prepare(&query).unwrap();
-    let mut rows = stmt.query([target_id_num]).unwrap();
-
-    while let Some(row) = rows.next().unwrap() {
-        // all methods checked = no longer necessary to check any more rows
-        if checked_methods.len() == tx_method.len() {
-            break;
-        }
-        // check each tx_method column in the current row
-        for (i, item) in tx_method.iter().enumerate() {
-            if !checked_methods.contains(&item.as_str()) {
-                let balance: f64 = row.get(i).unwrap();
-
-                // we only need non-zero balance
-                if balance != 0.0 {
-                    *final_value.get_mut(item).unwrap() = balance;
-                    checked_methods.push(item);
-                }
-            }
-        }
-    }
-
-    final_value
-}
-
-/// The functions sends all the changes that happened after transactions on the month and year provided
-pub fn get_all_changes(month: usize, year: usize, conn: &Connection) -> Vec<Vec<String>> {
-    let mut final_result = Vec::new();
-    let tx_methods = get_all_tx_methods(conn);
-
-    let (datetime_1, datetime_2) = get_sql_dates(month, year, &DateType::Monthly);
-
-    let mut statement = conn
-        .prepare("SELECT * FROM changes_all Where date BETWEEN date(?) AND date(?) ORDER BY date, id_num")
-        .expect("could not prepare statement");
-
-    let rows = statement
-        .query_map([datetime_1, datetime_2], |row| {
-            let mut balance_vec: Vec<String> = Vec::new();
-            // Why start at 2? Because the first two rows are date and id_num
-            for i in 2..tx_methods.len() + 2 {
-                balance_vec.push(row.get(i).unwrap());
-            }
-            Ok(balance_vec)
-        })
-        .unwrap();
-
-    for i in rows {
-        final_result.push(i.unwrap());
-    }
-    final_result
-}
-
-/// Used to retrieving all Transaction within a given date, balance and the `id_num` related to them.
-pub fn get_all_txs(
-    conn: &Connection,
-    month: usize,
-    year: usize,
-) -> (Vec<Vec<String>>, Vec<Vec<String>>, Vec<String>) {
-    // returns all transactions recorded within a given date + balance changes + the relevant id_num
-
-    let all_tx_methods = get_all_tx_methods(conn);
-
-    let mut final_all_txs: Vec<Vec<String>> = Vec::new();
-    let mut final_all_balances: Vec<Vec<String>> = Vec::new();
-    let mut all_id_num = Vec::new();
-
-    // we will go through the last month balances and add/subtract
-    // current month's transactions to the related tx method. After each tx calculation, add whatever
-    // balance for each tx method inside a vec to finally return them
-
-    let mut last_month_balance = get_last_time_balance(month, year, &all_tx_methods, conn);
-
-    let (datetime_1, datetime_2) = get_sql_dates(month, year, &DateType::Monthly);
-
-    // preparing the query for db, getting current month's all transactions
-    let mut statement = conn
-        .prepare(
-            "SELECT * FROM tx_all WHERE date BETWEEN date(?) AND date(?) ORDER BY date, id_num",
-        )
-        .expect("could not prepare statement");
-
-    let rows = statement
-        .query_map([&datetime_1, &datetime_2], |row| {
-            // collect the row data and put them in a vec
-            let date = reverse_date_format(row.get(0).unwrap());
-            let id_num: i32 = row.get(5).unwrap();
-
-            Ok(vec![
-                date,
-                row.get(1).unwrap(),
-                row.get(2).unwrap(),
-                row.get(3).unwrap(),
-                row.get(4).unwrap(),
-                row.get(6).unwrap(),
-                id_num.to_string(),
-            ])
-        })
-        .unwrap();
-
-    for i in rows.flatten() {
-        // data contains all tx data of a transaction
-        let mut data = i;
-        let id_num = &data.pop().unwrap();
-        all_id_num.push(id_num.to_string());
-        final_all_txs.push(data);
-    }
-
-    for i in &final_all_txs {
-        // this is where the calculation for the balance happens. We will loop through each tx,
-        // look at the tx type, tx method and add/subtract the amount on last month balance which was fetched earlier
-
-        // collect data inside variables
-        let tx_type = &i[4];
-        let amount = &i[3].to_string().parse::<f64>().unwrap();
-        let tx_method = &i[2];
-
-        // If the transaction is not a transfer, default balance goes to new_balance_from
-        // and new_balance_to remains empty. On transfer TX both of them are used
-
-        let mut new_balance_from: f64 = 0.0;
-        let mut new_balance_to: f64 = 0.0;
-
-        let mut from_method = String::new();
-        let mut to_method = String::new();
-
-        // add or subtract the amount based on the tx type
-        if tx_type == "Expense" {
-            new_balance_from = last_month_balance[tx_method] - amount;
-        } else if tx_type == "Income" {
-            new_balance_from = last_month_balance[tx_method] + amount;
-        } else if tx_type == "Transfer" {
-            let splitted = tx_method.split(" to ").collect::<Vec<&str>>();
-            from_method = splitted[0].to_string();
-            to_method = splitted[1].to_string();
-            new_balance_from = last_month_balance[&from_method] - amount;
-            new_balance_to = last_month_balance[&to_method] + amount;
-        }
-
-        // make changes to the balance map based on the tx
-        // for transfer TX first block executes
-        // new_balance_to != 0 means it's a transfer transaction
-        if new_balance_to == 0.0 {
-            *last_month_balance.get_mut(tx_method).unwrap() = new_balance_from;
-        } else {
-            *last_month_balance.get_mut(&from_method).unwrap() = new_balance_from;
-            *last_month_balance.get_mut(&to_method).unwrap() = new_balance_to;
-        }
-
-        // push all the changes gathered to the return variable
-        let mut to_push = vec![];
-        for i in &all_tx_methods {
-            to_push.push(format!("{:.2}", last_month_balance[i]));
-        }
-
-        final_all_balances.push(to_push);
-    }
-
-    // pushes the final balance that was calculated just now to the db on the balance_all table
-    if !final_all_balances.is_empty() {
-        let target_id_num = month as i32 + 1 + (year as i32 * 12);
-        let final_index = final_all_balances.len() - 1;
-
-        let balance_query = format!(
-            "UPDATE balance_all SET {} WHERE id_num = {}",
-            final_all_balances[final_index]
-                .iter()
-                .enumerate()
-                .map(|(i, balance)| format!(r#""{}" = "{}""#, all_tx_methods[i], balance))
-                .collect::<Vec<String>>()
-                .join(", "),
-            target_id_num
-        );
-        conn.execute(&balance_query, [])
-            .expect("Error updating balance query");
-    }
-
-    (final_all_txs, final_all_balances, all_id_num)
-}
-
-/// Returns the absolute final balance or the last row on `balance_all` table.
-pub fn get_last_balances(conn: &Connection) -> Vec<String> {
-    let tx_method = get_all_tx_methods(conn);
-    let mut query = format!("SELECT {tx_method:?} FROM balance_all ORDER BY id_num DESC LIMIT 1");
-    query = query.replace('[', "");
-    query = query.replace(']', "");
-
-    let final_balance = conn.query_row(&query, [], |row| {
-        let mut final_data: Vec<String> = Vec::new();
-        for i in 0..tx_method.len() {
-            let row_data: f64 = row.get(i).unwrap();
-            final_data.push(row_data.to_string());
-        }
-        Ok(final_data)
-    });
-    final_balance.unwrap()
-}
-
 /// Prompts the user to select and option and start taking relevant inputs
 #[cfg(not(tarpaulin_include))]
 pub fn start_taking_input(conn: &Connection) -> UserInputType {
     let mut stdout = stdout();
     clear_terminal(&mut stdout);
 
+    // the options are only available when built with SQLCipher. Synced dbs are encrypted with it
+    let encryption_option = if cfg!(feature = "encryption") {
+        "6. Encrypt the database or change its passphrase
+7. Set up WebDAV sync
+8. Sync with the WebDAV server\n"
+    } else {
+        ""
+    };
+
     loop {
         println!(
             "Enter an option number to proceed. Input 'Cancel' to cancel the operation
@@ -271,7 +38,8 @@ pub fn start_taking_input(conn: &Connection) -> UserInputType {
 2. Rename Transaction Method
 3. Reposition Transactions Methods
 4. Set a new location for app data
-5. Set backup DB paths\n"
+5. Set backup DB paths
+{encryption_option}"
         );
         print!("Proceed with option number: ");
         flush_output(&stdout);
@@ -285,6 +53,9 @@ pub fn start_taking_input(conn: &Connection) -> UserInputType {
             UserInputType::RepositionTxMethod(_) => return get_reposition_data(conn),
             UserInputType::SetNewLocation(_) => return get_new_location(),
             UserInputType::BackupDBPath(_) => return get_backup_db_paths(),
+            UserInputType::EncryptDB(_) => return get_new_passphrase(),
+            UserInputType::SetupSync(_) => return get_sync_settings_input(),
+            UserInputType::SyncDB(_) => return get_sync_passphrase(),
             UserInputType::CancelledOperation | UserInputType::ResetData(_) => return input_type,
             UserInputType::InvalidInput => clear_terminal(&mut stdout),
         }
@@ -350,6 +121,12 @@ Example input: Bank, Cash, PayPal.\n\nEnter Transaction Methods: "
             continue;
         }
 
+        if line.contains('"') {
+            clear_terminal(&mut stdout);
+            println!("'\"' cannot be used in Transaction Methods.\n");
+            continue;
+        }
+
         // split them and remove duplicates
         let mut inputted_methods: Vec<&str> = line
             .split(',')
@@ -480,6 +257,12 @@ Currently added Transaction Methods: \n"
             continue;
         }
 
+        if new_method_name.contains('"') {
+            clear_terminal(&mut stdout);
+            println!("'\"' cannot be used in Transaction Methods.\n");
+            continue;
+        }
+
         // Start from the beginning if the given tx method already exists
         if check_restricted(&new_method_name, Some(&tx_methods)) {
             clear_terminal(&mut stdout);
@@ -666,6 +449,153 @@ Windows: C:\\data\\save\\";
     }
 }
 
+#[cfg(not(tarpaulin_include))]
+fn get_new_passphrase() -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    loop {
+        println!("The database will be encrypted with the passphrase. It cannot be opened without it and a lost passphrase cannot be recovered.
+
+If the database is already encrypted, the passphrase gets changed.\n");
+        print!("Enter the new passphrase: ");
+        flush_output(&stdout);
+
+        let passphrase = take_hidden_input();
+
+        if passphrase.to_lowercase() == "cancel" {
+            return UserInputType::CancelledOperation;
+        }
+
+        if passphrase.is_empty() {
+            clear_terminal(&mut stdout);
+            println!("The passphrase cannot be empty.\n");
+            continue;
+        }
+
+        print!("Enter the passphrase again: ");
+        flush_output(&stdout);
+
+        if take_hidden_input() == passphrase {
+            return UserInputType::EncryptDB(passphrase);
+        }
+
+        clear_terminal(&mut stdout);
+        println!("The passphrases did not match.\n");
+    }
+}
+
+/// Asks for the WebDAV folder the db gets synced with and the login of the server
+#[cfg(not(tarpaulin_include))]
+fn get_sync_settings_input() -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    loop {
+        println!("Enter the url of the WebDAV folder the database will be synced with. Every device syncing with the same folder shares the same data.
+
+If previously saved sync settings exist, they will be overwritten.
+
+Empty input will be considered as turn off sync.
+
+Example url:
+
+Nextcloud: https://cloud.example.com/remote.php/dav/files/username/Rex");
+        print!("\nEnter the url: ");
+        flush_output(&stdout);
+
+        let url = take_input().trim().to_string();
+
+        if url.is_empty() {
+            println!("Clearing the sync settings");
+            return UserInputType::ResetData(ResetType::Sync);
+        }
+
+        if url.to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            clear_terminal(&mut stdout);
+            println!("The url {url} must start with https:// or http://.\n");
+            continue;
+        }
+
+        print!("Enter the username. Leave it empty if the server needs no login: ");
+        flush_output(&stdout);
+        let username = take_input().trim().to_string();
+
+        let password = if username.is_empty() {
+            String::new()
+        } else {
+            print!("Enter the password. An app password is recommended: ");
+            flush_output(&stdout);
+            take_hidden_input()
+        };
+
+        return UserInputType::SetupSync(SyncSettings {
+            url,
+            username,
+            password,
+        });
+    }
+}
+
+/// Asks for the passphrase the synced db is encrypted with. Every device must use the same one
+#[cfg(not(tarpaulin_include))]
+fn get_sync_passphrase() -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    loop {
+        println!("The database is encrypted with the sync passphrase before it is uploaded. Every device syncing with the same folder must use the same passphrase.\n");
+        print!("Enter the sync passphrase: ");
+        flush_output(&stdout);
+
+        let passphrase = take_hidden_input();
+
+        if passphrase.to_lowercase() == "cancel" {
+            return UserInputType::CancelledOperation;
+        }
+
+        if passphrase.is_empty() {
+            clear_terminal(&mut stdout);
+            println!("The passphrase cannot be empty.\n");
+            continue;
+        }
+
+        return UserInputType::SyncDB(passphrase);
+    }
+}
+
+/// Asks for the passphrase of an encrypted db until it unlocks the db. Gives up after
+/// 3 wrong passphrases
+#[cfg(not(tarpaulin_include))]
+pub fn ask_db_passphrase(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    if !cfg!(feature = "encryption") {
+        return Err(
+            "The database is encrypted. Build Rex with the encryption feature to open it.".into(),
+        );
+    }
+
+    let stdout = stdout();
+
+    for _ in 0..3 {
+        print!("Enter the passphrase of the database: ");
+        flush_output(&stdout);
+
+        if unlock_db(&take_hidden_input(), conn).is_ok() {
+            return Ok(());
+        }
+        println!("Wrong passphrase.");
+    }
+
+    Err("Failed to unlock the database.".into())
+}
+
 #[cfg(not(tarpaulin_include))]
 fn get_backup_db_paths() -> UserInputType {
     let mut stdout = stdout();
@@ -802,316 +732,3 @@ pub fn start_terminal(original_dir: &str) -> Result<(), TerminalExecutionError>
     };
     Ok(())
 }
-
-/// Creates the query to search for specific tx, gathers all rows and id numbers
-pub fn get_search_data(
-    date: &str,
-    details: &str,
-    from_method: &str,
-    to_method: &str,
-    amount: &str,
-    tx_type: &str,
-    tags: &str,
-    date_type: &DateType,
-    conn: &Connection,
-) -> (Vec<Vec<String>>, Vec<String>) {
-    let mut all_txs = Vec::new();
-    let mut all_ids = Vec::new();
-
-    // This will be used for the activity tx
-    let tx_method = if tx_type == "Transfer" && !from_method.is_empty() && !to_method.is_empty() {
-        format!("{from_method} to {to_method}").trim().to_string()
-    } else if tx_type == "Transfer" && !from_method.is_empty() && to_method.is_empty() {
-        format!("{from_method} to ?").trim().to_string()
-    } else if tx_type == "Transfer" && from_method.is_empty() && !to_method.is_empty() {
-        format!("? to {to_method}").trim().to_string()
-    } else if from_method.is_empty() && to_method.is_empty() {
-        String::new()
-    } else {
-        from_method.to_string()
-    };
-
-    let mut valid_fields = 0;
-
-    let mut query = "SELECT * FROM tx_all WHERE 1=1".to_string();
-
-    if !date.is_empty() {
-        valid_fields += 1;
-        match date_type {
-            DateType::Exact => query.push_str(&format!(r#" AND date = "{date}""#)),
-            DateType::Monthly => {
-                let splitted_date: Vec<usize> =
-                    date.split('-').map(|s| s.parse().unwrap()).collect();
-
-                let month_index = splitted_date[1] - 1;
-                let year_index = splitted_date[0] - 2022;
-
-                let (date_1, date_2) = get_sql_dates(month_index, year_index, date_type);
-
-                query.push_str(&format!(
-                    r#" AND date BETWEEN date("{date_1}") AND date("{date_2}")"#,
-                ));
-            }
-            DateType::Yearly => {
-                let year_index = date.parse::<usize>().unwrap() - 2022;
-
-                let (date_1, date_2) = get_sql_dates(0, year_index, date_type);
-
-                query.push_str(&format!(
-                    r#" AND date BETWEEN date("{date_1}") AND date("{date_2}")"#
-                ));
-            }
-        }
-    }
-
-    if !details.is_empty() {
-        valid_fields += 1;
-        query.push_str(&format!(r#" AND details LIKE "%{details}%""#,));
-    }
-
-    if !tx_type.is_empty() {
-        valid_fields += 1;
-        query.push_str(&format!(r#" AND tx_type = "{tx_type}""#,));
-    }
-
-    if !amount.is_empty() {
-        valid_fields += 1;
-        let comparison_type = check_comparison(amount);
-
-        let comparison_symbol = match comparison_type {
-            ComparisonType::BiggerThan => ">",
-            ComparisonType::SmallerThan => "<",
-            ComparisonType::Equal => "",
-            ComparisonType::EqualOrBigger => ">=",
-            ComparisonType::EqualOrSmaller => "<=",
-        };
-        let amount = amount.replace(comparison_symbol, "");
-
-        query.push_str(&format!(r#" AND {comparison_type} "{amount}""#));
-    }
-
-    if tx_type == "Transfer" {
-        // If neither are empty, look for exact matches
-        // Otherwise do partial matching
-        if !from_method.is_empty() && !to_method.is_empty() {
-            valid_fields += 1;
-            query.push_str(&format!(
-                r#" AND tx_method = "{from_method} to {to_method}""#
-            ));
-        } else if !from_method.is_empty() {
-            valid_fields += 1;
-            query.push_str(&format!(r#" AND tx_method LIKE "{from_method} to %" "#));
-        } else if !to_method.is_empty() {
-            valid_fields += 1;
-            query.push_str(&format!(r#" AND tx_method LIKE "% to {to_method}""#));
-        }
-    } else if tx_type != "Transfer" && !from_method.is_empty() {
-        valid_fields += 1;
-        query.push_str(&format!(r#" AND tx_method = "{from_method}""#));
-    }
-
-    if !tags.is_empty() {
-        valid_fields += 1;
-        let all_tags = tags.split(", ");
-        let tag_conditions = all_tags
-            .map(|tag| {
-                format!(
-                    r#"CASE 
-                          WHEN tags LIKE "{tag}, %" THEN 1
-                          WHEN tags LIKE "%, {tag}" THEN 1
-                          WHEN tags LIKE "%, {tag}," THEN 1
-                          WHEN tags = "{tag}" THEN 1
-                          ELSE 0
-                      END = 1"#,
-                )
-            })
-            .collect::<Vec<String>>()
-            .join(" OR ");
-        query.push_str(&format!(" AND ({tag_conditions})"));
-    }
-
-    let mut statement = conn.prepare(&query).unwrap();
-
-    let rows = statement
-        .query_map([], |row| {
-            let date = reverse_date_format(row.get(0).unwrap());
-            let id_num: i32 = row.get(5).unwrap();
-
-            Ok(vec![
-                date,
-                row.get(1).unwrap(),
-                row.get(2).unwrap(),
-                row.get(3).unwrap(),
-                row.get(4).unwrap(),
-                row.get(6).unwrap(),
-                id_num.to_string(),
-            ])
-        })
-        .unwrap();
-
-    for i in rows.flatten() {
-        let mut data = i;
-        let id_num = &data.pop().unwrap();
-        all_ids.push(id_num.to_string());
-        all_txs.push(data);
-    }
-
-    let activity_type = ActivityType::SearchTX(Some(valid_fields));
-    let search_data = vec![date, details, &tx_method, amount, tx_type, tags, ""];
-
-    let activity_num = add_new_activity(activity_type, conn);
-    add_new_activity_tx(&search_data, activity_num, conn);
-
-    (all_txs, all_ids)
-}
-
-/// Switches `id_num` of 2 txs in the DB to switch the indexes of the value in the UI table
-pub fn switch_tx_index(
-    id_1: i32,
-    id_2: i32,
-    tx_1: &[String],
-    tx_2: &[String],
-    conn: &mut Connection,
-) {
-    let tx_type_1 = &tx_1[4];
-    let tx_type_2 = &tx_2[4];
-
-    let tx_data_1 = if tx_type_1 == "Transfer" {
-        let splitted_method = tx_1[2].split(" to ").collect::<Vec<&str>>();
-        let from_method = splitted_method[0];
-        let to_method = splitted_method[1];
-
-        TxData::custom(
-            &tx_1[0],
-            &tx_1[1],
-            from_method,
-            to_method,
-            &tx_1[3],
-            "Transfer",
-            &tx_1[5],
-            id_1,
-        )
-    } else {
-        TxData::custom(
-            &tx_1[0], &tx_1[1], &tx_1[2], "", &tx_1[3], &tx_1[4], &tx_1[5], id_1,
-        )
-    };
-
-    let tx_data_2 = if tx_type_2 == "Transfer" {
-        let splitted_method = tx_2[2].split(" to ").collect::<Vec<&str>>();
-        let from_method = splitted_method[0];
-        let to_method = splitted_method[1];
-
-        TxData::custom(
-            &tx_2[0],
-            &tx_2[1],
-            from_method,
-            to_method,
-            &tx_2[3],
-            "Transfer",
-            &tx_2[5],
-            id_2,
-        )
-    } else {
-        TxData::custom(
-            &tx_2[0], &tx_2[1], &tx_2[2], "", &tx_2[3], &tx_2[4], &tx_2[5], id_1,
-        )
-    };
-
-    delete_tx(id_1, conn).unwrap();
-    delete_tx(id_2, conn).unwrap();
-
-    let activity_num = add_new_activity(ActivityType::IDNumSwap(Some(id_1), Some(id_2)), conn);
-
-    tx_data_1.switch_tx_id(id_2, activity_num, conn);
-    tx_data_2.switch_tx_id(id_1, activity_num, conn);
-}
-
-/// Returns all activities recorded within a given month and a year and all the activity txs related to the activities
-pub fn get_all_activities(
-    month: usize,
-    year: usize,
-    conn: &Connection,
-) -> (Vec<ActivityDetails>, HashMap<i32, Vec<ActivityTx>>) {
-    let (datetime_1, datetime_2) = get_sql_dates(month, year, &DateType::Monthly);
-
-    let mut statement = conn
-        .prepare("SELECT * from activities WHERE date BETWEEN date(?) AND date(?)")
-        .unwrap();
-
-    // Activity tx fetching happens based on the minimum activity num and the maximum activity num
-    let mut min_activity_num = i32::MAX;
-    let mut max_activity_num = i32::MIN;
-
-    let rows: Vec<ActivityDetails> = statement
-        .query_map([datetime_1, datetime_2], |row| {
-            let date = reverse_date_format(row.get(0).unwrap());
-            Ok(ActivityDetails::new(
-                date,
-                row.get(1).unwrap(),
-                row.get(2).unwrap(),
-                row.get(3).unwrap(),
-            ))
-        })
-        .unwrap()
-        .map(|wrapped_data| {
-            let data = wrapped_data.unwrap();
-            let id = data.activity_num();
-
-            if id > max_activity_num {
-                max_activity_num = id;
-            }
-
-            if id < min_activity_num {
-                min_activity_num = id;
-            }
-            data
-        })
-        .collect();
-
-    let activity_txs = get_all_activity_txs(min_activity_num, max_activity_num, conn);
-
-    (rows, activity_txs)
-}
-
-/// Returns all activity txs within the given activity number range
-pub fn get_all_activity_txs(
-    min_num: i32,
-    max_num: i32,
-    conn: &Connection,
-) -> HashMap<i32, Vec<ActivityTx>> {
-    let mut statement = conn
-        .prepare("SELECT * from activity_txs WHERE activity_num >= ? AND activity_num <= ?")
-        .unwrap();
-
-    // Contains data in the format {activity_num: Vec<ActivityTx>}
-    // In case of search or edit txs, a single activity can impact multiple txs
-    // The rest will always have only 1 tx
-    let mut activity_tx_data = HashMap::new();
-
-    for wrapped_data in statement
-        .query_map([min_num, max_num], |row| {
-            let date = reverse_date_format(row.get(0).unwrap());
-            Ok(ActivityTx::new(
-                date,
-                row.get(1).unwrap(),
-                row.get(2).unwrap(),
-                row.get(3).unwrap(),
-                row.get(4).unwrap(),
-                row.get(5).unwrap(),
-                row.get(6).unwrap(),
-                row.get(7).unwrap(),
-                row.get(8).unwrap(),
-            ))
-        })
-        .unwrap()
-    {
-        let data = wrapped_data.unwrap();
-        activity_tx_data
-            .entry(data.activity_num())
-            .or_insert(Vec::new())
-            .push(data);
-    }
-
-    activity_tx_data
-}
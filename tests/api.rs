@@ -0,0 +1,299 @@
+extern crate rex_tui;
+use rex_tui::api::{get_api_token, get_api_token_path, handle_api_request, ApiRequest};
+use rex_tui::db::{create_db, get_tx_foreign_amount, ForeignAmount};
+use rex_tui::events::EventHook;
+use rex_tui::outputs::ApiError;
+use rex_tui::tx_handler::add_tx;
+use rex_tui::utility::{get_tx_id_num, Config};
+use rusqlite::Connection;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn get_request(method: &str, path: &str, query: &[(&str, &str)], body: &str) -> ApiRequest {
+    ApiRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect(),
+        token: Some("secret".to_string()),
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn check_api_request_parsing() {
+    let raw = "POST /txs?month=5&details=Coffee+%26+cake HTTP/1.1\r\nHost: 127.0.0.1\r\nAuthorization: Bearer secret\r\nContent-Length: 13\r\n\r\n{\"amount\": 5}";
+    let request = ApiRequest::read(&mut Cursor::new(raw)).unwrap();
+
+    let expected = ApiRequest {
+        method: "POST".to_string(),
+        path: "/txs".to_string(),
+        query: HashMap::from([
+            ("month".to_string(), "5".to_string()),
+            ("details".to_string(), "Coffee & cake".to_string()),
+        ]),
+        token: Some("secret".to_string()),
+        body: "{\"amount\": 5}".to_string(),
+    };
+    assert_eq!(request, expected);
+
+    let raw = "GET /txs\r\n\r\n";
+    let request = ApiRequest::read(&mut Cursor::new(raw));
+    assert!(matches!(request, Err(ApiError::InvalidRequest)));
+
+    let raw = "POST /txs HTTP/1.1\r\nContent-Length: 1000000\r\n\r\n";
+    let request = ApiRequest::read(&mut Cursor::new(raw));
+    assert!(matches!(request, Err(ApiError::BodyTooLarge)));
+}
+
+#[test]
+fn check_api_token() {
+    let dir_name = "api_token_test_dir";
+    fs::create_dir_all(dir_name).unwrap();
+
+    let mut db_path = PathBuf::from(dir_name);
+    db_path.push("data.sqlite");
+    let token_path = get_api_token_path(&db_path);
+
+    let token = get_api_token(&db_path).unwrap();
+    let saved_token = get_api_token(&db_path).unwrap();
+
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(&token_path).unwrap().permissions().mode() & 0o777
+    };
+
+    fs::write(&token_path, "").unwrap();
+    let new_token = get_api_token(&db_path).unwrap();
+
+    fs::remove_dir_all(dir_name).unwrap();
+
+    assert_eq!(token.len(), 32);
+    assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(token, saved_token);
+    assert_ne!(token, new_token);
+    #[cfg(unix)]
+    assert_eq!(mode, 0o600);
+}
+
+#[test]
+fn check_api_endpoints() {
+    let file_name = "check_api_endpoints.sqlite".to_string();
+    let mut conn = create_test_db(&file_name);
+    let config = Config::default();
+    let events = EventHook::default();
+
+    add_tx(
+        "2022-08-19",
+        "Salary",
+        "test1",
+        "1000.00",
+        "Income",
+        "Job",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let mut request = get_request("GET", "/balances", &[], "");
+    request.token = Some("wrong".to_string());
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 401);
+
+    request.token = None;
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 401);
+
+    let body = json!({
+        "date": "2022-08-20",
+        "details": "Coffee",
+        "tx_method": "test1",
+        "amount": 4.5,
+        "tags": "Food"
+    });
+    let request = get_request("POST", "/txs", &[], &body.to_string());
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 201);
+    assert_eq!(response.body, json!({ "id": 2 }));
+
+    let body = json!({
+        "date": "2022-08-20",
+        "details": "Lunch",
+        "tx_method": "test3",
+        "amount": "10"
+    });
+    let request = get_request("POST", "/txs", &[], &body.to_string());
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 400);
+
+    let request = get_request("POST", "/txs", &[], "not json");
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 400);
+
+    let request = get_request("GET", "/txs", &[("month", "8"), ("year", "2022")], "");
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        response.body,
+        json!({
+            "txs": [
+                {
+                    "id": 1,
+                    "date": "2022-08-19",
+                    "details": "Salary",
+                    "tx_method": "test1",
+                    "amount": 1000.0,
+                    "tx_type": "Income",
+                    "tags": ["Job"]
+                },
+                {
+                    "id": 2,
+                    "date": "2022-08-20",
+                    "details": "Coffee",
+                    "tx_method": "test1",
+                    "amount": 4.5,
+                    "tx_type": "Expense",
+                    "tags": ["Food"]
+                }
+            ]
+        })
+    );
+
+    let request = get_request("GET", "/txs", &[("month", "13"), ("year", "2022")], "");
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 400);
+
+    let request = get_request("GET", "/balances", &[], "");
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(
+        response.body,
+        json!({
+            "balances": [
+                { "tx_method": "test1", "balance": 995.5 },
+                { "tx_method": "test 2", "balance": 0.0 }
+            ]
+        })
+    );
+
+    let request = get_request("GET", "/summary", &[("year", "2022")], "");
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(
+        response.body,
+        json!({
+            "income": 1000.0,
+            "expense": 4.5,
+            "net": 995.5,
+            "tags": [
+                { "tag": "Food", "income": 0.0, "expense": 4.5 },
+                { "tag": "Job", "income": 1000.0, "expense": 0.0 }
+            ]
+        })
+    );
+
+    let request = get_request("DELETE", "/txs", &[], "");
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 405);
+
+    let request = get_request("GET", "/unknown", &[], "");
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 404);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_api_foreign_amount() {
+    let file_name = "check_api_foreign_amount.sqlite".to_string();
+    let mut conn = create_test_db(&file_name);
+    let events = EventHook::default();
+
+    let mut config = Config::default();
+
+    let body = json!({
+        "date": "2022-08-20",
+        "details": "Museum",
+        "tx_method": "test1",
+        "amount": "25 EUR"
+    });
+    let request = get_request("POST", "/txs", &[], &body.to_string());
+
+    // without a base currency the amount is not saved as 25 of the base currency
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 400);
+
+    config.base_currency = "USD".to_string();
+    config.currency_rates = HashMap::from([
+        ("EUR".to_string(), 1.1),
+        ("GBP".to_string(), 1.25),
+        ("MXN".to_string(), 0.05),
+    ]);
+
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 201);
+
+    // the b of GBP and the m of MXN are not taken as the balance or a million
+    for (id_num, amount) in [(2, "10 GBP"), (3, "200 mxn")] {
+        let body = json!({
+            "date": "2022-08-20",
+            "details": "Souvenir",
+            "tx_method": "test1",
+            "amount": amount
+        });
+        let request = get_request("POST", "/txs", &[], &body.to_string());
+        let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+        assert_eq!(response.status, 201);
+        assert_eq!(response.body, json!({ "id": id_num }));
+    }
+
+    let body = json!({
+        "date": "2022-08-20",
+        "details": "Souvenir",
+        "tx_method": "test1",
+        "amount": "10 JPY"
+    });
+    let request = get_request("POST", "/txs", &[], &body.to_string());
+    let response = handle_api_request(&request, "secret", &config, &events, &mut conn);
+    assert_eq!(response.status, 400);
+
+    let amounts = (1..=3)
+        .map(|id_num| get_tx_id_num(id_num, &conn)[3].clone())
+        .collect::<Vec<String>>();
+    let foreign_amounts = (1..=3)
+        .map(|id_num| get_tx_foreign_amount(id_num, &conn))
+        .collect::<Vec<Option<ForeignAmount>>>();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(amounts, vec!["27.50", "12.50", "10.00"]);
+    assert_eq!(
+        foreign_amounts,
+        ["25.00", "10.00", "200.00"]
+            .iter()
+            .zip(["EUR", "GBP", "MXN"])
+            .map(|(amount, currency)| Some(ForeignAmount {
+                amount: amount.to_string(),
+                currency: currency.to_string(),
+            }))
+            .collect::<Vec<Option<ForeignAmount>>>()
+    );
+}
@@ -1,6 +1,7 @@
 extern crate rex_tui;
 use rex_tui::db::create_db;
 use rex_tui::tx_handler::*;
+use rex_tui::utility::get_matching_tags;
 use rex_tui::utility::traits::AutoFiller;
 use rusqlite::Connection;
 use std::fs;
@@ -20,7 +21,7 @@ fn create_test_db(file_name: &str) -> Connection {
 
     let mut conn = Connection::open(file_name).unwrap();
     create_db(
-        &vec!["Super Special Bank".to_string(), "Cash Cow".to_string()],
+        &["Super Special Bank".to_string(), "Cash Cow".to_string()],
         &mut conn,
     )
     .unwrap();
@@ -161,3 +162,83 @@ fn autofiller_test() {
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
 }
+
+#[test]
+fn check_details_autofill_frequency() {
+    let file_name = "autofiller_test_2.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    for details in [
+        "Coffee",
+        "Coffee beans",
+        "Coffee beans",
+        "Cofactor",
+        "Coffee beans",
+    ] {
+        add_tx(
+            "2023-07-19",
+            details,
+            "Cash Cow",
+            "10.00",
+            "Expense",
+            "Food",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let test_data = Testing {
+        data: Vec::new(),
+        expected: Vec::new(),
+    };
+
+    // the most used details comes first even if a shorter one also starts with the input
+    let most_used = test_data.autofill_details("cof", &conn);
+    let exact_but_less_used = test_data.autofill_details("Coffee", &conn);
+    let completed = test_data.autofill_details("coffee be", &conn);
+    let fully_typed = test_data.autofill_details("Coffee beans", &conn);
+    let less_used = test_data.autofill_details("cofa", &conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(most_used, "Coffee beans");
+    assert_eq!(exact_but_less_used, "Coffee beans");
+    assert_eq!(completed, "Coffee beans");
+    assert_eq!(fully_typed, "");
+    assert_eq!(less_used, "Cofactor");
+}
+
+#[test]
+fn check_tags_autofill_suggestions() {
+    let all_tags = ["Food", "Fuel", "Car", "Seafood"]
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+
+    let test_data = Testing {
+        data: Vec::new(),
+        expected: Vec::new(),
+    };
+
+    // tags that start with the fragment come before the ones that only contain it
+    assert_eq!(
+        get_matching_tags("foo", &all_tags),
+        vec!["Food".to_string(), "Seafood".to_string()]
+    );
+    // only the last tag is matched and already entered tags are not suggested again
+    assert_eq!(
+        get_matching_tags("Food, f", &all_tags),
+        vec!["Fuel".to_string(), "Seafood".to_string()]
+    );
+    assert_eq!(get_matching_tags("", &all_tags), all_tags);
+
+    assert_eq!(
+        test_data.autofill_tags_from("Car, sea", &all_tags),
+        "Seafood"
+    );
+    assert_eq!(test_data.autofill_tags_from("fu", &all_tags), "Fuel");
+    assert_eq!(test_data.autofill_tags_from("Food", &all_tags), "");
+    assert_eq!(test_data.autofill_tags_from("Food, ", &all_tags), "");
+}
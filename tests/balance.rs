@@ -1,6 +1,9 @@
 extern crate rex_tui;
 use chrono::{naive::NaiveDate, Duration};
-use rex_tui::db::create_db;
+use rex_tui::db::{
+    check_db_integrity, create_db, get_balance_divergence, get_balance_snapshot,
+    get_snapshot_drift, rebuild_derived_tables, take_balance_snapshots,
+};
 use rex_tui::tx_handler::*;
 use rex_tui::utility::*;
 use rusqlite::{Connection, Result as sqlResult};
@@ -15,7 +18,7 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
@@ -123,7 +126,7 @@ fn check_last_balances_3() {
 #[test]
 fn check_last_month_balance_1() {
     let file_name = "last_month_balance_1.sqlite";
-    let conn = create_test_db(&file_name);
+    let conn = create_test_db(file_name);
     let tx_methods = get_all_tx_methods(&conn);
 
     let data = get_last_time_balance(6, 1, &tx_methods, &conn);
@@ -138,7 +141,7 @@ fn check_last_month_balance_1() {
 #[test]
 fn check_last_balance_id() {
     let file_name = "last_balance_id.sqlite";
-    let conn = create_test_db(&file_name);
+    let conn = create_test_db(file_name);
 
     let data = get_last_balance_id(&conn);
     let expected_data: sqlResult<i32> = Ok(193);
@@ -152,7 +155,7 @@ fn check_last_balance_id() {
 #[test]
 fn check_last_month_balance_2() {
     let file_name = "last_month_balance_2.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
     let tx_methods = get_all_tx_methods(&conn);
 
     add_tx(
@@ -278,7 +281,7 @@ fn check_last_month_balance_2() {
 #[ignore]
 fn check_balance_all_day() {
     let file_name = "check_balance_all_day.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
     let tx_methods = get_all_tx_methods(&conn);
 
     let mut current_date = NaiveDate::parse_from_str("2022-01-01", "%Y-%m-%d").unwrap();
@@ -331,7 +334,7 @@ fn check_balance_all_day() {
 
     let expected_data_1 = vec!["0".to_string(), "0".to_string()];
     let mut expected_data_2 = HashMap::new();
-    for (i, _x) in &data_2 {
+    for i in data_2.keys() {
         expected_data_2.insert(i.to_string(), 0.0);
     }
 
@@ -341,3 +344,217 @@ fn check_balance_all_day() {
     assert_eq!(data_1, expected_data_1);
     assert_eq!(data_2, expected_data_2);
 }
+
+#[test]
+fn check_balance_snapshots() {
+    let file_name = "balance_snapshots.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2022-02-10",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Salary",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2022-04-10",
+        "Testing transaction",
+        "test 2",
+        "50.00",
+        "Income",
+        "Salary",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let tx_methods = get_all_tx_methods(&conn);
+    take_balance_snapshots(&mut conn).unwrap();
+
+    // 2 = end of February, 3 = end of march, 4 = end of april
+    let snapshot_1 = get_balance_snapshot(2, &tx_methods, &conn).unwrap();
+    let snapshot_2 = get_balance_snapshot(3, &tx_methods, &conn).unwrap();
+    let snapshot_3 = get_balance_snapshot(4, &tx_methods, &conn).unwrap();
+
+    let drift_1 = get_snapshot_drift(&conn);
+
+    // adding a tx on march invalidates every snapshot from march
+    add_tx(
+        "2022-03-10",
+        "Testing transaction",
+        "test1",
+        "20.00",
+        "Expense",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let snapshot_4 = get_balance_snapshot(2, &tx_methods, &conn);
+    let snapshot_5 = get_balance_snapshot(3, &tx_methods, &conn);
+
+    // edit the balance of february outside of the app
+    conn.execute(
+        r#"UPDATE balance_all SET "test1" = 90.0 WHERE id_num = 2"#,
+        [],
+    )
+    .unwrap();
+    let drift_2 = get_snapshot_drift(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(snapshot_1["test1"], 100.0);
+    assert_eq!(snapshot_1["test 2"], 0.0);
+    assert_eq!(snapshot_2["test1"], 100.0);
+    assert_eq!(snapshot_3["test 2"], 50.0);
+    assert!(drift_1.is_empty());
+    assert!(snapshot_4.is_some());
+    assert!(snapshot_5.is_none());
+    assert_eq!(drift_2, vec![(2, "test1".to_string(), 100.0, 90.0)]);
+}
+
+fn get_balance_rows(conn: &Connection) -> Vec<(i32, f64, f64)> {
+    let mut statement = conn
+        .prepare(r#"SELECT id_num, test1, "test 2" FROM balance_all ORDER BY id_num"#)
+        .unwrap();
+    statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+fn get_changes_rows(conn: &Connection) -> Vec<(i32, String, String)> {
+    let mut statement = conn
+        .prepare(r#"SELECT id_num, test1, "test 2" FROM changes_all ORDER BY id_num"#)
+        .unwrap();
+    statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn check_integrity_and_rebuild() {
+    let file_name = "integrity_rebuild.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2022-01-05", "test1", "500.00", "Income"),
+        ("2022-01-20", "test1", "120.50", "Expense"),
+        ("2022-03-02", "test1 to test 2", "200.00", "Transfer"),
+        ("2023-06-11", "test 2", "45.25", "Expense"),
+    ];
+    for (date, method, amount, tx_type) in txs {
+        add_tx(
+            date, "Testing", method, amount, tx_type, "Unknown", None, &mut conn,
+        )
+        .unwrap();
+    }
+
+    let balance_rows = get_balance_rows(&conn);
+    let changes_rows = get_changes_rows(&conn);
+    let clean_report = check_db_integrity(&conn).unwrap();
+
+    conn.execute("UPDATE balance_all SET test1 = 999 WHERE id_num = 193", [])
+        .unwrap();
+    conn.execute(
+        "UPDATE balance_all SET \"test 2\" = 1 WHERE id_num = 20",
+        [],
+    )
+    .unwrap();
+    conn.execute("DELETE FROM changes_all WHERE id_num = 2", [])
+        .unwrap();
+    // an older db or another tool could have written rows without the foreign key checks
+    conn.pragma_update(None, "foreign_keys", "OFF").unwrap();
+    conn.execute(
+        "INSERT INTO activity_txs (date, details, tx_method, amount, tx_type, tags, id_num, activity_num)
+        VALUES ('2022-01-05', 'Testing', 'test1', '1.00', 'Income', 'Unknown', '1', 50)",
+        [],
+    )
+    .unwrap();
+
+    let broken_report = check_db_integrity(&conn).unwrap();
+
+    rebuild_derived_tables(&mut conn).unwrap();
+
+    let rebuilt_balance_rows = get_balance_rows(&conn);
+    let rebuilt_changes_rows = get_changes_rows(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(clean_report.is_empty());
+    assert_eq!(
+        broken_report,
+        vec![
+            "1 row(s) of activity_txs point to a missing row of activities".to_string(),
+            "1 transaction(s) have no row in changes_all".to_string(),
+        ]
+    );
+    assert_eq!(rebuilt_balance_rows, balance_rows);
+    assert_eq!(rebuilt_changes_rows, changes_rows);
+}
+
+#[test]
+fn check_balance_divergence() {
+    let file_name = "balance_divergence.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2022-01-05",
+        "Testing",
+        "test1",
+        "500.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-03-02",
+        "Testing",
+        "test1 to test 2",
+        "200.00",
+        "Transfer",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let clean_divergence = get_balance_divergence(&conn).unwrap();
+
+    conn.execute("UPDATE balance_all SET test1 = 250 WHERE id_num = 3", [])
+        .unwrap();
+    conn.execute("UPDATE balance_all SET test1 = 999 WHERE id_num = 193", [])
+        .unwrap();
+
+    let divergence = get_balance_divergence(&conn).unwrap();
+
+    rebuild_derived_tables(&mut conn).unwrap();
+    let fixed_divergence = get_balance_divergence(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // the wrong March balance is carried to every month after it
+    let mut expected = (3..193)
+        .map(|id_num| (id_num, "test1".to_string(), 250.0, 300.0))
+        .collect::<Vec<_>>();
+    expected.push((193, "test1".to_string(), 999.0, 300.0));
+
+    assert!(clean_divergence.is_empty());
+    assert_eq!(divergence, expected);
+    assert!(fixed_divergence.is_empty());
+}
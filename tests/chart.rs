@@ -1,10 +1,13 @@
 extern crate rex_tui;
 use chrono::NaiveDate;
-use rex_tui::chart_page::ChartData;
+use rex_tui::chart_page::{
+    from_log_scale, get_chart_svg, get_line_extremes, to_log_scale, ChartData, ChartLines, Rgb,
+};
 use rex_tui::db::*;
-use rex_tui::page_handler::IndexedData;
+use rex_tui::page_handler::{ChartView, IndexedData, Theme};
 use rex_tui::tx_handler::add_tx;
 use rusqlite::Connection;
+use std::collections::HashMap;
 use std::fs;
 
 fn create_test_db(file_name: &str) -> Connection {
@@ -15,7 +18,7 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
@@ -60,7 +63,7 @@ fn add_dummy_tx(conn: &mut Connection) {
 #[test]
 fn check_chart_date() {
     let file_name = "chart_data_1.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
     add_dummy_tx(&mut conn);
 
     let chart_data = ChartData::new(&conn);
@@ -122,7 +125,7 @@ fn check_chart_date() {
 #[test]
 fn check_chart_data() {
     let file_name = "chart_data_2.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
     add_dummy_tx(&mut conn);
 
     let chart_data = ChartData::new(&conn);
@@ -130,7 +133,7 @@ fn check_chart_data() {
     let mut chart_mode = IndexedData::new_modes();
 
     let first_tx = [
-        "19-08-2022",
+        "2022-08-19",
         "Testing transaction",
         "test1",
         "100.00",
@@ -142,7 +145,7 @@ fn check_chart_data() {
     .collect();
 
     let second_tx = [
-        "19-07-2023",
+        "2023-07-19",
         "Testing transaction",
         "test 2",
         "100.00",
@@ -154,7 +157,7 @@ fn check_chart_data() {
     .collect();
 
     let third_tx = [
-        "25-07-2023",
+        "2023-07-25",
         "Testing transaction",
         "test1",
         "200.00",
@@ -240,3 +243,423 @@ fn check_chart_data() {
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
 }
+
+#[test]
+fn check_chart_cumulative_data() {
+    let file_name = "chart_data_3.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2023-07-25",
+        "Testing transaction",
+        "test 2",
+        "50.00",
+        "Expense",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let chart_data = ChartData::new(&conn);
+
+    let mut chart_mode = IndexedData::new_modes();
+    let mut activated_methods: HashMap<String, bool> = HashMap::new();
+    activated_methods.insert("test1".to_string(), true);
+    activated_methods.insert("test 2".to_string(), true);
+
+    let cumulative_data_1 = chart_data.get_cumulative_data(&chart_mode, 1, 1, &activated_methods);
+    let cumulative_data_2 = chart_data.get_cumulative_data(&chart_mode, 6, 1, &activated_methods);
+
+    let expected_data_2 = vec![
+        (NaiveDate::from_ymd_opt(2023, 7, 19).unwrap(), 0.0, 100.0),
+        (NaiveDate::from_ymd_opt(2023, 7, 25).unwrap(), 200.0, 150.0),
+    ];
+
+    assert_eq!(cumulative_data_1, Vec::new());
+    assert_eq!(cumulative_data_2, expected_data_2);
+
+    chart_mode.next();
+    chart_mode.next();
+    activated_methods.insert("test 2".to_string(), false);
+
+    let cumulative_data_3 = chart_data.get_cumulative_data(&chart_mode, 0, 0, &activated_methods);
+
+    let expected_data_3 = vec![
+        (NaiveDate::from_ymd_opt(2022, 8, 19).unwrap(), 0.0, 100.0),
+        (NaiveDate::from_ymd_opt(2023, 7, 19).unwrap(), 0.0, 100.0),
+        (NaiveDate::from_ymd_opt(2023, 7, 25).unwrap(), 200.0, 100.0),
+    ];
+
+    assert_eq!(cumulative_data_3, expected_data_3);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_chart_tag_data() {
+    let file_name = "chart_data_4.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2023-07-25",
+        "Testing transaction",
+        "test 2",
+        "50.00",
+        "Expense",
+        "Food, Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let chart_data = ChartData::new(&conn);
+
+    let mut chart_mode = IndexedData::new_modes();
+    let tags = vec!["Car".to_string(), "Food".to_string()];
+
+    let tag_data_1 = chart_data.get_tag_data(&chart_mode, 1, 1, &tags);
+    let tag_data_2 = chart_data.get_tag_data(&chart_mode, 6, 1, &tags);
+
+    let expected_data_2 = vec![
+        (
+            NaiveDate::from_ymd_opt(2023, 7, 19).unwrap(),
+            vec![0.0, 100.0],
+        ),
+        (
+            NaiveDate::from_ymd_opt(2023, 7, 25).unwrap(),
+            vec![50.0, 150.0],
+        ),
+    ];
+
+    assert_eq!(tag_data_1, Vec::new());
+    assert_eq!(tag_data_2, expected_data_2);
+
+    chart_mode.next();
+    chart_mode.next();
+
+    let tag_data_3 = chart_data.get_tag_data(&chart_mode, 0, 0, &tags[0..1]);
+
+    let expected_data_3 = vec![
+        (NaiveDate::from_ymd_opt(2022, 8, 19).unwrap(), vec![100.0]),
+        (NaiveDate::from_ymd_opt(2023, 7, 19).unwrap(), vec![100.0]),
+        (NaiveDate::from_ymd_opt(2023, 7, 25).unwrap(), vec![150.0]),
+    ];
+
+    assert_eq!(tag_data_3, expected_data_3);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_chart_log_scale() {
+    let values = [0.0, 9.0, 99.0, 999.0, -99.0, 1234.56];
+    let log_values = values.map(to_log_scale);
+
+    assert_eq!(log_values[0], 0.0);
+    assert!((log_values[1] - 1.0).abs() < 1e-9);
+    assert!((log_values[2] - 2.0).abs() < 1e-9);
+    assert!((log_values[3] - 3.0).abs() < 1e-9);
+    assert!((log_values[4] + 2.0).abs() < 1e-9);
+
+    for (value, log_value) in values.iter().zip(log_values) {
+        assert!((from_log_scale(log_value) - value).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn check_chart_lines() {
+    let file_name = "chart_data_5.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    let chart_data = ChartData::new(&conn);
+
+    let chart_mode = IndexedData::new_modes();
+    let chart_tags = IndexedData::new_tags(&conn);
+    let mut activated_methods: HashMap<String, bool> = HashMap::new();
+    activated_methods.insert("test1".to_string(), true);
+    activated_methods.insert("test 2".to_string(), false);
+    let activated_tags: HashMap<String, bool> = HashMap::new();
+    let mut method_colors: HashMap<String, Rgb> = HashMap::new();
+
+    let empty_lines = chart_data.get_chart_lines(
+        &ChartView::Balance,
+        &chart_mode,
+        1,
+        1,
+        &chart_tags,
+        &activated_methods,
+        &activated_tags,
+        &method_colors,
+    );
+
+    let chart_lines = chart_data.get_chart_lines(
+        &ChartView::Balance,
+        &chart_mode,
+        6,
+        1,
+        &chart_tags,
+        &activated_methods,
+        &activated_tags,
+        &method_colors,
+    );
+
+    let svg = get_chart_svg(
+        &chart_lines,
+        false,
+        "Balance - July 2023",
+        &Theme::default(),
+    );
+
+    activated_methods.insert("test 2".to_string(), true);
+    activated_methods.insert("Total".to_string(), true);
+    method_colors.insert("test 2".to_string(), Rgb(1, 2, 3));
+    let total_lines = chart_data.get_chart_lines(
+        &ChartView::Balance,
+        &chart_mode,
+        6,
+        1,
+        &chart_tags,
+        &activated_methods,
+        &activated_tags,
+        &method_colors,
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(empty_lines.start_date, None);
+    assert_eq!(empty_lines.lines[0].points, vec![(0.0, 0.0)]);
+
+    assert_eq!(
+        chart_lines.start_date,
+        Some(NaiveDate::from_ymd_opt(2023, 7, 19).unwrap())
+    );
+    assert_eq!(chart_lines.last_point, 6);
+    assert_eq!(chart_lines.step_days, 1);
+    assert_eq!(chart_lines.lines.len(), 3);
+    assert_eq!(chart_lines.lines[0].name, "test1");
+    assert!(chart_lines.lines[0].activated);
+    assert!(!chart_lines.lines[1].activated);
+    assert_eq!(chart_lines.lines[1].points.len(), 7);
+    // days without tx keep the last balance
+    assert_eq!(chart_lines.lines[1].points[0], (0.0, -100.0));
+    assert_eq!(chart_lines.lines[1].points[5], (5.0, -100.0));
+
+    // colors don't depend on which lines are activated. A pinned color is always used
+    assert_eq!(chart_lines.lines[0].color, total_lines.lines[0].color);
+    assert_eq!(total_lines.lines[1].color, Rgb(1, 2, 3));
+
+    // Total line is deactivated unless activated from the chart
+    assert_eq!(chart_lines.lines[2].name, "Total");
+    assert!(!chart_lines.lines[2].activated);
+    // Total only sums up the activated tx methods
+    assert_eq!(
+        chart_lines.lines[2].points[0].1,
+        chart_lines.lines[0].points[0].1
+    );
+    assert!(total_lines.lines[2].activated);
+    for i in 0..7 {
+        assert_eq!(
+            total_lines.lines[2].points[i].1,
+            total_lines.lines[0].points[i].1 + total_lines.lines[1].points[i].1
+        );
+    }
+
+    // only the activated line is drawn
+    assert_eq!(svg.matches("<polyline").count(), 1);
+    assert!(svg.contains("Balance - July 2023"));
+    assert!(svg.contains("test1"));
+    assert!(!svg.contains("test 2"));
+}
+
+#[test]
+fn check_chart_plain_export() {
+    let file_name = "chart_plain_export.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    let chart_data = ChartData::new(&conn);
+    let mut activated_methods: HashMap<String, bool> = HashMap::new();
+    activated_methods.insert("test1".to_string(), true);
+    let mut method_colors: HashMap<String, Rgb> = HashMap::new();
+    method_colors.insert("test1".to_string(), Rgb(1, 2, 3));
+
+    let chart_lines = chart_data.get_chart_lines(
+        &ChartView::Balance,
+        &IndexedData::new_modes(),
+        6,
+        1,
+        &IndexedData::new_tags(&conn),
+        &activated_methods,
+        &HashMap::new(),
+        &method_colors,
+    );
+
+    // the plain theme only uses the named terminal colors
+    let svg = get_chart_svg(&chart_lines, false, "Balance - July 2023", &Theme::PLAIN);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(svg.contains(r##"<rect width="100%" height="100%" fill="#000000"/>"##));
+    assert!(svg.contains(r##"fill="#ffffff" font-size="22""##));
+    assert!(svg.contains(r##"fill="none" stroke="#0000ee"/>"##));
+    assert!(svg.contains(r##"stroke="#010203""##));
+}
+
+#[test]
+fn check_chart_weekly_lines() {
+    let file_name = "chart_data_6.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    let chart_data = ChartData::new(&conn);
+
+    let chart_mode = IndexedData::new_modes();
+    let chart_tags = IndexedData::new_tags(&conn);
+    let mut activated_methods: HashMap<String, bool> = HashMap::new();
+    activated_methods.insert("test1".to_string(), true);
+    activated_methods.insert("test 2".to_string(), true);
+    let activated_tags: HashMap<String, bool> = HashMap::new();
+    let method_colors: HashMap<String, Rgb> = HashMap::new();
+
+    let daily_lines = chart_data.get_chart_lines(
+        &ChartView::Balance,
+        &chart_mode,
+        6,
+        1,
+        &chart_tags,
+        &activated_methods,
+        &activated_tags,
+        &method_colors,
+    );
+    let daily_points = daily_lines.lines[1].points.clone();
+
+    let weekly_lines = daily_lines.into_weekly();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // 2023-07-19 is a Wednesday and 2023-07-25 is the Tuesday of the next week
+    assert_eq!(
+        weekly_lines.start_date,
+        Some(NaiveDate::from_ymd_opt(2023, 7, 17).unwrap())
+    );
+    assert_eq!(weekly_lines.step_days, 7);
+    assert_eq!(weekly_lines.last_point, 1);
+    // each week keeps the value of its last day
+    assert_eq!(
+        weekly_lines.lines[1].points,
+        vec![(0.0, daily_points[4].1), (1.0, daily_points[6].1)]
+    );
+}
+
+#[test]
+fn check_chart_line_extremes() {
+    let points = vec![
+        (0.0, 50.0),
+        (1.0, 120.0),
+        (2.0, -30.0),
+        (3.0, 120.0),
+        (4.0, -30.0),
+    ];
+
+    assert_eq!(get_line_extremes(&[]), None);
+    assert_eq!(
+        get_line_extremes(&points[0..1]),
+        Some(((0.0, 50.0), (0.0, 50.0)))
+    );
+    assert_eq!(
+        get_line_extremes(&points),
+        Some(((2.0, -30.0), (1.0, 120.0)))
+    );
+}
+
+#[test]
+fn check_chart_cached_lines() {
+    let file_name = "chart_data_7.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    let mut chart_data = ChartData::new(&conn);
+
+    let chart_mode = IndexedData::new_modes();
+    let chart_tags = IndexedData::new_tags(&conn);
+    let mut activated_methods: HashMap<String, bool> = HashMap::new();
+    activated_methods.insert("test1".to_string(), true);
+    activated_methods.insert("test 2".to_string(), true);
+    let activated_tags: HashMap<String, bool> = HashMap::new();
+    let method_colors: HashMap<String, Rgb> = HashMap::new();
+
+    let get_points = |lines: &ChartLines| {
+        lines
+            .lines
+            .iter()
+            .map(|line| (line.activated, line.points.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let expected_daily = get_points(&chart_data.get_chart_lines(
+        &ChartView::Balance,
+        &chart_mode,
+        6,
+        1,
+        &chart_tags,
+        &activated_methods,
+        &activated_tags,
+        &method_colors,
+    ));
+
+    let expected_weekly = get_points(
+        &chart_data
+            .get_chart_lines(
+                &ChartView::Balance,
+                &chart_mode,
+                6,
+                1,
+                &chart_tags,
+                &activated_methods,
+                &activated_tags,
+                &method_colors,
+            )
+            .into_weekly(),
+    );
+
+    let mut get_cached = |weekly: bool, activated_methods: &HashMap<String, bool>| {
+        get_points(chart_data.get_cached_chart_lines(
+            &ChartView::Balance,
+            &chart_mode,
+            6,
+            1,
+            weekly,
+            &chart_tags,
+            activated_methods,
+            &activated_tags,
+            &method_colors,
+        ))
+    };
+
+    let first_daily = get_cached(false, &activated_methods);
+    let second_daily = get_cached(false, &activated_methods);
+    let weekly = get_cached(true, &activated_methods);
+
+    activated_methods.insert("test1".to_string(), false);
+    let deactivated = get_cached(false, &activated_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(first_daily, expected_daily);
+    assert_eq!(second_daily, expected_daily);
+    assert_eq!(weekly, expected_weekly);
+
+    // a changed activation creates the lines again
+    assert!(!deactivated[0].0);
+    assert_ne!(deactivated, expected_daily);
+}
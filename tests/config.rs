@@ -0,0 +1,193 @@
+extern crate rex_tui;
+use rex_tui::chart_page::Rgb;
+use rex_tui::db::create_db;
+use rex_tui::outputs::{ConfigError, ProfileError};
+use rex_tui::page_handler::HomeColumn;
+use rex_tui::utility::{
+    get_active_profile, get_all_profiles, get_config, get_config_path, get_profile_db_path,
+    parse_hex_color, save_active_profile, verify_profile_name, Config, COMBINED_PROFILE,
+    DEFAULT_PROFILE,
+};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn check_config() {
+    let dir_name = "config_test_dir";
+    fs::create_dir_all(dir_name).unwrap();
+
+    let mut db_path = PathBuf::from(dir_name);
+    db_path.push("data.sqlite");
+    let config_path = get_config_path(&db_path);
+
+    // missing config file gets created with the default values
+    let default_config = get_config(&db_path).unwrap();
+    let file_created = config_path.exists();
+
+    // missing fields are filled up by the default values
+    fs::write(&config_path, r#"{"idle_after_secs": 5}"#).unwrap();
+    let partial_config = get_config(&db_path).unwrap();
+
+    fs::write(&config_path, "not a json").unwrap();
+    let invalid_config = get_config(&db_path);
+
+    fs::write(&config_path, r#"{"idle_after_secs": "5"}"#).unwrap();
+    let wrong_type_config = get_config(&db_path);
+
+    fs::remove_dir_all(dir_name).unwrap();
+
+    assert_eq!(default_config, Config::default());
+    assert!(file_created);
+    assert_eq!(
+        partial_config,
+        Config {
+            idle_after_secs: 5,
+            ..Config::default()
+        }
+    );
+    // the error is returned instead of using the defaults
+    assert!(matches!(invalid_config, Err(ConfigError::InvalidConfig(_))));
+    assert!(matches!(
+        wrong_type_config,
+        Err(ConfigError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn check_tx_method_colors() {
+    assert_eq!(parse_hex_color("#ff5555"), Some(Rgb(255, 85, 85)));
+    assert_eq!(parse_hex_color(" #0A0b0C "), Some(Rgb(10, 11, 12)));
+    assert_eq!(parse_hex_color("ff5555"), None);
+    assert_eq!(parse_hex_color("#ff55"), None);
+    assert_eq!(parse_hex_color("#gg5555"), None);
+
+    let mut tx_method_colors = HashMap::new();
+    tx_method_colors.insert("Cash".to_string(), "#50fa7b".to_string());
+    tx_method_colors.insert("Bank".to_string(), "green".to_string());
+
+    let config = Config {
+        tx_method_colors,
+        ..Config::default()
+    };
+
+    let file_name = "tx_method_colors_config.sqlite";
+    if fs::metadata(file_name).is_ok() {
+        fs::remove_file(file_name).unwrap();
+    }
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["Cash".to_string(), "Bank".to_string()], &mut conn).unwrap();
+
+    let method_colors = config.get_tx_method_colors(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // invalid colors fall back to the default color of the method position
+    assert_eq!(method_colors.len(), 2);
+    assert_eq!(method_colors["Cash"], Rgb(80, 250, 123));
+    assert_eq!(method_colors["Bank"], Rgb(189, 147, 249));
+}
+
+#[test]
+fn check_home_columns() {
+    let get_columns = |columns: &[&str]| {
+        Config {
+            home_columns: columns.iter().map(ToString::to_string).collect(),
+            ..Config::default()
+        }
+        .get_home_columns()
+    };
+
+    let default_columns = Config::default().get_home_columns();
+    let reordered = get_columns(&["id", "Amount", "DATE", "amount", "Unknown"]);
+    let all_unknown = get_columns(&["Foo"]);
+
+    assert_eq!(default_columns, HomeColumn::default_columns());
+    assert_eq!(
+        reordered,
+        vec![HomeColumn::Id, HomeColumn::Amount, HomeColumn::Date]
+    );
+    assert_eq!(all_unknown, HomeColumn::default_columns());
+}
+
+#[test]
+fn check_default_tx() {
+    let file_name = "default_tx.sqlite";
+    if fs::metadata(file_name).is_ok() {
+        fs::remove_file(file_name).unwrap();
+    }
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["Cash".to_string(), "Bank".to_string()], &mut conn).unwrap();
+
+    let empty_tx = Config::default().get_default_tx(&conn);
+
+    let config = Config {
+        default_tx_method: "bank".to_string(),
+        default_tx_type: "expense".to_string(),
+        default_tags: " Food ".to_string(),
+        ..Config::default()
+    };
+    let default_tx = config.get_default_tx(&conn);
+
+    let invalid_config = Config {
+        default_tx_method: "Card".to_string(),
+        default_tx_type: "Refund".to_string(),
+        ..Config::default()
+    };
+    let invalid_tx = invalid_config.get_default_tx(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(empty_tx.get_all_texts()[2..7], ["", "", "", "", ""]);
+    assert_eq!(
+        default_tx.get_all_texts()[2..7],
+        ["Bank", "", "", "Expense", "Food"]
+    );
+    assert_eq!(invalid_tx.get_all_texts()[2..7], ["", "", "", "", ""]);
+    assert_eq!(default_tx.get_all_texts()[0].len(), 10);
+}
+
+#[test]
+fn check_profiles() {
+    let dir_name = "profile_test_dir";
+    fs::create_dir_all(format!("{dir_name}/profiles")).unwrap();
+
+    let mut db_path = PathBuf::from(dir_name);
+    db_path.push("data.sqlite");
+
+    let business_path = get_profile_db_path(&db_path, "Business");
+    fs::write(&business_path, "").unwrap();
+    fs::write(format!("{dir_name}/profiles/notes.txt"), "").unwrap();
+
+    let profiles = get_all_profiles(&db_path);
+    let existing = verify_profile_name(" business ", &db_path).unwrap();
+    let new_profile = verify_profile_name("Partner 2", &db_path).unwrap();
+    let empty = verify_profile_name("  ", &db_path);
+    let invalid = verify_profile_name("../Business", &db_path);
+    let combined = verify_profile_name("all profiles", &db_path).unwrap();
+
+    let default_active = get_active_profile(&db_path);
+    save_active_profile(&db_path, "Business").unwrap();
+    let saved_active = get_active_profile(&db_path);
+
+    fs::remove_dir_all(dir_name).unwrap();
+
+    assert_eq!(get_profile_db_path(&db_path, DEFAULT_PROFILE), db_path);
+    assert_eq!(
+        business_path,
+        PathBuf::from(dir_name)
+            .join("profiles")
+            .join("Business.sqlite")
+    );
+    assert_eq!(profiles, vec![DEFAULT_PROFILE, "Business"]);
+    assert_eq!(existing, "Business");
+    assert_eq!(new_profile, "Partner 2");
+    assert!(matches!(empty, Err(ProfileError::Empty)));
+    assert!(matches!(invalid, Err(ProfileError::InvalidName(_))));
+    assert_eq!(combined, COMBINED_PROFILE);
+    assert_eq!(default_active, DEFAULT_PROFILE);
+    assert_eq!(saved_active, "Business");
+}
@@ -1,9 +1,15 @@
 extern crate rex_tui;
-use rex_tui::db::{add_new_tx_methods, create_db, rename_column, reposition_column};
+use rex_tui::db::{
+    add_new_tx_methods, analyze_db, backup_db_now, checkpoint_db, create_combined_db, create_db,
+    get_conflict_copy_path, get_db_file_state, get_db_stats, open_read_only_db, reindex_db,
+    rename_column, reposition_column, save_db_copy, set_db_pragmas, set_read_only_pragmas,
+    vacuum_db,
+};
 use rex_tui::tx_handler::add_tx;
 use rex_tui::utility::{get_all_tx_methods, get_last_balances};
 use rusqlite::Connection;
 use std::fs;
+use std::path::Path;
 
 fn create_test_db(file_name: &str) -> Connection {
     if let Ok(metadata) = fs::metadata(file_name) {
@@ -13,7 +19,7 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
@@ -41,7 +47,7 @@ fn check_adding_new_tx_method() {
     let file_name = "test_db_2.sqlite";
     let mut conn = create_test_db(file_name);
 
-    let status = add_new_tx_methods(&vec!["test3".to_string(), "test 4".to_string()], &mut conn);
+    let status = add_new_tx_methods(&["test3".to_string(), "test 4".to_string()], &mut conn);
 
     let tx_methods = get_all_tx_methods(&conn);
     let expected_tx_methods = vec![
@@ -92,7 +98,7 @@ fn check_repositioning_columns() {
 
     let old_last_balances = get_last_balances(&conn);
 
-    let status = reposition_column(&vec!["test 2".to_string(), "test1".to_string()], &mut conn);
+    let status = reposition_column(&["test 2".to_string(), "test1".to_string()], &mut conn);
     let tx_methods = get_all_tx_methods(&conn);
     let expected_tx_methods = vec!["test 2".to_string(), "test1".to_string()];
 
@@ -106,3 +112,388 @@ fn check_repositioning_columns() {
     assert_eq!(old_last_balances, vec!["159", "0"]);
     assert_eq!(last_balances, vec!["0", "159"]);
 }
+
+#[test]
+fn check_renaming_transfer_txs() {
+    let file_name = "test_db_6.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2022-07-20",
+        "Testing transaction",
+        "test1 to test 2",
+        "50.00",
+        "Transfer",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let status = rename_column("test1", "test", &mut conn);
+
+    let tx_methods: Vec<String> = conn
+        .prepare("SELECT tx_method FROM tx_all ORDER BY id_num")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .map(|method| method.unwrap())
+        .collect();
+    let last_balances = get_last_balances(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(status, Ok(()));
+    assert_eq!(
+        tx_methods,
+        vec!["test".to_string(), "test to test 2".to_string()]
+    );
+    assert_eq!(last_balances, vec!["50", "50"]);
+}
+
+#[test]
+fn check_db_pragmas() {
+    let file_name = "test_db_pragmas.sqlite";
+    let conn = create_test_db(file_name);
+
+    set_db_pragmas(&conn).unwrap();
+
+    let journal_mode: String = conn
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .unwrap();
+    let synchronous: i32 = conn
+        .query_row("PRAGMA synchronous", [], |row| row.get(0))
+        .unwrap();
+    let busy_timeout: i32 = conn
+        .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+        .unwrap();
+
+    // a second connection can read while the first one holds a write transaction
+    conn.execute("BEGIN IMMEDIATE", []).unwrap();
+    conn.execute("DELETE FROM tx_all", []).unwrap();
+    let reader = Connection::open(file_name).unwrap();
+    let read_status = reader.query_row("SELECT COUNT(*) FROM tx_all", [], |row| {
+        row.get::<_, i64>(0)
+    });
+    conn.execute("COMMIT", []).unwrap();
+
+    checkpoint_db(&conn).unwrap();
+
+    reader.close().unwrap();
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(journal_mode, "wal");
+    assert_eq!(synchronous, 1);
+    assert_eq!(busy_timeout, 5000);
+    assert!(read_status.is_ok());
+}
+
+#[test]
+fn check_db_file_changes() {
+    let file_name = "test_db_file_changes.sqlite";
+    let synced_name = "test_db_file_changes_synced.sqlite";
+    let mut conn = create_test_db(file_name);
+    let db_path = Path::new(file_name);
+
+    add_tx(
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let first_state = get_db_file_state(db_path);
+    let unchanged_state = get_db_file_state(db_path);
+
+    // a sync app writes the db of the other device next to it and replaces the file with it
+    let mut synced_conn = create_test_db(synced_name);
+    for details in ["First synced", "Second synced"] {
+        add_tx(
+            "2022-08-20",
+            details,
+            "test1",
+            "50.00",
+            "Expense",
+            "Food",
+            None,
+            &mut synced_conn,
+        )
+        .unwrap();
+    }
+    synced_conn.close().unwrap();
+    fs::rename(synced_name, file_name).unwrap();
+
+    let changed_state = get_db_file_state(db_path);
+
+    // the open connection still has the data of this session
+    let copy_path = get_conflict_copy_path(db_path);
+    save_db_copy(&copy_path, &conn).unwrap();
+    conn.close().unwrap();
+
+    let copy_conn = Connection::open(&copy_path).unwrap();
+    let copy_txs: i64 = copy_conn
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap();
+    copy_conn.close().unwrap();
+
+    let reopened_conn = Connection::open(file_name).unwrap();
+    let synced_txs: i64 = reopened_conn
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap();
+    reopened_conn.close().unwrap();
+
+    let copy_name = copy_path.file_name().unwrap().to_string_lossy().to_string();
+
+    fs::remove_file(&copy_path).unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(first_state.is_some());
+    assert_eq!(first_state, unchanged_state);
+    assert_ne!(first_state, changed_state);
+    assert!(get_db_file_state(Path::new("missing_db.sqlite")).is_none());
+    assert!(copy_name.starts_with("test_db_file_changes.conflict-"));
+    assert!(copy_name.ends_with(".sqlite"));
+    assert_eq!(copy_txs, 1);
+    assert_eq!(synced_txs, 2);
+}
+
+#[test]
+fn check_db_maintenance() {
+    let folder = "test_db_maintenance";
+    fs::create_dir_all(folder).unwrap();
+    let file_name = "test_db_maintenance/data.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    for details in ["First", "Second", "Third"] {
+        add_tx(
+            "2022-08-19",
+            details,
+            "test1",
+            "100.00",
+            "Income",
+            "Unknown",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let stats = get_db_stats(&conn).unwrap();
+    let tx_rows = stats
+        .tables
+        .iter()
+        .find(|(name, _)| name == "tx_all")
+        .map(|(_, rows)| *rows);
+
+    // the deleted rows leave free pages behind until the db is vacuumed
+    conn.execute("DELETE FROM activity_txs", []).unwrap();
+    conn.execute("DELETE FROM activities", []).unwrap();
+
+    vacuum_db(&conn).unwrap();
+    analyze_db(&conn).unwrap();
+    reindex_db(&conn).unwrap();
+
+    let vacuumed_stats = get_db_stats(&conn).unwrap();
+
+    let backup_path = backup_db_now(Path::new(file_name), &conn).unwrap();
+    conn.close().unwrap();
+
+    let backup_conn = Connection::open(&backup_path).unwrap();
+    let backup_txs: i64 = backup_conn
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap();
+    backup_conn.close().unwrap();
+
+    fs::remove_dir_all(folder).unwrap();
+
+    assert_eq!(tx_rows, Some(3));
+    assert!(stats.size > 0);
+    assert!(stats.tables.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(vacuumed_stats.free_size, 0);
+    assert!(backup_path.starts_with("test_db_maintenance/backups"));
+    assert_eq!(backup_txs, 3);
+}
+
+#[test]
+fn check_read_only_db() {
+    let file_name = "test_read_only_db.sqlite";
+    let side_files = [
+        "test_read_only_db.sqlite-wal",
+        "test_read_only_db.sqlite-shm",
+    ];
+    let mut conn = create_test_db(file_name);
+    set_db_pragmas(&conn).unwrap();
+
+    add_tx(
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    checkpoint_db(&conn).unwrap();
+    conn.close().unwrap();
+
+    let mut read_only_conn = open_read_only_db(Path::new(file_name)).unwrap();
+    set_read_only_pragmas(&read_only_conn).unwrap();
+
+    let balances = get_last_balances(&read_only_conn);
+    let add_status = add_tx(
+        "2022-08-20",
+        "Blocked transaction",
+        "test1",
+        "50.00",
+        "Expense",
+        "Food",
+        None,
+        &mut read_only_conn,
+    );
+    let temp_status = read_only_conn.execute("CREATE TEMP TABLE scratch (id INTEGER)", []);
+    let vacuum_status = vacuum_db(&read_only_conn);
+    let total_txs: i64 = read_only_conn
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap();
+    let side_files_created = side_files.iter().any(|file| Path::new(file).exists());
+    read_only_conn.close().unwrap();
+
+    // while another connection is writing, the changes still inside the WAL file are seen
+    let mut conn = Connection::open(file_name).unwrap();
+    set_db_pragmas(&conn).unwrap();
+    add_tx(
+        "2022-08-21",
+        "Testing transaction",
+        "test 2",
+        "20.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let read_only_conn = open_read_only_db(Path::new(file_name)).unwrap();
+    let live_balances = get_last_balances(&read_only_conn);
+    read_only_conn.close().unwrap();
+    conn.close().unwrap();
+
+    let missing_status = open_read_only_db(Path::new("missing_read_only.sqlite"));
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(balances, vec!["100".to_string(), "0".to_string()]);
+    assert!(add_status.is_err());
+    assert!(temp_status.is_err());
+    assert!(vacuum_status.is_err());
+    assert_eq!(total_txs, 1);
+    assert!(!side_files_created);
+    assert_eq!(live_balances, vec!["100".to_string(), "20".to_string()]);
+    assert!(side_files.iter().all(|file| !Path::new(file).exists()));
+    assert!(missing_status.is_err());
+    assert!(!Path::new("missing_read_only.sqlite").exists());
+}
+
+#[test]
+fn check_combined_db() {
+    let personal_name = "test_combined_personal.sqlite";
+    let business_name = "test_combined_business.sqlite";
+
+    let mut personal_conn = create_test_db(personal_name);
+    fs::remove_file(business_name).ok();
+    let mut business_conn = Connection::open(business_name).unwrap();
+    create_db(
+        &["test1".to_string(), "Office".to_string()],
+        &mut business_conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2022-08-19",
+        "Salary",
+        "test1",
+        "100.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut personal_conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-08-25",
+        "Groceries",
+        "test 2",
+        "20.00",
+        "Expense",
+        "Food",
+        None,
+        &mut personal_conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-08-20",
+        "Invoice",
+        "test1",
+        "50.00",
+        "Income",
+        "Work",
+        None,
+        &mut business_conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-09-01",
+        "Rent",
+        "Office",
+        "30.00",
+        "Expense",
+        "Office",
+        None,
+        &mut business_conn,
+    )
+    .unwrap();
+
+    personal_conn.close().unwrap();
+    business_conn.close().unwrap();
+
+    let conn = create_combined_db(&[personal_name.into(), business_name.into()]).unwrap();
+
+    let tx_methods = get_all_tx_methods(&conn);
+    let details = conn
+        .prepare("SELECT details FROM tx_all ORDER BY id_num")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<Vec<String>, _>>()
+        .unwrap();
+    let balances = get_last_balances(&conn);
+
+    fs::remove_file(personal_name).unwrap();
+    fs::remove_file(business_name).unwrap();
+
+    // the tx methods with the same name become one
+    assert_eq!(tx_methods, vec!["test1", "test 2", "Office"]);
+    assert_eq!(details, vec!["Salary", "Invoice", "Groceries", "Rent"]);
+    assert_eq!(balances, vec!["150", "-20", "-30"]);
+}
@@ -1,8 +1,13 @@
 extern crate rex_tui;
-use rex_tui::db::{add_tags_column, update_balance_type};
-use rex_tui::utility::{check_old_balance_sql, get_all_tx_columns, get_last_balance_id};
+use rex_tui::db::{
+    add_tags_column, create_db, is_db_encrypted, migrate_to_tx_indexes, update_balance_type,
+};
+use rex_tui::utility::{
+    check_old_balance_sql, get_all_index_names, get_all_tx_columns, get_last_balance_id,
+};
 use rusqlite::Connection;
 use std::fs;
+use std::path::Path;
 
 fn check_test_db(file_name: &str) {
     if let Ok(metadata) = fs::metadata(file_name) {
@@ -48,7 +53,7 @@ fn check_tags_migration() {
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
 
-    assert_eq!(old_columns.contains(&"tags".to_string()), false);
+    assert!(!old_columns.contains(&"tags".to_string()));
     assert_eq!(new_columns, expected_columns);
 }
 
@@ -68,7 +73,8 @@ fn check_balance_migration() {
     )
     .unwrap();
 
-    let query = format!(r#"INSERT INTO balance_all ("test1", "test 2") VALUES ("0.00", "0.00")"#);
+    let query =
+        r#"INSERT INTO balance_all ("test1", "test 2") VALUES ("0.00", "0.00")"#.to_string();
     for _i in 0..49 {
         conn.execute(&query, []).unwrap();
     }
@@ -88,7 +94,7 @@ fn check_balance_migration() {
         })
         .unwrap();
 
-    let old_db_status = check_old_balance_sql(&mut conn);
+    let old_db_status = check_old_balance_sql(&conn);
     let old_last_balance_id = get_last_balance_id(&conn).unwrap();
 
     update_balance_type(&mut conn).unwrap();
@@ -101,23 +107,88 @@ fn check_balance_migration() {
         })
         .unwrap();
 
-    let db_status = check_old_balance_sql(&mut conn);
+    let db_status = check_old_balance_sql(&conn);
     let last_balance_id = get_last_balance_id(&conn).unwrap();
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
 
-    assert_eq!(old_db_status, true);
+    assert!(old_db_status);
     assert_eq!(old_last_balance_id, 49);
     assert_eq!(
         old_last_balances,
         vec!["200.19".to_string(), "159.19".to_string()]
     );
 
-    assert_eq!(db_status, false);
+    assert!(!db_status);
     assert_eq!(last_balance_id, 193);
     assert_eq!(
         last_balances,
         vec!["200.19".to_string(), "159.19".to_string()]
     );
 }
+
+#[test]
+fn check_db_encryption_detection() {
+    let file_name = "db_update_3.sqlite";
+    check_test_db(file_name);
+    let path = Path::new(file_name);
+
+    assert!(!is_db_encrypted(path));
+
+    let conn = Connection::open(file_name).unwrap();
+    conn.execute("CREATE TABLE test (id INTEGER)", []).unwrap();
+    conn.close().unwrap();
+
+    let plain_status = is_db_encrypted(path);
+
+    // encrypted dbs do not start with the sqlite header
+    fs::write(file_name, [7; 64]).unwrap();
+    let encrypted_status = is_db_encrypted(path);
+
+    fs::remove_file(file_name).unwrap();
+
+    assert!(!plain_status);
+    assert!(encrypted_status);
+}
+
+#[test]
+fn check_tx_indexes_migration() {
+    let file_name = "db_update_4.sqlite";
+    check_test_db(file_name);
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    let new_db_indexes = get_all_index_names(&conn);
+
+    // a db from before the indexes existed
+    for index in [
+        "tx_all_tags_idx",
+        "tx_all_tx_method_idx",
+        "tx_all_date_id_num_idx",
+        "changes_all_date_id_num_idx",
+    ] {
+        conn.execute(&format!("DROP INDEX {index}"), []).unwrap();
+    }
+    let old_db_indexes = get_all_index_names(&conn);
+
+    migrate_to_tx_indexes(&mut conn).unwrap();
+    let migrated_indexes = get_all_index_names(&conn);
+
+    let method_query_plan: String = conn
+        .query_row(
+            "EXPLAIN QUERY PLAN SELECT * FROM tx_all WHERE tx_method = 'test1'",
+            [],
+            |row| row.get(3),
+        )
+        .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(new_db_indexes.contains(&"tx_all_tags_idx".to_string()));
+    assert!(!old_db_indexes.contains(&"tx_all_tx_method_idx".to_string()));
+    assert!(migrated_indexes.contains(&"tx_all_tx_method_idx".to_string()));
+    assert!(migrated_indexes.contains(&"changes_all_date_id_num_idx".to_string()));
+    assert!(method_query_plan.contains("tx_all_tx_method_idx"));
+}
@@ -0,0 +1,84 @@
+extern crate rex_tui;
+use rex_tui::db::create_db;
+use rex_tui::page_handler::{AppState, DbRequest, DbResponse, DbWorker};
+use rex_tui::tx_handler::add_tx;
+use rex_tui::utility::Config;
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn wait_response(worker: &DbWorker) -> DbResponse {
+    for _ in 0..500 {
+        if let Some(response) = worker.try_recv() {
+            return response;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("The db worker did not respond");
+}
+
+#[test]
+fn check_db_worker() {
+    let file_name = "check_db_worker.sqlite".to_string();
+    let mut conn = create_test_db(&file_name);
+    let mut state = AppState::new(&None, &Config::default(), &conn);
+
+    assert!(state.summary.table.items.is_empty());
+    assert!(!state.is_loading());
+
+    add_tx(
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let worker = DbWorker::new(Path::new(&file_name)).unwrap();
+    // All Time mode so the table does not depend on the current month
+    state.summary.modes.index = 2;
+
+    assert!(worker.request(DbRequest::Summary));
+    state.summary.loading = true;
+    assert!(state.is_loading());
+
+    let response = wait_response(&worker);
+    assert!(matches!(response, DbResponse::Summary(_)));
+    state.apply_db_response(response, &conn);
+
+    assert!(!state.is_loading());
+    assert_eq!(state.summary.table.items.len(), 1);
+    assert_eq!(state.summary.table.items[0][0], "Car");
+
+    assert!(worker.request(DbRequest::Chart));
+    state.chart.loading = true;
+
+    let response = wait_response(&worker);
+    assert!(matches!(response, DbResponse::Chart(_)));
+    state.apply_db_response(response, &conn);
+
+    assert!(!state.is_loading());
+    assert!(state.chart.tags.titles.contains(&"Car".to_string()));
+
+    drop(worker);
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
@@ -0,0 +1,186 @@
+extern crate rex_tui;
+use rex_tui::db::{create_db, dump_db, load_dump, set_tx_note};
+use rex_tui::outputs::DumpError;
+use rex_tui::tx_handler::{add_exchange_transfer, add_tx};
+use rex_tui::utility::{get_all_tx_methods, get_last_balances};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str, tx_methods: &[&str]) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let tx_methods = tx_methods
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&tx_methods, &mut conn).unwrap();
+    conn
+}
+
+fn add_test_txs(conn: &mut Connection) {
+    let txs = [
+        ("2022-09-05", "Salary", "test1", "1000.00", "Income", "Job"),
+        ("2022-08-19", "Lunch", "test1", "50.00", "Expense", "Food"),
+        (
+            "2022-10-01",
+            "Rent",
+            "test 2",
+            "120.00",
+            "Expense",
+            "Home, Bills",
+        ),
+    ];
+
+    for (date, details, tx_method, amount, tx_type, tags) in txs {
+        add_tx(date, details, tx_method, amount, tx_type, tags, None, conn).unwrap();
+    }
+
+    add_exchange_transfer(
+        "2022-08-19",
+        "Savings",
+        "test1 to test 2",
+        "200.00",
+        "Unknown",
+        1.5,
+        None,
+        conn,
+    )
+    .unwrap();
+    set_tx_note(2, "Paid for\ttwo\nat the \\ cafe", conn).unwrap();
+}
+
+#[test]
+fn check_dump_round_trip() {
+    let file_name = "dump_round_trip.sqlite";
+    let target_name = "dump_round_trip_target.sqlite";
+
+    let mut conn = create_test_db(file_name, &["test1", "test 2"]);
+    add_test_txs(&mut conn);
+
+    let dump = dump_db(&conn).unwrap();
+
+    // every tx is a single line sorted by the date
+    let tx_lines = dump
+        .lines()
+        .filter(|line| line.starts_with("tx\t"))
+        .collect::<Vec<&str>>();
+    assert_eq!(tx_lines.len(), 4);
+    assert!(tx_lines[0].starts_with("tx\t2022-08-19\tExpense\ttest1\t50.00"));
+    assert!(tx_lines[0].ends_with("Paid for\\ttwo\\nat the \\\\ cafe"));
+    assert!(tx_lines[1].contains("\t1.5\t"));
+    assert!(tx_lines[3].starts_with("tx\t2022-10-01"));
+
+    // the db it is loaded into starts with different tx methods and txs
+    let mut target_conn = create_test_db(target_name, &["test1"]);
+    add_tx(
+        "2021-01-01",
+        "Old",
+        "test1",
+        "5.00",
+        "Expense",
+        "Food",
+        None,
+        &mut target_conn,
+    )
+    .unwrap();
+
+    let total_tx = load_dump(&dump, &mut target_conn).unwrap();
+
+    assert_eq!(total_tx, 4);
+    assert_eq!(
+        get_all_tx_methods(&target_conn),
+        vec!["test1".to_string(), "test 2".to_string()]
+    );
+    assert_eq!(dump_db(&target_conn).unwrap(), dump);
+    assert_eq!(get_last_balances(&target_conn), get_last_balances(&conn));
+
+    // loading the same dump again changes nothing
+    load_dump(&dump, &mut target_conn).unwrap();
+    assert_eq!(dump_db(&target_conn).unwrap(), dump);
+
+    conn.close().unwrap();
+    target_conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(target_name).unwrap();
+}
+
+#[test]
+fn check_dump_is_deterministic() {
+    let file_name = "dump_deterministic.sqlite";
+    let other_name = "dump_deterministic_other.sqlite";
+
+    let mut conn = create_test_db(file_name, &["test1", "test 2"]);
+    add_test_txs(&mut conn);
+
+    // the same txs added in the same order on another device
+    let mut other_conn = create_test_db(other_name, &["test1", "test 2"]);
+    add_test_txs(&mut other_conn);
+
+    assert_eq!(dump_db(&conn).unwrap(), dump_db(&conn).unwrap());
+    assert_eq!(dump_db(&conn).unwrap(), dump_db(&other_conn).unwrap());
+
+    conn.close().unwrap();
+    other_conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(other_name).unwrap();
+}
+
+#[test]
+fn check_invalid_dump() {
+    let file_name = "dump_invalid.sqlite";
+
+    let mut conn = create_test_db(file_name, &["test1"]);
+    add_tx(
+        "2022-08-19",
+        "Lunch",
+        "test1",
+        "50.00",
+        "Expense",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    let dump = dump_db(&conn).unwrap();
+
+    let invalid_date =
+        "# Rex dump v1\nmethod\ttest1\ntx\t19-08-2022\tExpense\ttest1\t5\t\tFood\tLunch\t\n";
+    let invalid_amount =
+        "# Rex dump v1\nmethod\ttest1\ntx\t2022-08-19\tExpense\ttest1\tfive\t\tFood\tLunch\t\n";
+    let missing_field = "# Rex dump v1\nmethod\ttest1\ntx\t2022-08-19\tExpense\ttest1\t5\n";
+    let unknown_method = "# Rex dump v1\nmethod\ttest1\n\ntx\t2022-08-19\tTransfer\ttest1 to test2\t5\t\tFood\tLunch\t\n";
+    let no_method = "# Rex dump v1\n";
+
+    assert!(matches!(
+        load_dump(invalid_date, &mut conn),
+        Err(DumpError::InvalidLine(3))
+    ));
+    assert!(matches!(
+        load_dump(invalid_amount, &mut conn),
+        Err(DumpError::InvalidLine(3))
+    ));
+    assert!(matches!(
+        load_dump(missing_field, &mut conn),
+        Err(DumpError::InvalidLine(3))
+    ));
+    assert!(matches!(
+        load_dump(unknown_method, &mut conn),
+        Err(DumpError::UnknownTxMethod(4, method)) if method == "test2"
+    ));
+    assert!(matches!(
+        load_dump(no_method, &mut conn),
+        Err(DumpError::NoTxMethod)
+    ));
+
+    // nothing was changed by the failed loads
+    assert_eq!(dump_db(&conn).unwrap(), dump);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
@@ -0,0 +1,165 @@
+extern crate rex_tui;
+use rex_tui::db::create_db;
+use rex_tui::events::{EventHook, EventTx, RexEvent};
+use rex_tui::tx_handler::add_tx;
+use rex_tui::utility::Config;
+use rusqlite::Connection;
+use serde_json::{json, Value};
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+#[test]
+fn check_event_messages() {
+    let file_name = "check_event_messages.sqlite".to_string();
+    let mut conn = create_test_db(&file_name);
+
+    add_tx(
+        "2022-08-19",
+        "Transfer",
+        "test1 to test 2",
+        "100.00",
+        "Transfer",
+        "Savings, Monthly",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let expected_tx = EventTx {
+        id: 1,
+        date: "2022-08-19".to_string(),
+        details: "Transfer".to_string(),
+        tx_method: "test1 to test 2".to_string(),
+        amount: 100.0,
+        tx_type: "Transfer".to_string(),
+        tags: vec!["Savings".to_string(), "Monthly".to_string()],
+    };
+
+    assert_eq!(
+        RexEvent::tx_added(1, &conn),
+        RexEvent::TxAdded {
+            tx: expected_tx.clone()
+        }
+    );
+    assert_eq!(
+        RexEvent::last_tx_added(&conn),
+        RexEvent::TxAdded { tx: expected_tx }
+    );
+
+    assert_eq!(
+        RexEvent::month_closed(1),
+        RexEvent::MonthClosed {
+            month: "2022-01".to_string()
+        }
+    );
+    assert_eq!(
+        RexEvent::month_closed(29),
+        RexEvent::MonthClosed {
+            month: "2024-05".to_string()
+        }
+    );
+
+    let hook = EventHook::new(&Config::default(), "Work");
+
+    let message: Value =
+        serde_json::from_str(&hook.get_message(&RexEvent::tx_added(1, &conn))).unwrap();
+    assert_eq!(message["event"], "tx_added");
+    assert_eq!(message["profile"], "Work");
+    assert!(message["time"].is_string());
+    assert_eq!(
+        message["tx"],
+        json!({
+            "id": 1,
+            "date": "2022-08-19",
+            "details": "Transfer",
+            "tx_method": "test1 to test 2",
+            "amount": 100.0,
+            "tx_type": "Transfer",
+            "tags": ["Savings", "Monthly"]
+        })
+    );
+
+    let message: Value = serde_json::from_str(&hook.get_message(&RexEvent::TxDeleted {
+        id_nums: vec![1, 2],
+    }))
+    .unwrap();
+    assert_eq!(message["event"], "tx_deleted");
+    assert_eq!(message["id_nums"], json!([1, 2]));
+
+    // nothing is set so there is nowhere to send it
+    assert!(hook
+        .emit(&RexEvent::BackupDone {
+            paths: vec!["backup.sqlite".to_string()]
+        })
+        .is_ok());
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn check_event_script() {
+    use std::time::{Duration, Instant};
+
+    // the script exits without reading anything
+    let config = Config {
+        event_hook_script: "true".to_string(),
+        ..Config::default()
+    };
+    let hook = EventHook::new(&config, "Default");
+
+    // far larger than the pipe can hold
+    let event = RexEvent::TxDeleted {
+        id_nums: (0..100_000).collect(),
+    };
+    assert!(hook.get_message(&event).len() > 500_000);
+
+    let start = Instant::now();
+    assert!(hook.emit(&event).is_ok());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(unix)]
+#[test]
+fn check_event_socket() {
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::net::UnixListener;
+
+    let socket_path = "check_event_socket.sock";
+    fs::remove_file(socket_path).ok();
+    let listener = UnixListener::bind(socket_path).unwrap();
+
+    let config = Config {
+        event_hook_socket: socket_path.to_string(),
+        ..Config::default()
+    };
+    let hook = EventHook::new(&config, "Default");
+
+    hook.emit(&RexEvent::month_closed(13)).unwrap();
+
+    let (stream, _) = listener.accept().unwrap();
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).unwrap();
+
+    let message: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(message["event"], "month_closed");
+    assert_eq!(message["month"], "2023-01");
+    assert_eq!(message["profile"], "Default");
+
+    fs::remove_file(socket_path).unwrap();
+
+    // the socket is gone so the event cannot be sent
+    assert!(hook.emit(&RexEvent::month_closed(13)).is_err());
+}
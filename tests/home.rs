@@ -1,9 +1,14 @@
 extern crate rex_tui;
+use chrono::NaiveDate;
 use rex_tui::db::*;
-use rex_tui::home_page::TransactionData;
+use rex_tui::home_page::{
+    get_day_groups, HomeFilter, TransactionData, TX_WINDOW_SIZE, UNTAGGED_FILTER,
+};
+use rex_tui::page_handler::{HomeSortingType, SortingDirection, TxStatus};
 use rex_tui::tx_handler::add_tx;
 use rex_tui::utility::get_all_txs;
 use rusqlite::Connection;
+use std::collections::HashSet;
 use std::fs;
 
 fn create_test_db(file_name: &str) -> Connection {
@@ -14,7 +19,7 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
@@ -59,17 +64,17 @@ fn add_dummy_tx(conn: &mut Connection) {
 #[test]
 fn test_home_data() {
     let file_name = "home_data_1.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
     add_dummy_tx(&mut conn);
 
-    let tx_data_1 = TransactionData::new(1, 1, &mut conn);
-    let tx_data_2 = TransactionData::new(6, 1, &mut conn);
+    let tx_data_1 = TransactionData::new(1, 1, &conn);
+    let tx_data_2 = TransactionData::new(6, 1, &conn);
 
     let is_tx_empty_1 = tx_data_1.is_tx_empty();
     let is_tx_empty_2 = tx_data_2.is_tx_empty();
 
-    assert_eq!(is_tx_empty_1, true);
-    assert_eq!(is_tx_empty_2, false);
+    assert!(is_tx_empty_1);
+    assert!(!is_tx_empty_2);
 
     let all_tx_1 = tx_data_1.get_txs();
     let all_tx_2 = tx_data_2.get_txs();
@@ -77,7 +82,7 @@ fn test_home_data() {
     let expected_data_1: Vec<Vec<String>> = Vec::new();
 
     let tx_1 = vec![
-        "19-07-2023",
+        "2023-07-19",
         "Testing transaction",
         "test 2",
         "100.00",
@@ -88,7 +93,7 @@ fn test_home_data() {
     .map(|a| a.to_string())
     .collect();
     let tx_2 = vec![
-        "25-07-2023",
+        "2023-07-25",
         "Testing transaction",
         "test1",
         "200.00",
@@ -189,9 +194,9 @@ fn test_home_data() {
 
     tx_data_2.del_tx(0, &mut conn).unwrap();
 
-    let tx_data_2 = TransactionData::new(6, 1, &mut conn);
+    let tx_data_2 = TransactionData::new(6, 1, &conn);
 
-    assert_eq!(tx_data_2.is_tx_empty(), false);
+    assert!(!tx_data_2.is_tx_empty());
     assert_eq!(tx_data_2.get_txs().len(), 1);
 
     let txs = get_all_txs(&conn, 6, 1);
@@ -202,3 +207,591 @@ fn test_home_data() {
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
 }
+
+#[test]
+fn check_home_sorting() {
+    let file_name = "home_data_2.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2023-07-20",
+        "Bus ticket",
+        "test1",
+        "50.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+
+    let get_details = |tx_data: &TransactionData| {
+        tx_data
+            .get_txs()
+            .iter()
+            .map(|tx| format!("{} {}", tx[0], tx[3]))
+            .collect::<Vec<String>>()
+    };
+
+    tx_data.sort_txs(&HomeSortingType::ByAmount, &SortingDirection::Descending);
+    let amount_sorted = get_details(&tx_data);
+
+    // The balance, changes and id follow the tx to its new position
+    let balance = tx_data.get_balance(2);
+    let changes = tx_data.get_changes(2);
+    let id_num = tx_data.get_id_num(2);
+    let position = tx_data.get_tx_position(2);
+
+    tx_data.sort_txs(&HomeSortingType::ByTags, &SortingDirection::Ascending);
+    let tag_sorted = get_details(&tx_data);
+
+    tx_data.sort_txs(&HomeSortingType::ByDate, &SortingDirection::Descending);
+    let date_sorted = get_details(&tx_data);
+
+    tx_data.sort_txs(&HomeSortingType::ByDate, &SortingDirection::Ascending);
+    let chronological = get_details(&tx_data);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        amount_sorted,
+        vec!["2023-07-25 200.00", "2023-07-19 100.00", "2023-07-20 50.00"]
+    );
+    assert_eq!(balance, vec!["Balance", "-150.00", "-100.00", "-250.00"]);
+    assert_eq!(changes, vec!["Changes", "↓50.00", "0.00"]);
+    assert_eq!(id_num, 4);
+    assert_eq!(position, Some(1));
+
+    assert_eq!(
+        tag_sorted,
+        vec!["2023-07-20 50.00", "2023-07-19 100.00", "2023-07-25 200.00"]
+    );
+    assert_eq!(
+        date_sorted,
+        vec!["2023-07-25 200.00", "2023-07-20 50.00", "2023-07-19 100.00"]
+    );
+    assert_eq!(
+        chronological,
+        vec!["2023-07-19 100.00", "2023-07-20 50.00", "2023-07-25 200.00"]
+    );
+}
+
+#[test]
+fn check_home_filter() {
+    let file_name = "home_data_3.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2023-07-20",
+        "Bus ticket",
+        "test1",
+        "50.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let mut home_filter = HomeFilter::new(&conn);
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+
+    let get_dates = |tx_data: &mut TransactionData, home_filter: &HomeFilter| {
+        tx_data.filter_txs(home_filter);
+        tx_data.sort_txs(&HomeSortingType::ByDate, &SortingDirection::Ascending);
+        tx_data
+            .get_txs()
+            .iter()
+            .map(|tx| tx[0].to_string())
+            .collect::<Vec<String>>()
+    };
+
+    // the filters do nothing while the bar is hidden
+    home_filter.tags.index = 1;
+    let hidden_bar = get_dates(&mut tx_data, &home_filter);
+
+    home_filter.shown = true;
+    let car_txs = get_dates(&mut tx_data, &home_filter);
+    let full_balance = tx_data.get_balance(0);
+
+    home_filter.filter_balance = true;
+    get_dates(&mut tx_data, &home_filter);
+    let filtered_balance = tx_data.get_balance(0);
+    let filtered_expense = tx_data.get_total_expense(None, &conn);
+
+    home_filter.tags.index = 0;
+    home_filter.tx_methods.index = 2;
+    let method_txs = get_dates(&mut tx_data, &home_filter);
+
+    home_filter.tx_methods.index = 0;
+    home_filter.tx_types.index = 1;
+    let income_txs = get_dates(&mut tx_data, &home_filter);
+
+    home_filter.tx_types.index = 2;
+    home_filter.tags.index = 2;
+    let food_expense_txs = get_dates(&mut tx_data, &home_filter);
+
+    add_tx(
+        "2023-07-22",
+        "Parking",
+        "test1",
+        "5.00",
+        "Expense",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+
+    home_filter.tx_types.index = 0;
+    home_filter.tags.index = 3;
+    let untagged_txs = get_dates(&mut tx_data, &home_filter);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        home_filter.tags.titles,
+        vec!["All", "Car", "Food", UNTAGGED_FILTER]
+    );
+    assert_eq!(hidden_bar, vec!["2023-07-19", "2023-07-20", "2023-07-25"]);
+    assert_eq!(car_txs, vec!["2023-07-20"]);
+    assert_eq!(
+        full_balance,
+        vec!["Balance", "-150.00", "-100.00", "-250.00"]
+    );
+    assert_eq!(
+        filtered_balance,
+        vec!["Balance", "-150.00", "0.00", "-150.00"]
+    );
+    assert_eq!(filtered_expense, vec!["Expense", "50.00", "0.00", "50.00"]);
+    assert_eq!(method_txs, vec!["2023-07-19"]);
+    assert_eq!(income_txs, vec!["2023-07-25"]);
+    assert_eq!(food_expense_txs, vec!["2023-07-19"]);
+    assert_eq!(untagged_txs, vec!["2023-07-22"]);
+}
+
+#[test]
+fn check_home_lazy_loading() {
+    let file_name = "home_data_4.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let total_txs = TX_WINDOW_SIZE + 30;
+
+    for i in 0..total_txs {
+        add_tx(
+            &format!("2023-07-{:02}", i % 28 + 1),
+            "Testing transaction",
+            "test1",
+            &format!("{}.00", i + 1),
+            "Expense",
+            "Food",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+
+    let first_window = tx_data.get_txs().len();
+    let fully_loaded = tx_data.is_fully_loaded();
+    let window_expense = tx_data.get_total_expense(None, &conn);
+    let window_balance = tx_data.get_balance(TX_WINDOW_SIZE - 1);
+
+    let loaded_more = tx_data.load_more(&conn);
+    let loaded_again = tx_data.load_more(&conn);
+
+    let (all_txs, all_balance, all_id_num) = get_all_txs(&conn, 6, 1);
+
+    let full_expense = tx_data.get_total_expense(None, &conn);
+    let last_balance = tx_data.get_balance(total_txs - 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // 1 + 2 + ... + total_txs
+    let total_expense = format!("{:.2}", (total_txs * (total_txs + 1) / 2) as f64);
+
+    assert_eq!(first_window, TX_WINDOW_SIZE);
+    assert!(!fully_loaded);
+    assert_eq!(tx_data.get_total_txs(), total_txs);
+    assert_eq!(
+        window_expense,
+        vec!["Expense", &total_expense, "0.00", &total_expense]
+    );
+    assert_eq!(window_balance[1], all_balance[TX_WINDOW_SIZE - 1][0]);
+
+    assert!(loaded_more);
+    assert!(!loaded_again);
+    assert!(tx_data.is_fully_loaded());
+    assert_eq!(tx_data.get_txs(), all_txs);
+    assert_eq!(full_expense, window_expense);
+    assert_eq!(last_balance[1], all_balance[total_txs - 1][0]);
+    assert_eq!(
+        tx_data.get_id_num(total_txs - 1),
+        all_id_num[total_txs - 1].parse::<i32>().unwrap()
+    );
+}
+
+#[test]
+fn check_home_date_position() {
+    let file_name = "home_data_5.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+    let date = |day| NaiveDate::from_ymd_opt(2023, 7, day).unwrap();
+
+    let exact_day = tx_data.get_date_position(date(19));
+    let next_day = tx_data.get_date_position(date(20));
+    let no_tx = tx_data.get_date_position(date(26));
+
+    // the position follows the sorted table
+    tx_data.sort_txs(&HomeSortingType::ByDate, &SortingDirection::Descending);
+    let sorted_position = tx_data.get_date_position(date(1));
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(exact_day, Some(0));
+    assert_eq!(next_day, Some(1));
+    assert_eq!(no_tx, None);
+    assert_eq!(sorted_position, Some(1));
+}
+
+#[test]
+fn check_home_running_balance() {
+    let file_name = "home_data_6.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2023-07-20",
+        "Bus ticket",
+        "test1",
+        "50.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+
+    let chronological_balance = tx_data.get_running_balances(0);
+    let chronological_total = tx_data.get_running_balances(2);
+
+    tx_data.sort_txs(&HomeSortingType::ByAmount, &SortingDirection::Descending);
+    let sorted_balance = tx_data.get_running_balances(0);
+    let sorted_matches_widget = (0..3).all(|index| {
+        (0..3).all(|column| {
+            tx_data.get_running_balances(column)[index] == tx_data.get_balance(index)[column + 1]
+        })
+    });
+
+    let mut home_filter = HomeFilter::new(&conn);
+    home_filter.shown = true;
+    home_filter.filter_balance = true;
+    home_filter.tags.index = 1;
+    tx_data.filter_txs(&home_filter);
+    tx_data.sort_txs(&HomeSortingType::ByDate, &SortingDirection::Ascending);
+    let filtered_total = tx_data.get_running_balances(2);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(chronological_balance, vec!["-100.00", "-150.00", "50.00"]);
+    assert_eq!(chronological_total, vec!["-200.00", "-250.00", "-50.00"]);
+    assert_eq!(sorted_balance, vec!["50.00", "-100.00", "-150.00"]);
+    assert!(sorted_matches_widget);
+    assert_eq!(filtered_total, vec!["-150.00"]);
+}
+
+#[test]
+fn check_home_weekly() {
+    let file_name = "home_data_7.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2023-07-20",
+        "Bus ticket",
+        "test1",
+        "50.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+    let week_1 = TransactionData::new_weekly(date(2023, 7, 17), &conn);
+    let week_1_dates = week_1
+        .get_txs()
+        .iter()
+        .map(|tx| tx[0].to_string())
+        .collect::<Vec<String>>();
+    let week_1_expense = week_1.get_total_expense(None, &conn);
+
+    // the balance of the week starts from the txs of the month before the week
+    let week_2 = TransactionData::new_weekly(date(2023, 7, 24), &conn);
+    let week_2_total = week_2.get_total_txs();
+    let week_2_balance = week_2.get_balance(0);
+
+    let empty_week = TransactionData::new_weekly(date(2023, 7, 3), &conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(week_1_dates, vec!["2023-07-19", "2023-07-20"]);
+    assert_eq!(week_1_expense, vec!["Expense", "50.00", "100.00", "150.00"]);
+    assert_eq!(week_2_total, 1);
+    assert_eq!(
+        week_2_balance,
+        vec!["Balance", "50.00", "-100.00", "-50.00"]
+    );
+    assert!(empty_week.is_tx_empty());
+}
+
+#[test]
+fn check_home_date_range() {
+    let file_name = "home_data_8.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    for (date, tx_method, amount, tx_type) in [
+        ("2023-07-20", "test1", "50.00", "Expense"),
+        ("2023-08-02", "test 2", "30.00", "Income"),
+    ] {
+        add_tx(
+            date,
+            "Testing transaction",
+            tx_method,
+            amount,
+            tx_type,
+            "Car",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+    let tx_data = TransactionData::new_range(date(2023, 7, 20), date(2023, 8, 9), &conn);
+    let dates = tx_data
+        .get_txs()
+        .iter()
+        .map(|tx| tx[0].to_string())
+        .collect::<Vec<String>>();
+    let first_balance = tx_data.get_balance(0);
+    let final_balance = tx_data.get_balance(2);
+    let income = tx_data.get_total_income(None, &conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(dates, vec!["2023-07-20", "2023-07-25", "2023-08-02"]);
+    assert_eq!(
+        first_balance,
+        vec!["Balance", "-150.00", "-100.00", "-250.00"]
+    );
+    assert_eq!(final_balance, vec!["Balance", "50.00", "-70.00", "-20.00"]);
+    assert_eq!(income, vec!["Income", "200.00", "30.00", "230.00"]);
+}
+
+#[test]
+fn check_home_text_filter() {
+    let file_name = "home_data_9.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2023-07-20",
+        "Bus ticket",
+        "test1",
+        "50.00",
+        "Expense",
+        "Travel",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let mut home_filter = HomeFilter::new(&conn);
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+
+    let mut get_dates = |home_filter: &HomeFilter| {
+        tx_data.filter_txs(home_filter);
+        tx_data.sort_txs(&HomeSortingType::ByDate, &SortingDirection::Ascending);
+        tx_data
+            .get_txs()
+            .iter()
+            .map(|tx| tx[0].to_string())
+            .collect::<Vec<String>>()
+    };
+
+    // the text filter works without the filter bar and ignores the case
+    home_filter.text = "BUS".to_string();
+    let details_match = get_dates(&home_filter);
+
+    home_filter.text = "foo".to_string();
+    let tags_match = get_dates(&home_filter);
+
+    // stacks with the filter bar
+    home_filter.shown = true;
+    home_filter.tx_types.index = 1;
+    let with_bar = get_dates(&home_filter);
+
+    home_filter.shown = false;
+    home_filter.text.clear();
+    let cleared = get_dates(&home_filter);
+    let active_after_clear = home_filter.is_active();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(details_match, vec!["2023-07-20"]);
+    assert_eq!(tags_match, vec!["2023-07-19", "2023-07-25"]);
+    assert_eq!(with_bar, vec!["2023-07-25"]);
+    assert_eq!(cleared, vec!["2023-07-19", "2023-07-20", "2023-07-25"]);
+    assert!(!active_after_clear);
+}
+
+#[test]
+fn check_home_day_groups() {
+    let items = [
+        "2023-07-19",
+        "2023-07-19",
+        "2023-07-20",
+        "2023-07-25",
+        "2023-07-25",
+    ]
+    .into_iter()
+    .map(|date| vec![date.to_string()])
+    .collect::<Vec<Vec<String>>>();
+
+    assert_eq!(get_day_groups(&items), vec![0..2, 2..3, 3..5]);
+    assert!(get_day_groups(&[]).is_empty());
+
+    // the same day is only grouped while the rows are next to each other
+    let unsorted = ["2023-07-19", "2023-07-20", "2023-07-19"]
+        .into_iter()
+        .map(|date| vec![date.to_string()])
+        .collect::<Vec<Vec<String>>>();
+
+    assert_eq!(get_day_groups(&unsorted), vec![0..1, 1..2, 2..3]);
+}
+
+#[test]
+fn check_home_cleared_balance() {
+    let file_name = "home_data_10.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+    let statuses = tx_data.get_statuses();
+
+    tx_data.set_status(1, TxStatus::Cleared, &conn).unwrap();
+    tx_data.set_status(0, TxStatus::Reconciled, &conn).unwrap();
+    tx_data.set_status(0, TxStatus::Pending, &conn).unwrap();
+
+    // the status is saved in the db so it stays after fetching the txs again
+    let mut tx_data = TransactionData::new(6, 1, &conn);
+    let reloaded_statuses = tx_data.get_statuses();
+    let pending_status = get_tx_status(2, &conn);
+
+    tx_data.set_cleared_balance(true, &conn);
+    let cleared_balance = tx_data.get_balance(1);
+    let cleared_last_balance = tx_data.get_last_balance(&conn);
+    let cleared_income = tx_data.get_total_income(None, &conn);
+    let cleared_expense = tx_data.get_total_expense(None, &conn);
+
+    tx_data.set_cleared_balance(false, &conn);
+    let balance = tx_data.get_balance(1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(statuses, vec![TxStatus::Pending, TxStatus::Pending]);
+    assert_eq!(
+        reloaded_statuses,
+        vec![TxStatus::Pending, TxStatus::Cleared]
+    );
+    assert_eq!(pending_status, TxStatus::Pending);
+
+    // the pending txs of the earlier months are also left out
+    assert_eq!(cleared_balance, vec!["Balance", "200.00", "0.00", "200.00"]);
+    assert_eq!(
+        cleared_last_balance,
+        vec!["Balance", "200.00", "0.00", "200.00"]
+    );
+    assert_eq!(cleared_income, vec!["Income", "200.00", "0.00", "200.00"]);
+    assert_eq!(cleared_expense, vec!["Expense", "0.00", "0.00", "0.00"]);
+    assert_eq!(balance, vec!["Balance", "100.00", "-100.00", "0.00"]);
+}
+
+#[test]
+fn check_home_future_txs() {
+    let file_name = "home_data_11.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2037-01-10",
+        "Testing transaction",
+        "test1",
+        "50.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+    let mut tx_data = TransactionData::new_range(date(2023, 7, 1), date(2037, 1, 31), &conn);
+    tx_data.load_all(&conn);
+
+    // the future tx has the smallest amount but still stays at the end
+    tx_data.sort_txs(&HomeSortingType::ByAmount, &SortingDirection::Ascending);
+    let amounts = tx_data
+        .get_txs()
+        .iter()
+        .map(|tx| tx[3].to_string())
+        .collect::<Vec<String>>();
+    let future_positions = tx_data.get_future_positions();
+
+    let last_balance = tx_data.get_last_balance(&conn);
+    let projected_balance = tx_data.get_projected_balance(&conn);
+
+    let past_data = TransactionData::new(6, 1, &conn);
+    let past_future_positions = past_data.get_future_positions();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(amounts, vec!["100.00", "200.00", "50.00"]);
+    assert_eq!(future_positions, HashSet::from([2]));
+    assert_eq!(last_balance, vec!["Balance", "100.00", "-100.00", "0.00"]);
+    assert_eq!(
+        projected_balance,
+        Some(
+            ["Balance", "50.00", "-100.00", "-50.00"]
+                .map(ToString::to_string)
+                .to_vec()
+        )
+    );
+    assert!(past_future_positions.is_empty());
+}
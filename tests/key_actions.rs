@@ -0,0 +1,255 @@
+extern crate rex_tui;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use rex_tui::db::create_db;
+use rex_tui::key_checker::{get_action, Action, InputKeyHandler};
+use rex_tui::outputs::HandlingOutput;
+use rex_tui::page_handler::{AppState, CurrentUi, Page, PageContext, Pages, PopupState, TxTab};
+use rex_tui::utility::Config;
+use rusqlite::Connection;
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn check_page_actions() {
+    let file_name = "check_page_actions.sqlite".to_string();
+    let conn = create_test_db(&file_name);
+    let mut state = AppState::new(&None, &Config::default(), &conn);
+    let pages = Pages::with_extensions(Vec::new());
+
+    assert!(matches!(state.page, CurrentUi::Initial));
+    assert_eq!(
+        get_action(key(KeyCode::Char('q')), &state, &pages),
+        Some(Action::Quit)
+    );
+    assert_eq!(
+        get_action(key(KeyCode::Char('x')), &state, &pages),
+        Some(Action::GoHome)
+    );
+
+    state.page = CurrentUi::Home;
+    assert_eq!(
+        get_action(key(KeyCode::Char('a')), &state, &pages),
+        Some(Action::GoAddTx)
+    );
+    assert_eq!(
+        get_action(key(KeyCode::Up), &state, &pages),
+        Some(Action::Up)
+    );
+    assert_eq!(get_action(key(KeyCode::F(1)), &state, &pages), None);
+
+    state.home.filter.typing = true;
+    assert_eq!(
+        get_action(key(KeyCode::Char('a')), &state, &pages),
+        Some(Action::HomeFilterInput)
+    );
+
+    state.page = CurrentUi::AddTx;
+    assert_eq!(
+        get_action(key(KeyCode::Char('s')), &state, &pages),
+        Some(Action::AddTx)
+    );
+    assert_eq!(
+        get_action(key(KeyCode::Char('3')), &state, &pages),
+        Some(Action::SelectField)
+    );
+
+    state.add_tx.tab = TxTab::Details;
+    assert_eq!(
+        get_action(key(KeyCode::Char('s')), &state, &pages),
+        Some(Action::FieldInput)
+    );
+    assert_eq!(
+        get_action(
+            KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL),
+            &state,
+            &pages
+        ),
+        Some(Action::UndoFieldEdit)
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_popup_actions() {
+    let file_name = "check_popup_actions.sqlite".to_string();
+    let conn = create_test_db(&file_name);
+    let mut state = AppState::new(&None, &Config::default(), &conn);
+    let pages = Pages::with_extensions(Vec::new());
+    state.page = CurrentUi::Home;
+
+    state.popup = PopupState::HomeHelp;
+    assert_eq!(
+        get_action(key(KeyCode::Down), &state, &pages),
+        Some(Action::ScrollPopupDown)
+    );
+    assert_eq!(
+        get_action(key(KeyCode::Char('a')), &state, &pages),
+        Some(Action::ClosePopup)
+    );
+
+    state.popup = PopupState::TxDeletion;
+    assert_eq!(
+        get_action(key(KeyCode::Enter), &state, &pages),
+        Some(Action::PopupInput)
+    );
+    assert_eq!(get_action(key(KeyCode::Char('a')), &state, &pages), None);
+
+    state.popup = PopupState::Maintenance(String::new());
+    assert_eq!(
+        get_action(key(KeyCode::Char('c')), &state, &pages),
+        Some(Action::PopupWrite)
+    );
+    assert_eq!(
+        get_action(key(KeyCode::Char('b')), &state, &pages),
+        Some(Action::PopupInput)
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_db_writing_actions() {
+    assert!(Action::AddTx.writes_db());
+    assert!(Action::DeletionPopup.writes_db());
+    assert!(Action::RestoreTrashTx.writes_db());
+    assert!(Action::PopupWrite.writes_db());
+    assert!(Action::PageWrite.writes_db());
+
+    assert!(!Action::GoHome.writes_db());
+    assert!(!Action::PopupInput.writes_db());
+    assert!(!Action::PageInput.writes_db());
+    assert!(!Action::GoPage(0).writes_db());
+    assert!(!Action::ShowHomeTxDetails.writes_db());
+}
+
+/// Counts how many times it was opened and got a key
+struct CounterPage {
+    opened: Rc<Cell<usize>>,
+    keys: Rc<Cell<usize>>,
+}
+
+impl Page for CounterPage {
+    fn name(&self) -> &str {
+        "Counter"
+    }
+
+    fn draw(&mut self, _f: &mut Frame, _state: &mut AppState, _context: &PageContext) {}
+
+    fn get_action(&self, key: KeyEvent, _state: &AppState) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('q') => Some(Action::Quit),
+            KeyCode::Char('+') => Some(Action::PageInput),
+            KeyCode::Char('s') => Some(Action::PageWrite),
+            _ => None,
+        }
+    }
+
+    fn open(&mut self, _state: &mut AppState, _conn: &Connection) {
+        self.opened.set(self.opened.get() + 1);
+    }
+
+    fn handle_key(
+        &mut self,
+        _key: KeyEvent,
+        _state: &mut AppState,
+        _conn: &mut Connection,
+    ) -> Option<HandlingOutput> {
+        self.keys.set(self.keys.get() + 1);
+        None
+    }
+}
+
+#[test]
+fn check_extension_pages() {
+    let file_name = "check_extension_pages.sqlite".to_string();
+    let mut conn = create_test_db(&file_name);
+    let config = Config::default();
+    let mut state = AppState::new(&None, &config, &conn);
+
+    let opened = Rc::new(Cell::new(0));
+    let keys = Rc::new(Cell::new(0));
+    let mut pages = Pages::with_extensions(vec![Box::new(CounterPage {
+        opened: opened.clone(),
+        keys: keys.clone(),
+    })]);
+
+    assert_eq!(pages.total_extensions(), 1);
+    assert_eq!(pages.get(&CurrentUi::Summary).name(), "Summary");
+    assert_eq!(pages.get(&CurrentUi::Extension(0)).name(), "Counter");
+    // an extension that was not built falls back to Home
+    assert_eq!(pages.get(&CurrentUi::Extension(1)).name(), "Home");
+
+    state.page = CurrentUi::Home;
+    assert_eq!(
+        get_action(key(KeyCode::F(1)), &state, &pages),
+        Some(Action::GoPage(0))
+    );
+    assert_eq!(get_action(key(KeyCode::F(2)), &state, &pages), None);
+
+    let db_path = Path::new(&file_name);
+    let profiles = ["Default".to_string()];
+
+    for (action, read_only) in [
+        (Action::GoPage(0), false),
+        (Action::PageInput, false),
+        (Action::PageWrite, false),
+        (Action::PageWrite, true),
+        (Action::PageInput, true),
+    ] {
+        InputKeyHandler::new(
+            key(KeyCode::Char('+')),
+            &mut state,
+            &config,
+            "Default",
+            &profiles,
+            db_path,
+            read_only,
+            None,
+            &mut pages,
+            &mut conn,
+        )
+        .reduce(action);
+    }
+
+    assert!(matches!(state.page, CurrentUi::Extension(0)));
+    assert!(matches!(state.popup, PopupState::ReadOnly));
+    assert_eq!(opened.get(), 1);
+    // the write in read only mode is ignored
+    assert_eq!(keys.get(), 3);
+
+    state.popup = PopupState::Nothing;
+    assert_eq!(
+        get_action(key(KeyCode::Char('+')), &state, &pages),
+        Some(Action::PageInput)
+    );
+    assert_eq!(
+        get_action(key(KeyCode::Char('q')), &state, &pages),
+        Some(Action::Quit)
+    );
+    assert_eq!(get_action(key(KeyCode::Char('x')), &state, &pages), None);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
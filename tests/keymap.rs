@@ -0,0 +1,128 @@
+extern crate rex_tui;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rex_tui::key_checker::KeyBinding;
+use rex_tui::outputs::{HandlingOutput, KeymapError};
+use rex_tui::page_handler::CurrentUi;
+use rex_tui::testing::{create_sample_db, TestApp};
+
+#[test]
+fn check_key_binding_parsing() {
+    let ctrl = KeyModifiers::CONTROL;
+    let none = KeyModifiers::NONE;
+
+    assert_eq!(
+        KeyBinding::parse("a"),
+        Some(KeyBinding::new(KeyCode::Char('a'), none))
+    );
+    assert_eq!(
+        KeyBinding::parse("A"),
+        Some(KeyBinding::new(KeyCode::Char('A'), none))
+    );
+    assert_eq!(
+        KeyBinding::parse(" Ctrl+n "),
+        Some(KeyBinding::new(KeyCode::Char('n'), ctrl))
+    );
+    assert_eq!(
+        KeyBinding::parse("ctrl+alt+Enter"),
+        Some(KeyBinding::new(KeyCode::Enter, ctrl | KeyModifiers::ALT))
+    );
+    assert_eq!(
+        KeyBinding::parse("space"),
+        Some(KeyBinding::new(KeyCode::Char(' '), none))
+    );
+    assert_eq!(
+        KeyBinding::parse("F5"),
+        Some(KeyBinding::new(KeyCode::F(5), none))
+    );
+    assert_eq!(
+        KeyBinding::parse("f"),
+        Some(KeyBinding::new(KeyCode::Char('f'), none))
+    );
+
+    // shift is part of the character
+    assert_eq!(
+        KeyBinding::from(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+        KeyBinding::new(KeyCode::Char('A'), none)
+    );
+
+    assert_eq!(KeyBinding::parse(""), None);
+    assert_eq!(KeyBinding::parse("ctrl+"), None);
+    assert_eq!(KeyBinding::parse("ab"), None);
+    assert_eq!(KeyBinding::parse("f13"), None);
+}
+
+#[test]
+fn check_swapped_keys() {
+    let mut app = TestApp::new(create_sample_db());
+    let errors = app.set_key_bindings(&[("GoAddTx", "n"), ("ChangeBalanceColumn", "a")]);
+    assert!(errors.is_empty());
+
+    app.press_keys(&[KeyCode::Enter, KeyCode::Char('a')]);
+    assert!(matches!(app.state.page, CurrentUi::Home));
+
+    app.press_keys(&[KeyCode::Char('n')]);
+    assert!(matches!(app.state.page, CurrentUi::AddTx));
+}
+
+#[test]
+fn check_moved_key_while_typing() {
+    let mut app = TestApp::new(create_sample_db());
+    let errors = app.set_key_bindings(&[("Quit", "ctrl+q")]);
+    assert!(errors.is_empty());
+
+    // the default key no longer quits
+    assert!(app
+        .press_keys(&[KeyCode::Enter, KeyCode::Char('q')])
+        .is_none());
+    assert!(matches!(app.state.page, CurrentUi::Home));
+
+    // but it is still typed to the input fields
+    app.press_keys(&[KeyCode::Char('a'), KeyCode::Char('2')]);
+    app.type_text("quiz");
+    assert_eq!(app.state.add_tx.data.get_all_texts()[1], "quiz");
+
+    app.press_keys(&[KeyCode::Esc]);
+    assert!(matches!(
+        app.press(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL)),
+        Some(HandlingOutput::QuitUi)
+    ));
+}
+
+#[test]
+fn check_keymap_errors() {
+    let mut app = TestApp::new(create_sample_db());
+
+    let errors = app.set_key_bindings(&[
+        ("Foo", "a"),
+        ("GoAddTx", "ctrl+"),
+        ("GoChart", "F2"),
+        ("ToggleHomeWeekly", "x"),
+        ("GoTrash", "Z"),
+        ("MaintenancePopup", "Z"),
+    ]);
+
+    assert_eq!(
+        errors,
+        vec![
+            KeymapError::UnknownAction("Foo".to_string()),
+            KeymapError::InvalidKey("GoAddTx".to_string(), "ctrl+".to_string()),
+            KeymapError::ExtensionKey("GoChart".to_string(), "F2".to_string()),
+            KeymapError::KeyShared(
+                "Z".to_string(),
+                "GoTrash".to_string(),
+                "MaintenancePopup".to_string(),
+                "Home".to_string()
+            ),
+            KeymapError::KeyTaken(
+                "x".to_string(),
+                "ToggleHomeWeekly".to_string(),
+                "ChangeHomeSort".to_string(),
+                "Home".to_string()
+            ),
+        ]
+    );
+
+    // the actions that could not be moved keep their default key
+    app.press_keys(&[KeyCode::Enter, KeyCode::Char('X')]);
+    assert!(matches!(app.state.page, CurrentUi::Trash));
+}
@@ -0,0 +1,55 @@
+extern crate rex_tui;
+use rex_tui::utility::{
+    get_active_log_dir, get_last_log_lines, get_latest_log_file, get_log_dir, start_logging,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn check_log_files() {
+    let dir_name = "log_test_dir";
+    fs::create_dir_all(dir_name).unwrap();
+
+    let mut db_path = PathBuf::from(dir_name);
+    db_path.push("data.sqlite");
+    let log_dir = get_log_dir(&db_path);
+
+    let missing_dir_lines = get_last_log_lines(&log_dir, 10).unwrap();
+
+    fs::create_dir_all(&log_dir).unwrap();
+    fs::write(log_dir.join("rex.2024-05-11.log"), "old line\n").unwrap();
+    fs::write(
+        log_dir.join("rex.2024-05-12.log"),
+        "line 1\nline 2\nline 3\n",
+    )
+    .unwrap();
+    fs::write(log_dir.join("notes.txt"), "not a log\n").unwrap();
+
+    let latest_file = get_latest_log_file(&log_dir);
+    let last_lines = get_last_log_lines(&log_dir, 2).unwrap();
+
+    fs::remove_dir_all(dir_name).unwrap();
+
+    assert_eq!(log_dir, Path::new(dir_name).join("logs"));
+    assert!(missing_dir_lines.is_empty());
+    assert_eq!(latest_file, Some(log_dir.join("rex.2024-05-12.log")));
+    assert_eq!(last_lines, vec!["line 3".to_string(), "line 2".to_string()]);
+}
+
+#[test]
+fn check_logging() {
+    let log_dir = PathBuf::from("logging_test_dir");
+
+    start_logging(&log_dir, false).unwrap();
+    tracing::info!("An info line");
+    tracing::debug!("A debug line");
+
+    let active_dir = get_active_log_dir().map(Path::to_path_buf);
+    let lines = get_last_log_lines(&log_dir, 10).unwrap();
+
+    fs::remove_dir_all(&log_dir).unwrap();
+
+    assert_eq!(active_dir, Some(log_dir));
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].contains("An info line"));
+}
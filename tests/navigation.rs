@@ -0,0 +1,192 @@
+extern crate rex_tui;
+use chrono::{Days, Local};
+use crossterm::event::KeyCode;
+use rex_tui::db::{add_scheduled_tx, get_scheduled_txs};
+use rex_tui::home_page::TX_WINDOW_SIZE;
+use rex_tui::outputs::HandlingOutput;
+use rex_tui::page_handler::{CurrentUi, HomeTab, PopupState};
+use rex_tui::testing::{add_sample_txs, create_memory_db, create_sample_db, TestApp, SAMPLE_TXS};
+
+#[test]
+fn check_home_table_navigation() {
+    let mut app = TestApp::new(create_sample_db());
+
+    app.press_keys(&[KeyCode::Enter]);
+    assert!(matches!(app.state.page, CurrentUi::Home));
+    assert!(app.state.home.tab == HomeTab::Months);
+    assert_eq!(app.state.home.table.items.len(), SAMPLE_TXS);
+
+    app.press_keys(&[KeyCode::Down]);
+    assert!(app.state.home.tab == HomeTab::Table);
+    assert_eq!(app.state.home.table.state.selected(), Some(0));
+
+    // up on the first row leaves the table
+    app.press_keys(&[KeyCode::Up]);
+    assert!(app.state.home.tab == HomeTab::Months);
+    assert_eq!(app.state.home.table.state.selected(), None);
+
+    // up on the Years tab goes around to the last row
+    app.press_keys(&[KeyCode::Up, KeyCode::Up]);
+    assert!(app.state.home.tab == HomeTab::Table);
+    assert_eq!(app.state.home.table.state.selected(), Some(SAMPLE_TXS - 1));
+
+    // down on the last row goes around to the Years tab
+    app.press_keys(&[KeyCode::Down]);
+    assert!(app.state.home.tab == HomeTab::Years);
+    assert_eq!(app.state.home.table.state.selected(), None);
+
+    assert!(matches!(
+        app.press_keys(&[KeyCode::Char('q')]),
+        Some(HandlingOutput::QuitUi)
+    ));
+}
+
+#[test]
+fn check_home_empty_table_navigation() {
+    let mut app = TestApp::new(create_memory_db());
+
+    app.press_keys(&[KeyCode::Enter]);
+    assert!(app.state.home.table.items.is_empty());
+
+    // the table is skipped both ways without txs
+    app.press_keys(&[KeyCode::Down]);
+    assert!(app.state.home.tab == HomeTab::Years);
+    assert_eq!(app.state.home.table.state.selected(), None);
+
+    app.press_keys(&[KeyCode::Up]);
+    assert!(app.state.home.tab == HomeTab::Months);
+    assert_eq!(app.state.home.table.state.selected(), None);
+}
+
+#[test]
+fn check_home_windowed_navigation() {
+    let total_txs = TX_WINDOW_SIZE + 50;
+
+    let mut conn = create_memory_db();
+    add_sample_txs(total_txs, &mut conn);
+    let mut app = TestApp::new(conn);
+
+    app.press_keys(&[KeyCode::Enter, KeyCode::Down]);
+    assert_eq!(app.state.home.table.items.len(), TX_WINDOW_SIZE);
+
+    // the next window is fetched before the selection reaches the end of the loaded txs
+    app.press_keys(&[KeyCode::Down; TX_WINDOW_SIZE - 1]);
+    assert_eq!(
+        app.state.home.table.state.selected(),
+        Some(TX_WINDOW_SIZE - 1)
+    );
+    assert_eq!(app.state.home.table.items.len(), total_txs);
+
+    let mut app = TestApp::new(app.conn);
+
+    // going around from the Years tab needs every tx to select the last row
+    app.press_keys(&[KeyCode::Enter, KeyCode::Up, KeyCode::Up]);
+    assert!(app.state.home.tab == HomeTab::Table);
+    assert_eq!(app.state.home.table.items.len(), total_txs);
+    assert_eq!(app.state.home.table.state.selected(), Some(total_txs - 1));
+}
+
+#[test]
+fn check_test_app_drawing() {
+    let mut app = TestApp::new(create_sample_db());
+    app.press_keys(&[KeyCode::Enter]);
+
+    let screen = app.draw(160, 50);
+    assert_eq!(screen.len(), 50);
+    assert!(screen.iter().any(|line| line.contains("Sample tx 1")));
+
+    app.press_keys(&[KeyCode::Char('h')]);
+    let screen = app.draw(160, 50);
+    assert!(screen.iter().any(|line| line.contains("Help")));
+}
+
+#[test]
+fn check_batch_add_tx_drawing() {
+    let mut app = TestApp::new(create_sample_db());
+
+    // start a batch, fill the form with the last tx and add it
+    app.press_keys(&[
+        KeyCode::Enter,
+        KeyCode::Char('a'),
+        KeyCode::Char('b'),
+        KeyCode::Char('l'),
+        KeyCode::Char('s'),
+    ]);
+    assert!(matches!(app.state.page, CurrentUi::AddTx));
+    assert_eq!(app.state.add_tx.batch, Some(1));
+
+    // the balance section still has the Add Tx rows after the Home data was reloaded
+    let screen = app.draw(160, 50);
+    assert!(screen
+        .iter()
+        .any(|line| line.contains("Batch: Transaction 1 added.")));
+}
+
+#[test]
+fn check_queued_popups() {
+    let mut app = TestApp::new(create_sample_db());
+
+    app.state.queue_popup(PopupState::InvalidConfig(
+        "Config: config.json is not valid".to_string(),
+    ));
+    app.state.queue_popup(PopupState::KeymapErrors(
+        "Key Bindings: Unknown key".to_string(),
+    ));
+
+    // the popups are shown one after another as each one is closed
+    assert!(matches!(app.state.popup, PopupState::InvalidConfig(_)));
+    assert_eq!(app.state.popup_queue.len(), 1);
+
+    app.press_keys(&[KeyCode::Enter]);
+    assert!(matches!(app.state.popup, PopupState::KeymapErrors(_)));
+    assert!(app.state.popup_queue.is_empty());
+
+    app.press_keys(&[KeyCode::Enter]);
+    assert!(matches!(app.state.popup, PopupState::Nothing));
+}
+
+#[test]
+fn check_test_app_foreign_keys() {
+    let app = TestApp::new(create_sample_db());
+
+    let foreign_keys: bool = app
+        .conn
+        .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+        .unwrap();
+    assert!(foreign_keys);
+
+    // a note of a tx that does not exist is rejected like in the app
+    let result = app.conn.execute(
+        "INSERT INTO tx_notes (id_num, note) VALUES (9999, 'Note')",
+        [],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn check_scheduled_txs_on_date_change() {
+    let mut app = TestApp::new(create_sample_db());
+    app.press_keys(&[KeyCode::Enter]);
+
+    let tomorrow = Local::now().date_naive() + Days::new(1);
+    add_scheduled_tx(
+        &tomorrow.format("%Y-%m-%d").to_string(),
+        "Rent",
+        "Cash",
+        "100.00",
+        "Expense",
+        "Home",
+        &app.conn,
+    )
+    .unwrap();
+
+    // nothing is added before the date arrives
+    app.change_date(Local::now().date_naive());
+    assert_eq!(get_scheduled_txs(&app.conn).len(), 1);
+    assert!(matches!(app.state.popup, PopupState::Nothing));
+
+    app.change_date(tomorrow);
+    assert!(get_scheduled_txs(&app.conn).is_empty());
+    assert!(matches!(app.state.popup, PopupState::ScheduledTxs(_)));
+    assert!(app.state.all_tags.contains(&"Home".to_string()));
+}
@@ -0,0 +1,127 @@
+extern crate rex_tui;
+use rex_tui::db::{create_db, run_report_query, ReportResult};
+use rex_tui::outputs::ReportError;
+use rex_tui::tx_handler::add_tx;
+use rex_tui::utility::{Config, ReportQuery};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+#[test]
+fn check_report_queries() {
+    let file_name = "report_queries.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2022-08-19", "Lunch", "50.00", "Food"),
+        ("2022-08-20", "Dinner", "70.50", "Food"),
+        ("2022-09-01", "Bus", "10.00", "Travel"),
+    ];
+    for (date, details, amount, tags) in txs {
+        add_tx(
+            date, details, "test1", amount, "Expense", tags, None, &mut conn,
+        )
+        .unwrap();
+    }
+
+    let by_tag = run_report_query(
+        "SELECT tags AS Tag, SUM(amount) AS Total, COUNT(*), NULL FROM tx_all
+        GROUP BY tags ORDER BY tags",
+        &conn,
+    )
+    .unwrap();
+
+    let with_query = run_report_query(
+        "  with months AS (SELECT substr(date, 1, 7) AS month FROM tx_all)
+        SELECT month, COUNT(*) FROM months GROUP BY month",
+        &conn,
+    )
+    .unwrap();
+
+    let deletion = run_report_query("DELETE FROM tx_all", &conn);
+    let attach = run_report_query("ATTACH 'other.sqlite' AS other", &conn);
+    let multiple = run_report_query("SELECT 1; DELETE FROM tx_all", &conn);
+    let empty = run_report_query("  -- nothing yet", &conn);
+    let invalid = run_report_query("SELECT * FROM missing_table", &conn);
+
+    let total_txs: i64 = conn
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        by_tag,
+        ReportResult {
+            columns: vec![
+                "Tag".to_string(),
+                "Total".to_string(),
+                "COUNT(*)".to_string(),
+                "NULL".to_string()
+            ],
+            rows: vec![
+                vec![
+                    "Food".to_string(),
+                    "120.50".to_string(),
+                    "2".to_string(),
+                    String::new()
+                ],
+                vec![
+                    "Travel".to_string(),
+                    "10.00".to_string(),
+                    "1".to_string(),
+                    String::new()
+                ],
+            ],
+        }
+    );
+    assert_eq!(
+        with_query.rows,
+        vec![
+            vec!["2022-08".to_string(), "2".to_string()],
+            vec!["2022-09".to_string(), "1".to_string()],
+        ]
+    );
+    assert!(matches!(deletion, Err(ReportError::NotReadOnly)));
+    assert!(matches!(attach, Err(ReportError::NotReadOnly)));
+    assert!(matches!(multiple, Err(ReportError::MultipleQueries)));
+    assert!(matches!(empty, Err(ReportError::EmptyQuery)));
+    assert!(matches!(invalid, Err(ReportError::FailedQuery(_))));
+    assert_eq!(total_txs, 3);
+    assert!(!std::path::Path::new("other.sqlite").exists());
+}
+
+#[test]
+fn check_report_config() {
+    let config: Config = serde_json::from_str(
+        r#"{"reports": [{"name": "Spent by tag", "query": "SELECT tags FROM tx_all"}, {"name": "Empty"}]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.reports,
+        vec![
+            ReportQuery {
+                name: "Spent by tag".to_string(),
+                query: "SELECT tags FROM tx_all".to_string(),
+            },
+            ReportQuery {
+                name: "Empty".to_string(),
+                query: String::new(),
+            },
+        ]
+    );
+    assert!(Config::default().reports.is_empty());
+}
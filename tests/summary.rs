@@ -1,10 +1,12 @@
 extern crate rex_tui;
+use chrono::{Datelike, Local, Months};
 use rex_tui::db::*;
-use rex_tui::page_handler::{IndexedData, SortingType};
-use rex_tui::summary_page::SummaryData;
+use rex_tui::page_handler::{IndexedData, SortingDirection, SortingType};
+use rex_tui::summary_page::{SummaryData, TagStats};
 use rex_tui::tx_handler::add_tx;
-use rex_tui::utility::sort_table_data;
+use rex_tui::utility::{get_all_tx_methods, get_sparkline, sort_table_data};
 use rusqlite::Connection;
+use std::collections::HashSet;
 use std::fs;
 
 fn create_test_db(file_name: &str) -> Connection {
@@ -15,14 +17,14 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
 #[test]
 fn check_summary_data_1() {
     let file_name = "summary_data_1.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
 
     add_tx(
         "2022-08-19",
@@ -63,10 +65,19 @@ fn check_summary_data_1() {
     let summary_modes = IndexedData::new_modes();
 
     let my_summary = SummaryData::new(&conn);
-    let my_summary_text = my_summary.get_table_data(&summary_modes, 6, 1);
+    let my_summary_text = my_summary.get_table_data(
+        &summary_modes,
+        6,
+        1,
+        &SortingType::ByTags,
+        &SortingDirection::Ascending,
+        &HashSet::new(),
+    );
     let my_summary_text_2 = my_summary.get_tx_data(&summary_modes, 6, 1, &conn);
 
-    let expected_data_1 = vec![vec!["Food", "200.00", "100.00", "100.00", "100.00"]];
+    let expected_data_1 = vec![vec![
+        "Food", "200.00", "100.00", "100.00", "100.00", "100.00", "50.00",
+    ]];
 
     let expected_data_2 = (
         vec![
@@ -97,13 +108,13 @@ fn check_summary_data_1() {
         vec![
             vec![
                 "Largest Income".to_string(),
-                "25-07-2023".to_string(),
+                "2023-07-25".to_string(),
                 "200.00".to_string(),
                 "test1".to_string(),
             ],
             vec![
                 "Largest Expense".to_string(),
-                "19-07-2023".to_string(),
+                "2023-07-19".to_string(),
                 "100.00".to_string(),
                 "test 2".to_string(),
             ],
@@ -160,7 +171,7 @@ fn check_summary_data_1() {
 #[test]
 fn check_summary_data_2() {
     let file_name = "summary_data_2.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
 
     add_tx(
         "2022-08-19",
@@ -214,7 +225,14 @@ fn check_summary_data_2() {
     summary_modes.next();
 
     let my_summary = SummaryData::new(&conn);
-    let my_summary_text = my_summary.get_table_data(&summary_modes, 0, 0);
+    let my_summary_text = my_summary.get_table_data(
+        &summary_modes,
+        0,
+        0,
+        &SortingType::ByTags,
+        &SortingDirection::Ascending,
+        &HashSet::new(),
+    );
     let my_summary_text_2 = my_summary.get_tx_data(&summary_modes, 0, 0, &conn);
 
     let expected_data_1 = vec![
@@ -222,15 +240,19 @@ fn check_summary_data_2() {
             "Car".to_string(),
             "0.00".to_string(),
             "1000.00".to_string(),
+            "-1000.00".to_string(),
             "0.00".to_string(),
             "100.00".to_string(),
+            "58.82".to_string(),
         ],
         vec![
             "Food".to_string(),
             "1700.00".to_string(),
             "0.00".to_string(),
+            "1700.00".to_string(),
             "100.00".to_string(),
             "0.00".to_string(),
+            "0.00".to_string(),
         ],
     ];
 
@@ -263,13 +285,13 @@ fn check_summary_data_2() {
         vec![
             vec![
                 "Largest Income".to_string(),
-                "19-05-2022".to_string(),
+                "2022-05-19".to_string(),
                 "1000.00".to_string(),
                 "test1".to_string(),
             ],
             vec![
                 "Largest Expense".to_string(),
-                "19-01-2022".to_string(),
+                "2022-01-19".to_string(),
                 "500.00".to_string(),
                 "test1".to_string(),
             ],
@@ -326,7 +348,7 @@ fn check_summary_data_2() {
 #[test]
 fn check_summary_data_3() {
     let file_name = "summary_data_3.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
 
     add_tx(
         "2022-08-19",
@@ -369,7 +391,14 @@ fn check_summary_data_3() {
     summary_modes.next();
 
     let my_summary = SummaryData::new(&conn);
-    let my_summary_text = my_summary.get_table_data(&summary_modes, 0, 1);
+    let my_summary_text = my_summary.get_table_data(
+        &summary_modes,
+        0,
+        1,
+        &SortingType::ByTags,
+        &SortingDirection::Ascending,
+        &HashSet::new(),
+    );
     let my_summary_text_2 = my_summary.get_tx_data(&summary_modes, 0, 1, &conn);
 
     let expected_data_1 = vec![
@@ -377,15 +406,19 @@ fn check_summary_data_3() {
             "Car".to_string(),
             "0.00".to_string(),
             "100.00".to_string(),
+            "-100.00".to_string(),
             "0.00".to_string(),
             "100.00".to_string(),
+            "50.00".to_string(),
         ],
         vec![
             "Food".to_string(),
             "200.00".to_string(),
             "0.00".to_string(),
+            "200.00".to_string(),
             "100.00".to_string(),
             "0.00".to_string(),
+            "0.00".to_string(),
         ],
     ];
 
@@ -418,13 +451,13 @@ fn check_summary_data_3() {
         vec![
             vec![
                 "Largest Income".to_string(),
-                "19-07-2023".to_string(),
+                "2023-07-19".to_string(),
                 "100.00".to_string(),
                 "test 2".to_string(),
             ],
             vec![
                 "Largest Expense".to_string(),
-                "19-08-2022".to_string(),
+                "2022-08-19".to_string(),
                 "100.00".to_string(),
                 "test1".to_string(),
             ],
@@ -481,7 +514,7 @@ fn check_summary_data_3() {
 #[test]
 fn check_summary_sorting() {
     let file_name = "summary_sorting.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
 
     add_tx(
         "2022-08-19",
@@ -524,52 +557,103 @@ fn check_summary_sorting() {
     summary_modes.next();
 
     let my_summary = SummaryData::new(&conn);
-    let table_data = my_summary.get_table_data(&summary_modes, 0, 0);
+    let table_data = my_summary.get_table_data(
+        &summary_modes,
+        0,
+        0,
+        &SortingType::ByTags,
+        &SortingDirection::Ascending,
+        &HashSet::new(),
+    );
 
-    let sorted_data_1 = sort_table_data(table_data.clone(), &SortingType::ByTags);
-    let sorted_data_2 = sort_table_data(table_data.clone(), &SortingType::ByIncome);
-    let sorted_data_3 = sort_table_data(table_data.clone(), &SortingType::ByExpense);
+    let sorted_data_1 = sort_table_data(
+        table_data.clone(),
+        &SortingType::ByTags,
+        &SortingDirection::Ascending,
+    );
+    let sorted_data_2 = sort_table_data(
+        table_data.clone(),
+        &SortingType::ByIncome,
+        &SortingDirection::Descending,
+    );
+    let sorted_data_3 = sort_table_data(
+        table_data.clone(),
+        &SortingType::ByExpense,
+        &SortingDirection::Descending,
+    );
+    let sorted_data_4 = sort_table_data(
+        table_data.clone(),
+        &SortingType::ByTags,
+        &SortingDirection::Descending,
+    );
+    let sorted_data_5 = sort_table_data(
+        table_data.clone(),
+        &SortingType::ByNet,
+        &SortingDirection::Ascending,
+    );
+    let sorted_data_6 = sort_table_data(
+        table_data.clone(),
+        &SortingType::ByIncomePercentage,
+        &SortingDirection::Ascending,
+    );
+    let sorted_data_7 = sort_table_data(
+        table_data.clone(),
+        &SortingType::ByIncomeShare,
+        &SortingDirection::Descending,
+    );
 
     let expected_data_1 = vec![
-        vec!["Bank", "2000.00", "0.00", "80.00", "0.00"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>(),
-        vec!["Car", "0.00", "1000.00", "0.00", "100.00"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>(),
-        vec!["Food", "500.00", "0.00", "20.00", "0.00"]
+        [
+            "Bank", "2000.00", "0.00", "2000.00", "80.00", "0.00", "0.00",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>(),
+        [
+            "Car", "0.00", "1000.00", "-1000.00", "0.00", "100.00", "40.00",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>(),
+        ["Food", "500.00", "0.00", "500.00", "20.00", "0.00", "0.00"]
             .iter()
             .map(|s| s.to_string())
             .collect::<Vec<String>>(),
     ];
 
     let expected_data_2 = vec![
-        vec!["Bank", "2000.00", "0.00", "80.00", "0.00"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>(),
-        vec!["Food", "500.00", "0.00", "20.00", "0.00"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>(),
-        vec!["Car", "0.00", "1000.00", "0.00", "100.00"]
+        [
+            "Bank", "2000.00", "0.00", "2000.00", "80.00", "0.00", "0.00",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>(),
+        ["Food", "500.00", "0.00", "500.00", "20.00", "0.00", "0.00"]
             .iter()
             .map(|s| s.to_string())
             .collect::<Vec<String>>(),
+        [
+            "Car", "0.00", "1000.00", "-1000.00", "0.00", "100.00", "40.00",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>(),
     ];
 
     let expected_data_3 = vec![
-        vec!["Car", "0.00", "1000.00", "0.00", "100.00"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>(),
-        vec!["Bank", "2000.00", "0.00", "80.00", "0.00"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>(),
-        vec!["Food", "500.00", "0.00", "20.00", "0.00"]
+        [
+            "Car", "0.00", "1000.00", "-1000.00", "0.00", "100.00", "40.00",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>(),
+        [
+            "Bank", "2000.00", "0.00", "2000.00", "80.00", "0.00", "0.00",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>(),
+        ["Food", "500.00", "0.00", "500.00", "20.00", "0.00", "0.00"]
             .iter()
             .map(|s| s.to_string())
             .collect::<Vec<String>>(),
@@ -581,4 +665,651 @@ fn check_summary_sorting() {
     assert_eq!(sorted_data_1, expected_data_1);
     assert_eq!(sorted_data_2, expected_data_2);
     assert_eq!(sorted_data_3, expected_data_3);
+
+    let get_tags = |data: &Vec<Vec<String>>| {
+        data.iter()
+            .map(|row| row[0].to_string())
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(get_tags(&sorted_data_4), vec!["Food", "Car", "Bank"]);
+    assert_eq!(get_tags(&sorted_data_5), vec!["Car", "Food", "Bank"]);
+    assert_eq!(get_tags(&sorted_data_6), vec!["Car", "Food", "Bank"]);
+    assert_eq!(get_tags(&sorted_data_7), vec!["Car", "Bank", "Food"]);
+}
+
+#[test]
+fn check_summary_tag_method_data() {
+    let file_name = "summary_tag_method.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2023-07-19", "test1", "70.00", "Expense", "Food"),
+        ("2023-07-20", "test 2", "30.00", "Expense", "Food, Car"),
+        ("2023-07-21", "test1", "500.00", "Income", "Salary"),
+        ("2023-07-22", "test 2", "50.00", "Income", "Food"),
+    ];
+
+    for (date, method, amount, tx_type, tags) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let summary_modes = IndexedData::new_modes();
+    let tx_methods = get_all_tx_methods(&conn);
+
+    let my_summary = SummaryData::new(&conn);
+    let food_data = my_summary.get_tag_method_data(&summary_modes, 6, 1, "Food", &tx_methods);
+    let salary_data = my_summary.get_tag_method_data(&summary_modes, 6, 1, "Salary", &tx_methods);
+    let empty_data = my_summary.get_tag_method_data(&summary_modes, 5, 1, "Food", &tx_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        food_data,
+        vec![
+            vec!["test1", "0.00", "70.00", "0.00", "70.00"],
+            vec!["test 2", "50.00", "30.00", "100.00", "30.00"],
+        ]
+    );
+    assert_eq!(
+        salary_data,
+        vec![vec!["test1", "500.00", "0.00", "100.00", "0.00"]]
+    );
+    assert!(empty_data.is_empty());
+}
+
+#[test]
+fn check_summary_comparison_data() {
+    let file_name = "summary_comparison.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2023-06-10", "test1", "100.00", "Expense", "Food"),
+        ("2023-06-12", "test1", "40.00", "Expense", "Car"),
+        ("2023-07-19", "test1", "150.00", "Expense", "Food"),
+        ("2023-07-20", "test 2", "20.00", "Expense", "Car"),
+        ("2023-07-21", "test 2", "80.00", "Expense", "Gift"),
+    ];
+
+    for (date, method, amount, tx_type, tags) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let mut summary_modes = IndexedData::new_modes();
+
+    let my_summary = SummaryData::new(&conn);
+    let table_data = my_summary.get_table_data(
+        &summary_modes,
+        6,
+        1,
+        &SortingType::ByTags,
+        &SortingDirection::Ascending,
+        &HashSet::new(),
+    );
+    let monthly_comparison = my_summary.get_comparison_data(&table_data, &summary_modes, 6, 1);
+
+    summary_modes.next();
+    summary_modes.next();
+    let all_time_comparison = my_summary.get_comparison_data(&table_data, &summary_modes, 6, 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // Car, Food and Gift
+    assert_eq!(
+        monthly_comparison,
+        vec![
+            vec!["40.00", "-50.00"],
+            vec!["100.00", "50.00"],
+            vec!["0.00", "-"],
+        ]
+    );
+    assert_eq!(all_time_comparison, vec![vec!["-", "-"]; 3]);
+}
+
+#[test]
+fn check_summary_largest_txs() {
+    let file_name = "summary_largest_txs.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let mut expected_expenses = Vec::new();
+
+    // 12 expenses of the same month, only the 10 largest are kept
+    for day in 1..=12 {
+        let date = format!("2023-07-{day:02}");
+        let amount = format!("{:.2}", day as f64 * 10.0);
+        add_tx(
+            &date,
+            "Testing transaction",
+            "test1",
+            &amount,
+            "Expense",
+            "Food",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+
+        if day > 2 {
+            expected_expenses.push((format!("2023-07-{day:02}"), amount, day.to_string()));
+        }
+    }
+
+    add_tx(
+        "2023-07-20",
+        "Salary",
+        "test 2",
+        "1000.00",
+        "Income",
+        "Salary",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-06-20",
+        "Earlier month",
+        "test 2",
+        "5000.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let summary_modes = IndexedData::new_modes();
+
+    let my_summary = SummaryData::new(&conn);
+    let largest_txs = my_summary.get_largest_txs(&summary_modes, 6, 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    expected_expenses.reverse();
+
+    let mut expected_data = expected_expenses
+        .into_iter()
+        .map(|(date, amount, id_num)| {
+            vec![
+                date,
+                "Testing transaction".to_string(),
+                "test1".to_string(),
+                amount,
+                "Expense".to_string(),
+                id_num,
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    expected_data.push(
+        ["2023-07-20", "Salary", "test 2", "1000.00", "Income", "13"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+    );
+
+    assert_eq!(largest_txs, expected_data);
+}
+
+#[test]
+fn check_summary_statistics() {
+    let file_name = "summary_statistics.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2023-07-02", "test1", "30.00", "Expense", "Food"),
+        ("2023-07-05", "test1", "10.00", "Expense", "Food"),
+        ("2023-07-05", "test 2", "20.00", "Expense", "Car"),
+        ("2023-07-10", "test1", "500.00", "Income", "Salary"),
+        ("2023-07-11", "test 2", "100.00", "Income", "Gift, Salary"),
+        (
+            "2023-07-11",
+            "test1 to test 2",
+            "1000.00",
+            "Transfer",
+            "Savings",
+        ),
+    ];
+
+    for (date, method, amount, tx_type, tags) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let mut summary_modes = IndexedData::new_modes();
+
+    let my_summary = SummaryData::new(&conn);
+    let monthly_stats = my_summary.get_statistics(&summary_modes, 6, 1);
+    let empty_stats = my_summary.get_statistics(&summary_modes, 5, 1);
+
+    summary_modes.next();
+    let yearly_stats = my_summary.get_statistics(&summary_modes, 0, 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let to_strings = |data: [[&str; 2]; 5]| {
+        data.iter()
+            .map(|row| row.iter().map(|s| s.to_string()).collect::<Vec<String>>())
+            .collect::<Vec<Vec<String>>>()
+    };
+
+    assert_eq!(
+        monthly_stats,
+        to_strings([
+            ["Average Daily Expense", "1.94"],
+            ["Median Tx Amount", "30.00"],
+            ["Total Txs", "5"],
+            ["Busiest Day", "05-07-2023 (2 txs)"],
+            ["Average Income per Source", "300.00"],
+        ])
+    );
+
+    assert_eq!(yearly_stats[0][1], "0.16");
+    assert_eq!(yearly_stats[2][1], "5");
+
+    assert_eq!(
+        empty_stats,
+        to_strings([
+            ["Average Daily Expense", "0.00"],
+            ["Median Tx Amount", "0.00"],
+            ["Total Txs", "0"],
+            ["Busiest Day", "-"],
+            ["Average Income per Source", "0.00"],
+        ])
+    );
+}
+
+#[test]
+fn check_summary_tag_hierarchy() {
+    let file_name = "summary_tag_hierarchy.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2023-07-19", "test1", "70.00", "Expense", "food:groceries"),
+        (
+            "2023-07-20",
+            "test 2",
+            "30.00",
+            "Expense",
+            "food:restaurants",
+        ),
+        ("2023-07-21", "test1", "100.00", "Expense", "Car"),
+    ];
+
+    for (date, method, amount, tx_type, tags) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let summary_modes = IndexedData::new_modes();
+    let tx_methods = get_all_tx_methods(&conn);
+
+    let my_summary = SummaryData::new(&conn);
+
+    let mut expanded_tags = HashSet::new();
+    let collapsed_data = my_summary.get_table_data(
+        &summary_modes,
+        6,
+        1,
+        &SortingType::ByTags,
+        &SortingDirection::Ascending,
+        &expanded_tags,
+    );
+
+    expanded_tags.insert("food".to_string());
+    let expanded_data = my_summary.get_table_data(
+        &summary_modes,
+        6,
+        1,
+        &SortingType::ByExpense,
+        &SortingDirection::Descending,
+        &expanded_tags,
+    );
+
+    let parent_tags = my_summary.get_parent_tags(&summary_modes, 6, 1);
+    let food_data = my_summary.get_tag_method_data(&summary_modes, 6, 1, "food", &tx_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        collapsed_data,
+        vec![
+            vec!["Car", "0.00", "100.00", "-100.00", "0.00", "50.00", "0.00"],
+            vec!["food", "0.00", "100.00", "-100.00", "0.00", "50.00", "0.00"],
+        ]
+    );
+
+    let tags = expanded_data
+        .iter()
+        .map(|row| row[0].to_string())
+        .collect::<Vec<String>>();
+
+    assert_eq!(
+        tags,
+        vec!["Car", "food", "food:groceries", "food:restaurants"]
+    );
+    assert_eq!(parent_tags, HashSet::from(["food".to_string()]));
+    assert_eq!(
+        food_data,
+        vec![
+            vec!["test1", "0.00", "70.00", "0.00", "70.00"],
+            vec!["test 2", "0.00", "30.00", "0.00", "30.00"],
+        ]
+    );
+}
+
+#[test]
+fn check_summary_transfers() {
+    let file_name = "summary_transfers.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2023-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-07-20",
+        "Testing transaction",
+        "test1 to test 2",
+        "50.00",
+        "Transfer",
+        "Savings",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let summary_modes = IndexedData::new_modes();
+
+    let mut my_summary = SummaryData::new(&conn);
+    let get_table = |summary: &SummaryData| {
+        summary.get_table_data(
+            &summary_modes,
+            6,
+            1,
+            &SortingType::ByTags,
+            &SortingDirection::Ascending,
+            &HashSet::new(),
+        )
+    };
+
+    let excluded_data = get_table(&my_summary);
+    let excluded_methods = my_summary.get_tx_data(&summary_modes, 6, 1, &conn).4;
+
+    my_summary.set_include_transfers(true);
+    let included_data = get_table(&my_summary);
+    let included_methods = my_summary.get_tx_data(&summary_modes, 6, 1, &conn).4;
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        excluded_data,
+        vec![vec![
+            "Food", "0.00", "100.00", "-100.00", "0.00", "100.00", "0.00"
+        ]]
+    );
+    assert_eq!(
+        included_data,
+        vec![
+            vec!["Food", "0.00", "100.00", "-100.00", "0.00", "66.67", "200.00"],
+            vec!["Savings", "50.00", "50.00", "0.00", "100.00", "33.33", "100.00"],
+        ]
+    );
+
+    // the transfer is an expense of test1 and an income of test 2
+    assert_eq!(excluded_methods[1][1], "0.00");
+    assert_eq!(included_methods[0][2], "150.00");
+    assert_eq!(included_methods[1][1], "50.00");
+}
+
+#[test]
+fn check_summary_tx_method_filter() {
+    let file_name = "summary_tx_method_filter.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2023-07-19", "test1", "70.00", "Expense", "Food"),
+        ("2023-07-20", "test 2", "30.00", "Expense", "Food"),
+        ("2023-07-21", "test 2", "200.00", "Income", "Salary"),
+        (
+            "2023-07-22",
+            "test1 to test 2",
+            "50.00",
+            "Transfer",
+            "Savings",
+        ),
+    ];
+
+    for (date, method, amount, tx_type, tags) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let summary_modes = IndexedData::new_modes();
+    let summary_tx_methods = IndexedData::new_summary_tx_methods(&conn);
+
+    let mut my_summary = SummaryData::new(&conn);
+    let get_tags = |summary: &SummaryData| {
+        summary
+            .get_table_data(
+                &summary_modes,
+                6,
+                1,
+                &SortingType::ByTags,
+                &SortingDirection::Ascending,
+                &HashSet::new(),
+            )
+            .iter()
+            .map(|row| (row[0].to_string(), row[1].to_string(), row[2].to_string()))
+            .collect::<Vec<(String, String, String)>>()
+    };
+
+    my_summary.set_tx_method(Some("test1".to_string()));
+    let test1_data = get_tags(&my_summary);
+
+    my_summary.set_include_transfers(true);
+    let test1_transfer_data = get_tags(&my_summary);
+
+    my_summary.set_tx_method(None);
+    my_summary.set_include_transfers(false);
+    let all_data = get_tags(&my_summary);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let to_rows = |rows: &[(&str, &str, &str)]| {
+        rows.iter()
+            .map(|(tag, income, expense)| {
+                (tag.to_string(), income.to_string(), expense.to_string())
+            })
+            .collect::<Vec<(String, String, String)>>()
+    };
+
+    assert_eq!(summary_tx_methods.titles, vec!["All", "test1", "test 2"]);
+    assert_eq!(test1_data, to_rows(&[("Food", "0.00", "70.00")]));
+    assert_eq!(
+        test1_transfer_data,
+        to_rows(&[("Food", "0.00", "70.00"), ("Savings", "0.00", "50.00")])
+    );
+    assert_eq!(
+        all_data,
+        to_rows(&[("Food", "0.00", "100.00"), ("Salary", "200.00", "0.00")])
+    );
+}
+
+#[test]
+fn check_summary_tag_trends() {
+    let file_name = "summary_tag_trends.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2023-01-10", "test1", "100.00", "Expense", "Food"),
+        ("2023-06-10", "test1", "50.00", "Expense", "Food:Snacks"),
+        ("2023-07-19", "test1", "200.00", "Expense", "Food"),
+        ("2023-07-20", "test 2", "300.00", "Income", "Salary"),
+        ("2023-08-20", "test 2", "999.00", "Income", "Salary"),
+    ];
+
+    for (date, method, amount, tx_type, tags) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let summary_modes = IndexedData::new_modes();
+    let my_summary = SummaryData::new(&conn);
+
+    // July 2023, the window goes from August 2022
+    let trends = my_summary.get_tag_trends(&summary_modes, 6, 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let mut food = vec![0.0; 12];
+    food[5] = 100.0;
+    food[10] = 50.0;
+    food[11] = 200.0;
+
+    let mut snacks = vec![0.0; 12];
+    snacks[10] = 50.0;
+
+    let mut salary = vec![0.0; 12];
+    salary[11] = 300.0;
+
+    assert_eq!(trends["Food"], food);
+    assert_eq!(trends["Food:Snacks"], snacks);
+    assert_eq!(trends["Salary"], salary);
+
+    assert_eq!(get_sparkline(&food), "▁▁▁▁▁▅▁▁▁▁▃█");
+    assert_eq!(get_sparkline(&[0.0, 0.0]), "▁▁");
+}
+
+#[test]
+fn check_summary_tag_stats() {
+    let file_name = "summary_tag_stats.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let today = Local::now().date_naive();
+    let this_month = today.format("%Y-%m-01").to_string();
+    let two_months_ago = (today - Months::new(2)).format("%Y-%m-01").to_string();
+
+    let txs = [
+        ("2023-01-10", "100.00", "Expense", "Food"),
+        (two_months_ago.as_str(), "50.00", "Expense", "Food:Snacks"),
+        (this_month.as_str(), "30.00", "Income", "Food, Refund"),
+        (this_month.as_str(), "999.00", "Income", "Salary"),
+    ];
+
+    for (date, amount, tx_type, tags) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            "test1",
+            amount,
+            tx_type,
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let my_summary = SummaryData::new(&conn);
+    let food_stats = my_summary.get_tag_stats("Food").unwrap();
+    let missing_stats = my_summary.get_tag_stats("Car");
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let total_months =
+        f64::from((today.year() - 2023) * 12 + i32::try_from(today.month0()).unwrap() + 1);
+
+    let mut trend = vec![0.0; 12];
+    trend[9] = 50.0;
+    trend[11] = 30.0;
+
+    assert_eq!(
+        food_stats,
+        TagStats {
+            tag: "Food".to_string(),
+            total_txs: 3,
+            total_income: 30.0,
+            total_expense: 150.0,
+            monthly_income: 30.0 / total_months,
+            monthly_expense: 150.0 / total_months,
+            trend,
+            first_date: "10-01-2023".to_string(),
+            last_date: (today.with_day(1).unwrap()).format("%d-%m-%Y").to_string(),
+        }
+    );
+    assert!(missing_stats.is_none());
 }
@@ -0,0 +1,250 @@
+extern crate rex_tui;
+use rex_tui::db::create_db;
+use rex_tui::outputs::SyncError;
+use rex_tui::sync_handler::{
+    delete_sync_settings, get_sync_settings, get_sync_settings_path, merge_synced_txs,
+    save_sync_settings, synced_txs_match, MergeSummary, SyncSettings,
+};
+use rex_tui::tx_handler::{add_tx, delete_tx};
+use rex_tui::utility::get_last_balances;
+use rusqlite::Connection;
+use std::fs;
+use std::path::PathBuf;
+
+fn create_test_db(file_name: &str, tx_methods: &[&str]) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let tx_methods = tx_methods
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&tx_methods, &mut conn).unwrap();
+    conn
+}
+
+fn add_test_tx(details: &str, amount: &str, id_num: Option<&str>, conn: &mut Connection) {
+    add_tx(
+        "2022-08-19",
+        details,
+        "test1",
+        amount,
+        "Expense",
+        "Food",
+        id_num,
+        conn,
+    )
+    .unwrap();
+}
+
+fn get_details(conn: &Connection) -> Vec<(i32, String)> {
+    let mut statement = conn
+        .prepare("SELECT id_num, details FROM tx_all ORDER BY id_num")
+        .unwrap();
+    statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .map(|row| row.unwrap())
+        .collect()
+}
+
+#[test]
+fn check_merging_synced_txs() {
+    let file_name = "sync_merge_local.sqlite";
+    let base_name = "sync_merge_base.sqlite";
+    let remote_name = "sync_merge_remote.sqlite";
+
+    let mut conn = create_test_db(file_name, &["test1", "test 2"]);
+    for details in ["First", "Second", "Third"] {
+        add_test_tx(details, "100.00", None, &mut conn);
+    }
+    conn.close().unwrap();
+
+    // both devices start from the last synced db
+    fs::copy(file_name, base_name).unwrap();
+    fs::copy(file_name, remote_name).unwrap();
+
+    let mut conn = Connection::open(file_name).unwrap();
+    conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+    delete_tx(1, &mut conn).unwrap();
+    add_test_tx("First edited here", "10.00", Some("1"), &mut conn);
+    add_test_tx("Only here", "20.00", None, &mut conn);
+    add_test_tx("On both", "30.00", None, &mut conn);
+
+    let mut remote_conn = Connection::open(remote_name).unwrap();
+    remote_conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+    delete_tx(2, &mut remote_conn).unwrap();
+    delete_tx(3, &mut remote_conn).unwrap();
+    add_test_tx("Third edited there", "40.00", Some("3"), &mut remote_conn);
+    add_test_tx("Only there", "50.00", None, &mut remote_conn);
+    add_test_tx("On both", "30.00", None, &mut remote_conn);
+    remote_conn.close().unwrap();
+
+    conn.execute(
+        &format!("ATTACH DATABASE '{remote_name}' AS sync_remote"),
+        [],
+    )
+    .unwrap();
+    conn.execute(&format!("ATTACH DATABASE '{base_name}' AS sync_base"), [])
+        .unwrap();
+
+    let summary = merge_synced_txs("sync_remote", Some("sync_base"), &mut conn).unwrap();
+    let in_sync = synced_txs_match("sync_remote", &conn).unwrap();
+
+    let details = get_details(&conn);
+    let balances = get_last_balances(&conn);
+
+    // the next new tx comes after every merged one
+    add_test_tx("Added after the sync", "1.00", None, &mut conn);
+    let last_id = get_details(&conn).last().unwrap().0;
+
+    conn.close().unwrap();
+    for file in [file_name, base_name, remote_name] {
+        fs::remove_file(file).unwrap();
+    }
+
+    assert_eq!(
+        summary,
+        MergeSummary {
+            added: 1,
+            edited: 1,
+            deleted: 1,
+        }
+    );
+    assert!(!in_sync);
+    assert_eq!(
+        details,
+        vec![
+            (1, "First edited here".to_string()),
+            (3, "Third edited there".to_string()),
+            (4, "Only there".to_string()),
+            (5, "On both".to_string()),
+            (6, "Only here".to_string()),
+        ]
+    );
+    assert_eq!(balances, vec!["-150", "0"]);
+    assert_eq!(last_id, 7);
+}
+
+#[test]
+fn check_merging_without_base() {
+    let file_name = "sync_no_base_local.sqlite";
+    let remote_name = "sync_no_base_remote.sqlite";
+
+    let mut remote_conn = create_test_db(remote_name, &["test1", "test 2"]);
+    add_test_tx("Remote", "100.00", None, &mut remote_conn);
+    add_test_tx("Same", "10.00", None, &mut remote_conn);
+    remote_conn.close().unwrap();
+
+    let mut conn = create_test_db(file_name, &["test1", "test 2"]);
+    conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+    add_test_tx("Same", "10.00", None, &mut conn);
+    add_test_tx("Local", "5.00", None, &mut conn);
+
+    conn.execute(
+        &format!("ATTACH DATABASE '{remote_name}' AS sync_remote"),
+        [],
+    )
+    .unwrap();
+
+    let summary = merge_synced_txs("sync_remote", None, &mut conn).unwrap();
+    let details = get_details(&conn);
+    let balances = get_last_balances(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(remote_name).unwrap();
+
+    assert_eq!(summary.added, 1);
+    assert_eq!(
+        details,
+        vec![
+            (1, "Remote".to_string()),
+            (2, "Same".to_string()),
+            (4, "Local".to_string()),
+        ]
+    );
+    assert_eq!(balances, vec!["-115", "0"]);
+}
+
+#[test]
+fn check_merging_missing_tx_method() {
+    let file_name = "sync_missing_method_local.sqlite";
+    let remote_name = "sync_missing_method_remote.sqlite";
+
+    let mut remote_conn = create_test_db(remote_name, &["test1", "Other"]);
+    add_tx(
+        "2022-08-19",
+        "Remote",
+        "Other",
+        "100.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut remote_conn,
+    )
+    .unwrap();
+    remote_conn.close().unwrap();
+
+    let mut conn = create_test_db(file_name, &["test1", "test 2"]);
+    conn.execute(
+        &format!("ATTACH DATABASE '{remote_name}' AS sync_remote"),
+        [],
+    )
+    .unwrap();
+
+    let status = merge_synced_txs("sync_remote", None, &mut conn);
+    let details = get_details(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(remote_name).unwrap();
+
+    assert!(matches!(status, Err(SyncError::MissingTxMethod(method)) if method == "Other"));
+    assert!(details.is_empty());
+}
+
+#[test]
+fn check_sync_settings() {
+    let dir = PathBuf::from("sync_settings_test");
+    fs::create_dir_all(&dir).unwrap();
+    let db_path = dir.join("data.sqlite");
+
+    let missing_settings = get_sync_settings(&db_path);
+
+    let settings = SyncSettings {
+        url: "https://cloud.example.com/remote.php/dav/files/user/Rex".to_string(),
+        username: "user".to_string(),
+        password: "password".to_string(),
+    };
+    // settings saved before the permissions were set may still be readable by others
+    fs::write(get_sync_settings_path(&db_path), "{}").unwrap();
+    save_sync_settings(&db_path, &settings).unwrap();
+    let saved_settings = get_sync_settings(&db_path);
+
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(get_sync_settings_path(&db_path))
+            .unwrap()
+            .permissions()
+            .mode()
+            & 0o777
+    };
+
+    delete_sync_settings(&db_path).unwrap();
+    let deleted_settings = get_sync_settings(&db_path);
+
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(missing_settings, None);
+    assert_eq!(saved_settings, Some(settings));
+    assert_eq!(deleted_settings, None);
+    #[cfg(unix)]
+    assert_eq!(mode, 0o600);
+}
@@ -0,0 +1,61 @@
+extern crate rex_tui;
+use ratatui::style::Color;
+use rex_tui::outputs::ThemeError;
+use rex_tui::page_handler::{Theme, THEME_NAMES};
+use rex_tui::utility::Config;
+use std::collections::HashMap;
+
+#[test]
+fn check_theme_names() {
+    for name in THEME_NAMES {
+        assert!(Theme::from_name(name).is_some());
+    }
+
+    assert_eq!(Theme::from_name(" gruvbox "), Some(Theme::GRUVBOX));
+    assert_eq!(
+        Theme::from_name("SOLARIZED LIGHT"),
+        Some(Theme::SOLARIZED_LIGHT)
+    );
+    assert_eq!(Theme::from_name("Monokai"), None);
+}
+
+#[test]
+fn check_theme_from_config() {
+    let mut config = Config::default();
+    assert_eq!(Theme::from_config(&config), (Theme::DRACULA, Vec::new()));
+
+    config.theme = "Plain".to_string();
+    assert_eq!(Theme::from_config(&config), (Theme::PLAIN, Vec::new()));
+
+    // unknown themes fall back to Dracula
+    config.theme = "Monokai".to_string();
+    assert_eq!(
+        Theme::from_config(&config),
+        (
+            Theme::DRACULA,
+            vec![ThemeError::UnknownTheme("Monokai".to_string())]
+        )
+    );
+
+    config.theme = "Gruvbox".to_string();
+    config.theme_colors = HashMap::from([
+        ("Background".to_string(), "#1e1e2e".to_string()),
+        ("expense".to_string(), "#f38ba8".to_string()),
+        ("text".to_string(), "not a color".to_string()),
+        ("red".to_string(), "#ffffff".to_string()),
+    ]);
+
+    let (theme, errors) = Theme::from_config(&config);
+
+    assert_eq!(theme.background, Color::Rgb(30, 30, 46));
+    assert_eq!(theme.expense, Color::Rgb(243, 139, 168));
+    assert_eq!(theme.text, Theme::GRUVBOX.text);
+    assert_eq!(theme.border, Theme::GRUVBOX.border);
+    assert_eq!(
+        errors,
+        vec![
+            ThemeError::UnknownColor("red".to_string()),
+            ThemeError::InvalidColor("text".to_string(), "not a color".to_string()),
+        ]
+    );
+}
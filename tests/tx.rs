@@ -1,10 +1,30 @@
 extern crate rex_tui;
-use rex_tui::db::create_db;
+use chrono::{Days, Local};
+use rex_tui::db::{
+    add_tx_attachment, cache_rate, create_db, delete_trashed_tx, empty_trash, get_scheduled_txs,
+    get_split_parts, get_trashed_txs, get_tx_attachments, get_tx_foreign_amount, get_tx_note,
+    get_tx_status, link_split_parts, set_tx_attachments, set_tx_foreign_amount, set_tx_note,
+    set_tx_status, ForeignAmount,
+};
+use rex_tui::outputs::{
+    AType, AttachmentError, CurrencyError, FeeError, NAType, QuickAddError, RetagError, SplitError,
+    TagRuleError, TrashError,
+};
+use rex_tui::page_handler::{IndexedData, TxStatus};
+use rex_tui::summary_page::SummaryData;
 use rex_tui::tx_handler::add_tx;
-use rex_tui::tx_handler::delete_tx;
-use rex_tui::utility::{get_all_tx_columns, get_all_txs, get_last_tx_id};
+use rex_tui::tx_handler::{
+    add_tag_to_txs, delete_tx, delete_txs, get_split_details, get_tag_rules_text, parse_quick_add,
+    post_scheduled_txs, remove_tag_from_txs, remove_tag_rule, restore_tx, save_tag_rule, trash_tx,
+    trash_txs, TxData,
+};
+use rex_tui::utility::{
+    get_all_tx_columns, get_all_txs, get_attachment_path, get_last_balances, get_last_tx_id,
+    get_tx_id_num, switch_tx_index, Config,
+};
 use rusqlite::{Connection, Result as sqlResult};
 use std::fs;
+use std::path::PathBuf;
 
 fn create_test_db(file_name: &str) -> Connection {
     if let Ok(metadata) = fs::metadata(file_name) {
@@ -14,14 +34,14 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
 #[test]
 fn check_last_tx_id_1() {
     let file_name = "last_tx_id_1.sqlite";
-    let conn = create_test_db(&file_name);
+    let conn = create_test_db(file_name);
 
     let data = get_last_tx_id(&conn);
     let expected_data: sqlResult<i32> = Err(rusqlite::Error::QueryReturnedNoRows);
@@ -35,7 +55,7 @@ fn check_last_tx_id_1() {
 #[test]
 fn check_last_tx_id_2() {
     let file_name = "last_tx_id_2.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
 
     add_tx(
         "2022-09-19",
@@ -61,7 +81,7 @@ fn check_last_tx_id_2() {
 #[test]
 fn check_getting_all_tx_1() {
     let file_name = "getting_tx_1.sqlite";
-    let conn = create_test_db(&file_name);
+    let conn = create_test_db(file_name);
 
     let data = get_all_txs(&conn, 6, 0);
     let expected_data = (Vec::new(), Vec::new(), Vec::new());
@@ -75,7 +95,7 @@ fn check_getting_all_tx_1() {
 #[test]
 fn check_getting_all_tx_2() {
     let file_name = "getting_tx_2.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
 
     add_tx(
         "2022-07-19",
@@ -143,7 +163,7 @@ fn check_getting_all_tx_2() {
     let expected_data = (
         vec![
             vec![
-                "19-07-2022".to_string(),
+                "2022-07-19".to_string(),
                 "Testing transaction".to_string(),
                 "test1".to_string(),
                 "100.00".to_string(),
@@ -151,7 +171,7 @@ fn check_getting_all_tx_2() {
                 "Unknown".to_string(),
             ],
             vec![
-                "19-07-2022".to_string(),
+                "2022-07-19".to_string(),
                 "Testing transaction".to_string(),
                 "test 2".to_string(),
                 "100.00".to_string(),
@@ -169,7 +189,7 @@ fn check_getting_all_tx_2() {
     let expected_data_2 = (
         vec![
             vec![
-                "15-05-2022".to_string(),
+                "2022-05-15".to_string(),
                 "Testing transaction".to_string(),
                 "test 2".to_string(),
                 "100.00".to_string(),
@@ -177,7 +197,7 @@ fn check_getting_all_tx_2() {
                 "Unknown".to_string(),
             ],
             vec![
-                "20-05-2022".to_string(),
+                "2022-05-20".to_string(),
                 "Testing transaction".to_string(),
                 "test 2".to_string(),
                 "100.00".to_string(),
@@ -185,7 +205,7 @@ fn check_getting_all_tx_2() {
                 "Unknown".to_string(),
             ],
             vec![
-                "25-05-2022".to_string(),
+                "2022-05-25".to_string(),
                 "Testing transfer".to_string(),
                 "test 2 to test1".to_string(),
                 "100.00".to_string(),
@@ -220,7 +240,7 @@ fn check_getting_all_tx_2() {
     let expected_data_3 = (
         vec![
             vec![
-                "15-05-2022".to_string(),
+                "2022-05-15".to_string(),
                 "Testing transaction".to_string(),
                 "test 2".to_string(),
                 "100.00".to_string(),
@@ -228,7 +248,7 @@ fn check_getting_all_tx_2() {
                 "Unknown".to_string(),
             ],
             vec![
-                "20-05-2022".to_string(),
+                "2022-05-20".to_string(),
                 "Testing transaction".to_string(),
                 "test 2".to_string(),
                 "100.00".to_string(),
@@ -236,7 +256,7 @@ fn check_getting_all_tx_2() {
                 "Unknown".to_string(),
             ],
             vec![
-                "25-05-2022".to_string(),
+                "2022-05-25".to_string(),
                 "Testing transfer".to_string(),
                 "test 2 to test1".to_string(),
                 "500.00".to_string(),
@@ -264,7 +284,7 @@ fn check_getting_all_tx_2() {
 
 fn check_tx_columns() {
     let file_name = "tx_columns.sqlite";
-    let conn = create_test_db(&file_name);
+    let conn = create_test_db(file_name);
 
     let columns = get_all_tx_columns(&conn);
     let expected_data = vec![
@@ -283,3 +303,977 @@ fn check_tx_columns() {
     assert_eq!(columns, expected_data);
     assert_eq!(columns.len(), 7);
 }
+
+#[test]
+fn check_tx_notes() {
+    let file_name = "tx_notes.sqlite";
+    let mut conn = create_test_db(file_name);
+    conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+    add_tx(
+        "2022-09-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let empty_note = get_tx_note(1, &conn);
+
+    set_tx_note(1, "First line\n\nSecond paragraph", &conn).unwrap();
+    let saved_note = get_tx_note(1, &conn);
+
+    set_tx_note(1, "Replaced", &conn).unwrap();
+    let replaced_note = get_tx_note(1, &conn);
+
+    set_tx_note(1, "  ", &conn).unwrap();
+    let removed_note = get_tx_note(1, &conn);
+
+    set_tx_note(1, "Deleted with the tx", &conn).unwrap();
+    delete_tx(1, &mut conn).unwrap();
+    let deleted_note = get_tx_note(1, &conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(empty_note, "");
+    assert_eq!(saved_note, "First line\n\nSecond paragraph");
+    assert_eq!(replaced_note, "Replaced");
+    assert_eq!(removed_note, "");
+    assert_eq!(deleted_note, "");
+}
+
+#[test]
+fn check_tx_write_rollback() {
+    let file_name = "tx_write_rollback.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let count_rows = |table: &str, conn: &Connection| -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    };
+
+    let mut tx_data = TxData::filled(
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "",
+        "100.00",
+        "Income",
+        "Unknown",
+    );
+    tx_data.add_tx(&mut conn).unwrap();
+
+    let activities_after_add = count_rows("activities", &conn);
+    let balance_after_add = get_last_balances(&conn);
+    let tx_after_add = get_tx_id_num(1, &conn);
+
+    // make every activity write fail so each write has to roll back
+    conn.execute(
+        "CREATE TRIGGER fail_activity BEFORE INSERT ON activities BEGIN SELECT RAISE(ABORT, 'failed'); END",
+        [],
+    )
+    .unwrap();
+
+    let mut new_tx = TxData::filled(
+        "2022-08-20",
+        "Another transaction",
+        "test1",
+        "",
+        "50.00",
+        "Expense",
+        "Food",
+    );
+    let new_status = new_tx.add_tx(&mut conn);
+
+    let mut edited_tx = TxData::custom(
+        "2022-08-19",
+        "Edited transaction",
+        "test 2",
+        "",
+        "500.00",
+        "Expense",
+        "Unknown",
+        1,
+    );
+    let edit_status = edited_tx.add_tx(&mut conn);
+
+    let trash_status = trash_tx(1, &mut conn);
+
+    let tx_count = count_rows("tx_all", &conn);
+    let changes_count = count_rows("changes_all", &conn);
+    let trash_count = count_rows("trashed_txs", &conn);
+    let activities = count_rows("activities", &conn);
+    let balance = get_last_balances(&conn);
+    let tx_after_fail = get_tx_id_num(1, &conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(activities_after_add, 1);
+    assert!(new_status.is_err());
+    assert!(edit_status.is_err());
+    assert!(trash_status.is_err());
+    assert_eq!(tx_count, 1);
+    assert_eq!(changes_count, 1);
+    assert_eq!(trash_count, 0);
+    assert_eq!(activities, 1);
+    assert_eq!(balance, balance_after_add);
+    assert_eq!(tx_after_fail, tx_after_add);
+}
+
+#[test]
+fn check_deleting_multiple_txs() {
+    let file_name = "delete_txs_1.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2022-08-19", "test1", "100.00", "Expense"),
+        ("2022-09-19", "test 2", "500.00", "Income"),
+        ("2022-09-20", "test1 to test 2", "50.00", "Transfer"),
+        ("2022-10-01", "test1", "20.00", "Expense"),
+    ];
+
+    for (date, method, amount, tx_type) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            "Unknown",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    // a missing tx fails the whole deletion
+    let failed_deletion = delete_txs(&[1, 99], &mut conn);
+    let txs_after_failure = get_last_tx_id(&conn).unwrap();
+    let balance_after_failure = get_last_balances(&conn);
+
+    delete_txs(&[1, 3], &mut conn).unwrap();
+
+    let remaining_txs = [get_all_txs(&conn, 7, 0).0, get_all_txs(&conn, 8, 0).0];
+    let last_balance = get_last_balances(&conn);
+    let october_balance = get_all_txs(&conn, 9, 0).1;
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(failed_deletion.is_err());
+    assert_eq!(txs_after_failure, 4);
+    assert_eq!(balance_after_failure, vec!["-170", "550"]);
+
+    assert!(remaining_txs[0].is_empty());
+    assert_eq!(remaining_txs[1].len(), 1);
+    assert_eq!(last_balance, vec!["-20", "500"]);
+    assert_eq!(october_balance, vec![vec!["-20.00", "500.00"]]);
+}
+
+#[test]
+fn check_trash_and_restore() {
+    let file_name = "trash_txs_1.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        ("2022-08-19", "test1", "100.00", "Expense"),
+        ("2022-09-19", "test 2", "500.00", "Income"),
+        ("2022-09-20", "test1 to test 2", "50.00", "Transfer"),
+    ];
+
+    for (date, method, amount, tx_type) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            "Unknown",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+    set_tx_note(1, "Kept in the trash", &conn).unwrap();
+
+    trash_txs(&[1, 3], &mut conn).unwrap();
+
+    let trashed = get_trashed_txs(&conn);
+    let trashed_balance = get_last_balances(&conn);
+
+    // ids are never given to new txs again so the restored txs get their old id back
+    let restored_id = restore_tx(trashed[1][7].parse().unwrap(), &mut conn).unwrap();
+    let restored_note = get_tx_note(1, &conn);
+    let restored_balance = get_last_balances(&conn);
+
+    add_tx(
+        "2022-09-21",
+        "Testing transaction",
+        "test 2",
+        "10.00",
+        "Expense",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    let transfer_id = restore_tx(trashed[0][7].parse().unwrap(), &mut conn).unwrap();
+    let final_balance = get_last_balances(&conn);
+
+    let missing_restore = restore_tx(99, &mut conn);
+
+    trash_tx(2, &mut conn).unwrap();
+    conn.execute("UPDATE trashed_txs SET tx_method = 'test3'", [])
+        .unwrap();
+    let missing_method = restore_tx(get_trashed_txs(&conn)[0][7].parse().unwrap(), &mut conn);
+
+    trash_txs(&[1, 4], &mut conn).unwrap();
+    let before_purge = get_trashed_txs(&conn).len();
+    delete_trashed_tx(get_trashed_txs(&conn)[0][7].parse().unwrap(), &conn).unwrap();
+    let after_purge = get_trashed_txs(&conn).len();
+    empty_trash(&conn).unwrap();
+    let after_empty = get_trashed_txs(&conn).len();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(trashed.len(), 2);
+    assert_eq!(
+        trashed[1][1..7],
+        [
+            "2022-08-19",
+            "Testing transaction",
+            "test1",
+            "100.00",
+            "Expense",
+            "Unknown"
+        ]
+    );
+    assert_eq!(trashed[0][3], "test1 to test 2");
+    assert_eq!(trashed_balance, vec!["0", "500"]);
+
+    assert_eq!(restored_id, 1);
+    assert_eq!(restored_note, "Kept in the trash");
+    assert_eq!(restored_balance, vec!["-100", "500"]);
+
+    assert_eq!(transfer_id, 3);
+    assert_eq!(final_balance, vec!["-150", "540"]);
+
+    assert!(matches!(missing_restore, Err(TrashError::NotFound)));
+    assert!(matches!(
+        missing_method,
+        Err(TrashError::MissingTxMethod(method)) if method == "test3"
+    ));
+
+    assert_eq!(before_purge, 3);
+    assert_eq!(after_purge, 2);
+    assert_eq!(after_empty, 0);
+}
+
+#[test]
+fn check_trash_keeps_tx_data() {
+    let file_name = "trash_txs_2.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    for amount in ["60.00", "40.00"] {
+        add_tx(
+            "2022-08-19",
+            "Testing transaction",
+            "test1",
+            amount,
+            "Expense",
+            "Unknown",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let foreign_amount = ForeignAmount {
+        amount: "25.00".to_string(),
+        currency: "EUR".to_string(),
+    };
+    let attachments = vec!["receipt.pdf".to_string(), "photo.png".to_string()];
+
+    set_tx_status(1, TxStatus::Reconciled, &conn).unwrap();
+    set_tx_foreign_amount(1, Some(&foreign_amount), &conn).unwrap();
+    set_tx_attachments(1, &attachments, &conn).unwrap();
+    link_split_parts(&[1, 2], &conn).unwrap();
+
+    trash_tx(1, &mut conn).unwrap();
+
+    let trashed_status = get_tx_status(1, &conn);
+    let trashed_attachments = get_tx_attachments(1, &conn);
+    let trashed_parts = get_split_parts(2, &conn);
+
+    let restored_id = restore_tx(get_trashed_txs(&conn)[0][7].parse().unwrap(), &mut conn).unwrap();
+
+    let restored_status = get_tx_status(1, &conn);
+    let restored_foreign_amount = get_tx_foreign_amount(1, &conn);
+    let restored_attachments = get_tx_attachments(1, &conn);
+    let restored_parts = get_split_parts(2, &conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(trashed_status == TxStatus::Pending);
+    assert!(trashed_attachments.is_empty());
+    assert_eq!(trashed_parts, vec![2]);
+
+    assert_eq!(restored_id, 1);
+    assert!(restored_status == TxStatus::Reconciled);
+    assert_eq!(restored_foreign_amount, Some(foreign_amount));
+    assert_eq!(restored_attachments, attachments);
+    assert_eq!(restored_parts, vec![1, 2]);
+}
+
+#[test]
+fn check_retagging_multiple_txs() {
+    let file_name = "retag_txs_1.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    for tags in ["Unknown", "Food", "Food, Car"] {
+        add_tx(
+            "2022-08-19",
+            "Testing transaction",
+            "test1",
+            "100.00",
+            "Expense",
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let config = Config::default();
+
+    add_tag_to_txs(&[1, 2, 3], "Car", &config, &mut conn).unwrap();
+    let added_tags = (1..=3)
+        .map(|id_num| get_tx_id_num(id_num, &conn)[5].clone())
+        .collect::<Vec<String>>();
+
+    remove_tag_from_txs(&[1, 3], " Car ", &config, &mut conn).unwrap();
+    let removed_tags = (1..=3)
+        .map(|id_num| get_tx_id_num(id_num, &conn)[5].clone())
+        .collect::<Vec<String>>();
+
+    // a missing tx fails the whole retagging
+    let failed_retag = add_tag_to_txs(&[2, 99], "Gift", &config, &mut conn);
+    let tags_after_failure = get_tx_id_num(2, &conn)[5].clone();
+
+    let empty_tag = add_tag_to_txs(&[1], " ", &config, &mut conn);
+    let comma_tag = remove_tag_from_txs(&[1], "Food, Car", &config, &mut conn);
+
+    // every tx is checked against the tag policies before any of them is changed
+    let mut config = Config {
+        allowed_tags: vec!["Food".to_string(), "Car".to_string(), "Gift".to_string()],
+        max_tags: 2,
+        ..Default::default()
+    };
+    let too_many_tags = add_tag_to_txs(&[1, 2], "Gift", &config, &mut conn);
+    let not_allowed = add_tag_to_txs(&[1], "Rent", &config, &mut conn);
+
+    config.require_tag = true;
+    let missing_tag = remove_tag_from_txs(&[3, 1], "Food", &config, &mut conn);
+    let policy_tags = (1..=3)
+        .map(|id_num| get_tx_id_num(id_num, &conn)[5].clone())
+        .collect::<Vec<String>>();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(added_tags, vec!["Car", "Food, Car", "Food, Car"]);
+    assert_eq!(removed_tags, vec!["Unknown", "Food, Car", "Food"]);
+    assert!(matches!(failed_retag, Err(RetagError::FailedRetag(_))));
+    assert_eq!(tags_after_failure, "Food, Car");
+
+    assert!(matches!(empty_tag, Err(RetagError::InvalidTag(_))));
+    assert!(matches!(comma_tag, Err(RetagError::InvalidTag(_))));
+    assert!(matches!(
+        too_many_tags,
+        Err(RetagError::NotAccepted(2, NAType::TooManyTags(2)))
+    ));
+    assert!(matches!(
+        not_allowed,
+        Err(RetagError::NotAccepted(1, NAType::TagNotAllowed(tag))) if tag == "Rent"
+    ));
+    assert!(matches!(
+        missing_tag,
+        Err(RetagError::NotAccepted(3, NAType::MissingTag))
+    ));
+    assert_eq!(policy_tags, removed_tags);
+}
+
+#[test]
+fn check_quick_add() {
+    let file_name = "quick_add_1.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let get_texts = |input: &str| {
+        parse_quick_add(input, 6, 1, &conn).map(|tx_data| {
+            tx_data
+                .get_all_texts()
+                .iter()
+                .take(7)
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+        })
+    };
+
+    let expense = get_texts("12-05 groceries 45.50 TEST1 #food #home").unwrap();
+    let income = get_texts("20 salary +200 test 2").unwrap();
+    let transfer = get_texts("move 50 test1>test 2").unwrap();
+
+    let no_amount = get_texts("groceries test1");
+    let no_method = get_texts("groceries 45");
+    let invalid_date = get_texts("32-05 groceries 45 test1");
+    let invalid_method = get_texts("coffee 4 cash");
+
+    let mut tx_data = parse_quick_add("12-05 groceries 45.50 test1 #food", 6, 1, &conn).unwrap();
+    tx_data.add_tx(&mut conn).unwrap();
+    let txs = get_all_txs(&conn, 4, 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        expense,
+        vec![
+            "2023-05-12",
+            "groceries",
+            "test1",
+            "",
+            "45.50",
+            "Expense",
+            "food, home"
+        ]
+    );
+    assert_eq!(
+        income,
+        vec!["2023-07-20", "salary", "test 2", "", "200.00", "Income", ""]
+    );
+    assert_eq!(
+        transfer[1..],
+        ["move", "test1", "test 2", "50.00", "Transfer", ""]
+    );
+
+    assert_eq!(
+        no_amount.err(),
+        Some(QuickAddError::MissingAmount("groceries test1".to_string()))
+    );
+    assert_eq!(
+        no_method.err(),
+        Some(QuickAddError::MissingMethod("groceries 45".to_string()))
+    );
+    assert_eq!(
+        invalid_date.err(),
+        Some(QuickAddError::InvalidDate("32-05".to_string()))
+    );
+    assert_eq!(
+        invalid_method.err(),
+        Some(QuickAddError::NotAccepted(NAType::InvalidTxMethod))
+    );
+    assert_eq!(
+        txs.0[0][1..],
+        ["groceries", "test1", "45.50", "Expense", "food"]
+    );
+}
+
+#[test]
+fn check_split_tx() {
+    let file_name = "split_tx.sqlite";
+    let mut conn = create_test_db(file_name);
+    conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+    let mut tx_data = TxData::filled(
+        "2023-07-19",
+        "Supermarket",
+        "test1",
+        "",
+        "50.00",
+        "Expense",
+        "Other",
+    );
+
+    assert_eq!(
+        tx_data.set_splits("food", &conn),
+        Err(SplitError::InvalidPart("food".to_string()))
+    );
+    assert_eq!(
+        tx_data.set_splits("food thirty", &conn),
+        Err(SplitError::NotAccepted(NAType::ParsingError(AType::Amount)))
+    );
+    assert_eq!(
+        tx_data.set_splits("food 0", &conn),
+        Err(SplitError::NotAccepted(NAType::AmountBelowZero))
+    );
+
+    tx_data
+        .set_splits("Food 30, Home Goods 5*2", &conn)
+        .unwrap();
+    assert_eq!(tx_data.get_split_text(), "Food 30.00, Home Goods 10.00");
+
+    // the rest of the amount goes to the tags field
+    assert_eq!(
+        tx_data
+            .get_split_txs()
+            .unwrap()
+            .into_iter()
+            .map(|part| (part.tags, part.tx_method, part.amount))
+            .collect::<Vec<(String, String, String)>>(),
+        vec![
+            ("Food".to_string(), "test1".to_string(), "30.00".to_string()),
+            (
+                "Home Goods".to_string(),
+                "test1".to_string(),
+                "10.00".to_string()
+            ),
+            (
+                "Other".to_string(),
+                "test1".to_string(),
+                "10.00".to_string()
+            ),
+        ]
+    );
+
+    assert_eq!(tx_data.add_tx(&mut conn), Ok(vec![1, 2, 3]));
+
+    let parts = get_split_parts(2, &conn);
+    assert_eq!(parts, vec![1, 2, 3]);
+    assert_eq!(get_split_parts(1, &conn), parts);
+    assert_eq!(get_tx_id_num(2, &conn)[3], "10.00");
+    assert_eq!(get_tx_id_num(2, &conn)[5], "Home Goods");
+    assert_eq!(get_last_balances(&conn), vec!["-50", "0"]);
+
+    add_tx(
+        "2023-07-19",
+        "Bus",
+        "test1",
+        "20.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    assert!(get_split_parts(4, &conn).is_empty());
+
+    // a split payment is counted as a single tx on the summary
+    let summary_modes = IndexedData::new_modes();
+    let summary_data = SummaryData::new(&conn);
+    let largest_txs = summary_data.get_largest_txs(&summary_modes, 6, 1);
+    let statistics = summary_data.get_statistics(&summary_modes, 6, 1);
+
+    assert_eq!(largest_txs.len(), 2);
+    assert_eq!(largest_txs[0][3], "50.00");
+    assert_eq!(statistics[2][1], "2");
+
+    // an edited part stays linked to the others but cannot be split again
+    let mut edited_tx = TxData::custom(
+        "2023-07-19",
+        "Supermarket",
+        "test1",
+        "",
+        "15.00",
+        "Expense",
+        "Home Goods",
+        2,
+    );
+    assert_eq!(
+        edited_tx.set_splits("Food 5", &conn),
+        Err(SplitError::Editing)
+    );
+    assert_eq!(edited_tx.add_tx(&mut conn), Ok(Vec::new()));
+    assert_eq!(get_split_parts(3, &conn), vec![1, 2, 3]);
+
+    // too large parts are rejected when adding
+    let mut tx_data = TxData::filled("2023-07-19", "", "test1", "", "10.00", "Expense", "");
+    tx_data.set_splits("Food 30", &conn).unwrap();
+    assert!(tx_data.add_tx(&mut conn).is_err());
+
+    delete_tx(1, &mut conn).unwrap();
+    assert_eq!(get_split_parts(2, &conn), vec![2, 3]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_split_tx_methods() {
+    let file_name = "split_tx_methods.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let mut tx_data = TxData::filled(
+        "2023-07-19",
+        "Dinner",
+        "test1",
+        "",
+        "55.00",
+        "Expense",
+        "Food",
+    );
+
+    assert_eq!(
+        tx_data.set_splits("Food @nothing 20", &conn),
+        Err(SplitError::NotAccepted(NAType::InvalidTxMethod))
+    );
+    assert_eq!(
+        tx_data.set_splits("@ 20", &conn),
+        Err(SplitError::InvalidPart("@ 20".to_string()))
+    );
+
+    tx_data.set_splits("@test 2 20", &conn).unwrap();
+    assert_eq!(tx_data.get_split_text(), "@test 2 20.00");
+    tx_data.add_tx(&mut conn).unwrap();
+
+    // 20 was paid with test 2 and the remaining 35 with test1
+    assert_eq!(get_tx_id_num(1, &conn)[2], "test 2");
+    assert_eq!(get_tx_id_num(2, &conn)[2], "test1");
+    assert_eq!(get_tx_id_num(2, &conn)[3], "35.00");
+    assert_eq!(get_last_balances(&conn), vec!["-35", "-20"]);
+
+    assert_eq!(
+        get_split_details(2, &conn).unwrap(),
+        "Split into 2 parts. Total: 55.00\ntest 2: 20.00 (Food)\ntest1: 35.00 (Food)"
+    );
+
+    let summary_modes = IndexedData::new_modes();
+    let largest_txs = SummaryData::new(&conn).get_largest_txs(&summary_modes, 6, 1);
+    assert_eq!(largest_txs.len(), 1);
+    assert_eq!(largest_txs[0][2], "test 2 & test1");
+    assert_eq!(largest_txs[0][3], "55.00");
+
+    // a transfer cannot take the tx methods from the parts
+    let mut tx_data = TxData::filled("2023-07-19", "", "test1", "test 2", "55.00", "Transfer", "");
+    tx_data.set_splits("@test1 20", &conn).unwrap();
+    assert_eq!(tx_data.get_split_txs(), Err(SplitError::TransferMethod));
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_tx_attachments() {
+    let file_name = "tx_attachments.sqlite";
+    let mut conn = create_test_db(file_name);
+    conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+    for details in ["TV", "Fridge"] {
+        add_tx(
+            "2023-07-19",
+            details,
+            "test1",
+            "100.00",
+            "Expense",
+            "Home",
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    assert!(matches!(
+        get_attachment_path("no_such_receipt.pdf"),
+        Err(AttachmentError::NotFound(_))
+    ));
+
+    let receipt = get_attachment_path("Cargo.toml").unwrap();
+    assert!(receipt.ends_with("Cargo.toml"));
+    assert!(PathBuf::from(&receipt).is_absolute());
+
+    add_tx_attachment(1, &receipt, &conn).unwrap();
+    add_tx_attachment(1, &receipt, &conn).unwrap();
+    add_tx_attachment(1, "warranty.pdf", &conn).unwrap();
+    assert_eq!(
+        get_tx_attachments(1, &conn),
+        vec![receipt.clone(), "warranty.pdf".to_string()]
+    );
+
+    // attachments follow the tx when it is edited or moved
+    let mut tx_data = TxData::custom(
+        "2023-07-19",
+        "TV",
+        "test1",
+        "",
+        "150.00",
+        "Expense",
+        "Home",
+        1,
+    );
+    tx_data.add_tx(&mut conn).unwrap();
+    assert_eq!(get_tx_attachments(1, &conn).len(), 2);
+
+    let tx_1 = get_tx_id_num(1, &conn);
+    let tx_2 = get_tx_id_num(2, &conn);
+    switch_tx_index(1, 2, &tx_1, &tx_2, &mut conn);
+    assert!(get_tx_attachments(1, &conn).is_empty());
+    assert_eq!(get_tx_attachments(2, &conn).len(), 2);
+
+    set_tx_attachments(2, &[], &conn).unwrap();
+    assert!(get_tx_attachments(2, &conn).is_empty());
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_transfer_fee() {
+    let file_name = "transfer_fee.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let mut tx_data = TxData::filled(
+        "2023-07-19",
+        "Savings",
+        "test1",
+        "test 2",
+        "100.00",
+        "Transfer",
+        "",
+    );
+
+    assert_eq!(
+        tx_data.set_fee("0"),
+        Err(FeeError::NotAccepted(NAType::AmountBelowZero))
+    );
+
+    tx_data.set_fee("1.5 * 2").unwrap();
+    assert_eq!(tx_data.get_fee(), "3.00");
+
+    // both the transfer and the fee are returned
+    assert_eq!(tx_data.add_tx(&mut conn), Ok(vec![1, 2]));
+
+    let fee_tx = get_tx_id_num(2, &conn);
+    assert_eq!(get_tx_id_num(1, &conn)[4], "Transfer");
+    assert_eq!(fee_tx[1], "Transfer fee: Savings");
+    assert_eq!(fee_tx[2], "test1");
+    assert_eq!(fee_tx[3], "3.00");
+    assert_eq!(fee_tx[4], "Expense");
+    assert_eq!(fee_tx[5], "fees");
+    assert_eq!(get_last_balances(&conn), vec!["-103", "100"]);
+
+    // a fee is only added with a transfer that is not split
+    let mut tx_data = TxData::filled("2023-07-19", "", "test1", "", "10.00", "Expense", "");
+    tx_data.set_fee("1").unwrap();
+    assert!(tx_data.add_tx(&mut conn).is_err());
+
+    let mut tx_data = TxData::filled("2023-07-19", "", "test1", "test 2", "10.00", "Transfer", "");
+    tx_data.set_fee("1").unwrap();
+    tx_data.set_splits("Food 5", &conn).unwrap();
+    assert!(tx_data.add_tx(&mut conn).is_err());
+
+    tx_data.set_fee("").unwrap();
+    assert_eq!(tx_data.get_fee(), "");
+    assert_eq!(get_last_tx_id(&conn), Ok(2));
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_foreign_amount() {
+    let file_name = "foreign_amount.sqlite";
+    let mut conn = create_test_db(file_name);
+    conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+    let mut tx_data = TxData::filled("2023-07-19", "Hotel", "test1", "", "25 eur", "Expense", "");
+
+    assert_eq!(
+        tx_data.convert_foreign_amount(&Config::default(), &conn),
+        Err(CurrencyError::NoBaseCurrency)
+    );
+
+    let mut config = Config {
+        base_currency: "USD".to_string(),
+        ..Config::default()
+    };
+    config.currency_rates.insert("EUR".to_string(), 1.1);
+
+    assert_eq!(
+        tx_data.convert_foreign_amount(&config, &conn),
+        Ok(Some(1.1))
+    );
+    assert_eq!(tx_data.get_all_texts()[4], "27.50");
+    assert_eq!(
+        tx_data.get_foreign_amount(),
+        Some(&ForeignAmount {
+            amount: "25.00".to_string(),
+            currency: "EUR".to_string()
+        })
+    );
+
+    // an amount without a currency is left alone
+    assert_eq!(tx_data.convert_foreign_amount(&config, &conn), Ok(None));
+
+    tx_data.add_tx(&mut conn).unwrap();
+    assert_eq!(get_tx_id_num(1, &conn)[3], "27.50");
+    assert_eq!(
+        get_tx_foreign_amount(1, &conn),
+        Some(ForeignAmount {
+            amount: "25.00".to_string(),
+            currency: "EUR".to_string()
+        })
+    );
+
+    // the base currency is only removed from the amount
+    let mut tx_data = TxData::filled("2023-07-19", "", "test1", "", "10 usd", "Expense", "");
+    assert_eq!(tx_data.convert_foreign_amount(&config, &conn), Ok(None));
+    assert_eq!(tx_data.get_all_texts()[4], "10.00");
+    assert!(tx_data.get_foreign_amount().is_none());
+
+    // fetched rates are cached
+    let mut tx_data = TxData::filled("2023-07-19", "", "test1", "", "2*5 GBP", "Expense", "");
+    assert_eq!(
+        tx_data.convert_foreign_amount(&config, &conn),
+        Err(CurrencyError::NoRate("GBP".to_string()))
+    );
+    cache_rate("GBP", 1.25, &conn).unwrap();
+    assert_eq!(
+        tx_data.convert_foreign_amount(&config, &conn),
+        Ok(Some(1.25))
+    );
+    assert_eq!(tx_data.get_all_texts()[4], "12.50");
+
+    // changing the converted amount removes the foreign amount
+    tx_data.edit_amount(Some('0'));
+    assert!(tx_data.get_foreign_amount().is_none());
+
+    delete_tx(1, &mut conn).unwrap();
+    assert!(get_tx_foreign_amount(1, &conn).is_none());
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_scheduled_tx() {
+    let file_name = "scheduled_tx.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let today = Local::now().date_naive();
+    let scheduled_date = today.checked_add_days(Days::new(5)).unwrap();
+    let date = scheduled_date.format("%Y-%m-%d").to_string();
+
+    // only a tx dated after today can be scheduled
+    let today_text = today.format("%Y-%m-%d").to_string();
+    let mut tx_data = TxData::filled(&today_text, "Rent", "test1", "", "500.00", "Expense", "");
+    assert!(tx_data.schedule_tx(&mut conn).is_err());
+
+    let mut tx_data = TxData::filled(&date, "Rent", "test1", "", "500.00", "Expense", "");
+    tx_data.schedule_tx(&mut conn).unwrap();
+
+    let scheduled_txs = get_scheduled_txs(&conn);
+    assert_eq!(scheduled_txs.len(), 1);
+    assert_eq!(
+        scheduled_txs[0],
+        vec![&date, "Rent", "test1", "500.00", "Expense", "Unknown", "1"]
+    );
+
+    // the balance is untouched until the date arrives
+    assert!(get_last_tx_id(&conn).is_err());
+    assert_eq!(get_last_balances(&conn), vec!["0", "0"]);
+    assert!(post_scheduled_txs(today, &mut conn).unwrap().is_empty());
+
+    assert_eq!(post_scheduled_txs(scheduled_date, &mut conn), Ok(vec![1]));
+    assert!(get_scheduled_txs(&conn).is_empty());
+    assert_eq!(get_tx_id_num(1, &conn)[1], "Rent");
+    assert_eq!(get_last_balances(&conn), vec!["-500", "0"]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_tag_rules() {
+    let file_name = "tag_rules.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    save_tag_rule("details uber, Transport", &conn).unwrap();
+    save_tag_rule("method Test 2, Savings", &conn).unwrap();
+    save_tag_rule("amount >=100, Big", &conn).unwrap();
+    save_tag_rule("details Uber Eats, Food", &conn).unwrap();
+
+    let missing_tag = save_tag_rule("details uber", &conn);
+    let invalid_field = save_tag_rule("date 2022, Old", &conn);
+    let invalid_amount = save_tag_rule("amount >abc, Big", &conn);
+
+    remove_tag_rule("4", &conn).unwrap();
+    let missing_rule = remove_tag_rule("7", &conn);
+    let rules_text = get_tag_rules_text(&conn);
+
+    add_tx(
+        "2022-08-19",
+        "Uber to work",
+        "test1",
+        "15",
+        "Expense",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-08-20",
+        "Savings",
+        "test1 to test 2",
+        "150",
+        "Transfer",
+        "big, Monthly",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-08-21",
+        "Coffee",
+        "test1",
+        "5",
+        "Expense",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    // edited txs keep their tags as they are
+    delete_tx(3, &mut conn).unwrap();
+    add_tx(
+        "2022-08-21",
+        "Uber Coffee",
+        "test1",
+        "5",
+        "Expense",
+        "Food",
+        Some("3"),
+        &mut conn,
+    )
+    .unwrap();
+
+    let tags = (1..=3)
+        .map(|id_num| get_tx_id_num(id_num, &conn)[5].clone())
+        .collect::<Vec<String>>();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(matches!(missing_tag, Err(TagRuleError::MissingTag)));
+    assert!(matches!(invalid_field, Err(TagRuleError::InvalidField(_))));
+    assert!(matches!(
+        invalid_amount,
+        Err(TagRuleError::InvalidAmount(_))
+    ));
+    assert!(matches!(missing_rule, Err(TagRuleError::NotFound(_))));
+    assert_eq!(
+        rules_text,
+        "1. details uber → Transport\n2. method Test 2 → Savings\n3. amount >=100 → Big"
+    );
+    assert_eq!(tags, vec!["Transport", "big, Monthly, Savings", "Food"]);
+}
@@ -3,9 +3,10 @@ use chrono::prelude::Local;
 use rex_tui::db::create_db;
 use rex_tui::outputs::{AType, CheckingError, NAType, TxType, VerifyingOutput};
 use rex_tui::page_handler::{DateType, TxTab};
-use rex_tui::tx_handler::{add_tx, TxData};
+use rex_tui::tx_handler::{add_tx, get_draft_path, load_draft, remove_draft, save_draft, TxData};
 use rusqlite::Connection;
 use std::fs;
+use std::path::Path;
 
 fn create_test_db(file_name: &str) -> Connection {
     if let Ok(metadata) = fs::metadata(file_name) {
@@ -15,7 +16,7 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
@@ -70,7 +71,7 @@ fn test_tx_data_1() {
     let expected_data = vec!["", "", "", "", "", "", "", ""];
     assert_eq!(tx_data.get_all_texts(), expected_data);
     assert_eq!(tx_data.get_tx_status(), &Vec::<String>::new());
-    assert_eq!(tx_data.check_all_empty(), true);
+    assert!(tx_data.check_all_empty());
 
     tx_data.add_tx_status("Some status".to_string());
 
@@ -104,7 +105,7 @@ fn test_tx_data_1() {
     assert_eq!(tx_data.get_tx_type(), TxType::IncomeExpense);
     assert_eq!(tx_data.get_tx_method(), "test1".to_string());
     assert!(tx_data.check_all_fields().is_none());
-    assert_eq!(tx_data.check_all_empty(), false);
+    assert!(!tx_data.check_all_empty());
 
     let current_index = tx_data.get_current_index();
 
@@ -225,10 +226,10 @@ fn test_tx_data_1() {
 #[test]
 fn test_tx_data_verifier() {
     let file_name = "tx_data_verifier.sqlite";
-    let conn = create_test_db(&file_name);
+    let conn = create_test_db(file_name);
 
     let mut tx_data = TxData::custom(
-        "15-06-2023",
+        "2023-06-15",
         "details",
         "test1",
         "Nope",
@@ -262,7 +263,7 @@ fn test_tx_data_verifier() {
     assert_eq!(amount_status, VerifyingOutput::Accepted(AType::Amount));
 
     let mut tx_data = TxData::custom(
-        "15-06-2023",
+        "2023-06-15",
         "details",
         "test1",
         "Nope",
@@ -283,10 +284,10 @@ fn test_tx_data_verifier() {
 #[test]
 fn test_tx_data_stepper() {
     let file_name = "tx_data_stepper.sqlite";
-    let conn = create_test_db(&file_name);
+    let conn = create_test_db(file_name);
 
     let mut tx_data = TxData::custom(
-        "15-06-2023",
+        "2023-06-15",
         "details",
         "test1",
         "Nope",
@@ -334,7 +335,7 @@ fn test_tx_data_stepper() {
 #[test]
 fn tx_data_searching() {
     let file_name = "tx_data_searching_test.sqlite";
-    let mut conn = create_test_db(&file_name);
+    let mut conn = create_test_db(file_name);
     let tx_data = TxData::new_empty();
 
     let data = tx_data.get_search_tx(&DateType::Exact, &conn);
@@ -342,21 +343,21 @@ fn tx_data_searching() {
 
     add_dummy_tx(&mut conn);
 
-    assert_eq!(tx_data.check_all_empty(), true);
+    assert!(tx_data.check_all_empty());
 
-    let tx_data = TxData::custom("19-07-2023", "", "", "", "", "", "", 0);
+    let tx_data = TxData::custom("2023-07-19", "", "", "", "", "", "", 0);
 
     let data = tx_data.get_search_tx(&DateType::Exact, &conn);
     assert_eq!(data.0.len(), 1);
 
-    let tx_data = TxData::custom("19-07-2023", "", "", "", "", "", "", 0);
+    let tx_data = TxData::custom("2023-07-19", "", "", "", "", "", "", 0);
 
     let data = tx_data.get_search_tx(&DateType::Monthly, &conn);
     assert_eq!(data.0.len(), 2);
 
     let mut tx_data = TxData::new_empty();
 
-    for i in vec!['2', '0', '2', '3'] {
+    for i in ['2', '0', '2', '3'] {
         tx_data.edit_date(Some(i))
     }
 
@@ -414,7 +415,7 @@ fn tx_data_searching() {
     assert_eq!(data.0.len(), 3);
 
     let tx_data = TxData::custom(
-        "19-07-2023",
+        "2023-07-19",
         "Testing transaction",
         "test 2",
         "",
@@ -435,43 +436,43 @@ fn tx_data_searching() {
 fn tx_data_editing() {
     let mut tx_data = TxData::new_empty();
 
-    for i in vec!['2', '0', '2', '3', '-', '0', '7', '-', '0', '5'] {
+    for i in ['2', '0', '2', '3', '-', '0', '7', '-', '0', '5'] {
         tx_data.edit_date(Some(i))
     }
 
     tx_data.go_current_index(&TxTab::Details);
 
-    for i in vec!['S', 'o', 'm', 'e'] {
+    for i in ['S', 'o', 'm', 'e'] {
         tx_data.edit_details(Some(i))
     }
 
     tx_data.go_current_index(&TxTab::FromMethod);
 
-    for i in vec!['t', 'e', 's', 't', '1'] {
+    for i in ['t', 'e', 's', 't', '1'] {
         tx_data.edit_from_method(Some(i))
     }
 
     tx_data.go_current_index(&TxTab::Amount);
 
-    for i in vec!['2', '0', '2', '3'] {
+    for i in ['2', '0', '2', '3'] {
         tx_data.edit_amount(Some(i))
     }
 
     tx_data.go_current_index(&TxTab::TxType);
 
-    for i in vec!['E'] {
+    for i in ['E'] {
         tx_data.edit_tx_type(Some(i))
     }
 
     tx_data.go_current_index(&TxTab::ToMethod);
 
-    for i in vec!['t', 'e', 's', 't', ' ', '2'] {
+    for i in ['t', 'e', 's', 't', ' ', '2'] {
         tx_data.edit_to_method(Some(i))
     }
 
     tx_data.go_current_index(&TxTab::Tags);
 
-    for i in vec!['T', 'a', 'g'] {
+    for i in ['T', 'a', 'g'] {
         tx_data.edit_tags(Some(i))
     }
 
@@ -493,43 +494,43 @@ fn tx_data_editing() {
 
     tx_data.go_current_index(&TxTab::Date);
 
-    for _ in vec!['2', '0', '2', '3', '-', '0', '7', '-', '0', '5'] {
+    for _ in ['2', '0', '2', '3', '-', '0', '7', '-', '0', '5'] {
         tx_data.edit_date(None)
     }
 
     tx_data.go_current_index(&TxTab::Details);
 
-    for _ in vec!['S', 'o', 'm', 'e'] {
+    for _ in ['S', 'o', 'm', 'e'] {
         tx_data.edit_details(None)
     }
 
     tx_data.go_current_index(&TxTab::FromMethod);
 
-    for _ in vec!['t', 'e', 's', 't', '1'] {
+    for _ in ['t', 'e', 's', 't', '1'] {
         tx_data.edit_from_method(None)
     }
 
     tx_data.go_current_index(&TxTab::Amount);
 
-    for _ in vec!['2', '0', '2', '3'] {
+    for _ in ['2', '0', '2', '3'] {
         tx_data.edit_amount(None)
     }
 
     tx_data.go_current_index(&TxTab::TxType);
 
-    for _ in vec!['E'] {
+    for _ in ['E'] {
         tx_data.edit_tx_type(None)
     }
 
     tx_data.go_current_index(&TxTab::ToMethod);
 
-    for _ in vec!['t', 'e', 's', 't', ' ', '2'] {
+    for _ in ['t', 'e', 's', 't', ' ', '2'] {
         tx_data.edit_to_method(None)
     }
 
     tx_data.go_current_index(&TxTab::Tags);
 
-    for _ in vec!['T', 'a', 'g'] {
+    for _ in ['T', 'a', 'g'] {
         tx_data.edit_tags(None)
     }
 
@@ -540,3 +541,269 @@ fn tx_data_editing() {
 
     assert_eq!(tx_data.get_all_texts(), expected_data);
 }
+
+#[test]
+fn tx_data_amount_expressions() {
+    let file_name = "tx_data_amount_expressions.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    // test1 ends with -300 and test 2 with 100 balance
+    let cases = [
+        (
+            "test 2",
+            "10%",
+            "10.00",
+            VerifyingOutput::Accepted(AType::Amount),
+        ),
+        (
+            "test 2",
+            "(12.99*3)+4.50",
+            "43.47",
+            VerifyingOutput::Accepted(AType::Amount),
+        ),
+        (
+            "test 2",
+            "b - 50",
+            "50.00",
+            VerifyingOutput::Accepted(AType::Amount),
+        ),
+        (
+            "test 2",
+            "2 * (b + 5%)",
+            "210.00",
+            VerifyingOutput::Accepted(AType::Amount),
+        ),
+        (
+            "test1",
+            "b+500",
+            "200.00",
+            VerifyingOutput::Accepted(AType::Amount),
+        ),
+        (
+            "test1",
+            "50%",
+            "150.00",
+            VerifyingOutput::NotAccepted(NAType::AmountBelowZero),
+        ),
+        (
+            "test 2",
+            "(10+5",
+            "(10+5",
+            VerifyingOutput::NotAccepted(NAType::ParsingError(AType::Amount)),
+        ),
+        (
+            "",
+            "5%",
+            "5%",
+            VerifyingOutput::NotAccepted(NAType::InvalidBValue),
+        ),
+    ];
+
+    for (method, amount, expected, expected_status) in cases {
+        let mut tx_data = TxData::custom("2023-07-25", "", method, "", amount, "Expense", "", 0);
+
+        assert!(tx_data.is_amount_expression());
+
+        let status = tx_data.check_amount(false, &conn);
+        assert_eq!(status, expected_status, "{amount}");
+        assert_eq!(tx_data.get_all_texts()[4], expected, "{amount}");
+    }
+
+    let tx_data = TxData::custom("2023-07-25", "", "test1", "", "100.50", "Expense", "", 0);
+    assert!(!tx_data.is_amount_expression());
+    assert!(!tx_data.is_balance_amount());
+
+    // the balance a percentage is taken from
+    let tx_data = TxData::custom("2023-07-25", "", "test1", "", "50%", "Expense", "", 0);
+    assert!(tx_data.is_balance_amount());
+    assert_eq!(
+        tx_data.get_from_method_balance(&conn),
+        Some("-300".to_string())
+    );
+
+    let tx_data = TxData::custom("2023-07-25", "", "", "", "50%", "Expense", "", 0);
+    assert_eq!(tx_data.get_from_method_balance(&conn), None);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn tx_data_repeat_last() {
+    let file_name = "tx_data_repeat_last.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    assert!(TxData::last_in_db(&conn).is_none());
+
+    add_dummy_tx(&mut conn);
+
+    let last_tx = TxData::last_in_db(&conn).unwrap();
+    assert_eq!(
+        last_tx.get_all_texts()[..7],
+        [
+            "2023-07-25",
+            "Testing transaction",
+            "test1",
+            "test 2",
+            "200.00",
+            "Transfer",
+            "Food"
+        ]
+    );
+
+    let mut tx_data = TxData::custom(
+        "2023-07-19",
+        "Coffee",
+        "test 2",
+        "",
+        "5.50",
+        "Expense",
+        "Food",
+        1,
+    );
+    tx_data.set_note("receipt".to_string());
+
+    // the repeated tx is a new one and does not carry the note
+    let mut repeated = tx_data.repeated();
+    assert_eq!(repeated.get_all_texts()[..7], tx_data.get_all_texts()[..7]);
+    assert_eq!(repeated.get_note(), "");
+
+    repeated.add_tx(&mut conn).unwrap();
+    assert_eq!(
+        TxData::last_in_db(&conn).unwrap().get_all_texts()[..7],
+        tx_data.get_all_texts()[..7]
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn tx_data_swap_methods() {
+    let mut tx_data = TxData::filled(
+        "2023-07-19",
+        "Savings",
+        "test1",
+        "test 2",
+        "100.00",
+        "Transfer",
+        "",
+    );
+
+    assert!(tx_data.swap_tx_methods());
+    assert_eq!(tx_data.get_tx_method(), "test 2 to test1");
+
+    assert!(tx_data.swap_tx_methods());
+    assert_eq!(tx_data.get_tx_method(), "test1 to test 2");
+
+    let mut tx_data = TxData::filled("2023-07-19", "", "test1", "", "10.00", "Expense", "");
+    assert!(!tx_data.swap_tx_methods());
+    assert_eq!(tx_data.get_tx_method(), "test1");
+}
+
+#[test]
+fn tx_data_batch_next() {
+    let mut tx_data = TxData::filled(
+        "2023-07-19",
+        "Receipt",
+        "test1",
+        "test 2",
+        "100.00",
+        "Transfer",
+        "Food",
+    );
+    tx_data.set_note("paper".to_string());
+
+    let next_tx = tx_data.batch_next();
+    assert_eq!(
+        next_tx.get_all_texts()[..7],
+        ["2023-07-19", "", "test1", "test 2", "", "Transfer", ""]
+    );
+    assert_eq!(next_tx.get_note(), "");
+    assert!(!next_tx.is_editing());
+}
+
+#[test]
+fn tx_data_field_undo() {
+    let mut tx_data = TxData::new_empty();
+
+    for c in "Coffee".chars() {
+        tx_data.edit_details(Some(c));
+    }
+    tx_data.edit_details(None);
+    assert_eq!(tx_data.get_all_texts()[1], "Coffe");
+
+    assert!(tx_data.undo_field(&TxTab::Details));
+    assert_eq!(tx_data.get_all_texts()[1], "Coffee");
+    assert_eq!(tx_data.get_current_index(), 6);
+
+    // nothing was typed on the amount field
+    assert!(!tx_data.undo_field(&TxTab::Amount));
+
+    tx_data.clear_field(&TxTab::Details);
+    assert_eq!(tx_data.get_all_texts()[1], "");
+    assert_eq!(tx_data.get_current_index(), 0);
+
+    assert!(tx_data.undo_field(&TxTab::Details));
+    assert_eq!(tx_data.get_all_texts()[1], "Coffee");
+
+    assert!(!tx_data.undo_field(&TxTab::Nothing));
+}
+
+#[test]
+fn tx_data_draft() {
+    // the defaults of a new form are not worth keeping
+    assert!(TxData::new_with_defaults("test1", "Expense", "Food")
+        .get_draft()
+        .is_none());
+
+    let mut tx_data = TxData::filled(
+        "2023-07-19",
+        "Receipt",
+        "test1",
+        "test 2",
+        "100.00",
+        "Transfer",
+        "Food",
+    );
+    tx_data.set_note("paper".to_string());
+
+    let draft = tx_data.get_draft().unwrap();
+    assert_eq!(draft.editing_id, None);
+    assert_eq!(
+        draft.get_summary(),
+        "Date: 2023-07-19\nDetails: Receipt\nTx Method: test1 to test 2\nAmount: 100.00\nTx Type: Transfer\nTags: Food\nNote: paper"
+    );
+
+    let restored = TxData::from_draft(draft.clone());
+    assert_eq!(restored.get_all_texts(), tx_data.get_all_texts());
+    assert_eq!(restored.get_note(), "paper");
+    assert!(!restored.is_editing());
+
+    let editing_draft = TxData::custom(
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "",
+        "100.00",
+        "Expense",
+        "Car",
+        5,
+    )
+    .get_draft()
+    .unwrap();
+    assert_eq!(editing_draft.editing_id, Some(5));
+    assert!(TxData::from_draft(editing_draft).is_editing());
+
+    let draft_path = get_draft_path(Path::new("tx_data_draft.sqlite"));
+    assert_eq!(draft_path, Path::new("tx_data_draft.draft.json"));
+
+    save_draft(&draft_path, &draft).unwrap();
+    let loaded = load_draft(&draft_path);
+    remove_draft(&draft_path);
+
+    assert_eq!(loaded, Some(draft));
+    assert!(!draft_path.exists());
+    assert_eq!(load_draft(&draft_path), None);
+}
@@ -1,6 +1,20 @@
 extern crate rex_tui;
+use rex_tui::chart_page::Rgb;
 use rex_tui::db::*;
-use rex_tui::utility::get_all_tx_methods;
+use rex_tui::home_page::get_label_method;
+use rex_tui::outputs::ExchangeRateError;
+use rex_tui::page_handler::MethodType;
+use rex_tui::tx_handler::{
+    add_exchange_transfer, add_tx, add_tx_method, adjust_starting_balance, change_credit_limit,
+    change_tx_method_color, change_tx_method_currency, change_tx_method_group,
+    change_tx_method_type, delete_tx, delete_tx_method, get_deletion_details, get_merge_details,
+    merge_tx_methods, rename_tx_method, switch_tx_method_archive, trash_tx, MergeDetails, TxData,
+};
+use rex_tui::utility::{
+    get_active_tx_methods, get_all_tx_methods, get_group_balances, get_last_balances,
+    get_spendable_balance, get_tx_id_num, hide_archived_columns, show_credit_as_owed,
+    show_credit_utilization, show_method_currencies, Config, TxMethodCache,
+};
 use rusqlite::Connection;
 use std::fs;
 
@@ -12,7 +26,7 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
@@ -34,7 +48,7 @@ fn check_getting_tx_methods_2() {
     let mut conn = create_test_db(file_name);
 
     add_new_tx_methods(
-        &vec!["new method 1".to_string(), "testing methods".to_string()],
+        &["new method 1".to_string(), "testing methods".to_string()],
         &mut conn,
     )
     .unwrap();
@@ -54,3 +68,746 @@ fn check_getting_tx_methods_2() {
         ]
     );
 }
+
+#[test]
+fn check_adding_tx_method() {
+    let file_name = "adding_tx_method.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let name = add_tx_method(" Savings , 500", &mut conn).unwrap();
+    add_tx_method("Wallet", &mut conn).unwrap();
+
+    let empty = add_tx_method(" ", &mut conn).is_err();
+    let duplicate = add_tx_method("savings", &mut conn).is_err();
+    let restricted = add_tx_method("Total", &mut conn).is_err();
+    let with_to = add_tx_method("Bank to Bank", &mut conn).is_err();
+    let with_quote = add_tx_method("My \"Bank\"", &mut conn).is_err();
+
+    let data = get_all_tx_methods(&conn);
+    let balances = get_last_balances(&conn);
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(name, "Savings".to_string());
+    assert!(empty && duplicate && restricted && with_to && with_quote);
+    assert_eq!(
+        data,
+        vec![
+            "test1".to_string(),
+            "test 2".to_string(),
+            "Savings".to_string(),
+            "Wallet".to_string()
+        ]
+    );
+    assert_eq!(
+        balances,
+        vec![
+            "0".to_string(),
+            "0".to_string(),
+            "500".to_string(),
+            "0".to_string()
+        ]
+    );
+}
+
+#[test]
+fn check_renaming_tx_method() {
+    let file_name = "renaming_tx_method.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let renamed = rename_tx_method("TEST1, Test1", &mut conn).unwrap();
+
+    let no_comma = rename_tx_method("Test1", &mut conn).is_err();
+    let not_found = rename_tx_method("test3, test4", &mut conn).is_err();
+    let duplicate = rename_tx_method("Test1, TEST 2", &mut conn).is_err();
+
+    let data = get_all_tx_methods(&conn);
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(renamed, ("test1".to_string(), "Test1".to_string()));
+    assert!(no_comma && not_found && duplicate);
+    assert_eq!(data, vec!["Test1".to_string(), "test 2".to_string()]);
+}
+
+#[test]
+fn check_archiving_tx_method() {
+    let file_name = "archiving_tx_method.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let archived = switch_tx_method_archive("TEST1", &conn).unwrap();
+    let last_active = switch_tx_method_archive("test 2", &conn).is_err();
+
+    rename_tx_method("test1, test3", &mut conn).unwrap();
+    let active_methods = get_active_tx_methods(&conn);
+    let archived_methods = get_archived_tx_methods(&conn);
+
+    let mut balance_rows = vec![vec![
+        String::new(),
+        "test3".to_string(),
+        "test 2".to_string(),
+        "Total".to_string(),
+    ]];
+    hide_archived_columns(&mut balance_rows, &conn);
+
+    let restored = switch_tx_method_archive("test3", &conn).unwrap();
+    let restored_methods = get_active_tx_methods(&conn);
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(archived, ("test1".to_string(), true));
+    assert!(last_active);
+    assert_eq!(active_methods, vec!["test 2".to_string()]);
+    assert_eq!(archived_methods, vec!["test3".to_string()]);
+    assert_eq!(
+        balance_rows,
+        vec![vec![
+            String::new(),
+            "test 2".to_string(),
+            "Total".to_string()
+        ]]
+    );
+    assert_eq!(restored, ("test3".to_string(), false));
+    assert_eq!(
+        restored_methods,
+        vec!["test3".to_string(), "test 2".to_string()]
+    );
+}
+
+#[test]
+fn check_tx_method_types() {
+    let file_name = "tx_method_types.sqlite";
+    let conn = create_test_db(file_name);
+
+    let changed = change_tx_method_type("TEST1, credit", &conn).unwrap();
+    change_tx_method_type("test 2, Savings", &conn).unwrap();
+
+    let missing_type = change_tx_method_type("test1", &conn).is_err();
+    let invalid_type = change_tx_method_type("test1, Loan", &conn).is_err();
+
+    let method_types = get_tx_method_types(&conn);
+
+    let mut balance_rows = vec![
+        vec![
+            String::new(),
+            "test1".to_string(),
+            "test 2".to_string(),
+            "Total".to_string(),
+        ],
+        vec![
+            "Balance".to_string(),
+            "-50.00".to_string(),
+            "100.00".to_string(),
+            "50.00".to_string(),
+        ],
+    ];
+    let spendable = get_spendable_balance(&balance_rows[1], &conn);
+    show_credit_as_owed(&mut balance_rows, &conn);
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(changed, ("test1".to_string(), MethodType::Credit));
+    assert!(missing_type && invalid_type);
+    assert_eq!(method_types.get("test1"), Some(&MethodType::Credit));
+    assert_eq!(method_types.get("test 2"), Some(&MethodType::Savings));
+    assert_eq!(spendable, Some(-50.0));
+    assert_eq!(
+        balance_rows,
+        vec![
+            vec![
+                String::new(),
+                "test1 (Owed)".to_string(),
+                "test 2".to_string(),
+                "Total".to_string(),
+            ],
+            vec![
+                "Balance".to_string(),
+                "50.00".to_string(),
+                "100.00".to_string(),
+                "50.00".to_string(),
+            ],
+        ]
+    );
+}
+
+#[test]
+fn check_tx_method_currencies() {
+    let file_name = "tx_method_currencies.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let changed = change_tx_method_currency("TEST 2, eur", &conn).unwrap();
+
+    let missing_currency = change_tx_method_currency("test 2", &conn).is_err();
+    let invalid_currency = change_tx_method_currency("test 2, euro", &conn).is_err();
+
+    let mut balance_rows = vec![vec![
+        String::new(),
+        "test1".to_string(),
+        "test 2".to_string(),
+        "Total".to_string(),
+    ]];
+    show_method_currencies(&mut balance_rows, &conn);
+
+    let mut tx_data = TxData::custom(
+        "2022-08-19",
+        "Exchange",
+        "test1",
+        "test 2",
+        "100",
+        "Transfer",
+        "",
+        0,
+    );
+    let missing_rate = tx_data.get_transfer_rate(&conn);
+    let invalid_rate = tx_data.set_exchange_rate("-2");
+    tx_data.set_exchange_rate("0.5").unwrap();
+    let rate = tx_data.get_transfer_rate(&conn);
+
+    add_exchange_transfer(
+        "2022-08-19",
+        "Exchange",
+        "test1 to test 2",
+        "100",
+        "",
+        0.5,
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    let balances = get_last_balances(&conn);
+    let saved_rate = get_tx_exchange_rate(1, &conn);
+
+    delete_tx(1, &mut conn).unwrap();
+    let balances_after_deletion = get_last_balances(&conn);
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(changed, ("test 2".to_string(), "EUR".to_string()));
+    assert!(missing_currency && invalid_currency);
+    assert_eq!(balance_rows[0][2], "test 2 (€)");
+    assert_eq!(get_label_method(&balance_rows[0][2]), "test 2");
+    assert_eq!(
+        missing_rate,
+        Err(ExchangeRateError::Missing(String::new(), "EUR".to_string()))
+    );
+    assert!(invalid_rate.is_err());
+    assert_eq!(rate, Ok(Some(0.5)));
+    assert_eq!(balances, vec!["-100", "50"]);
+    assert_eq!(saved_rate, Some(0.5));
+    assert_eq!(balances_after_deletion, vec!["0", "0"]);
+}
+
+#[test]
+fn check_adjusting_starting_balance() {
+    let file_name = "adjusting_starting_balance.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2022-08-19",
+        "Rent",
+        "test1",
+        "100",
+        "Expense",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let raised = adjust_starting_balance("Test1, 500", &mut conn).unwrap();
+    let balances_raised = get_last_balances(&conn);
+    let starting_balance = get_starting_balance("test1", &conn);
+
+    let lowered = adjust_starting_balance("test1, 300", &mut conn).unwrap();
+    let balances_lowered = get_last_balances(&conn);
+
+    let same_balance = adjust_starting_balance("test1, 300", &mut conn).is_err();
+    let missing_balance = adjust_starting_balance("test1", &mut conn).is_err();
+    let not_found = adjust_starting_balance("test3, 300", &mut conn).is_err();
+
+    let adjustment = get_tx_id_num(3, &conn);
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(raised, ("test1".to_string(), 500.0));
+    assert_eq!(balances_raised, vec!["400", "0"]);
+    assert_eq!(starting_balance, 500.0);
+    assert_eq!(lowered, ("test1".to_string(), -200.0));
+    assert_eq!(balances_lowered, vec!["200", "0"]);
+    assert!(same_balance && missing_balance && not_found);
+    assert_eq!(
+        adjustment,
+        vec![
+            "2022-08-19",
+            "Starting balance adjustment",
+            "test1",
+            "200.00",
+            "Expense",
+            "Starting Balance",
+            "3"
+        ]
+    );
+}
+
+#[test]
+fn check_merging_tx_methods() {
+    let file_name = "merging_tx_methods.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_tx_method("test3", &mut conn).unwrap();
+
+    add_tx(
+        "2022-08-19",
+        "Salary",
+        "test1",
+        "500",
+        "Income",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-08-20",
+        "Savings",
+        "test1 to test 2",
+        "200",
+        "Transfer",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-09-01",
+        "Rent",
+        "test 2",
+        "50",
+        "Expense",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-09-02",
+        "Cash",
+        "test 2 to test3",
+        "30",
+        "Transfer",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let same_method = get_merge_details("test1, TEST1", &conn).is_err();
+    let missing_target = get_merge_details("test1", &conn).is_err();
+
+    let details = get_merge_details("Test 2, test1", &conn).unwrap();
+    merge_tx_methods(&details.source, &details.target, &mut conn).unwrap();
+
+    let tx_methods = get_all_tx_methods(&conn);
+    let balances = get_last_balances(&conn);
+    let moved_transfer = get_tx_id_num(4, &conn);
+    let total_txs: i32 = conn
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap();
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert!(same_method && missing_target);
+    assert_eq!(
+        details,
+        MergeDetails {
+            source: "test 2".to_string(),
+            target: "test1".to_string(),
+            moved_txs: 2,
+            removed_transfers: 1,
+            moved_scheduled: 0,
+            source_balance: 120.0,
+            target_balance: 300.0,
+        }
+    );
+    assert_eq!(tx_methods, vec!["test1", "test3"]);
+    assert_eq!(balances, vec!["420", "30"]);
+    assert_eq!(moved_transfer[2], "test1 to test3");
+    assert_eq!(total_txs, 3);
+}
+
+#[test]
+fn check_deleting_tx_methods() {
+    let file_name = "deleting_tx_methods.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_tx_method("test3", &mut conn).unwrap();
+
+    add_tx(
+        "2022-08-19",
+        "Salary",
+        "test1",
+        "500",
+        "Income",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-08-20",
+        "Savings",
+        "test1 to test 2",
+        "200",
+        "Transfer",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-09-01",
+        "Rent",
+        "test 2",
+        "50",
+        "Expense",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let missing_method = get_deletion_details("test4", &conn).is_err();
+    let details = get_deletion_details("TEST 2", &conn).unwrap();
+    let empty_method = get_deletion_details("test3", &conn).unwrap();
+
+    delete_tx_method(&details.0, &mut conn).unwrap();
+
+    let tx_methods = get_all_tx_methods(&conn);
+    let balances = get_last_balances(&conn);
+    let total_txs: i32 = conn
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap();
+
+    delete_tx_method("test3", &mut conn).unwrap();
+    let last_method = get_deletion_details("test1", &conn).is_err();
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert!(missing_method && last_method);
+    assert_eq!(details, ("test 2".to_string(), 2));
+    assert_eq!(empty_method, ("test3".to_string(), 0));
+    assert_eq!(tx_methods, vec!["test1", "test3"]);
+    assert_eq!(balances, vec!["500", "0"]);
+    assert_eq!(total_txs, 1);
+}
+
+#[test]
+fn check_tx_method_tx_matching() {
+    let file_name = "tx_method_tx_matching.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_tx_method("test_", &mut conn).unwrap();
+    add_tx_method("test%", &mut conn).unwrap();
+
+    add_tx(
+        "2022-08-19",
+        "Savings",
+        "test1 to test 2",
+        "200",
+        "Transfer",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-09-01",
+        "Rent",
+        "test%",
+        "50",
+        "Expense",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    // _ and % are not wildcards and the names are matched exactly
+    let underscore_txs = get_tx_method_tx_ids("test_", &conn);
+    let percent_txs = get_tx_method_tx_ids("test%", &conn);
+    let transfer_txs = get_tx_method_tx_ids("test 2", &conn);
+    let wrong_case_txs = get_tx_method_tx_ids("TEST1", &conn);
+    let percent_date = get_first_tx_date("test%", &conn);
+    let underscore_date = get_first_tx_date("test_", &conn);
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert!(underscore_txs.is_empty());
+    assert_eq!(percent_txs, vec![2]);
+    assert_eq!(transfer_txs, vec![1]);
+    assert!(wrong_case_txs.is_empty());
+    assert_eq!(percent_date, Some("2022-09-01".to_string()));
+    assert_eq!(underscore_date, None);
+}
+
+#[test]
+fn check_merging_deleting_exact_names() {
+    let file_name = "merging_deleting_exact_names.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_tx_method("test_", &mut conn).unwrap();
+    add_tx_method("test%", &mut conn).unwrap();
+
+    add_tx(
+        "2022-08-19",
+        "Savings",
+        "test1 to test 2",
+        "200",
+        "Transfer",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-08-20",
+        "Savings",
+        "test1 to test 2",
+        "100",
+        "Transfer",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2022-09-01",
+        "Rent",
+        "test_",
+        "50",
+        "Expense",
+        "",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    trash_tx(2, &mut conn).unwrap();
+
+    let get_changes = |conn: &Connection| -> String {
+        conn.query_row(
+            r#"SELECT "test 2" FROM changes_all WHERE id_num = 1"#,
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+    let changes_before = get_changes(&conn);
+
+    // the transfer between test1 and test 2 is not part of test_ or test%
+    let details = get_merge_details("test_, test 2", &conn).unwrap();
+    merge_tx_methods(&details.source, &details.target, &mut conn).unwrap();
+    let changes_after = get_changes(&conn);
+
+    delete_tx_method("test%", &mut conn).unwrap();
+
+    let balances = get_last_balances(&conn);
+    let trashed_txs = get_trashed_txs(&conn).len();
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(details.moved_txs, 1);
+    assert_eq!(changes_after, changes_before);
+    assert_eq!(balances, vec!["-200", "150"]);
+    assert_eq!(trashed_txs, 1);
+}
+
+#[test]
+fn check_tx_method_colors() {
+    let file_name = "tx_method_colors.sqlite";
+    let conn = create_test_db(file_name);
+
+    let mut config = Config::default();
+    config
+        .tx_method_colors
+        .insert("test 2".to_string(), "#0a0b0c".to_string());
+
+    let default_colors = config.get_tx_method_colors(&conn);
+
+    let named = change_tx_method_color("TEST1, Green", &conn).unwrap();
+    let hex = change_tx_method_color("test 2, #FF5555", &conn).unwrap();
+    let picked_colors = config.get_tx_method_colors(&conn);
+
+    change_tx_method_color("test 2, ", &conn).unwrap();
+    let cleared_colors = config.get_tx_method_colors(&conn);
+
+    let missing_color = change_tx_method_color("test1", &conn).is_err();
+    let invalid_color = change_tx_method_color("test1, blurple", &conn).is_err();
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(default_colors["test1"], Rgb(255, 85, 85));
+    assert_eq!(default_colors["test 2"], Rgb(10, 11, 12));
+    assert_eq!(named, ("test1".to_string(), "#50fa7b".to_string()));
+    assert_eq!(hex, ("test 2".to_string(), "#ff5555".to_string()));
+    assert_eq!(picked_colors["test1"], Rgb(80, 250, 123));
+    assert_eq!(picked_colors["test 2"], Rgb(255, 85, 85));
+    assert_eq!(cleared_colors["test 2"], Rgb(10, 11, 12));
+    assert!(missing_color && invalid_color);
+}
+
+#[test]
+fn check_credit_utilization() {
+    let file_name = "credit_utilization.sqlite";
+    let conn = create_test_db(file_name);
+
+    let not_credit = change_credit_limit("test1, 1000", &conn).is_err();
+
+    change_tx_method_type("test1, Credit", &conn).unwrap();
+    let changed = change_credit_limit("TEST1, 1000", &conn).unwrap();
+    let missing_limit = change_credit_limit("test1", &conn).is_err();
+
+    let balance_rows = vec![
+        vec![
+            String::new(),
+            "test1".to_string(),
+            "test 2".to_string(),
+            "Total".to_string(),
+        ],
+        vec![
+            "Balance".to_string(),
+            "-250.00".to_string(),
+            "100.00".to_string(),
+            "-150.00".to_string(),
+        ],
+    ];
+
+    let mut below_warning = balance_rows.clone();
+    show_credit_utilization(&mut below_warning, 30.0, &conn);
+
+    let mut past_warning = balance_rows.clone();
+    show_credit_utilization(&mut past_warning, 20.0, &conn);
+
+    change_credit_limit("test1, 0", &conn).unwrap();
+    let mut without_limit = balance_rows.clone();
+    show_credit_utilization(&mut without_limit, 30.0, &conn);
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert!(not_credit && missing_limit);
+    assert_eq!(changed, ("test1".to_string(), 1000.0));
+    assert_eq!(below_warning[2], vec!["Utilization", "25.0%", "", ""]);
+    assert_eq!(past_warning[2], vec!["Utilization", "⚠ 25.0%", "", ""]);
+    assert_eq!(without_limit, balance_rows);
+}
+
+#[test]
+fn check_tx_method_groups() {
+    let file_name = "tx_method_groups.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_tx_method("test3", &mut conn).unwrap();
+
+    let no_groups = get_group_balances(&["Balance".to_string(), "10".to_string()], &conn);
+
+    let changed = change_tx_method_group("TEST1, Liquid", &conn).unwrap();
+    let same_group = change_tx_method_group("test3, liquid", &conn).unwrap();
+    change_tx_method_group("test 2, Invested", &conn).unwrap();
+    let missing_group = change_tx_method_group("test1", &conn).is_err();
+
+    let balance = vec![
+        "Balance".to_string(),
+        "100.00".to_string(),
+        "500.00".to_string(),
+        "-20.00".to_string(),
+        "580.00".to_string(),
+    ];
+    let group_balances = get_group_balances(&balance, &conn);
+
+    change_tx_method_group("test 2, ", &conn).unwrap();
+    let removed_group = get_group_balances(&balance, &conn);
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert!(no_groups.is_empty());
+    assert_eq!(changed, ("test1".to_string(), "Liquid".to_string()));
+    assert_eq!(same_group, ("test3".to_string(), "Liquid".to_string()));
+    assert!(missing_group);
+    assert_eq!(
+        group_balances,
+        vec![
+            ("Invested".to_string(), 500.0),
+            ("Liquid".to_string(), 80.0)
+        ]
+    );
+    assert_eq!(removed_group, vec![("Liquid".to_string(), 80.0)]);
+}
+
+#[test]
+fn check_tx_method_cache() {
+    let file_name = "tx_method_cache.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let mut cache = TxMethodCache::new(&conn);
+    let initial = cache.clone();
+
+    switch_tx_method_archive("test1", &conn).unwrap();
+    change_tx_method_group("test 2, Liquid", &conn).unwrap();
+    add_tx_method("test3", &mut conn).unwrap();
+
+    // nothing changes until the cache is reloaded
+    let stale = cache.clone();
+    cache.reload(&conn);
+
+    let balance = vec![
+        "Balance".to_string(),
+        "100.00".to_string(),
+        "500.00".to_string(),
+        "-20.00".to_string(),
+        "580.00".to_string(),
+    ];
+    let group_balances = cache.get_group_balances(&balance);
+    let spendable = cache.get_spendable_balance(&balance);
+
+    conn.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(initial.all, vec!["test1".to_string(), "test 2".to_string()]);
+    assert_eq!(initial.active, initial.all);
+    assert!(initial.groups.is_empty());
+    assert_eq!(stale, initial);
+    assert_eq!(
+        cache.all,
+        vec![
+            "test1".to_string(),
+            "test 2".to_string(),
+            "test3".to_string()
+        ]
+    );
+    assert_eq!(
+        cache.active,
+        vec!["test 2".to_string(), "test3".to_string()]
+    );
+    assert_eq!(cache.types.get("test3"), Some(&MethodType::Cash));
+    assert_eq!(group_balances, vec![("Liquid".to_string(), 500.0)]);
+    assert_eq!(spendable, None);
+}
@@ -7,7 +7,7 @@ use rex_tui::page_handler::*;
 fn test_table_data() {
     let table_data = vec![
         vec![
-            "15-05-2022".to_string(),
+            "2022-05-15".to_string(),
             "Testing transaction".to_string(),
             "test 2".to_string(),
             "100.00".to_string(),
@@ -15,7 +15,7 @@ fn test_table_data() {
             "Unknown".to_string(),
         ],
         vec![
-            "20-05-2022".to_string(),
+            "2022-05-20".to_string(),
             "Testing transaction".to_string(),
             "test 2".to_string(),
             "100.00".to_string(),
@@ -23,7 +23,7 @@ fn test_table_data() {
             "Unknown".to_string(),
         ],
         vec![
-            "25-05-2022".to_string(),
+            "2022-05-25".to_string(),
             "Testing transfer".to_string(),
             "test 2 to test1".to_string(),
             "100.00".to_string(),
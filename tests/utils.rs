@@ -1,6 +1,8 @@
 extern crate rex_tui;
 
+use chrono::{Local, NaiveDate};
 use rex_tui::db::create_db;
+use rex_tui::outputs::{DateRangeError, JumpDateError};
 use rex_tui::tx_handler::add_tx;
 use rex_tui::utility::*;
 use rusqlite::Connection;
@@ -15,7 +17,7 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
@@ -61,7 +63,7 @@ fn check_unique_tags() {
     .unwrap();
 
     let all_tags = get_all_tags(&conn);
-    let expected_data = vec!["Tag name", "Test tag", "test tag"]
+    let expected_data = ["Tag name", "Test tag", "test tag"]
         .iter()
         .map(|s| s.to_string())
         .collect::<Vec<String>>();
@@ -74,7 +76,7 @@ fn check_unique_tags() {
 
 #[test]
 fn check_restricted_test() {
-    let word_list = vec!["Unknown", "Words", "Testing"]
+    let word_list = ["Unknown", "Words", "Testing"]
         .iter()
         .map(|s| s.to_string())
         .collect::<Vec<String>>();
@@ -82,14 +84,14 @@ fn check_restricted_test() {
     let is_restricted = check_restricted("cancel", None);
     let not_restricted = check_restricted("some word", None);
 
-    assert_eq!(is_restricted, true);
-    assert_eq!(not_restricted, false);
+    assert!(is_restricted);
+    assert!(!not_restricted);
 
     let is_restricted = check_restricted("unknown", Some(&word_list));
     let not_restricted = check_restricted("some word", Some(&word_list));
 
-    assert_eq!(is_restricted, true);
-    assert_eq!(not_restricted, false);
+    assert!(is_restricted);
+    assert!(!not_restricted);
 }
 
 #[test]
@@ -130,3 +132,85 @@ fn test_location_json() {
     current_dir.push("location.json");
     fs::remove_file(current_dir).unwrap();
 }
+
+#[test]
+fn check_jump_date_parsing() {
+    let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+    // July 2023
+    assert_eq!(parse_jump_date("15", 6, 1), Ok(date(2023, 7, 15)));
+    assert_eq!(parse_jump_date(" 2023-08-01 ", 6, 1), Ok(date(2023, 8, 1)));
+    assert_eq!(parse_jump_date("01-08-2023", 6, 1), Ok(date(2023, 8, 1)));
+    assert_eq!(
+        parse_jump_date("TODAY", 6, 1),
+        Ok(Local::now().date_naive())
+    );
+
+    assert_eq!(
+        parse_jump_date("31", 1, 1),
+        Err(JumpDateError::InvalidDate("31".to_string()))
+    );
+    assert_eq!(
+        parse_jump_date("yesterday", 6, 1),
+        Err(JumpDateError::InvalidDate("yesterday".to_string()))
+    );
+    assert_eq!(
+        parse_jump_date("2021-05-05", 6, 1),
+        Err(JumpDateError::OutOfRange(date(2021, 5, 5)))
+    );
+}
+
+#[test]
+fn check_month_weeks() {
+    let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+    // July 2023 starts on a Saturday and ends on a Monday
+    assert_eq!(
+        get_month_weeks(6, 1),
+        vec![
+            date(2023, 6, 26),
+            date(2023, 7, 3),
+            date(2023, 7, 10),
+            date(2023, 7, 17),
+            date(2023, 7, 24),
+            date(2023, 7, 31),
+        ]
+    );
+
+    // the first week of January 2022 starts on the previous year
+    assert_eq!(get_month_weeks(0, 0)[0], date(2021, 12, 27));
+    assert_eq!(get_month_weeks(0, 0).len(), 6);
+}
+
+#[test]
+fn check_date_range_parsing() {
+    let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+    assert_eq!(
+        parse_date_range("2023-01-28 to 09-02-2023", 6, 1),
+        Ok((date(2023, 1, 28), date(2023, 2, 9)))
+    );
+    assert_eq!(
+        parse_date_range(" 5 20 ", 6, 1),
+        Ok((date(2023, 7, 5), date(2023, 7, 20)))
+    );
+    assert_eq!(
+        parse_date_range("2023-02-09 2023-01-28", 6, 1),
+        Err(DateRangeError::Reversed(
+            date(2023, 2, 9),
+            date(2023, 1, 28)
+        ))
+    );
+    assert_eq!(
+        parse_date_range("2023-01-28", 6, 1),
+        Err(DateRangeError::InvalidRange("2023-01-28".to_string()))
+    );
+    assert_eq!(
+        parse_date_range("1 to 40", 6, 1),
+        Err(DateRangeError::InvalidDate("40".to_string()))
+    );
+    assert_eq!(
+        parse_date_range("2020-01-01 to 2023-01-01", 6, 1),
+        Err(DateRangeError::OutOfRange(date(2020, 1, 1)))
+    );
+}
@@ -1,4 +1,5 @@
 extern crate rex_tui;
+use chrono::{Local, NaiveDate};
 use rex_tui::db::create_db;
 use rex_tui::outputs::{AType, NAType, VerifyingOutput};
 use rex_tui::page_handler::DateType;
@@ -22,7 +23,7 @@ fn create_test_db(file_name: &str) -> Connection {
     }
 
     let mut conn = Connection::open(file_name).unwrap();
-    create_db(&vec!["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
     conn
 }
 
@@ -33,6 +34,55 @@ fn check_sql_dates() {
     assert_eq!(data, expected_data);
 }
 
+#[test]
+fn check_short_dates() {
+    let today = NaiveDate::from_ymd_opt(2024, 3, 2).unwrap();
+
+    assert_eq!(
+        expand_short_date("t", today),
+        Some("2024-03-02".to_string())
+    );
+    assert_eq!(
+        expand_short_date(" Today ", today),
+        Some("2024-03-02".to_string())
+    );
+    assert_eq!(
+        expand_short_date("y", today),
+        Some("2024-03-01".to_string())
+    );
+    assert_eq!(
+        expand_short_date("-3", today),
+        Some("2024-02-28".to_string())
+    );
+    assert_eq!(
+        expand_short_date("17", today),
+        Some("2024-03-17".to_string())
+    );
+    assert_eq!(
+        expand_short_date("5", today),
+        Some("2024-03-05".to_string())
+    );
+    assert_eq!(expand_short_date("-x", today), None);
+    assert_eq!(expand_short_date("2024-03-02", today), None);
+    assert_eq!(expand_short_date("170", today), None);
+
+    let testing = Testing {
+        data: Vec::new(),
+        expected: Vec::new(),
+        result: Vec::new(),
+    };
+
+    let mut user_date = "t".to_string();
+    let result = testing.verify_date(&mut user_date, &DateType::Exact);
+    assert_eq!(result, VerifyingOutput::Accepted(AType::Date));
+    assert_eq!(user_date, Local::now().format("%Y-%m-%d").to_string());
+
+    // the expanded day still has to exist
+    let mut user_date = "32".to_string();
+    let result = testing.verify_date(&mut user_date, &DateType::Exact);
+    assert_eq!(result, VerifyingOutput::NotAccepted(NAType::DayTooBig));
+}
+
 #[test]
 fn check_verifier_date() {
     let test_data = Testing {
@@ -118,6 +168,10 @@ fn check_verifier_amount() {
             "@%15612".to_string(),
             " 5 + 2 * 3 - 5".to_string(),
             "1.0000".to_string(),
+            "(12.99*3)+4.50".to_string(),
+            "2 * (5 + (1 - 3) * 2)".to_string(),
+            "(10 + 5".to_string(),
+            "10 / 0".to_string(),
         ],
         expected: vec![
             "".to_string(),
@@ -137,6 +191,10 @@ fn check_verifier_amount() {
             "15612.00".to_string(),
             "6.00".to_string(),
             "1.00".to_string(),
+            "43.47".to_string(),
+            "2.00".to_string(),
+            "(10+5".to_string(),
+            "10/0".to_string(),
         ],
 
         result: vec![
@@ -157,6 +215,10 @@ fn check_verifier_amount() {
             VerifyingOutput::Accepted(AType::Amount),
             VerifyingOutput::Accepted(AType::Amount),
             VerifyingOutput::Accepted(AType::Amount),
+            VerifyingOutput::Accepted(AType::Amount),
+            VerifyingOutput::Accepted(AType::Amount),
+            VerifyingOutput::NotAccepted(NAType::ParsingError(AType::Amount)),
+            VerifyingOutput::NotAccepted(NAType::ParsingError(AType::Amount)),
         ],
     };
     for i in 0..test_data.data.len() {
@@ -270,3 +332,42 @@ fn check_verifier_tags() {
         assert_eq!(to_verify, test_data.expected[i]);
     }
 }
+
+#[test]
+fn check_verifier_tag_constraints() {
+    let test_data = Testing {
+        data: Vec::new(),
+        expected: Vec::new(),
+        result: Vec::new(),
+    };
+
+    let config = Config {
+        require_tag: true,
+        allowed_tags: vec!["Food".to_string(), "Car".to_string(), "Rent".to_string()],
+        max_tags: 2,
+        ..Config::default()
+    };
+
+    let cases = [
+        ("", VerifyingOutput::NotAccepted(NAType::MissingTag)),
+        ("Unknown", VerifyingOutput::NotAccepted(NAType::MissingTag)),
+        ("Food", VerifyingOutput::Accepted(AType::Tags)),
+        (
+            "Food, Fun",
+            VerifyingOutput::NotAccepted(NAType::TagNotAllowed("Fun".to_string())),
+        ),
+        (
+            "Food, Car, Rent",
+            VerifyingOutput::NotAccepted(NAType::TooManyTags(2)),
+        ),
+    ];
+
+    for (tags, expected) in cases {
+        assert_eq!(test_data.verify_tag_constraints(tags, &config), expected);
+    }
+
+    assert_eq!(
+        test_data.verify_tag_constraints("", &Config::default()),
+        VerifyingOutput::Nothing(AType::Tags)
+    );
+}